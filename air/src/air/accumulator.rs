@@ -0,0 +1,406 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::{boxed::Box, vec::Vec};
+use core::ops::{Add, Mul, Sub};
+
+use math::{batch_inversion, ExtensionOf, FieldElement, StarkField};
+
+use super::{Assertion, EvaluationFrame, TransitionConstraintDegree};
+
+// ACCUMULATOR TERM
+// ================================================================================================
+
+/// An expression describing a term added to (or multiplied into) an [Accumulator] at every step
+/// of an execution trace.
+///
+/// Terms are built from values of the current row of the main trace segment, constants, and
+/// random elements (e.g., elements drawn by the verifier for auxiliary trace segments), and can
+/// be combined using `+`, `-`, and `*` operators. For example, a term `α - c_0` can be described
+/// as `AccumulatorTerm::rand(0) - AccumulatorTerm::column(0)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AccumulatorTerm<B: StarkField> {
+    /// Value in the specified column of the current row of the main trace segment.
+    Column(usize),
+    /// A constant value.
+    Constant(B),
+    /// Random element at the specified index.
+    RandElement(usize),
+    /// Sum of two terms.
+    Add(Box<AccumulatorTerm<B>>, Box<AccumulatorTerm<B>>),
+    /// Difference of two terms.
+    Sub(Box<AccumulatorTerm<B>>, Box<AccumulatorTerm<B>>),
+    /// Product of two terms.
+    Mul(Box<AccumulatorTerm<B>>, Box<AccumulatorTerm<B>>),
+}
+
+impl<B: StarkField> AccumulatorTerm<B> {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a term describing the value in the specified column of the main trace segment.
+    pub fn column(index: usize) -> Self {
+        Self::Column(index)
+    }
+
+    /// Returns a term describing the specified constant.
+    pub fn constant(value: B) -> Self {
+        Self::Constant(value)
+    }
+
+    /// Returns a term describing the random element at the specified index.
+    pub fn rand(index: usize) -> Self {
+        Self::RandElement(index)
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the degree of this term with respect to trace columns.
+    pub fn degree(&self) -> usize {
+        match self {
+            Self::Column(_) => 1,
+            Self::Constant(_) | Self::RandElement(_) => 0,
+            Self::Add(lhs, rhs) | Self::Sub(lhs, rhs) => lhs.degree().max(rhs.degree()),
+            Self::Mul(lhs, rhs) => lhs.degree() + rhs.degree(),
+        }
+    }
+
+    /// Evaluates this term against the specified row of the main trace segment and the specified
+    /// random elements.
+    ///
+    /// # Panics
+    /// Panics if this term refers to a column or a random element which does not exist.
+    pub fn evaluate<F, E>(&self, row: &[F], rand_elements: &[E]) -> E
+    where
+        F: FieldElement<BaseField = B>,
+        E: FieldElement<BaseField = B> + ExtensionOf<F>,
+    {
+        match self {
+            Self::Column(index) => E::from(row[*index]),
+            Self::Constant(value) => E::from(*value),
+            Self::RandElement(index) => rand_elements[*index],
+            Self::Add(lhs, rhs) => {
+                lhs.evaluate(row, rand_elements) + rhs.evaluate(row, rand_elements)
+            },
+            Self::Sub(lhs, rhs) => {
+                lhs.evaluate(row, rand_elements) - rhs.evaluate(row, rand_elements)
+            },
+            Self::Mul(lhs, rhs) => {
+                lhs.evaluate(row, rand_elements) * rhs.evaluate(row, rand_elements)
+            },
+        }
+    }
+}
+
+impl<B: StarkField> Add for AccumulatorTerm<B> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::Add(Box::new(self), Box::new(rhs))
+    }
+}
+
+impl<B: StarkField> Sub for AccumulatorTerm<B> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::Sub(Box::new(self), Box::new(rhs))
+    }
+}
+
+impl<B: StarkField> Mul for AccumulatorTerm<B> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Self::Mul(Box::new(self), Box::new(rhs))
+    }
+}
+
+// ACCUMULATOR
+// ================================================================================================
+
+/// Defines how an [Accumulator] combines its terms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccumulatorKind {
+    /// Running sum; the initial value of the accumulator is 0.
+    Sum,
+    /// Running product; the initial value of the accumulator is 1.
+    Product,
+}
+
+/// A column of an execution trace holding a running sum or a running product of terms computed
+/// from the rows of the main trace segment.
+///
+/// An accumulator is described by a numerator term n and an optional denominator term d (both
+/// described via [AccumulatorTerm]s). For a running sum, the value in the accumulator column at
+/// step i + 1 is a_{i + 1} = a_i + n_i / d_i, and for a running product, it is
+/// a_{i + 1} = a_i * n_i / d_i, where n_i and d_i are the values of the terms at step i. The
+/// accumulator starts at 0 for sums and at 1 for products.
+///
+/// The same accumulator description is used to build the accumulator column during trace
+/// generation (see [Accumulator::build_column()]) and to evaluate the transition constraint
+/// (see [Accumulator::evaluate_transition()]) and the boundary constraint (see
+/// [Accumulator::initial_assertion()]) which enforce correctness of the column. The transition
+/// constraint is defined as:
+/// * (a_{i + 1} - a_i) * d_i - n_i = 0 for running sums.
+/// * a_{i + 1} * d_i - a_i * n_i = 0 for running products.
+///
+/// The transition constraint does not apply to the last step of the trace, and thus, it should
+/// use the default number of transition exemptions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Accumulator<B: StarkField> {
+    kind: AccumulatorKind,
+    column: usize,
+    numerator: AccumulatorTerm<B>,
+    denominator: Option<AccumulatorTerm<B>>,
+}
+
+impl<B: StarkField> Accumulator<B> {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a running sum of the specified term located in the specified column.
+    pub fn sum(column: usize, term: AccumulatorTerm<B>) -> Self {
+        Self {
+            kind: AccumulatorKind::Sum,
+            column,
+            numerator: term,
+            denominator: None,
+        }
+    }
+
+    /// Returns a running product of the specified term located in the specified column.
+    pub fn product(column: usize, term: AccumulatorTerm<B>) -> Self {
+        Self {
+            kind: AccumulatorKind::Product,
+            column,
+            numerator: term,
+            denominator: None,
+        }
+    }
+
+    /// Updates this accumulator to divide its terms by the specified denominator.
+    ///
+    /// Denominators must not evaluate to zero at any step of the execution trace.
+    pub fn with_denominator(mut self, denominator: AccumulatorTerm<B>) -> Self {
+        self.denominator = Some(denominator);
+        self
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the kind of this accumulator.
+    pub fn kind(&self) -> AccumulatorKind {
+        self.kind
+    }
+
+    /// Returns the index of the column containing this accumulator.
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// Returns the initial value of this accumulator.
+    pub fn initial_value<E: FieldElement>(&self) -> E {
+        match self.kind {
+            AccumulatorKind::Sum => E::ZERO,
+            AccumulatorKind::Product => E::ONE,
+        }
+    }
+
+    /// Returns the degree of the transition constraint enforcing this accumulator.
+    pub fn transition_degree(&self) -> TransitionConstraintDegree {
+        let num_degree = self.numerator.degree();
+        let den_degree = self.denominator.as_ref().map_or(0, |den| den.degree());
+        let degree = match self.kind {
+            AccumulatorKind::Sum => (den_degree + 1).max(num_degree),
+            AccumulatorKind::Product => den_degree.max(num_degree) + 1,
+        };
+        TransitionConstraintDegree::new(degree)
+    }
+
+    /// Returns an assertion against the first step of the accumulator column.
+    pub fn initial_assertion<E: FieldElement>(&self) -> Assertion<E> {
+        Assertion::single(self.column, 0, self.initial_value())
+    }
+
+    // TRACE BUILDING
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns values of the accumulator column for an execution trace with the specified number
+    /// of rows.
+    ///
+    /// Rows of the main trace segment are read via the `read_row` function, which must write the
+    /// row at the specified step into the provided slice of length `row_width`.
+    ///
+    /// # Panics
+    /// Panics if a denominator of this accumulator evaluates to zero at any step (except the
+    /// last one).
+    pub fn build_column<F, E>(
+        &self,
+        num_rows: usize,
+        row_width: usize,
+        rand_elements: &[E],
+        mut read_row: impl FnMut(usize, &mut [F]),
+    ) -> Vec<E>
+    where
+        F: FieldElement<BaseField = B>,
+        E: FieldElement<BaseField = B> + ExtensionOf<F>,
+    {
+        // evaluate the terms at all steps except the last one; the term at the last step does
+        // not contribute to the accumulator
+        let mut row = vec![F::ZERO; row_width];
+        let mut numerators = Vec::with_capacity(num_rows);
+        let mut denominators = Vec::with_capacity(num_rows);
+        for step in 0..num_rows.saturating_sub(1) {
+            read_row(step, &mut row);
+            numerators.push(self.numerator.evaluate(&row, rand_elements));
+            if let Some(denominator) = &self.denominator {
+                denominators.push(denominator.evaluate(&row, rand_elements));
+            }
+        }
+
+        if !denominators.is_empty() {
+            assert!(
+                denominators.iter().all(|&den| den != E::ZERO),
+                "accumulator denominator must not evaluate to zero"
+            );
+            let inv_denominators = batch_inversion(&denominators);
+            for (num, inv_den) in numerators.iter_mut().zip(inv_denominators) {
+                *num *= inv_den;
+            }
+        }
+
+        let mut result = Vec::with_capacity(num_rows);
+        let mut acc = self.initial_value::<E>();
+        result.push(acc);
+        for term in numerators {
+            match self.kind {
+                AccumulatorKind::Sum => acc += term,
+                AccumulatorKind::Product => acc *= term,
+            }
+            result.push(acc);
+        }
+        result.truncate(num_rows);
+
+        result
+    }
+
+    // CONSTRAINT EVALUATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Evaluates the transition constraint of this accumulator.
+    ///
+    /// `current` and `next` are the values of the accumulator column in the current and the next
+    /// rows of the evaluation frame, and the terms are evaluated against the current row of
+    /// `main_frame`.
+    pub fn evaluate_transition<F, E>(
+        &self,
+        main_frame: &EvaluationFrame<F>,
+        current: E,
+        next: E,
+        rand_elements: &[E],
+    ) -> E
+    where
+        F: FieldElement<BaseField = B>,
+        E: FieldElement<BaseField = B> + ExtensionOf<F>,
+    {
+        let row = main_frame.current();
+        let numerator = self.numerator.evaluate(row, rand_elements);
+        let denominator = match &self.denominator {
+            Some(denominator) => denominator.evaluate(row, rand_elements),
+            None => E::ONE,
+        };
+
+        match self.kind {
+            AccumulatorKind::Sum => (next - current) * denominator - numerator,
+            AccumulatorKind::Product => next * denominator - current * numerator,
+        }
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use math::{
+        fields::{f64::BaseElement, QuadExtension},
+        FieldElement,
+    };
+
+    use super::{Accumulator, AccumulatorTerm, EvaluationFrame};
+
+    type E2 = QuadExtension<BaseElement>;
+
+    fn build_trace() -> Vec<Vec<BaseElement>> {
+        (0..16_u32)
+            .map(|i| vec![BaseElement::from(i), BaseElement::from(i * i + 1)])
+            .collect()
+    }
+
+    fn check_constraints<E>(acc: &Accumulator<BaseElement>, trace: &[Vec<BaseElement>], rand: &[E])
+    where
+        E: FieldElement<BaseField = BaseElement>,
+    {
+        let column = acc.build_column(trace.len(), 2, rand, |step, row: &mut [BaseElement]| {
+            row.copy_from_slice(&trace[step])
+        });
+        assert_eq!(trace.len(), column.len());
+        assert_eq!(acc.initial_value::<E>(), column[0]);
+
+        for step in 0..trace.len() - 1 {
+            let frame = EvaluationFrame::from_rows(trace[step].clone(), trace[step + 1].clone());
+            let evaluation = acc.evaluate_transition(&frame, column[step], column[step + 1], rand);
+            assert_eq!(E::ZERO, evaluation);
+
+            // a different value in the next row violates the constraint
+            let next = column[step + 1] + E::ONE;
+            let evaluation = acc.evaluate_transition(&frame, column[step], next, rand);
+            assert_ne!(E::ZERO, evaluation);
+        }
+    }
+
+    #[test]
+    fn running_sum() {
+        let trace = build_trace();
+
+        // a_{i + 1} = a_i + c_0 * c_1
+        let term = AccumulatorTerm::column(0) * AccumulatorTerm::column(1);
+        let acc = Accumulator::sum(0, term);
+        assert_eq!(2, acc.transition_degree().min_blowup_factor());
+        check_constraints::<BaseElement>(&acc, &trace, &[]);
+
+        // a_{i + 1} = a_i + 1 / (α - c_1)
+        let den = AccumulatorTerm::rand(0) - AccumulatorTerm::column(1);
+        let acc =
+            Accumulator::sum(0, AccumulatorTerm::constant(BaseElement::ONE)).with_denominator(den);
+        check_constraints(&acc, &trace, &[E2::new(BaseElement::from(7_u32), BaseElement::ONE)]);
+    }
+
+    #[test]
+    fn running_product() {
+        let trace = build_trace();
+        let alpha = E2::new(BaseElement::from(7_u32), BaseElement::ONE);
+
+        // a_{i + 1} = a_i * (α - c_0) / (α - c_1)
+        let num = AccumulatorTerm::rand(0) - AccumulatorTerm::column(0);
+        let den = AccumulatorTerm::rand(0) - AccumulatorTerm::column(1);
+        let acc = Accumulator::product(0, num).with_denominator(den);
+        check_constraints(&acc, &trace, &[alpha]);
+
+        // the last value of the accumulator is the product of terms at all other steps
+        let column = acc.build_column(trace.len(), 2, &[alpha], |step, row: &mut [BaseElement]| {
+            row.copy_from_slice(&trace[step])
+        });
+        let expected = (0..15)
+            .map(|i| (alpha - E2::from(trace[i][0])) / (alpha - E2::from(trace[i][1])))
+            .fold(E2::ONE, |acc, term| acc * term);
+        assert_eq!(expected, column[15]);
+    }
+}
@@ -41,7 +41,13 @@ const NO_STRIDE: usize = 0;
 ///    to the values from the provided list. The cells must be evenly spaced at intervals with
 ///    lengths equal to powers of two. For example, we can specify that values in a column must
 ///    be equal to a sequence 1, 2, 3, 4 at steps 0, 8, 16, 24. That is, value at step 0 should be
-///    equal to 1, value at step 8 should be equal to 2 etc.
+///    equal to 1, value at step 8 should be equal to 2 etc. The number of values does not need
+///    to be a power of two; internally, the list is padded to the next power of two, and the
+///    padded steps are exempted from the resulting boundary constraint.
+///
+/// In zero-knowledge mode, periodic and sequence assertions are enforced only on the steps up to
+/// and including the last step of the computation; the remaining asserted steps fall on the
+/// random rows appended to the trace, and thus, are exempted from the boundary constraints.
 ///
 /// Note that single and periodic assertions are succinct. That is, a verifier can evaluate them
 /// very efficiently. However, sequence assertions have liner complexity in the number of
@@ -97,23 +103,23 @@ impl<E: FieldElement> Assertion<E> {
     /// the provided `values` at steps which start at `first_step` and repeat in equal intervals
     /// specified by `stride` until all values have been consumed.
     ///
+    /// The number of values rounded up to the next power of two, multiplied by `stride`, must be
+    /// equal to the length of the execution trace. For example, 100 values can be asserted at
+    /// every 8th step of a trace with 1024 steps. The steps which follow the last asserted value
+    /// at the same intervals are not constrained, but other assertions are still considered to
+    /// overlap with this assertion at these steps.
+    ///
     /// # Panics
     /// Panics if:
     /// * `stride` is not a power of two, or is smaller than 2.
     /// * `first_step` is greater than `stride`.
-    /// * `values` is empty or number of values in not a power of two.
+    /// * `values` is empty.
     pub fn sequence(column: usize, first_step: usize, stride: usize, values: Vec<E>) -> Self {
         validate_stride(stride, first_step, column);
         assert!(
             !values.is_empty(),
             "invalid assertion for column {column}: number of asserted values must be greater than zero"
         );
-        assert!(
-            values.len().is_power_of_two(),
-            "invalid assertion for column {}: number of asserted values must be a power of two, but was {}",
-            column,
-            values.len()
-        );
         Assertion {
             column,
             first_step,
@@ -222,7 +228,8 @@ impl<E: FieldElement> Assertion<E> {
     /// * `trace_length` is not a power of two.
     /// * For single assertion, `first_step` >= `trace_length`.
     /// * For periodic assertion, `stride` > `trace_length`.
-    /// * For sequence assertion, `num_values` * `stride` != `trace_length`;
+    /// * For sequence assertion, `num_values` rounded up to the next power of two times `stride`
+    ///   != `trace_length`.
     pub fn validate_trace_length(&self, trace_length: usize) -> Result<(), AssertionError> {
         if !trace_length.is_power_of_two() {
             return Err(AssertionError::TraceLengthNotPowerOfTwo(trace_length));
@@ -239,7 +246,7 @@ impl<E: FieldElement> Assertion<E> {
                 return Err(AssertionError::TraceLengthTooShort(self.stride, trace_length));
             }
         } else {
-            let expected_length = self.values.len() * self.stride;
+            let expected_length = self.values.len().next_power_of_two() * self.stride;
             if expected_length != trace_length {
                 return Err(AssertionError::TraceLengthNotExact(expected_length, trace_length));
             }
@@ -168,12 +168,22 @@ fn sequence_assertion_empty_values() {
 }
 
 #[test]
-#[should_panic(
-    expected = "invalid assertion for column 3: number of asserted values must be a power of two, but was 3"
-)]
 fn sequence_assertion_num_values_not_power_of_two() {
-    let _ =
-        Assertion::sequence(3, 2, 4, vec![BaseElement::ONE, BaseElement::ZERO, BaseElement::ONE]);
+    let values = rand_vector::<BaseElement>(3);
+    let a = Assertion::sequence(3, 2, 4, values.clone());
+    assert_eq!(3, a.get_num_steps(16));
+
+    // only the provided values are asserted; the number of values is padded to 4 when
+    // validating the trace length
+    let mut steps = Vec::new();
+    a.apply(16, |step, val| {
+        assert_eq!(values[steps.len()], val);
+        steps.push(step);
+    });
+    assert_eq!(vec![2, 6, 10], steps);
+
+    assert_eq!(Ok(()), a.validate_trace_length(16));
+    assert_eq!(Err(AssertionError::TraceLengthNotExact(16, 8)), a.validate_trace_length(8));
 }
 
 // OVERLAPPING ASSERTIONS
@@ -0,0 +1,354 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::vec::Vec;
+
+use math::{FieldElement, StarkField, ToElements};
+use utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+
+use super::{Air, AirContext, Assertion, EvaluationFrame, LookupTable, Program, TraceInfo};
+use crate::ProofOptions;
+
+// BATCH PUBLIC INPUTS
+// ================================================================================================
+
+/// Public inputs of a batch of computations proven together via [BatchAir].
+///
+/// Contains public inputs of every instance in the batch, in the same order as the instances
+/// are laid out in the execution trace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchPublicInputs<P> {
+    inputs: Vec<P>,
+}
+
+impl<P> BatchPublicInputs<P> {
+    /// Returns new public inputs for a batch of computations with the specified public inputs.
+    ///
+    /// # Panics
+    /// Panics if `inputs` is an empty vector.
+    pub fn new(inputs: Vec<P>) -> Self {
+        assert!(!inputs.is_empty(), "a batch must contain at least one instance");
+        Self { inputs }
+    }
+
+    /// Returns the number of instances in this batch.
+    pub fn num_instances(&self) -> usize {
+        self.inputs.len()
+    }
+
+    /// Returns public inputs of all instances in this batch.
+    pub fn inputs(&self) -> &[P] {
+        &self.inputs
+    }
+}
+
+impl<B: StarkField, P: ToElements<B>> ToElements<B> for BatchPublicInputs<P> {
+    /// Returns public inputs of all instances in this batch, each preceded by the number of its
+    /// elements, so that different batches never map to the same sequence of elements.
+    fn to_elements(&self) -> Vec<B> {
+        let mut result = Vec::new();
+        for inputs in self.inputs.iter() {
+            let elements = inputs.to_elements();
+            result.push(B::from(elements.len() as u32));
+            result.extend_from_slice(&elements);
+        }
+        result
+    }
+}
+
+impl<P: Serializable> Serializable for BatchPublicInputs<P> {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u32(self.inputs.len() as u32);
+        target.write_many(&self.inputs);
+    }
+}
+
+impl<P: Deserializable> Deserializable for BatchPublicInputs<P> {
+    /// Reads batch public inputs from the specified `source` and returns the result.
+    ///
+    /// # Errors
+    /// Returns an error if valid batch public inputs could not be read from the specified
+    /// `source`.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let num_instances = source.read_u32()? as usize;
+        if num_instances == 0 {
+            return Err(DeserializationError::InvalidValue(
+                "a batch must contain at least one instance".into(),
+            ));
+        }
+        let inputs = source.read_many(num_instances)?;
+        Ok(Self { inputs })
+    }
+}
+
+// BATCH AIR
+// ================================================================================================
+
+/// An AIR which proves several instances of the same computation with a single proof.
+///
+/// The execution trace of a batch consists of the execution traces of all instances placed side
+/// by side: for a batch of `k` instances of a computation with trace width `w`, columns
+/// `[i * w, (i + 1) * w)` of the batch trace contain the trace of the `i`-th instance. Thus, all
+/// instances must have traces of the same width and length (shorter traces must be padded by the
+/// caller in a way which satisfies the constraints of the underlying AIR).
+///
+/// Since the instances share a single execution trace, they share the commitments, the
+/// Fiat-Shamir randomness, the constraint composition polynomial, the FRI proof, and the set of
+/// query positions. As a result, a proof for a batch of `k` instances is much smaller than `k`
+/// individual proofs: only the trace openings at the queried positions grow with the number of
+/// instances.
+///
+/// Transition constraints of the batch are the transition constraints of all instances (in the
+/// order of instances), and the same is true for assertions and periodic columns. Frame offsets,
+/// transition exemptions, and custom transition divisors of the underlying AIR are preserved.
+///
+/// The batch trace info is passed to the instances with the width of a single instance and with
+/// the same metadata. Currently, batching is supported only for computations with a single
+/// execution trace segment.
+pub struct BatchAir<A: Air> {
+    context: AirContext<A::BaseField>,
+    instances: Vec<A>,
+    instance_width: usize,
+    num_periodic_columns: Vec<usize>,
+}
+
+impl<A: Air> BatchAir<A> {
+    /// Returns the AIRs of all instances in this batch.
+    pub fn instances(&self) -> &[A] {
+        &self.instances
+    }
+
+    /// Returns the number of columns in the execution trace of a single instance.
+    pub fn instance_width(&self) -> usize {
+        self.instance_width
+    }
+}
+
+impl<A: Air> Air for BatchAir<A> {
+    type BaseField = A::BaseField;
+    type PublicInputs = BatchPublicInputs<A::PublicInputs>;
+    type GkrProof = ();
+    type GkrVerifier = ();
+
+    /// Returns a new AIR for a batch of instances of computation `A`.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `trace_info` describes a multi-segment execution trace.
+    /// * Width of the execution trace is not divisible by the number of instances.
+    /// * The instances require auxiliary trace segments, or have different frame offsets.
+    /// * The instances use different programs or lookup tables.
+    fn new(trace_info: TraceInfo, pub_inputs: Self::PublicInputs, options: ProofOptions) -> Self {
+        assert!(
+            !trace_info.is_multi_segment(),
+            "batched computations cannot have auxiliary trace segments"
+        );
+        let num_instances = pub_inputs.num_instances();
+        let width = trace_info.main_trace_width();
+        assert!(
+            width % num_instances == 0,
+            "trace width must be divisible by the number of instances {num_instances}, but was {width}"
+        );
+        let instance_width = width / num_instances;
+
+        let instances = pub_inputs
+            .inputs
+            .into_iter()
+            .map(|inputs| {
+                let instance_info = TraceInfo::with_meta(
+                    instance_width,
+                    trace_info.length(),
+                    trace_info.meta().to_vec(),
+                );
+                A::new(instance_info, inputs, options.clone())
+            })
+            .collect::<Vec<_>>();
+
+        let first = instances[0].context();
+        for instance in instances.iter() {
+            assert!(
+                !instance.trace_info().is_multi_segment(),
+                "batched computations cannot have auxiliary trace segments"
+            );
+            assert!(
+                instance.context().frame_offsets() == first.frame_offsets(),
+                "all instances in a batch must have the same frame offsets"
+            );
+            assert!(
+                instance.get_program() == instances[0].get_program(),
+                "all instances in a batch must execute the same program"
+            );
+            assert!(
+                instance.get_lookup_tables() == instances[0].get_lookup_tables(),
+                "all instances in a batch must use the same lookup tables"
+            );
+        }
+
+        let degrees = instances
+            .iter()
+            .flat_map(|instance| instance.context().main_transition_constraint_degrees.clone())
+            .collect();
+        let num_assertions =
+            instances.iter().map(|instance| instance.context().num_main_assertions).sum();
+
+        let num_exemptions = instances
+            .iter()
+            .map(|instance| {
+                instance.context().num_transition_exemptions()
+                    - instance.context().num_random_rows()
+            })
+            .max()
+            .expect("no instances");
+        let mut context = AirContext::new(trace_info, degrees, num_assertions, options)
+            .set_frame_offsets(first.frame_offsets().to_vec())
+            .set_num_transition_exemptions(num_exemptions);
+
+        let mut constraint_offset = 0;
+        for instance in instances.iter() {
+            let num_constraints = instance.context().num_main_transition_constraints();
+            for i in 0..num_constraints {
+                if let Some(divisor) = instance.context().transition_divisor(i) {
+                    context =
+                        context.set_transition_divisor(constraint_offset + i, divisor.clone());
+                }
+            }
+            constraint_offset += num_constraints;
+        }
+
+        let ce_blowup_factor = instances
+            .iter()
+            .map(|instance| instance.ce_blowup_factor())
+            .max()
+            .expect("no instances");
+        if ce_blowup_factor > context.ce_blowup_factor {
+            context = context.set_ce_blowup_factor(ce_blowup_factor);
+        }
+
+        let num_periodic_columns = instances
+            .iter()
+            .map(|instance| instance.get_periodic_column_values().len())
+            .collect();
+
+        Self {
+            context,
+            instances,
+            instance_width,
+            num_periodic_columns,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let mut instance_frame =
+            EvaluationFrame::with_offsets(self.instance_width, frame.offsets().to_vec());
+        let mut constraint_offset = 0;
+        let mut periodic_offset = 0;
+        for (i, instance) in self.instances.iter().enumerate() {
+            let columns = i * self.instance_width..(i + 1) * self.instance_width;
+            for row_idx in 0..frame.num_rows() {
+                instance_frame
+                    .row_mut(row_idx)
+                    .copy_from_slice(&frame.row(row_idx)[columns.clone()]);
+            }
+
+            let num_constraints = instance.context().num_main_transition_constraints();
+            let num_periodic_columns = self.num_periodic_columns[i];
+            instance.evaluate_transition(
+                &instance_frame,
+                &periodic_values[periodic_offset..periodic_offset + num_periodic_columns],
+                &mut result[constraint_offset..constraint_offset + num_constraints],
+            );
+            constraint_offset += num_constraints;
+            periodic_offset += num_periodic_columns;
+        }
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        let mut result = Vec::with_capacity(self.context.num_main_assertions);
+        for (i, instance) in self.instances.iter().enumerate() {
+            result.extend(instance.get_assertions().into_iter().map(|mut assertion| {
+                assertion.column += i * self.instance_width;
+                assertion
+            }));
+        }
+        result
+    }
+
+    fn get_periodic_column_values(&self) -> Vec<Vec<Self::BaseField>> {
+        self.instances
+            .iter()
+            .flat_map(|instance| instance.get_periodic_column_values())
+            .collect()
+    }
+
+    fn get_program(&self) -> Option<&Program<Self::BaseField>> {
+        self.instances[0].get_program()
+    }
+
+    fn get_lookup_tables(&self) -> &[LookupTable<Self::BaseField>] {
+        self.instances[0].get_lookup_tables()
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use alloc::{vec, vec::Vec};
+
+    use math::{fields::f64::BaseElement, ToElements};
+    use utils::{Deserializable, Serializable};
+
+    use super::BatchPublicInputs;
+
+    struct Inputs(Vec<BaseElement>);
+
+    impl ToElements<BaseElement> for Inputs {
+        fn to_elements(&self) -> Vec<BaseElement> {
+            self.0.clone()
+        }
+    }
+
+    #[test]
+    fn batch_public_inputs_to_elements() {
+        let inputs = BatchPublicInputs::new(vec![
+            Inputs(vec![BaseElement::new(1), BaseElement::new(2)]),
+            Inputs(vec![BaseElement::new(3)]),
+        ]);
+        assert_eq!(2, inputs.num_instances());
+
+        // elements of each instance are preceded by their number
+        let expected: Vec<BaseElement> =
+            [2, 1, 2, 1, 3].into_iter().map(BaseElement::new).collect();
+        assert_eq!(expected, inputs.to_elements());
+
+        // the same elements split differently between instances map to different sequences
+        let other = BatchPublicInputs::new(vec![
+            Inputs(vec![BaseElement::new(1)]),
+            Inputs(vec![BaseElement::new(2), BaseElement::new(3)]),
+        ]);
+        assert_ne!(inputs.to_elements(), other.to_elements());
+    }
+
+    #[test]
+    fn batch_public_inputs_serialization() {
+        let inputs = BatchPublicInputs::new(vec![BaseElement::new(1), BaseElement::new(2)]);
+        let bytes = inputs.to_bytes();
+        assert_eq!(inputs, BatchPublicInputs::read_from_bytes(&bytes).unwrap());
+
+        // a batch cannot be empty
+        assert!(BatchPublicInputs::<BaseElement>::read_from_bytes(&0_u32.to_bytes()).is_err());
+    }
+}
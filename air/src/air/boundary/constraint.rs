@@ -61,10 +61,14 @@ where
         // build a polynomial which evaluates to constraint values at asserted steps; for
         // single-value assertions we use the value as constant coefficient of degree 0
         // polynomial; but for multi-value assertions, we need to interpolate the values
-        // into a polynomial using inverse FFT
+        // into a polynomial using inverse FFT. if the number of values is not a power of two,
+        // the values are padded with zeros; the padded steps are exempted from the divisor of
+        // the constraint, and thus, the padded values are not enforced.
         let mut poly_offset = (0, F::BaseField::ONE);
         let mut poly = assertion.values;
         if poly.len() > 1 {
+            poly.resize(poly.len().next_power_of_two(), F::ZERO);
+
             // get the twiddles from the map; if twiddles for this domain haven't been built
             // yet, build them and add them to the map
             let inv_twiddles = twiddle_map
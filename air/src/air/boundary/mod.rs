@@ -164,13 +164,18 @@ where
 {
     // iterate over all assertions, which are sorted first by stride and then by first_step
     // in ascending order
+    // in zero-knowledge mode, asserted steps which fall on the random rows appended to the trace
+    // are exempted from the divisors
+    let last_step = context.trace_len() - context.num_random_rows() - 1;
+
     let mut groups = BTreeMap::new();
     for (assertion, &cc) in assertions.into_iter().zip(composition_coefficients) {
         let key = (assertion.stride(), assertion.first_step());
         let group = groups.entry(key).or_insert_with(|| {
-            BoundaryConstraintGroup::new(ConstraintDivisor::from_assertion(
+            BoundaryConstraintGroup::new(ConstraintDivisor::from_assertion_up_to(
                 &assertion,
                 context.trace_len(),
+                last_step,
             ))
         });
 
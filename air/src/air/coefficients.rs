@@ -5,8 +5,11 @@
 
 use alloc::vec::Vec;
 
+use crypto::{DefaultRandomCoin, RandomCoin, RandomCoinError};
 use math::FieldElement;
 
+use crate::CoefficientDerivation;
+
 // CONSTRAINT COMPOSITION COEFFICIENTS
 // ================================================================================================
 /// Coefficients used in construction of constraint composition polynomial.
@@ -110,3 +113,33 @@ pub struct DeepCompositionCoefficients<E: FieldElement> {
     /// Lagrange kernel trace polynomial composition coefficient $\gamma$.
     pub lagrange: Option<E>,
 }
+
+// COEFFICIENT DERIVATION
+// ================================================================================================
+
+/// Draws `num_coefficients` coefficients from the provided public coin using the specified
+/// derivation method.
+///
+/// For [CoefficientDerivation::Expanded], a single seed element is drawn from the public coin and
+/// the coefficients are expanded from it via a [DefaultRandomCoin] instantiated with the same hash
+/// function as the public coin (see [CoefficientDerivation] for details).
+pub(super) fn draw_coefficients<E, R>(
+    public_coin: &mut R,
+    derivation: CoefficientDerivation,
+    num_coefficients: usize,
+) -> Result<Vec<E>, RandomCoinError>
+where
+    E: FieldElement<BaseField = R::BaseField>,
+    R: RandomCoin,
+{
+    match derivation {
+        CoefficientDerivation::Sequential => {
+            (0..num_coefficients).map(|_| public_coin.draw()).collect()
+        },
+        CoefficientDerivation::Expanded => {
+            let seed: E = public_coin.draw()?;
+            let mut kdf = DefaultRandomCoin::<R::Hasher>::new(E::slice_as_base_elements(&[seed]));
+            (0..num_coefficients).map(|_| kdf.draw()).collect()
+        },
+    }
+}
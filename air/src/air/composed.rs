@@ -0,0 +1,315 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::vec::Vec;
+
+use math::{FieldElement, StarkField, ToElements};
+use utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+
+use super::{Air, AirContext, Assertion, EvaluationFrame, LookupTable, Program, TraceInfo};
+use crate::ProofOptions;
+
+// COMPOSED PUBLIC INPUTS
+// ================================================================================================
+
+/// Public inputs of two computations proven together via [ComposedAir].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComposedPublicInputs<P1, P2> {
+    first: P1,
+    second: P2,
+}
+
+impl<P1, P2> ComposedPublicInputs<P1, P2> {
+    /// Returns new public inputs for a composition of two computations with the specified
+    /// public inputs.
+    pub fn new(first: P1, second: P2) -> Self {
+        Self { first, second }
+    }
+
+    /// Returns public inputs of the first computation.
+    pub fn first(&self) -> &P1 {
+        &self.first
+    }
+
+    /// Returns public inputs of the second computation.
+    pub fn second(&self) -> &P2 {
+        &self.second
+    }
+}
+
+impl<B, P1, P2> ToElements<B> for ComposedPublicInputs<P1, P2>
+where
+    B: StarkField,
+    P1: ToElements<B>,
+    P2: ToElements<B>,
+{
+    /// Returns public inputs of both computations, each preceded by the number of its elements,
+    /// so that different compositions never map to the same sequence of elements.
+    fn to_elements(&self) -> Vec<B> {
+        let first = self.first.to_elements();
+        let second = self.second.to_elements();
+
+        let mut result = Vec::with_capacity(first.len() + second.len() + 2);
+        result.push(B::from(first.len() as u32));
+        result.extend_from_slice(&first);
+        result.push(B::from(second.len() as u32));
+        result.extend_from_slice(&second);
+        result
+    }
+}
+
+impl<P1: Serializable, P2: Serializable> Serializable for ComposedPublicInputs<P1, P2> {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.first.write_into(target);
+        self.second.write_into(target);
+    }
+}
+
+impl<P1: Deserializable, P2: Deserializable> Deserializable for ComposedPublicInputs<P1, P2> {
+    /// Reads composed public inputs from the specified `source` and returns the result.
+    ///
+    /// # Errors
+    /// Returns an error if valid composed public inputs could not be read from the specified
+    /// `source`.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let first = P1::read_from(source)?;
+        let second = P2::read_from(source)?;
+        Ok(Self { first, second })
+    }
+}
+
+// COMPOSED AIR
+// ================================================================================================
+
+/// An AIR which proves two different computations with a single proof.
+///
+/// The execution trace of a composition consists of the execution traces of both computations
+/// placed side by side: the columns of the first computation are followed by the columns of the
+/// second computation. Thus, both computations must have traces of the same length (a shorter
+/// trace must be padded by the caller in a way which satisfies the constraints of its AIR). Trace
+/// info for a composed trace is built via [TraceInfo::compose()], which records the layout of the
+/// trace in the trace metadata.
+///
+/// Transition constraints of the composition are the transition constraints of the first
+/// computation followed by the transition constraints of the second computation, and the same is
+/// true for assertions and periodic columns. Frame offsets, transition exemptions, and custom
+/// transition divisors of the underlying AIRs are preserved.
+///
+/// Compositions can be nested to combine more than two computations: for example,
+/// `ComposedAir<A, ComposedAir<B, C>>` proves computations `A`, `B`, and `C` together. This makes
+/// it possible to describe a complex computation as a set of independent components (e.g.,
+/// "chips" of a virtual machine), each with its own AIR and public inputs. Currently, composition
+/// is supported only for computations with a single execution trace segment.
+pub struct ComposedAir<A1: Air, A2: Air<BaseField = A1::BaseField>> {
+    context: AirContext<A1::BaseField>,
+    first: A1,
+    second: A2,
+    first_width: usize,
+    first_num_periodic_columns: usize,
+    lookup_tables: Vec<LookupTable<A1::BaseField>>,
+}
+
+impl<A1: Air, A2: Air<BaseField = A1::BaseField>> ComposedAir<A1, A2> {
+    /// Returns the AIR of the first computation.
+    pub fn first(&self) -> &A1 {
+        &self.first
+    }
+
+    /// Returns the AIR of the second computation.
+    pub fn second(&self) -> &A2 {
+        &self.second
+    }
+}
+
+impl<A1: Air, A2: Air<BaseField = A1::BaseField>> Air for ComposedAir<A1, A2> {
+    type BaseField = A1::BaseField;
+    type PublicInputs = ComposedPublicInputs<A1::PublicInputs, A2::PublicInputs>;
+    type GkrProof = ();
+    type GkrVerifier = ();
+
+    /// Returns a new AIR for a composition of computations `A1` and `A2`.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `trace_info` was not built via [TraceInfo::compose()].
+    /// * Either of the computations requires auxiliary trace segments.
+    /// * The computations have different frame offsets.
+    /// * Both computations execute programs.
+    fn new(trace_info: TraceInfo, pub_inputs: Self::PublicInputs, options: ProofOptions) -> Self {
+        let (first_info, second_info) =
+            trace_info.decompose().expect("trace info does not describe a composed trace");
+        let first_width = first_info.main_trace_width();
+
+        let first = A1::new(first_info, pub_inputs.first, options.clone());
+        let second = A2::new(second_info, pub_inputs.second, options.clone());
+        let (first_context, second_context) = (first.context(), second.context());
+
+        assert!(
+            !first.trace_info().is_multi_segment() && !second.trace_info().is_multi_segment(),
+            "composed computations cannot have auxiliary trace segments"
+        );
+        assert!(
+            first_context.frame_offsets() == second_context.frame_offsets(),
+            "composed computations must have the same frame offsets"
+        );
+        assert!(
+            first.get_program().is_none() || second.get_program().is_none(),
+            "at most one of the composed computations can execute a program"
+        );
+
+        let degrees = first_context
+            .main_transition_constraint_degrees
+            .iter()
+            .chain(second_context.main_transition_constraint_degrees.iter())
+            .cloned()
+            .collect();
+        let num_assertions = first_context.num_main_assertions + second_context.num_main_assertions;
+        let num_exemptions = core::cmp::max(
+            first_context.num_transition_exemptions() - first_context.num_random_rows(),
+            second_context.num_transition_exemptions() - second_context.num_random_rows(),
+        );
+        let mut context = AirContext::new(trace_info, degrees, num_assertions, options)
+            .set_frame_offsets(first_context.frame_offsets().to_vec())
+            .set_num_transition_exemptions(num_exemptions);
+
+        let num_first_constraints = first_context.num_main_transition_constraints();
+        for i in 0..num_first_constraints {
+            if let Some(divisor) = first_context.transition_divisor(i) {
+                context = context.set_transition_divisor(i, divisor.clone());
+            }
+        }
+        for i in 0..second_context.num_main_transition_constraints() {
+            if let Some(divisor) = second_context.transition_divisor(i) {
+                context =
+                    context.set_transition_divisor(num_first_constraints + i, divisor.clone());
+            }
+        }
+
+        let ce_blowup_factor = core::cmp::max(first.ce_blowup_factor(), second.ce_blowup_factor());
+        if ce_blowup_factor > context.ce_blowup_factor {
+            context = context.set_ce_blowup_factor(ce_blowup_factor);
+        }
+
+        let first_num_periodic_columns = first.get_periodic_column_values().len();
+        let lookup_tables = first
+            .get_lookup_tables()
+            .iter()
+            .chain(second.get_lookup_tables())
+            .cloned()
+            .collect();
+
+        Self {
+            context,
+            first,
+            second,
+            first_width,
+            first_num_periodic_columns,
+            lookup_tables,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let second_width = frame.current().len() - self.first_width;
+        let mut first_frame =
+            EvaluationFrame::with_offsets(self.first_width, frame.offsets().to_vec());
+        let mut second_frame =
+            EvaluationFrame::with_offsets(second_width, frame.offsets().to_vec());
+        for row_idx in 0..frame.num_rows() {
+            let (first_row, second_row) = frame.row(row_idx).split_at(self.first_width);
+            first_frame.row_mut(row_idx).copy_from_slice(first_row);
+            second_frame.row_mut(row_idx).copy_from_slice(second_row);
+        }
+
+        let (first_periodic_values, second_periodic_values) =
+            periodic_values.split_at(self.first_num_periodic_columns);
+        let (first_result, second_result) =
+            result.split_at_mut(self.first.context().num_main_transition_constraints());
+        self.first
+            .evaluate_transition(&first_frame, first_periodic_values, first_result);
+        self.second
+            .evaluate_transition(&second_frame, second_periodic_values, second_result);
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        let mut result = self.first.get_assertions();
+        result.extend(self.second.get_assertions().into_iter().map(|mut assertion| {
+            assertion.column += self.first_width;
+            assertion
+        }));
+        result
+    }
+
+    fn get_periodic_column_values(&self) -> Vec<Vec<Self::BaseField>> {
+        let mut result = self.first.get_periodic_column_values();
+        result.extend(self.second.get_periodic_column_values());
+        result
+    }
+
+    fn get_program(&self) -> Option<&Program<Self::BaseField>> {
+        self.first.get_program().or_else(|| self.second.get_program())
+    }
+
+    fn get_lookup_tables(&self) -> &[LookupTable<Self::BaseField>] {
+        &self.lookup_tables
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use alloc::{vec, vec::Vec};
+
+    use math::{fields::f64::BaseElement, ToElements};
+    use utils::{Deserializable, Serializable};
+
+    use super::ComposedPublicInputs;
+
+    struct Inputs(Vec<BaseElement>);
+
+    impl ToElements<BaseElement> for Inputs {
+        fn to_elements(&self) -> Vec<BaseElement> {
+            self.0.clone()
+        }
+    }
+
+    #[test]
+    fn composed_public_inputs_to_elements() {
+        let inputs = ComposedPublicInputs::new(
+            Inputs(vec![BaseElement::new(1), BaseElement::new(2)]),
+            Inputs(vec![BaseElement::new(3)]),
+        );
+
+        // elements of each computation are preceded by their number
+        let expected: Vec<BaseElement> =
+            [2, 1, 2, 1, 3].into_iter().map(BaseElement::new).collect();
+        assert_eq!(expected, inputs.to_elements());
+
+        // the same elements split differently between computations map to different sequences
+        let other = ComposedPublicInputs::new(
+            Inputs(vec![BaseElement::new(1)]),
+            Inputs(vec![BaseElement::new(2), BaseElement::new(3)]),
+        );
+        assert_ne!(inputs.to_elements(), other.to_elements());
+    }
+
+    #[test]
+    fn composed_public_inputs_serialization() {
+        let inputs = ComposedPublicInputs::new(BaseElement::new(1), 2_u64);
+        let bytes = inputs.to_bytes();
+        assert_eq!(inputs, ComposedPublicInputs::read_from_bytes(&bytes).unwrap());
+    }
+}
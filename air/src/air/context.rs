@@ -3,12 +3,18 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use alloc::vec::Vec;
+use alloc::{collections::BTreeMap, vec::Vec};
 use core::cmp;
 
 use math::StarkField;
 
-use crate::{air::TransitionConstraintDegree, ProofOptions, TraceInfo};
+use crate::{
+    air::{
+        transition::validate_frame_offsets, ConstraintDivisor, SymbolicConstraints, SymbolicNode,
+        TransitionConstraintDegree,
+    },
+    ProofOptions, TraceInfo,
+};
 
 // AIR CONTEXT
 // ================================================================================================
@@ -26,6 +32,9 @@ pub struct AirContext<B: StarkField> {
     pub(super) trace_domain_generator: B,
     pub(super) lde_domain_generator: B,
     pub(super) num_transition_exemptions: usize,
+    pub(super) num_random_rows: usize,
+    pub(super) frame_offsets: Vec<usize>,
+    pub(super) transition_divisors: BTreeMap<usize, ConstraintDivisor<B>>,
 }
 
 impl<B: StarkField> AirContext<B> {
@@ -45,6 +54,8 @@ impl<B: StarkField> AirContext<B> {
     /// * `num_assertions` is zero.
     /// * Blowup factor specified by the provided `options` is too small to accommodate degrees
     ///   of the specified transition constraints.
+    /// * The LDE domain (i.e., trace length multiplied by the blowup factor) is larger than the
+    ///   largest multiplicative subgroup of the base field.
     /// * `trace_info` describes a multi-segment execution trace.
     pub fn new(
         trace_info: TraceInfo,
@@ -67,6 +78,53 @@ impl<B: StarkField> AirContext<B> {
         )
     }
 
+    /// Returns a new instance of [AirContext] instantiated for computations which require a single
+    /// execution trace segment and describe their transition constraints symbolically.
+    ///
+    /// The context is derived from the specified constraints as follows:
+    /// * Degrees of transition constraints are inferred via [SymbolicConstraints::degrees()];
+    ///   `periodic_column_lengths` must contain the numbers of values in the periodic columns of
+    ///   the computation (see [Air::get_periodic_column_values()](crate::Air::get_periodic_column_values)).
+    /// * If the constraints refer to rows of an evaluation frame beyond the next row, the number
+    ///   of frame rows is set accordingly (see [AirContext::set_num_frame_rows()]).
+    /// * Constraints enforced only on a period of steps are assigned custom divisors (see
+    ///   [AirContext::set_transition_period()]); constraints with the same period share a
+    ///   divisor.
+    ///
+    /// # Panics
+    /// Panics if the degrees of the constraints cannot be inferred (see
+    /// [SymbolicConstraints::degrees()]), or for any of the reasons listed for [AirContext::new()],
+    /// [AirContext::set_num_frame_rows()], and [AirContext::set_transition_period()].
+    pub fn from_symbolic_constraints(
+        trace_info: TraceInfo,
+        constraints: &SymbolicConstraints<B>,
+        periodic_column_lengths: &[usize],
+        num_assertions: usize,
+        options: ProofOptions,
+    ) -> Self {
+        let degrees = constraints.degrees(trace_info.length(), periodic_column_lengths);
+        let mut context = Self::new(trace_info, degrees, num_assertions, options);
+
+        let num_frame_rows = constraints
+            .nodes()
+            .iter()
+            .filter_map(|node| match node {
+                SymbolicNode::Trace { row, .. } => Some(row + 1),
+                _ => None,
+            })
+            .fold(2, cmp::max);
+        if num_frame_rows > 2 {
+            context = context.set_num_frame_rows(num_frame_rows);
+        }
+
+        for constraint_idx in 0..constraints.num_constraints() {
+            if let Some((first_step, period)) = constraints.period(constraint_idx) {
+                context = context.set_transition_period(constraint_idx, first_step, period);
+            }
+        }
+        context
+    }
+
     /// Returns a new instance of [AirContext] instantiated for computations which require multiple
     /// execution trace segments.
     ///
@@ -89,6 +147,11 @@ impl<B: StarkField> AirContext<B> {
     ///   - `num_aux_assertions` is greater than zero.
     /// * Blowup factor specified by the provided `options` is too small to accommodate degrees
     ///   of the specified transition constraints.
+    /// * The LDE domain (i.e., trace length multiplied by the blowup factor) is larger than the
+    ///   largest multiplicative subgroup of the base field.
+    /// * Zero-knowledge mode is specified by the provided `options` but:
+    ///   - A Lagrange kernel column is specified.
+    ///   - The trace is too short to accommodate the random rows appended to it.
     pub fn new_multi_segment(
         trace_info: TraceInfo,
         main_transition_constraint_degrees: Vec<TransitionConstraintDegree>,
@@ -156,9 +219,25 @@ impl<B: StarkField> AirContext<B> {
         );
 
         let trace_length = trace_info.length();
-        let lde_domain_size = trace_length * options.blowup_factor();
+        let lde_domain_size = trace_length
+            .checked_mul(options.blowup_factor())
+            .expect("LDE domain size does not fit into a usize value");
+        assert!(
+            lde_domain_size.ilog2() <= B::TWO_ADICITY,
+            "LDE domain size cannot exceed 2^{} for the specified base field, but was 2^{}",
+            B::TWO_ADICITY,
+            lde_domain_size.ilog2()
+        );
+
+        if options.is_zk() {
+            assert!(
+                lagrange_kernel_aux_column_idx.is_none(),
+                "Lagrange kernel columns are not supported in zero-knowledge mode"
+            );
+        }
+        let num_random_rows = get_num_random_rows(&options, trace_length, 2);
 
-        AirContext {
+        let mut context = AirContext {
             options,
             trace_info,
             main_transition_constraint_degrees,
@@ -169,13 +248,25 @@ impl<B: StarkField> AirContext<B> {
             ce_blowup_factor,
             trace_domain_generator: B::get_root_of_unity(trace_length.ilog2()),
             lde_domain_generator: B::get_root_of_unity(lde_domain_size.ilog2()),
-            num_transition_exemptions: 1,
+            num_transition_exemptions: 1 + num_random_rows,
+            num_random_rows,
+            frame_offsets: vec![0, 1],
+            transition_divisors: BTreeMap::new(),
+        };
+        if context.options.is_zk() {
+            context.update_zk_ce_blowup_factor();
         }
+        context
     }
 
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
+    /// Returns the STARK protocol parameters for an instance of a computation.
+    pub fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
     /// Returns the trace info for an instance of a computation.
     pub fn trace_info(&self) -> &TraceInfo {
         &self.trace_info
@@ -254,11 +345,57 @@ impl<B: StarkField> AirContext<B> {
     ///
     /// This is guaranteed to be at least 1 (which is the default value), but could be greater.
     /// The maximum number of exemptions is determined by a combination of transition constraint
-    /// degrees and blowup factor specified for the computation.
+    /// degrees and blowup factor specified for the computation. In zero-knowledge mode, the
+    /// exempted rows include the random rows appended to the trace.
     pub fn num_transition_exemptions(&self) -> usize {
         self.num_transition_exemptions
     }
 
+    /// Returns the number of trace rows in the evaluation frames passed to the transition
+    /// constraint evaluators.
+    ///
+    /// This is 2 (the current and the next row) unless set otherwise via
+    /// [AirContext::set_num_frame_rows()] or [AirContext::set_frame_offsets()].
+    pub fn num_frame_rows(&self) -> usize {
+        self.frame_offsets.len()
+    }
+
+    /// Returns offsets of the trace rows in the evaluation frames passed to the transition
+    /// constraint evaluators, relative to the current row.
+    ///
+    /// This is `[0, 1]` (the current and the next row) unless set otherwise via
+    /// [AirContext::set_num_frame_rows()] or [AirContext::set_frame_offsets()].
+    pub fn frame_offsets(&self) -> &[usize] {
+        &self.frame_offsets
+    }
+
+    /// Returns the custom divisor of the transition constraint at the specified index, or `None`
+    /// if the constraint uses the default transition constraint divisor.
+    ///
+    /// Transition constraints are indexed with constraints against the main trace segment
+    /// first, followed by constraints against the auxiliary trace segment. Custom divisors can be
+    /// set via [AirContext::set_transition_divisor()].
+    pub fn transition_divisor(&self, constraint_idx: usize) -> Option<&ConstraintDivisor<B>> {
+        self.transition_divisors.get(&constraint_idx)
+    }
+
+    /// Returns the number of random rows appended to each execution trace segment.
+    ///
+    /// This is zero unless proofs are generated in zero-knowledge mode, in which case the last
+    /// step of the computation is at `trace_length - num_random_rows - 1`.
+    pub fn num_random_rows(&self) -> usize {
+        self.num_random_rows
+    }
+
+    /// Returns the number of coefficients of the constraint composition polynomial stored in
+    /// each constraint composition column.
+    ///
+    /// This is equal to the trace length, except in zero-knowledge mode, where the space left in
+    /// each column is used for the random polynomials which mask the composition polynomial.
+    pub fn composition_segment_len(&self) -> usize {
+        self.trace_len() - self.num_random_rows
+    }
+
     /// Returns the number of columns needed to store the constraint composition polynomial.
     ///
     /// This is the maximum of:
@@ -292,24 +429,19 @@ impl<B: StarkField> AirContext<B> {
     /// numerator is `trace_len - 1` for all transition constraints (i.e. the base degree is 1).
     /// Hence, no matter what the degree of the divisor is for each, the degree of the fraction will
     /// be at most `trace_len - 1`.
+    ///
+    /// In zero-knowledge mode, the composition polynomial is split into at least two segments of
+    /// [AirContext::composition_segment_len()] coefficients each, and an additional column holds
+    /// a random polynomial which masks the DEEP composition polynomial.
     pub fn num_constraint_composition_columns(&self) -> usize {
-        let mut highest_constraint_degree = 0_usize;
-        for degree in self
-            .main_transition_constraint_degrees
-            .iter()
-            .chain(self.aux_transition_constraint_degrees.iter())
-        {
-            let eval_degree = degree.get_evaluation_degree(self.trace_len());
-            if eval_degree > highest_constraint_degree {
-                highest_constraint_degree = eval_degree
-            }
+        if self.options.is_zk() {
+            let num_segments =
+                (self.composition_degree() + 1).div_ceil(self.composition_segment_len());
+            return cmp::max(num_segments, 2) + 1;
         }
-        let trace_length = self.trace_len();
-        let transition_divisior_degree = trace_length - self.num_transition_exemptions();
 
         // we use the identity: ceil(a/b) = (a + b - 1)/b
-        let num_constraint_col =
-            (highest_constraint_degree - transition_divisior_degree).div_ceil(trace_length);
+        let num_constraint_col = self.composition_degree().div_ceil(self.trace_len());
 
         cmp::max(num_constraint_col, 1)
     }
@@ -319,15 +451,27 @@ impl<B: StarkField> AirContext<B> {
 
     /// Sets the number of transition exemptions for this context.
     ///
+    /// In zero-knowledge mode, the random rows appended to the trace are exempted in addition to
+    /// the specified number of rows, and the constraint evaluation blowup factor is increased as
+    /// needed to accommodate the degree of the resulting constraint composition polynomial.
+    ///
     /// # Panics
     /// Panics if:
     /// * The number of exemptions is zero.
+    /// * The number of exemptions is smaller than the largest evaluation frame row offset.
     /// * The number of exemptions exceeds half of the trace length.
     /// * Given the combination of transition constraints degrees and the blowup factor in this
     ///   context, the number of exemptions is too larger for a valid computation of the constraint
     ///   composition polynomial.
+    /// * In zero-knowledge mode, the blowup factor in this context is too small for the
+    ///   resulting constraint evaluation blowup factor.
     pub fn set_num_transition_exemptions(mut self, n: usize) -> Self {
         assert!(n > 0, "number of transition exemptions must be greater than zero");
+        let max_frame_offset = self.max_frame_offset();
+        assert!(
+            n >= max_frame_offset,
+            "number of transition exemptions must be at least {max_frame_offset} for evaluation frame row offset {max_frame_offset}, but was {n}"
+        );
         // exemptions which are for more than half the trace plus one are probably a mistake
         assert!(
             n <= self.trace_len() / 2 + 1,
@@ -341,11 +485,20 @@ impl<B: StarkField> AirContext<B> {
         // polynomial we can accommodate. On the other hand, adding exemption points reduces the
         // degree of the divisor which results in an increase of the resulting constraint composition
         // polynomial.Thus we need to check that the number of exemption points is not too large
-        // given the above.
-        for degree in self
+        // given the above. In zero-knowledge mode, the `ce_blowup` factor is increased instead.
+        self.num_transition_exemptions = n + self.num_random_rows;
+        if self.options.is_zk() {
+            self.update_zk_ce_blowup_factor();
+            return self;
+        }
+
+        // constraints with custom divisors are not affected by the number of exemptions
+        for (_, degree) in self
             .main_transition_constraint_degrees
             .iter()
             .chain(self.aux_transition_constraint_degrees.iter())
+            .enumerate()
+            .filter(|(i, _)| !self.transition_divisors.contains_key(i))
         {
             let eval_degree = degree.get_evaluation_degree(self.trace_len());
             let max_constraint_composition_degree = self.ce_domain_size() - 1;
@@ -355,8 +508,274 @@ impl<B: StarkField> AirContext<B> {
                 "number of transition exemptions cannot exceed: {max_exemptions}, but was {n}"
             )
         }
+        self
+    }
+
+    /// Sets the number of consecutive trace rows in the evaluation frames passed to the
+    /// transition constraint evaluators.
+    ///
+    /// This is equivalent to setting frame row offsets to `[0, 1, ..., num_rows - 1]` via
+    /// [AirContext::set_frame_offsets()].
+    ///
+    /// # Panics
+    /// Panics if `num_rows` is smaller than two or greater than 255, or if the offsets are not
+    /// valid for this context (see [AirContext::set_frame_offsets()]).
+    pub fn set_num_frame_rows(self, num_rows: usize) -> Self {
+        assert!(num_rows >= 2, "number of frame rows must be at least two, but was {num_rows}");
+        self.set_frame_offsets((0..num_rows).collect())
+    }
+
+    /// Sets offsets of the trace rows, relative to the current row, in the evaluation frames
+    /// passed to the transition constraint evaluators.
+    ///
+    /// For example, offsets `[0, 1, 8]` make rows `i`, `i + 1`, and `i + 8` available to the
+    /// transition constraints evaluated at step `i`; these rows are accessible via
+    /// [EvaluationFrame::row()](crate::EvaluationFrame::row) at positions 0, 1, and 2
+    /// respectively.
+    ///
+    /// Transition constraints which access the row at offset `d` cannot apply to the last `d`
+    /// steps of the trace, and thus, the number of transition exemptions is increased to the
+    /// largest offset if it is currently smaller. In zero-knowledge mode, the number of random
+    /// rows appended to the trace also grows with the number of frame rows.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The offsets are not valid frame offsets: fewer than two or more than 255 offsets are
+    ///   provided, the first offset is not zero, or the offsets are not strictly increasing.
+    /// * The offsets are different from `[0, 1]`, and the auxiliary trace segment contains a
+    ///   Lagrange kernel column.
+    /// * The resulting number of transition exemptions is not valid for this context (see
+    ///   [AirContext::set_num_transition_exemptions()]).
+    pub fn set_frame_offsets(mut self, offsets: Vec<usize>) -> Self {
+        validate_frame_offsets(&offsets);
+        assert!(
+            offsets == [0, 1] || !self.has_lagrange_kernel_aux_column(),
+            "evaluation frames other than the current and the next row are not supported with Lagrange kernel columns"
+        );
 
-        self.num_transition_exemptions = n;
+        let num_exemptions = self.num_transition_exemptions - self.num_random_rows;
+        self.num_random_rows = get_num_random_rows(&self.options, self.trace_len(), offsets.len());
+        self.frame_offsets = offsets;
+        let num_exemptions = cmp::max(num_exemptions, self.max_frame_offset());
+        self.set_num_transition_exemptions(num_exemptions)
+    }
+
+    /// Sets a custom divisor for the transition constraint at the specified index.
+    ///
+    /// By default, all transition constraints are divided by the same divisor which enforces
+    /// them on all steps of the trace except for the last `num_transition_exemptions` steps. A
+    /// custom divisor can restrict a constraint to a subset of steps (e.g., every 8th step via
+    /// [ConstraintDivisor::from_transition_coset()]), or exempt arbitrary steps from it (via
+    /// [ConstraintDivisor::from_transition_with_exemptions()]). Custom divisors are not affected
+    /// by the number of transition exemptions set for this context, and thus, the divisor must
+    /// itself exempt the steps at which the evaluation frame extends beyond the end of the trace.
+    ///
+    /// Transition constraints are indexed with constraints against the main trace segment
+    /// first, followed by constraints against the auxiliary trace segment.
+    ///
+    /// The constraint evaluation blowup factor is increased as needed to accommodate the degree
+    /// of the resulting constraint composition polynomial.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The constraint index is not smaller than the number of transition constraints.
+    /// * This context is set up for zero-knowledge mode.
+    /// * The blowup factor in this context is too small for the resulting constraint evaluation
+    ///   blowup factor.
+    pub fn set_transition_divisor(
+        mut self,
+        constraint_idx: usize,
+        divisor: ConstraintDivisor<B>,
+    ) -> Self {
+        assert!(
+            constraint_idx < self.num_transition_constraints(),
+            "constraint index must be smaller than {}, but was {}",
+            self.num_transition_constraints(),
+            constraint_idx
+        );
+        assert!(
+            !self.options.is_zk(),
+            "custom transition divisors are not supported in zero-knowledge mode"
+        );
+        self.transition_divisors.insert(constraint_idx, divisor);
+
+        while self.ce_domain_size() <= self.composition_degree() {
+            self.ce_blowup_factor *= 2;
+        }
+        assert!(
+            self.options.blowup_factor() >= self.ce_blowup_factor,
+            "blowup factor too small for custom transition divisor; expected at least {}, but was {}",
+            self.ce_blowup_factor,
+            self.options.blowup_factor()
+        );
         self
     }
+
+    /// Restricts the transition constraint at the specified index to every `period`-th step of
+    /// the execution trace starting with `first_step`.
+    ///
+    /// This is an alternative to multiplying a constraint by a selector periodic column: instead
+    /// of increasing the degree of the constraint, the constraint is assigned a custom divisor
+    /// which vanishes only on the enforced steps (see
+    /// [ConstraintDivisor::from_transition_coset()]). Thus, the degree of the constraint, as
+    /// well as the cost of evaluating it, is not affected by the restriction.
+    ///
+    /// Enforced steps which fall on the last `num_transition_exemptions` steps of the trace are
+    /// exempted from the constraint. The exemptions are determined by the number of transition
+    /// exemptions at the time this method is called, and thus, this method should be called
+    /// after frame offsets and transition exemptions are set.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `period` is not a power of two, or is greater than the trace length.
+    /// * `first_step` is not smaller than `period`.
+    /// * All enforced steps are exempted.
+    /// * The custom divisor cannot be set for the specified constraint (see
+    ///   [AirContext::set_transition_divisor()]).
+    pub fn set_transition_period(
+        self,
+        constraint_idx: usize,
+        first_step: usize,
+        period: usize,
+    ) -> Self {
+        let trace_len = self.trace_len();
+        let num_exemptions = self.num_transition_exemptions - self.num_random_rows;
+        let exempted_steps = (first_step..trace_len)
+            .step_by(period.max(1))
+            .filter(|&step| step >= trace_len - num_exemptions)
+            .collect::<Vec<_>>();
+        let divisor = ConstraintDivisor::from_transition_coset(
+            trace_len,
+            first_step,
+            period,
+            &exempted_steps,
+        );
+        self.set_transition_divisor(constraint_idx, divisor)
+    }
+
+    /// Sets the constraint evaluation blowup factor for this context.
+    ///
+    /// By default, the constraint evaluation blowup factor is the smallest factor which can
+    /// accommodate the degrees of transition constraints (e.g., 4 for constraints of degree 3),
+    /// independently of the blowup factor of the LDE domain specified by proof options. This
+    /// method can be used to evaluate constraints over a larger domain; this does not affect the
+    /// proofs which can be generated for this context, but increases the cost of constraint
+    /// evaluation.
+    ///
+    /// Setting transition divisors or exemptions after calling this method may further increase
+    /// the constraint evaluation blowup factor as needed.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `ce_blowup_factor` is not a power of two.
+    /// * `ce_blowup_factor` is greater than the blowup factor specified by proof options.
+    /// * `ce_blowup_factor` is too small to accommodate the degrees of transition constraints.
+    pub fn set_ce_blowup_factor(mut self, ce_blowup_factor: usize) -> Self {
+        assert!(
+            ce_blowup_factor.is_power_of_two(),
+            "constraint evaluation blowup factor must be a power of two, but was {ce_blowup_factor}"
+        );
+        assert!(
+            ce_blowup_factor <= self.options.blowup_factor(),
+            "constraint evaluation blowup factor cannot exceed {}, but was {}",
+            self.options.blowup_factor(),
+            ce_blowup_factor
+        );
+        let min_ce_blowup_factor = self.min_ce_blowup_factor();
+        assert!(
+            ce_blowup_factor >= min_ce_blowup_factor,
+            "constraint evaluation blowup factor must be at least {min_ce_blowup_factor}, but was {ce_blowup_factor}"
+        );
+        self.ce_blowup_factor = ce_blowup_factor;
+        self
+    }
+
+    // HELPER FUNCTIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the smallest constraint evaluation blowup factor which can accommodate the degrees
+    /// of transition constraints, as well as the degree of the constraint composition polynomial.
+    fn min_ce_blowup_factor(&self) -> usize {
+        let mut result = self
+            .main_transition_constraint_degrees
+            .iter()
+            .chain(self.aux_transition_constraint_degrees.iter())
+            .map(|degree| degree.min_blowup_factor())
+            .max()
+            .unwrap_or(1);
+        while self.trace_len() * result <= self.composition_degree() {
+            result *= 2;
+        }
+        result
+    }
+
+    /// Returns the offset of the last row in the evaluation frames.
+    fn max_frame_offset(&self) -> usize {
+        *self.frame_offsets.last().expect("no frame offsets")
+    }
+
+    /// Returns the degree of the constraint composition polynomial, computed as the maximum
+    /// evaluation degree over all transition constraints minus the degree of the transition
+    /// constraint divisor.
+    fn composition_degree(&self) -> usize {
+        let transition_divisor_degree = self.trace_len() - self.num_transition_exemptions();
+        self.main_transition_constraint_degrees
+            .iter()
+            .chain(self.aux_transition_constraint_degrees.iter())
+            .enumerate()
+            .map(|(i, degree)| {
+                let divisor_degree = match self.transition_divisors.get(&i) {
+                    Some(divisor) => divisor.degree(),
+                    None => transition_divisor_degree,
+                };
+                degree.get_evaluation_degree(self.trace_len()).saturating_sub(divisor_degree)
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Increases the constraint evaluation blowup factor until the constraint evaluation domain
+    /// is large enough to interpolate the constraint composition polynomial.
+    ///
+    /// In zero-knowledge mode, the random rows appended to the trace are exempt from transition
+    /// constraints, and thus, the composition polynomial may have a degree higher than the one
+    /// implied by the transition constraint degrees alone.
+    fn update_zk_ce_blowup_factor(&mut self) {
+        while self.ce_domain_size() <= self.composition_degree() {
+            self.ce_blowup_factor *= 2;
+        }
+        assert!(
+            self.options.blowup_factor() >= self.ce_blowup_factor,
+            "blowup factor too small for zero-knowledge mode; expected at least {}, but was {}",
+            self.ce_blowup_factor,
+            self.options.blowup_factor()
+        );
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the number of random rows appended to each trace segment for the specified options.
+///
+/// In zero-knowledge mode, the number of random rows must exceed the number of points at which the
+/// prover reveals values of trace polynomials: `num_frame_rows` points per query (the query itself
+/// and the following rows which affect the value of the composition polynomial at that query),
+/// and `num_frame_rows` out-of-domain points.
+fn get_num_random_rows(
+    options: &ProofOptions,
+    trace_length: usize,
+    num_frame_rows: usize,
+) -> usize {
+    if !options.is_zk() {
+        return 0;
+    }
+    let num_random_rows = num_frame_rows * (options.num_queries() + 1);
+    assert!(
+        num_random_rows <= trace_length / 2,
+        "trace length must be at least {} in zero-knowledge mode, but was {}",
+        (2 * num_random_rows).next_power_of_two(),
+        trace_length
+    );
+    num_random_rows
 }
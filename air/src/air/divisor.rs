@@ -23,7 +23,12 @@ use crate::air::Assertion;
 /// numerator: `[(a, 1), (b, 2)]`, exemptions: `[3]`.
 ///
 /// A divisor cannot be instantiated directly, and instead must be created either for an
-/// [Assertion] or for a transition constraint.
+/// [Assertion] or for a transition constraint. Besides the divisor shared by all transition
+/// constraints (see [ConstraintDivisor::from_transition()]), custom transition constraint
+/// divisors can be built via [ConstraintDivisor::from_transition_with_exemptions()] and
+/// [ConstraintDivisor::from_transition_coset()], and attached to individual transition
+/// constraints via
+/// [AirContext::set_transition_divisor()](crate::AirContext::set_transition_divisor).
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct ConstraintDivisor<B: StarkField> {
     pub(super) numerator: Vec<(usize, B)>,
@@ -63,6 +68,83 @@ impl<B: StarkField> ConstraintDivisor<B> {
         Self::new(vec![(constraint_enforcement_domain_size, B::ONE)], exemptions)
     }
 
+    /// Builds a divisor for transition constraints which must hold on all steps of the execution
+    /// trace except for the specified exemption steps.
+    ///
+    /// The divisor polynomial is defined as:
+    ///
+    /// $$ z(x) = \frac{x^n - 1}{ \prod_{i \in E} (x - g^i)} $$
+    ///
+    /// where, $n$ is the length of the execution trace, $g$ is the generator of the trace domain,
+    /// and $E$ is the set of exempted steps. Unlike [ConstraintDivisor::from_transition()], the
+    /// exempted steps do not need to be at the end of the trace.
+    ///
+    /// # Panics
+    /// Panics if any of the exempted steps is outside of the trace, if the exempted steps contain
+    /// duplicates, or if all steps of the trace are exempted.
+    pub fn from_transition_with_exemptions(trace_length: usize, exempted_steps: &[usize]) -> Self {
+        Self::from_transition_coset(trace_length, 0, 1, exempted_steps)
+    }
+
+    /// Builds a divisor for transition constraints which must hold on every `stride`-th step of
+    /// the execution trace starting with `first_step`, except for the specified exemption steps.
+    ///
+    /// The divisor polynomial is defined as:
+    ///
+    /// $$ z(x) = \frac{x^k - g^{a \cdot k}}{ \prod_{i \in E} (x - g^i)} $$
+    ///
+    /// where, $g$ is the generator of the trace domain, $k$ is the number of steps in the coset
+    /// (i.e., trace length divided by `stride`), $a$ is the `first_step`, and $E$ is the set of
+    /// exempted steps.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `stride` is not a power of two, or is greater than `trace_length`.
+    /// * `first_step` is not smaller than `stride`.
+    /// * Any of the exempted steps is not in the coset (i.e., is outside of the trace or is not
+    ///   equal to `first_step` modulo `stride`), or the exempted steps contain duplicates.
+    /// * All steps of the coset are exempted.
+    pub fn from_transition_coset(
+        trace_length: usize,
+        first_step: usize,
+        stride: usize,
+        exempted_steps: &[usize],
+    ) -> Self {
+        assert!(
+            stride.is_power_of_two(),
+            "transition divisor stride must be a power of two, but was {stride}"
+        );
+        assert!(
+            stride <= trace_length,
+            "transition divisor stride cannot exceed trace length {trace_length}, but was {stride}"
+        );
+        assert!(
+            first_step < stride,
+            "first step of a transition divisor must be smaller than stride {stride}, but was {first_step}"
+        );
+
+        let num_steps = trace_length / stride;
+        assert!(
+            exempted_steps.len() < num_steps,
+            "number of exempted steps must be smaller than {num_steps}, but was {}",
+            exempted_steps.len()
+        );
+        for (i, &step) in exempted_steps.iter().enumerate() {
+            assert!(
+                step < trace_length && step % stride == first_step,
+                "exempted step {step} is not one of the steps enforced by the divisor"
+            );
+            assert!(!exempted_steps[..i].contains(&step), "exempted step {step} is duplicated");
+        }
+
+        let offset = get_trace_domain_value_at::<B>(trace_length, num_steps * first_step);
+        let exemptions = exempted_steps
+            .iter()
+            .map(|&step| get_trace_domain_value_at::<B>(trace_length, step))
+            .collect();
+        Self::new(vec![(num_steps, offset)], exemptions)
+    }
+
     /// Builds a divisor for a boundary constraint described by the assertion.
     ///
     /// For boundary constraints, the divisor polynomial is defined as:
@@ -84,22 +166,69 @@ impl<B: StarkField> ConstraintDivisor<B> {
     ///   $(x - g^a) \cdot (x - g^{a + j}) \cdot (x - g^{a + 2 \cdot j}) ... (x - g^{a + (k  - 1) \cdot j})$,
     ///   where $j$ is the length of interval between asserted steps (e.g. 8).
     ///
+    /// For sequence assertions with a number of values which is not a power of two, $k$ is the
+    /// number of values rounded up to the next power of two, and the steps which follow the last
+    /// asserted value are exempted from the divisor.
+    ///
     /// # Panics
     /// Panics of the specified `trace_length` is inconsistent with the specified `assertion`.
     pub fn from_assertion<E>(assertion: &Assertion<E>, trace_length: usize) -> Self
     where
         E: FieldElement<BaseField = B>,
     {
-        let num_steps = assertion.get_num_steps(trace_length);
+        // for sequence assertions, the number of values is padded to the next power of two,
+        // and the padded steps are exempted from the divisor
+        let num_values = assertion.get_num_steps(trace_length);
+        let num_steps = num_values.next_power_of_two();
+        let exemptions = (num_values..num_steps)
+            .map(|i| {
+                let step = assertion.first_step + assertion.stride * i;
+                get_trace_domain_value_at::<B>(trace_length, step)
+            })
+            .collect();
+
         if assertion.first_step == 0 {
-            Self::new(vec![(num_steps, B::ONE)], vec![])
+            Self::new(vec![(num_steps, B::ONE)], exemptions)
         } else {
             let trace_offset = num_steps * assertion.first_step;
             let offset = get_trace_domain_value_at::<B>(trace_length, trace_offset);
-            Self::new(vec![(num_steps, offset)], vec![])
+            Self::new(vec![(num_steps, offset)], exemptions)
         }
     }
 
+    /// Builds a divisor for a boundary constraint described by the assertion which is enforced
+    /// only on the asserted steps up to and including `last_step`.
+    ///
+    /// This is the same as the divisor built by [ConstraintDivisor::from_assertion()], except
+    /// that the asserted steps which come after `last_step` are exempted from the divisor. For
+    /// example, in zero-knowledge mode, periodic assertions are not enforced on the random rows
+    /// which follow the last step of the computation.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The specified `trace_length` is inconsistent with the specified `assertion`.
+    /// * The assertion is against a single step which comes after `last_step`.
+    pub fn from_assertion_up_to<E>(
+        assertion: &Assertion<E>,
+        trace_length: usize,
+        last_step: usize,
+    ) -> Self
+    where
+        E: FieldElement<BaseField = B>,
+    {
+        let mut divisor = Self::from_assertion(assertion, trace_length);
+        assertion.apply(trace_length, |step, _| {
+            if step > last_step {
+                divisor.exemptions.push(get_trace_domain_value_at::<B>(trace_length, step));
+            }
+        });
+        assert!(
+            divisor.degree() > 0,
+            "assertion {assertion} is not enforced on any step up to step {last_step}"
+        );
+        divisor
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -127,7 +256,7 @@ impl<B: StarkField> ConstraintDivisor<B> {
         // compute the numerator value
         let mut numerator = E::ONE;
         for (degree, constant) in self.numerator.iter() {
-            let v = x.exp((*degree as u32).into());
+            let v = x.exp((*degree as u64).into());
             let v = v - E::from(*constant);
             numerator *= v;
         }
@@ -138,6 +267,18 @@ impl<B: StarkField> ConstraintDivisor<B> {
         numerator / denominator
     }
 
+    /// Returns true if the divisor polynomial vanishes at the provided `x` coordinate.
+    ///
+    /// For transition constraint divisors, this means that constraints divided by this divisor
+    /// must hold at the trace step corresponding to `x`.
+    pub fn vanishes_at<E: FieldElement<BaseField = B>>(&self, x: E) -> bool {
+        let is_numerator_root = self
+            .numerator
+            .iter()
+            .any(|(degree, constant)| x.exp((*degree as u64).into()) == E::from(*constant));
+        is_numerator_root && self.evaluate_exemptions_at(x) != E::ZERO
+    }
+
     /// Evaluates the denominator of this divisor (the exemption points) at the provided `x`
     /// coordinate.
     #[inline(always)]
@@ -289,6 +430,12 @@ mod tests {
             }
         }
 
+        // the same divisor can be built for transition constraints
+        assert_eq!(
+            ConstraintDivisor::from_transition_coset(n, offset as usize, j as usize, &[]),
+            divisor
+        );
+
         // create a divisor for assertion which repeats every 4 steps starting at step 3
         let offset = 3_u32;
         let k = 2_u32;
@@ -315,4 +462,102 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn sequence_assertion_divisor_num_values_not_power_of_two() {
+        let n = 64_usize;
+        let g = BaseElement::get_root_of_unity(n.trailing_zeros());
+
+        // values at steps 3, 19, 35 are padded to 4 values; step 51 is exempted
+        let assertion = Assertion::sequence(0, 3, 16, vec![BaseElement::ONE; 3]);
+        let div = ConstraintDivisor::from_assertion(&assertion, n);
+        assert_eq!(&[(4, g.exp(4 * 3))], div.numerator());
+        assert_eq!(&[g.exp(51)], div.exemptions());
+        assert_eq!(3, div.degree());
+        assert!(div.vanishes_at(g.exp(35)));
+        assert!(!div.vanishes_at(g.exp(51)));
+    }
+
+    #[test]
+    fn assertion_divisor_up_to_last_step() {
+        let n = 64_usize;
+        let g = BaseElement::get_root_of_unity(n.trailing_zeros());
+
+        // steps 7, 15, ..., 63 with the last step at 59: step 63 is exempted
+        let assertion = Assertion::periodic(0, 7, 8, BaseElement::ONE);
+        let div = ConstraintDivisor::from_assertion_up_to(&assertion, n, 59);
+        assert_eq!(ConstraintDivisor::from_assertion(&assertion, n).numerator(), div.numerator());
+        assert_eq!(&[g.exp(63)], div.exemptions());
+        assert_eq!(7, div.degree());
+        assert!(div.vanishes_at(g.exp(55)));
+        assert!(!div.vanishes_at(g.exp(63)));
+
+        // when all asserted steps are at or before the last step, nothing is exempted
+        let assertion = Assertion::periodic(0, 0, 8, BaseElement::ONE);
+        let div = ConstraintDivisor::from_assertion_up_to(&assertion, n, 59);
+        assert_eq!(ConstraintDivisor::from_assertion(&assertion, n), div);
+    }
+
+    #[test]
+    #[should_panic(expected = "is not enforced on any step up to step 59")]
+    fn assertion_divisor_up_to_last_step_single() {
+        let assertion = Assertion::single(0, 63, BaseElement::ONE);
+        let _ = ConstraintDivisor::<BaseElement>::from_assertion_up_to(&assertion, 64, 59);
+    }
+
+    #[test]
+    fn transition_divisor_with_exemptions() {
+        let n = 16_usize;
+        let g = BaseElement::get_root_of_unity(n.trailing_zeros());
+
+        // exempting the last step is the same as the default transition divisor
+        let divisor = ConstraintDivisor::<BaseElement>::from_transition_with_exemptions(n, &[15]);
+        assert_eq!(ConstraintDivisor::from_transition(n, 1), divisor);
+
+        // the divisor vanishes on all steps except for the exempted ones
+        let divisor = ConstraintDivisor::from_transition_with_exemptions(n, &[3, 7]);
+        assert_eq!(n - 2, divisor.degree());
+        for i in 0..n {
+            let value = divisor.evaluate_at(g.exp((i as u32).into()));
+            if i == 3 || i == 7 {
+                // at exempted points, the divisor is not defined
+                continue;
+            }
+            assert_eq!(BaseElement::ZERO, value);
+        }
+        assert_ne!(BaseElement::ZERO, divisor.evaluate_at(BaseElement::new(5)));
+
+        // a divisor over a coset vanishes only on steps 1, 5, 9, and 13, except for step 5
+        let divisor = ConstraintDivisor::from_transition_coset(n, 1, 4, &[5]);
+        assert_eq!(3, divisor.degree());
+        let poly = polynom::mul(
+            &polynom::mul(&[-g, BaseElement::ONE], &[-g.exp(9), BaseElement::ONE]),
+            &[-g.exp(13), BaseElement::ONE],
+        );
+        for i in (0..n).filter(|&i| i != 5) {
+            let x = g.exp((i as u32).into());
+            assert_eq!(polynom::eval(&poly, x), divisor.evaluate_at(x));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "exempted step 6 is not one of the steps enforced by the divisor")]
+    fn transition_divisor_invalid_exemption() {
+        let _ = ConstraintDivisor::<BaseElement>::from_transition_coset(16, 1, 4, &[6]);
+    }
+
+    #[test]
+    fn transition_divisor_long_trace() {
+        // divisors of traces longer than 2^32 steps are evaluated without truncating the degree
+        let trace_length = 1 << 33;
+        let divisor = ConstraintDivisor::<BaseElement>::from_transition(trace_length, 1);
+
+        let x = BaseElement::new(3);
+        let expected = (x.exp(1 << 33) - BaseElement::ONE) / (x - divisor.exemptions()[0]);
+        assert_eq!(expected, divisor.evaluate_at(x));
+
+        let g = BaseElement::get_root_of_unity(33);
+        assert!(divisor.vanishes_at(g));
+        assert!(!divisor.vanishes_at(g.exp(trace_length as u128 - 1)));
+    }
 }
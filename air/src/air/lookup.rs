@@ -0,0 +1,593 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::{collections::BTreeMap, vec::Vec};
+
+use crypto::ElementHasher;
+use math::{batch_inversion, ExtensionOf, FieldElement, StarkField, ToElements};
+
+use super::{AccumulatorTerm, Assertion, EvaluationFrame, TransitionConstraintDegree};
+
+// LOGUP
+// ================================================================================================
+
+/// A LogUp lookup argument enforced via a running sum in a column of the auxiliary trace segment.
+///
+/// The argument proves that every value looked up in the main trace segment is contained in a
+/// lookup table, which is also described by columns of the main trace segment together with the
+/// number of times each table entry is looked up (its multiplicity). Lookups and table entries
+/// are tuples of [AccumulatorTerm]s; e.g., a range check of column 0 against a table in column 1
+/// with multiplicities in column 2 can be described as `LogUp::new(0)
+/// .with_lookup(vec![AccumulatorTerm::column(0)]).with_table(vec![AccumulatorTerm::column(1)],
+/// AccumulatorTerm::column(2))`.
+///
+/// Given random elements α and β drawn by the verifier, a tuple (v_0, ..., v_k) is reduced to
+/// a single value d = α - (v_0 + β * v_1 + ... + β^k * v_k). The running sum s is then defined as
+/// s_0 = 0 and s_{i + 1} = s_i + Σ 1 / d_{i, j} - Σ m_{i, t} / d_{i, t}, where j ranges over
+/// lookups and t over tables at step i, and m_{i, t} is the multiplicity of table entry t. The
+/// lookups are contained in the tables with high probability if s_{n - 1} = 0.
+///
+/// The random elements are read from the beginning of the auxiliary random elements: α is the
+/// first element, and β is the second element (β is needed only if some tuple contains more than
+/// one value; see [LogUp::num_rand_elements()]). The same description is used to build the
+/// running sum column (see [LogUp::build_column()]), and to evaluate its transition constraint
+/// (see [LogUp::evaluate_transition()]) and boundary constraints (see [LogUp::assertions()]).
+///
+/// Similar to transition constraints, the argument does not apply to the last step of the trace;
+/// thus, lookups and table entries at the last step are not included into the running sum, and
+/// the last row of the trace should be a padding row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogUp<B: StarkField> {
+    column: usize,
+    lookups: Vec<Vec<AccumulatorTerm<B>>>,
+    tables: Vec<(Vec<AccumulatorTerm<B>>, AccumulatorTerm<B>)>,
+}
+
+impl<B: StarkField> LogUp<B> {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new lookup argument with the running sum located in the specified column of the
+    /// auxiliary trace segment.
+    ///
+    /// The returned argument contains no lookups and no tables; these should be added via
+    /// [LogUp::with_lookup()] and [LogUp::with_table()] methods.
+    pub fn new(column: usize) -> Self {
+        Self {
+            column,
+            lookups: Vec::new(),
+            tables: Vec::new(),
+        }
+    }
+
+    /// Updates this argument to look up the specified tuple of values at every step.
+    ///
+    /// # Panics
+    /// Panics if `values` is empty.
+    pub fn with_lookup(mut self, values: Vec<AccumulatorTerm<B>>) -> Self {
+        assert!(!values.is_empty(), "a lookup must contain at least one value");
+        self.lookups.push(values);
+        self
+    }
+
+    /// Updates this argument with a table containing the specified tuple of values at every step;
+    /// `multiplicity` describes the number of times the entry at a given step is looked up.
+    ///
+    /// # Panics
+    /// Panics if `values` is empty.
+    pub fn with_table(
+        mut self,
+        values: Vec<AccumulatorTerm<B>>,
+        multiplicity: AccumulatorTerm<B>,
+    ) -> Self {
+        assert!(!values.is_empty(), "a table must contain at least one value");
+        self.tables.push((values, multiplicity));
+        self
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the index of the auxiliary column containing the running sum of this argument.
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// Returns the number of random elements needed by this argument.
+    pub fn num_rand_elements(&self) -> usize {
+        let max_width = self
+            .lookups
+            .iter()
+            .chain(self.tables.iter().map(|(values, _)| values))
+            .map(Vec::len)
+            .max()
+            .unwrap_or(1);
+        if max_width > 1 {
+            2
+        } else {
+            1
+        }
+    }
+
+    /// Returns the degree of the transition constraint enforcing this argument.
+    ///
+    /// The constraint is obtained by multiplying the running sum update by all denominators;
+    /// thus, its degree grows with the number of lookups and tables.
+    pub fn transition_degree(&self) -> TransitionConstraintDegree {
+        let fractions = self.fractions();
+        let den_degrees: Vec<usize> = fractions.iter().map(|(_, den)| den.degree()).collect();
+        let den_degree_sum: usize = den_degrees.iter().sum();
+        let degree = fractions
+            .iter()
+            .zip(den_degrees.iter())
+            .map(|((num, _), den_degree)| num.degree() + den_degree_sum - den_degree)
+            .fold(den_degree_sum + 1, usize::max);
+        TransitionConstraintDegree::new(degree)
+    }
+
+    /// Returns assertions against the first and the last steps of the running sum column for an
+    /// execution trace of the specified length.
+    pub fn assertions<E: FieldElement>(&self, trace_length: usize) -> Vec<Assertion<E>> {
+        vec![
+            Assertion::single(self.column, 0, E::ZERO),
+            Assertion::single(self.column, trace_length - 1, E::ZERO),
+        ]
+    }
+
+    // TRACE BUILDING
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns values of the running sum column for an execution trace with the specified number
+    /// of rows.
+    ///
+    /// Rows of the main trace segment are read via the `read_row` function, which must write the
+    /// row at the specified step into the provided slice of length `row_width`.
+    ///
+    /// # Panics
+    /// Panics if a lookup or a table entry reduces to zero at any step (except the last one).
+    pub fn build_column<F, E>(
+        &self,
+        num_rows: usize,
+        row_width: usize,
+        rand_elements: &[E],
+        mut read_row: impl FnMut(usize, &mut [F]),
+    ) -> Vec<E>
+    where
+        F: FieldElement<BaseField = B>,
+        E: FieldElement<BaseField = B> + ExtensionOf<F>,
+    {
+        // evaluate all fractions at all steps except the last one; fractions at the last step do
+        // not contribute to the running sum
+        let fractions = self.fractions();
+        let num_steps = num_rows.saturating_sub(1);
+        let mut row = vec![F::ZERO; row_width];
+        let mut numerators = Vec::with_capacity(num_steps * fractions.len());
+        let mut denominators = Vec::with_capacity(num_steps * fractions.len());
+        for step in 0..num_steps {
+            read_row(step, &mut row);
+            for (num, den) in fractions.iter() {
+                numerators.push(num.evaluate(&row, rand_elements));
+                denominators.push(den.evaluate(&row, rand_elements));
+            }
+        }
+
+        assert!(
+            denominators.iter().all(|&den| den != E::ZERO),
+            "lookup denominator must not evaluate to zero"
+        );
+        let inv_denominators = batch_inversion(&denominators);
+
+        let mut result = Vec::with_capacity(num_rows);
+        let mut acc = E::ZERO;
+        result.push(acc);
+        let terms: Vec<E> =
+            numerators.iter().zip(inv_denominators).map(|(&num, inv)| num * inv).collect();
+        for step_terms in terms.chunks(fractions.len().max(1)) {
+            acc += step_terms.iter().fold(E::ZERO, |sum, &term| sum + term);
+            result.push(acc);
+        }
+        result.resize(num_rows, acc);
+
+        result
+    }
+
+    // CONSTRAINT EVALUATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Evaluates the transition constraint of this argument.
+    ///
+    /// `current` and `next` are the values of the running sum column in the current and the next
+    /// rows of the evaluation frame, and lookups and tables are evaluated against the current row
+    /// of `main_frame`. The constraint is (s_{i + 1} - s_i) * Π d_k - Σ n_k * Π_{l != k} d_l = 0,
+    /// where n_k / d_k are the terms added to the running sum at step i.
+    pub fn evaluate_transition<F, E>(
+        &self,
+        main_frame: &EvaluationFrame<F>,
+        current: E,
+        next: E,
+        rand_elements: &[E],
+    ) -> E
+    where
+        F: FieldElement<BaseField = B>,
+        E: FieldElement<BaseField = B> + ExtensionOf<F>,
+    {
+        let row = main_frame.current();
+        let fractions = self.fractions();
+
+        // compute products of all denominators except the k-th one via prefix and suffix products
+        let denominators: Vec<E> =
+            fractions.iter().map(|(_, den)| den.evaluate(row, rand_elements)).collect();
+        let mut suffix_products = vec![E::ONE; denominators.len() + 1];
+        for (k, &den) in denominators.iter().enumerate().rev() {
+            suffix_products[k] = suffix_products[k + 1] * den;
+        }
+
+        let mut prefix_product = E::ONE;
+        let mut numerator = E::ZERO;
+        for (k, (num, _)) in fractions.iter().enumerate() {
+            numerator += num.evaluate(row, rand_elements) * prefix_product * suffix_products[k + 1];
+            prefix_product *= denominators[k];
+        }
+
+        (next - current) * suffix_products[0] - numerator
+    }
+
+    // HELPER FUNCTIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns numerators and denominators of the terms added to the running sum at every step.
+    fn fractions(&self) -> Vec<(AccumulatorTerm<B>, AccumulatorTerm<B>)> {
+        let lookups = self
+            .lookups
+            .iter()
+            .map(|values| (AccumulatorTerm::constant(B::ONE), reduce_tuple(values)));
+        let tables = self.tables.iter().map(|(values, multiplicity)| {
+            (AccumulatorTerm::constant(B::ZERO) - multiplicity.clone(), reduce_tuple(values))
+        });
+        lookups.chain(tables).collect()
+    }
+}
+
+// LOOKUP TABLE
+// ================================================================================================
+
+/// A fixed lookup table which is known to both the prover and the verifier.
+///
+/// A table consists of one or more columns of values of the same length; a row of the table is
+/// a table entry. The AIR makes the table available to its constraints via periodic columns (see
+/// [LookupTable::get_periodic_columns()]), and thus, the table is never committed to by the
+/// prover. Instead, tables returned from [Air::get_lookup_tables()] are committed to once by
+/// hashing them (see [LookupTable::commitment()]); the commitment is carried in the proof context
+/// and the verifier checks that it matches the tables of its AIR.
+///
+/// To prove that trace values are contained in a table, the AIR usually copies the table into
+/// columns of the main trace segment (enforced by transition constraints against the periodic
+/// columns), and looks up the values in these columns via a [LogUp] argument, with the number of
+/// lookups of each entry provided in another column (see [LookupTable::get_multiplicities()]).
+/// Columns containing a table repeat every [length()](LookupTable::length) steps, and thus, have
+/// lower degree than other trace columns; the degrees of constraints involving these columns
+/// should be defined via [TransitionConstraintDegree::with_cycles()]. This does not apply in
+/// zero-knowledge mode, where random rows appended to the trace give these columns full degree.
+///
+/// The length of a table must be a power of two which is at least 2 and is smaller than the
+/// length of the execution trace; the table repeats every [length()](LookupTable::length) steps.
+///
+/// [Air::get_lookup_tables()]: crate::Air::get_lookup_tables
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LookupTable<B: StarkField> {
+    columns: Vec<Vec<B>>,
+}
+
+impl<B: StarkField> LookupTable<B> {
+    // CONSTANTS
+    // --------------------------------------------------------------------------------------------
+    /// Maximum number of columns in a lookup table; currently set at 255.
+    pub const MAX_NUM_COLUMNS: usize = 255;
+
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new lookup table consisting of the specified columns.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// - `columns` is empty or contains more than 255 columns.
+    /// - Not all columns have the same length.
+    /// - The length of the columns is smaller than 2 or is not a power of two.
+    pub fn new(columns: Vec<Vec<B>>) -> Self {
+        assert!(!columns.is_empty(), "a lookup table must consist of at least one column");
+        assert!(
+            columns.len() <= Self::MAX_NUM_COLUMNS,
+            "a lookup table cannot consist of more than {} columns, but was {}",
+            Self::MAX_NUM_COLUMNS,
+            columns.len()
+        );
+        let len = columns[0].len();
+        assert!(
+            columns.iter().all(|column| column.len() == len),
+            "all lookup table columns must have the same length"
+        );
+        assert!(len >= 2, "lookup table length must be at least 2, but was {len}");
+        assert!(
+            len.is_power_of_two(),
+            "lookup table length must be a power of two, but was {len}"
+        );
+
+        Self { columns }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of columns in this table.
+    pub fn num_columns(&self) -> usize {
+        self.columns.len()
+    }
+
+    /// Returns the number of entries in this table.
+    pub fn length(&self) -> usize {
+        self.columns[0].len()
+    }
+
+    /// Returns the columns of this table.
+    pub fn columns(&self) -> &[Vec<B>] {
+        &self.columns
+    }
+
+    /// Returns a commitment to the specified tables computed using the specified hash function.
+    ///
+    /// The commitment binds the number of tables, as well as the dimensions and all values of
+    /// each table.
+    pub fn commitment<H: ElementHasher<BaseField = B>>(tables: &[Self]) -> H::Digest {
+        let mut elements = vec![B::from(tables.len() as u32)];
+        for table in tables {
+            elements.append(&mut table.to_elements());
+        }
+        H::hash_elements(&elements)
+    }
+
+    /// Returns table columns in the form expected by
+    /// [Air::get_periodic_column_values()](crate::Air::get_periodic_column_values).
+    pub fn get_periodic_columns(&self) -> Vec<Vec<B>> {
+        self.columns.clone()
+    }
+
+    // TRACE BUILDING
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a column of the specified length with the number of times each entry of this table
+    /// is looked up by the specified `lookups`.
+    ///
+    /// The multiplicity of the i-th table entry is placed at step i; all other steps (i.e., the
+    /// steps at which the table repeats) have zero multiplicity. Each lookup must have the same
+    /// number of values as the number of table columns.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// - `trace_length` is not greater than the length of this table.
+    /// - Any of the lookups is not an entry of this table.
+    pub fn get_multiplicities<'a>(
+        &self,
+        trace_length: usize,
+        lookups: impl IntoIterator<Item = &'a [B]>,
+    ) -> Vec<B>
+    where
+        B: 'a,
+    {
+        assert!(
+            trace_length > self.length(),
+            "trace length must be greater than lookup table length {}, but was {}",
+            self.length(),
+            trace_length
+        );
+
+        // map each table entry to its index
+        let mut entry = vec![B::ZERO; self.num_columns()];
+        let mut entries = BTreeMap::new();
+        for i in (0..self.length()).rev() {
+            for (value, column) in entry.iter_mut().zip(self.columns.iter()) {
+                *value = column[i];
+            }
+            entries.insert(B::elements_as_bytes(&entry).to_vec(), i);
+        }
+
+        let mut result = vec![B::ZERO; trace_length];
+        for lookup in lookups {
+            let index = entries
+                .get(B::elements_as_bytes(lookup))
+                .expect("looked up values are not an entry of the lookup table");
+            result[*index] += B::ONE;
+        }
+
+        result
+    }
+}
+
+impl<B: StarkField> ToElements<B> for LookupTable<B> {
+    fn to_elements(&self) -> Vec<B> {
+        let mut result = Vec::with_capacity(2 + self.num_columns() * self.length());
+        result.push(B::from(self.num_columns() as u32));
+        result.push(B::from(self.length() as u32));
+        for column in self.columns.iter() {
+            result.extend_from_slice(column);
+        }
+        result
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns a term describing α - (v_0 + β * v_1 + ... + β^k * v_k) for the specified tuple of
+/// values, where α and β are the first and the second random elements.
+pub(super) fn reduce_tuple<B: StarkField>(values: &[AccumulatorTerm<B>]) -> AccumulatorTerm<B> {
+    let value = values
+        .iter()
+        .rev()
+        .cloned()
+        .reduce(|acc, value| value + AccumulatorTerm::rand(1) * acc)
+        .expect("tuple must contain at least one value");
+    AccumulatorTerm::rand(0) - value
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use math::{
+        fields::{f64::BaseElement, QuadExtension},
+        FieldElement,
+    };
+
+    use crypto::hashers::Blake3_256;
+
+    use super::{AccumulatorTerm, EvaluationFrame, LogUp, LookupTable, TransitionConstraintDegree};
+
+    type E2 = QuadExtension<BaseElement>;
+
+    /// Builds a trace where column 0 contains looked up values, column 1 contains a table of
+    /// values 0..8, and column 2 contains multiplicities of the table entries; the last row is
+    /// a padding row.
+    fn build_trace() -> Vec<Vec<BaseElement>> {
+        let lookups = [3_u32, 1, 4, 1, 5, 1, 2, 6, 5, 3, 5, 0, 0, 7, 7, 0];
+        (0..16_u32)
+            .map(|i| {
+                let multiplicity = lookups[..15].iter().filter(|&&v| v == i).count() as u32;
+                let table_value = if i < 8 { i } else { 0 };
+                let multiplicity = if i < 8 { multiplicity } else { 0 };
+                vec![lookups[i as usize].into(), table_value.into(), multiplicity.into()]
+            })
+            .collect()
+    }
+
+    fn build_column(
+        logup: &LogUp<BaseElement>,
+        trace: &[Vec<BaseElement>],
+        rand: &[E2],
+    ) -> Vec<E2> {
+        logup.build_column(trace.len(), 3, rand, |step, row: &mut [BaseElement]| {
+            row.copy_from_slice(&trace[step])
+        })
+    }
+
+    fn check_transitions(logup: &LogUp<BaseElement>, trace: &[Vec<BaseElement>], rand: &[E2]) {
+        let column = build_column(logup, trace, rand);
+        assert_eq!(trace.len(), column.len());
+        assert_eq!(E2::ZERO, column[0]);
+
+        for step in 0..trace.len() - 1 {
+            let frame = EvaluationFrame::from_rows(trace[step].clone(), trace[step + 1].clone());
+            let evaluation =
+                logup.evaluate_transition(&frame, column[step], column[step + 1], rand);
+            assert_eq!(E2::ZERO, evaluation);
+
+            // a different value in the next row violates the constraint
+            let next = column[step + 1] + E2::ONE;
+            let evaluation = logup.evaluate_transition(&frame, column[step], next, rand);
+            assert_ne!(E2::ZERO, evaluation);
+        }
+    }
+
+    #[test]
+    fn logup_range_check() {
+        let trace = build_trace();
+        let rand = [E2::new(BaseElement::from(1234_u32), BaseElement::from(5_u32))];
+
+        let logup = LogUp::new(0)
+            .with_lookup(vec![AccumulatorTerm::column(0)])
+            .with_table(vec![AccumulatorTerm::column(1)], AccumulatorTerm::column(2));
+        assert_eq!(1, logup.num_rand_elements());
+        assert_eq!(TransitionConstraintDegree::new(3), logup.transition_degree());
+        check_transitions(&logup, &trace, &rand);
+
+        // all looked up values are in the table, and thus, the running sum ends at zero
+        let column = build_column(&logup, &trace, &rand);
+        assert_eq!(E2::ZERO, column[trace.len() - 1]);
+        let assertions = logup.assertions::<E2>(trace.len());
+        assert_eq!(trace.len() - 1, assertions[1].first_step());
+
+        // a value which is not in the table results in a non-zero running sum
+        let mut trace = trace;
+        trace[4][0] = BaseElement::from(9_u32);
+        check_transitions(&logup, &trace, &rand);
+        let column = build_column(&logup, &trace, &rand);
+        assert_ne!(E2::ZERO, column[trace.len() - 1]);
+    }
+
+    #[test]
+    fn logup_tuples() {
+        let trace = build_trace();
+        let rand = [
+            E2::new(BaseElement::from(1234_u32), BaseElement::from(5_u32)),
+            E2::new(BaseElement::from(77_u32), BaseElement::from(3_u32)),
+        ];
+
+        // look up pairs (v, v + 1) in a table of pairs (t, t + 1)
+        let one = AccumulatorTerm::constant(BaseElement::ONE);
+        let logup = LogUp::new(0)
+            .with_lookup(vec![AccumulatorTerm::column(0), AccumulatorTerm::column(0) + one.clone()])
+            .with_table(
+                vec![AccumulatorTerm::column(1), AccumulatorTerm::column(1) + one],
+                AccumulatorTerm::column(2),
+            );
+        assert_eq!(2, logup.num_rand_elements());
+        check_transitions(&logup, &trace, &rand);
+        let column = build_column(&logup, &trace, &rand);
+        assert_eq!(E2::ZERO, column[trace.len() - 1]);
+
+        // pairs which are not in the table result in a non-zero running sum
+        let logup = LogUp::new(0)
+            .with_lookup(vec![AccumulatorTerm::column(0), AccumulatorTerm::column(0)])
+            .with_table(
+                vec![AccumulatorTerm::column(1), AccumulatorTerm::column(1)],
+                AccumulatorTerm::column(2),
+            )
+            .with_lookup(vec![AccumulatorTerm::column(0), AccumulatorTerm::column(1)]);
+        assert_eq!(TransitionConstraintDegree::new(4), logup.transition_degree());
+        check_transitions(&logup, &trace, &rand);
+        let column = build_column(&logup, &trace, &rand);
+        assert_ne!(E2::ZERO, column[trace.len() - 1]);
+    }
+
+    #[test]
+    fn lookup_table_multiplicities() {
+        let trace = build_trace();
+        let table = LookupTable::new(vec![(0..8_u32).map(BaseElement::from).collect()]);
+
+        // multiplicities match the column of the test trace; the last row is not looked up
+        let lookups = trace[..trace.len() - 1].iter().map(|row| &row[..1]);
+        let expected: Vec<BaseElement> = trace.iter().map(|row| row[2]).collect();
+        assert_eq!(expected, table.get_multiplicities(trace.len(), lookups));
+    }
+
+    #[test]
+    #[should_panic(expected = "not an entry of the lookup table")]
+    fn lookup_table_missing_entry() {
+        let table = LookupTable::new(vec![(0..8_u32).map(BaseElement::from).collect()]);
+        let lookups = [[BaseElement::from(9_u32)]];
+        table.get_multiplicities(16, lookups.iter().map(|row| &row[..]));
+    }
+
+    #[test]
+    fn lookup_table_commitment() {
+        type Hasher = Blake3_256<BaseElement>;
+
+        let table = LookupTable::new(vec![(0..8_u32).map(BaseElement::from).collect()]);
+        let other_table = LookupTable::new(vec![(1..9_u32).map(BaseElement::from).collect()]);
+        let wide_table = LookupTable::new(vec![
+            (0..4_u32).map(BaseElement::from).collect(),
+            (4..8_u32).map(BaseElement::from).collect(),
+        ]);
+
+        let same_table = LookupTable::new(vec![(0..8_u32).map(BaseElement::from).collect()]);
+        let commitment = LookupTable::commitment::<Hasher>(core::slice::from_ref(&table));
+        assert_eq!(commitment, LookupTable::commitment::<Hasher>(&[same_table]));
+        assert_ne!(commitment, LookupTable::commitment::<Hasher>(&[other_table]));
+        assert_ne!(commitment, LookupTable::commitment::<Hasher>(&[wide_table]));
+        assert_ne!(commitment, LookupTable::commitment::<Hasher>(&[table.clone(), table]));
+    }
+}
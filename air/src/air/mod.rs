@@ -6,7 +6,7 @@
 use alloc::{collections::BTreeMap, vec::Vec};
 
 use crypto::{RandomCoin, RandomCoinError};
-use math::{fft, ExtensibleField, ExtensionOf, FieldElement, StarkField, ToElements};
+use math::{fft, ExtensibleField, ExtensionOf, FieldElement, StarkField};
 
 use crate::ProofOptions;
 
@@ -26,7 +26,11 @@ mod boundary;
 pub use boundary::{BoundaryConstraint, BoundaryConstraintGroup, BoundaryConstraints};
 
 mod transition;
-pub use transition::{EvaluationFrame, TransitionConstraintDegree, TransitionConstraints};
+pub(crate) use transition::validate_frame_offsets;
+pub use transition::{
+    ConstraintExpr, EvaluationFrame, ExprNode, NodeId, SymbolicConstraints, SymbolicNode,
+    TransitionConstraintDegree, TransitionConstraints,
+};
 
 mod lagrange;
 pub use lagrange::{
@@ -42,6 +46,29 @@ pub use coefficients::{
 
 mod divisor;
 pub use divisor::ConstraintDivisor;
+
+mod program;
+pub use program::Program;
+
+mod queries;
+
+mod accumulator;
+pub use accumulator::{Accumulator, AccumulatorKind, AccumulatorTerm};
+
+mod lookup;
+pub use lookup::{LogUp, LookupTable};
+
+mod permutation;
+pub use permutation::Permutation;
+
+mod public_inputs;
+pub use public_inputs::PublicInputs;
+
+mod batch;
+pub use batch::{BatchAir, BatchPublicInputs};
+
+mod composed;
+pub use composed::{ComposedAir, ComposedPublicInputs};
 use utils::{Deserializable, Serializable};
 
 #[cfg(test)]
@@ -84,7 +111,8 @@ const MIN_CYCLE_LENGTH: usize = 2;
 /// [Air::evaluate_transition()] function which takes the following parameters:
 ///
 /// - [EvaluationFrame] which contains vectors with current and next states of the
-///   computation.
+///   computation. Wider frames can be requested via [AirContext::set_num_frame_rows()]
+///   and [AirContext::set_frame_offsets()].
 /// - A list of periodic values. When periodic columns are defined for a computation,
 ///   this will contain values of periodic columns at the current step of the computation.
 ///   Otherwise, this will be an empty list.
@@ -126,6 +154,13 @@ const MIN_CYCLE_LENGTH: usize = 2;
 /// In general, multiplications should be used judiciously - though, there are ways to ease this
 /// restriction a bit at the expense of wider execution trace.
 ///
+/// Instead of evaluating transition constraints in [Air::evaluate_transition()] and describing
+/// their degrees manually, transition constraints over the main trace segment can be described
+/// symbolically via [ConstraintExpr] expressions collected into [SymbolicConstraints]. In this
+/// case, [AirContext::from_symbolic_constraints()] infers constraint degrees from the
+/// expressions, [Air::get_symbolic_constraints()] should return the constraints, and
+/// [Air::evaluate_transition()] can evaluate them via [SymbolicConstraints::evaluate()].
+///
 /// ### Trace assertions
 /// Assertions are used to specify that a valid execution trace of a computation must contain
 /// certain values in certain cells. They are frequently used to tie public inputs to a specific
@@ -158,6 +193,12 @@ const MIN_CYCLE_LENGTH: usize = 2;
 /// step of the computation will be supplied to the [Air::evaluate_transition()] method via the
 /// `periodic_values` parameter.
 ///
+/// Since the length of an execution trace is always a power of two, the number of values in a
+/// periodic column must be a power of two as well. A sequence of values whose length is not a
+/// power of two can be padded with zeros to the next power of two via [pad_periodic_column()].
+/// For example, a column of round constants for a hash function with 10 rounds padded in this
+/// way repeats every 16 steps, and contains zeros at the last 6 steps of each cycle.
+///
 /// ### Randomized AIR
 /// Randomized AIR is a powerful extension of AIR which enables, among other things, multiset and
 /// permutation checks similar to the ones available in PLONKish systems. These, in turn, allow
@@ -192,7 +233,10 @@ pub trait Air: Send + Sync {
 
     /// A type defining shape of public inputs for the computation described by this protocol.
     /// This could be any type as long as it can be serialized into a sequence of field elements.
-    type PublicInputs: ToElements<Self::BaseField> + Send;
+    ///
+    /// The elements are used to seed the public coin of the protocol; canonical byte encoding and
+    /// digest of public inputs are derived from them via the [PublicInputs] trait.
+    type PublicInputs: PublicInputs<Self::BaseField> + Send;
 
     /// An GKR proof object. If not needed, set to `()`.
     type GkrProof: Serializable + Deserializable + Send;
@@ -200,6 +244,14 @@ pub trait Air: Send + Sync {
     /// A verifier for verifying GKR proofs. If not needed, set to `()`.
     type GkrVerifier: GkrVerifier<GkrProof = Self::GkrProof>;
 
+    /// An optional identifier of the computation described by this AIR.
+    ///
+    /// If set, the identifier is bound into the context of every proof generated for this AIR,
+    /// and the verifier rejects proofs whose context carries a different identifier. Identifiers
+    /// also allow a verifier to determine which of several computations a proof was generated
+    /// for. Defaults to `None`.
+    const ID: Option<u32> = None;
+
     // REQUIRED METHODS
     // --------------------------------------------------------------------------------------------
 
@@ -226,6 +278,10 @@ pub trait Air: Send + Sync {
     /// We define type `E` separately from `Self::BaseField` to allow evaluation of constraints
     /// over the out-of-domain evaluation frame, which may be defined over an extension field
     /// (when extension fields are used).
+    ///
+    /// AIRs which describe their transition constraints symbolically (see
+    /// [Air::get_symbolic_constraints()]) can implement this method by calling
+    /// [SymbolicConstraints::evaluate()].
     fn evaluate_transition<E: FieldElement<BaseField = Self::BaseField>>(
         &self,
         frame: &EvaluationFrame<E>,
@@ -359,11 +415,56 @@ pub trait Air: Send + Sync {
     ///
     /// The default implementation of this method returns an empty vector. For computations which
     /// rely on periodic columns, this method should be overridden in the specialized
-    /// implementation. Number of values for each periodic column must be a power of two.
+    /// implementation. Number of values for each periodic column must be a power of two; columns
+    /// with other numbers of values can be padded via [pad_periodic_column()].
     fn get_periodic_column_values(&self) -> Vec<Vec<Self::BaseField>> {
         Vec::new()
     }
 
+    /// Returns the program executed by the computation described by this AIR.
+    ///
+    /// This is relevant for AIRs describing virtual machines which can execute arbitrary
+    /// programs. A verifier can use the returned program to make sure a proof attests to the
+    /// execution of a specific program (see [Program::hash()]).
+    ///
+    /// The default implementation of this method returns `None`. AIRs which execute programs
+    /// should return the program (usually, taken from the public inputs) and should include its
+    /// columns in the values returned from
+    /// [get_periodic_column_values()](Air::get_periodic_column_values).
+    fn get_program(&self) -> Option<&Program<Self::BaseField>> {
+        None
+    }
+
+    /// Returns fixed lookup tables used by the computation described by this AIR.
+    ///
+    /// The prover commits to the returned tables in the proof context (see
+    /// [LookupTable::commitment()]), and the verifier rejects proofs which were generated
+    /// against different tables.
+    ///
+    /// The default implementation of this method returns an empty slice. AIRs which use lookup
+    /// tables should include their columns in the values returned from
+    /// [get_periodic_column_values()](Air::get_periodic_column_values).
+    fn get_lookup_tables(&self) -> &[LookupTable<Self::BaseField>] {
+        &[]
+    }
+
+    /// Returns symbolic representation of transition constraints over the main segment of the
+    /// execution trace.
+    ///
+    /// When symbolic constraints are provided, the prover lowers them into a sequence of register
+    /// instructions and interprets these instructions over batches of rows of the constraint
+    /// evaluation domain instead of calling [Air::evaluate_transition()] for every step. This is
+    /// much faster than evaluating the symbolic constraints row by row, but a hand-written
+    /// [Air::evaluate_transition()] may still be faster.
+    ///
+    /// The symbolic constraints must describe exactly the same constraints as
+    /// [Air::evaluate_transition()]; the verifier still relies on the latter.
+    ///
+    /// The default implementation of this method returns `None`.
+    fn get_symbolic_constraints(&self) -> Option<&SymbolicConstraints<Self::BaseField>> {
+        None
+    }
+
     /// Returns polynomial for all periodic columns.
     ///
     /// These polynomials are interpolated from the values returned from the
@@ -458,6 +559,15 @@ pub trait Air: Send + Sync {
         self.context().trace_info.length()
     }
 
+    /// Returns the index of the last step of the computation described by this AIR.
+    ///
+    /// This is `trace_length - 1`, except in zero-knowledge mode, where random rows appended to
+    /// the trace follow the last step of the computation. Assertions against the final state of
+    /// the computation should be placed at this step.
+    fn last_step(&self) -> usize {
+        self.trace_length() - self.context().num_random_rows() - 1
+    }
+
     /// Returns degree of trace polynomials for an instance of the computation described by
     /// this AIR.
     ///
@@ -477,11 +587,12 @@ pub trait Air: Send + Sync {
     /// Returns constraint evaluation domain blowup factor for the computation described by this
     /// AIR.
     ///
-    /// The blowup factor is defined as the smallest power of two greater than or equal to the
-    /// hightest transition constraint degree. For example, if the hightest transition
-    /// constraint degree = 3, `ce_blowup_factor` will be set to 4.
+    /// By default, the blowup factor is defined as the smallest power of two greater than or equal
+    /// to the hightest transition constraint degree. For example, if the hightest transition
+    /// constraint degree = 3, `ce_blowup_factor` will be set to 4. A larger blowup factor can be
+    /// set via [AirContext::set_ce_blowup_factor()].
     ///
-    /// `ce_blowup_factor` is guaranteed to be smaller than or equal to the `lde_blowup_factor`.
+    /// `ce_blowup_factor` is independent of the `lde_blowup_factor`, but is guaranteed to be smaller than or equal to the `lde_blowup_factor`.
     fn ce_blowup_factor(&self) -> usize {
         self.context().ce_blowup_factor
     }
@@ -527,6 +638,10 @@ pub trait Air: Send + Sync {
 
     /// Returns coefficients needed for random linear combination during construction of constraint
     /// composition polynomial.
+    ///
+    /// Depending on the [CoefficientDerivation](crate::CoefficientDerivation) specified in proof
+    /// options, the coefficients are either drawn from the public coin one by one, or are all
+    /// expanded from a single seed drawn from the public coin.
     fn get_constraint_composition_coefficients<E, R>(
         &self,
         public_coin: &mut R,
@@ -535,23 +650,28 @@ pub trait Air: Send + Sync {
         E: FieldElement<BaseField = Self::BaseField>,
         R: RandomCoin<BaseField = Self::BaseField>,
     {
-        let mut t_coefficients = Vec::new();
-        for _ in 0..self.context().num_transition_constraints() {
-            t_coefficients.push(public_coin.draw()?);
-        }
+        let num_t_coefficients = self.context().num_transition_constraints();
+        let num_b_coefficients = self.context().num_assertions();
+        let num_lagrange_coefficients = if self.context().has_lagrange_kernel_aux_column() {
+            self.context().trace_len().ilog2() as usize + 1
+        } else {
+            0
+        };
 
-        let mut b_coefficients = Vec::new();
-        for _ in 0..self.context().num_assertions() {
-            b_coefficients.push(public_coin.draw()?);
-        }
+        let mut coefficients = coefficients::draw_coefficients(
+            public_coin,
+            self.options().coefficient_derivation(),
+            num_t_coefficients + num_b_coefficients + num_lagrange_coefficients,
+        )?
+        .into_iter();
 
-        let lagrange = if self.context().has_lagrange_kernel_aux_column() {
-            let mut lagrange_kernel_t_coefficients = Vec::new();
-            for _ in 0..self.context().trace_len().ilog2() {
-                lagrange_kernel_t_coefficients.push(public_coin.draw()?);
-            }
+        let t_coefficients = coefficients.by_ref().take(num_t_coefficients).collect();
+        let b_coefficients = coefficients.by_ref().take(num_b_coefficients).collect();
 
-            let lagrange_kernel_boundary = public_coin.draw()?;
+        let lagrange = if self.context().has_lagrange_kernel_aux_column() {
+            let lagrange_kernel_t_coefficients =
+                coefficients.by_ref().take(num_lagrange_coefficients - 1).collect();
+            let lagrange_kernel_boundary = coefficients.next().expect("missing coefficient");
 
             Some(LagrangeConstraintsCompositionCoefficients {
                 transition: lagrange_kernel_t_coefficients,
@@ -570,6 +690,10 @@ pub trait Air: Send + Sync {
 
     /// Returns coefficients needed for random linear combinations during construction of DEEP
     /// composition polynomial.
+    ///
+    /// Depending on the [CoefficientDerivation](crate::CoefficientDerivation) specified in proof
+    /// options, the coefficients are either drawn from the public coin one by one, or are all
+    /// expanded from a single seed drawn from the public coin.
     fn get_deep_composition_coefficients<E, R>(
         &self,
         public_coin: &mut R,
@@ -578,21 +702,20 @@ pub trait Air: Send + Sync {
         E: FieldElement<BaseField = Self::BaseField>,
         R: RandomCoin<BaseField = Self::BaseField>,
     {
-        let mut t_coefficients = Vec::new();
-        for _ in 0..self.trace_info().width() {
-            t_coefficients.push(public_coin.draw()?);
-        }
+        let num_t_coefficients = self.trace_info().width();
+        let num_c_coefficients = self.context().num_constraint_composition_columns();
+        let num_lagrange_coefficients = self.context().has_lagrange_kernel_aux_column() as usize;
 
-        let mut c_coefficients = Vec::new();
-        for _ in 0..self.context().num_constraint_composition_columns() {
-            c_coefficients.push(public_coin.draw()?);
-        }
+        let mut coefficients = coefficients::draw_coefficients(
+            public_coin,
+            self.options().coefficient_derivation(),
+            num_t_coefficients + num_c_coefficients + num_lagrange_coefficients,
+        )?
+        .into_iter();
 
-        let lagrange_cc = if self.context().has_lagrange_kernel_aux_column() {
-            Some(public_coin.draw()?)
-        } else {
-            None
-        };
+        let t_coefficients = coefficients.by_ref().take(num_t_coefficients).collect();
+        let c_coefficients = coefficients.by_ref().take(num_c_coefficients).collect();
+        let lagrange_cc = coefficients.next();
 
         Ok(DeepCompositionCoefficients {
             trace: t_coefficients,
@@ -600,4 +723,60 @@ pub trait Air: Send + Sync {
             lagrange: lagrange_cc,
         })
     }
+
+    // QUERY POSITIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns positions in the LDE domain against which the evaluations of trace and constraint
+    /// composition polynomials should be queried. The positions are drawn from the public coin
+    /// uniformly at random after the coin is reseeded with the specified `nonce`; the returned
+    /// positions may contain duplicates.
+    ///
+    /// If proof options require positions which map onto points of the trace domain to be
+    /// excluded from query sampling (see [ProofOptions::with_trace_domain_query_exclusion()]),
+    /// such positions are never returned.
+    fn get_query_positions<R>(
+        &self,
+        public_coin: &mut R,
+        nonce: u64,
+    ) -> Result<Vec<usize>, RandomCoinError>
+    where
+        R: RandomCoin<BaseField = Self::BaseField>,
+    {
+        let blowup_factor = self.lde_blowup_factor();
+        let excluded_residue = if self.options().excludes_trace_domain_queries() {
+            queries::get_trace_domain_residue(
+                self.trace_length(),
+                blowup_factor,
+                self.domain_offset(),
+            )
+        } else {
+            None
+        };
+
+        queries::draw_query_positions(
+            public_coin,
+            self.options().num_queries(),
+            self.lde_domain_size(),
+            blowup_factor,
+            excluded_residue,
+            nonce,
+        )
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Pads the values of a periodic column with zeros to the next power of two.
+///
+/// This can be used in [Air::get_periodic_column_values()] to define periodic columns by
+/// sequences of values whose length is not a power of two. The period of the padded column is
+/// the next power of two, and the column is equal to zero at the padded steps of every cycle. For
+/// example, 10 round constants are padded to a column with a period of 16 steps, which is zero at
+/// the last 6 steps of each cycle. Degrees of transition constraints involving such columns can
+/// be described via [TransitionConstraintDegree::with_padded_cycles()].
+pub fn pad_periodic_column<B: StarkField>(mut values: Vec<B>) -> Vec<B> {
+    values.resize(values.len().next_power_of_two(), B::ZERO);
+    values
 }
@@ -0,0 +1,284 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::vec::Vec;
+
+use math::{ExtensionOf, FieldElement, StarkField};
+
+use super::{
+    lookup::reduce_tuple, Accumulator, AccumulatorTerm, Assertion, EvaluationFrame,
+    TransitionConstraintDegree,
+};
+
+// PERMUTATION
+// ================================================================================================
+
+/// A multiset equality argument enforced via a running product in a column of the auxiliary trace
+/// segment.
+///
+/// The argument proves that the rows of one set of columns of the main trace segment are a
+/// permutation of the rows of another set of columns (e.g., that a memory access log sorted by
+/// address contains the same accesses as the log in execution order). Each set is described by
+/// a tuple of [AccumulatorTerm]s of the same width; e.g., a permutation of columns (0, 1) into
+/// columns (2, 3) can be described as `Permutation::from_columns(0, &[0, 1], &[2, 3])`.
+///
+/// Given random elements α and β drawn by the verifier, a tuple (v_0, ..., v_k) is reduced to
+/// a single value α - (v_0 + β * v_1 + ... + β^k * v_k), and the running product p is defined as
+/// p_0 = 1 and p_{i + 1} = p_i * l_i / r_i, where l_i and r_i are the reduced left and right
+/// tuples at step i. The left tuples are a permutation of the right tuples with high probability
+/// if p_{n - 1} = 1.
+///
+/// The random elements are read from the beginning of the auxiliary random elements: α is the
+/// first element, and β is the second element (β is needed only if the tuples contain more than
+/// one value; see [Permutation::num_rand_elements()]). The running product column is built and
+/// constrained by an [Accumulator] (see [Permutation::accumulator()]); in addition to the
+/// initial assertion of the accumulator, the argument asserts the final value of the column (see
+/// [Permutation::assertions()]).
+///
+/// Similar to transition constraints, the argument does not apply to the last step of the trace;
+/// thus, tuples at the last step are not included into the running product.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Permutation<B: StarkField> {
+    accumulator: Accumulator<B>,
+    width: usize,
+}
+
+impl<B: StarkField> Permutation<B> {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new argument enforcing that tuples of `left` values are a permutation of tuples
+    /// of `right` values, with the running product located in the specified column of the
+    /// auxiliary trace segment.
+    ///
+    /// # Panics
+    /// Panics if `left` is empty or if `left` and `right` tuples have different widths.
+    pub fn new(
+        column: usize,
+        left: Vec<AccumulatorTerm<B>>,
+        right: Vec<AccumulatorTerm<B>>,
+    ) -> Self {
+        assert!(!left.is_empty(), "permuted tuples must contain at least one value");
+        assert_eq!(
+            left.len(),
+            right.len(),
+            "permuted tuples must have the same width, but were {} and {}",
+            left.len(),
+            right.len()
+        );
+
+        Self {
+            accumulator: Accumulator::product(column, reduce_tuple(&left))
+                .with_denominator(reduce_tuple(&right)),
+            width: left.len(),
+        }
+    }
+
+    /// Returns a new argument enforcing that rows of `left` columns of the main trace segment are
+    /// a permutation of rows of `right` columns, with the running product located in the
+    /// specified column of the auxiliary trace segment.
+    ///
+    /// # Panics
+    /// Panics if `left` is empty or if `left` and `right` contain different numbers of columns.
+    pub fn from_columns(column: usize, left: &[usize], right: &[usize]) -> Self {
+        let to_terms =
+            |columns: &[usize]| columns.iter().map(|&i| AccumulatorTerm::column(i)).collect();
+        Self::new(column, to_terms(left), to_terms(right))
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the index of the auxiliary column containing the running product.
+    pub fn column(&self) -> usize {
+        self.accumulator.column()
+    }
+
+    /// Returns the accumulator which builds and constrains the running product column.
+    pub fn accumulator(&self) -> &Accumulator<B> {
+        &self.accumulator
+    }
+
+    /// Returns the number of random elements needed by this argument.
+    pub fn num_rand_elements(&self) -> usize {
+        if self.width > 1 {
+            2
+        } else {
+            1
+        }
+    }
+
+    /// Returns the degree of the transition constraint enforcing this argument.
+    pub fn transition_degree(&self) -> TransitionConstraintDegree {
+        self.accumulator.transition_degree()
+    }
+
+    /// Returns assertions against the first and the last steps of the running product column for
+    /// an execution trace of the specified length.
+    pub fn assertions<E: FieldElement>(&self, trace_length: usize) -> Vec<Assertion<E>> {
+        vec![
+            self.accumulator.initial_assertion(),
+            Assertion::single(self.column(), trace_length - 1, E::ONE),
+        ]
+    }
+
+    // TRACE BUILDING
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns values of the running product column for an execution trace with the specified
+    /// number of rows.
+    ///
+    /// Rows of the main trace segment are read via the `read_row` function, which must write the
+    /// row at the specified step into the provided slice of length `row_width`.
+    ///
+    /// # Panics
+    /// Panics if a right tuple reduces to zero at any step (except the last one).
+    pub fn build_column<F, E>(
+        &self,
+        num_rows: usize,
+        row_width: usize,
+        rand_elements: &[E],
+        read_row: impl FnMut(usize, &mut [F]),
+    ) -> Vec<E>
+    where
+        F: FieldElement<BaseField = B>,
+        E: FieldElement<BaseField = B> + ExtensionOf<F>,
+    {
+        self.accumulator.build_column(num_rows, row_width, rand_elements, read_row)
+    }
+
+    // CONSTRAINT EVALUATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Evaluates the transition constraint of this argument.
+    ///
+    /// `current` and `next` are the values of the running product column in the current and the
+    /// next rows of the evaluation frame, and the tuples are evaluated against the current row of
+    /// `main_frame`.
+    pub fn evaluate_transition<F, E>(
+        &self,
+        main_frame: &EvaluationFrame<F>,
+        current: E,
+        next: E,
+        rand_elements: &[E],
+    ) -> E
+    where
+        F: FieldElement<BaseField = B>,
+        E: FieldElement<BaseField = B> + ExtensionOf<F>,
+    {
+        self.accumulator.evaluate_transition(main_frame, current, next, rand_elements)
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use math::{
+        fields::{f64::BaseElement, QuadExtension},
+        FieldElement,
+    };
+
+    use super::{EvaluationFrame, Permutation};
+
+    type E2 = QuadExtension<BaseElement>;
+
+    /// Builds a trace of memory accesses where columns 0 and 1 contain (address, value) pairs in
+    /// execution order, and columns 2 and 3 contain the same pairs sorted by address; the last
+    /// row is a padding row.
+    fn build_trace() -> Vec<Vec<BaseElement>> {
+        let accesses: Vec<(u32, u32)> = (0..15_u32).map(|i| ((i * 7) % 5, i)).collect();
+        let mut sorted = accesses.clone();
+        sorted.sort();
+
+        accesses
+            .iter()
+            .zip(sorted.iter())
+            .map(|(&(a, v), &(sa, sv))| [a, v, sa, sv].map(BaseElement::from).to_vec())
+            .chain([vec![BaseElement::ZERO; 4]])
+            .collect()
+    }
+
+    fn build_column(
+        perm: &Permutation<BaseElement>,
+        trace: &[Vec<BaseElement>],
+        rand: &[E2],
+    ) -> Vec<E2> {
+        perm.build_column(trace.len(), 4, rand, |step, row: &mut [BaseElement]| {
+            row.copy_from_slice(&trace[step])
+        })
+    }
+
+    fn check_transitions(perm: &Permutation<BaseElement>, trace: &[Vec<BaseElement>], rand: &[E2]) {
+        let column = build_column(perm, trace, rand);
+        assert_eq!(trace.len(), column.len());
+        assert_eq!(E2::ONE, column[0]);
+
+        for step in 0..trace.len() - 1 {
+            let frame = EvaluationFrame::from_rows(trace[step].clone(), trace[step + 1].clone());
+            let evaluation = perm.evaluate_transition(&frame, column[step], column[step + 1], rand);
+            assert_eq!(E2::ZERO, evaluation);
+
+            // a different value in the next row violates the constraint
+            let next = column[step + 1] + E2::ONE;
+            let evaluation = perm.evaluate_transition(&frame, column[step], next, rand);
+            assert_ne!(E2::ZERO, evaluation);
+        }
+    }
+
+    #[test]
+    fn permutation_of_columns() {
+        let trace = build_trace();
+        let rand = [E2::new(BaseElement::from(1234_u32), BaseElement::from(5_u32))];
+
+        // values in column 1 are a permutation of values in column 3
+        let perm = Permutation::from_columns(0, &[1], &[3]);
+        assert_eq!(1, perm.num_rand_elements());
+        check_transitions(&perm, &trace, &rand);
+        let column = build_column(&perm, &trace, &rand);
+        assert_eq!(E2::ONE, column[trace.len() - 1]);
+
+        let assertions = perm.assertions::<E2>(trace.len());
+        assert_eq!(0, assertions[0].first_step());
+        assert_eq!(trace.len() - 1, assertions[1].first_step());
+
+        // values in column 1 are not a permutation of values in column 2
+        let perm = Permutation::from_columns(0, &[1], &[2]);
+        check_transitions(&perm, &trace, &rand);
+        let column = build_column(&perm, &trace, &rand);
+        assert_ne!(E2::ONE, column[trace.len() - 1]);
+    }
+
+    #[test]
+    fn permutation_of_tuples() {
+        let trace = build_trace();
+        let rand = [
+            E2::new(BaseElement::from(1234_u32), BaseElement::from(5_u32)),
+            E2::new(BaseElement::from(77_u32), BaseElement::from(3_u32)),
+        ];
+
+        // (address, value) pairs are a permutation of sorted pairs
+        let perm = Permutation::from_columns(0, &[0, 1], &[2, 3]);
+        assert_eq!(2, perm.num_rand_elements());
+        check_transitions(&perm, &trace, &rand);
+        let column = build_column(&perm, &trace, &rand);
+        assert_eq!(E2::ONE, column[trace.len() - 1]);
+
+        // but pairs with swapped elements are not
+        let perm = Permutation::from_columns(0, &[0, 1], &[3, 2]);
+        check_transitions(&perm, &trace, &rand);
+        let column = build_column(&perm, &trace, &rand);
+        assert_ne!(E2::ONE, column[trace.len() - 1]);
+    }
+
+    #[test]
+    #[should_panic(expected = "permuted tuples must have the same width")]
+    fn permutation_width_mismatch() {
+        Permutation::<BaseElement>::from_columns(0, &[0, 1], &[2]);
+    }
+}
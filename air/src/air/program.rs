@@ -0,0 +1,218 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::vec::Vec;
+
+use crypto::ElementHasher;
+use math::{StarkField, ToElements};
+use utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+
+// PROGRAM
+// ================================================================================================
+
+/// A program executed by a computation described by an AIR.
+///
+/// This is intended for virtual machine use cases where a single AIR can prove execution of
+/// arbitrary programs. A program consists of one or more columns of values (e.g., opcodes and
+/// immediate values) of the same length. The AIR makes the program available to its constraints
+/// via periodic columns (see [Program::get_periodic_columns()]), and thus, the values of the
+/// program columns are computed by the verifier directly rather than committed to by the prover.
+///
+/// A program is usually a part of the public inputs of a computation. A verifier can pin a
+/// specific program by checking that the hash of the program returned from [Air::get_program()]
+/// matches the expected program hash (see [Program::hash()]).
+///
+/// The length of a program must be a power of two which is at least 2 and does not exceed the
+/// length of the execution trace. Programs of other lengths must be padded by the caller (e.g.,
+/// with no-op instructions of the virtual machine).
+///
+/// [Air::get_program()]: crate::Air::get_program
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Program<B: StarkField> {
+    columns: Vec<Vec<B>>,
+}
+
+impl<B: StarkField> Program<B> {
+    // CONSTANTS
+    // --------------------------------------------------------------------------------------------
+
+    /// Maximum number of columns in a program; currently set at 255.
+    pub const MAX_NUM_COLUMNS: usize = 255;
+
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new program consisting of the specified columns.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// - `columns` is empty or contains more than 255 columns.
+    /// - Not all columns have the same length.
+    /// - The length of the columns is smaller than 2 or is not a power of two.
+    pub fn new(columns: Vec<Vec<B>>) -> Self {
+        assert!(!columns.is_empty(), "a program must consist of at least one column");
+        assert!(
+            columns.len() <= Self::MAX_NUM_COLUMNS,
+            "a program cannot consist of more than {} columns, but was {}",
+            Self::MAX_NUM_COLUMNS,
+            columns.len()
+        );
+
+        let len = columns[0].len();
+        assert!(
+            columns.iter().all(|column| column.len() == len),
+            "all program columns must have the same length"
+        );
+        assert!(len >= 2, "program length must be at least 2, but was {len}");
+        assert!(len.is_power_of_two(), "program length must be a power of two, but was {len}");
+
+        Self { columns }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of columns in this program.
+    pub fn num_columns(&self) -> usize {
+        self.columns.len()
+    }
+
+    /// Returns the number of values in each column of this program.
+    pub fn length(&self) -> usize {
+        self.columns[0].len()
+    }
+
+    /// Returns the columns of this program.
+    pub fn columns(&self) -> &[Vec<B>] {
+        &self.columns
+    }
+
+    /// Returns the hash of this program computed using the specified hash function.
+    ///
+    /// The hash commits to the dimensions of the program as well as to all of its values (in
+    /// column-major order).
+    pub fn hash<H: ElementHasher<BaseField = B>>(&self) -> H::Digest {
+        H::hash_elements(&self.to_elements())
+    }
+
+    /// Returns program columns in the form expected by
+    /// [Air::get_periodic_column_values()](crate::Air::get_periodic_column_values).
+    ///
+    /// When the program is shorter than the execution trace, the values of the returned columns
+    /// repeat every [length()](Program::length) steps.
+    pub fn get_periodic_columns(&self) -> Vec<Vec<B>> {
+        self.columns.clone()
+    }
+}
+
+impl<B: StarkField> ToElements<B> for Program<B> {
+    fn to_elements(&self) -> Vec<B> {
+        let mut result = Vec::with_capacity(2 + self.num_columns() * self.length());
+        result.push(B::from(self.num_columns() as u32));
+        result.push(B::from(self.length() as u32));
+        for column in self.columns.iter() {
+            result.extend_from_slice(column);
+        }
+        result
+    }
+}
+
+// SERIALIZATION
+// ================================================================================================
+
+impl<B: StarkField> Serializable for Program<B> {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u8(self.num_columns() as u8);
+        target.write_u32(self.length() as u32);
+        for column in self.columns.iter() {
+            target.write_many(column);
+        }
+    }
+}
+
+impl<B: StarkField> Deserializable for Program<B> {
+    /// Reads a program from the specified `source` and returns the result.
+    ///
+    /// # Errors
+    /// Returns an error if a valid program could not be read from the specified `source`.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let num_columns = source.read_u8()? as usize;
+        if num_columns == 0 {
+            return Err(DeserializationError::InvalidValue(
+                "a program must consist of at least one column".into(),
+            ));
+        }
+
+        let len = source.read_u32()? as usize;
+        if len < 2 || !len.is_power_of_two() {
+            return Err(DeserializationError::InvalidValue(format!(
+                "program length must be a power of two which is at least 2, but was {len}"
+            )));
+        }
+
+        let mut columns = Vec::with_capacity(num_columns);
+        for _ in 0..num_columns {
+            columns.push(source.read_many(len)?);
+        }
+
+        Ok(Self { columns })
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use crypto::{hashers::Blake3_256, ElementHasher};
+    use math::{fields::f64::BaseElement, ToElements};
+    use utils::{Deserializable, Serializable};
+
+    use super::Program;
+
+    type Hasher = Blake3_256<BaseElement>;
+
+    #[test]
+    fn program_hash() {
+        let values = (0..8_u32).map(BaseElement::from).collect::<Vec<_>>();
+        let program = Program::new(vec![values[..4].to_vec(), values[4..].to_vec()]);
+
+        let mut elements = vec![BaseElement::from(2_u32), BaseElement::from(4_u32)];
+        elements.extend_from_slice(&values);
+        assert_eq!(elements, program.to_elements());
+        assert_eq!(Hasher::hash_elements(&elements), program.hash::<Hasher>());
+
+        // programs with the same values but different dimensions have different hashes
+        let other = Program::new(vec![values.clone()]);
+        assert_ne!(program.hash::<Hasher>(), other.hash::<Hasher>());
+    }
+
+    #[test]
+    fn program_serialization() {
+        let values = (0..8_u32).map(BaseElement::from).collect::<Vec<_>>();
+        let program = Program::new(vec![values[..4].to_vec(), values[4..].to_vec()]);
+        let bytes = program.to_bytes();
+        assert_eq!(program, Program::read_from_bytes(&bytes).unwrap());
+
+        // invalid program length
+        let mut invalid_bytes = bytes.clone();
+        invalid_bytes[1] = 3;
+        assert!(Program::<BaseElement>::read_from_bytes(&invalid_bytes).is_err());
+
+        // no columns
+        let mut invalid_bytes = bytes;
+        invalid_bytes[0] = 0;
+        assert!(Program::<BaseElement>::read_from_bytes(&invalid_bytes).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "program length must be a power of two")]
+    fn program_invalid_length() {
+        let _ = Program::new(vec![vec![BaseElement::from(1_u32); 3]]);
+    }
+}
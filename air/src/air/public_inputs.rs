@@ -0,0 +1,80 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::vec::Vec;
+
+use crypto::ElementHasher;
+use math::{StarkField, ToElements};
+use utils::ByteWriter;
+
+// PUBLIC INPUTS
+// ================================================================================================
+
+/// Public inputs of a computation described by an AIR.
+///
+/// Public inputs are defined by the sequence of field elements returned from
+/// [ToElements::to_elements()]. These elements are used to seed the public coin of the protocol,
+/// and all other encodings of public inputs are derived from them. Specifically:
+/// * [PublicInputs::to_canonical_bytes()] returns the canonical byte encoding of public inputs.
+/// * [PublicInputs::hash()] returns a digest of public inputs (e.g., to be stored alongside a
+///   proof in a [ProofContainer](crate::proof::ProofContainer)).
+///
+/// Since the above encodings depend only on the field elements of public inputs, any two
+/// implementations (e.g., a prover and a verifier written in different languages) which agree on
+/// the elements also agree on the bytes and the digest.
+///
+/// This trait is implemented for all types which implement [ToElements].
+pub trait PublicInputs<B: StarkField>: ToElements<B> {
+    /// Returns the canonical byte encoding of these public inputs.
+    ///
+    /// The encoding consists of the number of elements in public inputs encoded as a 4-byte
+    /// little-endian integer, followed by the canonical encodings of the elements themselves.
+    fn to_canonical_bytes(&self) -> Vec<u8> {
+        let elements = self.to_elements();
+        let mut result = Vec::with_capacity(4 + elements.len() * B::ELEMENT_BYTES);
+        result.write_u32(elements.len() as u32);
+        result.write_many(&elements);
+        result
+    }
+
+    /// Returns a digest of these public inputs computed by hashing their elements with the
+    /// specified hash function.
+    fn hash<H: ElementHasher<BaseField = B>>(&self) -> H::Digest {
+        H::hash_elements(&self.to_elements())
+    }
+}
+
+impl<B: StarkField, T: ToElements<B>> PublicInputs<B> for T {}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use crypto::{hashers::Blake3_256, ElementHasher};
+    use math::{fields::f64::BaseElement, FieldElement};
+
+    use super::PublicInputs;
+
+    #[test]
+    fn canonical_bytes() {
+        let result = BaseElement::new(5);
+        let mut expected = vec![1, 0, 0, 0];
+        expected.extend_from_slice(&5_u64.to_le_bytes());
+        assert_eq!(expected, PublicInputs::<BaseElement>::to_canonical_bytes(&result));
+
+        assert_eq!(vec![0, 0, 0, 0], PublicInputs::<BaseElement>::to_canonical_bytes(&()));
+    }
+
+    #[test]
+    fn hash() {
+        type Hasher = Blake3_256<BaseElement>;
+        let result = BaseElement::ONE;
+        assert_eq!(
+            Hasher::hash_elements(&[BaseElement::ONE]),
+            PublicInputs::<BaseElement>::hash::<Hasher>(&result)
+        );
+    }
+}
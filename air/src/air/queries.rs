@@ -0,0 +1,115 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::vec::Vec;
+
+use crypto::{RandomCoin, RandomCoinError};
+use math::StarkField;
+
+// QUERY POSITIONS
+// ================================================================================================
+
+/// Draws `num_queries` positions in an LDE domain of size `lde_domain_size` from the provided
+/// public coin after it reseeds the coin with the specified `nonce`.
+///
+/// If `excluded_residue` is provided, positions `i` such that `i % blowup_factor` equals the
+/// excluded residue are never drawn. This is done by drawing positions uniformly at random from a
+/// domain which contains only the remaining positions, and then mapping them onto the LDE domain.
+/// Thus, the distribution of the drawn positions is uniform over the positions which are not
+/// excluded.
+pub(super) fn draw_query_positions<R: RandomCoin>(
+    public_coin: &mut R,
+    num_queries: usize,
+    lde_domain_size: usize,
+    blowup_factor: usize,
+    excluded_residue: Option<usize>,
+    nonce: u64,
+) -> Result<Vec<usize>, RandomCoinError> {
+    match excluded_residue {
+        None => public_coin.draw_integers(num_queries, lde_domain_size, nonce),
+        Some(residue) => {
+            // there is exactly one excluded position per block of `blowup_factor` positions
+            let block_size = blowup_factor - 1;
+            let domain_size = lde_domain_size / blowup_factor * block_size;
+            let positions = public_coin.draw_integers(num_queries, domain_size, nonce)?;
+            Ok(positions
+                .into_iter()
+                .map(|position| {
+                    let (block, offset) = (position / block_size, position % block_size);
+                    let offset = if offset < residue { offset } else { offset + 1 };
+                    block * blowup_factor + offset
+                })
+                .collect())
+        },
+    }
+}
+
+/// Returns the residue r such that positions `i` of the LDE domain with `i % blowup_factor == r`
+/// map onto points of the trace domain, or `None` if no position of the LDE domain maps onto the
+/// trace domain.
+///
+/// A point x of the LDE domain is a point of the trace domain iff x^trace_length = 1. For position
+/// `i`, x^trace_length = offset^trace_length * g^i, where g is a primitive root of unity of order
+/// `blowup_factor`; thus, whether a position maps onto the trace domain depends only on
+/// `i % blowup_factor`, and at most one such residue exists.
+pub(super) fn get_trace_domain_residue<B: StarkField>(
+    trace_length: usize,
+    blowup_factor: usize,
+    domain_offset: B,
+) -> Option<usize> {
+    let g = B::get_root_of_unity(blowup_factor.ilog2());
+    let mut x = domain_offset.exp((trace_length as u64).into());
+    for residue in 0..blowup_factor {
+        if x == B::ONE {
+            return Some(residue);
+        }
+        x *= g;
+    }
+    None
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use crypto::{hashers::Blake3_256, DefaultRandomCoin, RandomCoin};
+    use math::{fields::f64::BaseElement, FieldElement, StarkField};
+
+    use super::{draw_query_positions, get_trace_domain_residue};
+
+    type Coin = DefaultRandomCoin<Blake3_256<BaseElement>>;
+
+    #[test]
+    fn trace_domain_residue() {
+        // LDE domain which is not shifted contains the trace domain at positions 0, 8, 16, ...
+        assert_eq!(Some(0), get_trace_domain_residue(64, 8, BaseElement::ONE));
+
+        // shifting the LDE domain by a power of the LDE domain generator shifts the residue
+        let g = BaseElement::get_root_of_unity(9);
+        assert_eq!(Some(5), get_trace_domain_residue(64, 8, g.exp(3)));
+
+        // shifting the LDE domain by the field generator moves it off the trace domain
+        assert_eq!(None, get_trace_domain_residue(64, 8, BaseElement::GENERATOR));
+    }
+
+    #[test]
+    fn query_positions_with_exclusion() {
+        let seed = [BaseElement::ONE; 4];
+
+        // without exclusion, positions are drawn directly from the public coin
+        let mut coin = Coin::new(&seed);
+        let expected = Coin::new(&seed).draw_integers(32, 512, 7).unwrap();
+        assert_eq!(expected, draw_query_positions(&mut coin, 32, 512, 8, None, 7).unwrap());
+
+        // with exclusion, positions in the excluded residue class are never drawn
+        for residue in [0, 3, 7] {
+            let mut coin = Coin::new(&seed);
+            let positions = draw_query_positions(&mut coin, 200, 512, 8, Some(residue), 7).unwrap();
+            assert_eq!(200, positions.len());
+            assert!(positions.iter().all(|&p| p < 512 && p % 8 != residue));
+        }
+    }
+}
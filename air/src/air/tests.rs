@@ -9,10 +9,11 @@ use crypto::{hashers::Blake3_256, DefaultRandomCoin, RandomCoin};
 use math::{fields::f64::BaseElement, get_power_series, polynom, FieldElement, StarkField};
 
 use super::{
-    Air, AirContext, Assertion, EvaluationFrame, ProofOptions, TraceInfo,
-    TransitionConstraintDegree,
+    pad_periodic_column, Air, AirContext, Assertion, ConstraintDivisor, ConstraintExpr,
+    EvaluationFrame, ProofOptions, SymbolicConstraints, TraceInfo, TransitionConstraintDegree,
+    TransitionConstraints,
 };
-use crate::FieldExtension;
+use crate::{CoefficientDerivation, FieldExtension};
 
 // PERIODIC COLUMNS
 // ================================================================================================
@@ -52,6 +53,48 @@ fn get_periodic_column_polys_num_values_not_power_of_two() {
     assert_eq!(0, column_polys.len());
 }
 
+#[test]
+fn get_periodic_column_polys_padded() {
+    let col1 =
+        pad_periodic_column(vec![BaseElement::ONE, BaseElement::new(2), BaseElement::new(3)]);
+    let col2 = pad_periodic_column((1..11).map(BaseElement::new).collect::<Vec<_>>());
+    assert_eq!(4, col1.len());
+    assert_eq!(16, col2.len());
+
+    let air = MockAir::with_periodic_columns(vec![col1, col2], 16);
+    let column_polys = air.get_periodic_column_polys();
+    assert_eq!(2, column_polys.len());
+
+    // the padded column repeats every 16 steps and is zero at the last 6 steps of each cycle
+    let g = BaseElement::get_root_of_unity(4);
+    for step in 0..32_u64 {
+        let x = g.exp(step);
+        let expected = match step % 16 {
+            i if i < 10 => BaseElement::new(i + 1),
+            _ => BaseElement::ZERO,
+        };
+        assert_eq!(expected, polynom::eval(&column_polys[1], x));
+    }
+    // the polynomial of a column with 4 values is evaluated at the 4th roots of unity
+    let g = BaseElement::get_root_of_unity(2);
+    assert_eq!(BaseElement::new(3), polynom::eval(&column_polys[0], g.exp(2)));
+    assert_eq!(BaseElement::ZERO, polynom::eval(&column_polys[0], g.exp(3)));
+
+    // a column which is already a power of two is not changed by padding
+    let col3 = vec![BaseElement::ONE, BaseElement::ZERO];
+    assert_eq!(col3, pad_periodic_column(col3.clone()));
+
+    // degrees of constraints involving padded columns are described by unpadded lengths
+    let degree = TransitionConstraintDegree::with_padded_cycles(1, vec![10, 3]);
+    assert_eq!(TransitionConstraintDegree::with_cycles(1, vec![16, 4]), degree);
+}
+
+#[test]
+#[should_panic(expected = "cycle length must be a power of two, but was 10 for cycle 0")]
+fn transition_constraint_degree_cycle_not_power_of_two() {
+    TransitionConstraintDegree::with_cycles(1, vec![10]);
+}
+
 // TRANSITION CONSTRAINTS
 // ================================================================================================
 
@@ -188,6 +231,414 @@ fn get_boundary_constraints() {
     assert_eq!(expected_cc[&5], constraint.cc().clone());
 }
 
+// COMPOSITION COEFFICIENTS
+// ================================================================================================
+
+#[test]
+fn get_composition_coefficients_sequential() {
+    let assertions = vec![Assertion::single(0, 0, BaseElement::ONE); 3];
+    let air = MockAir::with_assertions(assertions, 16);
+
+    // coefficients are drawn directly from the public coin
+    let mut prng = build_prng();
+    let expected = (0..4).map(|_| prng.draw().unwrap()).collect::<Vec<BaseElement>>();
+
+    let mut public_coin = build_prng();
+    let coefficients = air.get_constraint_composition_coefficients(&mut public_coin).unwrap();
+    assert_eq!(expected[..1], coefficients.transition);
+    assert_eq!(expected[1..], coefficients.boundary);
+    assert!(coefficients.lagrange.is_none());
+    assert_eq!(prng.draw::<BaseElement>(), public_coin.draw::<BaseElement>());
+}
+
+#[test]
+fn get_composition_coefficients_expanded() {
+    let assertions = vec![Assertion::single(0, 0, BaseElement::ONE); 3];
+    let options = ProofOptions::new(32, 8, 0, FieldExtension::None, 4, 31)
+        .with_coefficient_derivation(CoefficientDerivation::Expanded);
+    let air = MockAir::with_assertions(assertions, 16).with_options(options);
+
+    // coefficients are expanded from a single seed drawn from the public coin
+    let mut prng = build_prng();
+    let seed: BaseElement = prng.draw().unwrap();
+    let mut kdf = DefaultRandomCoin::<Blake3_256<BaseElement>>::new(&[seed]);
+    let expected = (0..4).map(|_| kdf.draw().unwrap()).collect::<Vec<BaseElement>>();
+
+    let mut public_coin = build_prng();
+    let coefficients = air.get_constraint_composition_coefficients(&mut public_coin).unwrap();
+    assert_eq!(expected[..1], coefficients.transition);
+    assert_eq!(expected[1..], coefficients.boundary);
+
+    // the public coin was advanced only once
+    assert_eq!(prng.draw::<BaseElement>(), public_coin.draw::<BaseElement>());
+
+    // the same applies to DEEP composition coefficients
+    let seed: BaseElement = prng.draw().unwrap();
+    let mut kdf = DefaultRandomCoin::<Blake3_256<BaseElement>>::new(&[seed]);
+    let expected = (0..5).map(|_| kdf.draw().unwrap()).collect::<Vec<BaseElement>>();
+
+    let coefficients = air.get_deep_composition_coefficients(&mut public_coin).unwrap();
+    assert_eq!(expected[..4], coefficients.trace);
+    assert_eq!(expected[4..], coefficients.constraints);
+    assert!(coefficients.lagrange.is_none());
+    assert_eq!(prng.draw::<BaseElement>(), public_coin.draw::<BaseElement>());
+}
+
+// ZERO-KNOWLEDGE MODE
+// ================================================================================================
+
+#[test]
+fn zk_context() {
+    let options = ProofOptions::new(4, 8, 0, FieldExtension::None, 4, 31);
+    let trace_info = TraceInfo::new(4, 64);
+    let degrees = vec![TransitionConstraintDegree::new(3)];
+
+    let context = AirContext::<BaseElement>::new(trace_info.clone(), degrees.clone(), 1, options);
+    assert_eq!(0, context.num_random_rows());
+    assert_eq!(1, context.num_transition_exemptions());
+    assert_eq!(64, context.composition_segment_len());
+    assert_eq!(128, context.ce_domain_size());
+    assert_eq!(2, context.num_constraint_composition_columns());
+
+    // in zero-knowledge mode, 2 * (num_queries + 1) random rows are appended to the trace; these
+    // rows are exempt from transition constraints, which increases the degree of the composition
+    // polynomial, and thus, the size of the constraint evaluation domain
+    let options = ProofOptions::new(4, 8, 0, FieldExtension::None, 4, 31).with_zk();
+    let context = AirContext::<BaseElement>::new(trace_info.clone(), degrees.clone(), 1, options);
+    assert_eq!(10, context.num_random_rows());
+    assert_eq!(11, context.num_transition_exemptions());
+    assert_eq!(54, context.composition_segment_len());
+    assert_eq!(256, context.ce_domain_size());
+    assert_eq!(4, context.num_constraint_composition_columns());
+
+    let context = context.set_num_transition_exemptions(2);
+    assert_eq!(12, context.num_transition_exemptions());
+    assert_eq!(4, context.num_constraint_composition_columns());
+}
+
+#[test]
+#[should_panic(expected = "trace length must be at least 32 in zero-knowledge mode")]
+fn zk_context_trace_too_short() {
+    let options = ProofOptions::new(4, 8, 0, FieldExtension::None, 4, 31).with_zk();
+    let degrees = vec![TransitionConstraintDegree::new(2)];
+    let _ = AirContext::<BaseElement>::new(TraceInfo::new(4, 16), degrees, 1, options);
+}
+
+// MULTI-ROW FRAMES
+// ================================================================================================
+
+#[test]
+fn multi_row_frame_context() {
+    let options = ProofOptions::new(4, 8, 0, FieldExtension::None, 4, 31);
+    let trace_info = TraceInfo::new(4, 64);
+    let degrees = vec![TransitionConstraintDegree::new(3)];
+
+    // transitions over k rows cannot be enforced against the last k - 1 rows of the trace
+    let context = AirContext::<BaseElement>::new(trace_info.clone(), degrees.clone(), 1, options)
+        .set_num_frame_rows(3);
+    assert_eq!(3, context.num_frame_rows());
+    assert_eq!(2, context.num_transition_exemptions());
+
+    // wider frames reveal more evaluations of each trace polynomial, and thus, more random rows
+    // are needed in zero-knowledge mode
+    let options = ProofOptions::new(4, 8, 0, FieldExtension::None, 4, 31).with_zk();
+    let context = AirContext::<BaseElement>::new(trace_info, degrees, 1, options)
+        .set_num_transition_exemptions(2)
+        .set_num_frame_rows(3);
+    assert_eq!(15, context.num_random_rows());
+    assert_eq!(17, context.num_transition_exemptions());
+    assert_eq!(49, context.composition_segment_len());
+}
+
+#[test]
+#[should_panic(expected = "number of transition exemptions must be at least 2")]
+fn multi_row_frame_context_too_few_exemptions() {
+    let options = ProofOptions::new(4, 8, 0, FieldExtension::None, 4, 31);
+    let degrees = vec![TransitionConstraintDegree::new(2)];
+    let _ = AirContext::<BaseElement>::new(TraceInfo::new(4, 64), degrees, 1, options)
+        .set_num_frame_rows(3)
+        .set_num_transition_exemptions(1);
+}
+
+#[test]
+fn frame_offsets_context() {
+    let options = ProofOptions::new(4, 8, 0, FieldExtension::None, 4, 31);
+    let degrees = vec![TransitionConstraintDegree::new(4)];
+    let context = AirContext::<BaseElement>::new(TraceInfo::new(4, 64), degrees, 1, options);
+    assert_eq!(&[0, 1], context.frame_offsets());
+
+    // the number of exemptions is determined by the largest offset
+    let context = context.set_frame_offsets(vec![0, 1, 8]);
+    assert_eq!(3, context.num_frame_rows());
+    assert_eq!(&[0, 1, 8], context.frame_offsets());
+    assert_eq!(8, context.num_transition_exemptions());
+}
+
+#[test]
+#[should_panic(expected = "frame row offsets must be strictly increasing")]
+fn frame_offsets_context_not_increasing() {
+    let options = ProofOptions::new(4, 8, 0, FieldExtension::None, 4, 31);
+    let degrees = vec![TransitionConstraintDegree::new(2)];
+    let _ = AirContext::<BaseElement>::new(TraceInfo::new(4, 64), degrees, 1, options)
+        .set_frame_offsets(vec![0, 8, 1]);
+}
+
+#[test]
+#[should_panic(expected = "number of frame rows cannot exceed 255, but was 256")]
+fn frame_offsets_context_too_many_rows() {
+    let options = ProofOptions::new(4, 8, 0, FieldExtension::None, 4, 31);
+    let degrees = vec![TransitionConstraintDegree::new(2)];
+    let _ = AirContext::<BaseElement>::new(TraceInfo::new(4, 1024), degrees, 1, options)
+        .set_num_frame_rows(256);
+}
+
+#[test]
+fn long_trace_context() {
+    // the two-adicity of the f64 field is 32, and thus, LDE domains of up to 2^32 are supported
+    let options = ProofOptions::new(4, 4, 0, FieldExtension::None, 4, 31);
+    let degrees = vec![TransitionConstraintDegree::new(2)];
+    let context = AirContext::<BaseElement>::new(TraceInfo::new(4, 1 << 30), degrees, 1, options);
+    assert_eq!(1 << 32, context.lde_domain_size());
+}
+
+#[test]
+#[should_panic(
+    expected = "LDE domain size cannot exceed 2^32 for the specified base field, but was 2^33"
+)]
+fn long_trace_context_domain_too_large() {
+    let options = ProofOptions::new(4, 8, 0, FieldExtension::None, 4, 31);
+    let degrees = vec![TransitionConstraintDegree::new(2)];
+    let _ = AirContext::<BaseElement>::new(TraceInfo::new(4, 1 << 30), degrees, 1, options);
+}
+
+// CONSTRAINT EVALUATION BLOWUP FACTOR
+// ================================================================================================
+
+#[test]
+fn ce_blowup_factor_context() {
+    let options = ProofOptions::new(4, 16, 0, FieldExtension::None, 4, 31);
+    let degrees = vec![TransitionConstraintDegree::new(5), TransitionConstraintDegree::new(2)];
+    let context = AirContext::<BaseElement>::new(TraceInfo::new(4, 64), degrees, 1, options);
+
+    // by default, the blowup factor is determined by the highest constraint degree
+    assert_eq!(4, context.ce_blowup_factor);
+    assert_eq!(256, context.ce_domain_size());
+    assert_eq!(1024, context.lde_domain_size());
+    assert_eq!(4, context.num_constraint_composition_columns());
+
+    // a larger blowup factor does not affect the number of composition columns
+    let context = context.set_ce_blowup_factor(8);
+    assert_eq!(512, context.ce_domain_size());
+    assert_eq!(1024, context.lde_domain_size());
+    assert_eq!(4, context.num_constraint_composition_columns());
+}
+
+#[test]
+#[should_panic(expected = "constraint evaluation blowup factor must be at least 4, but was 2")]
+fn ce_blowup_factor_context_too_small() {
+    let options = ProofOptions::new(4, 16, 0, FieldExtension::None, 4, 31);
+    let degrees = vec![TransitionConstraintDegree::new(5)];
+    let _ = AirContext::<BaseElement>::new(TraceInfo::new(4, 64), degrees, 1, options)
+        .set_ce_blowup_factor(2);
+}
+
+#[test]
+#[should_panic(expected = "constraint evaluation blowup factor cannot exceed 16, but was 32")]
+fn ce_blowup_factor_context_too_large() {
+    let options = ProofOptions::new(4, 16, 0, FieldExtension::None, 4, 31);
+    let degrees = vec![TransitionConstraintDegree::new(3)];
+    let _ = AirContext::<BaseElement>::new(TraceInfo::new(4, 64), degrees, 1, options)
+        .set_ce_blowup_factor(32);
+}
+
+// CUSTOM TRANSITION DIVISORS
+// ================================================================================================
+
+#[test]
+fn custom_transition_divisor_context() {
+    let options = ProofOptions::new(4, 8, 0, FieldExtension::None, 4, 31);
+    let degrees = vec![TransitionConstraintDegree::new(3), TransitionConstraintDegree::new(2)];
+    let context = AirContext::<BaseElement>::new(TraceInfo::new(4, 64), degrees, 1, options);
+    assert!(context.transition_divisor(0).is_none());
+    assert_eq!(128, context.ce_domain_size());
+    assert_eq!(2, context.num_constraint_composition_columns());
+
+    // enforcing the first constraint only on every 8th step reduces the degree of its divisor
+    // from 63 to 8, and thus, the composition polynomial has degree 3 * 63 - 8 = 181
+    let divisor = ConstraintDivisor::from_transition_coset(64, 0, 8, &[]);
+    let context = context.set_transition_divisor(0, divisor.clone());
+    assert_eq!(Some(&divisor), context.transition_divisor(0));
+    assert!(context.transition_divisor(1).is_none());
+    assert_eq!(256, context.ce_domain_size());
+    assert_eq!(3, context.num_constraint_composition_columns());
+
+    // constraints with custom divisors are excluded from the default divisor and the composition
+    // coefficients apply to each constraint independently of its divisor
+    let constraints = TransitionConstraints::new(&context, &[BaseElement::ONE; 2]);
+    assert_eq!(2, constraints.divisors().len());
+    assert_eq!(&divisor, &constraints.divisors()[1]);
+    assert_eq!(&[1, 0], constraints.main_divisor_indices());
+}
+
+#[test]
+fn transition_period_context() {
+    let options = ProofOptions::new(4, 8, 0, FieldExtension::None, 4, 31);
+    let degrees = vec![TransitionConstraintDegree::new(3), TransitionConstraintDegree::new(2)];
+    let context = AirContext::<BaseElement>::new(TraceInfo::new(4, 64), degrees, 1, options);
+
+    // none of the enforced steps fall on the last step of the trace
+    let context = context.set_transition_period(0, 0, 8);
+    let divisor = ConstraintDivisor::from_transition_coset(64, 0, 8, &[]);
+    assert_eq!(Some(&divisor), context.transition_divisor(0));
+
+    // step 63 is exempted since the next row wraps around to the start of the trace
+    let context = context.set_transition_period(1, 7, 8);
+    let divisor = ConstraintDivisor::from_transition_coset(64, 7, 8, &[63]);
+    assert_eq!(Some(&divisor), context.transition_divisor(1));
+
+    // with frame offsets [0, 1, 8], the enforced steps among the last 8 steps are exempted
+    let options = ProofOptions::new(4, 8, 0, FieldExtension::None, 4, 31);
+    let degrees = vec![TransitionConstraintDegree::new(2)];
+    let context = AirContext::<BaseElement>::new(TraceInfo::new(4, 64), degrees, 1, options)
+        .set_frame_offsets(vec![0, 1, 8])
+        .set_transition_period(0, 0, 4);
+    let divisor = ConstraintDivisor::from_transition_coset(64, 0, 4, &[56, 60]);
+    assert_eq!(Some(&divisor), context.transition_divisor(0));
+}
+
+#[test]
+#[should_panic(expected = "first step of a transition divisor must be smaller than stride 8")]
+fn transition_period_context_invalid_first_step() {
+    let options = ProofOptions::new(4, 8, 0, FieldExtension::None, 4, 31);
+    let degrees = vec![TransitionConstraintDegree::new(2)];
+    let _ = AirContext::<BaseElement>::new(TraceInfo::new(4, 64), degrees, 1, options)
+        .set_transition_period(0, 8, 8);
+}
+
+#[test]
+#[should_panic(expected = "constraint index must be smaller than 1, but was 1")]
+fn custom_transition_divisor_context_invalid_index() {
+    let options = ProofOptions::new(4, 8, 0, FieldExtension::None, 4, 31);
+    let degrees = vec![TransitionConstraintDegree::new(2)];
+    let _ = AirContext::<BaseElement>::new(TraceInfo::new(4, 64), degrees, 1, options)
+        .set_transition_divisor(0, ConstraintDivisor::from_transition_with_exemptions(64, &[63]))
+        .set_transition_divisor(1, ConstraintDivisor::from_transition_with_exemptions(64, &[63]));
+}
+
+// SYMBOLIC CONSTRAINTS
+// ================================================================================================
+
+#[test]
+fn symbolic_constraint_expressions() {
+    type Expr = ConstraintExpr<BaseElement>;
+    let (a, b) = (Expr::current(0), Expr::current(1));
+    let k = Expr::periodic(0);
+
+    // a' = (a + b)^3 * k - 5 ; b' = -(a + b) * 2
+    let sum = &a + &b;
+    let c0 = Expr::next(0) - (sum.exp(3) * &k - BaseElement::new(5));
+    let c1 = Expr::next(1) - -(&sum * BaseElement::new(2));
+
+    let mut constraints = SymbolicConstraints::new();
+    let node = constraints.insert(&c0);
+    constraints.enforce(node);
+    let num_nodes = constraints.nodes().len();
+    let node = constraints.insert(&c1);
+    constraints.enforce(node);
+
+    // the shared sum is added once within an expression, but again for a separate expression
+    assert_eq!(10, num_nodes);
+    assert_eq!(num_nodes + 8, constraints.nodes().len());
+
+    let (a, b, a_next, b_next, k) = (3u64, 4u64, 100u64, 200u64, 7u64);
+    let frame = EvaluationFrame::from_rows(
+        vec![BaseElement::new(a), BaseElement::new(b)],
+        vec![BaseElement::new(a_next), BaseElement::new(b_next)],
+    );
+    let mut result = [BaseElement::ZERO; 2];
+    constraints.evaluate(&frame, &[BaseElement::new(k)], &mut result);
+    let expected = [
+        BaseElement::new(a_next) - BaseElement::new((a + b).pow(3) * k - 5),
+        BaseElement::new(b_next) + BaseElement::new((a + b) * 2),
+    ];
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn symbolic_constraint_degrees() {
+    type Expr = ConstraintExpr<BaseElement>;
+    let (a, b) = (Expr::current(0), Expr::current(1));
+    let (k, s) = (Expr::periodic(0), Expr::periodic(1));
+
+    let mut constraints = SymbolicConstraints::new();
+    for expr in [
+        // a' = a * b
+        Expr::next(0) - &a * &b,
+        // b' = k * a^3 + 1
+        Expr::next(1) - (&k * a.exp(3) + BaseElement::ONE),
+        // a' = a + s: the trace column dominates the periodic column with 8 values
+        Expr::next(0) - (&a + &s),
+        // a' = a * k + b^2: a squared trace column dominates a trace column times a periodic
+        // column
+        Expr::next(0) - (&a * &k + b.exp(2)),
+    ] {
+        let node = constraints.insert(&expr);
+        constraints.enforce(node);
+    }
+
+    let degrees = constraints.degrees(64, &[16, 8]);
+    assert_eq!(
+        vec![
+            TransitionConstraintDegree::new(2),
+            TransitionConstraintDegree::with_cycles(3, vec![16]),
+            TransitionConstraintDegree::new(1),
+            TransitionConstraintDegree::new(2),
+        ],
+        degrees
+    );
+}
+
+#[test]
+#[should_panic(expected = "degree of transition constraint 0 cannot be described")]
+fn symbolic_constraint_degrees_no_trace_columns() {
+    let mut constraints = SymbolicConstraints::<BaseElement>::new();
+    let node = constraints.insert(&(ConstraintExpr::periodic(0) - BaseElement::ONE));
+    constraints.enforce(node);
+    constraints.degrees(64, &[8]);
+}
+
+#[test]
+fn symbolic_constraints_context() {
+    type Expr = ConstraintExpr<BaseElement>;
+    let a = Expr::current(0);
+
+    // a'' = a' * a, enforced on all steps, and a' = a^2, enforced on every 8th step
+    let mut constraints = SymbolicConstraints::new();
+    let node = constraints.insert(&(Expr::trace(2, 0) - Expr::next(0) * &a));
+    constraints.enforce(node);
+    let node = constraints.insert(&(Expr::next(0) - a.exp(2)));
+    constraints.enforce_on_period(node, 0, 8);
+
+    let options = ProofOptions::new(4, 8, 0, FieldExtension::None, 4, 31);
+    let trace_info = TraceInfo::new(1, 64);
+    let context = AirContext::from_symbolic_constraints(
+        trace_info.clone(),
+        &constraints,
+        &[],
+        1,
+        options.clone(),
+    );
+
+    let degrees = vec![TransitionConstraintDegree::new(2), TransitionConstraintDegree::new(2)];
+    let expected = AirContext::new(trace_info, degrees, 1, options)
+        .set_num_frame_rows(3)
+        .set_transition_period(1, 0, 8);
+    assert!(context == expected);
+    assert_eq!(3, context.num_frame_rows());
+    assert!(context.transition_divisor(0).is_none());
+    assert!(context.transition_divisor(1).is_some());
+}
+
 // MOCK AIR
 // ================================================================================================
 
@@ -220,6 +671,11 @@ impl MockAir {
         result.assertions = assertions;
         result
     }
+
+    pub fn with_options(mut self, options: ProofOptions) -> Self {
+        self.context.options = options;
+        self
+    }
 }
 
 impl Air for MockAir {
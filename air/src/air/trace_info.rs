@@ -32,8 +32,9 @@ pub struct TraceInfo {
 impl TraceInfo {
     /// Smallest allowed execution trace length; currently set at 8.
     pub const MIN_TRACE_LENGTH: usize = 8;
-    /// Maximum number of columns in an execution trace (across all segments); currently set at 255.
-    pub const MAX_TRACE_WIDTH: usize = 255;
+    /// Maximum number of columns in an execution trace (across all segments); currently set at
+    /// 65535.
+    pub const MAX_TRACE_WIDTH: usize = 65535;
     /// Maximum number of bytes in trace metadata; currently set at 65535.
     pub const MAX_META_LENGTH: usize = 65535;
     /// Maximum number of random elements in the auxiliary trace segment; currently set to 255.
@@ -48,7 +49,7 @@ impl TraceInfo {
     ///
     /// # Panics
     /// Panics if:
-    /// * Trace width is zero or greater than 255.
+    /// * Trace width is zero or greater than 65535.
     /// * Trace length is smaller than 8 or is not a power of two.
     pub fn new(width: usize, length: usize) -> Self {
         Self::with_meta(width, length, vec![])
@@ -60,7 +61,7 @@ impl TraceInfo {
     ///
     /// # Panics
     /// Panics if:
-    /// * Trace width is zero or greater than 255.
+    /// * Trace width is zero or greater than 65535.
     /// * Trace length is smaller than 8 or is not a power of two.
     /// * Length of `meta` is greater than 65535;
     pub fn with_meta(width: usize, length: usize, meta: Vec<u8>) -> Self {
@@ -76,7 +77,7 @@ impl TraceInfo {
     /// # Panics
     /// Panics if:
     /// * The width of the first trace segment is zero.
-    /// * Total width of all trace segments is greater than 255.
+    /// * Total width of all trace segments is greater than 65535.
     /// * Trace length is smaller than 8 or is not a power of two.
     /// * A zero entry in auxiliary segment width array is followed by a non-zero entry.
     /// * Number of random elements for the auxiliary trace segment of non-zero width is set to
@@ -146,7 +147,7 @@ impl TraceInfo {
 
     /// Returns the total number of columns in an execution trace.
     ///
-    /// This is guaranteed to be between 1 and 255.
+    /// This is guaranteed to be between 1 and 65535.
     pub fn width(&self) -> usize {
         self.main_segment_width + self.aux_segment_width
     }
@@ -170,7 +171,7 @@ impl TraceInfo {
 
     /// Returns the number of columns in the main segment of an execution trace.
     ///
-    /// This is guaranteed to be between 1 and 255.
+    /// This is guaranteed to be between 1 and 65535.
     pub fn main_trace_width(&self) -> usize {
         self.main_segment_width
     }
@@ -208,25 +209,97 @@ impl TraceInfo {
     pub fn get_num_aux_segment_rand_elements(&self) -> usize {
         self.num_aux_segment_rands
     }
+
+    // TRACE COMPOSITION
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns trace info for an execution trace consisting of two traces placed side by side.
+    ///
+    /// The columns of the `first` trace are followed by the columns of the `second` trace, and
+    /// the layout of the composed trace (i.e., the width of the first trace and the metadata of
+    /// both traces) is recorded in the metadata of the composed trace; it can be recovered via
+    /// [TraceInfo::decompose()]. This is the trace info expected by
+    /// [ComposedAir](crate::ComposedAir).
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * Either of the traces has an auxiliary segment.
+    /// * The traces have different lengths.
+    /// * Total width of the traces is greater than 65535.
+    /// * Total length of the metadata of the traces is greater than 65531.
+    pub fn compose(first: &TraceInfo, second: &TraceInfo) -> Self {
+        assert!(
+            !first.is_multi_segment() && !second.is_multi_segment(),
+            "composed traces cannot have auxiliary segments"
+        );
+        assert_eq!(
+            first.length(),
+            second.length(),
+            "composed traces must have the same length, but were {} and {}",
+            first.length(),
+            second.length()
+        );
+
+        let mut meta = Vec::with_capacity(4 + first.meta().len() + second.meta().len());
+        meta.extend_from_slice(&(first.main_trace_width() as u16).to_le_bytes());
+        meta.extend_from_slice(&(first.meta().len() as u16).to_le_bytes());
+        meta.extend_from_slice(first.meta());
+        meta.extend_from_slice(second.meta());
+
+        Self::with_meta(first.main_trace_width() + second.main_trace_width(), first.length(), meta)
+    }
+
+    /// Returns trace infos of the two traces from which this trace was composed via
+    /// [TraceInfo::compose()].
+    ///
+    /// Returns `None` if this trace has an auxiliary segment, or if the metadata of this trace
+    /// does not describe a valid layout of a composed trace.
+    pub fn decompose(&self) -> Option<(TraceInfo, TraceInfo)> {
+        if self.is_multi_segment() || self.trace_meta.len() < 4 {
+            return None;
+        }
+
+        let first_width = u16::from_le_bytes([self.trace_meta[0], self.trace_meta[1]]) as usize;
+        let first_meta_len = u16::from_le_bytes([self.trace_meta[2], self.trace_meta[3]]) as usize;
+        if first_width == 0
+            || first_width >= self.main_segment_width
+            || first_meta_len > self.trace_meta.len() - 4
+        {
+            return None;
+        }
+        let (first_meta, second_meta) = self.trace_meta[4..].split_at(first_meta_len);
+
+        let first = Self::with_meta(first_width, self.trace_length, first_meta.to_vec());
+        let second = Self::with_meta(
+            self.main_segment_width - first_width,
+            self.trace_length,
+            second_meta.to_vec(),
+        );
+        Some((first, second))
+    }
 }
 
 impl<E: StarkField> ToElements<E> for TraceInfo {
     fn to_elements(&self) -> Vec<E> {
         let mut result = Vec::new();
 
-        // main segment width, number of auxiliary segments, and parameters of the first auxiliary
-        // segment (if present) go into the first field element; we assume that each parameter can
-        // be encoded in 8 bits (which is enforced by the constructor)
-        let mut buf = self.main_segment_width as u32;
-        buf = (buf << 8) | self.num_aux_segments() as u32;
+        // main segment width and the number of auxiliary segments go into the first field
+        // element, and parameters of the auxiliary segment (if present) go into the second field
+        // element; we assume that segment widths can be encoded in 16 bits and the other
+        // parameters can be encoded in 8 bits (which is enforced by the constructor)
+        let buf = ((self.main_segment_width as u32) << 8) | self.num_aux_segments() as u32;
+        result.push(E::from(buf));
         if self.num_aux_segments() == 1 {
-            buf = (buf << 8) | self.aux_segment_width as u32;
-            buf = (buf << 8) | self.num_aux_segment_rands as u32;
+            let buf = ((self.aux_segment_width as u32) << 8) | self.num_aux_segment_rands as u32;
+            result.push(E::from(buf));
         }
-        result.push(E::from(buf));
 
-        // We assume here that the trace length is never greater than 2^32.
-        result.push(E::from(self.trace_length as u32));
+        // the trace length always fits into a single field element since it cannot exceed the
+        // size of the largest multiplicative subgroup of the field
+        let trace_length = E::try_from(self.trace_length as u64)
+            .ok()
+            .expect("trace length does not fit into a field element");
+        result.push(trace_length);
 
         // convert trace metadata to elements; this is done by breaking trace metadata into chunks
         // of bytes which are slightly smaller than the number of bytes needed to encode a field
@@ -245,13 +318,13 @@ impl Serializable for TraceInfo {
     /// Serializes `self` and writes the resulting bytes into the `target`.
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
         // store segments
-        target.write_u8(self.main_segment_width as u8);
+        target.write_u16(self.main_segment_width as u16);
 
         debug_assert!(
-            self.aux_segment_width <= u8::MAX as usize,
-            "aux segment width does not fit into u8 value"
+            self.aux_segment_width <= u16::MAX as usize,
+            "aux segment width does not fit into u16 value"
         );
-        target.write_u8(self.aux_segment_width as u8);
+        target.write_u16(self.aux_segment_width as u16);
         debug_assert!(
             self.num_aux_segment_rands <= u8::MAX as usize,
             "aux segment random element count does not fit into u8 value"
@@ -274,7 +347,7 @@ impl Deserializable for TraceInfo {
     /// Returns an error of a valid [`TraceInfo`] struct could not be read from the specified
     /// `source`.
     fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
-        let main_segment_width = source.read_u8()? as usize;
+        let main_segment_width = source.read_u16()? as usize;
         if main_segment_width == 0 {
             return Err(DeserializationError::InvalidValue(
                 "main trace segment width must be greater than zero".to_string(),
@@ -282,10 +355,10 @@ impl Deserializable for TraceInfo {
         }
 
         // read auxiliary trace segment width
-        let aux_segment_width = source.read_u8()? as usize;
+        let aux_segment_width = source.read_u16()? as usize;
 
         let full_trace_width = main_segment_width + aux_segment_width;
-        if full_trace_width >= TraceInfo::MAX_TRACE_WIDTH {
+        if full_trace_width > TraceInfo::MAX_TRACE_WIDTH {
             return Err(DeserializationError::InvalidValue(format!(
                 "full trace width cannot be greater than {}, but was {}",
                 TraceInfo::MAX_TRACE_WIDTH,
@@ -316,6 +389,13 @@ impl Deserializable for TraceInfo {
                 trace_length
             )));
         }
+        if trace_length as u32 >= usize::BITS {
+            return Err(DeserializationError::InvalidValue(format!(
+                "trace length cannot be greater than 2^{}, but was 2^{}",
+                usize::BITS - 1,
+                trace_length
+            )));
+        }
         let trace_length = 2_usize.pow(trace_length as u32);
 
         // read trace metadata
@@ -342,6 +422,7 @@ impl Deserializable for TraceInfo {
 #[cfg(test)]
 mod tests {
     use math::{fields::f64::BaseElement, FieldElement};
+    use utils::{Deserializable, Serializable};
 
     use super::{ToElements, TraceInfo};
 
@@ -370,15 +451,20 @@ mod tests {
         let trace_meta = vec![1_u8, 2, 3, 4];
 
         let expected = {
-            let first_ele =
-                u32::from_le_bytes([aux_rands as u8, aux_width, num_aux_segments, main_width]);
+            let first_ele = u32::from_le_bytes([num_aux_segments, main_width, 0, 0]);
+            let second_ele = u32::from_le_bytes([aux_rands as u8, aux_width, 0, 0]);
 
             // `trace_meta` is 4 bytes, so fits into a single element
             let mut meta_bytes = trace_meta.clone();
             meta_bytes.resize(BaseElement::ELEMENT_BYTES, 0);
             let meta_ele = BaseElement::try_from(meta_bytes.as_slice()).unwrap();
 
-            vec![BaseElement::from(first_ele), BaseElement::from(trace_length), meta_ele]
+            vec![
+                BaseElement::from(first_ele),
+                BaseElement::from(second_ele),
+                BaseElement::from(trace_length),
+                meta_ele,
+            ]
         };
 
         let info = TraceInfo::new_multi_segment(
@@ -391,4 +477,73 @@ mod tests {
 
         assert_eq!(expected, info.to_elements());
     }
+
+    #[test]
+    fn wide_trace_info() {
+        // segment widths are encoded using 16 bits
+        let info = TraceInfo::new_multi_segment(1000, 300, 4, 64, vec![]);
+        let expected = vec![
+            BaseElement::from(u32::from_le_bytes([1, 0xe8, 0x03, 0])),
+            BaseElement::from(u32::from_le_bytes([4, 0x2c, 0x01, 0])),
+            BaseElement::from(64_u32),
+        ];
+        assert_eq!(expected, info.to_elements());
+
+        let bytes = info.to_bytes();
+        assert_eq!(info, TraceInfo::read_from_bytes(&bytes).unwrap());
+
+        // the total width of the trace cannot exceed the maximum
+        let max_width = TraceInfo::MAX_TRACE_WIDTH;
+        let mut bytes = TraceInfo::new_multi_segment(max_width - 1, 1, 1, 64, vec![]).to_bytes();
+        assert!(TraceInfo::read_from_bytes(&bytes).is_ok());
+        // increase the width of the auxiliary segment (stored in bytes 2 and 3) to 2
+        bytes[2] = 2;
+        assert!(TraceInfo::read_from_bytes(&bytes).is_err());
+    }
+
+    #[test]
+    fn long_trace_info() {
+        // trace lengths greater than 2^32 are encoded without truncation
+        let info = TraceInfo::new(4, 1 << 33);
+        assert_eq!(BaseElement::new(1 << 33), info.to_elements()[1]);
+
+        let mut bytes = info.to_bytes();
+        assert_eq!(info, TraceInfo::read_from_bytes(&bytes).unwrap());
+
+        // the trace length (stored as a power of two in byte 5) must fit into a usize value
+        bytes[5] = usize::BITS as u8;
+        assert!(TraceInfo::read_from_bytes(&bytes).is_err());
+    }
+
+    #[test]
+    fn composed_trace_info() {
+        let first = TraceInfo::with_meta(2, 64, vec![1, 2, 3]);
+        let second = TraceInfo::with_meta(3, 64, vec![4]);
+
+        let info = TraceInfo::compose(&first, &second);
+        assert_eq!(5, info.main_trace_width());
+        assert_eq!(64, info.length());
+        assert_eq!(&[2, 0, 3, 0, 1, 2, 3, 4], info.meta());
+        assert_eq!(Some((first.clone(), second.clone())), info.decompose());
+
+        // compositions can be nested
+        let third = TraceInfo::new(4, 64);
+        let nested = TraceInfo::compose(&third, &info);
+        assert_eq!(9, nested.main_trace_width());
+        let (nested_first, nested_second) = nested.decompose().unwrap();
+        assert_eq!(third, nested_first);
+        assert_eq!(Some((first, second)), nested_second.decompose());
+
+        // trace metadata which does not describe a valid layout is rejected
+        assert_eq!(None, TraceInfo::new(5, 64).decompose());
+        assert_eq!(None, TraceInfo::with_meta(5, 64, vec![0, 0, 0, 0]).decompose());
+        assert_eq!(None, TraceInfo::with_meta(5, 64, vec![5, 0, 0, 0]).decompose());
+        assert_eq!(None, TraceInfo::with_meta(5, 64, vec![2, 0, 1, 0]).decompose());
+    }
+
+    #[test]
+    #[should_panic(expected = "composed traces must have the same length, but were 64 and 32")]
+    fn composed_trace_info_different_lengths() {
+        let _ = TraceInfo::compose(&TraceInfo::new(2, 64), &TraceInfo::new(2, 32));
+    }
 }
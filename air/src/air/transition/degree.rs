@@ -67,6 +67,25 @@ impl TransitionConstraintDegree {
         TransitionConstraintDegree { base: base_degree, cycles }
     }
 
+    /// Creates a new transition degree descriptor for constraints which involve multiplication
+    /// of trace columns and periodic columns padded via
+    /// [pad_periodic_column()](crate::pad_periodic_column).
+    ///
+    /// Values in the `cycles` vector are the numbers of values in the respective periodic columns
+    /// before padding; these are rounded up to the next power of two. For example, if a constraint
+    /// involves multiplication of one trace column and a column of 10 round constants, `cycles`
+    /// can be set to `vec![10]`, which is equivalent to `vec![16]` in
+    /// [TransitionConstraintDegree::with_cycles()].
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `base_degree` is zero.
+    /// * Any of the values in the `cycles` vector is smaller than two.
+    pub fn with_padded_cycles(base_degree: usize, cycles: Vec<usize>) -> Self {
+        let cycles = cycles.into_iter().map(usize::next_power_of_two).collect();
+        Self::with_cycles(base_degree, cycles)
+    }
+
     /// Computes a degree to which this degree description expands in the context of execution
     /// trace of the specified length.
     ///
@@ -0,0 +1,208 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::sync::Arc;
+use core::ops::{Add, Mul, Neg, Sub};
+
+use math::StarkField;
+
+// CONSTRAINT EXPRESSION
+// ================================================================================================
+
+/// An arithmetic expression over trace columns, periodic columns, and constants.
+///
+/// Expressions are built from column references (see [ConstraintExpr::current()],
+/// [ConstraintExpr::next()], and [ConstraintExpr::periodic()]) and constants, which are combined
+/// using `+`, `-`, `*`, unary `-`, and [ConstraintExpr::exp()]. Expressions are cheap to clone:
+/// clones share their sub-expressions, and shared sub-expressions are evaluated only once when
+/// the expressions are added to [SymbolicConstraints](crate::SymbolicConstraints) via
+/// [SymbolicConstraints::insert()](crate::SymbolicConstraints::insert).
+///
+/// For example, a constraint enforcing `a' = a^3 + k * b` could be described as follows:
+/// ```
+/// # use winter_air::{ConstraintExpr, SymbolicConstraints};
+/// # use math::fields::f64::BaseElement;
+/// let a = ConstraintExpr::<BaseElement>::current(0);
+/// let b = ConstraintExpr::current(1);
+/// let k = ConstraintExpr::periodic(0);
+/// let constraint = ConstraintExpr::next(0) - (a.exp(3) + k * b);
+///
+/// let mut constraints = SymbolicConstraints::new();
+/// let node = constraints.insert(&constraint);
+/// constraints.enforce(node);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ConstraintExpr<B: StarkField>(Arc<ExprNode<B>>);
+
+impl<B: StarkField> ConstraintExpr<B> {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns an expression holding the specified constant value.
+    pub fn constant(value: B) -> Self {
+        Self::from_node(ExprNode::Constant(value))
+    }
+
+    /// Returns an expression referring to the specified column in the current row of an
+    /// evaluation frame.
+    pub fn current(column: usize) -> Self {
+        Self::trace(0, column)
+    }
+
+    /// Returns an expression referring to the specified column in the next row of an evaluation
+    /// frame.
+    pub fn next(column: usize) -> Self {
+        Self::trace(1, column)
+    }
+
+    /// Returns an expression referring to the specified column in the specified row of an
+    /// evaluation frame.
+    ///
+    /// The row is an index into the rows of the frame (see
+    /// [EvaluationFrame::row()](crate::EvaluationFrame::row)) rather than an offset from the
+    /// current row.
+    pub fn trace(row: usize, column: usize) -> Self {
+        Self::from_node(ExprNode::Trace { row, column })
+    }
+
+    /// Returns an expression referring to the specified periodic column.
+    pub fn periodic(index: usize) -> Self {
+        Self::from_node(ExprNode::Periodic(index))
+    }
+
+    // OPERATIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns an expression computing this expression raised to the specified power.
+    pub fn exp(&self, power: u32) -> Self {
+        Self::from_node(ExprNode::Exp(self.clone(), power))
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the root node of this expression.
+    pub fn node(&self) -> &ExprNode<B> {
+        &self.0
+    }
+
+    /// Returns a value which uniquely identifies the root node of this expression among all
+    /// expressions which are alive at the same time; clones of an expression have the same ID.
+    pub(crate) fn id(&self) -> usize {
+        Arc::as_ptr(&self.0) as usize
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    fn from_node(node: ExprNode<B>) -> Self {
+        Self(Arc::new(node))
+    }
+}
+
+impl<B: StarkField> From<B> for ConstraintExpr<B> {
+    fn from(value: B) -> Self {
+        Self::constant(value)
+    }
+}
+
+/// A single node of a [ConstraintExpr].
+#[derive(Debug, Clone)]
+pub enum ExprNode<B: StarkField> {
+    /// A constant value.
+    Constant(B),
+    /// Value of a trace column in one of the rows of an evaluation frame.
+    Trace { row: usize, column: usize },
+    /// Value of a periodic column.
+    Periodic(usize),
+    /// Sum of two expressions.
+    Add(ConstraintExpr<B>, ConstraintExpr<B>),
+    /// Difference of two expressions.
+    Sub(ConstraintExpr<B>, ConstraintExpr<B>),
+    /// Product of two expressions.
+    Mul(ConstraintExpr<B>, ConstraintExpr<B>),
+    /// Negation of an expression.
+    Neg(ConstraintExpr<B>),
+    /// An expression raised to a constant power.
+    Exp(ConstraintExpr<B>, u32),
+}
+
+// OPERATOR OVERLOADS
+// ================================================================================================
+
+macro_rules! impl_binary_op {
+    ($trait:ident, $method:ident, $variant:ident) => {
+        impl<B: StarkField> $trait<ConstraintExpr<B>> for ConstraintExpr<B> {
+            type Output = ConstraintExpr<B>;
+
+            fn $method(self, rhs: ConstraintExpr<B>) -> ConstraintExpr<B> {
+                ConstraintExpr::from_node(ExprNode::$variant(self, rhs))
+            }
+        }
+
+        impl<B: StarkField> $trait<&ConstraintExpr<B>> for ConstraintExpr<B> {
+            type Output = ConstraintExpr<B>;
+
+            fn $method(self, rhs: &ConstraintExpr<B>) -> ConstraintExpr<B> {
+                ConstraintExpr::from_node(ExprNode::$variant(self, rhs.clone()))
+            }
+        }
+
+        impl<B: StarkField> $trait<ConstraintExpr<B>> for &ConstraintExpr<B> {
+            type Output = ConstraintExpr<B>;
+
+            fn $method(self, rhs: ConstraintExpr<B>) -> ConstraintExpr<B> {
+                ConstraintExpr::from_node(ExprNode::$variant(self.clone(), rhs))
+            }
+        }
+
+        impl<B: StarkField> $trait<&ConstraintExpr<B>> for &ConstraintExpr<B> {
+            type Output = ConstraintExpr<B>;
+
+            fn $method(self, rhs: &ConstraintExpr<B>) -> ConstraintExpr<B> {
+                ConstraintExpr::from_node(ExprNode::$variant(self.clone(), rhs.clone()))
+            }
+        }
+
+        impl<B: StarkField> $trait<B> for ConstraintExpr<B> {
+            type Output = ConstraintExpr<B>;
+
+            fn $method(self, rhs: B) -> ConstraintExpr<B> {
+                ConstraintExpr::from_node(ExprNode::$variant(self, ConstraintExpr::constant(rhs)))
+            }
+        }
+
+        impl<B: StarkField> $trait<B> for &ConstraintExpr<B> {
+            type Output = ConstraintExpr<B>;
+
+            fn $method(self, rhs: B) -> ConstraintExpr<B> {
+                ConstraintExpr::from_node(ExprNode::$variant(
+                    self.clone(),
+                    ConstraintExpr::constant(rhs),
+                ))
+            }
+        }
+    };
+}
+
+impl_binary_op!(Add, add, Add);
+impl_binary_op!(Sub, sub, Sub);
+impl_binary_op!(Mul, mul, Mul);
+
+impl<B: StarkField> Neg for ConstraintExpr<B> {
+    type Output = ConstraintExpr<B>;
+
+    fn neg(self) -> ConstraintExpr<B> {
+        ConstraintExpr::from_node(ExprNode::Neg(self))
+    }
+}
+
+impl<B: StarkField> Neg for &ConstraintExpr<B> {
+    type Output = ConstraintExpr<B>;
+
+    fn neg(self) -> ConstraintExpr<B> {
+        ConstraintExpr::from_node(ExprNode::Neg(self.clone()))
+    }
+}
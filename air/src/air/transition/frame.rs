@@ -12,56 +12,144 @@ use super::FieldElement;
 
 /// A set of execution trace rows required for evaluation of transition constraints.
 ///
-/// In the current implementation, an evaluation frame always contains two consecutive rows of the
-/// execution trace. It is passed in as one of the parameters into
+/// An evaluation frame contains rows of the execution trace at fixed offsets from the current
+/// row. By default, the frame consists of two rows (the current and the next row); AIRs which
+/// need to look further ahead can request wider frames via
+/// [AirContext::set_num_frame_rows()](crate::AirContext::set_num_frame_rows), or frames of rows at
+/// arbitrary offsets via [AirContext::set_frame_offsets()](crate::AirContext::set_frame_offsets).
+/// A frame is passed in as one of the parameters into
 /// [Air::evaluate_transition()](crate::Air::evaluate_transition) function.
 #[derive(Debug, Clone)]
 pub struct EvaluationFrame<E: FieldElement> {
-    current: Vec<E>,
-    next: Vec<E>,
+    rows: Vec<Vec<E>>,
+    offsets: Vec<usize>,
 }
 
 impl<E: FieldElement> EvaluationFrame<E> {
     // CONSTRUCTORS
     // --------------------------------------------------------------------------------------------
 
-    /// Returns a new evaluation frame instantiated with the specified number of columns.
+    /// Returns a new evaluation frame of two rows instantiated with the specified number of
+    /// columns.
     ///
     /// # Panics
     /// Panics if `num_columns` is zero.
     pub fn new(num_columns: usize) -> Self {
+        Self::new_multi_row(num_columns, 2)
+    }
+
+    /// Returns a new evaluation frame instantiated with the specified number of columns and rows.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `num_columns` is zero.
+    /// * `num_rows` is smaller than two.
+    pub fn new_multi_row(num_columns: usize, num_rows: usize) -> Self {
+        assert!(num_rows >= 2, "number of rows must be at least two, but was {num_rows}");
+        Self::with_offsets(num_columns, (0..num_rows).collect())
+    }
+
+    /// Returns a new evaluation frame instantiated with the specified number of columns and rows
+    /// at the specified offsets from the current row.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `num_columns` is zero.
+    /// * Fewer than two offsets are provided, the first offset is not zero, or the offsets are
+    ///   not strictly increasing.
+    pub fn with_offsets(num_columns: usize, offsets: Vec<usize>) -> Self {
         assert!(num_columns > 0, "number of columns must be greater than zero");
+        validate_frame_offsets(&offsets);
         EvaluationFrame {
-            current: vec![E::ZERO; num_columns],
-            next: vec![E::ZERO; num_columns],
+            rows: vec![vec![E::ZERO; num_columns]; offsets.len()],
+            offsets,
         }
     }
 
-    /// Returns a new evaluation frame instantiated from the provided rows.
+    /// Returns a new evaluation frame instantiated from the provided current and next rows.
     ///
     /// # Panics
     /// Panics if:
     /// * Lengths of the provided rows are zero.
     /// * Lengths of the provided rows are not the same.
     pub fn from_rows(current: Vec<E>, next: Vec<E>) -> Self {
-        assert!(!current.is_empty(), "a row must contain at least one value");
-        assert_eq!(current.len(), next.len(), "number of values in the rows must be the same");
-        Self { current, next }
+        Self::from_row_window(vec![current, next])
+    }
+
+    /// Returns a new evaluation frame instantiated from the provided window of consecutive rows.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * Fewer than two rows are provided.
+    /// * Lengths of the provided rows are zero.
+    /// * Lengths of the provided rows are not the same.
+    pub fn from_row_window(rows: Vec<Vec<E>>) -> Self {
+        assert!(rows.len() >= 2, "number of rows must be at least two, but was {}", rows.len());
+        let offsets = (0..rows.len()).collect();
+        Self::from_offset_rows(rows, offsets)
+    }
+
+    /// Returns a new evaluation frame instantiated from the provided rows located at the
+    /// specified offsets from the current row.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The number of rows is different from the number of offsets.
+    /// * Fewer than two offsets are provided, the first offset is not zero, or the offsets are
+    ///   not strictly increasing.
+    /// * Lengths of the provided rows are zero.
+    /// * Lengths of the provided rows are not the same.
+    pub fn from_offset_rows(rows: Vec<Vec<E>>, offsets: Vec<usize>) -> Self {
+        validate_frame_offsets(&offsets);
+        assert_eq!(rows.len(), offsets.len(), "number of rows must match the number of offsets");
+        assert!(!rows[0].is_empty(), "a row must contain at least one value");
+        assert!(
+            rows.iter().all(|row| row.len() == rows[0].len()),
+            "number of values in the rows must be the same"
+        );
+        Self { rows, offsets }
     }
 
     // ROW ACCESSORS
     // --------------------------------------------------------------------------------------------
 
+    /// Returns the number of rows in this frame.
+    #[inline(always)]
+    pub fn num_rows(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Returns offsets of the rows in this frame relative to the current row.
+    #[inline(always)]
+    pub fn offsets(&self) -> &[usize] {
+        &self.offsets
+    }
+
     /// Returns a reference to the current row.
     #[inline(always)]
     pub fn current(&self) -> &[E] {
-        &self.current
+        &self.rows[0]
     }
 
-    /// Returns a reference to the next row.
+    /// Returns a reference to the second row of this frame.
+    ///
+    /// This is the next row unless the frame was defined with a different offset for its second
+    /// row.
     #[inline(always)]
     pub fn next(&self) -> &[E] {
-        &self.next
+        &self.rows[1]
+    }
+
+    /// Returns a reference to the row at the specified position in this frame.
+    ///
+    /// The returned row is located at offset `offsets()[index]` from the current row; for frames
+    /// of consecutive rows, the index is the same as the offset.
+    ///
+    /// # Panics
+    /// Panics if `index` is greater than or equal to the number of rows in this frame.
+    #[inline(always)]
+    pub fn row(&self, index: usize) -> &[E] {
+        &self.rows[index]
     }
 
     // DATA MUTATORS
@@ -70,12 +158,51 @@ impl<E: FieldElement> EvaluationFrame<E> {
     /// Returns a mutable reference to the current row.
     #[inline(always)]
     pub fn current_mut(&mut self) -> &mut [E] {
-        &mut self.current
+        &mut self.rows[0]
     }
 
-    /// Returns a mutable reference to the next row.
+    /// Returns a mutable reference to the second row of this frame.
     #[inline(always)]
     pub fn next_mut(&mut self) -> &mut [E] {
-        &mut self.next
+        &mut self.rows[1]
+    }
+
+    /// Returns a mutable reference to the row at the specified position in this frame.
+    ///
+    /// # Panics
+    /// Panics if `index` is greater than or equal to the number of rows in this frame.
+    #[inline(always)]
+    pub fn row_mut(&mut self, index: usize) -> &mut [E] {
+        &mut self.rows[index]
     }
 }
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Maximum number of rows in an evaluation frame; the number of rows of an out-of-domain frame is
+/// encoded using a single byte in STARK proofs.
+pub(crate) const MAX_FRAME_ROWS: usize = u8::MAX as usize;
+
+/// Makes sure the provided offsets describe a valid evaluation frame.
+///
+/// # Panics
+/// Panics if fewer than two or more than 255 offsets are provided, if the first offset is not
+/// zero, or if the offsets are not strictly increasing.
+pub(crate) fn validate_frame_offsets(offsets: &[usize]) {
+    assert!(
+        offsets.len() >= 2,
+        "number of frame rows must be at least two, but was {}",
+        offsets.len()
+    );
+    assert!(
+        offsets.len() <= MAX_FRAME_ROWS,
+        "number of frame rows cannot exceed {MAX_FRAME_ROWS}, but was {}",
+        offsets.len()
+    );
+    assert_eq!(offsets[0], 0, "the first frame row must be the current row");
+    assert!(
+        offsets.windows(2).all(|w| w[0] < w[1]),
+        "frame row offsets must be strictly increasing"
+    );
+}
@@ -8,11 +8,18 @@ use alloc::vec::Vec;
 use super::{AirContext, ConstraintDivisor, ExtensionOf, FieldElement};
 
 mod frame;
+pub(crate) use frame::validate_frame_offsets;
 pub use frame::EvaluationFrame;
 
 mod degree;
 pub use degree::TransitionConstraintDegree;
 
+mod expr;
+pub use expr::{ConstraintExpr, ExprNode};
+
+mod symbolic;
+pub use symbolic::{NodeId, SymbolicConstraints, SymbolicNode};
+
 // CONSTANTS
 // ================================================================================================
 
@@ -28,13 +35,16 @@ const MIN_CYCLE_LENGTH: usize = 2;
 ///   trace segments (if any).
 /// - Groupings of random composition constraint coefficients separately for the main trace segment
 ///   and for auxiliary tace segment.
-/// - Divisor of transition constraints for a computation.
+/// - Divisors of transition constraints for a computation, together with the mapping of each
+///   constraint to its divisor.
 pub struct TransitionConstraints<E: FieldElement> {
     main_constraint_coef: Vec<E>,
     main_constraint_degrees: Vec<TransitionConstraintDegree>,
     aux_constraint_coef: Vec<E>,
     aux_constraint_degrees: Vec<TransitionConstraintDegree>,
-    divisor: ConstraintDivisor<E::BaseField>,
+    divisors: Vec<ConstraintDivisor<E::BaseField>>,
+    main_divisor_indices: Vec<usize>,
+    aux_divisor_indices: Vec<usize>,
 }
 
 impl<E: FieldElement> TransitionConstraints<E> {
@@ -53,11 +63,28 @@ impl<E: FieldElement> TransitionConstraints<E> {
             "number of transition constraints must match the number of composition coefficient tuples"
         );
 
-        // build constraint divisor; the same divisor applies to all transition constraints
-        let divisor = ConstraintDivisor::from_transition(
+        // build constraint divisors; the default divisor is always at index 0, and each distinct
+        // custom divisor is included only once
+        let mut divisors = vec![ConstraintDivisor::from_transition(
             context.trace_len(),
             context.num_transition_exemptions(),
-        );
+        )];
+        let mut divisor_indices = Vec::with_capacity(context.num_transition_constraints());
+        for i in 0..context.num_transition_constraints() {
+            let divisor_idx = match context.transition_divisor(i) {
+                Some(divisor) => match divisors.iter().position(|d| d == divisor) {
+                    Some(idx) => idx,
+                    None => {
+                        divisors.push(divisor.clone());
+                        divisors.len() - 1
+                    },
+                },
+                None => 0,
+            };
+            divisor_indices.push(divisor_idx);
+        }
+        let aux_divisor_indices =
+            divisor_indices.split_off(context.main_transition_constraint_degrees.len());
 
         let main_constraint_degrees = context.main_transition_constraint_degrees.clone();
         let aux_constraint_degrees = context.aux_transition_constraint_degrees.clone();
@@ -69,7 +96,9 @@ impl<E: FieldElement> TransitionConstraints<E> {
             main_constraint_degrees,
             aux_constraint_coef: aux_constraint_coef.to_vec(),
             aux_constraint_degrees,
-            divisor,
+            divisors,
+            main_divisor_indices: divisor_indices,
+            aux_divisor_indices,
         }
     }
 
@@ -118,9 +147,11 @@ impl<E: FieldElement> TransitionConstraints<E> {
         self.aux_constraint_coef.clone()
     }
 
-    /// Returns a divisor for transition constraints.
+    /// Returns the default divisor for transition constraints.
     ///
-    /// All transition constraints have the same divisor which has the form:
+    /// Unless a custom divisor was set for a constraint via
+    /// [AirContext::set_transition_divisor()], all transition constraints have the same divisor
+    /// which has the form:
     /// $$
     /// z(x) = \frac{x^n - 1}{x - g^{n - 1}}
     /// $$
@@ -130,14 +161,34 @@ impl<E: FieldElement> TransitionConstraints<E> {
     /// This divisor specifies that transition constraints must hold on all steps of the
     /// execution trace except for the last one.
     pub fn divisor(&self) -> &ConstraintDivisor<E::BaseField> {
-        &self.divisor
+        &self.divisors[0]
+    }
+
+    /// Returns all distinct divisors of transition constraints.
+    ///
+    /// The default divisor (see [TransitionConstraints::divisor()]) is always the first divisor
+    /// in the list; it is followed by the custom divisors set in the AIR context, if any.
+    pub fn divisors(&self) -> &[ConstraintDivisor<E::BaseField>] {
+        &self.divisors
+    }
+
+    /// Returns positions in [TransitionConstraints::divisors()] of the divisors for constraints
+    /// applied against the main trace segment of a computation.
+    pub fn main_divisor_indices(&self) -> &[usize] {
+        &self.main_divisor_indices
+    }
+
+    /// Returns positions in [TransitionConstraints::divisors()] of the divisors for constraints
+    /// applied against the auxiliary trace segment of a computation.
+    pub fn aux_divisor_indices(&self) -> &[usize] {
+        &self.aux_divisor_indices
     }
 
     // CONSTRAINT COMPOSITION
     // --------------------------------------------------------------------------------------------
 
-    /// Computes a linear combination of all transition constraint evaluations and divides the
-    /// result by transition constraint divisor.
+    /// Computes a linear combination of all transition constraint evaluations divided by their
+    /// respective transition constraint divisors.
     ///
     /// A transition constraint is described by a rational function of the form $\frac{C(x)}{z(x)}$,
     /// where:
@@ -146,30 +197,39 @@ impl<E: FieldElement> TransitionConstraints<E> {
     ///
     /// Thus, this function computes a linear combination of $C(x)$ evaluations.
     ///
-    /// Since, the divisor polynomial is the same for all transition constraints (see
-    /// [ConstraintDivisor::from_transition]), we can divide the linear combination by the
-    /// divisor rather than dividing each individual $C(x)$ evaluation. This requires executing only
-    /// one division at the end.
+    /// Since, the divisor polynomial is usually the same for many transition constraints (see
+    /// [ConstraintDivisor::from_transition]), we group constraints by divisor and divide the
+    /// linear combination for each group by its divisor rather than dividing each individual
+    /// $C(x)$ evaluation. This requires executing only one division per distinct divisor.
     pub fn combine_evaluations<F>(&self, main_evaluations: &[F], aux_evaluations: &[E], x: F) -> E
     where
         F: FieldElement<BaseField = E::BaseField>,
         E: ExtensionOf<F>,
     {
+        let mut results = vec![E::ZERO; self.divisors.len()];
+
         // merge constraint evaluations for the main trace segment
-        let mut result = main_evaluations
+        for ((&const_eval, &coef), &divisor_idx) in main_evaluations
             .iter()
             .zip(self.main_constraint_coef.iter())
-            .fold(E::ZERO, |acc, (&const_eval, &coef)| acc + coef.mul_base(const_eval));
-
-        if !self.aux_constraint_coef.is_empty() {
-            result += aux_evaluations
-                .iter()
-                .zip(self.aux_constraint_coef.iter())
-                .fold(E::ZERO, |acc, (&const_eval, &coef)| acc + coef * const_eval);
-        };
-        // divide out the evaluation of divisor at x and return the result
-        let z = E::from(self.divisor.evaluate_at(x));
-
-        result / z
+            .zip(self.main_divisor_indices.iter())
+        {
+            results[divisor_idx] += coef.mul_base(const_eval);
+        }
+
+        // merge constraint evaluations for the auxiliary trace segment
+        for ((&const_eval, &coef), &divisor_idx) in aux_evaluations
+            .iter()
+            .zip(self.aux_constraint_coef.iter())
+            .zip(self.aux_divisor_indices.iter())
+        {
+            results[divisor_idx] += coef * const_eval;
+        }
+
+        // divide out the evaluation of each divisor at x and return the sum of the results
+        results
+            .into_iter()
+            .zip(self.divisors.iter())
+            .fold(E::ZERO, |acc, (result, divisor)| acc + result / E::from(divisor.evaluate_at(x)))
     }
 }
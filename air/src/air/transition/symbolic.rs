@@ -0,0 +1,476 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::{collections::BTreeMap, vec::Vec};
+use core::cmp::Ordering;
+
+use math::StarkField;
+
+use super::{ConstraintExpr, EvaluationFrame, ExprNode, FieldElement, TransitionConstraintDegree};
+
+// SYMBOLIC CONSTRAINTS
+// ================================================================================================
+
+/// Symbolic representation of transition constraints over the main segment of an execution
+/// trace.
+///
+/// Symbolic constraints are described by a list of nodes, where each node is either a leaf (a
+/// constant, a value of a trace column in one of the rows of an evaluation frame, or a value of
+/// a periodic column), or an arithmetic operation over previously defined nodes. A subset of the
+/// nodes are marked as constraints via [SymbolicConstraints::enforce()], and the evaluations of
+/// these nodes are the evaluations of transition constraints (in the order in which the nodes were
+/// marked).
+///
+/// Constraints can also be described by [ConstraintExpr] expressions, which are added to the
+/// nodes via [SymbolicConstraints::insert()]. Degrees of the constraints can be inferred from
+/// their nodes via [SymbolicConstraints::degrees()], and an [AirContext](crate::AirContext) for
+/// the constraints can be built via
+/// [AirContext::from_symbolic_constraints()](crate::AirContext::from_symbolic_constraints).
+///
+/// An AIR can return its symbolic constraints from
+/// [Air::get_symbolic_constraints()](crate::Air::get_symbolic_constraints). In such a case, the
+/// prover interprets the constraints over batches of rows instead of calling
+/// [Air::evaluate_transition()](crate::Air::evaluate_transition) for every step of the
+/// constraint evaluation domain. The verifier still calls
+/// [Air::evaluate_transition()](crate::Air::evaluate_transition), which can be implemented by
+/// evaluating the constraints via [SymbolicConstraints::evaluate()].
+///
+/// For example, constraints for a Fibonacci sequence in two columns could be described as follows:
+/// ```
+/// # use winter_air::SymbolicConstraints;
+/// # use math::fields::f128::BaseElement;
+/// let mut constraints = SymbolicConstraints::<BaseElement>::new();
+/// let (a, b) = (constraints.current(0), constraints.current(1));
+/// let (a_next, b_next) = (constraints.next(0), constraints.next(1));
+///
+/// // a' = a + b
+/// let a_sum = constraints.add(a, b);
+/// let c0 = constraints.sub(a_next, a_sum);
+/// constraints.enforce(c0);
+///
+/// // b' = a' + b
+/// let b_sum = constraints.add(a_next, b);
+/// let c1 = constraints.sub(b_next, b_sum);
+/// constraints.enforce(c1);
+///
+/// assert_eq!(2, constraints.num_constraints());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SymbolicConstraints<B: StarkField> {
+    nodes: Vec<SymbolicNode<B>>,
+    constraints: Vec<NodeId>,
+    periods: Vec<Option<(usize, usize)>>,
+}
+
+impl<B: StarkField> SymbolicConstraints<B> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new empty set of symbolic constraints.
+    pub fn new() -> Self {
+        Self {
+            nodes: Vec::new(),
+            constraints: Vec::new(),
+            periods: Vec::new(),
+        }
+    }
+
+    // LEAF NODES
+    // --------------------------------------------------------------------------------------------
+
+    /// Adds a node holding the specified constant value and returns its ID.
+    pub fn constant(&mut self, value: B) -> NodeId {
+        self.push(SymbolicNode::Constant(value))
+    }
+
+    /// Adds a node holding the value of the specified column in the current row of an evaluation
+    /// frame and returns its ID.
+    pub fn current(&mut self, column: usize) -> NodeId {
+        self.trace(0, column)
+    }
+
+    /// Adds a node holding the value of the specified column in the next row of an evaluation
+    /// frame and returns its ID.
+    pub fn next(&mut self, column: usize) -> NodeId {
+        self.trace(1, column)
+    }
+
+    /// Adds a node holding the value of the specified column in the specified row of an
+    /// evaluation frame and returns its ID.
+    ///
+    /// The row is an index into the rows of the frame (see
+    /// [EvaluationFrame::row()](crate::EvaluationFrame::row)) rather than an offset from the
+    /// current row.
+    pub fn trace(&mut self, row: usize, column: usize) -> NodeId {
+        self.push(SymbolicNode::Trace { row, column })
+    }
+
+    /// Adds a node holding the value of the specified periodic column and returns its ID.
+    pub fn periodic(&mut self, index: usize) -> NodeId {
+        self.push(SymbolicNode::Periodic(index))
+    }
+
+    // OPERATION NODES
+    // --------------------------------------------------------------------------------------------
+
+    /// Adds a node computing `lhs + rhs` and returns its ID.
+    ///
+    /// # Panics
+    /// Panics if any of the operands is not a node of these constraints.
+    pub fn add(&mut self, lhs: NodeId, rhs: NodeId) -> NodeId {
+        self.push(SymbolicNode::Add(lhs, rhs))
+    }
+
+    /// Adds a node computing `lhs - rhs` and returns its ID.
+    ///
+    /// # Panics
+    /// Panics if any of the operands is not a node of these constraints.
+    pub fn sub(&mut self, lhs: NodeId, rhs: NodeId) -> NodeId {
+        self.push(SymbolicNode::Sub(lhs, rhs))
+    }
+
+    /// Adds a node computing `lhs * rhs` and returns its ID.
+    ///
+    /// # Panics
+    /// Panics if any of the operands is not a node of these constraints.
+    pub fn mul(&mut self, lhs: NodeId, rhs: NodeId) -> NodeId {
+        self.push(SymbolicNode::Mul(lhs, rhs))
+    }
+
+    /// Adds a node computing `-value` and returns its ID.
+    ///
+    /// # Panics
+    /// Panics if the operand is not a node of these constraints.
+    pub fn neg(&mut self, value: NodeId) -> NodeId {
+        self.push(SymbolicNode::Neg(value))
+    }
+
+    /// Adds a node computing `base^power` and returns its ID.
+    ///
+    /// # Panics
+    /// Panics if the operand is not a node of these constraints.
+    pub fn exp(&mut self, base: NodeId, power: u32) -> NodeId {
+        self.push(SymbolicNode::Exp(base, power))
+    }
+
+    /// Marks the specified node as a transition constraint.
+    ///
+    /// # Panics
+    /// Panics if the specified node is not a node of these constraints.
+    pub fn enforce(&mut self, value: NodeId) {
+        self.check_node(value);
+        self.constraints.push(value);
+        self.periods.push(None);
+    }
+
+    /// Marks the specified node as a transition constraint which is enforced only on every
+    /// `period`-th step of the execution trace starting with `first_step`.
+    ///
+    /// The restriction is applied by assigning a custom divisor to the constraint when an
+    /// [AirContext](crate::AirContext) is built via
+    /// [AirContext::from_symbolic_constraints()](crate::AirContext::from_symbolic_constraints)
+    /// (see [AirContext::set_transition_period()](crate::AirContext::set_transition_period)).
+    ///
+    /// # Panics
+    /// Panics if the specified node is not a node of these constraints.
+    pub fn enforce_on_period(&mut self, value: NodeId, first_step: usize, period: usize) {
+        self.check_node(value);
+        self.constraints.push(value);
+        self.periods.push(Some((first_step, period)));
+    }
+
+    // EXPRESSIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// Adds nodes computing the specified expression and returns the ID of the node holding the
+    /// value of the expression.
+    ///
+    /// Sub-expressions shared within the expression (i.e., clones of the same expression) are
+    /// added only once.
+    pub fn insert(&mut self, expr: &ConstraintExpr<B>) -> NodeId {
+        // expressions are traversed in post-order using an explicit stack, so that deeply nested
+        // expressions (e.g., long sums) do not overflow the call stack; an expression is pushed
+        // onto the stack twice: first to schedule its children, and then to add its own node.
+        let mut inserted: BTreeMap<usize, NodeId> = BTreeMap::new();
+        let mut stack = vec![(expr, false)];
+        while let Some((expr, children_inserted)) = stack.pop() {
+            if inserted.contains_key(&expr.id()) {
+                continue;
+            }
+
+            if !children_inserted {
+                stack.push((expr, true));
+                match expr.node() {
+                    ExprNode::Add(lhs, rhs) | ExprNode::Sub(lhs, rhs) | ExprNode::Mul(lhs, rhs) => {
+                        stack.push((rhs, false));
+                        stack.push((lhs, false));
+                    },
+                    ExprNode::Neg(value) | ExprNode::Exp(value, _) => stack.push((value, false)),
+                    _ => (),
+                }
+                continue;
+            }
+
+            let id = |expr: &ConstraintExpr<B>| inserted[&expr.id()];
+            let node = match expr.node() {
+                ExprNode::Constant(value) => SymbolicNode::Constant(*value),
+                ExprNode::Trace { row, column } => {
+                    SymbolicNode::Trace { row: *row, column: *column }
+                },
+                ExprNode::Periodic(index) => SymbolicNode::Periodic(*index),
+                ExprNode::Add(lhs, rhs) => SymbolicNode::Add(id(lhs), id(rhs)),
+                ExprNode::Sub(lhs, rhs) => SymbolicNode::Sub(id(lhs), id(rhs)),
+                ExprNode::Mul(lhs, rhs) => SymbolicNode::Mul(id(lhs), id(rhs)),
+                ExprNode::Neg(value) => SymbolicNode::Neg(id(value)),
+                ExprNode::Exp(value, power) => SymbolicNode::Exp(id(value), *power),
+            };
+            let node = self.push(node);
+            inserted.insert(expr.id(), node);
+        }
+
+        inserted[&expr.id()]
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns all nodes of these constraints; each node refers only to the nodes which precede
+    /// it in the list.
+    pub fn nodes(&self) -> &[SymbolicNode<B>] {
+        &self.nodes
+    }
+
+    /// Returns IDs of the nodes marked as transition constraints.
+    pub fn constraints(&self) -> &[NodeId] {
+        &self.constraints
+    }
+
+    /// Returns the number of transition constraints described by these symbolic constraints.
+    pub fn num_constraints(&self) -> usize {
+        self.constraints.len()
+    }
+
+    /// Returns the first step and the period of steps on which the constraint at the specified
+    /// index is enforced, or `None` if the constraint is enforced on all steps.
+    ///
+    /// # Panics
+    /// Panics if `constraint_idx` is not smaller than the number of constraints.
+    pub fn period(&self, constraint_idx: usize) -> Option<(usize, usize)> {
+        self.periods[constraint_idx]
+    }
+
+    // DEGREE INFERENCE
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns degree descriptors of these constraints for an execution trace of the specified
+    /// length.
+    ///
+    /// Degrees are inferred from the structure of the nodes: trace columns have degree one,
+    /// periodic columns contribute their cycles (i.e., the numbers of values in the columns, as
+    /// specified by `periodic_column_lengths`), multiplication combines degrees of the operands,
+    /// and the degree of a sum or a difference is the larger of the degrees of the operands when
+    /// evaluated for the specified trace length. Thus, the inferred degrees do not account for
+    /// cancellations of the leading terms of the constraints.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * Any of the nodes refers to a periodic column for which the length is not specified.
+    /// * Any of the constraints does not depend on trace columns, or its degree is dominated by
+    ///   periodic columns (such degrees cannot be described by [TransitionConstraintDegree]).
+    pub fn degrees(
+        &self,
+        trace_length: usize,
+        periodic_column_lengths: &[usize],
+    ) -> Vec<TransitionConstraintDegree> {
+        let mut degrees: Vec<NodeDegree> = Vec::with_capacity(self.nodes.len());
+        for node in self.nodes.iter() {
+            let degree = match *node {
+                SymbolicNode::Constant(_) => NodeDegree::default(),
+                SymbolicNode::Trace { .. } => NodeDegree { base: 1, cycles: Vec::new() },
+                SymbolicNode::Periodic(index) => {
+                    let length = periodic_column_lengths.get(index).unwrap_or_else(|| {
+                        panic!("length of periodic column {index} is not specified")
+                    });
+                    NodeDegree { base: 0, cycles: vec![*length] }
+                },
+                SymbolicNode::Add(lhs, rhs) | SymbolicNode::Sub(lhs, rhs) => {
+                    let (lhs, rhs) = (&degrees[lhs.0], &degrees[rhs.0]);
+                    match lhs.evaluate(trace_length).cmp(&rhs.evaluate(trace_length)) {
+                        Ordering::Less => rhs.clone(),
+                        _ => lhs.clone(),
+                    }
+                },
+                SymbolicNode::Mul(lhs, rhs) => {
+                    let (lhs, rhs) = (&degrees[lhs.0], &degrees[rhs.0]);
+                    let mut cycles = lhs.cycles.clone();
+                    cycles.extend_from_slice(&rhs.cycles);
+                    NodeDegree { base: lhs.base + rhs.base, cycles }
+                },
+                SymbolicNode::Neg(value) => degrees[value.0].clone(),
+                SymbolicNode::Exp(value, power) => {
+                    let value = &degrees[value.0];
+                    let mut cycles = Vec::with_capacity(value.cycles.len() * power as usize);
+                    for _ in 0..power {
+                        cycles.extend_from_slice(&value.cycles);
+                    }
+                    NodeDegree {
+                        base: value.base * power as usize,
+                        cycles,
+                    }
+                },
+            };
+            degrees.push(degree);
+        }
+
+        self.constraints
+            .iter()
+            .enumerate()
+            .map(|(i, node)| {
+                let degree = &degrees[node.0];
+                assert!(
+                    degree.base > 0,
+                    "degree of transition constraint {i} cannot be described since the constraint \
+                    does not depend on trace columns or its degree is dominated by periodic columns"
+                );
+                TransitionConstraintDegree::with_cycles(degree.base, degree.cycles.clone())
+            })
+            .collect()
+    }
+
+    // EVALUATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Evaluates these constraints over the specified evaluation frame and periodic values, and
+    /// writes the results into the `result` slice.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The length of `result` is not equal to the number of constraints.
+    /// * Any of the nodes refers to a row, a column, or a periodic column not present in the
+    ///   provided frame or periodic values.
+    pub fn evaluate<E: FieldElement<BaseField = B>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        assert_eq!(
+            self.constraints.len(),
+            result.len(),
+            "expected {} constraint evaluations, but result slice has length {}",
+            self.constraints.len(),
+            result.len()
+        );
+
+        let mut values: Vec<E> = Vec::with_capacity(self.nodes.len());
+        for node in self.nodes.iter() {
+            let value = match *node {
+                SymbolicNode::Constant(value) => E::from(value),
+                SymbolicNode::Trace { row, column } => frame.row(row)[column],
+                SymbolicNode::Periodic(index) => periodic_values[index],
+                SymbolicNode::Add(lhs, rhs) => values[lhs.0] + values[rhs.0],
+                SymbolicNode::Sub(lhs, rhs) => values[lhs.0] - values[rhs.0],
+                SymbolicNode::Mul(lhs, rhs) => values[lhs.0] * values[rhs.0],
+                SymbolicNode::Neg(value) => -values[value.0],
+                SymbolicNode::Exp(base, power) => values[base.0].exp(power.into()),
+            };
+            values.push(value);
+        }
+
+        for (result, node) in result.iter_mut().zip(self.constraints.iter()) {
+            *result = values[node.0];
+        }
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Appends the specified node to the list of nodes and returns its ID.
+    fn push(&mut self, node: SymbolicNode<B>) -> NodeId {
+        match node {
+            SymbolicNode::Add(lhs, rhs)
+            | SymbolicNode::Sub(lhs, rhs)
+            | SymbolicNode::Mul(lhs, rhs) => {
+                self.check_node(lhs);
+                self.check_node(rhs);
+            },
+            SymbolicNode::Neg(value) | SymbolicNode::Exp(value, _) => self.check_node(value),
+            _ => (),
+        }
+        self.nodes.push(node);
+        NodeId(self.nodes.len() - 1)
+    }
+
+    /// Makes sure the specified ID refers to an existing node.
+    fn check_node(&self, node: NodeId) {
+        assert!(
+            node.0 < self.nodes.len(),
+            "node {} does not exist; the constraints have {} nodes",
+            node.0,
+            self.nodes.len()
+        );
+    }
+}
+
+impl<B: StarkField> Default for SymbolicConstraints<B> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// SYMBOLIC NODE
+// ================================================================================================
+
+/// Degree of a node expressed in the same way as [TransitionConstraintDegree], except that the
+/// base degree can be zero.
+#[derive(Debug, Clone, Default)]
+struct NodeDegree {
+    base: usize,
+    cycles: Vec<usize>,
+}
+
+impl NodeDegree {
+    /// Returns the degree of the node for an execution trace of the specified length (see
+    /// [TransitionConstraintDegree::get_evaluation_degree()]).
+    fn evaluate(&self, trace_length: usize) -> usize {
+        let mut result = self.base * (trace_length - 1);
+        for cycle_length in self.cycles.iter() {
+            result += (trace_length / cycle_length) * (cycle_length - 1);
+        }
+        result
+    }
+}
+
+/// ID of a node in [SymbolicConstraints].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NodeId(usize);
+
+impl NodeId {
+    /// Returns the index of this node in the list of nodes of the constraints it belongs to.
+    pub fn index(&self) -> usize {
+        self.0
+    }
+}
+
+/// A single node of [SymbolicConstraints].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SymbolicNode<B: StarkField> {
+    /// A constant value.
+    Constant(B),
+    /// Value of a trace column in one of the rows of an evaluation frame.
+    Trace { row: usize, column: usize },
+    /// Value of a periodic column.
+    Periodic(usize),
+    /// Sum of two nodes.
+    Add(NodeId, NodeId),
+    /// Difference of two nodes.
+    Sub(NodeId, NodeId),
+    /// Product of two nodes.
+    Mul(NodeId, NodeId),
+    /// Negation of a node.
+    Neg(NodeId),
+    /// A node raised to a constant power.
+    Exp(NodeId, u32),
+}
@@ -38,15 +38,20 @@ mod errors;
 pub use errors::AssertionError;
 
 mod options;
-pub use options::{FieldExtension, PartitionOptions, ProofOptions};
+pub use options::{
+    CoefficientDerivation, FieldExtension, OptionsExtension, PartitionOptions, ProofOptions,
+    SoundnessMode, UnknownExtensionPolicy,
+};
 
 mod air;
 pub use air::{
-    Air, AirContext, Assertion, AuxRandElements, BoundaryConstraint, BoundaryConstraintGroup,
-    BoundaryConstraints, ConstraintCompositionCoefficients, ConstraintDivisor,
-    DeepCompositionCoefficients, EvaluationFrame, GkrRandElements, GkrVerifier,
-    LagrangeConstraintsCompositionCoefficients, LagrangeKernelBoundaryConstraint,
-    LagrangeKernelConstraints, LagrangeKernelEvaluationFrame, LagrangeKernelRandElements,
-    LagrangeKernelTransitionConstraints, TraceInfo, TransitionConstraintDegree,
-    TransitionConstraints,
+    pad_periodic_column, Accumulator, AccumulatorKind, AccumulatorTerm, Air, AirContext, Assertion,
+    AuxRandElements, BatchAir, BatchPublicInputs, BoundaryConstraint, BoundaryConstraintGroup,
+    BoundaryConstraints, ComposedAir, ComposedPublicInputs, ConstraintCompositionCoefficients,
+    ConstraintDivisor, ConstraintExpr, DeepCompositionCoefficients, EvaluationFrame, ExprNode,
+    GkrRandElements, GkrVerifier, LagrangeConstraintsCompositionCoefficients,
+    LagrangeKernelBoundaryConstraint, LagrangeKernelConstraints, LagrangeKernelEvaluationFrame,
+    LagrangeKernelRandElements, LagrangeKernelTransitionConstraints, LogUp, LookupTable, NodeId,
+    Permutation, Program, PublicInputs, SymbolicConstraints, SymbolicNode, TraceInfo,
+    TransitionConstraintDegree, TransitionConstraints,
 };
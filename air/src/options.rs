@@ -10,6 +10,8 @@ use fri::FriOptions;
 use math::{FieldElement, StarkField, ToElements};
 use utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
 
+use crate::proof::get_security_level;
+
 // CONSTANTS
 // ================================================================================================
 
@@ -25,6 +27,67 @@ const MAX_GRINDING_FACTOR: u32 = 32;
 const FRI_MIN_FOLDING_FACTOR: usize = 2;
 const FRI_MAX_FOLDING_FACTOR: usize = 16;
 const FRI_MAX_REMAINDER_DEGREE: usize = 255;
+const FRI_MAX_EXTENDED_REMAINDER_DEGREE: usize = (1 << FRI_MAX_EXTENDED_REMAINDER_LOG_SIZE) - 1;
+const FRI_MIN_EXTENDED_REMAINDER_LOG_SIZE: u8 = 9;
+const FRI_MAX_EXTENDED_REMAINDER_LOG_SIZE: u8 = 16;
+
+/// Version of the encoding used for serializing proof options extensions.
+const EXTENSIONS_VERSION: u8 = 1;
+
+const MAX_NUM_EXTENSIONS: usize = 255;
+const MAX_EXTENSION_SIZE: usize = u16::MAX as usize;
+
+/// IDs of the extensions whose semantics are understood by this version of the library. Proof
+/// options may carry extensions not listed here; how these are treated by the verifier is defined
+/// by [UnknownExtensionPolicy].
+const KNOWN_EXTENSIONS: &[u16] = &[
+    DIGEST_SIZE_EXTENSION,
+    TRACE_DOMAIN_QUERY_EXCLUSION_EXTENSION,
+    FRI_REMAINDER_DEGREE_EXTENSION,
+    DEEP_FRI_EXTENSION,
+    SOUNDNESS_MODE_EXTENSION,
+    FRI_FOLDING_SCHEDULE_EXTENSION,
+    FRI_LAYER_SKIPPING_EXTENSION,
+    FRI_DIGEST_SIZE_EXTENSION,
+    ZK_EXTENSION,
+];
+
+/// ID of the extension specifying the size of digests used in vector commitments.
+const DIGEST_SIZE_EXTENSION: u16 = 1;
+
+/// ID of the extension specifying that query positions which map onto trace domain points must
+/// not be sampled.
+const TRACE_DOMAIN_QUERY_EXCLUSION_EXTENSION: u16 = 2;
+
+/// ID of the extension specifying the maximum degree of the FRI remainder polynomial when it is
+/// greater than 255; the data contains log2(max_degree + 1).
+const FRI_REMAINDER_DEGREE_EXTENSION: u16 = 3;
+
+/// ID of the extension specifying that FRI folding challenges are sampled using the DEEP variant
+/// of FRI.
+const DEEP_FRI_EXTENSION: u16 = 4;
+
+/// ID of the extension specifying the soundness mode and the target security level of a proof;
+/// the data contains the mode followed by the security level (in bits).
+const SOUNDNESS_MODE_EXTENSION: u16 = 5;
+
+/// ID of the extension specifying folding factors of the first FRI layers; the data contains one
+/// folding factor per layer.
+const FRI_FOLDING_SCHEDULE_EXTENSION: u16 = 6;
+
+/// ID of the extension specifying the maximum degree of polynomials for which FRI layers are
+/// skipped; the data contains log2(max_degree + 1).
+const FRI_LAYER_SKIPPING_EXTENSION: u16 = 7;
+
+/// ID of the extension specifying the size of digests used in FRI layer commitments when it
+/// differs from the size of digests used in trace and constraint commitments.
+const FRI_DIGEST_SIZE_EXTENSION: u16 = 8;
+
+/// ID of the extension specifying that proofs are generated in zero-knowledge mode.
+const ZK_EXTENSION: u16 = 9;
+
+const MIN_DIGEST_SIZE: usize = 16;
+const MAX_DIGEST_SIZE: usize = 32;
 
 // TYPES AND INTERFACES
 // ================================================================================================
@@ -53,6 +116,51 @@ pub enum FieldExtension {
     Cubic = 3,
 }
 
+/// Defines how constraint composition and DEEP composition coefficients are derived from the
+/// public coin.
+///
+/// With [CoefficientDerivation::Sequential], each coefficient is drawn from the public coin
+/// directly, and thus the transcript is advanced once per coefficient.
+///
+/// With [CoefficientDerivation::Expanded], a single seed element $s$ is drawn from the public coin,
+/// and all coefficients are then expanded from $s$ using the following KDF:
+///
+/// 1. $s$ is hashed into a digest $d = H(s)$, where $H$ is the hash function of the public coin
+///    and $s$ is absorbed as a sequence of base field elements.
+/// 2. For $i = 0, 1, 2, \ldots$ a candidate value $c_i = H(d \| i)$ is computed (this is the
+///    `merge_with_int()` operation of the hasher), and the coefficient is set to the field element
+///    encoded by the first bytes of $c_i$. Candidates which do not encode a valid field element are
+///    skipped.
+///
+/// This reduces the number of operations on the transcript to one per group of coefficients, which
+/// is useful when the verifier is itself being proven (e.g., in recursive settings), and makes the
+/// derivation easier to replicate in external implementations.
+#[repr(u8)]
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub enum CoefficientDerivation {
+    /// Each coefficient is drawn from the public coin directly.
+    #[default]
+    Sequential = 0,
+    /// All coefficients are expanded from a single seed drawn from the public coin.
+    Expanded = 1,
+}
+
+/// Defines which soundness bounds are used to estimate the security level of a proof.
+///
+/// Conjectured bounds rely on the conjecture that FRI is sound up to the list-decoding capacity
+/// bound, and thus, require fewer queries for the same security level. Proven bounds rely only on
+/// the results proven for FRI in the list-decoding regime (see
+/// [Proof::security_level()](crate::proof::Proof::security_level)), and usually require 2x - 3x
+/// more queries than conjectured bounds.
+#[repr(u8)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum SoundnessMode {
+    /// Security level is estimated using conjectured soundness bounds.
+    Conjectured = 0,
+    /// Security level is estimated using proven soundness bounds.
+    Proven = 1,
+}
+
 /// STARK protocol parameters.
 ///
 /// These parameters have a direct impact on proof soundness, proof generation time, and proof
@@ -83,6 +191,18 @@ pub enum FieldExtension {
 /// `num_partitions` digests (per row) are combined into one digest (per row) and at this point
 /// a vector commitment scheme can be called. In the case when `num_partitions` is equal to `1` (default)
 /// the prover will hash each row in one go producing one digest per row of the trace.
+///
+/// The way in which composition coefficients are derived from the public coin can be set via
+/// [CoefficientDerivation] (by default, coefficients are drawn sequentially).
+///
+/// Canonical proving can be requested (see [ProofOptions::with_canonical_proving()]) to make
+/// sure that the same proof bytes are produced for the same inputs regardless of the machine or
+/// the number of threads used to generate the proof.
+///
+/// Lastly, proof options may carry a set of [OptionsExtension]s. These are used for parameters
+/// which were added to the protocol after the base set of options was fixed: each extension is
+/// length-prefixed, and thus, readers can deserialize proof options containing extensions they
+/// do not understand.
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct ProofOptions {
     num_queries: u8,
@@ -92,6 +212,9 @@ pub struct ProofOptions {
     fri_folding_factor: u8,
     fri_remainder_max_degree: u8,
     partition_options: PartitionOptions,
+    coefficient_derivation: CoefficientDerivation,
+    canonical: bool,
+    extensions: Vec<OptionsExtension>,
 }
 
 // PROOF OPTIONS IMPLEMENTATION
@@ -166,6 +289,9 @@ impl ProofOptions {
             fri_folding_factor: fri_folding_factor as u8,
             fri_remainder_max_degree: fri_remainder_max_degree as u8,
             partition_options: PartitionOptions::new(1, 1),
+            coefficient_derivation: CoefficientDerivation::Sequential,
+            canonical: false,
+            extensions: Vec::new(),
         }
     }
 
@@ -185,6 +311,291 @@ impl ProofOptions {
         self
     }
 
+    /// Updates the provided [ProofOptions] instance with the specified method of deriving
+    /// constraint composition and DEEP composition coefficients.
+    pub const fn with_coefficient_derivation(
+        mut self,
+        coefficient_derivation: CoefficientDerivation,
+    ) -> ProofOptions {
+        self.coefficient_derivation = coefficient_derivation;
+
+        self
+    }
+
+    /// Updates the provided [ProofOptions] instance to enable or disable canonical proving.
+    ///
+    /// When canonical proving is enabled, the prover fixes every source of nondeterminism, and
+    /// thus, generates exactly the same proof bytes for the same execution trace, public inputs,
+    /// and proof options across machines and thread counts. Specifically, the proof-of-work nonce
+    /// for query seed grinding is always set to the smallest valid nonce (rather than to the first
+    /// nonce found by any of the threads). This may make grinding slightly slower in
+    /// multi-threaded mode.
+    ///
+    /// Canonical proving cannot be combined with zero-knowledge mode or with hiding vector
+    /// commitments (e.g., salted Merkle trees), since these rely on fresh randomness; the prover
+    /// rejects such combinations with an error.
+    ///
+    /// Canonical proving does not affect proof verification.
+    pub const fn with_canonical_proving(mut self, canonical: bool) -> ProofOptions {
+        self.canonical = canonical;
+
+        self
+    }
+
+    /// Updates the provided [ProofOptions] instance with the specified extension.
+    ///
+    /// Extensions are kept sorted by their IDs so that the same set of extensions always results
+    /// in the same serialized proof options.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// - An extension with the same ID has already been added to these proof options.
+    /// - The number of extensions would become greater than 255.
+    pub fn with_extension(mut self, extension: OptionsExtension) -> ProofOptions {
+        assert!(
+            self.extensions.len() < MAX_NUM_EXTENSIONS,
+            "number of proof options extensions cannot be greater than {MAX_NUM_EXTENSIONS}"
+        );
+        match self.extensions.binary_search_by_key(&extension.id, |ext| ext.id) {
+            Ok(_) => panic!("proof options extension with ID {} already exists", extension.id),
+            Err(pos) => self.extensions.insert(pos, extension),
+        }
+
+        self
+    }
+
+    /// Updates the provided [ProofOptions] instance with the specified size of digests (in
+    /// bytes) used in vector commitments.
+    ///
+    /// Truncated digests (e.g., 20 or 24 bytes) reduce the size of Merkle authentication paths,
+    /// and thus, proof size, but they also limit collision resistance of the commitment scheme to
+    /// `digest_size * 4` bits; this is reflected in [Proof::security_level()]. The hash function
+    /// used to generate and verify the proof must produce digests of exactly this size (e.g.,
+    /// `Truncated<Blake3_256<B>, 20>`); this is checked by both the prover and the verifier.
+    ///
+    /// Digest size is recorded as a proof options extension, and thus, is bound to the proof.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// - `digest_size` is smaller than 16 or greater than 32.
+    /// - Digest size has already been specified for these proof options.
+    ///
+    /// [Proof::security_level()]: crate::proof::Proof::security_level
+    pub fn with_digest_size(self, digest_size: usize) -> ProofOptions {
+        assert!(
+            (MIN_DIGEST_SIZE..=MAX_DIGEST_SIZE).contains(&digest_size),
+            "digest size must be between {MIN_DIGEST_SIZE} and {MAX_DIGEST_SIZE} bytes, but was {digest_size}"
+        );
+        self.with_extension(OptionsExtension::new(DIGEST_SIZE_EXTENSION, vec![digest_size as u8]))
+    }
+
+    /// Updates the provided [ProofOptions] instance with the specified size of digests (in
+    /// bytes) used in FRI layer commitments.
+    ///
+    /// This is needed only when FRI layers are committed to using a hash function different from
+    /// the one used for trace and constraint commitments (e.g., an algebraic hash function for
+    /// FRI layers and a faster hash function elsewhere). When specified, digest size of FRI layer
+    /// commitments also limits the security level reported by [Proof::security_level()], and
+    /// the hash function used for FRI layer commitments must produce digests of exactly this
+    /// size; this is checked by both the prover and the verifier.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// - `digest_size` is smaller than 16 or greater than 32.
+    /// - FRI digest size has already been specified for these proof options.
+    ///
+    /// [Proof::security_level()]: crate::proof::Proof::security_level
+    pub fn with_fri_digest_size(self, digest_size: usize) -> ProofOptions {
+        assert!(
+            (MIN_DIGEST_SIZE..=MAX_DIGEST_SIZE).contains(&digest_size),
+            "FRI digest size must be between {MIN_DIGEST_SIZE} and {MAX_DIGEST_SIZE} bytes, but was {digest_size}"
+        );
+        self.with_extension(OptionsExtension::new(
+            FRI_DIGEST_SIZE_EXTENSION,
+            vec![digest_size as u8],
+        ))
+    }
+
+    /// Updates the provided [ProofOptions] instance to exclude positions of the LDE domain which
+    /// coincide with points of the trace domain from query sampling.
+    ///
+    /// When the LDE domain is not shifted in relation to the trace domain (i.e., the domain offset
+    /// is 1), every `blowup_factor`-th point of the LDE domain is also a point of the trace
+    /// domain. At such points, constraint divisors evaluate to zero, and thus, queries against
+    /// these positions cannot be answered. With this option, query positions are drawn only from
+    /// the positions of the LDE domain which map onto points outside of the trace domain. The
+    /// verifier draws query positions in the same way, and thus, enforces this policy.
+    ///
+    /// This option has no effect for domain offsets which do not map any LDE domain points onto
+    /// the trace domain (e.g., the default offset).
+    ///
+    /// # Panics
+    /// Panics if query exclusion has already been specified for these proof options.
+    pub fn with_trace_domain_query_exclusion(self) -> ProofOptions {
+        self.with_extension(OptionsExtension::new(TRACE_DOMAIN_QUERY_EXCLUSION_EXTENSION, vec![]))
+    }
+
+    /// Updates the provided [ProofOptions] instance with the specified maximum degree of the FRI
+    /// remainder polynomial.
+    ///
+    /// FRI stops folding once the degree of the folded polynomial drops to this value, and sends
+    /// the remainder polynomial in the proof directly. A larger remainder increases the size of
+    /// the remainder in the proof, but reduces the number of FRI layers, and thus, the number of
+    /// Merkle authentication paths included in the proof for every query. Each doubling of
+    /// `(max_degree + 1)` removes at most one layer when the folding factor is 2 (and fewer for
+    /// larger folding factors).
+    ///
+    /// Degrees up to 255 are stored directly in the proof options; larger degrees are recorded as
+    /// a proof options extension, and thus, are bound to the proof as well.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// - `max_degree` is greater than 65535 or is not a power of two minus 1.
+    /// - A remainder degree greater than 255 has already been specified for these proof options.
+    pub fn with_fri_remainder_max_degree(mut self, max_degree: usize) -> ProofOptions {
+        assert!(
+            (max_degree + 1).is_power_of_two(),
+            "FRI polynomial remainder degree must be one less than a power of two"
+        );
+        assert!(
+            max_degree <= FRI_MAX_EXTENDED_REMAINDER_DEGREE,
+            "FRI polynomial remainder degree cannot be greater than {FRI_MAX_EXTENDED_REMAINDER_DEGREE}"
+        );
+        assert!(
+            self.get_extension(FRI_REMAINDER_DEGREE_EXTENSION).is_none(),
+            "FRI polynomial remainder degree has already been extended"
+        );
+
+        if max_degree <= FRI_MAX_REMAINDER_DEGREE {
+            self.fri_remainder_max_degree = max_degree as u8;
+            self
+        } else {
+            self.fri_remainder_max_degree = FRI_MAX_REMAINDER_DEGREE as u8;
+            let log_size = (max_degree + 1).ilog2() as u8;
+            self.with_extension(OptionsExtension::new(
+                FRI_REMAINDER_DEGREE_EXTENSION,
+                vec![log_size],
+            ))
+        }
+    }
+
+    /// Updates the provided [ProofOptions] instance to use the DEEP variant of FRI.
+    ///
+    /// In DEEP mode, for every FRI layer but the remainder, the prover evaluates the layer
+    /// polynomial over a coset generated by a point sampled from the entire field (rather than
+    /// from the evaluation domain), and the folded layer is replaced by its quotient with respect
+    /// to the value implied by these evaluations. This allows proving better soundness per query
+    /// at the same blowup factor, at the cost of `fri_folding_factor` additional field elements
+    /// per FRI layer in the proof.
+    ///
+    /// Note that the security estimates reported by [ProofOptions] do not take DEEP mode into
+    /// account, and thus, are conservative for proofs generated with this option.
+    ///
+    /// # Panics
+    /// Panics if DEEP FRI has already been specified for these proof options.
+    pub fn with_deep_fri(self) -> ProofOptions {
+        self.with_extension(OptionsExtension::new(DEEP_FRI_EXTENSION, vec![]))
+    }
+
+    /// Updates the provided [ProofOptions] instance with the specified folding factors for the
+    /// first FRI layers.
+    ///
+    /// The i-th FRI layer is folded by `schedule[i]`, and all layers beyond the schedule are
+    /// folded by `fri_folding_factor`. For example, `[8, 8]` with a folding factor of 4 folds
+    /// the first two layers by 8 and all subsequent layers by 4. Since the optimal folding factor
+    /// changes as the layers shrink (large factors reduce the number of layers, and thus, the
+    /// number of authentication paths, while small factors reduce the number of values opened
+    /// per query), mixing folding factors may result in smaller proofs than any single factor.
+    ///
+    /// The schedule is recorded as a proof options extension, and thus, is bound to the proof.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// - `schedule` is empty or any of its folding factors is not 2, 4, 8, or 16.
+    /// - A folding schedule has already been specified for these proof options.
+    pub fn with_fri_folding_schedule(self, schedule: &[usize]) -> ProofOptions {
+        assert!(!schedule.is_empty(), "FRI folding schedule cannot be empty");
+        for &folding_factor in schedule {
+            assert!(
+                is_valid_fri_folding_factor(folding_factor),
+                "FRI folding factor {folding_factor} in the folding schedule is not supported"
+            );
+        }
+        let data = schedule.iter().map(|&folding_factor| folding_factor as u8).collect();
+        self.with_extension(OptionsExtension::new(FRI_FOLDING_SCHEDULE_EXTENSION, data))
+    }
+
+    /// Updates the provided [ProofOptions] instance to skip FRI layers for polynomials of degree
+    /// at most `max_degree`.
+    ///
+    /// For short traces, the polynomial tested by FRI is small, and openings of FRI layers
+    /// make up a disproportionate part of the proof. With layer skipping, if the degree of this
+    /// polynomial is at most `max_degree`, the polynomial is sent to the verifier directly as the
+    /// FRI remainder, and if its degree after a single folding is at most `max_degree`, FRI
+    /// terminates after one layer. Proofs for longer traces are not affected. See
+    /// [FriOptions::with_layer_skipping()] for more details.
+    ///
+    /// Layer skipping is recorded as a proof options extension, and thus, is bound to the proof.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// - `max_degree` is greater than 65535 or is not a power of two minus 1.
+    /// - Layer skipping has already been specified for these proof options.
+    pub fn with_fri_layer_skipping(self, max_degree: usize) -> ProofOptions {
+        assert!(
+            (max_degree + 1).is_power_of_two(),
+            "FRI layer skipping degree must be one less than a power of two"
+        );
+        assert!(
+            max_degree <= FRI_MAX_EXTENDED_REMAINDER_DEGREE,
+            "FRI layer skipping degree cannot be greater than {FRI_MAX_EXTENDED_REMAINDER_DEGREE}"
+        );
+        let log_size = (max_degree + 1).ilog2() as u8;
+        self.with_extension(OptionsExtension::new(FRI_LAYER_SKIPPING_EXTENSION, vec![log_size]))
+    }
+
+    /// Updates the provided [ProofOptions] instance to declare that proofs generated with these
+    /// options achieve at least `security_level` bits of security under the soundness bounds
+    /// specified by `mode`.
+    ///
+    /// The declaration is recorded in the proof context, and the verifier rejects proofs which
+    /// do not achieve the declared security level under the declared soundness bounds. The
+    /// number of queries needed to achieve a given security level can be computed via
+    /// [ProofOptions::min_num_queries()].
+    ///
+    /// # Panics
+    /// Panics if:
+    /// - `security_level` is zero or greater than 255.
+    /// - A soundness mode has already been specified for these proof options.
+    pub fn with_soundness_mode(self, mode: SoundnessMode, security_level: u32) -> ProofOptions {
+        assert!(
+            (1..=u8::MAX as u32).contains(&security_level),
+            "security level must be between 1 and 255 bits, but was {security_level}"
+        );
+        self.with_extension(OptionsExtension::new(
+            SOUNDNESS_MODE_EXTENSION,
+            vec![mode as u8, security_level as u8],
+        ))
+    }
+
+    /// Updates the provided [ProofOptions] instance to specify that proofs should be generated
+    /// in zero-knowledge mode.
+    ///
+    /// In this mode, the prover appends random rows to every trace segment, masks the
+    /// constraint composition polynomial with random polynomials, and requires the trace and
+    /// constraint commitments to be hiding. As a result, the values opened by the prover reveal
+    /// no information about the witness beyond what is implied by the public inputs.
+    ///
+    /// The random rows are not subject to transition constraints, and thus, AIRs should place
+    /// assertions against the last step of the computation at `Air::last_step()` rather than at
+    /// the last step of the trace.
+    ///
+    /// # Panics
+    /// Panics if zero-knowledge mode has already been specified for these proof options.
+    pub fn with_zk(self) -> ProofOptions {
+        self.with_extension(OptionsExtension::new(ZK_EXTENSION, vec![]))
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -235,32 +646,188 @@ impl ProofOptions {
         B::GENERATOR
     }
 
+    /// Returns the maximum degree of the FRI remainder polynomial.
+    ///
+    /// This is the degree at which FRI stops folding and sends the remainder polynomial in the
+    /// proof directly.
+    pub fn fri_remainder_max_degree(&self) -> usize {
+        match self.get_extension(FRI_REMAINDER_DEGREE_EXTENSION) {
+            Some(ext) => (1 << ext.data[0]) - 1,
+            None => self.fri_remainder_max_degree as usize,
+        }
+    }
+
+    /// Returns folding factors of the first FRI layers; this is empty if all FRI layers are
+    /// folded by `fri_folding_factor`.
+    pub fn fri_folding_schedule(&self) -> Vec<usize> {
+        match self.get_extension(FRI_FOLDING_SCHEDULE_EXTENSION) {
+            Some(ext) => ext.data.iter().map(|&folding_factor| folding_factor as usize).collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Returns the maximum degree of polynomials for which FRI layers are skipped, or `None` if
+    /// FRI layer skipping is not enabled.
+    pub fn fri_layer_skipping_max_degree(&self) -> Option<usize> {
+        self.get_extension(FRI_LAYER_SKIPPING_EXTENSION)
+            .map(|ext| (1 << ext.data[0]) - 1)
+    }
+
     /// Returns options for FRI protocol instantiated with parameters from this proof options.
     pub fn to_fri_options(&self) -> FriOptions {
         let folding_factor = self.fri_folding_factor as usize;
-        let remainder_max_degree = self.fri_remainder_max_degree as usize;
-        FriOptions::new(self.blowup_factor(), folding_factor, remainder_max_degree)
+        let remainder_max_degree = self.fri_remainder_max_degree();
+        let mut options =
+            FriOptions::new(self.blowup_factor(), folding_factor, remainder_max_degree)
+                .with_folding_schedule(self.fri_folding_schedule());
+        if let Some(max_degree) = self.fri_layer_skipping_max_degree() {
+            options = options.with_layer_skipping(max_degree);
+        }
+        if self.uses_deep_fri() {
+            options.with_deep_folding()
+        } else {
+            options
+        }
     }
 
     /// Returns the `[PartitionOptions]` used in this instance of proof options.
     pub fn partition_options(&self) -> PartitionOptions {
         self.partition_options
     }
+
+    /// Returns the method used to derive constraint composition and DEEP composition
+    /// coefficients from the public coin.
+    pub const fn coefficient_derivation(&self) -> CoefficientDerivation {
+        self.coefficient_derivation
+    }
+
+    /// Returns `true` if the prover must generate proofs in canonical mode.
+    ///
+    /// In canonical mode, proof bytes are fully determined by the execution trace, public inputs,
+    /// and proof options.
+    pub const fn is_canonical(&self) -> bool {
+        self.canonical
+    }
+
+    /// Returns the size of digests (in bytes) used in vector commitments, or `None` if digest
+    /// size is not specified by these proof options.
+    ///
+    /// When digest size is not specified, digests of any size produced by the hash function used
+    /// to generate the proof are accepted.
+    pub fn digest_size(&self) -> Option<usize> {
+        self.get_extension(DIGEST_SIZE_EXTENSION).map(|ext| ext.data[0] as usize)
+    }
+
+    /// Returns the size of digests (in bytes) used in FRI layer commitments, or `None` if FRI
+    /// digest size is not specified by these proof options.
+    pub fn fri_digest_size(&self) -> Option<usize> {
+        self.get_extension(FRI_DIGEST_SIZE_EXTENSION).map(|ext| ext.data[0] as usize)
+    }
+
+    /// Returns `true` if positions of the LDE domain which coincide with points of the trace
+    /// domain must be excluded from query sampling.
+    pub fn excludes_trace_domain_queries(&self) -> bool {
+        self.get_extension(TRACE_DOMAIN_QUERY_EXCLUSION_EXTENSION).is_some()
+    }
+
+    /// Returns `true` if FRI folding challenges are sampled using the DEEP variant of FRI.
+    pub fn uses_deep_fri(&self) -> bool {
+        self.get_extension(DEEP_FRI_EXTENSION).is_some()
+    }
+
+    /// Returns `true` if proofs must be generated in zero-knowledge mode.
+    pub fn is_zk(&self) -> bool {
+        self.get_extension(ZK_EXTENSION).is_some()
+    }
+
+    /// Returns the soundness mode and the security level (in bits) declared for proofs generated
+    /// with these options, or `None` if these options do not declare a soundness mode.
+    pub fn soundness_mode(&self) -> Option<(SoundnessMode, u32)> {
+        self.get_extension(SOUNDNESS_MODE_EXTENSION).map(|ext| {
+            let mode = match ext.data[0] {
+                0 => SoundnessMode::Conjectured,
+                _ => SoundnessMode::Proven,
+            };
+            (mode, ext.data[1] as u32)
+        })
+    }
+
+    /// Returns the smallest number of queries for which proofs for traces of `trace_length`
+    /// rows over the base field `B`, generated with these options (but with the number of queries
+    /// replaced), achieve `security_level` bits of security under the soundness bounds specified
+    /// by `mode`.
+    ///
+    /// Returns `None` if the security level cannot be achieved by increasing the number of
+    /// queries alone (e.g., because it exceeds the security offered by the field). Collision
+    /// resistance of the hash function, which also limits the security level of a proof, is not
+    /// taken into account.
+    pub fn min_num_queries<B: StarkField>(
+        &self,
+        mode: SoundnessMode,
+        trace_length: usize,
+        security_level: u32,
+    ) -> Option<usize> {
+        (1..=MAX_NUM_QUERIES).find(|&num_queries| {
+            let options = ProofOptions {
+                num_queries: num_queries as u8,
+                ..self.clone()
+            };
+            let security =
+                get_security_level(&options, mode, B::MODULUS_BITS, trace_length, u32::MAX);
+            security >= security_level
+        })
+    }
+
+    /// Returns all extensions carried by these proof options sorted by their IDs.
+    pub fn extensions(&self) -> &[OptionsExtension] {
+        &self.extensions
+    }
+
+    /// Returns the extension with the specified ID, or `None` if these proof options do not
+    /// carry such an extension.
+    pub fn get_extension(&self, id: u16) -> Option<&OptionsExtension> {
+        self.extensions
+            .binary_search_by_key(&id, |ext| ext.id)
+            .ok()
+            .map(|pos| &self.extensions[pos])
+    }
+
+    /// Returns an iterator over the extensions whose semantics are not understood by this version
+    /// of the library.
+    pub fn unknown_extensions(&self) -> impl Iterator<Item = &OptionsExtension> {
+        self.extensions.iter().filter(|ext| !ext.is_known())
+    }
 }
 
 impl<E: StarkField> ToElements<E> for ProofOptions {
     fn to_elements(&self) -> Vec<E> {
-        // encode field extension and FRI parameters into a single field element
-        let mut buf = self.field_extension as u32;
+        // encode canonical proving flag, coefficient derivation, field extension, and FRI
+        // parameters into a single field element; the canonical proving flag is the top bit of
+        // the most significant byte
+        let mut buf = ((self.canonical as u32) << 7) | self.coefficient_derivation as u32;
+        buf = (buf << 8) | self.field_extension as u32;
         buf = (buf << 8) | self.fri_folding_factor as u32;
         buf = (buf << 8) | self.fri_remainder_max_degree as u32;
 
-        vec![
+        let mut result = vec![
             E::from(buf),
             E::from(self.grinding_factor),
             E::from(self.blowup_factor),
             E::from(self.num_queries),
-        ]
+        ];
+
+        // extensions (if any) are bound to the proof as well; we encode ID and length of each
+        // extension into a single element followed by extension data packed into 4-byte chunks
+        for extension in self.extensions.iter() {
+            result.push(E::from(((extension.id as u32) << 16) | extension.data.len() as u32));
+            for chunk in extension.data.chunks(4) {
+                let mut buf = [0_u8; 4];
+                buf[..chunk.len()].copy_from_slice(chunk);
+                result.push(E::from(u32::from_le_bytes(buf)));
+            }
+        }
+
+        result
     }
 }
 
@@ -275,6 +842,11 @@ impl Serializable for ProofOptions {
         target.write_u8(self.fri_remainder_max_degree);
         target.write_u8(self.partition_options.num_partitions);
         target.write_u8(self.partition_options.hash_rate);
+        target.write(self.coefficient_derivation);
+        target.write_bool(self.canonical);
+        target.write_u8(EXTENSIONS_VERSION);
+        target.write_u8(self.extensions.len() as u8);
+        target.write_many(&self.extensions);
     }
 }
 
@@ -292,10 +864,203 @@ impl Deserializable for ProofOptions {
             source.read_u8()? as usize,
             source.read_u8()? as usize,
         );
-        Ok(result.with_partitions(source.read_u8()? as usize, source.read_u8()? as usize))
+        let mut result = result
+            .with_partitions(source.read_u8()? as usize, source.read_u8()? as usize)
+            .with_coefficient_derivation(CoefficientDerivation::read_from(source)?)
+            .with_canonical_proving(source.read_bool()?);
+
+        let version = source.read_u8()?;
+        if version != EXTENSIONS_VERSION {
+            return Err(DeserializationError::InvalidValue(format!(
+                "unsupported proof options extensions version {version}"
+            )));
+        }
+        let num_extensions = source.read_u8()? as usize;
+        result.extensions = source.read_many(num_extensions)?;
+        if !result.extensions.windows(2).all(|pair| pair[0].id < pair[1].id) {
+            return Err(DeserializationError::InvalidValue(
+                "proof options extensions must be sorted by their IDs without duplicates".into(),
+            ));
+        }
+        if let Some(extension) = result.get_extension(DIGEST_SIZE_EXTENSION) {
+            let valid = match extension.data() {
+                &[size] => (MIN_DIGEST_SIZE..=MAX_DIGEST_SIZE).contains(&(size as usize)),
+                _ => false,
+            };
+            if !valid {
+                return Err(DeserializationError::InvalidValue(
+                    "invalid digest size proof options extension".into(),
+                ));
+            }
+        }
+        if let Some(extension) = result.get_extension(FRI_DIGEST_SIZE_EXTENSION) {
+            let valid = match extension.data() {
+                &[size] => (MIN_DIGEST_SIZE..=MAX_DIGEST_SIZE).contains(&(size as usize)),
+                _ => false,
+            };
+            if !valid {
+                return Err(DeserializationError::InvalidValue(
+                    "invalid FRI digest size proof options extension".into(),
+                ));
+            }
+        }
+        if let Some(extension) = result.get_extension(TRACE_DOMAIN_QUERY_EXCLUSION_EXTENSION) {
+            if !extension.data().is_empty() {
+                return Err(DeserializationError::InvalidValue(
+                    "invalid trace domain query exclusion proof options extension".into(),
+                ));
+            }
+        }
+        if let Some(extension) = result.get_extension(FRI_REMAINDER_DEGREE_EXTENSION) {
+            let valid = match extension.data() {
+                &[log_size] => {
+                    result.fri_remainder_max_degree as usize == FRI_MAX_REMAINDER_DEGREE
+                        && (FRI_MIN_EXTENDED_REMAINDER_LOG_SIZE
+                            ..=FRI_MAX_EXTENDED_REMAINDER_LOG_SIZE)
+                            .contains(&log_size)
+                },
+                _ => false,
+            };
+            if !valid {
+                return Err(DeserializationError::InvalidValue(
+                    "invalid FRI remainder degree proof options extension".into(),
+                ));
+            }
+        }
+        if let Some(extension) = result.get_extension(DEEP_FRI_EXTENSION) {
+            if !extension.data().is_empty() {
+                return Err(DeserializationError::InvalidValue(
+                    "invalid DEEP FRI proof options extension".into(),
+                ));
+            }
+        }
+        if let Some(extension) = result.get_extension(FRI_FOLDING_SCHEDULE_EXTENSION) {
+            let data = extension.data();
+            if data.is_empty()
+                || !data
+                    .iter()
+                    .all(|&folding_factor| is_valid_fri_folding_factor(folding_factor as usize))
+            {
+                return Err(DeserializationError::InvalidValue(
+                    "invalid FRI folding schedule proof options extension".into(),
+                ));
+            }
+        }
+        if let Some(extension) = result.get_extension(FRI_LAYER_SKIPPING_EXTENSION) {
+            let valid = matches!(
+                extension.data(),
+                &[log_size] if log_size <= FRI_MAX_EXTENDED_REMAINDER_LOG_SIZE
+            );
+            if !valid {
+                return Err(DeserializationError::InvalidValue(
+                    "invalid FRI layer skipping proof options extension".into(),
+                ));
+            }
+        }
+        if let Some(extension) = result.get_extension(SOUNDNESS_MODE_EXTENSION) {
+            let valid = matches!(extension.data(), &[0 | 1, security_level] if security_level > 0);
+            if !valid {
+                return Err(DeserializationError::InvalidValue(
+                    "invalid soundness mode proof options extension".into(),
+                ));
+            }
+        }
+        if let Some(extension) = result.get_extension(ZK_EXTENSION) {
+            if !extension.data().is_empty() {
+                return Err(DeserializationError::InvalidValue(
+                    "invalid zero-knowledge proof options extension".into(),
+                ));
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+// OPTIONS EXTENSION
+// ================================================================================================
+
+/// An extension of [ProofOptions] identified by a 16-bit ID.
+///
+/// Extension data is an opaque byte string of up to 65535 bytes; its interpretation is defined by
+/// the extension ID. Extensions are serialized with a length prefix, and thus, extensions which a
+/// reader does not understand can still be deserialized and carried along. Whether proofs with
+/// such extensions are accepted is up to the verifier (see [UnknownExtensionPolicy]).
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct OptionsExtension {
+    id: u16,
+    data: Vec<u8>,
+}
+
+impl OptionsExtension {
+    /// Returns a new extension with the specified ID and data.
+    ///
+    /// # Panics
+    /// Panics if `data` is longer than 65535 bytes.
+    pub fn new(id: u16, data: Vec<u8>) -> Self {
+        assert!(
+            data.len() <= MAX_EXTENSION_SIZE,
+            "proof options extension data cannot be longer than {MAX_EXTENSION_SIZE} bytes"
+        );
+        Self { id, data }
+    }
+
+    /// Returns the ID of this extension.
+    pub fn id(&self) -> u16 {
+        self.id
+    }
+
+    /// Returns the data of this extension.
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Returns `true` if the semantics of this extension are understood by this version of the
+    /// library.
+    pub fn is_known(&self) -> bool {
+        KNOWN_EXTENSIONS.contains(&self.id)
+    }
+}
+
+impl Serializable for OptionsExtension {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u16(self.id);
+        target.write_u16(self.data.len() as u16);
+        target.write_bytes(&self.data);
+    }
+
+    fn get_size_hint(&self) -> usize {
+        4 + self.data.len()
     }
 }
 
+impl Deserializable for OptionsExtension {
+    /// Reads an options extension from the specified `source` and returns the result.
+    ///
+    /// # Errors
+    /// Returns an error if a valid extension could not be read from the specified `source`.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let id = source.read_u16()?;
+        let len = source.read_u16()? as usize;
+        let data = source.read_vec(len)?;
+        Ok(Self { id, data })
+    }
+}
+
+/// Defines how a verifier treats [OptionsExtension]s whose semantics it does not understand.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub enum UnknownExtensionPolicy {
+    /// Proofs generated with unknown extensions are rejected. This is the safe choice because
+    /// an unknown extension may change the meaning of the proof.
+    #[default]
+    Reject,
+    /// Unknown extensions are ignored. This is sound only for extensions which do not affect
+    /// the structure of the proof (e.g., metadata); since extensions are bound into the public
+    /// coin seed, ignoring them does not let a prover alter them after the fact.
+    Ignore,
+}
+
 // FIELD EXTENSION IMPLEMENTATION
 // ================================================================================================
 
@@ -341,6 +1106,34 @@ impl Deserializable for FieldExtension {
     }
 }
 
+// COEFFICIENT DERIVATION IMPLEMENTATION
+// ================================================================================================
+
+impl Serializable for CoefficientDerivation {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u8(*self as u8);
+    }
+
+    /// Returns an estimate of how many bytes are needed to represent self.
+    fn get_size_hint(&self) -> usize {
+        1
+    }
+}
+
+impl Deserializable for CoefficientDerivation {
+    /// Reads a coefficient derivation enum from the specified `source`.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        match source.read_u8()? {
+            0 => Ok(CoefficientDerivation::Sequential),
+            1 => Ok(CoefficientDerivation::Expanded),
+            value => Err(DeserializationError::InvalidValue(format!(
+                "value {value} cannot be deserialized as CoefficientDerivation enum"
+            ))),
+        }
+    }
+}
+
 // PARTITION OPTION IMPLEMENTATION
 // ================================================================================================
 
@@ -406,14 +1199,31 @@ impl Default for PartitionOptions {
     }
 }
 
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns `true` if the specified FRI folding factor is supported.
+fn is_valid_fri_folding_factor(folding_factor: usize) -> bool {
+    folding_factor.is_power_of_two()
+        && (FRI_MIN_FOLDING_FACTOR..=FRI_MAX_FOLDING_FACTOR).contains(&folding_factor)
+}
+
 // TESTS
 // ================================================================================================
 
 #[cfg(test)]
 mod tests {
+    use alloc::vec::Vec;
+
     use math::fields::{f64::BaseElement, CubeExtension};
 
-    use super::{FieldExtension, PartitionOptions, ProofOptions, ToElements};
+    use utils::{Deserializable, Serializable};
+
+    use super::{
+        CoefficientDerivation, FieldExtension, OptionsExtension, PartitionOptions, ProofOptions,
+        SoundnessMode, ToElements,
+    };
+    use crate::proof::get_security_level;
 
     #[test]
     fn proof_options_to_elements() {
@@ -446,6 +1256,382 @@ mod tests {
             fri_remainder_max_degree as usize,
         );
         assert_eq!(expected, options.to_elements());
+
+        let ext_fri = u32::from_le_bytes([
+            fri_remainder_max_degree,
+            fri_folding_factor,
+            field_extension as u8,
+            CoefficientDerivation::Expanded as u8,
+        ]);
+        let options = options.with_coefficient_derivation(CoefficientDerivation::Expanded);
+        assert_eq!(BaseElement::from(ext_fri), options.to_elements()[0]);
+
+        let ext_fri = u32::from_le_bytes([
+            fri_remainder_max_degree,
+            fri_folding_factor,
+            field_extension as u8,
+            0x80 | CoefficientDerivation::Expanded as u8,
+        ]);
+        let options = options.with_canonical_proving(true);
+        assert_eq!(BaseElement::from(ext_fri), options.to_elements()[0]);
+    }
+
+    #[test]
+    fn proof_options_serialization() {
+        let options = ProofOptions::new(30, 8, 20, FieldExtension::Quadratic, 8, 127)
+            .with_partitions(4, 8)
+            .with_coefficient_derivation(CoefficientDerivation::Expanded)
+            .with_canonical_proving(true);
+        let bytes = options.to_bytes();
+        assert_eq!(options, ProofOptions::read_from_bytes(&bytes).unwrap());
+
+        // invalid coefficient derivation
+        let mut invalid_bytes = bytes.clone();
+        invalid_bytes[bytes.len() - 4] = 2;
+        assert!(ProofOptions::read_from_bytes(&invalid_bytes).is_err());
+
+        // invalid canonical proving flag
+        let mut invalid_bytes = bytes.clone();
+        invalid_bytes[bytes.len() - 3] = 2;
+        assert!(ProofOptions::read_from_bytes(&invalid_bytes).is_err());
+
+        // unsupported extensions version
+        let mut invalid_bytes = bytes.clone();
+        invalid_bytes[bytes.len() - 2] = 2;
+        assert!(ProofOptions::read_from_bytes(&invalid_bytes).is_err());
+    }
+
+    #[test]
+    fn proof_options_extensions() {
+        let base_options = ProofOptions::new(30, 8, 20, FieldExtension::Quadratic, 8, 127);
+        let options = base_options
+            .clone()
+            .with_extension(OptionsExtension::new(107, vec![1, 2, 3, 4, 5]))
+            .with_extension(OptionsExtension::new(100, vec![]));
+
+        // extensions are sorted by their IDs
+        let ids = options.extensions().iter().map(|ext| ext.id()).collect::<Vec<_>>();
+        assert_eq!(vec![100, 107], ids);
+        assert_eq!(&[1, 2, 3, 4, 5], options.get_extension(107).unwrap().data());
+        assert!(options.get_extension(105).is_none());
+        assert_eq!(2, options.unknown_extensions().count());
+
+        // extensions survive a serialization round trip
+        let bytes = options.to_bytes();
+        assert_eq!(options, ProofOptions::read_from_bytes(&bytes).unwrap());
+
+        // extensions are bound to the proof options elements, but do not change the elements of
+        // proof options without extensions
+        let base_elements: Vec<BaseElement> = base_options.to_elements();
+        let elements: Vec<BaseElement> = options.to_elements();
+        assert_eq!(4, base_elements.len());
+        assert_eq!(base_elements, elements[..4]);
+        assert_eq!(
+            vec![
+                BaseElement::from(100_u32 << 16),
+                BaseElement::from((107_u32 << 16) | 5),
+                BaseElement::from(u32::from_le_bytes([1, 2, 3, 4])),
+                BaseElement::from(5_u32),
+            ],
+            elements[4..]
+        );
+
+        // extensions which are not sorted by ID are rejected
+        let mut invalid_bytes = bytes;
+        let ext_start = invalid_bytes.len() - 13;
+        invalid_bytes[ext_start + 1] = 110;
+        assert!(ProofOptions::read_from_bytes(&invalid_bytes).is_err());
+    }
+
+    #[test]
+    fn proof_options_digest_size() {
+        let options = ProofOptions::new(30, 8, 20, FieldExtension::Quadratic, 8, 127);
+        assert_eq!(None, options.digest_size());
+
+        let options = options.with_digest_size(20);
+        assert_eq!(Some(20), options.digest_size());
+        assert_eq!(0, options.unknown_extensions().count());
+
+        let bytes = options.to_bytes();
+        assert_eq!(options, ProofOptions::read_from_bytes(&bytes).unwrap());
+
+        // digest size out of range
+        let mut invalid_bytes = bytes.clone();
+        invalid_bytes[bytes.len() - 1] = 15;
+        assert!(ProofOptions::read_from_bytes(&invalid_bytes).is_err());
+
+        // digest size extension with more than one byte of data
+        let options = ProofOptions::new(30, 8, 20, FieldExtension::Quadratic, 8, 127)
+            .with_extension(OptionsExtension::new(1, vec![20, 0]));
+        assert!(ProofOptions::read_from_bytes(&options.to_bytes()).is_err());
+    }
+
+    #[test]
+    fn proof_options_fri_digest_size() {
+        let options = ProofOptions::new(30, 8, 20, FieldExtension::Quadratic, 8, 127);
+        assert_eq!(None, options.fri_digest_size());
+
+        let options = options.with_digest_size(20).with_fri_digest_size(32);
+        assert_eq!(Some(20), options.digest_size());
+        assert_eq!(Some(32), options.fri_digest_size());
+        assert_eq!(0, options.unknown_extensions().count());
+
+        let bytes = options.to_bytes();
+        assert_eq!(options, ProofOptions::read_from_bytes(&bytes).unwrap());
+
+        // FRI digest size out of range
+        let mut invalid_bytes = bytes.clone();
+        invalid_bytes[bytes.len() - 1] = 33;
+        assert!(ProofOptions::read_from_bytes(&invalid_bytes).is_err());
+    }
+
+    #[test]
+    fn proof_options_trace_domain_query_exclusion() {
+        let options = ProofOptions::new(30, 8, 20, FieldExtension::Quadratic, 8, 127);
+        assert!(!options.excludes_trace_domain_queries());
+
+        let options = options.with_trace_domain_query_exclusion();
+        assert!(options.excludes_trace_domain_queries());
+        assert_eq!(0, options.unknown_extensions().count());
+
+        let bytes = options.to_bytes();
+        assert_eq!(options, ProofOptions::read_from_bytes(&bytes).unwrap());
+
+        // query exclusion extension with non-empty data
+        let options = ProofOptions::new(30, 8, 20, FieldExtension::Quadratic, 8, 127)
+            .with_extension(OptionsExtension::new(2, vec![1]));
+        assert!(ProofOptions::read_from_bytes(&options.to_bytes()).is_err());
+    }
+
+    #[test]
+    fn proof_options_fri_remainder_max_degree() {
+        let options = ProofOptions::new(30, 8, 20, FieldExtension::Quadratic, 8, 127);
+        assert_eq!(127, options.fri_remainder_max_degree());
+
+        // small degrees are stored directly in the options
+        let options = options.with_fri_remainder_max_degree(31);
+        assert_eq!(31, options.fri_remainder_max_degree());
+        assert!(options.extensions().is_empty());
+
+        // larger degrees are stored in an extension and reduce the number of FRI layers
+        let domain_size = 1 << 20;
+        let small = options.to_fri_options().num_fri_layers(domain_size);
+        let options = options.with_fri_remainder_max_degree(4095);
+        assert_eq!(4095, options.fri_remainder_max_degree());
+        assert_eq!(4095, options.to_fri_options().remainder_max_degree());
+        assert_eq!(small - 2, options.to_fri_options().num_fri_layers(domain_size));
+        assert_eq!(0, options.unknown_extensions().count());
+
+        let bytes = options.to_bytes();
+        assert_eq!(options, ProofOptions::read_from_bytes(&bytes).unwrap());
+
+        // extended degree which fits into the options directly
+        let mut invalid_bytes = bytes.clone();
+        invalid_bytes[bytes.len() - 1] = 8;
+        assert!(ProofOptions::read_from_bytes(&invalid_bytes).is_err());
+
+        // extended degree which is too large
+        let mut invalid_bytes = bytes;
+        let last = invalid_bytes.len() - 1;
+        invalid_bytes[last] = 17;
+        assert!(ProofOptions::read_from_bytes(&invalid_bytes).is_err());
+    }
+
+    #[test]
+    fn proof_options_deep_fri() {
+        let options = ProofOptions::new(30, 8, 20, FieldExtension::Quadratic, 8, 127);
+        assert!(!options.uses_deep_fri());
+        assert!(!options.to_fri_options().deep_folding());
+
+        let options = options.with_deep_fri();
+        assert!(options.uses_deep_fri());
+        assert!(options.to_fri_options().deep_folding());
+        assert_eq!(0, options.unknown_extensions().count());
+
+        let bytes = options.to_bytes();
+        assert_eq!(options, ProofOptions::read_from_bytes(&bytes).unwrap());
+
+        // DEEP FRI extension with non-empty data
+        let options = ProofOptions::new(30, 8, 20, FieldExtension::Quadratic, 8, 127)
+            .with_extension(OptionsExtension::new(4, vec![1]));
+        assert!(ProofOptions::read_from_bytes(&options.to_bytes()).is_err());
+    }
+
+    #[test]
+    fn proof_options_zk() {
+        let options = ProofOptions::new(30, 8, 20, FieldExtension::Quadratic, 8, 127);
+        assert!(!options.is_zk());
+
+        let options = options.with_zk();
+        assert!(options.is_zk());
+        assert_eq!(0, options.unknown_extensions().count());
+
+        let bytes = options.to_bytes();
+        assert_eq!(options, ProofOptions::read_from_bytes(&bytes).unwrap());
+
+        // zero-knowledge extension with non-empty data
+        let options = ProofOptions::new(30, 8, 20, FieldExtension::Quadratic, 8, 127)
+            .with_extension(OptionsExtension::new(9, vec![1]));
+        assert!(ProofOptions::read_from_bytes(&options.to_bytes()).is_err());
+    }
+
+    #[test]
+    fn proof_options_fri_folding_schedule() {
+        let options = ProofOptions::new(30, 8, 20, FieldExtension::Quadratic, 4, 7);
+        assert!(options.fri_folding_schedule().is_empty());
+
+        let options = options.with_fri_folding_schedule(&[16, 8]);
+        assert_eq!(vec![16, 8], options.fri_folding_schedule());
+        assert_eq!(0, options.unknown_extensions().count());
+
+        // layers beyond the schedule are folded by the folding factor of the options
+        let fri_options = options.to_fri_options();
+        assert_eq!(16, fri_options.layer_folding_factor(0));
+        assert_eq!(8, fri_options.layer_folding_factor(1));
+        assert_eq!(4, fri_options.layer_folding_factor(2));
+        assert_eq!(3, fri_options.num_fri_layers(1 << 15));
+
+        let bytes = options.to_bytes();
+        assert_eq!(options, ProofOptions::read_from_bytes(&bytes).unwrap());
+
+        // unsupported folding factor in the schedule
+        let mut invalid_bytes = bytes;
+        let last = invalid_bytes.len() - 1;
+        invalid_bytes[last] = 32;
+        assert!(ProofOptions::read_from_bytes(&invalid_bytes).is_err());
+
+        // empty folding schedule
+        let options = ProofOptions::new(30, 8, 20, FieldExtension::Quadratic, 4, 7)
+            .with_extension(OptionsExtension::new(6, vec![]));
+        assert!(ProofOptions::read_from_bytes(&options.to_bytes()).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "FRI folding factor 3 in the folding schedule is not supported")]
+    fn proof_options_invalid_fri_folding_schedule() {
+        let _ = ProofOptions::new(30, 8, 20, FieldExtension::None, 4, 7)
+            .with_fri_folding_schedule(&[8, 3]);
+    }
+
+    #[test]
+    fn proof_options_fri_layer_skipping() {
+        let options = ProofOptions::new(30, 8, 20, FieldExtension::Quadratic, 4, 7);
+        assert_eq!(None, options.fri_layer_skipping_max_degree());
+        assert_eq!(None, options.to_fri_options().skipping_max_degree());
+
+        let options = options.with_fri_layer_skipping(1023);
+        assert_eq!(Some(1023), options.fri_layer_skipping_max_degree());
+        assert_eq!(Some(1023), options.to_fri_options().skipping_max_degree());
+        assert_eq!(0, options.to_fri_options().num_fri_layers(1024 * 8));
+        assert_eq!(0, options.unknown_extensions().count());
+
+        let bytes = options.to_bytes();
+        assert_eq!(options, ProofOptions::read_from_bytes(&bytes).unwrap());
+
+        // layer skipping degree which is too large
+        let mut invalid_bytes = bytes;
+        let last = invalid_bytes.len() - 1;
+        invalid_bytes[last] = 17;
+        assert!(ProofOptions::read_from_bytes(&invalid_bytes).is_err());
+
+        // layer skipping extension with more than one byte of data
+        let options = ProofOptions::new(30, 8, 20, FieldExtension::Quadratic, 4, 7)
+            .with_extension(OptionsExtension::new(7, vec![10, 0]));
+        assert!(ProofOptions::read_from_bytes(&options.to_bytes()).is_err());
+    }
+
+    #[test]
+    fn proof_options_soundness_mode() {
+        let options = ProofOptions::new(30, 8, 20, FieldExtension::Quadratic, 8, 127);
+        assert_eq!(None, options.soundness_mode());
+
+        let options = options.with_soundness_mode(SoundnessMode::Proven, 100);
+        assert_eq!(Some((SoundnessMode::Proven, 100)), options.soundness_mode());
+        assert_eq!(0, options.unknown_extensions().count());
+
+        let bytes = options.to_bytes();
+        assert_eq!(options, ProofOptions::read_from_bytes(&bytes).unwrap());
+
+        // invalid soundness mode
+        let mut invalid_bytes = bytes.clone();
+        invalid_bytes[bytes.len() - 2] = 2;
+        assert!(ProofOptions::read_from_bytes(&invalid_bytes).is_err());
+
+        // zero security level
+        let mut invalid_bytes = bytes;
+        let last = invalid_bytes.len() - 1;
+        invalid_bytes[last] = 0;
+        assert!(ProofOptions::read_from_bytes(&invalid_bytes).is_err());
+
+        // soundness mode extension with missing security level
+        let options = ProofOptions::new(30, 8, 20, FieldExtension::Quadratic, 8, 127)
+            .with_extension(OptionsExtension::new(5, vec![1]));
+        assert!(ProofOptions::read_from_bytes(&options.to_bytes()).is_err());
+    }
+
+    #[test]
+    fn proof_options_min_num_queries() {
+        let options = ProofOptions::new(1, 8, 16, FieldExtension::Cubic, 8, 127);
+        let trace_length = 1 << 16;
+        let base_field_bits = 64;
+
+        let mut num_queries = [0; 2];
+        for (i, mode) in [SoundnessMode::Conjectured, SoundnessMode::Proven].into_iter().enumerate()
+        {
+            let min_queries =
+                options.min_num_queries::<BaseElement>(mode, trace_length, 100).unwrap();
+            let security = |num_queries: usize| {
+                let options = ProofOptions {
+                    num_queries: num_queries as u8,
+                    ..options.clone()
+                };
+                get_security_level(&options, mode, base_field_bits, trace_length, u32::MAX)
+            };
+            assert!(security(min_queries) >= 100);
+            assert!(security(min_queries - 1) < 100);
+            num_queries[i] = min_queries;
+        }
+
+        // proven bounds require more queries than conjectured bounds
+        assert!(num_queries[1] > num_queries[0]);
+
+        // security level exceeding the security offered by the field cannot be achieved
+        let mode = SoundnessMode::Conjectured;
+        assert_eq!(None, options.min_num_queries::<BaseElement>(mode, trace_length, 200));
+    }
+
+    #[test]
+    #[should_panic(expected = "security level must be between 1 and 255 bits")]
+    fn proof_options_invalid_soundness_security_level() {
+        let _ = ProofOptions::new(30, 8, 20, FieldExtension::None, 8, 127)
+            .with_soundness_mode(SoundnessMode::Conjectured, 256);
+    }
+
+    #[test]
+    #[should_panic(expected = "one less than a power of two")]
+    fn proof_options_invalid_fri_remainder_max_degree() {
+        let _ = ProofOptions::new(30, 8, 20, FieldExtension::None, 8, 127)
+            .with_fri_remainder_max_degree(1000);
+    }
+
+    #[test]
+    #[should_panic(expected = "must be between 16 and 32 bytes")]
+    fn proof_options_invalid_digest_size() {
+        let _ = ProofOptions::new(30, 8, 20, FieldExtension::None, 8, 127).with_digest_size(8);
+    }
+
+    #[test]
+    #[should_panic(expected = "FRI digest size must be between 16 and 32 bytes")]
+    fn proof_options_invalid_fri_digest_size() {
+        let _ = ProofOptions::new(30, 8, 20, FieldExtension::None, 8, 127).with_fri_digest_size(64);
+    }
+
+    #[test]
+    #[should_panic(expected = "already exists")]
+    fn proof_options_duplicate_extension() {
+        let _ = ProofOptions::new(30, 8, 20, FieldExtension::None, 8, 127)
+            .with_extension(OptionsExtension::new(1, vec![1]))
+            .with_extension(OptionsExtension::new(1, vec![2]));
     }
 
     #[test]
@@ -68,6 +68,24 @@ impl Commitments {
         num_trace_segments: usize,
         num_fri_layers: usize,
     ) -> Result<(Vec<H::Digest>, H::Digest, Vec<H::Digest>), DeserializationError> {
+        self.parse_with_fri_hasher::<H, H>(num_trace_segments, num_fri_layers)
+    }
+
+    /// Parses the serialized commitments into distinct parts, where FRI layer commitments are
+    /// digests of hash function `FH` and all other commitments are digests of hash function `H`.
+    ///
+    /// This is the same as [parse()](Commitments::parse), but is intended for proofs in which
+    /// FRI layers are committed to using a different hash function than the execution trace and
+    /// constraint evaluations.
+    ///
+    /// # Errors
+    /// Returns an error under the same conditions as [parse()](Commitments::parse).
+    #[allow(clippy::type_complexity)]
+    pub fn parse_with_fri_hasher<H: Hasher, FH: Hasher>(
+        self,
+        num_trace_segments: usize,
+        num_fri_layers: usize,
+    ) -> Result<(Vec<H::Digest>, H::Digest, Vec<FH::Digest>), DeserializationError> {
         let mut reader = SliceReader::new(&self.0);
 
         // parse trace commitments
@@ -0,0 +1,296 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::vec::Vec;
+
+use utils::{
+    ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable, SliceReader,
+};
+
+use super::Proof;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Magic bytes identifying a serialized proof container.
+const MAGIC: [u8; 4] = *b"WFPC";
+
+/// Current version of the proof container format.
+const VERSION: u8 = 1;
+
+/// Maximum length of a public input digest; currently set at 255 bytes.
+const MAX_DIGEST_LENGTH: usize = u8::MAX as usize;
+
+// PROOF CONTAINER
+// ================================================================================================
+
+/// A serialized STARK proof together with metadata describing the proof.
+///
+/// A container makes it possible to route, deduplicate, and audit proofs without parsing the
+/// proofs themselves. Specifically, a container holds the following metadata:
+/// * AIR identifier of the computation described by the proof (if the proof specifies one, see
+///   [Context::air_id()](crate::proof::Context::air_id)).
+/// * Identifier of the parameter set against which the proof should be verified. This is an
+///   opaque value defined by the application (e.g., an index into a registry of acceptable proof
+///   options).
+/// * Digest of public inputs of the computation (e.g., as computed by
+///   [PublicInputs::hash()](crate::PublicInputs::hash)). The hash function used to compute the
+///   digest is defined by the application.
+/// * Time at which the proof was created, in seconds since the Unix epoch.
+///
+/// When serialized, a container is protected by a CRC-32 checksum computed over all preceding
+/// bytes; the checksum is verified on deserialization. The checksum protects against accidental
+/// corruption only; it does not provide any protection against malicious modifications.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ProofContainer {
+    air_id: Option<u32>,
+    params_id: u32,
+    pub_inputs_digest: Vec<u8>,
+    timestamp: u64,
+    proof: Vec<u8>,
+}
+
+impl ProofContainer {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new container for the specified proof. The AIR identifier of the container is
+    /// taken from the context of the proof.
+    ///
+    /// # Panics
+    /// Panics if `pub_inputs_digest` is longer than 255 bytes.
+    pub fn new(proof: &Proof, params_id: u32, pub_inputs_digest: &[u8], timestamp: u64) -> Self {
+        assert!(
+            pub_inputs_digest.len() <= MAX_DIGEST_LENGTH,
+            "public input digest cannot be longer than {} bytes, but was {} bytes",
+            MAX_DIGEST_LENGTH,
+            pub_inputs_digest.len()
+        );
+
+        Self {
+            air_id: proof.context.air_id(),
+            params_id,
+            pub_inputs_digest: pub_inputs_digest.to_vec(),
+            timestamp,
+            proof: proof.to_bytes(),
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the AIR identifier of the computation described by the contained proof.
+    pub fn air_id(&self) -> Option<u32> {
+        self.air_id
+    }
+
+    /// Returns the identifier of the parameter set against which the contained proof should be
+    /// verified.
+    pub fn params_id(&self) -> u32 {
+        self.params_id
+    }
+
+    /// Returns the digest of public inputs of the computation described by the contained proof.
+    pub fn pub_inputs_digest(&self) -> &[u8] {
+        &self.pub_inputs_digest
+    }
+
+    /// Returns the time at which the contained proof was created, in seconds since the Unix
+    /// epoch.
+    pub fn timestamp(&self) -> u64 {
+        self.timestamp
+    }
+
+    /// Returns serialized bytes of the contained proof.
+    pub fn proof_bytes(&self) -> &[u8] {
+        &self.proof
+    }
+
+    /// Parses and returns the contained proof.
+    ///
+    /// # Errors
+    /// Returns an error if a valid STARK proof could not be read from the contained bytes.
+    pub fn proof(&self) -> Result<Proof, DeserializationError> {
+        Proof::from_bytes(&self.proof)
+    }
+
+    // SERIALIZATION / DESERIALIZATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Serializes this container into a vector of bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        Serializable::to_bytes(self)
+    }
+
+    /// Returns a proof container read from the specified `source`.
+    ///
+    /// The contained proof is not parsed.
+    ///
+    /// # Errors
+    /// Returns an error if a valid proof container could not be read from the specified `source`,
+    /// or if the checksum of the container does not match its contents.
+    pub fn from_bytes(source: &[u8]) -> Result<Self, DeserializationError> {
+        Deserializable::read_from_bytes(source)
+    }
+
+    /// Returns a proof container with metadata read from the specified `source`, but without
+    /// the proof.
+    ///
+    /// This is useful for tooling which needs to route proofs based on their metadata only: the
+    /// contained proof is neither read nor copied, and the checksum of the container is not
+    /// verified.
+    ///
+    /// # Errors
+    /// Returns an error if a valid proof container header could not be read from `source`.
+    pub fn read_header(source: &[u8]) -> Result<Self, DeserializationError> {
+        read_header(&mut SliceReader::new(source))
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Writes all fields of this container (excluding the checksum) into the `target`.
+    fn write_fields<W: ByteWriter>(&self, target: &mut W) {
+        target.write_bytes(&MAGIC);
+        target.write_u8(VERSION);
+        self.air_id.write_into(target);
+        target.write_u32(self.params_id);
+        target.write_u8(self.pub_inputs_digest.len() as u8);
+        target.write_bytes(&self.pub_inputs_digest);
+        target.write_u64(self.timestamp);
+        target.write_u32(self.proof.len() as u32);
+        target.write_bytes(&self.proof);
+    }
+}
+
+// SERIALIZATION
+// ================================================================================================
+
+impl Serializable for ProofContainer {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        let mut bytes = Vec::new();
+        self.write_fields(&mut bytes);
+        target.write_bytes(&bytes);
+        target.write_u32(crc32(&bytes));
+    }
+}
+
+impl Deserializable for ProofContainer {
+    /// Reads a proof container from the specified `source` and returns the result.
+    ///
+    /// # Errors
+    /// Returns an error if a valid proof container could not be read from the specified `source`,
+    /// or if the checksum of the container does not match its contents.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let mut result = read_header(source)?;
+        let proof_len = source.read_u32()? as usize;
+        result.proof = source.read_vec(proof_len)?;
+
+        // re-serialize the fields to verify the checksum; serialization is canonical, and thus,
+        // this yields exactly the bytes which have been read above
+        let mut bytes = Vec::new();
+        result.write_fields(&mut bytes);
+        let checksum = source.read_u32()?;
+        if checksum != crc32(&bytes) {
+            return Err(DeserializationError::InvalidValue(
+                "proof container checksum does not match its contents".into(),
+            ));
+        }
+
+        Ok(result)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Reads all fields of a proof container which precede the proof from the specified `source`;
+/// the proof of the returned container is empty.
+fn read_header<R: ByteReader>(source: &mut R) -> Result<ProofContainer, DeserializationError> {
+    let magic: [u8; 4] = source.read_array()?;
+    if magic != MAGIC {
+        return Err(DeserializationError::InvalidValue(
+            "bytes do not represent a proof container".into(),
+        ));
+    }
+    let version = source.read_u8()?;
+    if version != VERSION {
+        return Err(DeserializationError::InvalidValue(format!(
+            "proof container version {version} is not supported"
+        )));
+    }
+
+    let air_id = Option::<u32>::read_from(source)?;
+    let params_id = source.read_u32()?;
+    let digest_len = source.read_u8()? as usize;
+    let pub_inputs_digest = source.read_vec(digest_len)?;
+    let timestamp = source.read_u64()?;
+
+    Ok(ProofContainer {
+        air_id,
+        params_id,
+        pub_inputs_digest,
+        timestamp,
+        proof: Vec::new(),
+    })
+}
+
+/// Computes CRC-32 (IEEE 802.3) checksum of the provided bytes.
+fn crc32(bytes: &[u8]) -> u32 {
+    const POLYNOMIAL: u32 = 0xedb88320;
+
+    let mut crc = u32::MAX;
+    for &byte in bytes {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            let mask = 0u32.wrapping_sub(crc & 1);
+            crc = (crc >> 1) ^ (POLYNOMIAL & mask);
+        }
+    }
+    !crc
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{crc32, Proof, ProofContainer};
+
+    #[test]
+    fn crc32_test_vector() {
+        assert_eq!(0xcbf43926, crc32(b"123456789"));
+    }
+
+    #[test]
+    fn proof_container_serialization() {
+        let mut proof = Proof::new_dummy();
+        proof.context = proof.context.clone().with_air_id(Some(7));
+
+        let container = ProofContainer::new(&proof, 3, &[1, 2, 3, 4], 1_700_000_000);
+        assert_eq!(Some(7), container.air_id());
+        assert_eq!(3, container.params_id());
+        assert_eq!(&[1, 2, 3, 4], container.pub_inputs_digest());
+        assert_eq!(1_700_000_000, container.timestamp());
+        assert_eq!(proof.to_bytes(), container.proof_bytes());
+
+        let bytes = container.to_bytes();
+        assert_eq!(container, ProofContainer::from_bytes(&bytes).unwrap());
+
+        // the header can be read without the proof
+        let header = ProofContainer::read_header(&bytes).unwrap();
+        assert_eq!(Some(7), header.air_id());
+        assert_eq!(3, header.params_id());
+        assert!(header.proof_bytes().is_empty());
+
+        // corrupting any byte of the container is detected
+        for i in [0, 4, 10, bytes.len() / 2, bytes.len() - 1] {
+            let mut corrupted = bytes.clone();
+            corrupted[i] ^= 1;
+            assert!(ProofContainer::from_bytes(&corrupted).is_err());
+        }
+    }
+}
@@ -18,6 +18,8 @@ pub struct Context {
     trace_info: TraceInfo,
     field_modulus_bytes: Vec<u8>,
     options: ProofOptions,
+    air_id: Option<u32>,
+    lookup_table_commitment: Option<Vec<u8>>,
 }
 
 impl Context {
@@ -42,9 +44,31 @@ impl Context {
             trace_info,
             field_modulus_bytes: B::get_modulus_le_bytes(),
             options,
+            air_id: None,
+            lookup_table_commitment: None,
         }
     }
 
+    /// Returns this context with the AIR identifier set to the specified value.
+    ///
+    /// The identifier binds a proof to a specific computation; it is included into the seed of
+    /// the public coin and is serialized together with the rest of the context.
+    pub fn with_air_id(mut self, air_id: Option<u32>) -> Self {
+        self.air_id = air_id;
+        self
+    }
+
+    /// Returns this context with the commitment to fixed lookup tables set to the specified
+    /// value.
+    ///
+    /// The commitment binds a proof to the lookup tables of the AIR for which it was generated
+    /// (see [LookupTable::commitment()](crate::LookupTable::commitment)); it is included into
+    /// the seed of the public coin and is serialized together with the rest of the context.
+    pub fn with_lookup_table_commitment(mut self, commitment: Option<Vec<u8>>) -> Self {
+        self.lookup_table_commitment = commitment;
+        self
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -84,6 +108,17 @@ impl Context {
     pub fn options(&self) -> &ProofOptions {
         &self.options
     }
+
+    /// Returns the identifier of the AIR for which a proof in this context was generated, if any.
+    pub fn air_id(&self) -> Option<u32> {
+        self.air_id
+    }
+
+    /// Returns the serialized commitment to fixed lookup tables used by a proof in this context,
+    /// if any.
+    pub fn lookup_table_commitment(&self) -> Option<&[u8]> {
+        self.lookup_table_commitment.as_deref()
+    }
 }
 
 impl<E: StarkField> ToElements<E> for Context {
@@ -96,6 +131,9 @@ impl<E: StarkField> ToElements<E> for Context {
     /// - grinding factor [1 element].
     /// - blowup factor [1 element].
     /// - number of queries [1 element].
+    /// - AIR identifier presence flag [1 element].
+    /// - AIR identifier, or zero if not present [1 element].
+    /// - lookup table commitment [1 or more elements, only if present].
     fn to_elements(&self) -> Vec<E> {
         // convert trace layout
         let mut result = self.trace_info.to_elements();
@@ -109,6 +147,19 @@ impl<E: StarkField> ToElements<E> for Context {
         // convert proof options to elements
         result.append(&mut self.options.to_elements());
 
+        // append AIR identifier preceded by a presence flag; this way, contexts with and without
+        // an identifier cannot be encoded into the same elements
+        result.push(E::from(self.air_id.is_some() as u32));
+        result.push(E::from(self.air_id.unwrap_or(0)));
+
+        // append lookup table commitment (if any); similarly to trace metadata, the commitment is
+        // broken into chunks of bytes which fit into a single field element
+        if let Some(commitment) = &self.lookup_table_commitment {
+            for chunk in commitment.chunks(E::ELEMENT_BYTES - 1) {
+                result.push(E::from_bytes_with_padding(chunk));
+            }
+        }
+
         result
     }
 }
@@ -124,6 +175,8 @@ impl Serializable for Context {
         target.write_u8(self.field_modulus_bytes.len() as u8);
         target.write_bytes(&self.field_modulus_bytes);
         self.options.write_into(target);
+        self.air_id.write_into(target);
+        self.lookup_table_commitment.write_into(target);
     }
 }
 
@@ -148,7 +201,28 @@ impl Deserializable for Context {
         // read options
         let options = ProofOptions::read_from(source)?;
 
-        Ok(Context { trace_info, field_modulus_bytes, options })
+        // make sure the size of the LDE domain can be computed
+        if trace_info.length().checked_mul(options.blowup_factor()).is_none() {
+            return Err(DeserializationError::InvalidValue(format!(
+                "LDE domain size for trace length 2^{} and blowup factor {} does not fit into a usize value",
+                trace_info.length().ilog2(),
+                options.blowup_factor()
+            )));
+        }
+
+        // read AIR identifier
+        let air_id = Option::<u32>::read_from(source)?;
+
+        // read lookup table commitment
+        let lookup_table_commitment = Option::<Vec<u8>>::read_from(source)?;
+
+        Ok(Context {
+            trace_info,
+            field_modulus_bytes,
+            options,
+            air_id,
+            lookup_table_commitment,
+        })
     }
 }
 
@@ -157,7 +231,8 @@ impl Deserializable for Context {
 
 #[cfg(test)]
 mod tests {
-    use math::fields::f64::BaseElement;
+    use math::{fields::f64::BaseElement, FieldElement};
+    use utils::{Deserializable, Serializable};
 
     use super::{Context, ProofOptions, ToElements, TraceInfo};
     use crate::FieldExtension;
@@ -200,6 +275,8 @@ mod tests {
                 BaseElement::from(grinding_factor),
                 BaseElement::from(blowup_factor as u32),
                 BaseElement::from(num_queries as u32),
+                BaseElement::ZERO, // AIR identifier presence flag
+                BaseElement::ZERO, // AIR identifier
             ]);
 
             expected
@@ -217,5 +294,36 @@ mod tests {
             TraceInfo::new_multi_segment(main_width, aux_width, aux_rands, trace_length, vec![]);
         let context = Context::new::<BaseElement>(trace_info, options);
         assert_eq!(expected, context.to_elements());
+
+        // AIR identifier is appended to the end of the context elements together with a presence
+        // flag, and thus, a zero identifier is distinguishable from no identifier
+        let mut expected = expected;
+        let num_elements = expected.len();
+        expected[num_elements - 2] = BaseElement::ONE;
+        expected[num_elements - 1] = BaseElement::from(42_u32);
+        assert_eq!(expected, context.clone().with_air_id(Some(42)).to_elements());
+
+        expected[num_elements - 1] = BaseElement::ZERO;
+        assert_eq!(expected, context.with_air_id(Some(0)).to_elements());
+    }
+
+    #[test]
+    fn context_serialization() {
+        let options = ProofOptions::new(30, 8, 20, FieldExtension::None, 8, 127);
+        let context = Context::new::<BaseElement>(TraceInfo::new(4, 1024), options);
+        let bytes = context.to_bytes();
+        assert_eq!(context, Context::read_from_bytes(&bytes).unwrap());
+
+        let context = context.with_air_id(Some(7));
+        let bytes = context.to_bytes();
+        let parsed = Context::read_from_bytes(&bytes).unwrap();
+        assert_eq!(Some(7), parsed.air_id());
+        assert_eq!(context, parsed);
+
+        let context = context.with_lookup_table_commitment(Some(vec![3; 32]));
+        let bytes = context.to_bytes();
+        let parsed = Context::read_from_bytes(&bytes).unwrap();
+        assert_eq!(Some([3_u8; 32].as_slice()), parsed.lookup_table_commitment());
+        assert_eq!(context, parsed);
     }
 }
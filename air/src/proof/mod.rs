@@ -13,7 +13,7 @@ use fri::FriProof;
 use math::FieldElement;
 use utils::{ByteReader, Deserializable, DeserializationError, Serializable, SliceReader};
 
-use crate::{ProofOptions, TraceInfo};
+use crate::{ProofOptions, SoundnessMode, TraceInfo};
 
 mod context;
 pub use context::Context;
@@ -21,12 +21,21 @@ pub use context::Context;
 mod commitments;
 pub use commitments::Commitments;
 
+mod container;
+pub use container::ProofContainer;
+
 mod queries;
 pub use queries::Queries;
 
+mod opening;
+pub use opening::{BatchOpening, QueryOpening, QueryOpeningError};
+
 mod ood_frame;
 pub use ood_frame::{OodFrame, TraceOodFrame};
 
+mod size;
+pub use size::{FriProofSizeEstimate, ProofSizeEstimate, QueriesSizeEstimate};
+
 mod table;
 pub use table::Table;
 
@@ -105,22 +114,30 @@ impl Proof {
     /// security level is returned. Usually, the number of queries needed for provable security is
     /// 2x - 3x higher than the number of queries needed for conjectured security at the same
     /// security level.
+    ///
+    /// If the proof options specify a digest size (see [ProofOptions::with_digest_size()]), the
+    /// security level is limited to `digest_size * 4` bits; the same applies to the digest size
+    /// of FRI layer commitments (see [ProofOptions::with_fri_digest_size()]).
     pub fn security_level<H: Hasher>(&self, conjectured: bool) -> u32 {
-        if conjectured {
-            get_conjectured_security(
-                self.context.options(),
-                self.context.num_modulus_bits(),
-                self.trace_info().length(),
-                H::COLLISION_RESISTANCE,
-            )
+        let collision_resistance = [self.options().digest_size(), self.options().fri_digest_size()]
+            .into_iter()
+            .flatten()
+            .fold(H::COLLISION_RESISTANCE, |result, digest_size| {
+                cmp::min(result, digest_size as u32 * 4)
+            });
+
+        let mode = if conjectured {
+            SoundnessMode::Conjectured
         } else {
-            get_proven_security(
-                self.context.options(),
-                self.context.num_modulus_bits(),
-                self.trace_info().length(),
-                H::COLLISION_RESISTANCE,
-            )
-        }
+            SoundnessMode::Proven
+        };
+        get_security_level(
+            self.context.options(),
+            mode,
+            self.context.num_modulus_bits(),
+            self.trace_info().length(),
+            collision_resistance,
+        )
     }
 
     // SERIALIZATION / DESERIALIZATION
@@ -212,6 +229,27 @@ impl Deserializable for Proof {
 // HELPER FUNCTIONS
 // ================================================================================================
 
+/// Computes security level for the specified proof parameters under the specified soundness mode.
+pub(crate) fn get_security_level(
+    options: &ProofOptions,
+    mode: SoundnessMode,
+    base_field_bits: u32,
+    trace_domain_size: usize,
+    collision_resistance: u32,
+) -> u32 {
+    match mode {
+        SoundnessMode::Conjectured => get_conjectured_security(
+            options,
+            base_field_bits,
+            trace_domain_size,
+            collision_resistance,
+        ),
+        SoundnessMode::Proven => {
+            get_proven_security(options, base_field_bits, trace_domain_size, collision_resistance)
+        },
+    }
+}
+
 /// Computes conjectured security level for the specified proof parameters.
 fn get_conjectured_security(
     options: &ProofOptions,
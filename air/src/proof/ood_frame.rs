@@ -11,7 +11,7 @@ use utils::{
     ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable, SliceReader,
 };
 
-use crate::{EvaluationFrame, LagrangeKernelEvaluationFrame};
+use crate::{air::validate_frame_offsets, EvaluationFrame, LagrangeKernelEvaluationFrame};
 
 // OUT-OF-DOMAIN FRAME
 // ================================================================================================
@@ -19,8 +19,8 @@ use crate::{EvaluationFrame, LagrangeKernelEvaluationFrame};
 /// Trace and constraint polynomial evaluations at an out-of-domain point.
 ///
 /// This struct contains the following evaluations:
-/// * Evaluations of all trace polynomials at *z*.
-/// * Evaluations of all trace polynomials at *z * g*.
+/// * Evaluations of all trace polynomials at *z * g^d* for each row offset *d* of the evaluation
+///   frames of the AIR (i.e., at *z* and *z * g* by default).
 /// * Evaluations of Lagrange kernel trace polynomial (if any) at *z*, *z * g*, *z * g^2*, ...,
 ///   *z * g^(2^(v-1))*, where `v == log(trace_len)`
 /// * Evaluations of constraint composition column polynomials at *z*.
@@ -43,8 +43,8 @@ impl OodFrame {
     /// Updates the trace state portion of this out-of-domain frame, and returns the hash of the
     /// trace states.
     ///
-    /// The out-of-domain frame is stored as one vector of interleaved values, one from the current
-    /// row and the other from the next row. Given the input frame
+    /// The out-of-domain frame is stored as one vector of interleaved values, one from each row of
+    /// the frame. Given the input frame of two rows
     ///
     ///    +-------+-------+-------+-------+-------+-------+-------+-------+
     ///    |   a1  |   a2  |  ...  |  an   |  c1   |  c2   |  ...  |  cm   |
@@ -56,10 +56,12 @@ impl OodFrame {
     ///
     /// [a1, b1, a2, b2, ..., an, bn, c1, d1, c2, d2, ..., cm, dm]
     ///
-    /// into `Self::trace_states` (as byte values).
+    /// into `Self::trace_states` (as byte values). Frames with more rows are stored in the same
+    /// way, with the values of all rows interleaved for each column.
     ///
     /// # Panics
-    /// Panics if evaluation frame has already been set.
+    /// Panics if evaluation frame has already been set, or if the frame contains more than 255
+    /// rows.
     pub fn set_trace_states<E, H>(&mut self, trace_ood_frame: &TraceOodFrame<E>) -> H::Digest
     where
         E: FieldElement,
@@ -67,11 +69,11 @@ impl OodFrame {
     {
         assert!(self.trace_states.is_empty(), "trace sates have already been set");
 
-        // save the evaluations with the current and next evaluations interleaved for each polynomial
+        // save the evaluations with the evaluations of all rows interleaved for each polynomial
         let (main_and_aux_trace_states, lagrange_trace_states) = trace_ood_frame.to_trace_states();
 
-        // there are 2 frames: current and next
-        let frame_size: u8 = 2;
+        let frame_size = u8::try_from(trace_ood_frame.num_rows())
+            .expect("number of rows in an out-of-domain frame cannot exceed 255");
         self.trace_states.write_u8(frame_size);
         self.trace_states.write_many(&main_and_aux_trace_states);
 
@@ -111,8 +113,8 @@ impl OodFrame {
     ///
     /// # Errors
     /// Returns an error if:
-    /// * Valid [`crate::EvaluationFrame`]s for the specified `main_trace_width` and
-    ///   `aux_trace_width` could not be parsed from the internal bytes.
+    /// * Valid [`crate::EvaluationFrame`]s with rows at `frame_offsets` for the specified
+    ///   `main_trace_width` and `aux_trace_width` could not be parsed from the internal bytes.
     /// * A vector of evaluations specified by `num_evaluations` could not be parsed from the
     ///   internal bytes.
     /// * Any unconsumed bytes remained after the parsing was complete.
@@ -120,6 +122,7 @@ impl OodFrame {
         self,
         main_trace_width: usize,
         aux_trace_width: usize,
+        frame_offsets: &[usize],
         num_evaluations: usize,
     ) -> Result<(TraceOodFrame<E>, Vec<E>), DeserializationError> {
         assert!(main_trace_width > 0, "trace width cannot be zero");
@@ -141,24 +144,29 @@ impl OodFrame {
 
         // parse main and auxiliary trace evaluation frames. This does the reverse operation done in
         // `set_trace_states()`.
-        let (current_row, next_row) = {
+        let rows = {
             let mut reader = SliceReader::new(&self.trace_states);
             let frame_size = reader.read_u8()? as usize;
+            if frame_size != frame_offsets.len() {
+                return Err(DeserializationError::InvalidValue(format!(
+                    "expected out-of-domain frame of {} rows, but was {frame_size}",
+                    frame_offsets.len()
+                )));
+            }
             let trace = reader.read_many((main_trace_width + aux_trace_width) * frame_size)?;
 
             if reader.has_more_bytes() {
                 return Err(DeserializationError::UnconsumedBytes);
             }
 
-            let mut current_row = Vec::with_capacity(main_trace_width);
-            let mut next_row = Vec::with_capacity(main_trace_width);
-
-            for col in trace.chunks_exact(2) {
-                current_row.push(col[0]);
-                next_row.push(col[1]);
+            let mut rows = vec![Vec::with_capacity(main_trace_width); frame_size];
+            for col in trace.chunks_exact(frame_size) {
+                for (row, &value) in rows.iter_mut().zip(col) {
+                    row.push(value);
+                }
             }
 
-            (current_row, next_row)
+            rows
         };
 
         // parse the constraint evaluations
@@ -168,10 +176,10 @@ impl OodFrame {
             return Err(DeserializationError::UnconsumedBytes);
         }
 
-        Ok((
-            TraceOodFrame::new(current_row, next_row, main_trace_width, lagrange_kernel_frame),
-            evaluations,
-        ))
+        let trace_ood_frame = TraceOodFrame::new(rows, main_trace_width, lagrange_kernel_frame)
+            .with_frame_offsets(frame_offsets.to_vec());
+
+        Ok((trace_ood_frame, evaluations))
     }
 }
 
@@ -182,21 +190,24 @@ impl Serializable for OodFrame {
     /// Serializes `self` and writes the resulting bytes into the `target`.
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
         // write trace rows
-        target.write_u16(self.trace_states.len() as u16);
+        target.write_u32(self.trace_states.len() as u32);
         target.write_bytes(&self.trace_states);
 
         // write Lagrange kernel column trace rows
-        target.write_u16(self.lagrange_kernel_trace_states.len() as u16);
+        target.write_u32(self.lagrange_kernel_trace_states.len() as u32);
         target.write_bytes(&self.lagrange_kernel_trace_states);
 
         // write constraint evaluations row
-        target.write_u16(self.evaluations.len() as u16);
+        target.write_u32(self.evaluations.len() as u32);
         target.write_bytes(&self.evaluations)
     }
 
     /// Returns an estimate of how many bytes are needed to represent self.
     fn get_size_hint(&self) -> usize {
-        self.trace_states.len() + self.evaluations.len() + 4
+        self.trace_states.len()
+            + self.lagrange_kernel_trace_states.len()
+            + self.evaluations.len()
+            + 12
     }
 }
 
@@ -207,15 +218,15 @@ impl Deserializable for OodFrame {
     /// Returns an error of a valid OOD frame could not be read from the specified `source`.
     fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
         // read trace rows
-        let num_trace_state_bytes = source.read_u16()? as usize;
+        let num_trace_state_bytes = source.read_u32()? as usize;
         let trace_states = source.read_vec(num_trace_state_bytes)?;
 
         // read Lagrange kernel column trace rows
-        let num_lagrange_state_bytes = source.read_u16()? as usize;
+        let num_lagrange_state_bytes = source.read_u32()? as usize;
         let lagrange_kernel_trace_states = source.read_vec(num_lagrange_state_bytes)?;
 
         // read constraint evaluations row
-        let num_constraint_evaluation_bytes = source.read_u16()? as usize;
+        let num_constraint_evaluation_bytes = source.read_u32()? as usize;
         let evaluations = source.read_vec(num_constraint_evaluation_bytes)?;
 
         Ok(OodFrame {
@@ -231,67 +242,104 @@ impl Deserializable for OodFrame {
 
 /// Trace evaluation frame at the out-of-domain point.
 ///
-/// Stores the trace evaluations at `z` and `gz`, where `z` is a random Field element in
-/// `current_row` and `next_row`, respectively.
+/// Stores the trace evaluations at `g^d z` for each row offset `d` of the evaluation frames of
+/// the AIR, where `z` is a random field element. By default, frames contain the current and the
+/// next row, and thus, the evaluations are at `z` and `gz`.
 ///
 /// If the Air contains a Lagrange kernel auxiliary column, then that column interpolated polynomial
 /// will be evaluated at `z`, `gz`, `g^2 z`, ... `g^(2^(v-1)) z`, where `v == log(trace_len)`, and
 /// stored in `lagrange_kernel_frame`.
 pub struct TraceOodFrame<E: FieldElement> {
-    current_row: Vec<E>,
-    next_row: Vec<E>,
+    rows: Vec<Vec<E>>,
+    frame_offsets: Vec<usize>,
     main_trace_width: usize,
     lagrange_kernel_frame: Option<LagrangeKernelEvaluationFrame<E>>,
 }
 
 impl<E: FieldElement> TraceOodFrame<E> {
-    /// Creates a new [`TraceOodFrame`] from current, next and optionally Lagrange kernel frames.
+    /// Creates a new [`TraceOodFrame`] from rows of trace evaluations at `z`, `gz`, ...,
+    /// `g^(k-1) z`, and optionally, the Lagrange kernel frame.
+    ///
+    /// Frames of rows at other offsets can be created by calling
+    /// [TraceOodFrame::with_frame_offsets()] on the result.
+    ///
+    /// # Panics
+    /// Panics if fewer than two rows are provided, or if the rows have different lengths.
     pub fn new(
-        current_row: Vec<E>,
-        next_row: Vec<E>,
+        rows: Vec<Vec<E>>,
         main_trace_width: usize,
         lagrange_kernel_frame: Option<LagrangeKernelEvaluationFrame<E>>,
     ) -> Self {
-        assert_eq!(current_row.len(), next_row.len());
+        assert!(rows.len() >= 2, "out-of-domain frame must contain at least two rows");
+        assert!(rows.iter().all(|row| row.len() == rows[0].len()));
 
         Self {
-            current_row,
-            next_row,
+            frame_offsets: (0..rows.len()).collect(),
+            rows,
             main_trace_width,
             lagrange_kernel_frame,
         }
     }
 
-    /// Returns the number of columns for the current and next frames.
+    /// Sets offsets of the rows in this frame relative to `z`; that is, the rows of this frame
+    /// contain trace evaluations at `g^d z` for each offset `d`.
+    ///
+    /// # Panics
+    /// Panics if the number of offsets is different from the number of rows in this frame, or if
+    /// the offsets are not valid evaluation frame offsets.
+    pub fn with_frame_offsets(mut self, frame_offsets: Vec<usize>) -> Self {
+        assert_eq!(
+            frame_offsets.len(),
+            self.rows.len(),
+            "number of offsets must match the number of rows in the frame"
+        );
+        validate_frame_offsets(&frame_offsets);
+        self.frame_offsets = frame_offsets;
+        self
+    }
+
+    /// Returns the number of columns in each row of the frame.
     pub fn num_columns(&self) -> usize {
-        self.current_row.len()
+        self.rows[0].len()
+    }
+
+    /// Returns the number of rows in the frame.
+    pub fn num_rows(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Returns offsets of the rows in the frame.
+    pub fn frame_offsets(&self) -> &[usize] {
+        &self.frame_offsets
     }
 
     /// Returns the current row, consisting of both main and auxiliary columns.
     pub fn current_row(&self) -> &[E] {
-        &self.current_row
+        &self.rows[0]
     }
 
     /// Returns the next frame, consisting of both main and auxiliary columns.
     pub fn next_row(&self) -> &[E] {
-        &self.next_row
+        &self.rows[1]
+    }
+
+    /// Returns the row at the specified position in the frame, consisting of both main and
+    /// auxiliary columns.
+    pub fn row(&self, index: usize) -> &[E] {
+        &self.rows[index]
     }
 
     /// Returns the evaluation frame for the main trace
     pub fn main_frame(&self) -> EvaluationFrame<E> {
-        let current = self.current_row[0..self.main_trace_width].to_vec();
-        let next = self.next_row[0..self.main_trace_width].to_vec();
-
-        EvaluationFrame::from_rows(current, next)
+        let rows = self.rows.iter().map(|row| row[..self.main_trace_width].to_vec()).collect();
+        EvaluationFrame::from_offset_rows(rows, self.frame_offsets.clone())
     }
 
     /// Returns the evaluation frame for the auxiliary trace
     pub fn aux_frame(&self) -> Option<EvaluationFrame<E>> {
         if self.has_aux_frame() {
-            let current = self.current_row[self.main_trace_width..].to_vec();
-            let next = self.next_row[self.main_trace_width..].to_vec();
-
-            Some(EvaluationFrame::from_rows(current, next))
+            let rows = self.rows.iter().map(|row| row[self.main_trace_width..].to_vec()).collect();
+            Some(EvaluationFrame::from_offset_rows(rows, self.frame_offsets.clone()))
         } else {
             None
         }
@@ -313,16 +361,18 @@ impl<E: FieldElement> TraceOodFrame<E> {
 
     /// Returns true if an auxiliary frame is present
     fn has_aux_frame(&self) -> bool {
-        self.current_row.len() > self.main_trace_width
+        self.num_columns() > self.main_trace_width
     }
 
     /// Returns the main/aux frame and Lagrange kernel frame as element vectors. Specifically, the
     /// main and auxiliary frames are interleaved, as described in [`OodFrame::set_trace_states`].
     fn to_trace_states(&self) -> (Vec<E>, Vec<E>) {
-        let mut main_and_aux_frame_states = Vec::new();
-        for col in 0..self.current_row.len() {
-            main_and_aux_frame_states.push(self.current_row[col]);
-            main_and_aux_frame_states.push(self.next_row[col]);
+        let mut main_and_aux_frame_states =
+            Vec::with_capacity(self.num_columns() * self.num_rows());
+        for col in 0..self.num_columns() {
+            for row in self.rows.iter() {
+                main_and_aux_frame_states.push(row[col]);
+            }
         }
 
         let lagrange_frame_states = match self.lagrange_kernel_frame {
@@ -0,0 +1,119 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use crypto::{ElementHasher, VectorCommitment};
+use math::FieldElement;
+use utils::DeserializationError;
+
+use super::{Queries, Table};
+
+// QUERY OPENING
+// ================================================================================================
+
+/// A scheme for opening committed evaluations at query positions.
+///
+/// During the query phase of the protocol, the prover opens its commitments to the execution
+/// trace segments and to the constraint evaluations at positions drawn by the verifier, and the
+/// verifier checks the opened rows against these commitments. An implementation of this trait
+/// defines both steps, as well as how the opened rows and their opening proof are encoded in
+/// [Queries]. Thus, schemes other than batch openings of a [VectorCommitment] (e.g., aggregated
+/// openings) can be used without changes to the prover or the verifier.
+///
+/// The prover selects a scheme via the trace LDE and constraint commitment types it uses, and
+/// the verifier must be instantiated with the same scheme. [BatchOpening] is the scheme used by
+/// default.
+pub trait QueryOpening<H: ElementHasher, V: VectorCommitment<H>> {
+    /// Opens `vector_commitment` at the specified `positions`; `rows[i]` contains the values
+    /// committed to at `positions[i]`.
+    fn open<E: FieldElement<BaseField = H::BaseField>>(
+        vector_commitment: &V,
+        positions: &[usize],
+        rows: Vec<Vec<E>>,
+    ) -> Queries;
+
+    /// Parses `queries` into rows of `values_per_query` values opened at the specified
+    /// `positions` of a domain of `domain_size` elements, and checks these rows against the
+    /// `commitment`.
+    ///
+    /// `hash_row` maps an opened row to the item committed to at its position.
+    fn verify<E: FieldElement<BaseField = H::BaseField>>(
+        commitment: H::Digest,
+        positions: &[usize],
+        domain_size: usize,
+        values_per_query: usize,
+        queries: Queries,
+        hash_row: impl Fn(&[E]) -> H::Digest,
+    ) -> Result<Table<E>, QueryOpeningError>;
+}
+
+// BATCH OPENING
+// ================================================================================================
+
+/// Opens all query positions of a commitment using a single batch opening proof of the
+/// underlying [VectorCommitment].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BatchOpening;
+
+impl<H: ElementHasher, V: VectorCommitment<H>> QueryOpening<H, V> for BatchOpening {
+    fn open<E: FieldElement<BaseField = H::BaseField>>(
+        vector_commitment: &V,
+        positions: &[usize],
+        rows: Vec<Vec<E>>,
+    ) -> Queries {
+        let (_, opening_proof) = vector_commitment
+            .open_many(positions)
+            .expect("failed to generate a batch opening proof for queried positions");
+        Queries::new::<H, E, V>(opening_proof, rows)
+    }
+
+    fn verify<E: FieldElement<BaseField = H::BaseField>>(
+        commitment: H::Digest,
+        positions: &[usize],
+        domain_size: usize,
+        values_per_query: usize,
+        queries: Queries,
+        hash_row: impl Fn(&[E]) -> H::Digest,
+    ) -> Result<Table<E>, QueryOpeningError> {
+        let (opening_proof, rows) = queries
+            .parse::<E, H, V>(domain_size, positions.len(), values_per_query)
+            .map_err(QueryOpeningError::DeserializationError)?;
+
+        let items: Vec<H::Digest> = rows.rows().map(hash_row).collect();
+        V::verify_many(commitment, positions, &items, &opening_proof)
+            .map_err(|_| QueryOpeningError::CommitmentMismatch)?;
+
+        Ok(rows)
+    }
+}
+
+// QUERY OPENING ERROR
+// ================================================================================================
+
+/// Represents an error returned when opened rows could not be verified against a commitment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QueryOpeningError {
+    /// Opened rows or their opening proof could not be parsed.
+    DeserializationError(DeserializationError),
+    /// Opened rows do not match the commitment.
+    CommitmentMismatch,
+}
+
+impl fmt::Display for QueryOpeningError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::DeserializationError(err) => {
+                write!(f, "failed to parse opened rows: {err}")
+            },
+            Self::CommitmentMismatch => {
+                write!(f, "opened rows do not match the commitment")
+            },
+        }
+    }
+}
+
+impl core::error::Error for QueryOpeningError {}
@@ -51,6 +51,22 @@ impl Queries {
     pub fn new<H: Hasher, E: FieldElement, V: VectorCommitment<H>>(
         opening_proof: V::MultiProof,
         query_values: Vec<Vec<E>>,
+    ) -> Self {
+        Self::with_opening_proof_bytes(opening_proof.to_bytes(), query_values)
+    }
+
+    /// Returns queries constructed from evaluations of a set of functions at some number of points
+    /// in a domain and a serialized opening proof for these evaluations.
+    ///
+    /// This is intended for opening schemes which do not rely on batch opening proofs of a
+    /// [VectorCommitment] (see [QueryOpening](super::QueryOpening)); the encoding of the opening
+    /// proof is defined by the scheme.
+    ///
+    /// # Panics
+    /// Panics under the same conditions as [Queries::new()].
+    pub fn with_opening_proof_bytes<E: FieldElement>(
+        opening_proof: Vec<u8>,
+        query_values: Vec<Vec<E>>,
     ) -> Self {
         assert!(!query_values.is_empty(), "query values cannot be empty");
         let elements_per_query = query_values[0].len();
@@ -67,7 +83,6 @@ impl Queries {
             );
             values.write_many(elements);
         }
-        let opening_proof = opening_proof.to_bytes();
 
         Queries { opening_proof, values }
     }
@@ -94,25 +109,10 @@ impl Queries {
         V: VectorCommitment<H>,
     {
         assert!(domain_size.is_power_of_two(), "domain size must be a power of two");
-        assert!(num_queries > 0, "there must be at least one query");
-        assert!(values_per_query > 0, "a query must contain at least one value");
-
-        // make sure we have enough bytes to read the expected number of queries
-        let num_query_bytes = E::ELEMENT_BYTES * values_per_query;
-        let expected_bytes = num_queries * num_query_bytes;
-        if self.values.len() != expected_bytes {
-            return Err(DeserializationError::InvalidValue(format!(
-                "expected {} query value bytes, but was {}",
-                expected_bytes,
-                self.values.len()
-            )));
-        }
-
-        // read bytes corresponding to each query and convert them into field elements.
-        let query_values = Table::<E>::from_bytes(&self.values, num_queries, values_per_query)?;
+        let (query_values, opening_proof) = self.into_parts(num_queries, values_per_query)?;
 
         // build batch opening proof
-        let mut reader = SliceReader::new(&self.opening_proof);
+        let mut reader = SliceReader::new(&opening_proof);
         let opening_proof = <V::MultiProof as Deserializable>::read_from(&mut reader)?;
 
         // check that the opening proof matches the domain length
@@ -130,6 +130,38 @@ impl Queries {
 
         Ok((opening_proof, query_values))
     }
+
+    /// Converts internally stored bytes into a set of query values, and returns them together
+    /// with the serialized opening proof.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `num_queries` is zero.
+    /// * `values_per_query` is zero.
+    pub fn into_parts<E: FieldElement>(
+        self,
+        num_queries: usize,
+        values_per_query: usize,
+    ) -> Result<(Table<E>, Vec<u8>), DeserializationError> {
+        assert!(num_queries > 0, "there must be at least one query");
+        assert!(values_per_query > 0, "a query must contain at least one value");
+
+        // make sure we have enough bytes to read the expected number of queries
+        let num_query_bytes = E::ELEMENT_BYTES * values_per_query;
+        let expected_bytes = num_queries * num_query_bytes;
+        if self.values.len() != expected_bytes {
+            return Err(DeserializationError::InvalidValue(format!(
+                "expected {} query value bytes, but was {}",
+                expected_bytes,
+                self.values.len()
+            )));
+        }
+
+        // read bytes corresponding to each query and convert them into field elements.
+        let query_values = Table::<E>::from_bytes(&self.values, num_queries, values_per_query)?;
+
+        Ok((query_values, self.opening_proof))
+    }
 }
 
 // SERIALIZATION
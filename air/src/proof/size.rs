@@ -0,0 +1,282 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::vec::Vec;
+
+use math::StarkField;
+use utils::Serializable;
+
+use super::{powf, Context};
+use crate::AirContext;
+
+// PROOF SIZE ESTIMATE
+// ================================================================================================
+
+/// Predicted size (in bytes) of a serialized STARK proof, broken down by proof component.
+///
+/// An estimate is computed from the parameters of a computation (i.e., trace dimensions,
+/// constraint degrees, and proof options) without generating a proof. Sizes of components which
+/// do not depend on the randomness of the protocol (i.e., proof context, commitments,
+/// out-of-domain frame, FRI remainder, and DEEP evaluations) are exact. Sizes of query
+/// decommitments depend on which positions are queried by the verifier; for these components,
+/// expected sizes assuming uniformly random query positions are reported.
+///
+/// GKR proofs are not accounted for; proofs of computations which use LogUp-GKR will be larger
+/// than the estimate by the size of the GKR proof.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofSizeEstimate {
+    /// Size of the proof context.
+    pub context: usize,
+    /// Size of commitments to the trace segments, constraint evaluations, and FRI layers.
+    pub commitments: usize,
+    /// Expected sizes of trace decommitments; one entry per trace segment.
+    pub trace_queries: Vec<QueriesSizeEstimate>,
+    /// Expected size of constraint evaluation decommitments.
+    pub constraint_queries: QueriesSizeEstimate,
+    /// Size of the out-of-domain evaluation frame.
+    pub ood_frame: usize,
+    /// Expected size of the FRI proof.
+    pub fri_proof: FriProofSizeEstimate,
+    /// Size of the remaining proof fields (i.e., the number of unique queries, proof-of-work
+    /// nonce, and the GKR proof flag).
+    pub other: usize,
+}
+
+impl ProofSizeEstimate {
+    /// Returns an estimate of the size of a proof for the computation described by the specified
+    /// `context`.
+    ///
+    /// `air_id` must be the identifier of the AIR (see [Air::ID](crate::Air::ID)), and
+    /// `digest_size` must be the size (in bytes) of digests produced by the hash function used
+    /// for vector commitments. If the proof options specify a digest size for FRI layer
+    /// commitments (see [ProofOptions::with_fri_digest_size()]), this size is used for FRI layers
+    /// instead.
+    ///
+    /// [ProofOptions::with_fri_digest_size()]: crate::ProofOptions::with_fri_digest_size
+    pub fn new<B: StarkField>(
+        context: &AirContext<B>,
+        air_id: Option<u32>,
+        digest_size: usize,
+    ) -> Self {
+        let trace_info = context.trace_info();
+        let options = context.options();
+        let fri_options = options.to_fri_options();
+
+        let base_element_bytes = B::ELEMENT_BYTES;
+        let element_bytes = base_element_bytes * options.field_extension().degree() as usize;
+        let lde_domain_size = context.lde_domain_size();
+        let num_queries = options.num_queries();
+        let num_fri_layers = fri_options.num_fri_layers(lde_domain_size);
+        let num_composition_columns = context.num_constraint_composition_columns();
+        let fri_digest_size = options.fri_digest_size().unwrap_or(digest_size);
+
+        // --- proof context ----------------------------------------------------------------------
+        let proof_context = Context::new::<B>(trace_info.clone(), options.clone())
+            .with_air_id(air_id)
+            .to_bytes()
+            .len();
+
+        // --- commitments ------------------------------------------------------------------------
+        // one commitment per trace segment, one for constraint evaluations, one per FRI layer,
+        // and one for the FRI remainder
+        let num_commitments = trace_info.num_segments() + 1;
+        let num_fri_commitments = num_fri_layers + 1;
+        let commitments = 2 + num_commitments * digest_size + num_fri_commitments * fri_digest_size;
+
+        // --- trace and constraint queries -------------------------------------------------------
+        let mut trace_queries = vec![QueriesSizeEstimate::new(
+            lde_domain_size,
+            num_queries,
+            trace_info.main_trace_width() * base_element_bytes,
+            digest_size,
+        )];
+        if trace_info.is_multi_segment() {
+            trace_queries.push(QueriesSizeEstimate::new(
+                lde_domain_size,
+                num_queries,
+                trace_info.aux_segment_width() * element_bytes,
+                digest_size,
+            ));
+        }
+        let constraint_queries = QueriesSizeEstimate::new(
+            lde_domain_size,
+            num_queries,
+            num_composition_columns * element_bytes,
+            digest_size,
+        );
+
+        // --- out-of-domain frame ----------------------------------------------------------------
+        // the Lagrange kernel column is evaluated at log(trace_len) + 1 points, while all other
+        // trace columns are evaluated at 2 points
+        let (num_frame_columns, num_lagrange_kernel_evaluations) =
+            if context.has_lagrange_kernel_aux_column() {
+                (trace_info.width() - 1, context.trace_len().ilog2() as usize + 1)
+            } else {
+                (trace_info.width(), 0)
+            };
+        let ood_frame = (4 + 1 + 2 * num_frame_columns * element_bytes)
+            + (4 + 1 + num_lagrange_kernel_evaluations * element_bytes)
+            + (4 + num_composition_columns * element_bytes);
+
+        // --- FRI proof --------------------------------------------------------------------------
+        let mut layers = Vec::with_capacity(num_fri_layers);
+        let mut domain_size = lde_domain_size;
+        for depth in 0..num_fri_layers {
+            let folding_factor = fri_options.layer_folding_factor(depth);
+            layers.push(QueriesSizeEstimate::new(
+                domain_size / folding_factor,
+                num_queries,
+                folding_factor * element_bytes,
+                fri_digest_size,
+            ));
+            domain_size /= folding_factor;
+        }
+        let remainder = domain_size / fri_options.blowup_factor() * element_bytes;
+        let remainder = encoded_len(remainder) + remainder;
+        let num_deep_evaluations = if fri_options.deep_folding() {
+            (0..num_fri_layers).map(|depth| fri_options.layer_folding_factor(depth)).sum()
+        } else {
+            0
+        };
+        let deep_evaluations = num_deep_evaluations * element_bytes;
+        let deep_evaluations = encoded_len(deep_evaluations) + deep_evaluations;
+        let fri_proof = FriProofSizeEstimate {
+            layers,
+            remainder,
+            deep_evaluations,
+            other: 2,
+        };
+
+        ProofSizeEstimate {
+            context: proof_context,
+            commitments,
+            trace_queries,
+            constraint_queries,
+            ood_frame,
+            fri_proof,
+            other: 1 + 8 + 1,
+        }
+    }
+
+    /// Returns the estimated total size of a proof in bytes.
+    pub fn total(&self) -> usize {
+        self.context
+            + self.commitments
+            + self.trace_queries.iter().map(QueriesSizeEstimate::total).sum::<usize>()
+            + self.constraint_queries.total()
+            + self.ood_frame
+            + self.fri_proof.total()
+            + self.other
+    }
+}
+
+// FRI PROOF SIZE ESTIMATE
+// ================================================================================================
+
+/// Predicted size (in bytes) of a serialized FRI proof, broken down by FRI layer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FriProofSizeEstimate {
+    /// Expected sizes of FRI layer decommitments; one entry per FRI layer.
+    pub layers: Vec<QueriesSizeEstimate>,
+    /// Size of the FRI remainder polynomial.
+    pub remainder: usize,
+    /// Size of DEEP evaluations of FRI layer polynomials; this includes only the length prefix
+    /// when DEEP folding is not used.
+    pub deep_evaluations: usize,
+    /// Size of the remaining FRI proof fields (i.e., the number of layers and partitions).
+    pub other: usize,
+}
+
+impl FriProofSizeEstimate {
+    /// Returns the estimated total size of a FRI proof in bytes.
+    pub fn total(&self) -> usize {
+        self.layers.iter().map(QueriesSizeEstimate::total).sum::<usize>()
+            + self.remainder
+            + self.deep_evaluations
+            + self.other
+    }
+}
+
+// QUERIES SIZE ESTIMATE
+// ================================================================================================
+
+/// Expected size (in bytes) of decommitments to a vector commitment at a set of randomly chosen
+/// positions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueriesSizeEstimate {
+    /// Expected size of the opened values.
+    pub values: usize,
+    /// Expected size of the batch opening proof.
+    pub opening_proof: usize,
+}
+
+impl QueriesSizeEstimate {
+    /// Returns an estimate of the size of [Queries](super::Queries) against a vector commitment
+    /// to `num_leaves` leaves at `num_queries` random positions, where each leaf holds
+    /// `leaf_bytes` bytes of values.
+    fn new(num_leaves: usize, num_queries: usize, leaf_bytes: usize, digest_size: usize) -> Self {
+        let (values, opening_proof) =
+            estimate_openings(num_leaves, num_queries, leaf_bytes, digest_size);
+        QueriesSizeEstimate {
+            values: encoded_len(values) + values,
+            opening_proof: encoded_len(opening_proof) + opening_proof,
+        }
+    }
+
+    /// Returns the expected total size of the decommitments in bytes.
+    pub fn total(&self) -> usize {
+        self.values + self.opening_proof
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the expected sizes of opened values and of the batch opening proof for a Merkle tree
+/// with `num_leaves` leaves opened at `num_queries` positions drawn uniformly at random, where
+/// each leaf holds `leaf_bytes` bytes of values.
+///
+/// Positions are drawn with replacement, and duplicate positions are opened only once. Since
+/// a batch opening proof omits nodes which can be computed from the opened leaves, the expected
+/// number of nodes in the proof is computed level by level: a node is included if its parent has
+/// at least one opened descendant but the node itself does not.
+fn estimate_openings(
+    num_leaves: usize,
+    num_queries: usize,
+    leaf_bytes: usize,
+    digest_size: usize,
+) -> (usize, usize) {
+    let depth = num_leaves.ilog2();
+
+    // expected number of nodes at each level of the tree which have at least one opened
+    // descendant; level 0 contains the leaves
+    let occupied_nodes = (0..=depth)
+        .map(|level| {
+            let num_nodes = (num_leaves >> level) as f64;
+            num_nodes * (1.0 - powf(1.0 - 1.0 / num_nodes, num_queries as f64))
+        })
+        .collect::<Vec<_>>();
+    let num_digests = occupied_nodes.windows(2).map(|w| 2.0 * w[1] - w[0]).sum::<f64>();
+    let num_unique_queries = round(occupied_nodes[0]);
+
+    // a batch opening proof consists of the tree depth, the number of opened leaves, and a
+    // length-prefixed vector of digests per opened leaf
+    let opening_proof = 1
+        + encoded_len(num_unique_queries)
+        + num_unique_queries
+        + round(num_digests * digest_size as f64);
+
+    (num_unique_queries * leaf_bytes, opening_proof)
+}
+
+/// Returns the number of bytes needed to encode `value` as a variable-length integer.
+fn encoded_len(value: usize) -> usize {
+    value.get_size_hint()
+}
+
+/// Rounds a non-negative value to the nearest integer.
+fn round(value: f64) -> usize {
+    (value + 0.5) as usize
+}
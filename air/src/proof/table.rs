@@ -15,7 +15,7 @@ use super::{DeserializationError, SliceReader};
 // ================================================================================================
 
 const MAX_ROWS: usize = 255;
-const MAX_COLS: usize = 255;
+const MAX_COLS: usize = 65535;
 
 // TABLE
 // ================================================================================================
@@ -39,7 +39,7 @@ impl<E: FieldElement> Table<E> {
     /// # Panics
     /// Panics if:
     /// * Specified number of rows is 0 or greater than 255.
-    /// * Specified number of columns is 0 or greater than 255.
+    /// * Specified number of columns is 0 or greater than 65535.
     /// * Provided bytes do not encode valid field elements required to fill the table.
     pub fn from_bytes(
         bytes: &[u8],
@@ -48,12 +48,12 @@ impl<E: FieldElement> Table<E> {
     ) -> Result<Self, DeserializationError> {
         assert!(num_rows > 0, "number of rows must be greater than 0");
         assert!(
-            num_rows < MAX_ROWS,
+            num_rows <= MAX_ROWS,
             "number of rows cannot exceed {MAX_ROWS}, but was {num_rows}"
         );
         assert!(num_cols > 0, "number of columns must be greater than 0");
         assert!(
-            num_cols < MAX_ROWS,
+            num_cols <= MAX_COLS,
             "number of columns cannot exceed {MAX_COLS}, but was {num_cols}"
         );
 
@@ -85,7 +85,7 @@ impl<E: FieldElement> Table<E> {
     }
 
     /// Returns an iterator over rows of this table.
-    pub fn rows(&self) -> RowIterator<E> {
+    pub fn rows(&self) -> RowIterator<'_, E> {
         RowIterator::new(self)
     }
 
@@ -9,3 +9,112 @@ use super::Proof;
 pub fn starkproof_new_dummy_doesnt_panic() {
     let _ = Proof::new_dummy();
 }
+
+#[test]
+fn security_level_with_digest_size() {
+    use crypto::hashers::Blake3_256;
+    use math::fields::f128::BaseElement;
+
+    use super::Context;
+    use crate::{FieldExtension, ProofOptions, TraceInfo};
+
+    type Hasher = Blake3_256<BaseElement>;
+
+    let options = ProofOptions::new(96, 8, 0, FieldExtension::None, 8, 255);
+    let mut proof = Proof::new_dummy();
+    proof.context = Context::new::<BaseElement>(TraceInfo::new(4, 1 << 16), options.clone());
+    assert_eq!(108, proof.security_level::<Hasher>(true));
+    let proven_security = proof.security_level::<Hasher>(false);
+
+    // truncating digests to 20 bytes limits collision resistance to 80 bits
+    let options = options.with_digest_size(20);
+    proof.context = Context::new::<BaseElement>(TraceInfo::new(4, 1 << 16), options);
+    assert_eq!(80, proof.security_level::<Hasher>(true));
+    assert_eq!(proven_security.min(80), proof.security_level::<Hasher>(false));
+
+    // FRI layer digests truncated to 18 bytes limit collision resistance to 72 bits
+    let options = ProofOptions::new(96, 8, 0, FieldExtension::None, 8, 255)
+        .with_digest_size(20)
+        .with_fri_digest_size(18);
+    proof.context = Context::new::<BaseElement>(TraceInfo::new(4, 1 << 16), options);
+    assert_eq!(72, proof.security_level::<Hasher>(true));
+}
+
+#[test]
+fn proof_size_estimate_fri_layers() {
+    use math::fields::f128::BaseElement;
+
+    use super::ProofSizeEstimate;
+    use crate::{AirContext, FieldExtension, ProofOptions, TraceInfo, TransitionConstraintDegree};
+
+    let degrees = vec![TransitionConstraintDegree::new(2)];
+    let options = ProofOptions::new(32, 8, 0, FieldExtension::None, 4, 7);
+    let context = AirContext::<BaseElement>::new(TraceInfo::new(4, 1 << 12), degrees, 2, options);
+    let estimate = ProofSizeEstimate::new(&context, None, 32);
+
+    // the LDE domain of 2^15 elements is folded by a factor of 4 until at most 64 elements remain
+    assert_eq!(5, estimate.fri_proof.layers.len());
+    assert_eq!(1 + 32 / 8 * 16, estimate.fri_proof.remainder);
+    assert_eq!(1, estimate.fri_proof.deep_evaluations);
+
+    // trace, constraint, and FRI layer roots, and the remainder commitment
+    assert_eq!(2 + (1 + 1 + 5 + 1) * 32, estimate.commitments);
+
+    // openings get cheaper as layers get smaller
+    for layers in estimate.fri_proof.layers.windows(2) {
+        assert!(layers[1].opening_proof < layers[0].opening_proof);
+    }
+}
+
+#[test]
+fn proof_size_estimate_fri_digest_size() {
+    use math::fields::f128::BaseElement;
+
+    use super::ProofSizeEstimate;
+    use crate::{AirContext, FieldExtension, ProofOptions, TraceInfo, TransitionConstraintDegree};
+
+    let degrees = vec![TransitionConstraintDegree::new(2)];
+    let options = ProofOptions::new(32, 8, 0, FieldExtension::None, 4, 7).with_fri_digest_size(16);
+    let context = AirContext::<BaseElement>::new(TraceInfo::new(4, 1 << 12), degrees, 2, options);
+    let estimate = ProofSizeEstimate::new(&context, None, 32);
+
+    // trace and constraint roots use 32-byte digests, while FRI layer roots and the remainder
+    // commitment use 16-byte digests
+    assert_eq!(2 + (1 + 1) * 32 + (5 + 1) * 16, estimate.commitments);
+}
+
+#[test]
+fn ood_frame_serialization_max_trace_width() {
+    use crypto::hashers::Blake3_256;
+    use math::{fields::f128::BaseElement, FieldElement};
+    use rand_utils::rand_vector;
+    use utils::{ByteReader, Deserializable, Serializable, SliceReader};
+
+    use super::{OodFrame, TraceOodFrame};
+    use crate::TraceInfo;
+
+    type Hasher = Blake3_256<BaseElement>;
+
+    // trace states of a frame at the maximum trace width take up more than 2^16 bytes
+    let width = TraceInfo::MAX_TRACE_WIDTH;
+    let rows = vec![rand_vector::<BaseElement>(width), rand_vector::<BaseElement>(width)];
+    let evaluations = rand_vector::<BaseElement>(width);
+    let trace_ood_frame = TraceOodFrame::new(rows.clone(), width, None);
+
+    let mut ood_frame = OodFrame::default();
+    ood_frame.set_trace_states::<BaseElement, Hasher>(&trace_ood_frame);
+    ood_frame.set_constraint_evaluations(&evaluations);
+
+    let bytes = ood_frame.to_bytes();
+    assert!(bytes.len() > 2 * width * BaseElement::ELEMENT_BYTES);
+    let mut reader = SliceReader::new(&bytes);
+    let parsed = OodFrame::read_from(&mut reader).unwrap();
+    assert!(!reader.has_more_bytes());
+    assert_eq!(ood_frame, parsed);
+
+    let (parsed_trace_frame, parsed_evaluations) =
+        parsed.parse::<BaseElement>(width, 0, &[0, 1], width).unwrap();
+    assert_eq!(rows[0], parsed_trace_frame.current_row());
+    assert_eq!(rows[1], parsed_trace_frame.next_row());
+    assert_eq!(evaluations, parsed_evaluations);
+}
@@ -4,16 +4,19 @@
 // LICENSE file in the root directory of this source tree.
 
 use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
-use math::fields::f128;
-use rand_utils::rand_value;
+use math::fields::{f128, f64};
+use rand_utils::{rand_array, rand_value};
 use winter_crypto::{
-    hashers::{Blake3_256, Rp62_248, Rp64_256, RpJive64_256, Sha3_256},
-    Hasher,
+    hashers::{Blake2s256, Blake3_256, Rp62_248, Rp64_256, RpJive64_256, Sha3_256},
+    ElementHasher, Hasher, HASH_BATCH_SIZE,
 };
 
 type Blake3 = Blake3_256<f128::BaseElement>;
 type Blake3Digest = <Blake3 as Hasher>::Digest;
 
+type Blake2s = Blake2s256<f128::BaseElement>;
+type Blake2sDigest = <Blake2s as Hasher>::Digest;
+
 type Sha3 = Sha3_256<f128::BaseElement>;
 type Sha3Digest = <Sha3 as Hasher>::Digest;
 
@@ -39,6 +42,24 @@ fn blake3(c: &mut Criterion) {
     });
 }
 
+fn blake2s(c: &mut Criterion) {
+    let v: [Blake2sDigest; 2] = [Blake2s::hash(&[1u8]), Blake2s::hash(&[2u8])];
+    c.bench_function("hash_blake2s (cached)", |bench| bench.iter(|| Blake2s::merge(black_box(&v))));
+
+    c.bench_function("hash_blake2s (random)", |b| {
+        b.iter_batched(
+            || {
+                [
+                    Blake2s::hash(&rand_value::<u64>().to_le_bytes()),
+                    Blake2s::hash(&rand_value::<u64>().to_le_bytes()),
+                ]
+            },
+            |state| Blake2s::merge(&state),
+            BatchSize::SmallInput,
+        )
+    });
+}
+
 fn sha3(c: &mut Criterion) {
     let v: [Sha3Digest; 2] = [Sha3::hash(&[1u8]), Sha3::hash(&[2u8])];
     c.bench_function("hash_sha3 (cached)", |bench| bench.iter(|| Sha3::merge(black_box(&v))));
@@ -117,5 +138,51 @@ fn rescue_jive256(c: &mut Criterion) {
     });
 }
 
-criterion_group!(hash_group, blake3, sha3, rescue248, rescue256, rescue_jive256);
+fn batch<H: ElementHasher<BaseField = f64::BaseElement>>(c: &mut Criterion, name: &str) {
+    const ROW_WIDTH: usize = 16;
+
+    let mut group = c.benchmark_group(format!("hash_batch_{name}"));
+
+    let pairs: [[H::Digest; 2]; HASH_BATCH_SIZE] = core::array::from_fn(|i| {
+        [
+            H::hash(&(2 * i as u64).to_le_bytes()),
+            H::hash(&(2 * i as u64 + 1).to_le_bytes()),
+        ]
+    });
+    group.bench_function("merge", |bench| {
+        bench.iter(|| black_box(&pairs).map(|pair| H::merge(&pair)))
+    });
+    group.bench_function("merge_batch", |bench| bench.iter(|| H::merge_batch(black_box(&pairs))));
+
+    let rows: [[f64::BaseElement; ROW_WIDTH]; HASH_BATCH_SIZE] =
+        core::array::from_fn(|_| rand_array());
+    group.bench_function("hash_elements", |bench| {
+        bench.iter(|| black_box(&rows).each_ref().map(|row| H::hash_elements(row)))
+    });
+    group.bench_function("hash_elements_batch", |bench| {
+        bench.iter(|| H::hash_elements_batch(black_box(&rows).each_ref().map(|row| &row[..])))
+    });
+
+    group.finish();
+}
+
+fn blake3_batch(c: &mut Criterion) {
+    batch::<Blake3_256<f64::BaseElement>>(c, "blake3");
+}
+
+fn rescue256_batch(c: &mut Criterion) {
+    batch::<Rp64_256>(c, "rp64_256");
+}
+
+criterion_group!(
+    hash_group,
+    blake3,
+    blake2s,
+    sha3,
+    rescue248,
+    rescue256,
+    rescue_jive256,
+    blake3_batch,
+    rescue256_batch
+);
 criterion_main!(hash_group);
@@ -25,6 +25,18 @@ use crate::Hasher;
 ///
 /// The current implementation restricts both of the commitment string as well as the leaf values
 /// to be `H::Digest` where `H` is a type parameter such that `H: Hasher`.
+///
+/// The STARK prover and verifier use a single scheme for commitments to the execution trace, to
+/// the constraint evaluations, and to the FRI layers. The scheme is specified via the `VC`
+/// associated type of the prover and the `VC` type parameter of the verifier; thus, schemes other
+/// than the binary [MerkleTree](crate::MerkleTree) (e.g., [KaryMerkleTree](crate::KaryMerkleTree)
+/// or [SaltedMerkleTree](crate::SaltedMerkleTree)) can be used without modifying either of them.
+/// When implementing a new scheme, the following should be kept in mind:
+/// * The prover builds commitments using [VectorCommitment::new()], and thus, with the default
+///   options of the scheme.
+/// * The verifier deserializes batch opening proofs from untrusted input, and checks that the
+///   length of the committed vector reported by [VectorCommitment::get_multiproof_domain_len()]
+///   matches the expected domain size before verifying the proofs.
 pub trait VectorCommitment<H: Hasher>: Sized {
     /// Options defining the VC i.e., public parameters.
     type Options: Default;
@@ -35,6 +47,10 @@ pub trait VectorCommitment<H: Hasher>: Sized {
     /// Error returned by the scheme.
     type Error: Debug;
 
+    /// Indicates whether the commitment reveals no information about the committed values which
+    /// are not opened (e.g., because committed values are hashed together with random salts).
+    const IS_HIDING: bool = false;
+
     /// Creates a commitment to a vector of values (v_0, ..., v_{n-1}) using the default
     /// options.
     fn new(items: Vec<H::Digest>) -> Result<Self, Self::Error> {
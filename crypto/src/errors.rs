@@ -3,6 +3,7 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use alloc::string::String;
 use core::fmt;
 
 // MERKLE TREE ERROR
@@ -25,6 +26,24 @@ pub enum MerkleTreeError {
     TooManyLeafIndexes(usize, usize),
     /// Merkle proof is not valid for the specified position(s).
     InvalidProof,
+    /// Number of leaves provided for verifying a batch Merkle proof was not equal to the number
+    /// of leaf indexes.
+    LeafCountMismatch(usize, usize),
+    /// A batch Merkle proof did not contain the nodes needed to authenticate the leaf at the
+    /// specified index.
+    MissingProofNodes(usize),
+    /// A random seed for salting the leaves of a Merkle tree could not be generated.
+    SaltSeedUnavailable,
+    /// Storage holding the nodes of a Merkle tree could not be accessed.
+    StorageError(String),
+    /// No trees were provided to construct a Merkle forest.
+    EmptyForest,
+    /// Number of index lists for a Merkle forest proof was not equal to the number of trees.
+    InvalidNumberOfTrees(usize, usize),
+    /// Number of subtrees used to construct a Merkle tree was zero or not a power of two.
+    InvalidNumberOfSubtrees(usize),
+    /// Subtrees used to construct a Merkle tree did not all have the same depth.
+    SubtreeDepthMismatch(usize, usize),
 }
 
 impl fmt::Display for MerkleTreeError {
@@ -57,6 +76,39 @@ impl fmt::Display for MerkleTreeError {
             Self::InvalidProof => {
                 write!(f, "Merkle proof is invalid")
             },
+            Self::LeafCountMismatch(num_indexes, num_leaves) => {
+                write!(
+                    f,
+                    "expected {num_indexes} leaves for {num_indexes} leaf indexes, but {num_leaves} were provided"
+                )
+            },
+            Self::MissingProofNodes(index) => {
+                write!(f, "Merkle proof is missing nodes needed to authenticate the leaf at index {index}")
+            },
+            Self::SaltSeedUnavailable => {
+                write!(f, "a random seed for salting Merkle tree leaves could not be generated")
+            },
+            Self::StorageError(err) => {
+                write!(f, "failed to access Merkle tree storage: {err}")
+            },
+            Self::EmptyForest => {
+                write!(f, "a Merkle forest must contain at least one tree")
+            },
+            Self::InvalidNumberOfTrees(expected, actual) => {
+                write!(f, "expected leaf indexes for {expected} trees, but {actual} were provided")
+            },
+            Self::InvalidNumberOfSubtrees(num_subtrees) => {
+                write!(
+                    f,
+                    "number of subtrees must be a non-zero power of two, but {num_subtrees} were provided"
+                )
+            },
+            Self::SubtreeDepthMismatch(expected, actual) => {
+                write!(
+                    f,
+                    "all subtrees must have depth {expected}, but a subtree had depth {actual}"
+                )
+            },
         }
     }
 }
@@ -93,3 +145,79 @@ impl fmt::Display for RandomCoinError {
 }
 
 impl core::error::Error for RandomCoinError {}
+
+// HASH TO FIELD ERROR
+// ================================================================================================
+
+/// Defines errors which can occur when hashing data into field elements.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HashToFieldError {
+    /// The domain separation tag was empty.
+    EmptyDomainSeparationTag,
+    /// The requested number of output bytes was zero or greater than 65535.
+    InvalidOutputLength(usize),
+}
+
+impl fmt::Display for HashToFieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EmptyDomainSeparationTag => {
+                write!(f, "domain separation tag must not be empty")
+            },
+            Self::InvalidOutputLength(len) => {
+                write!(f, "output length must be between 1 and 65535 bytes, but was {len}")
+            },
+        }
+    }
+}
+
+impl core::error::Error for HashToFieldError {}
+
+// RESCUE PARAMETERS ERROR
+// ================================================================================================
+
+/// Defines errors which can occur when instantiating Rescue permutations with runtime parameters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RescueParamsError {
+    /// The capacity was zero or was not smaller than the state width.
+    InvalidCapacity(usize, usize),
+    /// The S-Box power was smaller than 3 or was not co-prime with p - 1.
+    InvalidAlpha(u64),
+    /// The MDS matrix was not a square matrix with the number of rows equal to the state width.
+    InvalidMdsMatrix(usize),
+    /// Round constants were empty, were provided for different numbers of rounds, or contained
+    /// rows with the number of elements different from the state width.
+    InvalidRoundConstants(usize),
+    /// Round constants could not be derived from the seed.
+    HashToFieldFailed(HashToFieldError),
+}
+
+impl fmt::Display for RescueParamsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidCapacity(capacity, width) => {
+                write!(f, "capacity must be non-zero and smaller than {width}, but was {capacity}")
+            },
+            Self::InvalidAlpha(alpha) => {
+                write!(f, "S-Box power {alpha} must be at least 3 and co-prime with p - 1")
+            },
+            Self::InvalidMdsMatrix(width) => {
+                write!(f, "MDS matrix must have dimensions {width} x {width}")
+            },
+            Self::InvalidRoundConstants(width) => {
+                write!(f, "round constants must contain two rows of {width} elements per round")
+            },
+            Self::HashToFieldFailed(err) => {
+                write!(f, "failed to derive round constants: {err}")
+            },
+        }
+    }
+}
+
+impl core::error::Error for RescueParamsError {}
+
+impl From<HashToFieldError> for RescueParamsError {
+    fn from(err: HashToFieldError) -> Self {
+        Self::HashToFieldFailed(err)
+    }
+}
@@ -0,0 +1,229 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Multi-message BLAKE3 hashing.
+//!
+//! This module hashes `N` independent messages of the same length at once. Full 64-byte blocks
+//! of the messages are compressed by the `hash_many` routine of the `blake3` crate, which
+//! detects the SIMD instructions available at runtime (SSE2/SSE4.1/AVX2/AVX-512 or NEON) and
+//! compresses up to 16 messages in parallel. Note that this routine is exposed by the `blake3`
+//! crate as a hidden API; for this reason, the `blake3` dependency is pinned to a minor version.
+//!
+//! A trailing partial block (if any) is compressed by a portable implementation of the
+//! compression function which keeps the state in "transposed" form: each of the 16 state words
+//! holds one lane per message, and all operations are applied lane-wise, so that the compiler
+//! can vectorize them.
+//!
+//! Only messages which fit into a single BLAKE3 chunk (i.e., messages of at most 1024 bytes) are
+//! supported; this covers Merkle tree nodes and rows of most execution traces.
+
+use blake3::{platform::Platform, IncrementCounter};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Maximum length of a message which can be hashed by [hash_many()].
+pub const MAX_MESSAGE_LEN: usize = 1024;
+
+const BLOCK_LEN: usize = 64;
+
+const IV: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+const MSG_PERMUTATION: [usize; 16] = [2, 6, 3, 10, 7, 0, 4, 13, 1, 11, 12, 5, 9, 14, 15, 8];
+
+const CHUNK_START: u8 = 1 << 0;
+const CHUNK_END: u8 = 1 << 1;
+const ROOT: u8 = 1 << 3;
+
+// MULTI-MESSAGE HASHING
+// ================================================================================================
+
+/// Returns BLAKE3 hashes of the provided messages.
+///
+/// The result is the same as hashing each message with `blake3::hash()`.
+///
+/// # Panics
+/// Panics if the messages are not all of the same length, or if the length exceeds
+/// [MAX_MESSAGE_LEN].
+pub fn hash_many<const N: usize>(messages: [&[u8]; N]) -> [[u8; 32]; N] {
+    let message_len = messages.first().map_or(0, |message| message.len());
+    assert!(message_len <= MAX_MESSAGE_LEN, "message is too long");
+    assert!(messages.iter().all(|message| message.len() == message_len), "lengths differ");
+
+    // compress all full blocks with SIMD instructions; if there is no trailing partial block,
+    // the last full block is the root of the hash
+    let num_full_blocks = message_len / BLOCK_LEN;
+    let tail_len = message_len % BLOCK_LEN;
+    let mut cv = [[0_u8; 32]; N];
+    if num_full_blocks > 0 {
+        let flags_end = if tail_len == 0 { CHUNK_END | ROOT } else { 0 };
+        compress_full_blocks(&messages, num_full_blocks, flags_end, cv.as_flattened_mut());
+        if tail_len == 0 {
+            return cv;
+        }
+    } else {
+        cv = [words_to_bytes(&IV); N];
+    }
+
+    // compress the trailing partial block (or the empty block of empty messages) of all
+    // messages lane by lane
+    let mut lanes: [[u32; N]; 8] = [[0; N]; 8];
+    for (lane, cv) in cv.iter().enumerate() {
+        for (word, chunk) in lanes.iter_mut().zip(cv.chunks_exact(4)) {
+            word[lane] = u32::from_le_bytes(chunk.try_into().unwrap());
+        }
+    }
+
+    let block_start = num_full_blocks * BLOCK_LEN;
+    let mut block = [[0_u32; N]; 16];
+    for (lane, message) in messages.iter().enumerate() {
+        let mut bytes = [0_u8; BLOCK_LEN];
+        bytes[..tail_len].copy_from_slice(&message[block_start..]);
+        for (word, chunk) in block.iter_mut().zip(bytes.chunks_exact(4)) {
+            word[lane] = u32::from_le_bytes(chunk.try_into().unwrap());
+        }
+    }
+
+    let flags = if num_full_blocks == 0 { CHUNK_START } else { 0 } | CHUNK_END | ROOT;
+    compress(&mut lanes, &block, tail_len as u32, flags as u32);
+
+    // the first 32 bytes of the root output are the chaining value of the last block
+    core::array::from_fn(|lane| words_to_bytes(&lanes.map(|word| word[lane])))
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Compresses the first `num_full_blocks` blocks of all messages with SIMD instructions, and
+/// writes the resulting chaining values into `out`.
+fn compress_full_blocks<const N: usize>(
+    messages: &[&[u8]; N],
+    num_full_blocks: usize,
+    flags_end: u8,
+    out: &mut [u8],
+) {
+    macro_rules! hash_blocks {
+        ($($num_blocks:literal),*) => {
+            match num_full_blocks {
+                $($num_blocks => {
+                    const LEN: usize = $num_blocks * BLOCK_LEN;
+                    let inputs: [&[u8; LEN]; N] =
+                        messages.map(|message| message[..LEN].try_into().unwrap());
+                    Platform::detect().hash_many(
+                        &inputs,
+                        &IV,
+                        0,
+                        IncrementCounter::No,
+                        0,
+                        CHUNK_START,
+                        flags_end,
+                        out,
+                    );
+                })*
+                _ => unreachable!("messages must fit into a single chunk"),
+            }
+        };
+    }
+
+    hash_blocks!(1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16);
+}
+
+fn words_to_bytes(words: &[u32; 8]) -> [u8; 32] {
+    let mut result = [0_u8; 32];
+    for (chunk, word) in result.chunks_exact_mut(4).zip(words) {
+        chunk.copy_from_slice(&word.to_le_bytes());
+    }
+    result
+}
+
+/// Applies the BLAKE3 compression function to `N` chaining values and blocks at once, and
+/// updates the chaining values with the result. The block counter is always zero since all
+/// messages fit into the first chunk.
+#[inline(always)]
+fn compress<const N: usize>(
+    cv: &mut [[u32; N]; 8],
+    block: &[[u32; N]; 16],
+    block_len: u32,
+    flags: u32,
+) {
+    let mut state = [
+        cv[0],
+        cv[1],
+        cv[2],
+        cv[3],
+        cv[4],
+        cv[5],
+        cv[6],
+        cv[7],
+        [IV[0]; N],
+        [IV[1]; N],
+        [IV[2]; N],
+        [IV[3]; N],
+        [0; N],
+        [0; N],
+        [block_len; N],
+        [flags; N],
+    ];
+
+    let mut m = *block;
+    for round in 0..7 {
+        // mix the columns
+        g(&mut state, 0, 4, 8, 12, &m[0], &m[1]);
+        g(&mut state, 1, 5, 9, 13, &m[2], &m[3]);
+        g(&mut state, 2, 6, 10, 14, &m[4], &m[5]);
+        g(&mut state, 3, 7, 11, 15, &m[6], &m[7]);
+
+        // mix the diagonals
+        g(&mut state, 0, 5, 10, 15, &m[8], &m[9]);
+        g(&mut state, 1, 6, 11, 12, &m[10], &m[11]);
+        g(&mut state, 2, 7, 8, 13, &m[12], &m[13]);
+        g(&mut state, 3, 4, 9, 14, &m[14], &m[15]);
+
+        if round < 6 {
+            m = MSG_PERMUTATION.map(|i| m[i]);
+        }
+    }
+
+    for (i, word) in cv.iter_mut().enumerate() {
+        for lane in 0..N {
+            word[lane] = state[i][lane] ^ state[i + 8][lane];
+        }
+    }
+}
+
+/// The BLAKE3 quarter-round function applied lane-wise.
+#[inline(always)]
+fn g<const N: usize>(
+    state: &mut [[u32; N]; 16],
+    a: usize,
+    b: usize,
+    c: usize,
+    d: usize,
+    mx: &[u32; N],
+    my: &[u32; N],
+) {
+    for lane in 0..N {
+        let mut va = state[a][lane];
+        let mut vb = state[b][lane];
+        let mut vc = state[c][lane];
+        let mut vd = state[d][lane];
+
+        va = va.wrapping_add(vb).wrapping_add(mx[lane]);
+        vd = (vd ^ va).rotate_right(16);
+        vc = vc.wrapping_add(vd);
+        vb = (vb ^ vc).rotate_right(12);
+        va = va.wrapping_add(vb).wrapping_add(my[lane]);
+        vd = (vd ^ va).rotate_right(8);
+        vc = vc.wrapping_add(vd);
+        vb = (vb ^ vc).rotate_right(7);
+
+        state[a][lane] = va;
+        state[b][lane] = vb;
+        state[c][lane] = vc;
+        state[d][lane] = vd;
+    }
+}
@@ -10,6 +10,8 @@ use utils::ByteWriter;
 
 use super::{ByteDigest, ElementHasher, Hasher};
 
+mod batch;
+
 #[cfg(test)]
 mod tests;
 
@@ -44,6 +46,11 @@ impl<B: StarkField> Hasher for Blake3_256<B> {
         data[32..].copy_from_slice(&value.to_le_bytes());
         ByteDigest(*blake3::hash(&data).as_bytes())
     }
+
+    fn merge_batch<const N: usize>(values: &[[Self::Digest; 2]; N]) -> [Self::Digest; N] {
+        batch::hash_many(values.each_ref().map(|pair| ByteDigest::digests_as_bytes(pair)))
+            .map(ByteDigest)
+    }
 }
 
 impl<B: StarkField> ElementHasher for Blake3_256<B> {
@@ -63,6 +70,16 @@ impl<B: StarkField> ElementHasher for Blake3_256<B> {
             ByteDigest(hasher.finalize())
         }
     }
+
+    fn hash_elements_batch<E, const N: usize>(elements: [&[E]; N]) -> [Self::Digest; N]
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        match hash_elements_many(elements) {
+            Some(result) => result.map(ByteDigest),
+            None => elements.map(Self::hash_elements),
+        }
+    }
 }
 
 // BLAKE3 192-BIT OUTPUT
@@ -101,6 +118,11 @@ impl<B: StarkField> Hasher for Blake3_192<B> {
         let result = blake3::hash(&data);
         ByteDigest(result.as_bytes()[..24].try_into().unwrap())
     }
+
+    fn merge_batch<const N: usize>(values: &[[Self::Digest; 2]; N]) -> [Self::Digest; N] {
+        batch::hash_many(values.each_ref().map(|pair| ByteDigest::digests_as_bytes(pair)))
+            .map(|result| ByteDigest(result[..24].try_into().unwrap()))
+    }
 }
 
 impl<B: StarkField> ElementHasher for Blake3_192<B> {
@@ -122,6 +144,83 @@ impl<B: StarkField> ElementHasher for Blake3_192<B> {
             ByteDigest(result[..24].try_into().unwrap())
         }
     }
+
+    fn hash_elements_batch<E, const N: usize>(elements: [&[E]; N]) -> [Self::Digest; N]
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        match hash_elements_many(elements) {
+            Some(result) => result.map(|result| ByteDigest(result[..24].try_into().unwrap())),
+            None => elements.map(Self::hash_elements),
+        }
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Hashes the provided sequences of elements all at once if all sequences have the same length
+/// and their serialized elements fit into a single BLAKE3 chunk; otherwise, returns None.
+///
+/// The result is the same as hashing each sequence with `hash_elements()`.
+fn hash_elements_many<E: FieldElement, const N: usize>(
+    elements: [&[E]; N],
+) -> Option<[[u8; 32]; N]> {
+    let num_elements = elements.first().map_or(0, |elements| elements.len());
+    if num_elements * E::ELEMENT_BYTES > batch::MAX_MESSAGE_LEN
+        || elements.iter().any(|elements| elements.len() != num_elements)
+    {
+        return None;
+    }
+
+    if E::BaseField::IS_CANONICAL {
+        // when element's internal and canonical representations are the same, we can hash
+        // element bytes directly
+        Some(batch::hash_many(elements.map(E::elements_as_bytes)))
+    } else {
+        // when elements' internal and canonical representations differ, we need to serialize
+        // them before hashing
+        let messages = elements.map(|elements| {
+            let mut message = MessageBuffer::new();
+            message.write_many(elements);
+            message
+        });
+        Some(batch::hash_many(messages.each_ref().map(MessageBuffer::as_bytes)))
+    }
+}
+
+// BUFFERS
+// ================================================================================================
+
+/// A fixed-size buffer which serialized elements are written into before being hashed by
+/// [batch::hash_many()].
+struct MessageBuffer {
+    bytes: [u8; batch::MAX_MESSAGE_LEN],
+    len: usize,
+}
+
+impl MessageBuffer {
+    pub fn new() -> Self {
+        Self {
+            bytes: [0; batch::MAX_MESSAGE_LEN],
+            len: 0,
+        }
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes[..self.len]
+    }
+}
+
+impl ByteWriter for MessageBuffer {
+    fn write_u8(&mut self, value: u8) {
+        self.write_bytes(&[value]);
+    }
+
+    fn write_bytes(&mut self, values: &[u8]) {
+        self.bytes[self.len..self.len + values.len()].copy_from_slice(values);
+        self.len += values.len();
+    }
 }
 
 // BLAKE HASHER
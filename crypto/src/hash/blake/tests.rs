@@ -3,8 +3,13 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use math::{fields::f62::BaseElement, FieldElement};
-use rand_utils::rand_array;
+use alloc::vec::Vec;
+
+use math::{
+    fields::{f128, f62::BaseElement},
+    FieldElement,
+};
+use rand_utils::{rand_array, rand_vector};
 use utils::Deserializable;
 
 use super::{Blake3_256, ElementHasher, Hasher};
@@ -53,3 +58,70 @@ fn merge_vs_merge_many_192() {
 
     assert_eq!(r1, r2)
 }
+
+#[test]
+fn merge_batch_vs_merge() {
+    let pairs: [[ByteDigest<32>; 2]; 8] = core::array::from_fn(|i| {
+        [ByteDigest::new([i as u8; 32]), ByteDigest::new([!(i as u8); 32])]
+    });
+
+    let expected = pairs.map(|pair| Blake3_256::<BaseElement>::merge(&pair));
+    assert_eq!(expected, Blake3_256::<BaseElement>::merge_batch(&pairs));
+}
+
+#[test]
+fn hash_elements_batch_vs_hash_elements() {
+    let elements: [BaseElement; 20] = rand_array();
+    let inputs = [&elements[..3], &elements[3..4], &elements[4..], &elements[..0]];
+
+    let expected = inputs.map(Blake3_256::hash_elements);
+    assert_eq!(expected, Blake3_256::hash_elements_batch(inputs));
+}
+
+#[test]
+fn merge_batch_vs_merge_192() {
+    let pairs: [[ByteDigest<24>; 2]; 5] = core::array::from_fn(|i| {
+        [ByteDigest::new([i as u8; 24]), ByteDigest::new([!(i as u8); 24])]
+    });
+
+    let expected = pairs.map(|pair| Blake3_192::<BaseElement>::merge(&pair));
+    assert_eq!(expected, Blake3_192::<BaseElement>::merge_batch(&pairs));
+}
+
+#[test]
+fn hash_elements_batch_same_length() {
+    // rows of equal length are hashed all at once; lengths cover single and multiple blocks as
+    // well as the maximum length of a message which fits into a single chunk
+    let elements: Vec<BaseElement> = rand_vector(128 * 8);
+    for num_elements in [0, 1, 7, 8, 9, 64, 128] {
+        let inputs: [&[BaseElement]; 8] =
+            core::array::from_fn(|i| &elements[i * num_elements..(i + 1) * num_elements]);
+
+        let expected = inputs.map(Blake3_256::hash_elements);
+        assert_eq!(expected, Blake3_256::hash_elements_batch(inputs));
+
+        let expected = inputs.map(Blake3_192::hash_elements);
+        assert_eq!(expected, Blake3_192::hash_elements_batch(inputs));
+    }
+
+    // elements with canonical internal representation are hashed without serialization
+    let elements: Vec<f128::BaseElement> = rand_vector(64 * 4);
+    let inputs: [&[f128::BaseElement]; 4] = core::array::from_fn(|i| &elements[i * 64..][..64]);
+    let expected = inputs.map(Blake3_256::hash_elements);
+    assert_eq!(expected, Blake3_256::hash_elements_batch(inputs));
+
+    // rows which do not fit into a single chunk are hashed one by one
+    let inputs = [&elements[..65], &elements[65..130]];
+    let expected = inputs.map(Blake3_256::hash_elements);
+    assert_eq!(expected, Blake3_256::hash_elements_batch(inputs));
+}
+
+#[test]
+fn hash_many_vs_blake3() {
+    let bytes: Vec<u8> = (0..4096).map(|i| (i * 7 + 3) as u8).collect();
+    for len in [0, 1, 31, 32, 63, 64, 65, 100, 1000, 1023, 1024] {
+        let messages: [&[u8]; 4] = core::array::from_fn(|i| &bytes[i * len..(i + 1) * len]);
+        let expected = messages.map(|message| *blake3::hash(message).as_bytes());
+        assert_eq!(expected, super::batch::hash_many(messages));
+    }
+}
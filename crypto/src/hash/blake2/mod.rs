@@ -0,0 +1,225 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use core::{fmt::Debug, marker::PhantomData};
+
+use math::{FieldElement, StarkField};
+use utils::ByteWriter;
+
+use super::{ByteDigest, ElementHasher, Hasher};
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Size of a message block in bytes.
+const BLOCK_SIZE: usize = 64;
+
+/// Size of the digest in bytes.
+const DIGEST_SIZE: usize = 32;
+
+/// Initialization vector (the same as for SHA-256).
+const IV: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+/// Message word permutations for each of the 10 rounds.
+const SIGMA: [[usize; 16]; 10] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+    [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+    [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+    [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+    [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+    [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+    [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+    [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+    [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+];
+
+// BLAKE2s 256-BIT OUTPUT
+// ================================================================================================
+
+/// Implementation of the [Hasher](super::Hasher) trait for BLAKE2s hash function with 256-bit
+/// output.
+///
+/// BLAKE2s operates on 32-bit words, and thus, it is usually faster than BLAKE3 or SHA3 on
+/// targets without native 64-bit arithmetic or SIMD support (e.g., wasm32 or 32-bit embedded
+/// targets). The implementation follows [RFC 7693](https://www.rfc-editor.org/rfc/rfc7693)
+/// (unkeyed, with 32-byte digests).
+#[derive(Debug, PartialEq, Eq)]
+pub struct Blake2s256<B: StarkField>(PhantomData<B>);
+
+impl<B: StarkField> Hasher for Blake2s256<B> {
+    type Digest = ByteDigest<32>;
+
+    const COLLISION_RESISTANCE: u32 = 128;
+
+    fn hash(bytes: &[u8]) -> Self::Digest {
+        ByteDigest(blake2s(bytes))
+    }
+
+    fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
+        ByteDigest(blake2s(ByteDigest::digests_as_bytes(values)))
+    }
+
+    fn merge_many(values: &[Self::Digest]) -> Self::Digest {
+        ByteDigest(blake2s(ByteDigest::digests_as_bytes(values)))
+    }
+
+    fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
+        let mut data = [0; 40];
+        data[..32].copy_from_slice(&seed.0);
+        data[32..].copy_from_slice(&value.to_le_bytes());
+        ByteDigest(blake2s(&data))
+    }
+}
+
+impl<B: StarkField> ElementHasher for Blake2s256<B> {
+    type BaseField = B;
+
+    fn hash_elements<E: FieldElement<BaseField = Self::BaseField>>(elements: &[E]) -> Self::Digest {
+        if B::IS_CANONICAL {
+            // when element's internal and canonical representations are the same, we can hash
+            // element bytes directly
+            let bytes = E::elements_as_bytes(elements);
+            ByteDigest(blake2s(bytes))
+        } else {
+            // when elements' internal and canonical representations differ, we need to serialize
+            // them before hashing
+            let mut hasher = Blake2sHasher::new();
+            hasher.write_many(elements);
+            ByteDigest(hasher.finalize())
+        }
+    }
+}
+
+// BLAKE2s HASHER
+// ================================================================================================
+
+/// Incremental BLAKE2s hasher with 256-bit output.
+///
+/// The last block of the message must be processed with the finalization flag set; thus, a full
+/// block is kept in the buffer until more data arrives or until the hasher is finalized.
+struct Blake2sHasher {
+    state: [u32; 8],
+    buffer: [u8; BLOCK_SIZE],
+    buffer_len: usize,
+    counter: u64,
+}
+
+impl Blake2sHasher {
+    /// Returns a new hasher for unkeyed BLAKE2s with 256-bit output.
+    pub fn new() -> Self {
+        let mut state = IV;
+        // parameter block: digest length = 32, key length = 0, fanout = 1, depth = 1
+        state[0] ^= 0x0101_0000 ^ DIGEST_SIZE as u32;
+        Self {
+            state,
+            buffer: [0; BLOCK_SIZE],
+            buffer_len: 0,
+            counter: 0,
+        }
+    }
+
+    /// Absorbs the provided bytes into the hasher.
+    pub fn update(&mut self, mut data: &[u8]) {
+        while !data.is_empty() {
+            // compress the buffered block only if there is more data to absorb
+            if self.buffer_len == BLOCK_SIZE {
+                self.counter += BLOCK_SIZE as u64;
+                compress(&mut self.state, &self.buffer, self.counter, false);
+                self.buffer_len = 0;
+            }
+
+            let num_bytes = core::cmp::min(BLOCK_SIZE - self.buffer_len, data.len());
+            self.buffer[self.buffer_len..self.buffer_len + num_bytes]
+                .copy_from_slice(&data[..num_bytes]);
+            self.buffer_len += num_bytes;
+            data = &data[num_bytes..];
+        }
+    }
+
+    /// Returns the digest of all absorbed bytes.
+    pub fn finalize(mut self) -> [u8; DIGEST_SIZE] {
+        self.counter += self.buffer_len as u64;
+        self.buffer[self.buffer_len..].fill(0);
+        compress(&mut self.state, &self.buffer, self.counter, true);
+
+        let mut result = [0; DIGEST_SIZE];
+        for (chunk, word) in result.chunks_mut(4).zip(self.state.iter()) {
+            chunk.copy_from_slice(&word.to_le_bytes());
+        }
+        result
+    }
+}
+
+impl ByteWriter for Blake2sHasher {
+    fn write_u8(&mut self, value: u8) {
+        self.update(&[value]);
+    }
+
+    fn write_bytes(&mut self, values: &[u8]) {
+        self.update(values);
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns BLAKE2s digest of the provided bytes.
+fn blake2s(bytes: &[u8]) -> [u8; DIGEST_SIZE] {
+    let mut hasher = Blake2sHasher::new();
+    hasher.update(bytes);
+    hasher.finalize()
+}
+
+/// BLAKE2s compression function F as defined in section 3.2 of RFC 7693.
+fn compress(state: &mut [u32; 8], block: &[u8; BLOCK_SIZE], counter: u64, is_last: bool) {
+    let mut m = [0u32; 16];
+    for (word, chunk) in m.iter_mut().zip(block.chunks_exact(4)) {
+        *word = u32::from_le_bytes(chunk.try_into().unwrap());
+    }
+
+    let mut v = [0u32; 16];
+    v[..8].copy_from_slice(state);
+    v[8..].copy_from_slice(&IV);
+    v[12] ^= counter as u32;
+    v[13] ^= (counter >> 32) as u32;
+    if is_last {
+        v[14] = !v[14];
+    }
+
+    for s in SIGMA.iter() {
+        mix(&mut v, 0, 4, 8, 12, m[s[0]], m[s[1]]);
+        mix(&mut v, 1, 5, 9, 13, m[s[2]], m[s[3]]);
+        mix(&mut v, 2, 6, 10, 14, m[s[4]], m[s[5]]);
+        mix(&mut v, 3, 7, 11, 15, m[s[6]], m[s[7]]);
+        mix(&mut v, 0, 5, 10, 15, m[s[8]], m[s[9]]);
+        mix(&mut v, 1, 6, 11, 12, m[s[10]], m[s[11]]);
+        mix(&mut v, 2, 7, 8, 13, m[s[12]], m[s[13]]);
+        mix(&mut v, 3, 4, 9, 14, m[s[14]], m[s[15]]);
+    }
+
+    for i in 0..8 {
+        state[i] ^= v[i] ^ v[i + 8];
+    }
+}
+
+/// BLAKE2s mixing function G as defined in section 3.1 of RFC 7693.
+#[inline(always)]
+#[allow(clippy::many_single_char_names)]
+fn mix(v: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize, x: u32, y: u32) {
+    v[a] = v[a].wrapping_add(v[b]).wrapping_add(x);
+    v[d] = (v[d] ^ v[a]).rotate_right(16);
+    v[c] = v[c].wrapping_add(v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(12);
+    v[a] = v[a].wrapping_add(v[b]).wrapping_add(y);
+    v[d] = (v[d] ^ v[a]).rotate_right(8);
+    v[c] = v[c].wrapping_add(v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(7);
+}
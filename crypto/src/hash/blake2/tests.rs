@@ -0,0 +1,85 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::vec::Vec;
+
+use math::{fields::f64::BaseElement, FieldElement};
+use rand_utils::rand_array;
+use utils::{Deserializable, Serializable};
+
+use super::{Blake2s256, Blake2sHasher, ElementHasher, Hasher};
+use crate::hash::ByteDigest;
+
+type Blake2s = Blake2s256<BaseElement>;
+
+#[test]
+fn hash_test_vectors() {
+    let expected = "69217a3079908094e11121d042354a7c1f55b6482ca1a51e1b250dfd1ed0eef9";
+    assert_eq!(decode_hex(expected), Blake2s::hash(b"").0);
+
+    // test vector from Appendix B of RFC 7693
+    let expected = "508c5e8c327c14e2e1a72ba34eeb452f37458b209ed63a294d999b4c86675982";
+    assert_eq!(decode_hex(expected), Blake2s::hash(b"abc").0);
+
+    // a message of exactly one block
+    let message = (0..64_u8).collect::<Vec<_>>();
+    let expected = "56f34e8b96557e90c1f24b52d0c89d51086acf1b00f634cf1dde9233b8eaaa3e";
+    assert_eq!(decode_hex(expected), Blake2s::hash(&message).0);
+
+    // a message spanning multiple blocks
+    let message = (0..1000).map(|i| (i % 251) as u8).collect::<Vec<_>>();
+    let expected = "1c067a5e746fb0f6734efac9a8cdb0e11061f0077f255184365c690115392501";
+    assert_eq!(decode_hex(expected), Blake2s::hash(&message).0);
+}
+
+#[test]
+fn hash_incremental() {
+    let message = (0..1000).map(|i| (i % 251) as u8).collect::<Vec<_>>();
+
+    // absorbing a message in chunks of different sizes should produce the same digest
+    for chunk_size in [1, 7, 32, 64, 65, 999] {
+        let mut hasher = Blake2sHasher::new();
+        for chunk in message.chunks(chunk_size) {
+            hasher.update(chunk);
+        }
+        assert_eq!(Blake2s::hash(&message).0, hasher.finalize());
+    }
+}
+
+#[test]
+fn merge_test_vectors() {
+    let digest_0 = ByteDigest::read_from_bytes(&[1_u8; 32]).unwrap();
+    let digest_1 = ByteDigest::read_from_bytes(&[2_u8; 32]).unwrap();
+
+    let expected = "280569932378c99f448df37e893f062fab951bea53515634b7875ae51e1954e7";
+    assert_eq!(decode_hex(expected), Blake2s::merge(&[digest_0, digest_1]).0);
+    assert_eq!(
+        Blake2s::merge(&[digest_0, digest_1]),
+        Blake2s::merge_many(&[digest_0, digest_1])
+    );
+
+    let expected = "89b8427ba34e5675e013c2066b37bc15e6e05cee20064f64efadf0327613c0d5";
+    assert_eq!(decode_hex(expected), Blake2s::merge_with_int(digest_0, 42).0);
+}
+
+#[test]
+fn hash_elements_vs_hash() {
+    // elements of the 64-bit field are not stored in canonical form, and thus, they must be
+    // serialized before being hashed
+    let elements: [BaseElement; 4] = rand_array();
+    assert_eq!(Blake2s::hash(&elements.to_bytes()), Blake2s::hash_elements(&elements));
+
+    // adding a zero element at the end of a list of elements should result in a different hash
+    let padded = [elements[0], elements[1], elements[2], elements[3], BaseElement::ZERO];
+    assert_ne!(Blake2s::hash_elements(&elements), Blake2s::hash_elements(&padded));
+}
+
+fn decode_hex(value: &str) -> [u8; 32] {
+    let bytes = (0..value.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&value[i..i + 2], 16).unwrap())
+        .collect::<Vec<_>>();
+    bytes.try_into().unwrap()
+}
@@ -0,0 +1,491 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use core::ops::Range;
+
+use math::{fields::f64::BaseElement, FieldElement, StarkField};
+
+use super::{AlgebraicSponge, Digest, ElementHasher, Hasher};
+
+mod digest;
+pub use digest::ElementDigest;
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Sponge state is set to 12 field elements or 96 bytes; 8 elements are reserved for rate and
+/// the remaining 4 elements are reserved for capacity.
+const STATE_WIDTH: usize = 12;
+
+/// The rate portion of the state is located in elements 4 through 11.
+const RATE_RANGE: Range<usize> = 4..12;
+const RATE_WIDTH: usize = RATE_RANGE.end - RATE_RANGE.start;
+
+const INPUT1_RANGE: Range<usize> = 4..8;
+const INPUT2_RANGE: Range<usize> = 8..12;
+
+/// The capacity portion of the state is located in elements 0, 1, 2, and 3.
+const CAPACITY_RANGE: Range<usize> = 0..4;
+
+/// The output of the hash function is a digest which consists of 4 field elements or 32 bytes.
+///
+/// The digest is returned from state elements 4, 5, 6, and 7 (the first four elements of the
+/// rate portion).
+const DIGEST_RANGE: Range<usize> = 4..8;
+const DIGEST_SIZE: usize = DIGEST_RANGE.end - DIGEST_RANGE.start;
+
+/// The number of rounds is set to 9 to target 128-bit security level.
+const NUM_ROUNDS: usize = 9;
+
+/// S-Box power; the smallest integer d such that gcd(d, p - 1) = 1.
+#[cfg(test)]
+const ALPHA: u64 = 7;
+
+/// Inverse of the S-Box power; this is the integer 1/d such that x^(d * 1/d) = x.
+const INV_ALPHA: u64 = 10540996611094048183;
+
+// HASHER IMPLEMENTATION
+// ================================================================================================
+
+/// Implementation of [Hasher] trait for Griffin hash function with 256-bit output.
+///
+/// The permutation is implemented according to the Griffin
+/// [specifications](https://eprint.iacr.org/2022/403.pdf) instantiated with the following
+/// parameters:
+/// * Field: 64-bit prime field with modulus 2^64 - 2^32 + 1.
+/// * State width: 12 field elements.
+/// * Capacity size: 4 field elements.
+/// * Number of rounds: 9.
+/// * S-Box degree: 7.
+///
+/// The non-linear layer of Griffin computes x^(1/7) for the first state element, x^7 for the
+/// second state element, and multiplies each of the remaining elements by a quadratic function
+/// of the preceding elements. Thus, a round can be described by a small number of low-degree
+/// constraints, which makes Griffin well suited for recursive proof verification. The linear
+/// layer uses the matrix circ(2 * M4, M4, M4), where M4 is the 4x4 MDS matrix from the
+/// specifications.
+///
+/// Round constants and the constants alpha and beta of the non-linear layer are generated by
+/// reading 8-byte little-endian chunks from the output of SHAKE128 seeded with the string
+/// `Griffin64_256` and discarding chunks which do not encode valid field elements. The first two
+/// elements are used as alpha and beta (which are chosen such that alpha^2 - 4 * beta is a
+/// quadratic non-residue), and the remaining elements are used as round constants.
+///
+/// The above parameters target 128-bit security level. The digest consists of four field elements
+/// and it can be serialized into 32 bytes (256 bits).
+///
+/// ## Sponge construction
+/// The sponge construction follows the one used by [Rp64_256](crate::hashers::Rp64_256): the
+/// first 4 elements of the state are used for capacity and the remaining 8 elements for rate,
+/// the first capacity element is initialized to the number of elements to be hashed, and the
+/// digest is read from the first four elements of the rate portion of the state. Thus, functions
+/// [hash_elements()](Griffin64_256::hash_elements), [merge()](Griffin64_256::merge), and
+/// [merge_with_int()](Griffin64_256::merge_with_int) are internally consistent, while the
+/// [hash()](Griffin64_256::hash) function uses a different (byte-oriented) encoding of its
+/// inputs.
+pub struct Griffin64_256();
+
+impl Hasher for Griffin64_256 {
+    type Digest = ElementDigest;
+
+    const COLLISION_RESISTANCE: u32 = 128;
+
+    fn hash(bytes: &[u8]) -> Self::Digest {
+        // compute the number of elements required to represent the string; we will be processing
+        // the string in 7-byte chunks, thus the number of elements will be equal to the number
+        // of such chunks (including a potential partial chunk at the end).
+        let num_elements = if bytes.len() % 7 == 0 {
+            bytes.len() / 7
+        } else {
+            bytes.len() / 7 + 1
+        };
+
+        // initialize state to all zeros, except for the first element of the capacity part, which
+        // is set to the number of elements to be hashed. this is done so that adding zero elements
+        // at the end of the list always results in a different hash.
+        let mut state = [BaseElement::ZERO; STATE_WIDTH];
+        state[CAPACITY_RANGE.start] = BaseElement::new(num_elements as u64);
+
+        // break the string into 7-byte chunks, convert each chunk into a field element, and
+        // absorb the element into the rate portion of the state. we use 7-byte chunks because
+        // every 7-byte chunk is guaranteed to map to some field element.
+        let mut i = 0;
+        let mut buf = [0_u8; 8];
+        for chunk in bytes.chunks(7) {
+            if i < num_elements - 1 {
+                buf[..7].copy_from_slice(chunk);
+            } else {
+                // if we are dealing with the last chunk, it may be smaller than 7 bytes long, so
+                // we need to handle it slightly differently. we also append a byte with value 1
+                // to the end of the string; this pads the string in such a way that adding
+                // trailing zeros results in different hash
+                let chunk_len = chunk.len();
+                buf = [0_u8; 8];
+                buf[..chunk_len].copy_from_slice(chunk);
+                buf[chunk_len] = 1;
+            }
+
+            // convert the bytes into a field element and absorb it into the rate portion of the
+            // state; if the rate is filled up, apply the Griffin permutation and start absorbing
+            // again from zero index.
+            state[RATE_RANGE.start + i] += BaseElement::new(u64::from_le_bytes(buf));
+            i += 1;
+            if i % RATE_WIDTH == 0 {
+                Self::apply_permutation(&mut state);
+                i = 0;
+            }
+        }
+
+        // if we absorbed some elements but didn't apply a permutation to them (would happen when
+        // the number of elements is not a multiple of RATE_WIDTH), apply the Griffin permutation.
+        // we don't need to apply any extra padding because we injected total number of elements
+        // in the input list into the capacity portion of the state during initialization.
+        if i > 0 {
+            Self::apply_permutation(&mut state);
+        }
+
+        // return the first 4 elements of the state as hash result
+        ElementDigest::new(state[DIGEST_RANGE].try_into().unwrap())
+    }
+
+    fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
+        // initialize the state by copying the digest elements into the rate portion of the state
+        // (8 total elements), and set the first capacity element to 8 (the number of elements to
+        // be hashed).
+        let mut state = [BaseElement::ZERO; STATE_WIDTH];
+        state[RATE_RANGE].copy_from_slice(Self::Digest::digests_as_elements(values));
+        state[CAPACITY_RANGE.start] = BaseElement::new(RATE_WIDTH as u64);
+
+        // apply the Griffin permutation and return the first four elements of the state
+        Self::apply_permutation(&mut state);
+        ElementDigest::new(state[DIGEST_RANGE].try_into().unwrap())
+    }
+
+    fn merge_many(values: &[Self::Digest]) -> Self::Digest {
+        Self::hash_elements(ElementDigest::digests_as_elements(values))
+    }
+
+    fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
+        // initialize the state as follows:
+        // - seed is copied into the first 4 elements of the rate portion of the state.
+        // - if the value fits into a single field element, copy it into the fifth rate element
+        //   and set the first capacity element to 5 (the number of elements to be hashed).
+        // - if the value doesn't fit into a single field element, split it into two field
+        //   elements, copy them into rate elements 5 and 6, and set the first capacity element
+        //   to 6.
+        let mut state = [BaseElement::ZERO; STATE_WIDTH];
+        state[INPUT1_RANGE].copy_from_slice(seed.as_elements());
+        state[INPUT2_RANGE.start] = BaseElement::new(value);
+        if value < BaseElement::MODULUS {
+            state[CAPACITY_RANGE.start] = BaseElement::new(DIGEST_SIZE as u64 + 1);
+        } else {
+            state[INPUT2_RANGE.start + 1] = BaseElement::new(value / BaseElement::MODULUS);
+            state[CAPACITY_RANGE.start] = BaseElement::new(DIGEST_SIZE as u64 + 2);
+        }
+
+        // apply the Griffin permutation and return the first four elements of the state
+        Self::apply_permutation(&mut state);
+        ElementDigest::new(state[DIGEST_RANGE].try_into().unwrap())
+    }
+}
+
+impl ElementHasher for Griffin64_256 {
+    type BaseField = BaseElement;
+
+    fn hash_elements<E: FieldElement<BaseField = Self::BaseField>>(elements: &[E]) -> Self::Digest {
+        // convert the elements into a list of base field elements
+        let elements = E::slice_as_base_elements(elements);
+
+        // initialize state to all zeros, except for the last element of the capacity part, which
+        // is set to the number of elements to be hashed. this is done so that adding zero elements
+        // at the end of the list always results in a different hash.
+        let mut state = [BaseElement::ZERO; STATE_WIDTH];
+        state[CAPACITY_RANGE.start] = BaseElement::new(elements.len() as u64);
+
+        // absorb elements into the state one by one until the rate portion of the state is filled
+        // up; then apply the Griffin permutation and start absorbing again; repeat until all
+        // elements have been absorbed
+        let mut i = 0;
+        for &element in elements.iter() {
+            state[RATE_RANGE.start + i] += element;
+            i += 1;
+            if i % RATE_WIDTH == 0 {
+                Self::apply_permutation(&mut state);
+                i = 0;
+            }
+        }
+
+        // if we absorbed some elements but didn't apply a permutation to them (would happen when
+        // the number of elements is not a multiple of RATE_WIDTH), apply the Griffin permutation.
+        // we don't need to apply any extra padding because we injected total number of elements
+        // in the input list into the capacity portion of the state during initialization.
+        if i > 0 {
+            Self::apply_permutation(&mut state);
+        }
+
+        // return the first 4 elements of the state as hash result
+        ElementDigest::new(state[DIGEST_RANGE].try_into().unwrap())
+    }
+}
+
+impl AlgebraicSponge for Griffin64_256 {
+    const STATE_WIDTH: usize = STATE_WIDTH;
+    const RATE_RANGE: Range<usize> = RATE_RANGE;
+    const CAPACITY_RANGE: Range<usize> = CAPACITY_RANGE;
+
+    fn permute(state: &mut [BaseElement]) {
+        Self::apply_permutation(state.try_into().expect("invalid state width"));
+    }
+
+    fn digest_elements(digest: &Self::Digest) -> &[BaseElement] {
+        digest.as_elements()
+    }
+}
+
+// HASH FUNCTION IMPLEMENTATION
+// ================================================================================================
+
+impl Griffin64_256 {
+    // CONSTANTS
+    // --------------------------------------------------------------------------------------------
+
+    /// The number of rounds is set to 9 to target 128-bit security level.
+    pub const NUM_ROUNDS: usize = NUM_ROUNDS;
+
+    /// Sponge state is set to 12 field elements or 96 bytes; 8 elements are reserved for rate and
+    /// the remaining 4 elements are reserved for capacity.
+    pub const STATE_WIDTH: usize = STATE_WIDTH;
+
+    /// The rate portion of the state is located in elements 4 through 11 (inclusive).
+    pub const RATE_RANGE: Range<usize> = RATE_RANGE;
+
+    /// The capacity portion of the state is located in elements 0, 1, 2, and 3.
+    pub const CAPACITY_RANGE: Range<usize> = CAPACITY_RANGE;
+
+    /// The output of the hash function can be read from state elements 4, 5, 6, and 7.
+    pub const DIGEST_RANGE: Range<usize> = DIGEST_RANGE;
+
+    /// Round constants added to the hasher state at the end of each round except for the last
+    /// one.
+    pub const ARK: [[BaseElement; STATE_WIDTH]; NUM_ROUNDS - 1] = ARK;
+
+    // GRIFFIN PERMUTATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Applies Griffin permutation to the provided state.
+    pub fn apply_permutation(state: &mut [BaseElement; STATE_WIDTH]) {
+        apply_linear_layer(state);
+        for round in 0..NUM_ROUNDS {
+            Self::apply_round(state, round);
+        }
+    }
+
+    /// Griffin round function; the round consists of the non-linear layer followed by the linear
+    /// layer. Round constants are added at the end of all rounds except for the last one.
+    #[inline(always)]
+    pub fn apply_round(state: &mut [BaseElement; STATE_WIDTH], round: usize) {
+        apply_nonlinear_layer(state);
+        apply_linear_layer(state);
+        if round < NUM_ROUNDS - 1 {
+            state.iter_mut().zip(ARK[round]).for_each(|(s, k)| *s += k);
+        }
+    }
+}
+
+// NON-LINEAR LAYER
+// ================================================================================================
+
+/// Applies the Griffin non-linear layer to the provided state.
+///
+/// The first two elements are mapped to x^(1/7) and x^7 respectively. Each of the remaining
+/// elements x_i is multiplied by L_i^2 + alpha_i * L_i + beta_i, where L_i is a linear
+/// combination of the first two outputs and the preceding input element, alpha_i = (i - 1) *
+/// alpha, and beta_i = (i - 1)^2 * beta.
+#[inline(always)]
+fn apply_nonlinear_layer(state: &mut [BaseElement; STATE_WIDTH]) {
+    let y0 = state[0].exp(INV_ALPHA);
+    let y1 = state[1].exp7();
+
+    // compute the outputs starting from the last element so that each step can still read the
+    // preceding input element
+    for i in (2..STATE_WIDTH).rev() {
+        let gamma = BaseElement::new(i as u64 - 1);
+        let l = if i == 2 {
+            y0 + y1
+        } else {
+            gamma * y0 + y1 + state[i - 1]
+        };
+        let alpha = gamma * GRIFFIN_ALPHA;
+        let beta = gamma.square() * GRIFFIN_BETA;
+        state[i] *= l.square() + alpha * l + beta;
+    }
+
+    state[0] = y0;
+    state[1] = y1;
+}
+
+// LINEAR LAYER
+// ================================================================================================
+
+/// Multiplies the state by the matrix circ(2 * M4, M4, M4).
+///
+/// This is done by first multiplying each 4-element chunk of the state by M4, and then adding
+/// to each element the sum of the elements at the same position across all chunks.
+#[inline(always)]
+fn apply_linear_layer(state: &mut [BaseElement; STATE_WIDTH]) {
+    for chunk in state.chunks_exact_mut(4) {
+        apply_m4(chunk.try_into().unwrap());
+    }
+
+    let mut sums = [BaseElement::ZERO; 4];
+    for chunk in state.chunks_exact(4) {
+        sums.iter_mut().zip(chunk).for_each(|(s, &c)| *s += c);
+    }
+    for chunk in state.chunks_exact_mut(4) {
+        chunk.iter_mut().zip(sums).for_each(|(c, s)| *c += s);
+    }
+}
+
+/// Multiplies a 4-element vector by the matrix M4 from the specifications using 8 additions and
+/// 4 doublings.
+#[inline(always)]
+fn apply_m4(x: &mut [BaseElement; 4]) {
+    let t0 = x[0] + x[1];
+    let t1 = x[2] + x[3];
+    let t2 = x[1].double() + t1;
+    let t3 = x[3].double() + t0;
+    let t4 = t1.double().double() + t3;
+    let t5 = t0.double().double() + t2;
+    let t6 = t3 + t5;
+    let t7 = t2 + t4;
+    *x = [t6, t5, t7, t4];
+}
+
+// CONSTANTS
+// ================================================================================================
+
+/// Constants alpha and beta used in the non-linear layer; alpha^2 - 4 * beta is a quadratic
+/// non-residue in the field.
+const GRIFFIN_ALPHA: BaseElement = BaseElement::new(17023818766566160255);
+const GRIFFIN_BETA: BaseElement = BaseElement::new(15189836158652586940);
+
+/// Griffin round constants.
+const ARK: [[BaseElement; STATE_WIDTH]; NUM_ROUNDS - 1] = [
+    [
+        BaseElement::new(4081023359738227526),
+        BaseElement::new(8725243712099188007),
+        BaseElement::new(4304171436312919690),
+        BaseElement::new(136257401612466130),
+        BaseElement::new(17680692689930241568),
+        BaseElement::new(379877117218006251),
+        BaseElement::new(2480399945327565286),
+        BaseElement::new(15989669280768077763),
+        BaseElement::new(211711291841076619),
+        BaseElement::new(8481482292894739465),
+        BaseElement::new(6136066556442025610),
+        BaseElement::new(13243542164580250015),
+    ],
+    [
+        BaseElement::new(3576063351275964256),
+        BaseElement::new(1258571708033730292),
+        BaseElement::new(13891717323245580901),
+        BaseElement::new(10770796826269029168),
+        BaseElement::new(1884504587885354353),
+        BaseElement::new(8748911957109890275),
+        BaseElement::new(10837608707407610479),
+        BaseElement::new(15138948689563785009),
+        BaseElement::new(2059095170217001055),
+        BaseElement::new(9306066625115492119),
+        BaseElement::new(11942918183918767732),
+        BaseElement::new(4477558061738483896),
+    ],
+    [
+        BaseElement::new(17162145033891971914),
+        BaseElement::new(14554415181282871605),
+        BaseElement::new(9615183429193375976),
+        BaseElement::new(2262961298069820442),
+        BaseElement::new(15133396238113435608),
+        BaseElement::new(7210817482967533180),
+        BaseElement::new(15004380111877537805),
+        BaseElement::new(7101677036418877761),
+        BaseElement::new(4956557616351941830),
+        BaseElement::new(13509169824146099027),
+        BaseElement::new(7644497946719889994),
+        BaseElement::new(2191836524802023498),
+    ],
+    [
+        BaseElement::new(15747651520862111534),
+        BaseElement::new(9330135238437581480),
+        BaseElement::new(13953837391481525501),
+        BaseElement::new(16965570168107763397),
+        BaseElement::new(183158882872343325),
+        BaseElement::new(18393876003091375466),
+        BaseElement::new(600722480670192804),
+        BaseElement::new(10990348863699409313),
+        BaseElement::new(10911839490894585915),
+        BaseElement::new(8016312344491931314),
+        BaseElement::new(5833838022016370012),
+        BaseElement::new(13919362146029674048),
+    ],
+    [
+        BaseElement::new(10405609429293055010),
+        BaseElement::new(1953022849019540740),
+        BaseElement::new(3507896884629484856),
+        BaseElement::new(1148406119862138673),
+        BaseElement::new(16486822665012294414),
+        BaseElement::new(4914079830603387594),
+        BaseElement::new(12499987730994502813),
+        BaseElement::new(4649915958248858310),
+        BaseElement::new(15254422415312659073),
+        BaseElement::new(2662999918130777874),
+        BaseElement::new(18202214755196092652),
+        BaseElement::new(12553402161353058130),
+    ],
+    [
+        BaseElement::new(5550054409328455027),
+        BaseElement::new(2065849026391026101),
+        BaseElement::new(14498547954364452455),
+        BaseElement::new(18012780509918638958),
+        BaseElement::new(13773802907859297590),
+        BaseElement::new(13660121156786293016),
+        BaseElement::new(4361459180297732792),
+        BaseElement::new(5534962245768815700),
+        BaseElement::new(14410046550092659044),
+        BaseElement::new(13349691798155114893),
+        BaseElement::new(8252383388082880628),
+        BaseElement::new(7543134891595026236),
+    ],
+    [
+        BaseElement::new(8144197002220180713),
+        BaseElement::new(17884796178873447225),
+        BaseElement::new(3813420947488276049),
+        BaseElement::new(8025631743065625061),
+        BaseElement::new(15229739151037952011),
+        BaseElement::new(4914153279032932513),
+        BaseElement::new(7759023416658491826),
+        BaseElement::new(17348725571372659354),
+        BaseElement::new(8638950968165565361),
+        BaseElement::new(3646600899144746961),
+        BaseElement::new(8692787190344614989),
+        BaseElement::new(9107753907094883975),
+    ],
+    [
+        BaseElement::new(2910921417561027467),
+        BaseElement::new(7316688574236813535),
+        BaseElement::new(67507196026362496),
+        BaseElement::new(8903272135365734808),
+        BaseElement::new(9837361999073728160),
+        BaseElement::new(5598336521297766177),
+        BaseElement::new(2282420585584112151),
+        BaseElement::new(2624609372485672696),
+        BaseElement::new(6024085866747242631),
+        BaseElement::new(10528589926689705313),
+        BaseElement::new(1975441594915981201),
+        BaseElement::new(16719760071322953304),
+    ],
+];
@@ -0,0 +1,207 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use proptest::prelude::*;
+use rand_utils::{rand_array, rand_value};
+
+use super::{
+    apply_linear_layer, apply_nonlinear_layer, BaseElement, ElementDigest, ElementHasher,
+    FieldElement, Griffin64_256, Hasher, StarkField, ALPHA, GRIFFIN_ALPHA, GRIFFIN_BETA, INV_ALPHA,
+    STATE_WIDTH,
+};
+
+#[test]
+fn test_sbox() {
+    let state: [BaseElement; STATE_WIDTH] = rand_array();
+
+    let mut expected = state;
+    expected.iter_mut().for_each(|v| *v = v.exp(ALPHA));
+
+    let mut actual = state;
+    actual.iter_mut().for_each(|v| *v = v.exp7());
+
+    assert_eq!(expected, actual);
+
+    // x^(1/7) is the inverse of x^7
+    actual.iter_mut().for_each(|v| *v = v.exp(INV_ALPHA));
+    assert_eq!(state, actual);
+}
+
+#[test]
+fn nonlinear_layer() {
+    let state: [BaseElement; STATE_WIDTH] = rand_array();
+
+    let mut actual = state;
+    apply_nonlinear_layer(&mut actual);
+
+    let y0 = state[0].exp(INV_ALPHA);
+    let y1 = state[1].exp(ALPHA);
+    assert_eq!(y0, actual[0]);
+    assert_eq!(y1, actual[1]);
+    for i in 2..STATE_WIDTH {
+        let gamma = BaseElement::new(i as u64 - 1);
+        let z = if i == 2 { BaseElement::ZERO } else { state[i - 1] };
+        let l = gamma * y0 + y1 + z;
+        let expected =
+            state[i] * (l * l + gamma * GRIFFIN_ALPHA * l + gamma * gamma * GRIFFIN_BETA);
+        assert_eq!(expected, actual[i]);
+    }
+}
+
+#[test]
+fn quadratic_non_residue() {
+    // alpha^2 - 4 * beta must be a quadratic non-residue
+    let d = GRIFFIN_ALPHA.square() - BaseElement::new(4) * GRIFFIN_BETA;
+    assert_eq!(-BaseElement::ONE, d.exp((BaseElement::MODULUS - 1) / 2));
+}
+
+#[test]
+fn apply_permutation() {
+    let mut state: [BaseElement; STATE_WIDTH] =
+        core::array::from_fn(|i| BaseElement::new(i as u64));
+
+    Griffin64_256::apply_permutation(&mut state);
+
+    // expected values are obtained by executing a reference Python implementation of the
+    // permutation instantiated with the same parameters
+    let expected = [
+        BaseElement::new(16389636648020526424),
+        BaseElement::new(12803057665398286209),
+        BaseElement::new(14288549118697547650),
+        BaseElement::new(14241728551953905137),
+        BaseElement::new(7001638993360075718),
+        BaseElement::new(15081445557208088913),
+        BaseElement::new(2444761944538324426),
+        BaseElement::new(16878772606940306455),
+        BaseElement::new(8371443984853768458),
+        BaseElement::new(11505238577453492193),
+        BaseElement::new(15792505948764489848),
+        BaseElement::new(17879956400406581295),
+    ];
+
+    assert_eq!(expected, state);
+}
+
+#[test]
+fn apply_permutation_zeros() {
+    let mut state = [BaseElement::ZERO; STATE_WIDTH];
+
+    Griffin64_256::apply_permutation(&mut state);
+
+    let expected = [
+        BaseElement::new(12309677510477153707),
+        BaseElement::new(2272335324046699107),
+        BaseElement::new(7519625034040043015),
+        BaseElement::new(11373172307865689392),
+        BaseElement::new(13188813546589296575),
+        BaseElement::new(17093290817111396705),
+        BaseElement::new(11754573665158485847),
+        BaseElement::new(3051466675711778428),
+        BaseElement::new(3522428143237367130),
+        BaseElement::new(4163712493201574588),
+        BaseElement::new(17255084565950595644),
+        BaseElement::new(1754343842618740657),
+    ];
+
+    assert_eq!(expected, state);
+}
+
+#[test]
+fn hash_elements_vs_merge() {
+    let elements: [BaseElement; 8] = rand_array();
+
+    let digests: [ElementDigest; 2] = [
+        ElementDigest::new(elements[..4].try_into().unwrap()),
+        ElementDigest::new(elements[4..].try_into().unwrap()),
+    ];
+
+    let m_result = Griffin64_256::merge(&digests);
+    let h_result = Griffin64_256::hash_elements(&elements);
+    assert_eq!(m_result, h_result);
+}
+
+#[test]
+fn merge_vs_merge_many() {
+    let elements: [BaseElement; 8] = rand_array();
+
+    let digests: [ElementDigest; 2] = [
+        ElementDigest::new(elements[..4].try_into().unwrap()),
+        ElementDigest::new(elements[4..].try_into().unwrap()),
+    ];
+
+    let m_result = Griffin64_256::merge(&digests);
+    let h_result = Griffin64_256::merge_many(&digests);
+    assert_eq!(m_result, h_result);
+}
+
+#[test]
+fn hash_elements_vs_merge_with_int() {
+    let seed = ElementDigest::new(rand_array());
+
+    // ----- value fits into a field element ------------------------------------------------------
+    let val: BaseElement = rand_value();
+    let m_result = Griffin64_256::merge_with_int(seed, val.as_int());
+
+    let mut elements = seed.as_elements().to_vec();
+    elements.push(val);
+    let h_result = Griffin64_256::hash_elements(&elements);
+
+    assert_eq!(m_result, h_result);
+
+    // ----- value does not fit into a field element ----------------------------------------------
+    let val = BaseElement::MODULUS + 2;
+    let m_result = Griffin64_256::merge_with_int(seed, val);
+
+    let mut elements = seed.as_elements().to_vec();
+    elements.push(BaseElement::new(val));
+    elements.push(BaseElement::new(1));
+    let h_result = Griffin64_256::hash_elements(&elements);
+
+    assert_eq!(m_result, h_result);
+}
+
+#[test]
+fn hash_padding() {
+    // adding a zero bytes at the end of a byte string should result in a different hash
+    let r1 = Griffin64_256::hash(&[1_u8, 2, 3]);
+    let r2 = Griffin64_256::hash(&[1_u8, 2, 3, 0]);
+    assert_ne!(r1, r2);
+
+    // same as above but with input splitting over two elements
+    let r1 = Griffin64_256::hash(&[1_u8, 2, 3, 4, 5, 6, 7]);
+    let r2 = Griffin64_256::hash(&[1_u8, 2, 3, 4, 5, 6, 7, 0]);
+    assert_ne!(r1, r2);
+}
+
+#[test]
+fn hash_elements_padding() {
+    let e1: [BaseElement; 2] = rand_array();
+    let e2 = [e1[0], e1[1], BaseElement::ZERO];
+
+    let r1 = Griffin64_256::hash_elements(&e1);
+    let r2 = Griffin64_256::hash_elements(&e2);
+    assert_ne!(r1, r2);
+}
+
+proptest! {
+    #[test]
+    fn linear_layer_proptest(a in any::<[u64; STATE_WIDTH]>()) {
+        let mut v1 = a.map(BaseElement::new);
+        apply_linear_layer(&mut v1);
+
+        // the linear layer matrix is circ(2 * M4, M4, M4)
+        let m4 = [[5_u64, 7, 1, 3], [4, 6, 1, 1], [1, 3, 5, 7], [1, 1, 4, 6]];
+        let mut v2 = [BaseElement::ZERO; STATE_WIDTH];
+        for (i, r) in v2.iter_mut().enumerate() {
+            for (j, &s) in a.iter().enumerate() {
+                let m = BaseElement::new(m4[i % 4][j % 4]);
+                let m = if i / 4 == j / 4 { m.double() } else { m };
+                *r += m * BaseElement::new(s);
+            }
+        }
+
+        prop_assert_eq!(v1, v2);
+    }
+}
@@ -0,0 +1,101 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use core::marker::PhantomData;
+
+use math::{FieldElement, StarkField};
+use sha3::Digest;
+use utils::ByteWriter;
+
+use super::{ByteDigest, ElementHasher, Hasher};
+
+#[cfg(test)]
+mod tests;
+
+// KECCAK WITH 256-BIT OUTPUT
+// ================================================================================================
+
+/// Implementation of the [Hasher](super::Hasher) trait for Keccak hash function with 256-bit
+/// output.
+///
+/// This is the original Keccak submission (i.e., with Keccak padding rather than the padding
+/// defined in FIPS 202), and thus, it is the same function as provided by `KECCAK256` opcode of
+/// the EVM. Digests are computed over the following byte strings:
+/// * `merge()` and `merge_many()` hash a concatenation of the provided digests (i.e., the same as
+///   `keccak256(abi.encodePacked(a, b))` in Solidity).
+/// * `merge_with_int()` hashes the seed followed by 8 bytes of the value in little-endian order.
+/// * `hash_elements()` hashes canonical representations of field elements, each encoded as
+///   [FieldElement::ELEMENT_BYTES] bytes in little-endian order.
+pub struct Keccak256<B: StarkField>(PhantomData<B>);
+
+impl<B: StarkField> Hasher for Keccak256<B> {
+    type Digest = ByteDigest<32>;
+
+    const COLLISION_RESISTANCE: u32 = 128;
+
+    fn hash(bytes: &[u8]) -> Self::Digest {
+        ByteDigest(sha3::Keccak256::digest(bytes).into())
+    }
+
+    fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
+        ByteDigest(sha3::Keccak256::digest(ByteDigest::digests_as_bytes(values)).into())
+    }
+
+    fn merge_many(values: &[Self::Digest]) -> Self::Digest {
+        ByteDigest(sha3::Keccak256::digest(ByteDigest::digests_as_bytes(values)).into())
+    }
+
+    fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
+        let mut data = [0; 40];
+        data[..32].copy_from_slice(&seed.0);
+        data[32..].copy_from_slice(&value.to_le_bytes());
+        ByteDigest(sha3::Keccak256::digest(data).into())
+    }
+}
+
+impl<B: StarkField> ElementHasher for Keccak256<B> {
+    type BaseField = B;
+
+    fn hash_elements<E: FieldElement<BaseField = Self::BaseField>>(elements: &[E]) -> Self::Digest {
+        if B::IS_CANONICAL {
+            // when element's internal and canonical representations are the same, we can hash
+            // element bytes directly
+            let bytes = E::elements_as_bytes(elements);
+            ByteDigest(sha3::Keccak256::digest(bytes).into())
+        } else {
+            // when elements' internal and canonical representations differ, we need to serialize
+            // them before hashing
+            let mut hasher = KeccakHasher::new();
+            hasher.write_many(elements);
+            ByteDigest(hasher.finalize())
+        }
+    }
+}
+
+// KECCAK HASHER
+// ================================================================================================
+
+/// Wrapper around Keccak hasher to implement [ByteWriter] trait for it.
+struct KeccakHasher(sha3::Keccak256);
+
+impl KeccakHasher {
+    pub fn new() -> Self {
+        Self(sha3::Keccak256::new())
+    }
+
+    pub fn finalize(self) -> [u8; 32] {
+        self.0.finalize().into()
+    }
+}
+
+impl ByteWriter for KeccakHasher {
+    fn write_u8(&mut self, value: u8) {
+        self.0.update([value]);
+    }
+
+    fn write_bytes(&mut self, values: &[u8]) {
+        self.0.update(values);
+    }
+}
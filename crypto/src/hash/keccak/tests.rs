@@ -0,0 +1,60 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::vec::Vec;
+
+use math::{fields::f64::BaseElement, FieldElement};
+use rand_utils::rand_array;
+use utils::{Deserializable, Serializable};
+
+use super::{ElementHasher, Hasher, Keccak256};
+use crate::hash::ByteDigest;
+
+type Keccak = Keccak256<BaseElement>;
+
+#[test]
+fn hash_test_vectors() {
+    // these are the same values as returned by KECCAK256 opcode of the EVM
+    let expected = "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470";
+    assert_eq!(decode_hex(expected), Keccak::hash(b"").0);
+
+    let expected = "4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45";
+    assert_eq!(decode_hex(expected), Keccak::hash(b"abc").0);
+}
+
+#[test]
+fn merge_test_vectors() {
+    let digest_0 = ByteDigest::read_from_bytes(&[1_u8; 32]).unwrap();
+    let digest_1 = ByteDigest::read_from_bytes(&[2_u8; 32]).unwrap();
+
+    // merging two digests is the same as hashing their concatenation
+    let expected = "346d8c96a2454213fcc0daff3c96ad0398148181b9fa6488f7ae2c0af5b20aa0";
+    assert_eq!(decode_hex(expected), Keccak::merge(&[digest_0, digest_1]).0);
+    assert_eq!(Keccak::merge(&[digest_0, digest_1]), Keccak::merge_many(&[digest_0, digest_1]));
+
+    // merging with an integer hashes the seed followed by little-endian bytes of the integer
+    let expected = "607a462f5a4640cf46e3dbd6e293e3b47bd78ede59b13c3ab94baa85ffe97623";
+    assert_eq!(decode_hex(expected), Keccak::merge_with_int(digest_0, 42).0);
+}
+
+#[test]
+fn hash_elements_vs_hash() {
+    // elements of the 64-bit field are not stored in canonical form, and thus, they must be
+    // serialized before being hashed
+    let elements: [BaseElement; 4] = rand_array();
+    assert_eq!(Keccak::hash(&elements.to_bytes()), Keccak::hash_elements(&elements));
+
+    // adding a zero element at the end of a list of elements should result in a different hash
+    let padded = [elements[0], elements[1], elements[2], elements[3], BaseElement::ZERO];
+    assert_ne!(Keccak::hash_elements(&elements), Keccak::hash_elements(&padded));
+}
+
+fn decode_hex(value: &str) -> [u8; 32] {
+    let bytes = (0..value.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&value[i..i + 2], 16).unwrap())
+        .collect::<Vec<_>>();
+    bytes.try_into().unwrap()
+}
@@ -3,7 +3,7 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use core::{fmt::Debug, slice};
+use core::{fmt::Debug, ops::Range, slice};
 
 use math::{FieldElement, StarkField};
 use utils::{ByteReader, Deserializable, DeserializationError, Serializable};
@@ -11,13 +11,38 @@ use utils::{ByteReader, Deserializable, DeserializationError, Serializable};
 mod blake;
 pub use blake::{Blake3_192, Blake3_256};
 
+mod blake2;
+pub use blake2::Blake2s256;
+
 mod sha;
 pub use sha::Sha3_256;
 
+mod keccak;
+pub use keccak::Keccak256;
+
+mod griffin;
+pub use griffin::Griffin64_256;
+
 mod mds;
 
+mod poseidon;
+pub use poseidon::Poseidon64_256;
+
+mod poseidon2;
+pub use poseidon2::Poseidon2_64_256;
+
 mod rescue;
-pub use rescue::{Rp62_248, Rp64_256, RpJive64_256};
+pub use rescue::{RescueParams, RescuePermutation, Rp62_248, Rp64_256, RpJive64_256};
+
+mod truncated;
+pub use truncated::Truncated;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of independent inputs hashed at once when hashing leaves of Merkle trees and rows of
+/// matrices via [Hasher::merge_batch()] and [ElementHasher::hash_elements_batch()].
+pub const HASH_BATCH_SIZE: usize = 8;
 
 // HASHER TRAITS
 // ================================================================================================
@@ -47,12 +72,27 @@ pub trait Hasher {
 
     /// Returns hash(`seed` || `value`). This method is intended for use in PRNG and PoW contexts.
     fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest;
+
+    /// Returns hashes of `N` independent pairs of digests.
+    ///
+    /// The result must be the same as applying [Hasher::merge()] to each pair. By default, pairs
+    /// are merged one by one; hash functions which can process several inputs at once (e.g., using
+    /// SIMD instructions) should override this method.
+    fn merge_batch<const N: usize>(values: &[[Self::Digest; 2]; N]) -> [Self::Digest; N] {
+        core::array::from_fn(|i| Self::merge(&values[i]))
+    }
 }
 
 /// Defines a cryptographic hash function for hashing field elements.
 ///
 /// This trait defines a hash procedure for a sequence of field elements. The elements can be
 /// either in the base field specified for this hasher, or in an extension of the base field.
+///
+/// This is the hash procedure used by the STARK protocol wherever field elements are hashed
+/// (e.g., rows of the extended execution trace, FRI layer evaluations, and public coin seeds).
+/// Algebraic hash functions (e.g., Rescue Prime or Poseidon) can absorb field elements directly,
+/// and thus, avoid serializing elements into bytes; this also keeps the hashing compatible with
+/// evaluation of the hash function inside an AIR.
 pub trait ElementHasher: Hasher {
     /// Specifies a base field for elements which can be hashed with this hasher.
     type BaseField: StarkField;
@@ -61,6 +101,45 @@ pub trait ElementHasher: Hasher {
     fn hash_elements<E>(elements: &[E]) -> Self::Digest
     where
         E: FieldElement<BaseField = Self::BaseField>;
+
+    /// Returns hashes of `N` independent sequences of field elements.
+    ///
+    /// The result must be the same as applying [ElementHasher::hash_elements()] to each sequence.
+    /// By default, sequences are hashed one by one; hash functions which can process several
+    /// inputs at once (e.g., using SIMD instructions) should override this method.
+    fn hash_elements_batch<E, const N: usize>(elements: [&[E]; N]) -> [Self::Digest; N]
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        elements.map(Self::hash_elements)
+    }
+}
+
+/// Defines an algebraic hash function built from a sponge over its base field.
+///
+/// This trait exposes the permutation and the state layout of the hash function so that the
+/// permutation can be used in other sponge constructions (e.g., in
+/// [SpongeCoin](crate::SpongeCoin)). The rate and capacity ranges must partition the state, and
+/// digests must consist of base field elements.
+pub trait AlgebraicSponge: ElementHasher {
+    /// Number of field elements in the sponge state.
+    const STATE_WIDTH: usize;
+
+    /// Indexes of the state elements into which inputs are absorbed and from which outputs are
+    /// squeezed.
+    const RATE_RANGE: Range<usize>;
+
+    /// Indexes of the state elements which are never directly read or overwritten.
+    const CAPACITY_RANGE: Range<usize>;
+
+    /// Applies the permutation of the hash function to the provided state.
+    ///
+    /// # Panics
+    /// Panics if the length of `state` is not [AlgebraicSponge::STATE_WIDTH].
+    fn permute(state: &mut [Self::BaseField]);
+
+    /// Returns the field elements which make up the provided digest.
+    fn digest_elements(digest: &Self::Digest) -> &[Self::BaseField];
 }
 
 // DIGEST TRAIT
@@ -82,6 +161,7 @@ pub trait Digest:
 // BYTE DIGEST
 // ================================================================================================
 
+/// Digest of a hash function which outputs `N` bytes (e.g., BLAKE3 or SHA3).
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub struct ByteDigest<const N: usize>([u8; N]);
 
@@ -0,0 +1,909 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use core::ops::Range;
+
+use math::{fields::f64::BaseElement, FieldElement, StarkField};
+
+use super::{AlgebraicSponge, Digest, ElementHasher, Hasher};
+
+mod digest;
+pub use digest::ElementDigest;
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Sponge state is set to 12 field elements or 96 bytes; 8 elements are reserved for rate and
+/// the remaining 4 elements are reserved for capacity.
+const STATE_WIDTH: usize = 12;
+
+/// The rate portion of the state is located in elements 0 through 7.
+const RATE_RANGE: Range<usize> = 0..8;
+const RATE_WIDTH: usize = RATE_RANGE.end - RATE_RANGE.start;
+
+/// The capacity portion of the state is located in elements 8, 9, 10, and 11.
+const CAPACITY_RANGE: Range<usize> = 8..12;
+
+/// The output of the hash function is a digest which consists of 4 field elements or 32 bytes.
+///
+/// The digest is returned from state elements 0, 1, 2, and 3 (the first four elements of the
+/// rate portion).
+const DIGEST_RANGE: Range<usize> = 0..4;
+const DIGEST_SIZE: usize = DIGEST_RANGE.end - DIGEST_RANGE.start;
+
+/// The number of full rounds is set to 8 (4 at the beginning and 4 at the end of the
+/// permutation), and the number of partial rounds is set to 22; this targets 128-bit security
+/// level for a 64-bit field with S-Box degree 7 as per section 5 of
+/// <https://eprint.iacr.org/2019/458.pdf>.
+const NUM_FULL_ROUNDS: usize = 8;
+const NUM_PARTIAL_ROUNDS: usize = 22;
+const NUM_ROUNDS: usize = NUM_FULL_ROUNDS + NUM_PARTIAL_ROUNDS;
+
+/// S-Box power; the smallest integer d such that gcd(d, p - 1) = 1.
+#[cfg(test)]
+const ALPHA: u64 = 7;
+
+// HASHER IMPLEMENTATION
+// ================================================================================================
+
+/// Implementation of [Hasher] trait for Poseidon hash function with 256-bit output.
+///
+/// The permutation is implemented according to the Poseidon
+/// [specifications](https://eprint.iacr.org/2019/458.pdf) instantiated with the following
+/// parameters:
+/// * Field: 64-bit prime field with modulus 2^64 - 2^32 + 1.
+/// * State width: 12 field elements.
+/// * Capacity size: 4 field elements.
+/// * Number of full rounds: 8.
+/// * Number of partial rounds: 22.
+/// * S-Box degree: 7.
+///
+/// The permutation is the same as the Poseidon permutation over this field used in
+/// [Plonky2](https://github.com/0xPolygonZero/plonky2): round constants and the MDS matrix are
+/// taken from Plonky2, and the partial S-Box layer is applied to the first state element.
+///
+/// The above parameters target 128-bit security level. The digest consists of four field elements
+/// and it can be serialized into 32 bytes (256 bits).
+///
+/// ## Sponge construction
+/// The sponge construction also follows the one used in Plonky2: the first 8 elements of the
+/// state are used for rate and the remaining 4 elements for capacity, inputs overwrite the rate
+/// portion of the state, and the digest is read from the first four elements of the state.
+/// Specifically:
+/// * [hash_elements()](Poseidon64_256::hash_elements) pads the input elements with a single one,
+///   followed by zeros, followed by a single one, such that the length of the padded input is a
+///   multiple of 8; this is the same as `hash_pad()` in Plonky2.
+/// * [merge()](Poseidon64_256::merge) copies the two digests into the rate portion of the zero
+///   state and applies the permutation once; this is the same as `two_to_one()` in Plonky2.
+/// * [merge_many()](Poseidon64_256::merge_many) and
+///   [merge_with_int()](Poseidon64_256::merge_with_int) hash the elements of the provided digests
+///   (and the integer) via [hash_elements()](Poseidon64_256::hash_elements).
+/// * [hash()](Poseidon64_256::hash) splits the input bytes into 7-byte chunks, appends a byte
+///   with value 1 to the last chunk, and hashes the resulting elements via
+///   [hash_elements()](Poseidon64_256::hash_elements).
+pub struct Poseidon64_256();
+
+impl Hasher for Poseidon64_256 {
+    type Digest = ElementDigest;
+
+    const COLLISION_RESISTANCE: u32 = 128;
+
+    fn hash(bytes: &[u8]) -> Self::Digest {
+        // compute the number of elements required to represent the string; we will be processing
+        // the string in 7-byte chunks, thus the number of elements will be equal to the number
+        // of such chunks (including a potential partial chunk at the end).
+        let num_elements = bytes.len().div_ceil(7);
+
+        // break the string into 7-byte chunks and convert each chunk into a field element; we use
+        // 7-byte chunks because every 7-byte chunk is guaranteed to map to some field element. we
+        // also append a byte with value 1 to the end of the last chunk; this pads the string in
+        // such a way that adding trailing zeros results in different hash.
+        let elements = bytes.chunks(7).enumerate().map(|(i, chunk)| {
+            let mut buf = [0_u8; 8];
+            buf[..chunk.len()].copy_from_slice(chunk);
+            if i == num_elements - 1 {
+                buf[chunk.len()] = 1;
+            }
+            BaseElement::new(u64::from_le_bytes(buf))
+        });
+
+        Self::hash_padded(elements)
+    }
+
+    fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
+        // initialize the state by copying the digest elements into the rate portion of the state
+        // (8 total elements), and leave the capacity portion of the state set to zeros
+        let mut state = [BaseElement::ZERO; STATE_WIDTH];
+        state[RATE_RANGE].copy_from_slice(Self::Digest::digests_as_elements(values));
+
+        // apply the Poseidon permutation and return the first four elements of the state
+        Self::apply_permutation(&mut state);
+        ElementDigest::new(state[DIGEST_RANGE].try_into().unwrap())
+    }
+
+    fn merge_many(values: &[Self::Digest]) -> Self::Digest {
+        Self::hash_elements(ElementDigest::digests_as_elements(values))
+    }
+
+    fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
+        // hash the elements of the seed followed by the value; if the value doesn't fit into a
+        // single field element, it is split into two field elements
+        let mut elements = [BaseElement::ZERO; DIGEST_SIZE + 2];
+        elements[..DIGEST_SIZE].copy_from_slice(seed.as_elements());
+        elements[DIGEST_SIZE] = BaseElement::new(value);
+        if value < BaseElement::MODULUS {
+            Self::hash_elements(&elements[..DIGEST_SIZE + 1])
+        } else {
+            elements[DIGEST_SIZE + 1] = BaseElement::new(value / BaseElement::MODULUS);
+            Self::hash_elements(&elements)
+        }
+    }
+}
+
+impl ElementHasher for Poseidon64_256 {
+    type BaseField = BaseElement;
+
+    fn hash_elements<E: FieldElement<BaseField = Self::BaseField>>(elements: &[E]) -> Self::Digest {
+        // convert the elements into a list of base field elements and hash them
+        let elements = E::slice_as_base_elements(elements);
+        Self::hash_padded(elements.iter().copied())
+    }
+}
+
+impl AlgebraicSponge for Poseidon64_256 {
+    const STATE_WIDTH: usize = STATE_WIDTH;
+    const RATE_RANGE: Range<usize> = RATE_RANGE;
+    const CAPACITY_RANGE: Range<usize> = CAPACITY_RANGE;
+
+    fn permute(state: &mut [BaseElement]) {
+        Self::apply_permutation(state.try_into().expect("invalid state width"));
+    }
+
+    fn digest_elements(digest: &Self::Digest) -> &[BaseElement] {
+        digest.as_elements()
+    }
+}
+
+// HASH FUNCTION IMPLEMENTATION
+// ================================================================================================
+
+impl Poseidon64_256 {
+    // CONSTANTS
+    // --------------------------------------------------------------------------------------------
+
+    /// The number of full rounds is set to 8: 4 at the beginning and 4 at the end of the
+    /// permutation.
+    pub const NUM_FULL_ROUNDS: usize = NUM_FULL_ROUNDS;
+
+    /// The number of partial rounds is set to 22.
+    pub const NUM_PARTIAL_ROUNDS: usize = NUM_PARTIAL_ROUNDS;
+
+    /// Sponge state is set to 12 field elements or 96 bytes; 8 elements are reserved for rate and
+    /// the remaining 4 elements are reserved for capacity.
+    pub const STATE_WIDTH: usize = STATE_WIDTH;
+
+    /// The rate portion of the state is located in elements 0 through 7 (inclusive).
+    pub const RATE_RANGE: Range<usize> = RATE_RANGE;
+
+    /// The capacity portion of the state is located in elements 8, 9, 10, and 11.
+    pub const CAPACITY_RANGE: Range<usize> = CAPACITY_RANGE;
+
+    /// The output of the hash function can be read from state elements 0, 1, 2, and 3.
+    pub const DIGEST_RANGE: Range<usize> = DIGEST_RANGE;
+
+    /// MDS matrix used for computing the linear layer in a Poseidon round.
+    pub const MDS: [[BaseElement; STATE_WIDTH]; STATE_WIDTH] = MDS;
+
+    /// Round constants added to the hasher state at the beginning of each Poseidon round.
+    pub const ARK: [[BaseElement; STATE_WIDTH]; NUM_ROUNDS] = ARK;
+
+    // POSEIDON PERMUTATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Applies Poseidon permutation to the provided state.
+    pub fn apply_permutation(state: &mut [BaseElement; STATE_WIDTH]) {
+        let half_full_rounds = NUM_FULL_ROUNDS / 2;
+        for round in 0..half_full_rounds {
+            Self::apply_full_round(state, round);
+        }
+        for round in half_full_rounds..half_full_rounds + NUM_PARTIAL_ROUNDS {
+            Self::apply_partial_round(state, round);
+        }
+        for round in half_full_rounds + NUM_PARTIAL_ROUNDS..NUM_ROUNDS {
+            Self::apply_full_round(state, round);
+        }
+    }
+
+    /// Poseidon full round function; the S-Box is applied to all state elements.
+    #[inline(always)]
+    pub fn apply_full_round(state: &mut [BaseElement; STATE_WIDTH], round: usize) {
+        Self::add_constants(state, &ARK[round]);
+        state.iter_mut().for_each(|s| *s = s.exp7());
+        mds_multiply(state);
+    }
+
+    /// Poseidon partial round function; the S-Box is applied to the first state element only.
+    #[inline(always)]
+    pub fn apply_partial_round(state: &mut [BaseElement; STATE_WIDTH], round: usize) {
+        Self::add_constants(state, &ARK[round]);
+        state[0] = state[0].exp7();
+        mds_multiply(state);
+    }
+
+    // HELPER FUNCTIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// Absorbs the provided elements followed by padding into the rate portion of the state, and
+    /// returns the first four elements of the resulting state.
+    ///
+    /// The elements are padded with a single one, followed by as many zeros as needed, followed
+    /// by a single one, such that the length of the padded input is a multiple of 8.
+    fn hash_padded<I: Iterator<Item = BaseElement>>(elements: I) -> ElementDigest {
+        let mut state = [BaseElement::ZERO; STATE_WIDTH];
+
+        // overwrite the rate portion of the state with the elements, and apply the Poseidon
+        // permutation every time the rate portion of the state is filled up
+        let mut i = 0;
+        for element in elements.chain(core::iter::once(BaseElement::ONE)) {
+            state[RATE_RANGE.start + i] = element;
+            i += 1;
+            if i == RATE_WIDTH {
+                Self::apply_permutation(&mut state);
+                i = 0;
+            }
+        }
+
+        // fill the remainder of the rate portion with zeros, followed by a single one, and apply
+        // the permutation to the last block
+        state[RATE_RANGE.start + i..RATE_RANGE.end - 1].fill(BaseElement::ZERO);
+        state[RATE_RANGE.end - 1] = BaseElement::ONE;
+        Self::apply_permutation(&mut state);
+
+        ElementDigest::new(state[DIGEST_RANGE].try_into().unwrap())
+    }
+
+    #[inline(always)]
+    fn add_constants(state: &mut [BaseElement; STATE_WIDTH], ark: &[BaseElement; STATE_WIDTH]) {
+        state.iter_mut().zip(ark).for_each(|(s, &k)| *s += k);
+    }
+}
+
+// MDS
+// ================================================================================================
+/// Poseidon MDS matrix; this is the sum of a circulant matrix with the first row
+/// [17, 15, 41, 16, 2, 28, 13, 13, 39, 18, 34, 20] and a diagonal matrix with the first diagonal
+/// element set to 8 and all other elements set to 0.
+const MDS: [[BaseElement; STATE_WIDTH]; STATE_WIDTH] = [
+    [
+        BaseElement::new(25),
+        BaseElement::new(15),
+        BaseElement::new(41),
+        BaseElement::new(16),
+        BaseElement::new(2),
+        BaseElement::new(28),
+        BaseElement::new(13),
+        BaseElement::new(13),
+        BaseElement::new(39),
+        BaseElement::new(18),
+        BaseElement::new(34),
+        BaseElement::new(20),
+    ],
+    [
+        BaseElement::new(20),
+        BaseElement::new(17),
+        BaseElement::new(15),
+        BaseElement::new(41),
+        BaseElement::new(16),
+        BaseElement::new(2),
+        BaseElement::new(28),
+        BaseElement::new(13),
+        BaseElement::new(13),
+        BaseElement::new(39),
+        BaseElement::new(18),
+        BaseElement::new(34),
+    ],
+    [
+        BaseElement::new(34),
+        BaseElement::new(20),
+        BaseElement::new(17),
+        BaseElement::new(15),
+        BaseElement::new(41),
+        BaseElement::new(16),
+        BaseElement::new(2),
+        BaseElement::new(28),
+        BaseElement::new(13),
+        BaseElement::new(13),
+        BaseElement::new(39),
+        BaseElement::new(18),
+    ],
+    [
+        BaseElement::new(18),
+        BaseElement::new(34),
+        BaseElement::new(20),
+        BaseElement::new(17),
+        BaseElement::new(15),
+        BaseElement::new(41),
+        BaseElement::new(16),
+        BaseElement::new(2),
+        BaseElement::new(28),
+        BaseElement::new(13),
+        BaseElement::new(13),
+        BaseElement::new(39),
+    ],
+    [
+        BaseElement::new(39),
+        BaseElement::new(18),
+        BaseElement::new(34),
+        BaseElement::new(20),
+        BaseElement::new(17),
+        BaseElement::new(15),
+        BaseElement::new(41),
+        BaseElement::new(16),
+        BaseElement::new(2),
+        BaseElement::new(28),
+        BaseElement::new(13),
+        BaseElement::new(13),
+    ],
+    [
+        BaseElement::new(13),
+        BaseElement::new(39),
+        BaseElement::new(18),
+        BaseElement::new(34),
+        BaseElement::new(20),
+        BaseElement::new(17),
+        BaseElement::new(15),
+        BaseElement::new(41),
+        BaseElement::new(16),
+        BaseElement::new(2),
+        BaseElement::new(28),
+        BaseElement::new(13),
+    ],
+    [
+        BaseElement::new(13),
+        BaseElement::new(13),
+        BaseElement::new(39),
+        BaseElement::new(18),
+        BaseElement::new(34),
+        BaseElement::new(20),
+        BaseElement::new(17),
+        BaseElement::new(15),
+        BaseElement::new(41),
+        BaseElement::new(16),
+        BaseElement::new(2),
+        BaseElement::new(28),
+    ],
+    [
+        BaseElement::new(28),
+        BaseElement::new(13),
+        BaseElement::new(13),
+        BaseElement::new(39),
+        BaseElement::new(18),
+        BaseElement::new(34),
+        BaseElement::new(20),
+        BaseElement::new(17),
+        BaseElement::new(15),
+        BaseElement::new(41),
+        BaseElement::new(16),
+        BaseElement::new(2),
+    ],
+    [
+        BaseElement::new(2),
+        BaseElement::new(28),
+        BaseElement::new(13),
+        BaseElement::new(13),
+        BaseElement::new(39),
+        BaseElement::new(18),
+        BaseElement::new(34),
+        BaseElement::new(20),
+        BaseElement::new(17),
+        BaseElement::new(15),
+        BaseElement::new(41),
+        BaseElement::new(16),
+    ],
+    [
+        BaseElement::new(16),
+        BaseElement::new(2),
+        BaseElement::new(28),
+        BaseElement::new(13),
+        BaseElement::new(13),
+        BaseElement::new(39),
+        BaseElement::new(18),
+        BaseElement::new(34),
+        BaseElement::new(20),
+        BaseElement::new(17),
+        BaseElement::new(15),
+        BaseElement::new(41),
+    ],
+    [
+        BaseElement::new(41),
+        BaseElement::new(16),
+        BaseElement::new(2),
+        BaseElement::new(28),
+        BaseElement::new(13),
+        BaseElement::new(13),
+        BaseElement::new(39),
+        BaseElement::new(18),
+        BaseElement::new(34),
+        BaseElement::new(20),
+        BaseElement::new(17),
+        BaseElement::new(15),
+    ],
+    [
+        BaseElement::new(15),
+        BaseElement::new(41),
+        BaseElement::new(16),
+        BaseElement::new(2),
+        BaseElement::new(28),
+        BaseElement::new(13),
+        BaseElement::new(13),
+        BaseElement::new(39),
+        BaseElement::new(18),
+        BaseElement::new(34),
+        BaseElement::new(20),
+        BaseElement::new(17),
+    ],
+];
+
+/// First row of the circulant part of the MDS matrix.
+const MDS_CIRC: [u64; STATE_WIDTH] = [17, 15, 41, 16, 2, 28, 13, 13, 39, 18, 34, 20];
+
+/// Diagonal part of the MDS matrix.
+const MDS_DIAG: [u64; STATE_WIDTH] = [8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+
+/// Multiplies the state by the MDS matrix.
+///
+/// Coefficients of the matrix are small, and thus, the products are accumulated into 128-bit
+/// integers and reduced only once per state element. Since the multiplication is linear, it can
+/// be performed directly on Montgomery representations of state elements.
+#[inline(always)]
+fn mds_multiply(state: &mut [BaseElement; STATE_WIDTH]) {
+    let values = state.map(|s| s.inner() as u128);
+    for (r, result) in state.iter_mut().enumerate() {
+        let mut acc = values[r] * MDS_DIAG[r] as u128;
+        for (i, &c) in MDS_CIRC.iter().enumerate() {
+            acc += values[(i + r) % STATE_WIDTH] * c as u128;
+        }
+
+        // reduce the accumulated value using 2^64 = 2^32 - 1 (mod p); the high part of the value
+        // is smaller than 2^9, and thus, a single reduction step is sufficient
+        let acc_hi = (acc >> 64) as u64;
+        let acc_lo = acc as u64;
+        let z = (acc_hi << 32) - acc_hi;
+        let (res, over) = acc_lo.overflowing_add(z);
+        *result = BaseElement::from_mont(res.wrapping_add(0u32.wrapping_sub(over as u32) as u64));
+    }
+}
+
+// ROUND CONSTANTS
+// ================================================================================================
+
+/// Poseidon round constants; these are the round constants used by Poseidon over the 64-bit field
+/// in [Plonky2](https://github.com/0xPolygonZero/plonky2), where they are sampled uniformly at
+/// random from ChaCha8 random number generator seeded with 0.
+const ARK: [[BaseElement; STATE_WIDTH]; NUM_ROUNDS] = [
+    [
+        BaseElement::new(13080132714287612933),
+        BaseElement::new(8594738767457295063),
+        BaseElement::new(12896916465481390516),
+        BaseElement::new(1109962092811921367),
+        BaseElement::new(16216730422861946898),
+        BaseElement::new(10137062673499593713),
+        BaseElement::new(15292064466732465823),
+        BaseElement::new(17255573294985989181),
+        BaseElement::new(14827154241873003558),
+        BaseElement::new(2846171647972703231),
+        BaseElement::new(16246264663680317601),
+        BaseElement::new(14214208087951879286),
+    ],
+    [
+        BaseElement::new(9667108687426275457),
+        BaseElement::new(6470857420712283733),
+        BaseElement::new(14103331940138337652),
+        BaseElement::new(11854816473550292865),
+        BaseElement::new(3498097497301325516),
+        BaseElement::new(7947235692523864220),
+        BaseElement::new(11110078701231901946),
+        BaseElement::new(16384314112672821048),
+        BaseElement::new(15404405912655775739),
+        BaseElement::new(14077880830714445579),
+        BaseElement::new(9555554662709218279),
+        BaseElement::new(13859595358210603949),
+    ],
+    [
+        BaseElement::new(16859897325061800066),
+        BaseElement::new(17685474420222222349),
+        BaseElement::new(17858764734618734949),
+        BaseElement::new(9410011022665866671),
+        BaseElement::new(12495243629579414666),
+        BaseElement::new(12416945298171515742),
+        BaseElement::new(5776666812364270983),
+        BaseElement::new(6314421662864060481),
+        BaseElement::new(7402742471423223171),
+        BaseElement::new(982536713192432718),
+        BaseElement::new(17321168865775127905),
+        BaseElement::new(2934354895005980211),
+    ],
+    [
+        BaseElement::new(10567510598607410195),
+        BaseElement::new(8135543733717919110),
+        BaseElement::new(116353493081713692),
+        BaseElement::new(8029688163494945618),
+        BaseElement::new(9003846637224807585),
+        BaseElement::new(7052445132467233849),
+        BaseElement::new(9645665432288852853),
+        BaseElement::new(5446430061030868787),
+        BaseElement::new(16770910634346036823),
+        BaseElement::new(17708360571433944729),
+        BaseElement::new(4661556288322237631),
+        BaseElement::new(11977051899316327985),
+    ],
+    [
+        BaseElement::new(4378616569090929672),
+        BaseElement::new(3334807502817538491),
+        BaseElement::new(8019184735943344966),
+        BaseElement::new(2395043908812246395),
+        BaseElement::new(6558421058331732611),
+        BaseElement::new(11735894060727326369),
+        BaseElement::new(8143540538889204488),
+        BaseElement::new(5991753489563751169),
+        BaseElement::new(12235918791502088007),
+        BaseElement::new(2880312033702687139),
+        BaseElement::new(18224748115308382355),
+        BaseElement::new(18070411013125314165),
+    ],
+    [
+        BaseElement::new(8156487614120951180),
+        BaseElement::new(10615269510047010719),
+        BaseElement::new(12489426404754222075),
+        BaseElement::new(5055279340069995710),
+        BaseElement::new(7231927319780248664),
+        BaseElement::new(2602078848106763799),
+        BaseElement::new(12445944369334781425),
+        BaseElement::new(3978905923892496205),
+        BaseElement::new(16711272944329818038),
+        BaseElement::new(10439032361227108922),
+        BaseElement::new(15110119871725214866),
+        BaseElement::new(821141790655890946),
+    ],
+    [
+        BaseElement::new(11073536380651186235),
+        BaseElement::new(4866839313097607757),
+        BaseElement::new(13118391689513956636),
+        BaseElement::new(14527674973762312380),
+        BaseElement::new(7612751959265567999),
+        BaseElement::new(6808090907814178161),
+        BaseElement::new(6899703779492644997),
+        BaseElement::new(3664666286336986826),
+        BaseElement::new(783179505424462608),
+        BaseElement::new(8990689241814097697),
+        BaseElement::new(9646603555412825679),
+        BaseElement::new(7351246026167205041),
+    ],
+    [
+        BaseElement::new(16970959813722173256),
+        BaseElement::new(15735726858241466429),
+        BaseElement::new(10347018221892268419),
+        BaseElement::new(12195545878449322889),
+        BaseElement::new(7423314197114049891),
+        BaseElement::new(14908016116973904153),
+        BaseElement::new(5840340122527363265),
+        BaseElement::new(17740311462440614128),
+        BaseElement::new(815306421953744623),
+        BaseElement::new(17456357368219253949),
+        BaseElement::new(6982651076559329072),
+        BaseElement::new(11970987324614963868),
+    ],
+    [
+        BaseElement::new(8167785008538063246),
+        BaseElement::new(9483259819397403968),
+        BaseElement::new(954550221664291548),
+        BaseElement::new(10339565171024313256),
+        BaseElement::new(8651171084286500102),
+        BaseElement::new(16974445528003515956),
+        BaseElement::new(15104530047940621190),
+        BaseElement::new(103271880867179718),
+        BaseElement::new(14654666245504492663),
+        BaseElement::new(12445769555936887967),
+        BaseElement::new(11250582358051997490),
+        BaseElement::new(6730977207490590241),
+    ],
+    [
+        BaseElement::new(15919951556166196935),
+        BaseElement::new(4423540216573360915),
+        BaseElement::new(16317664700341473511),
+        BaseElement::new(4723997214951767765),
+        BaseElement::new(10098756619006575500),
+        BaseElement::new(3223149401237667964),
+        BaseElement::new(6870494874300767682),
+        BaseElement::new(2902095711130291898),
+        BaseElement::new(7159372652788439733),
+        BaseElement::new(11500508372997952671),
+        BaseElement::new(13348148181479462670),
+        BaseElement::new(12729401155983882093),
+    ],
+    [
+        BaseElement::new(15021242795466053388),
+        BaseElement::new(3802990509227527157),
+        BaseElement::new(4665459515680145682),
+        BaseElement::new(13165553315407675603),
+        BaseElement::new(6496364397926233172),
+        BaseElement::new(12800832566287577810),
+        BaseElement::new(9737592377590267426),
+        BaseElement::new(8687131091302514939),
+        BaseElement::new(1488200421755445892),
+        BaseElement::new(11004377668730991641),
+        BaseElement::new(13516338734600228410),
+        BaseElement::new(2953581820660217936),
+    ],
+    [
+        BaseElement::new(3505040783153922951),
+        BaseElement::new(3710332827435113697),
+        BaseElement::new(15414874040873320221),
+        BaseElement::new(8602547649919482301),
+        BaseElement::new(13971349938398812007),
+        BaseElement::new(187239246702636066),
+        BaseElement::new(12886019973971254144),
+        BaseElement::new(4512274763990493707),
+        BaseElement::new(2986635507805503192),
+        BaseElement::new(2315252455709119454),
+        BaseElement::new(12537995864054210246),
+        BaseElement::new(2039491936479859267),
+    ],
+    [
+        BaseElement::new(1558644089185031256),
+        BaseElement::new(4074089203264759305),
+        BaseElement::new(2522268501749395707),
+        BaseElement::new(3414760436185256196),
+        BaseElement::new(17420887529146466921),
+        BaseElement::new(2817020417938125001),
+        BaseElement::new(16538346563888261485),
+        BaseElement::new(5592270336833998770),
+        BaseElement::new(16876602064684906232),
+        BaseElement::new(1793025614521516343),
+        BaseElement::new(2178510518148748532),
+        BaseElement::new(2726440714374752509),
+    ],
+    [
+        BaseElement::new(6502946837278398021),
+        BaseElement::new(15816362857667988792),
+        BaseElement::new(12997958454165692924),
+        BaseElement::new(5314892854495903792),
+        BaseElement::new(15533907063555687782),
+        BaseElement::new(12312015675698548715),
+        BaseElement::new(14140016464013350248),
+        BaseElement::new(16325589062962838690),
+        BaseElement::new(6796145646370327654),
+        BaseElement::new(1168753512742361735),
+        BaseElement::new(4100789820704709368),
+        BaseElement::new(15947554381540469177),
+    ],
+    [
+        BaseElement::new(8597377839806076919),
+        BaseElement::new(9704018824195918000),
+        BaseElement::new(12763288618765762688),
+        BaseElement::new(17249257732622847695),
+        BaseElement::new(1998710993415069759),
+        BaseElement::new(923759906393011543),
+        BaseElement::new(1271051229666811593),
+        BaseElement::new(17822362132088738077),
+        BaseElement::new(11797234543722669271),
+        BaseElement::new(5864538787265942447),
+        BaseElement::new(15975583211110506970),
+        BaseElement::new(7258516085733671960),
+    ],
+    [
+        BaseElement::new(17999926471875633100),
+        BaseElement::new(635992114476018166),
+        BaseElement::new(17205047318256576347),
+        BaseElement::new(17384900867876315312),
+        BaseElement::new(16484825562915784226),
+        BaseElement::new(16694130609036138894),
+        BaseElement::new(10575069350371260875),
+        BaseElement::new(8330575162062887277),
+        BaseElement::new(6212375704691932880),
+        BaseElement::new(15965138197626618226),
+        BaseElement::new(14285453069600046939),
+        BaseElement::new(10005163510208402517),
+    ],
+    [
+        BaseElement::new(885298637936952595),
+        BaseElement::new(541790758138118921),
+        BaseElement::new(5985203084790372993),
+        BaseElement::new(4685030219775483721),
+        BaseElement::new(1411106851304815020),
+        BaseElement::new(11290732479954096478),
+        BaseElement::new(208280581124868513),
+        BaseElement::new(10979018648467968495),
+        BaseElement::new(8600643745023338215),
+        BaseElement::new(3477453626867126061),
+        BaseElement::new(6428436309340258604),
+        BaseElement::new(5695415667275657934),
+    ],
+    [
+        BaseElement::new(15952065508715623490),
+        BaseElement::new(15571300830419767248),
+        BaseElement::new(17259785660502616862),
+        BaseElement::new(4298425495274316083),
+        BaseElement::new(9023601070579319352),
+        BaseElement::new(7353589709321807492),
+        BaseElement::new(2988848909076209475),
+        BaseElement::new(10439527789422046135),
+        BaseElement::new(6097734044161429459),
+        BaseElement::new(1113429873817861476),
+        BaseElement::new(1639063372386966591),
+        BaseElement::new(7863102812716788759),
+    ],
+    [
+        BaseElement::new(216040220732135364),
+        BaseElement::new(14252611488623712688),
+        BaseElement::new(9543395466794536974),
+        BaseElement::new(2714461051639810934),
+        BaseElement::new(2588317208781407279),
+        BaseElement::new(15458529123534594916),
+        BaseElement::new(15748417817551040856),
+        BaseElement::new(16414455697114422951),
+        BaseElement::new(13378164466674639511),
+        BaseElement::new(13894319928411294675),
+        BaseElement::new(5032680892090751540),
+        BaseElement::new(17201338494743078916),
+    ],
+    [
+        BaseElement::new(4397422800601932505),
+        BaseElement::new(11285062031581972327),
+        BaseElement::new(7309354640676468207),
+        BaseElement::new(10457152817239331848),
+        BaseElement::new(8855911538863247046),
+        BaseElement::new(4301853449821814398),
+        BaseElement::new(13001502396339103326),
+        BaseElement::new(10218424535115580246),
+        BaseElement::new(8628244713920681895),
+        BaseElement::new(17410423622514037261),
+        BaseElement::new(14080683768439215375),
+        BaseElement::new(11453161143447188100),
+    ],
+    [
+        BaseElement::new(16761509772042181939),
+        BaseElement::new(6688821660695954082),
+        BaseElement::new(12083434295263160416),
+        BaseElement::new(8540021431714616589),
+        BaseElement::new(6891616215679974226),
+        BaseElement::new(10229217098454812721),
+        BaseElement::new(3292165387203778711),
+        BaseElement::new(6090113424998243490),
+        BaseElement::new(13431780521962358660),
+        BaseElement::new(6061081364215809883),
+        BaseElement::new(16792066504222214142),
+        BaseElement::new(16134314044798124799),
+    ],
+    [
+        BaseElement::new(17070233710126619765),
+        BaseElement::new(6915716851370550800),
+        BaseElement::new(9505009849073026581),
+        BaseElement::new(6422700465081897153),
+        BaseElement::new(17977653991560529185),
+        BaseElement::new(5800870252836247255),
+        BaseElement::new(12096124733159345520),
+        BaseElement::new(7679273623392321940),
+        BaseElement::new(17835783910585744964),
+        BaseElement::new(2478664878205754377),
+        BaseElement::new(1720314468413114967),
+        BaseElement::new(10376757819003248056),
+    ],
+    [
+        BaseElement::new(10376377187857634245),
+        BaseElement::new(13344930747504284997),
+        BaseElement::new(11579281865160153596),
+        BaseElement::new(10300256980048736962),
+        BaseElement::new(378765236515040565),
+        BaseElement::new(11412420941557253424),
+        BaseElement::new(12931662470734252786),
+        BaseElement::new(43018908376346374),
+        BaseElement::new(3589810689190160071),
+        BaseElement::new(4688229274750659741),
+        BaseElement::new(13688957436484306091),
+        BaseElement::new(11424740943016984272),
+    ],
+    [
+        BaseElement::new(16001900718237913960),
+        BaseElement::new(5548469743008097574),
+        BaseElement::new(14584404916672178680),
+        BaseElement::new(3396622135873576824),
+        BaseElement::new(7861729246871155992),
+        BaseElement::new(16112271126908045545),
+        BaseElement::new(16988163966860016012),
+        BaseElement::new(273641680619529493),
+        BaseElement::new(15222677154027327363),
+        BaseElement::new(4070328078309830604),
+        BaseElement::new(13520458500363296391),
+        BaseElement::new(8235111705801363015),
+    ],
+    [
+        BaseElement::new(5575990058472514138),
+        BaseElement::new(2751301609188252989),
+        BaseElement::new(6478598528223547074),
+        BaseElement::new(386565553848556638),
+        BaseElement::new(9417729078939938713),
+        BaseElement::new(15204315939835727483),
+        BaseElement::new(14942015033780606261),
+        BaseElement::new(18369423901636582012),
+        BaseElement::new(4715338437538604447),
+        BaseElement::new(6840590980607806319),
+        BaseElement::new(5535471161490539014),
+        BaseElement::new(5341328005359029952),
+    ],
+    [
+        BaseElement::new(1475161295215894444),
+        BaseElement::new(7999197814297036636),
+        BaseElement::new(2984233088665867938),
+        BaseElement::new(3097746028144832229),
+        BaseElement::new(8849530863480031517),
+        BaseElement::new(7464920943249009773),
+        BaseElement::new(3802996844641460514),
+        BaseElement::new(6284458522545927646),
+        BaseElement::new(2307388003445002779),
+        BaseElement::new(4461479354745457623),
+        BaseElement::new(1649739722664588460),
+        BaseElement::new(3008391274160432867),
+    ],
+    [
+        BaseElement::new(5142217010456550622),
+        BaseElement::new(1775580461722730120),
+        BaseElement::new(161694268822794344),
+        BaseElement::new(1518963253808031703),
+        BaseElement::new(16475258091652710137),
+        BaseElement::new(119575899007375159),
+        BaseElement::new(1275863735937973999),
+        BaseElement::new(16539412514520642374),
+        BaseElement::new(2303365191438051950),
+        BaseElement::new(6435126839960916075),
+        BaseElement::new(17794599201026020053),
+        BaseElement::new(13847097589277840330),
+    ],
+    [
+        BaseElement::new(16645869274577729720),
+        BaseElement::new(8039205965509554440),
+        BaseElement::new(4788586935019371140),
+        BaseElement::new(15129007200040077746),
+        BaseElement::new(2055561615223771341),
+        BaseElement::new(4149731103701412892),
+        BaseElement::new(10268130195734144189),
+        BaseElement::new(13406631635880074708),
+        BaseElement::new(11429218277824986203),
+        BaseElement::new(15773968030812198565),
+        BaseElement::new(16050275277550506872),
+        BaseElement::new(11858586752031736643),
+    ],
+    [
+        BaseElement::new(8927746344866569756),
+        BaseElement::new(11802068403177695792),
+        BaseElement::new(157833420806751556),
+        BaseElement::new(4698875910749767878),
+        BaseElement::new(1616722774788291698),
+        BaseElement::new(3990951895163748090),
+        BaseElement::new(16758609224720795472),
+        BaseElement::new(3045571693290741477),
+        BaseElement::new(9281634245289836419),
+        BaseElement::new(13517688176723875370),
+        BaseElement::new(7961395585333219380),
+        BaseElement::new(1606574359105691080),
+    ],
+    [
+        BaseElement::new(17564372683613562171),
+        BaseElement::new(4664015225343144418),
+        BaseElement::new(6133721340680280128),
+        BaseElement::new(2667022304383014929),
+        BaseElement::new(12316557761857340230),
+        BaseElement::new(10375614850625292317),
+        BaseElement::new(8141542666379135068),
+        BaseElement::new(9185476451083834432),
+        BaseElement::new(4991072365274649547),
+        BaseElement::new(17398204971778820365),
+        BaseElement::new(16127888338958422584),
+        BaseElement::new(13586792051317758204),
+    ],
+];
@@ -0,0 +1,285 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use proptest::prelude::*;
+use rand_utils::{rand_array, rand_value};
+
+use super::{
+    mds_multiply, BaseElement, ElementDigest, ElementHasher, FieldElement, Hasher, Poseidon64_256,
+    StarkField, ALPHA, MDS, STATE_WIDTH,
+};
+
+#[test]
+fn test_sbox() {
+    let state: [BaseElement; STATE_WIDTH] = rand_array();
+
+    let mut expected = state;
+    expected.iter_mut().for_each(|v| *v = v.exp(ALPHA));
+
+    let mut actual = state;
+    actual.iter_mut().for_each(|v| *v = v.exp7());
+
+    assert_eq!(expected, actual);
+}
+
+// The test vectors below are the test vectors of the Poseidon permutation over the 64-bit field
+// published in Plonky2 (`plonky2/src/hash/poseidon_goldilocks.rs`).
+
+#[test]
+fn apply_permutation_zeros() {
+    let mut state = [BaseElement::ZERO; STATE_WIDTH];
+
+    Poseidon64_256::apply_permutation(&mut state);
+
+    let expected = [
+        0x3c18a9786cb0b359,
+        0xc4055e3364a246c3,
+        0x7953db0ab48808f4,
+        0xc71603f33a1144ca,
+        0xd7709673896996dc,
+        0x46a84e87642f44ed,
+        0xd032648251ee0b3c,
+        0x1c687363b207df62,
+        0xdf8565563e8045fe,
+        0x40f5b37ff4254dae,
+        0xd070f637b431067c,
+        0x1792b1c4342109d7,
+    ];
+
+    assert_eq!(expected.map(BaseElement::new), state);
+}
+
+#[test]
+fn apply_permutation() {
+    let mut state: [BaseElement; STATE_WIDTH] =
+        core::array::from_fn(|i| BaseElement::new(i as u64));
+
+    Poseidon64_256::apply_permutation(&mut state);
+
+    let expected = [
+        0xd64e1e3efc5b8e9e,
+        0x53666633020aaa47,
+        0xd40285597c6a8825,
+        0x613a4f81e81231d2,
+        0x414754bfebd051f0,
+        0xcb1f8980294a023f,
+        0x6eb2a9e4d54a9d0f,
+        0x1902bc3af467e056,
+        0xf045d5eafdc6021f,
+        0xe4150f77caaa3be5,
+        0xc9bfd01d39b50cce,
+        0x5c0a27fcb0e1459b,
+    ];
+
+    assert_eq!(expected.map(BaseElement::new), state);
+}
+
+#[test]
+fn apply_permutation_max_elements() {
+    let mut state = [-BaseElement::ONE; STATE_WIDTH];
+
+    Poseidon64_256::apply_permutation(&mut state);
+
+    let expected = [
+        0xbe0085cfc57a8357,
+        0xd95af71847d05c09,
+        0xcf55a13d33c1c953,
+        0x95803a74f4530e82,
+        0xfcd99eb30a135df1,
+        0xe095905e913a3029,
+        0xde0392461b42919b,
+        0x7d3260e24e81d031,
+        0x10d3d0465d9deaa0,
+        0xa87571083dfc2a47,
+        0xe18263681e9958f8,
+        0xe28e96f1ae5e60d3,
+    ];
+
+    assert_eq!(expected.map(BaseElement::new), state);
+}
+
+#[test]
+fn apply_permutation_random_elements() {
+    let mut state = [
+        0x8ccbbbea4fe5d2b7,
+        0xc2af59ee9ec49970,
+        0x90f7e1a9e658446a,
+        0xdcc0630a3ab8b1b8,
+        0x7ff8256bca20588c,
+        0x5d99a7ca0c44ecfb,
+        0x48452b17a70fbee3,
+        0xeb09d654690b6c88,
+        0x4a55d3a39c676a88,
+        0xc0407a38d2285139,
+        0xa234bac9356386d1,
+        0xe1633f2bad98a52f,
+    ]
+    .map(BaseElement::new);
+
+    Poseidon64_256::apply_permutation(&mut state);
+
+    let expected = [
+        0xa89280105650c4ec,
+        0xab542d53860d12ed,
+        0x5704148e9ccab94f,
+        0xd3a826d4b62da9f5,
+        0x8a7a6ca87892574f,
+        0xc7017e1cad1a674e,
+        0x1f06668922318e34,
+        0xa3b203bc8102676f,
+        0xfcc781b0ce382bf2,
+        0x934c69ff3ed14ba5,
+        0x504688a5996e8f13,
+        0x401f3f2ed524a2ba,
+    ];
+
+    assert_eq!(expected.map(BaseElement::new), state);
+}
+
+#[test]
+fn merge_vs_permutation() {
+    let elements: [BaseElement; 8] = rand_array();
+
+    let digests: [ElementDigest; 2] = [
+        ElementDigest::new(elements[..4].try_into().unwrap()),
+        ElementDigest::new(elements[4..].try_into().unwrap()),
+    ];
+
+    // merging two digests is the same as applying the permutation to the digests followed by
+    // four zeros and reading the first four elements of the result
+    let mut state = [BaseElement::ZERO; STATE_WIDTH];
+    state[..8].copy_from_slice(&elements);
+    Poseidon64_256::apply_permutation(&mut state);
+
+    let m_result = Poseidon64_256::merge(&digests);
+    assert_eq!(state[..4], *m_result.as_elements());
+}
+
+#[test]
+fn hash_elements_vs_permutation() {
+    let elements: [BaseElement; 9] = rand_array();
+
+    // nine elements are padded with a one, followed by five zeros, followed by a one, and the
+    // resulting two blocks are absorbed by overwriting the first eight elements of the state
+    let mut state = [BaseElement::ZERO; STATE_WIDTH];
+    state[..8].copy_from_slice(&elements[..8]);
+    Poseidon64_256::apply_permutation(&mut state);
+    state[..8].copy_from_slice(&[
+        elements[8],
+        BaseElement::ONE,
+        BaseElement::ZERO,
+        BaseElement::ZERO,
+        BaseElement::ZERO,
+        BaseElement::ZERO,
+        BaseElement::ZERO,
+        BaseElement::ONE,
+    ]);
+    Poseidon64_256::apply_permutation(&mut state);
+
+    let h_result = Poseidon64_256::hash_elements(&elements);
+    assert_eq!(state[..4], *h_result.as_elements());
+
+    // seven elements are padded with two ones only
+    let mut state = [BaseElement::ZERO; STATE_WIDTH];
+    state[..7].copy_from_slice(&elements[..7]);
+    state[7] = BaseElement::ONE;
+    Poseidon64_256::apply_permutation(&mut state);
+    state[..8].copy_from_slice(&[
+        BaseElement::ZERO,
+        BaseElement::ZERO,
+        BaseElement::ZERO,
+        BaseElement::ZERO,
+        BaseElement::ZERO,
+        BaseElement::ZERO,
+        BaseElement::ZERO,
+        BaseElement::ONE,
+    ]);
+    Poseidon64_256::apply_permutation(&mut state);
+
+    let h_result = Poseidon64_256::hash_elements(&elements[..7]);
+    assert_eq!(state[..4], *h_result.as_elements());
+}
+
+#[test]
+fn hash_elements_vs_merge_many() {
+    let elements: [BaseElement; 12] = rand_array();
+
+    let digests: [ElementDigest; 3] = [
+        ElementDigest::new(elements[..4].try_into().unwrap()),
+        ElementDigest::new(elements[4..8].try_into().unwrap()),
+        ElementDigest::new(elements[8..].try_into().unwrap()),
+    ];
+
+    let m_result = Poseidon64_256::merge_many(&digests);
+    let h_result = Poseidon64_256::hash_elements(&elements);
+    assert_eq!(m_result, h_result);
+}
+
+#[test]
+fn hash_elements_vs_merge_with_int() {
+    let seed = ElementDigest::new(rand_array());
+
+    // ----- value fits into a field element ------------------------------------------------------
+    let val: BaseElement = rand_value();
+    let m_result = Poseidon64_256::merge_with_int(seed, val.as_int());
+
+    let mut elements = seed.as_elements().to_vec();
+    elements.push(val);
+    let h_result = Poseidon64_256::hash_elements(&elements);
+
+    assert_eq!(m_result, h_result);
+
+    // ----- value does not fit into a field element ----------------------------------------------
+    let val = BaseElement::MODULUS + 2;
+    let m_result = Poseidon64_256::merge_with_int(seed, val);
+
+    let mut elements = seed.as_elements().to_vec();
+    elements.push(BaseElement::new(val));
+    elements.push(BaseElement::new(1));
+    let h_result = Poseidon64_256::hash_elements(&elements);
+
+    assert_eq!(m_result, h_result);
+}
+
+#[test]
+fn hash_padding() {
+    // adding a zero bytes at the end of a byte string should result in a different hash
+    let r1 = Poseidon64_256::hash(&[1_u8, 2, 3]);
+    let r2 = Poseidon64_256::hash(&[1_u8, 2, 3, 0]);
+    assert_ne!(r1, r2);
+
+    // same as above but with input splitting over two elements
+    let r1 = Poseidon64_256::hash(&[1_u8, 2, 3, 4, 5, 6, 7]);
+    let r2 = Poseidon64_256::hash(&[1_u8, 2, 3, 4, 5, 6, 7, 0]);
+    assert_ne!(r1, r2);
+}
+
+#[test]
+fn hash_elements_padding() {
+    let e1: [BaseElement; 2] = rand_array();
+    let e2 = [e1[0], e1[1], BaseElement::ZERO];
+
+    let r1 = Poseidon64_256::hash_elements(&e1);
+    let r2 = Poseidon64_256::hash_elements(&e2);
+    assert_ne!(r1, r2);
+}
+
+proptest! {
+    #[test]
+    fn mds_proptest(a in any::<[u64; STATE_WIDTH]>()) {
+        let mut v1 = a.map(BaseElement::new);
+        let mut v2 = v1;
+
+        mds_multiply(&mut v1);
+
+        let mut result = [BaseElement::ZERO; STATE_WIDTH];
+        result.iter_mut().zip(MDS).for_each(|(r, mds_row)| {
+            v2.iter().zip(mds_row).for_each(|(&s, m)| *r += m * s);
+        });
+        v2 = result;
+
+        prop_assert_eq!(v1, v2);
+    }
+}
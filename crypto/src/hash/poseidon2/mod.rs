@@ -0,0 +1,536 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use core::ops::Range;
+
+use math::{fields::f64::BaseElement, FieldElement, StarkField};
+
+use super::{AlgebraicSponge, Digest, ElementHasher, Hasher};
+
+mod digest;
+pub use digest::ElementDigest;
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Sponge state is set to 12 field elements or 96 bytes; 8 elements are reserved for rate and
+/// the remaining 4 elements are reserved for capacity.
+const STATE_WIDTH: usize = 12;
+
+/// The rate portion of the state is located in elements 4 through 11.
+const RATE_RANGE: Range<usize> = 4..12;
+const RATE_WIDTH: usize = RATE_RANGE.end - RATE_RANGE.start;
+
+const INPUT1_RANGE: Range<usize> = 4..8;
+const INPUT2_RANGE: Range<usize> = 8..12;
+
+/// The capacity portion of the state is located in elements 0, 1, 2, and 3.
+const CAPACITY_RANGE: Range<usize> = 0..4;
+
+/// The output of the hash function is a digest which consists of 4 field elements or 32 bytes.
+///
+/// The digest is returned from state elements 4, 5, 6, and 7 (the first four elements of the
+/// rate portion).
+const DIGEST_RANGE: Range<usize> = 4..8;
+const DIGEST_SIZE: usize = DIGEST_RANGE.end - DIGEST_RANGE.start;
+
+/// The number of external rounds is set to 8 (4 at the beginning and 4 at the end of the
+/// permutation), and the number of internal rounds is set to 22; this targets 128-bit security
+/// level for a 64-bit field with S-Box degree 7 as per section 6 of
+/// <https://eprint.iacr.org/2023/323.pdf>.
+const NUM_EXTERNAL_ROUNDS: usize = 8;
+const NUM_INTERNAL_ROUNDS: usize = 22;
+
+/// S-Box power; the smallest integer d such that gcd(d, p - 1) = 1.
+#[cfg(test)]
+const ALPHA: u64 = 7;
+
+// HASHER IMPLEMENTATION
+// ================================================================================================
+
+/// Implementation of [Hasher] trait for Poseidon2 hash function with 256-bit output.
+///
+/// The permutation is implemented according to the Poseidon2
+/// [specifications](https://eprint.iacr.org/2023/323.pdf) instantiated with the following
+/// parameters:
+/// * Field: 64-bit prime field with modulus 2^64 - 2^32 + 1.
+/// * State width: 12 field elements.
+/// * Capacity size: 4 field elements.
+/// * Number of external (full) rounds: 8.
+/// * Number of internal (partial) rounds: 22.
+/// * S-Box degree: 7.
+///
+/// Compared to [Poseidon64_256](crate::hashers::Poseidon64_256), Poseidon2 uses two different
+/// linear layers: external rounds use the matrix circ(2 * M4, M4, M4) built from the 4x4 MDS
+/// matrix M4 of the specifications, and internal rounds use the matrix 1 + diag(D), where 1 is
+/// the all-ones matrix and D is a vector of small constants. Multiplication by the latter matrix
+/// requires only a single sum and one multiplication by a small constant per state element, and
+/// internal rounds add a round constant to the first state element only. This makes Poseidon2
+/// considerably faster than Poseidon, which is especially noticeable when building large
+/// Merkle trees.
+///
+/// Round constants are generated using the Grain LFSR as described in appendix F of the Poseidon
+/// [specifications](https://eprint.iacr.org/2019/458.pdf) (i.e., via the reference
+/// `generate_parameters_grain.sage` script with parameters `1 0 64 12 8 22`); for internal rounds
+/// only the first constant of each round is used. The
+/// internal diagonal was chosen such that the internal matrix is invertible and the
+/// characteristic polynomials of its first 24 powers are irreducible, as required by section 5.3
+/// of the specifications.
+///
+/// The above parameters target 128-bit security level. The digest consists of four field elements
+/// and it can be serialized into 32 bytes (256 bits).
+///
+/// ## Sponge construction
+/// The sponge construction follows the one used by [Rp64_256](crate::hashers::Rp64_256): the
+/// first 4 elements of the state are used for capacity and the remaining 8 elements for rate,
+/// the first capacity element is initialized to the number of elements to be hashed, and the
+/// digest is read from the first four elements of the rate portion of the state. Thus, functions
+/// [hash_elements()](Poseidon2_64_256::hash_elements), [merge()](Poseidon2_64_256::merge), and
+/// [merge_with_int()](Poseidon2_64_256::merge_with_int) are internally consistent, while the
+/// [hash()](Poseidon2_64_256::hash) function uses a different (byte-oriented) encoding of its
+/// inputs.
+pub struct Poseidon2_64_256();
+
+impl Hasher for Poseidon2_64_256 {
+    type Digest = ElementDigest;
+
+    const COLLISION_RESISTANCE: u32 = 128;
+
+    fn hash(bytes: &[u8]) -> Self::Digest {
+        // compute the number of elements required to represent the string; we will be processing
+        // the string in 7-byte chunks, thus the number of elements will be equal to the number
+        // of such chunks (including a potential partial chunk at the end).
+        let num_elements = if bytes.len() % 7 == 0 {
+            bytes.len() / 7
+        } else {
+            bytes.len() / 7 + 1
+        };
+
+        // initialize state to all zeros, except for the first element of the capacity part, which
+        // is set to the number of elements to be hashed. this is done so that adding zero elements
+        // at the end of the list always results in a different hash.
+        let mut state = [BaseElement::ZERO; STATE_WIDTH];
+        state[CAPACITY_RANGE.start] = BaseElement::new(num_elements as u64);
+
+        // break the string into 7-byte chunks, convert each chunk into a field element, and
+        // absorb the element into the rate portion of the state. we use 7-byte chunks because
+        // every 7-byte chunk is guaranteed to map to some field element.
+        let mut i = 0;
+        let mut buf = [0_u8; 8];
+        for chunk in bytes.chunks(7) {
+            if i < num_elements - 1 {
+                buf[..7].copy_from_slice(chunk);
+            } else {
+                // if we are dealing with the last chunk, it may be smaller than 7 bytes long, so
+                // we need to handle it slightly differently. we also append a byte with value 1
+                // to the end of the string; this pads the string in such a way that adding
+                // trailing zeros results in different hash
+                let chunk_len = chunk.len();
+                buf = [0_u8; 8];
+                buf[..chunk_len].copy_from_slice(chunk);
+                buf[chunk_len] = 1;
+            }
+
+            // convert the bytes into a field element and absorb it into the rate portion of the
+            // state; if the rate is filled up, apply the Poseidon2 permutation and start absorbing
+            // again from zero index.
+            state[RATE_RANGE.start + i] += BaseElement::new(u64::from_le_bytes(buf));
+            i += 1;
+            if i % RATE_WIDTH == 0 {
+                Self::apply_permutation(&mut state);
+                i = 0;
+            }
+        }
+
+        // if we absorbed some elements but didn't apply a permutation to them (would happen when
+        // the number of elements is not a multiple of RATE_WIDTH), apply the Poseidon2 permutation.
+        // we don't need to apply any extra padding because we injected total number of elements
+        // in the input list into the capacity portion of the state during initialization.
+        if i > 0 {
+            Self::apply_permutation(&mut state);
+        }
+
+        // return the first 4 elements of the state as hash result
+        ElementDigest::new(state[DIGEST_RANGE].try_into().unwrap())
+    }
+
+    fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
+        // initialize the state by copying the digest elements into the rate portion of the state
+        // (8 total elements), and set the first capacity element to 8 (the number of elements to
+        // be hashed).
+        let mut state = [BaseElement::ZERO; STATE_WIDTH];
+        state[RATE_RANGE].copy_from_slice(Self::Digest::digests_as_elements(values));
+        state[CAPACITY_RANGE.start] = BaseElement::new(RATE_WIDTH as u64);
+
+        // apply the Poseidon2 permutation and return the first four elements of the state
+        Self::apply_permutation(&mut state);
+        ElementDigest::new(state[DIGEST_RANGE].try_into().unwrap())
+    }
+
+    fn merge_many(values: &[Self::Digest]) -> Self::Digest {
+        Self::hash_elements(ElementDigest::digests_as_elements(values))
+    }
+
+    fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
+        // initialize the state as follows:
+        // - seed is copied into the first 4 elements of the rate portion of the state.
+        // - if the value fits into a single field element, copy it into the fifth rate element
+        //   and set the first capacity element to 5 (the number of elements to be hashed).
+        // - if the value doesn't fit into a single field element, split it into two field
+        //   elements, copy them into rate elements 5 and 6, and set the first capacity element
+        //   to 6.
+        let mut state = [BaseElement::ZERO; STATE_WIDTH];
+        state[INPUT1_RANGE].copy_from_slice(seed.as_elements());
+        state[INPUT2_RANGE.start] = BaseElement::new(value);
+        if value < BaseElement::MODULUS {
+            state[CAPACITY_RANGE.start] = BaseElement::new(DIGEST_SIZE as u64 + 1);
+        } else {
+            state[INPUT2_RANGE.start + 1] = BaseElement::new(value / BaseElement::MODULUS);
+            state[CAPACITY_RANGE.start] = BaseElement::new(DIGEST_SIZE as u64 + 2);
+        }
+
+        // apply the Poseidon2 permutation and return the first four elements of the state
+        Self::apply_permutation(&mut state);
+        ElementDigest::new(state[DIGEST_RANGE].try_into().unwrap())
+    }
+}
+
+impl ElementHasher for Poseidon2_64_256 {
+    type BaseField = BaseElement;
+
+    fn hash_elements<E: FieldElement<BaseField = Self::BaseField>>(elements: &[E]) -> Self::Digest {
+        // convert the elements into a list of base field elements
+        let elements = E::slice_as_base_elements(elements);
+
+        // initialize state to all zeros, except for the last element of the capacity part, which
+        // is set to the number of elements to be hashed. this is done so that adding zero elements
+        // at the end of the list always results in a different hash.
+        let mut state = [BaseElement::ZERO; STATE_WIDTH];
+        state[CAPACITY_RANGE.start] = BaseElement::new(elements.len() as u64);
+
+        // absorb elements into the state one by one until the rate portion of the state is filled
+        // up; then apply the Poseidon2 permutation and start absorbing again; repeat until all
+        // elements have been absorbed
+        let mut i = 0;
+        for &element in elements.iter() {
+            state[RATE_RANGE.start + i] += element;
+            i += 1;
+            if i % RATE_WIDTH == 0 {
+                Self::apply_permutation(&mut state);
+                i = 0;
+            }
+        }
+
+        // if we absorbed some elements but didn't apply a permutation to them (would happen when
+        // the number of elements is not a multiple of RATE_WIDTH), apply the Poseidon2 permutation.
+        // we don't need to apply any extra padding because we injected total number of elements
+        // in the input list into the capacity portion of the state during initialization.
+        if i > 0 {
+            Self::apply_permutation(&mut state);
+        }
+
+        // return the first 4 elements of the state as hash result
+        ElementDigest::new(state[DIGEST_RANGE].try_into().unwrap())
+    }
+}
+
+impl AlgebraicSponge for Poseidon2_64_256 {
+    const STATE_WIDTH: usize = STATE_WIDTH;
+    const RATE_RANGE: Range<usize> = RATE_RANGE;
+    const CAPACITY_RANGE: Range<usize> = CAPACITY_RANGE;
+
+    fn permute(state: &mut [BaseElement]) {
+        Self::apply_permutation(state.try_into().expect("invalid state width"));
+    }
+
+    fn digest_elements(digest: &Self::Digest) -> &[BaseElement] {
+        digest.as_elements()
+    }
+}
+
+// HASH FUNCTION IMPLEMENTATION
+// ================================================================================================
+
+impl Poseidon2_64_256 {
+    // CONSTANTS
+    // --------------------------------------------------------------------------------------------
+
+    /// The number of external rounds is set to 8: 4 at the beginning and 4 at the end of the
+    /// permutation.
+    pub const NUM_EXTERNAL_ROUNDS: usize = NUM_EXTERNAL_ROUNDS;
+
+    /// The number of internal rounds is set to 22.
+    pub const NUM_INTERNAL_ROUNDS: usize = NUM_INTERNAL_ROUNDS;
+
+    /// Sponge state is set to 12 field elements or 96 bytes; 8 elements are reserved for rate and
+    /// the remaining 4 elements are reserved for capacity.
+    pub const STATE_WIDTH: usize = STATE_WIDTH;
+
+    /// The rate portion of the state is located in elements 4 through 11 (inclusive).
+    pub const RATE_RANGE: Range<usize> = RATE_RANGE;
+
+    /// The capacity portion of the state is located in elements 0, 1, 2, and 3.
+    pub const CAPACITY_RANGE: Range<usize> = CAPACITY_RANGE;
+
+    /// The output of the hash function can be read from state elements 4, 5, 6, and 7.
+    pub const DIGEST_RANGE: Range<usize> = DIGEST_RANGE;
+
+    /// Diagonal of the matrix used for computing the linear layer in internal rounds.
+    pub const INTERNAL_DIAGONAL: [BaseElement; STATE_WIDTH] = INTERNAL_DIAGONAL;
+
+    /// Round constants added to the hasher state at the beginning of each external round.
+    pub const ARK_EXTERNAL: [[BaseElement; STATE_WIDTH]; NUM_EXTERNAL_ROUNDS] = ARK_EXTERNAL;
+
+    /// Round constants added to the first state element at the beginning of each internal round.
+    pub const ARK_INTERNAL: [BaseElement; NUM_INTERNAL_ROUNDS] = ARK_INTERNAL;
+
+    // POSEIDON2 PERMUTATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Applies Poseidon2 permutation to the provided state.
+    pub fn apply_permutation(state: &mut [BaseElement; STATE_WIDTH]) {
+        let half_external_rounds = NUM_EXTERNAL_ROUNDS / 2;
+        apply_external_linear_layer(state);
+        for round in 0..half_external_rounds {
+            Self::apply_external_round(state, round);
+        }
+        for round in 0..NUM_INTERNAL_ROUNDS {
+            Self::apply_internal_round(state, round);
+        }
+        for round in half_external_rounds..NUM_EXTERNAL_ROUNDS {
+            Self::apply_external_round(state, round);
+        }
+    }
+
+    /// Poseidon2 external round function; the S-Box is applied to all state elements.
+    #[inline(always)]
+    pub fn apply_external_round(state: &mut [BaseElement; STATE_WIDTH], round: usize) {
+        state
+            .iter_mut()
+            .zip(ARK_EXTERNAL[round])
+            .for_each(|(s, k)| *s = (*s + k).exp7());
+        apply_external_linear_layer(state);
+    }
+
+    /// Poseidon2 internal round function; the round constant is added and the S-Box is applied to
+    /// the first state element only.
+    #[inline(always)]
+    pub fn apply_internal_round(state: &mut [BaseElement; STATE_WIDTH], round: usize) {
+        state[0] = (state[0] + ARK_INTERNAL[round]).exp7();
+        apply_internal_linear_layer(state);
+    }
+}
+
+// LINEAR LAYERS
+// ================================================================================================
+
+/// Multiplies the state by the matrix circ(2 * M4, M4, M4).
+///
+/// This is done by first multiplying each 4-element chunk of the state by M4, and then adding
+/// to each element the sum of the elements at the same position across all chunks.
+#[inline(always)]
+fn apply_external_linear_layer(state: &mut [BaseElement; STATE_WIDTH]) {
+    for chunk in state.chunks_exact_mut(4) {
+        apply_m4(chunk.try_into().unwrap());
+    }
+
+    let mut sums = [BaseElement::ZERO; 4];
+    for chunk in state.chunks_exact(4) {
+        sums.iter_mut().zip(chunk).for_each(|(s, &c)| *s += c);
+    }
+    for chunk in state.chunks_exact_mut(4) {
+        chunk.iter_mut().zip(sums).for_each(|(c, s)| *c += s);
+    }
+}
+
+/// Multiplies a 4-element vector by the matrix M4 from section 5.1 of the Poseidon2
+/// specifications using 8 additions and 4 doublings.
+#[inline(always)]
+fn apply_m4(x: &mut [BaseElement; 4]) {
+    let t0 = x[0] + x[1];
+    let t1 = x[2] + x[3];
+    let t2 = x[1].double() + t1;
+    let t3 = x[3].double() + t0;
+    let t4 = t1.double().double() + t3;
+    let t5 = t0.double().double() + t2;
+    let t6 = t3 + t5;
+    let t7 = t2 + t4;
+    *x = [t6, t5, t7, t4];
+}
+
+/// Multiplies the state by the matrix 1 + diag(INTERNAL_DIAGONAL), where 1 is the matrix with
+/// all entries set to one.
+#[inline(always)]
+fn apply_internal_linear_layer(state: &mut [BaseElement; STATE_WIDTH]) {
+    let sum = state.iter().fold(BaseElement::ZERO, |acc, &s| acc + s);
+    state.iter_mut().zip(INTERNAL_DIAGONAL).for_each(|(s, d)| *s = *s * d + sum);
+}
+
+// INTERNAL DIAGONAL
+// ================================================================================================
+
+/// Diagonal of the internal linear layer matrix; all values are small and distinct.
+const INTERNAL_DIAGONAL: [BaseElement; STATE_WIDTH] = [
+    BaseElement::new(9),
+    BaseElement::new(57),
+    BaseElement::new(58),
+    BaseElement::new(110),
+    BaseElement::new(114),
+    BaseElement::new(128),
+    BaseElement::new(137),
+    BaseElement::new(143),
+    BaseElement::new(187),
+    BaseElement::new(197),
+    BaseElement::new(242),
+    BaseElement::new(250),
+];
+
+// ROUND CONSTANTS
+// ================================================================================================
+
+/// Poseidon2 round constants; the constants for external rounds are added to all state elements,
+/// while the constants for internal rounds are added to the first state element only.
+const ARK_EXTERNAL: [[BaseElement; STATE_WIDTH]; NUM_EXTERNAL_ROUNDS] = [
+    [
+        BaseElement::new(1431286215153372998),
+        BaseElement::new(3509349009260703107),
+        BaseElement::new(2289575380984896342),
+        BaseElement::new(10625215922958251110),
+        BaseElement::new(17137022507167291684),
+        BaseElement::new(17143426961497010024),
+        BaseElement::new(9589775313463224365),
+        BaseElement::new(7736066733515538648),
+        BaseElement::new(2217569167061322248),
+        BaseElement::new(10394930802584583083),
+        BaseElement::new(4612393375016695705),
+        BaseElement::new(5332470884919453534),
+    ],
+    [
+        BaseElement::new(8724526834049581439),
+        BaseElement::new(17673787971454860688),
+        BaseElement::new(2519987773101056005),
+        BaseElement::new(7999687124137420323),
+        BaseElement::new(18312454652563306701),
+        BaseElement::new(15136091233824155669),
+        BaseElement::new(1257110570403430003),
+        BaseElement::new(5665449074466664773),
+        BaseElement::new(16178737609685266571),
+        BaseElement::new(52855143527893348),
+        BaseElement::new(8084454992943870230),
+        BaseElement::new(2597062441266647183),
+    ],
+    [
+        BaseElement::new(3342624911463171251),
+        BaseElement::new(6781356195391537436),
+        BaseElement::new(4697929572322733707),
+        BaseElement::new(4179687232228901671),
+        BaseElement::new(17841073646522133059),
+        BaseElement::new(18340176721233187897),
+        BaseElement::new(13152929999122219197),
+        BaseElement::new(6306257051437840427),
+        BaseElement::new(4974451914008050921),
+        BaseElement::new(11258703678970285201),
+        BaseElement::new(581736081259960204),
+        BaseElement::new(18323286026903235604),
+    ],
+    [
+        BaseElement::new(10250026231324330997),
+        BaseElement::new(13321947507807660157),
+        BaseElement::new(13020725208899496943),
+        BaseElement::new(11416990495425192684),
+        BaseElement::new(7221795794796219413),
+        BaseElement::new(2607917872900632985),
+        BaseElement::new(2591896057192169329),
+        BaseElement::new(10485489452304998145),
+        BaseElement::new(9480186048908910015),
+        BaseElement::new(2645141845409940474),
+        BaseElement::new(16242299839765162610),
+        BaseElement::new(12203738590896308135),
+    ],
+    [
+        BaseElement::new(10552448846206288151),
+        BaseElement::new(14987673924494666433),
+        BaseElement::new(18035303280469462414),
+        BaseElement::new(16595113834715919465),
+        BaseElement::new(15208661533916677630),
+        BaseElement::new(4170608138187333497),
+        BaseElement::new(16304084357983152470),
+        BaseElement::new(2331503858766652994),
+        BaseElement::new(8776079357547932587),
+        BaseElement::new(18299646478835171989),
+        BaseElement::new(3681263166902989193),
+        BaseElement::new(12612029705709390274),
+    ],
+    [
+        BaseElement::new(12014669431902405777),
+        BaseElement::new(11319504285297576766),
+        BaseElement::new(5234999940078631477),
+        BaseElement::new(1125448944938006422),
+        BaseElement::new(2164405204907480972),
+        BaseElement::new(6168495504522907053),
+        BaseElement::new(6250236942243891229),
+        BaseElement::new(18269902991411124149),
+        BaseElement::new(9426885685329917236),
+        BaseElement::new(4521800374915508165),
+        BaseElement::new(2213719649464492152),
+        BaseElement::new(9422759956003735939),
+    ],
+    [
+        BaseElement::new(12723275943377720767),
+        BaseElement::new(14785736031955679545),
+        BaseElement::new(15257683393549924851),
+        BaseElement::new(14586462537439744229),
+        BaseElement::new(13109892360729616102),
+        BaseElement::new(18054952537889795742),
+        BaseElement::new(12589969976105374274),
+        BaseElement::new(1436163932748701916),
+        BaseElement::new(14879322534176465619),
+        BaseElement::new(17580838042056220468),
+        BaseElement::new(17970300042937392952),
+        BaseElement::new(1420156878331078790),
+    ],
+    [
+        BaseElement::new(17310902395782251544),
+        BaseElement::new(9021117459098865178),
+        BaseElement::new(9956374953785489337),
+        BaseElement::new(9283926179170577664),
+        BaseElement::new(2866744588122882663),
+        BaseElement::new(12613310502798528952),
+        BaseElement::new(48642999969593367),
+        BaseElement::new(5069344854700671784),
+        BaseElement::new(17704314310866354161),
+        BaseElement::new(15988800480645163458),
+        BaseElement::new(5818851986787837003),
+        BaseElement::new(2578102338873304736),
+    ],
+];
+
+const ARK_INTERNAL: [BaseElement; NUM_INTERNAL_ROUNDS] = [
+    BaseElement::new(5395176197344543510),
+    BaseElement::new(4555032575628627551),
+    BaseElement::new(10887434669785806501),
+    BaseElement::new(12415218859476220947),
+    BaseElement::new(323544930728360053),
+    BaseElement::new(13821005335130766955),
+    BaseElement::new(8112988184280322821),
+    BaseElement::new(16331987863400672412),
+    BaseElement::new(16093634485870170562),
+    BaseElement::new(2038954051047328382),
+    BaseElement::new(17917233579925756683),
+    BaseElement::new(5480704578777097169),
+    BaseElement::new(741424706267005090),
+    BaseElement::new(7872218736019578342),
+    BaseElement::new(940187017142450256),
+    BaseElement::new(3242363036477934858),
+    BaseElement::new(15415646525902701306),
+    BaseElement::new(10673647621461954174),
+    BaseElement::new(10043771903993878423),
+    BaseElement::new(5731056810399963425),
+    BaseElement::new(12331335364636844807),
+    BaseElement::new(6376995477333092352),
+];
@@ -0,0 +1,192 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use proptest::prelude::*;
+use rand_utils::{rand_array, rand_value};
+
+use super::{
+    apply_external_linear_layer, apply_internal_linear_layer, BaseElement, ElementDigest,
+    ElementHasher, FieldElement, Hasher, Poseidon2_64_256, StarkField, ALPHA, INTERNAL_DIAGONAL,
+    STATE_WIDTH,
+};
+
+#[test]
+fn test_sbox() {
+    let state: [BaseElement; STATE_WIDTH] = rand_array();
+
+    let mut expected = state;
+    expected.iter_mut().for_each(|v| *v = v.exp(ALPHA));
+
+    let mut actual = state;
+    actual.iter_mut().for_each(|v| *v = v.exp7());
+
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn apply_permutation() {
+    let mut state: [BaseElement; STATE_WIDTH] =
+        core::array::from_fn(|i| BaseElement::new(i as u64));
+
+    Poseidon2_64_256::apply_permutation(&mut state);
+
+    // expected values are obtained by executing a reference Python implementation of the
+    // permutation instantiated with the same parameters
+    let expected = [
+        BaseElement::new(17914814668947201430),
+        BaseElement::new(4357540782408064166),
+        BaseElement::new(5536908563154878382),
+        BaseElement::new(7910015063408121529),
+        BaseElement::new(3172146256387650932),
+        BaseElement::new(7585816110612492399),
+        BaseElement::new(17109513073566791157),
+        BaseElement::new(7422132764325801676),
+        BaseElement::new(1848547975211368208),
+        BaseElement::new(10798242003113952785),
+        BaseElement::new(4835274280476973175),
+        BaseElement::new(9531922165830442576),
+    ];
+
+    assert_eq!(expected, state);
+}
+
+#[test]
+fn apply_permutation_zeros() {
+    let mut state = [BaseElement::ZERO; STATE_WIDTH];
+
+    Poseidon2_64_256::apply_permutation(&mut state);
+
+    let expected = [
+        BaseElement::new(785727700392604414),
+        BaseElement::new(13407584564939515468),
+        BaseElement::new(12479359968203397524),
+        BaseElement::new(12891963618097624569),
+        BaseElement::new(663463778367426401),
+        BaseElement::new(8476874531800113519),
+        BaseElement::new(14972040305156144418),
+        BaseElement::new(17797582717740216237),
+        BaseElement::new(10760852076963967748),
+        BaseElement::new(9821324994967021461),
+        BaseElement::new(2710964872922052039),
+        BaseElement::new(16583116736830254858),
+    ];
+
+    assert_eq!(expected, state);
+}
+
+#[test]
+fn hash_elements_vs_merge() {
+    let elements: [BaseElement; 8] = rand_array();
+
+    let digests: [ElementDigest; 2] = [
+        ElementDigest::new(elements[..4].try_into().unwrap()),
+        ElementDigest::new(elements[4..].try_into().unwrap()),
+    ];
+
+    let m_result = Poseidon2_64_256::merge(&digests);
+    let h_result = Poseidon2_64_256::hash_elements(&elements);
+    assert_eq!(m_result, h_result);
+}
+
+#[test]
+fn merge_vs_merge_many() {
+    let elements: [BaseElement; 8] = rand_array();
+
+    let digests: [ElementDigest; 2] = [
+        ElementDigest::new(elements[..4].try_into().unwrap()),
+        ElementDigest::new(elements[4..].try_into().unwrap()),
+    ];
+
+    let m_result = Poseidon2_64_256::merge(&digests);
+    let h_result = Poseidon2_64_256::merge_many(&digests);
+    assert_eq!(m_result, h_result);
+}
+
+#[test]
+fn hash_elements_vs_merge_with_int() {
+    let seed = ElementDigest::new(rand_array());
+
+    // ----- value fits into a field element ------------------------------------------------------
+    let val: BaseElement = rand_value();
+    let m_result = Poseidon2_64_256::merge_with_int(seed, val.as_int());
+
+    let mut elements = seed.as_elements().to_vec();
+    elements.push(val);
+    let h_result = Poseidon2_64_256::hash_elements(&elements);
+
+    assert_eq!(m_result, h_result);
+
+    // ----- value does not fit into a field element ----------------------------------------------
+    let val = BaseElement::MODULUS + 2;
+    let m_result = Poseidon2_64_256::merge_with_int(seed, val);
+
+    let mut elements = seed.as_elements().to_vec();
+    elements.push(BaseElement::new(val));
+    elements.push(BaseElement::new(1));
+    let h_result = Poseidon2_64_256::hash_elements(&elements);
+
+    assert_eq!(m_result, h_result);
+}
+
+#[test]
+fn hash_padding() {
+    // adding a zero bytes at the end of a byte string should result in a different hash
+    let r1 = Poseidon2_64_256::hash(&[1_u8, 2, 3]);
+    let r2 = Poseidon2_64_256::hash(&[1_u8, 2, 3, 0]);
+    assert_ne!(r1, r2);
+
+    // same as above but with input splitting over two elements
+    let r1 = Poseidon2_64_256::hash(&[1_u8, 2, 3, 4, 5, 6, 7]);
+    let r2 = Poseidon2_64_256::hash(&[1_u8, 2, 3, 4, 5, 6, 7, 0]);
+    assert_ne!(r1, r2);
+}
+
+#[test]
+fn hash_elements_padding() {
+    let e1: [BaseElement; 2] = rand_array();
+    let e2 = [e1[0], e1[1], BaseElement::ZERO];
+
+    let r1 = Poseidon2_64_256::hash_elements(&e1);
+    let r2 = Poseidon2_64_256::hash_elements(&e2);
+    assert_ne!(r1, r2);
+}
+
+proptest! {
+    #[test]
+    fn external_linear_layer_proptest(a in any::<[u64; STATE_WIDTH]>()) {
+        let mut v1 = a.map(BaseElement::new);
+        apply_external_linear_layer(&mut v1);
+
+        // the external matrix is circ(2 * M4, M4, M4)
+        let m4 = [[5_u64, 7, 1, 3], [4, 6, 1, 1], [1, 3, 5, 7], [1, 1, 4, 6]];
+        let mut v2 = [BaseElement::ZERO; STATE_WIDTH];
+        for (i, r) in v2.iter_mut().enumerate() {
+            for (j, &s) in a.iter().enumerate() {
+                let m = BaseElement::new(m4[i % 4][j % 4]);
+                let m = if i / 4 == j / 4 { m.double() } else { m };
+                *r += m * BaseElement::new(s);
+            }
+        }
+
+        prop_assert_eq!(v1, v2);
+    }
+
+    #[test]
+    fn internal_linear_layer_proptest(a in any::<[u64; STATE_WIDTH]>()) {
+        let mut v1 = a.map(BaseElement::new);
+        apply_internal_linear_layer(&mut v1);
+
+        // the internal matrix is 1 + diag(INTERNAL_DIAGONAL)
+        let mut v2 = [BaseElement::ZERO; STATE_WIDTH];
+        for (i, r) in v2.iter_mut().enumerate() {
+            for (j, &s) in a.iter().enumerate() {
+                let m = if i == j { INTERNAL_DIAGONAL[i] + BaseElement::ONE } else { BaseElement::ONE };
+                *r += m * BaseElement::new(s);
+            }
+        }
+
+        prop_assert_eq!(v1, v2);
+    }
+}
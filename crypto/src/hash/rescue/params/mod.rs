@@ -0,0 +1,388 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use math::{fields::f64::BaseElement, FieldElement, StarkField};
+
+use super::Rp64_256;
+use crate::{errors::RescueParamsError, hash_to_field};
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Domain separation tag used when deriving round constants in [RescueParams::generate()].
+const ROUND_CONSTANTS_DST: &[u8] = b"winterfell/rescue/round-constants/v1";
+
+// RESCUE PARAMETERS
+// ================================================================================================
+
+/// Parameters of a Rescue Prime permutation supplied at runtime.
+///
+/// The permutation operates on a state of `state_width` elements; the first `capacity` elements
+/// of the state form the capacity portion of the sponge, and the remaining elements form the rate
+/// portion. Each round of the permutation is computed as in `Rp64_256`: the S-Box x^alpha, the
+/// MDS matrix, the first set of round constants, the inverse S-Box x^(1/alpha), the MDS matrix,
+/// and the second set of round constants.
+///
+/// Parameters can be either provided explicitly via [RescueParams::new()] (e.g., to match an
+/// external specification), or derived deterministically from a seed via
+/// [RescueParams::generate()].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RescueParams<B: StarkField> {
+    state_width: usize,
+    capacity: usize,
+    alpha: u64,
+    inv_alpha: u64,
+    mds: Vec<Vec<B>>,
+    ark1: Vec<Vec<B>>,
+    ark2: Vec<Vec<B>>,
+}
+
+impl<B: StarkField<PositiveInteger = u64>> RescueParams<B> {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns new Rescue parameters built from the provided values.
+    ///
+    /// The number of rounds is defined by the number of rows in `ark1` and `ark2`. The MDS
+    /// property of `mds` is not checked; the caller is responsible for providing a matrix with
+    /// this property.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * `capacity` is zero, or is not smaller than `state_width`.
+    /// * `alpha` is smaller than 3, or is not co-prime with p - 1 where p is the field modulus.
+    /// * `mds` is not a `state_width` x `state_width` matrix.
+    /// * `ark1` and `ark2` are empty, have different numbers of rows, or contain rows with the
+    ///   number of elements different from `state_width`.
+    pub fn new(
+        state_width: usize,
+        capacity: usize,
+        alpha: u64,
+        mds: Vec<Vec<B>>,
+        ark1: Vec<Vec<B>>,
+        ark2: Vec<Vec<B>>,
+    ) -> Result<Self, RescueParamsError> {
+        if capacity == 0 || capacity >= state_width {
+            return Err(RescueParamsError::InvalidCapacity(capacity, state_width));
+        }
+        let inv_alpha = get_inv_alpha::<B>(alpha)?;
+        if mds.len() != state_width || mds.iter().any(|row| row.len() != state_width) {
+            return Err(RescueParamsError::InvalidMdsMatrix(state_width));
+        }
+        if ark1.is_empty()
+            || ark1.len() != ark2.len()
+            || ark1.iter().chain(ark2.iter()).any(|row| row.len() != state_width)
+        {
+            return Err(RescueParamsError::InvalidRoundConstants(state_width));
+        }
+
+        Ok(Self {
+            state_width,
+            capacity,
+            alpha,
+            inv_alpha,
+            mds,
+            ark1,
+            ark2,
+        })
+    }
+
+    /// Returns new Rescue parameters derived deterministically from the provided `seed`.
+    ///
+    /// The parameters are generated as follows:
+    /// * The S-Box power is set to the smallest alpha >= 3 which is co-prime with p - 1.
+    /// * The MDS matrix is set to the Cauchy matrix with M_ij = 1 / (i - (state_width + j)); every
+    ///   square sub-matrix of a Cauchy matrix is non-singular, and thus, the matrix is MDS.
+    /// * Round constants are derived from `seed` and all other parameters using
+    ///   [hash_to_field()](crate::hash_to_field), and thus, are indistinguishable from random.
+    ///
+    /// The number of rounds is not derived automatically: it must be chosen according to the
+    /// security analysis in the Rescue Prime [specifications](https://eprint.iacr.org/2020/1143.pdf)
+    /// for the target security level, the field, and the state width.
+    ///
+    /// # Errors
+    /// Returns an error if `capacity` is zero or is not smaller than `state_width`, if
+    /// `num_rounds` is zero, or if the round constants could not be derived.
+    pub fn generate(
+        state_width: usize,
+        capacity: usize,
+        num_rounds: usize,
+        seed: &[u8],
+    ) -> Result<Self, RescueParamsError> {
+        if capacity == 0 || capacity >= state_width {
+            return Err(RescueParamsError::InvalidCapacity(capacity, state_width));
+        }
+        if num_rounds == 0 {
+            return Err(RescueParamsError::InvalidRoundConstants(state_width));
+        }
+
+        let alpha = get_min_alpha::<B>();
+        let mds = build_cauchy_matrix::<B>(state_width);
+
+        // derive the constants of every round separately so that the number of bytes drawn from
+        // the XOF in a single call does not depend on the number of rounds
+        let mut ark1 = Vec::with_capacity(num_rounds);
+        let mut ark2 = Vec::with_capacity(num_rounds);
+        for round in 0..num_rounds {
+            let mut msg = seed.to_vec();
+            for value in [state_width, capacity, num_rounds, round] {
+                msg.extend_from_slice(&(value as u64).to_le_bytes());
+            }
+            msg.extend_from_slice(&alpha.to_le_bytes());
+
+            let constants = hash_to_field::<B>(&msg, ROUND_CONSTANTS_DST, 2 * state_width)?;
+            ark1.push(constants[..state_width].to_vec());
+            ark2.push(constants[state_width..].to_vec());
+        }
+
+        Self::new(state_width, capacity, alpha, mds, ark1, ark2)
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of elements in the permutation state.
+    pub fn state_width(&self) -> usize {
+        self.state_width
+    }
+
+    /// Returns the range of state elements forming the capacity portion of the sponge.
+    pub fn capacity_range(&self) -> Range<usize> {
+        0..self.capacity
+    }
+
+    /// Returns the range of state elements forming the rate portion of the sponge.
+    pub fn rate_range(&self) -> Range<usize> {
+        self.capacity..self.state_width
+    }
+
+    /// Returns the range of state elements from which hash outputs are read; these are the first
+    /// capacity-many elements of the rate portion of the state (or the entire rate portion if it
+    /// is smaller than the capacity).
+    pub fn digest_range(&self) -> Range<usize> {
+        let rate_width = self.state_width - self.capacity;
+        self.capacity..self.capacity + self.capacity.min(rate_width)
+    }
+
+    /// Returns the number of rounds of the permutation.
+    pub fn num_rounds(&self) -> usize {
+        self.ark1.len()
+    }
+
+    /// Returns the S-Box power.
+    pub fn alpha(&self) -> u64 {
+        self.alpha
+    }
+
+    /// Returns the inverse S-Box power, i.e., alpha^-1 mod (p - 1).
+    pub fn inv_alpha(&self) -> u64 {
+        self.inv_alpha
+    }
+
+    /// Returns the MDS matrix.
+    pub fn mds(&self) -> &[Vec<B>] {
+        &self.mds
+    }
+
+    /// Returns round constants added to the state in the first half of every round.
+    pub fn ark1(&self) -> &[Vec<B>] {
+        &self.ark1
+    }
+
+    /// Returns round constants added to the state in the second half of every round.
+    pub fn ark2(&self) -> &[Vec<B>] {
+        &self.ark2
+    }
+}
+
+impl RescueParams<BaseElement> {
+    /// Returns the parameters of [Rp64_256]; a permutation built from them is identical to
+    /// [Rp64_256::apply_permutation()].
+    pub fn rp64_256() -> Self {
+        let to_rows = |matrix: &[[BaseElement; Rp64_256::STATE_WIDTH]]| {
+            matrix.iter().map(|row| row.to_vec()).collect::<Vec<_>>()
+        };
+        Self::new(
+            Rp64_256::STATE_WIDTH,
+            Rp64_256::CAPACITY_RANGE.len(),
+            7,
+            to_rows(&Rp64_256::MDS),
+            to_rows(&Rp64_256::ARK1),
+            to_rows(&Rp64_256::ARK2),
+        )
+        .expect("Rp64_256 parameters must be valid")
+    }
+}
+
+// RESCUE PERMUTATION
+// ================================================================================================
+
+/// Rescue Prime permutation and sponge instantiated with [RescueParams] supplied at runtime.
+///
+/// The sponge follows the construction of `Rp64_256`: the first capacity element is initialized
+/// to the number of elements to be hashed, elements are added to the rate portion of the state,
+/// and the output is read from the first capacity-many elements of the rate. Thus, with
+/// [RescueParams::rp64_256()] parameters, [RescuePermutation::hash_elements()] returns the same
+/// digests as `Rp64_256`.
+///
+/// Wider permutations can be used to compress more digests with a single permutation; e.g., with
+/// a state width of 36 and a capacity of 4, 8 digests of 4 elements each fit into the rate, which
+/// makes a single permutation sufficient for every node of an 8-ary Merkle tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RescuePermutation<B: StarkField> {
+    params: RescueParams<B>,
+}
+
+impl<B: StarkField<PositiveInteger = u64>> RescuePermutation<B> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new permutation instantiated with the provided parameters.
+    pub fn new(params: RescueParams<B>) -> Self {
+        Self { params }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the parameters of this permutation.
+    pub fn params(&self) -> &RescueParams<B> {
+        &self.params
+    }
+
+    // PERMUTATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Applies the permutation to the provided state.
+    ///
+    /// # Panics
+    /// Panics if the length of `state` is not equal to the state width of the permutation.
+    pub fn apply_permutation(&self, state: &mut [B]) {
+        assert_eq!(state.len(), self.params.state_width, "invalid state length");
+        for round in 0..self.params.num_rounds() {
+            self.apply_round(state, round);
+        }
+    }
+
+    /// Applies the specified round of the permutation to the provided state.
+    ///
+    /// # Panics
+    /// Panics if the length of `state` is not equal to the state width of the permutation, or if
+    /// `round` is not smaller than the number of rounds.
+    pub fn apply_round(&self, state: &mut [B], round: usize) {
+        assert_eq!(state.len(), self.params.state_width, "invalid state length");
+
+        // apply first half of Rescue round
+        state.iter_mut().for_each(|s| *s = s.exp(self.params.alpha));
+        self.apply_mds(state);
+        state.iter_mut().zip(&self.params.ark1[round]).for_each(|(s, &k)| *s += k);
+
+        // apply second half of Rescue round
+        state.iter_mut().for_each(|s| *s = s.exp(self.params.inv_alpha));
+        self.apply_mds(state);
+        state.iter_mut().zip(&self.params.ark2[round]).for_each(|(s, &k)| *s += k);
+    }
+
+    // SPONGE
+    // --------------------------------------------------------------------------------------------
+
+    /// Hashes the provided elements and returns the digest read from
+    /// [RescueParams::digest_range()].
+    ///
+    /// Several digests can be merged (e.g., to compute a node of a k-ary Merkle tree) by hashing
+    /// their concatenated elements; if they fit into the rate portion of the state, this requires
+    /// a single permutation.
+    pub fn hash_elements<E: FieldElement<BaseField = B>>(&self, elements: &[E]) -> Vec<B> {
+        let elements = E::slice_as_base_elements(elements);
+        let rate_range = self.params.rate_range();
+
+        let mut state = vec![B::ZERO; self.params.state_width];
+        state[0] = B::try_from(elements.len() as u64)
+            .ok()
+            .expect("number of elements must fit into a field element");
+
+        let mut i = 0;
+        for &element in elements.iter() {
+            state[rate_range.start + i] += element;
+            i += 1;
+            if i == rate_range.len() {
+                self.apply_permutation(&mut state);
+                i = 0;
+            }
+        }
+        if i > 0 {
+            self.apply_permutation(&mut state);
+        }
+
+        state[self.params.digest_range()].to_vec()
+    }
+
+    // HELPER FUNCTIONS
+    // --------------------------------------------------------------------------------------------
+
+    fn apply_mds(&self, state: &mut [B]) {
+        let result: Vec<B> = self
+            .params
+            .mds
+            .iter()
+            .map(|row| row.iter().zip(state.iter()).fold(B::ZERO, |acc, (&m, &s)| acc + m * s))
+            .collect();
+        state.copy_from_slice(&result);
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns alpha^-1 mod (p - 1).
+///
+/// # Errors
+/// Returns an error if alpha is smaller than 3 or is not co-prime with p - 1.
+fn get_inv_alpha<B: StarkField<PositiveInteger = u64>>(
+    alpha: u64,
+) -> Result<u64, RescueParamsError> {
+    let order = B::MODULUS - 1;
+    if alpha < 3 {
+        return Err(RescueParamsError::InvalidAlpha(alpha));
+    }
+
+    // extended Euclidean algorithm over (alpha, p - 1)
+    let (mut r0, mut r1) = (order as i128, (alpha % order) as i128);
+    let (mut t0, mut t1) = (0i128, 1i128);
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (t0, t1) = (t1, t0 - q * t1);
+    }
+    if r0 != 1 {
+        return Err(RescueParamsError::InvalidAlpha(alpha));
+    }
+
+    Ok(t0.rem_euclid(order as i128) as u64)
+}
+
+/// Returns the smallest alpha >= 3 which is co-prime with p - 1.
+fn get_min_alpha<B: StarkField<PositiveInteger = u64>>() -> u64 {
+    (3..)
+        .find(|&alpha| get_inv_alpha::<B>(alpha).is_ok())
+        .expect("p - 1 must have a co-prime")
+}
+
+/// Returns a `width` x `width` Cauchy matrix with M_ij = 1 / (x_i - y_j), where x_i = i and
+/// y_j = width + j.
+fn build_cauchy_matrix<B: StarkField>(width: usize) -> Vec<Vec<B>> {
+    (0..width)
+        .map(|i| {
+            let x = B::from(i as u32);
+            (0..width).map(|j| (x - B::from((width + j) as u32)).inv()).collect()
+        })
+        .collect()
+}
@@ -0,0 +1,101 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::vec::Vec;
+
+use math::{fields::f64::BaseElement, FieldElement};
+use rand_utils::rand_array;
+
+use super::{RescueParams, RescuePermutation};
+use crate::{hash::rescue::Rp64_256, ElementHasher, RescueParamsError};
+
+#[test]
+fn rp64_256_params() {
+    let params = RescueParams::rp64_256();
+    assert_eq!(10540996611094048183, params.inv_alpha());
+    assert_eq!(Rp64_256::DIGEST_RANGE, params.digest_range());
+    let permutation = RescuePermutation::new(params);
+
+    // the runtime permutation is identical to the permutation with baked-in constants
+    let mut state1: [BaseElement; 12] = rand_array();
+    let mut state2 = state1;
+    permutation.apply_permutation(&mut state1);
+    Rp64_256::apply_permutation(&mut state2);
+    assert_eq!(state1, state2);
+
+    // and so are the sponges built from them
+    for num_elements in [0u64, 1, 8, 11, 16] {
+        let elements = (0..num_elements).map(BaseElement::new).collect::<Vec<_>>();
+        let expected = Rp64_256::hash_elements(&elements);
+        assert_eq!(expected.as_elements().to_vec(), permutation.hash_elements(&elements));
+    }
+}
+
+#[test]
+fn generated_params() {
+    let params = RescueParams::<BaseElement>::generate(12, 4, 7, b"seed").unwrap();
+    assert_eq!(7, params.alpha());
+    assert_eq!(7, params.num_rounds());
+    assert_eq!(
+        BaseElement::ONE,
+        BaseElement::new(3).exp(params.alpha()).exp(params.inv_alpha()) * BaseElement::new(3).inv()
+    );
+
+    // generation is deterministic and depends on the seed
+    assert_eq!(params, RescueParams::generate(12, 4, 7, b"seed").unwrap());
+    assert_ne!(params.ark1(), RescueParams::generate(12, 4, 7, b"other").unwrap().ark1());
+
+    // the generated matrix is invertible
+    let permutation = RescuePermutation::new(params);
+    let digest1 = permutation.hash_elements(&[BaseElement::ONE, BaseElement::ZERO]);
+    let digest2 = permutation.hash_elements(&[BaseElement::ZERO, BaseElement::ONE]);
+    assert_eq!(4, digest1.len());
+    assert_ne!(digest1, digest2);
+}
+
+#[test]
+fn wide_permutation() {
+    // 8 digests of 4 elements fit into the rate of a 36-element state; this is enough to hash
+    // the children of a node in an 8-ary Merkle tree with a single permutation
+    let params = RescueParams::<BaseElement>::generate(36, 4, 7, b"8-ary").unwrap();
+    assert_eq!(4..36, params.rate_range());
+    let permutation = RescuePermutation::new(params);
+
+    let children: [BaseElement; 32] = rand_array();
+    let mut state = vec![BaseElement::ZERO; 36];
+    state[0] = BaseElement::new(32);
+    state[4..].copy_from_slice(&children);
+    permutation.apply_permutation(&mut state);
+    assert_eq!(state[4..8].to_vec(), permutation.hash_elements(&children));
+}
+
+#[test]
+fn invalid_params() {
+    assert_eq!(
+        Err(RescueParamsError::InvalidCapacity(12, 12)),
+        RescueParams::<BaseElement>::generate(12, 12, 7, b"seed")
+    );
+    assert_eq!(
+        Err(RescueParamsError::InvalidRoundConstants(12)),
+        RescueParams::<BaseElement>::generate(12, 4, 0, b"seed")
+    );
+
+    // 3 and 5 divide p - 1 for the 64-bit field
+    let rp = RescueParams::rp64_256();
+    let mds = rp.mds().to_vec();
+    let ark = rp.ark1().to_vec();
+    assert_eq!(
+        Err(RescueParamsError::InvalidAlpha(5)),
+        RescueParams::new(12, 4, 5, mds.clone(), ark.clone(), ark.clone())
+    );
+    assert_eq!(
+        Err(RescueParamsError::InvalidMdsMatrix(12)),
+        RescueParams::new(12, 4, 7, mds[..11].to_vec(), ark.clone(), ark.clone())
+    );
+    assert_eq!(
+        Err(RescueParamsError::InvalidRoundConstants(12)),
+        RescueParams::new(12, 4, 7, mds, ark.clone(), ark[..6].to_vec())
+    );
+}
@@ -8,7 +8,8 @@ use core::slice;
 use math::{fields::f62::BaseElement, StarkField};
 use utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
 
-use super::{Digest, DIGEST_SIZE};
+use super::{Digest, Rp62_248, DIGEST_SIZE};
+use crate::hash::{ByteDigest, Hasher};
 
 // DIGEST TRAIT IMPLEMENTATIONS
 // ================================================================================================
@@ -80,6 +81,15 @@ impl Deserializable for ElementDigest {
     }
 }
 
+/// Converts a byte digest (e.g., a digest of BLAKE3) into an element digest by hashing its bytes
+/// with [Rp62_248]. This allows commitments built using a byte-oriented hash function to be absorbed
+/// into a random coin which uses [Rp62_248].
+impl<const N: usize> From<ByteDigest<N>> for ElementDigest {
+    fn from(value: ByteDigest<N>) -> Self {
+        Rp62_248::hash(&value.0)
+    }
+}
+
 // TESTS
 // ================================================================================================
 
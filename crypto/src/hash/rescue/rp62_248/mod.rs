@@ -3,9 +3,11 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use core::ops::Range;
+
 use math::{fields::f62::BaseElement, FieldElement, StarkField};
 
-use super::{exp_acc, Digest, ElementHasher, Hasher};
+use super::{exp_acc, AlgebraicSponge, Digest, ElementHasher, Hasher};
 
 mod digest;
 pub use digest::ElementDigest;
@@ -232,6 +234,20 @@ impl ElementHasher for Rp62_248 {
     }
 }
 
+impl AlgebraicSponge for Rp62_248 {
+    const STATE_WIDTH: usize = STATE_WIDTH;
+    const RATE_RANGE: Range<usize> = 0..RATE_WIDTH;
+    const CAPACITY_RANGE: Range<usize> = RATE_WIDTH..STATE_WIDTH;
+
+    fn permute(state: &mut [BaseElement]) {
+        apply_permutation(state.try_into().expect("invalid state width"));
+    }
+
+    fn digest_elements(digest: &Self::Digest) -> &[BaseElement] {
+        digest.as_elements()
+    }
+}
+
 // RESCUE PERMUTATION
 // ================================================================================================
 
@@ -7,7 +7,10 @@ use core::ops::Range;
 
 use math::{fields::f64::BaseElement, FieldElement, StarkField};
 
-use super::{super::mds::mds_f64_12x12::mds_multiply, exp_acc, Digest, ElementHasher, Hasher};
+use super::{
+    super::mds::mds_f64_12x12::mds_multiply, exp_acc, AlgebraicSponge, Digest, ElementHasher,
+    Hasher,
+};
 
 mod digest;
 pub use digest::ElementDigest;
@@ -46,9 +49,8 @@ const NUM_ROUNDS: usize = 7;
 /// S-Box and Inverse S-Box powers;
 /// computed using algorithm 6 from <https://eprint.iacr.org/2020/1143.pdf>
 ///
-/// The constants are defined for tests only because the exponentiations in the code are unrolled
-/// for efficiency reasons.
-#[cfg(test)]
+/// The inverse S-Box power is defined for tests only because the exponentiation in the code is
+/// unrolled for efficiency reasons.
 const ALPHA: u64 = 7;
 #[cfg(test)]
 const INV_ALPHA: u64 = 10540996611094048183;
@@ -258,6 +260,20 @@ impl ElementHasher for Rp64_256 {
     }
 }
 
+impl AlgebraicSponge for Rp64_256 {
+    const STATE_WIDTH: usize = STATE_WIDTH;
+    const RATE_RANGE: Range<usize> = RATE_RANGE;
+    const CAPACITY_RANGE: Range<usize> = CAPACITY_RANGE;
+
+    fn permute(state: &mut [BaseElement]) {
+        Self::apply_permutation(state.try_into().expect("invalid state width"));
+    }
+
+    fn digest_elements(digest: &Self::Digest) -> &[BaseElement] {
+        digest.as_elements()
+    }
+}
+
 // HASH FUNCTION IMPLEMENTATION
 // ================================================================================================
 
@@ -319,6 +335,54 @@ impl Rp64_256 {
         Self::add_constants(state, &ARK2[round]);
     }
 
+    // CONSTRAINT EVALUATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Evaluates constraints for a single Rescue-XLIX round applied to the `current` state and
+    /// resulting in the `next` state; `ark1` and `ark2` are the round constants for the round.
+    ///
+    /// This can be used to enforce the round function inside an AIR (e.g., with round constants
+    /// supplied via periodic columns). Since the inverse S-Box has a very high degree, the round
+    /// is checked by comparing the result of applying the first half of the round to `current`
+    /// with the result of undoing the second half of the round on `next`. Thus, all constraints
+    /// have degree 7, and all values written into `result` are zeros if and only if `next` is
+    /// the result of applying the round to `current`.
+    ///
+    /// # Panics
+    /// Panics if any of the slices does not have exactly 12 elements.
+    pub fn evaluate_round_constraints<E>(
+        current: &[E],
+        next: &[E],
+        ark1: &[E],
+        ark2: &[E],
+        result: &mut [E],
+    ) where
+        E: FieldElement + From<BaseElement>,
+    {
+        assert_eq!(current.len(), STATE_WIDTH, "invalid current state length");
+        assert_eq!(next.len(), STATE_WIDTH, "invalid next state length");
+        assert_eq!(ark1.len(), STATE_WIDTH, "invalid round constants length");
+        assert_eq!(ark2.len(), STATE_WIDTH, "invalid round constants length");
+        assert_eq!(result.len(), STATE_WIDTH, "invalid result length");
+
+        // apply the first half of the round to the current state
+        let mut step1 = [E::ZERO; STATE_WIDTH];
+        step1.iter_mut().zip(current).for_each(|(s, &c)| *s = c.exp(ALPHA.into()));
+        let mut step1 = matrix_multiply(&MDS, &step1);
+        step1.iter_mut().zip(ark1).for_each(|(s, &k)| *s += k);
+
+        // undo the second half of the round on the next state
+        let mut step2 = [E::ZERO; STATE_WIDTH];
+        step2.iter_mut().zip(next.iter().zip(ark2)).for_each(|(s, (&n, &k))| *s = n - k);
+        let mut step2 = matrix_multiply(&INV_MDS, &step2);
+        step2.iter_mut().for_each(|s| *s = s.exp(ALPHA.into()));
+
+        result
+            .iter_mut()
+            .zip(step1.iter().zip(step2.iter()))
+            .for_each(|(r, (&a, &b))| *r = a - b);
+    }
+
     // HELPER FUNCTIONS
     // --------------------------------------------------------------------------------------------
 
@@ -385,6 +449,22 @@ impl Rp64_256 {
     }
 }
 
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Multiplies the provided state by the specified matrix over the field `E`.
+#[inline(always)]
+fn matrix_multiply<E: FieldElement + From<BaseElement>>(
+    matrix: &[[BaseElement; STATE_WIDTH]; STATE_WIDTH],
+    state: &[E; STATE_WIDTH],
+) -> [E; STATE_WIDTH] {
+    let mut result = [E::ZERO; STATE_WIDTH];
+    for (r, row) in result.iter_mut().zip(matrix) {
+        *r = row.iter().zip(state).fold(E::ZERO, |acc, (&m, &s)| acc + E::from(m) * s);
+    }
+    result
+}
+
 // MDS
 // ================================================================================================
 /// Rescue MDS matrix
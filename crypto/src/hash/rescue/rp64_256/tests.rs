@@ -3,6 +3,7 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use math::fields::QuadExtension;
 use proptest::prelude::*;
 use rand_utils::{rand_array, rand_value};
 
@@ -104,6 +105,50 @@ fn apply_permutation() {
     assert_eq!(expected, state);
 }
 
+#[test]
+fn round_constraints() {
+    for round in 0..Rp64_256::NUM_ROUNDS {
+        let current: [BaseElement; STATE_WIDTH] = rand_array();
+        let mut next = current;
+        Rp64_256::apply_round(&mut next, round);
+
+        // constraints are satisfied for a valid round transition
+        let mut result = [BaseElement::ZERO; STATE_WIDTH];
+        Rp64_256::evaluate_round_constraints(
+            &current,
+            &next,
+            &Rp64_256::ARK1[round],
+            &Rp64_256::ARK2[round],
+            &mut result,
+        );
+        assert_eq!([BaseElement::ZERO; STATE_WIDTH], result);
+
+        // constraints can be evaluated over an extension field as well
+        let current_ext = current.map(QuadExtension::<BaseElement>::from);
+        let next_ext = next.map(QuadExtension::<BaseElement>::from);
+        let mut result_ext = [QuadExtension::<BaseElement>::ZERO; STATE_WIDTH];
+        Rp64_256::evaluate_round_constraints(
+            &current_ext,
+            &next_ext,
+            &Rp64_256::ARK1[round].map(QuadExtension::from),
+            &Rp64_256::ARK2[round].map(QuadExtension::from),
+            &mut result_ext,
+        );
+        assert_eq!([QuadExtension::<BaseElement>::ZERO; STATE_WIDTH], result_ext);
+
+        // constraints are not satisfied if the next state is modified
+        next[3] += BaseElement::ONE;
+        Rp64_256::evaluate_round_constraints(
+            &current,
+            &next,
+            &Rp64_256::ARK1[round],
+            &Rp64_256::ARK2[round],
+            &mut result,
+        );
+        assert_ne!([BaseElement::ZERO; STATE_WIDTH], result);
+    }
+}
+
 #[test]
 fn hash_elements_vs_merge() {
     let elements: [BaseElement; 8] = rand_array();
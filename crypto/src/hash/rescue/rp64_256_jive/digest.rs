@@ -8,7 +8,8 @@ use core::slice;
 use math::fields::f64::BaseElement;
 use utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
 
-use super::{Digest, DIGEST_SIZE};
+use super::{Digest, RpJive64_256, DIGEST_SIZE};
+use crate::hash::{ByteDigest, Hasher};
 
 // DIGEST TRAIT IMPLEMENTATIONS
 // ================================================================================================
@@ -87,6 +88,15 @@ impl From<ElementDigest> for [u8; 32] {
     }
 }
 
+/// Converts a byte digest (e.g., a digest of BLAKE3) into an element digest by hashing its bytes
+/// with [RpJive64_256]. This allows commitments built using a byte-oriented hash function to be absorbed
+/// into a random coin which uses [RpJive64_256].
+impl<const N: usize> From<ByteDigest<N>> for ElementDigest {
+    fn from(value: ByteDigest<N>) -> Self {
+        RpJive64_256::hash(&value.0)
+    }
+}
+
 // TESTS
 // ================================================================================================
 
@@ -7,7 +7,9 @@ use core::ops::Range;
 
 use math::{fields::f64::BaseElement, FieldElement, StarkField};
 
-use super::{super::mds::mds_f64_8x8::mds_multiply, exp_acc, Digest, ElementHasher, Hasher};
+use super::{
+    super::mds::mds_f64_8x8::mds_multiply, exp_acc, AlgebraicSponge, Digest, ElementHasher, Hasher,
+};
 
 mod digest;
 pub use digest::ElementDigest;
@@ -273,6 +275,20 @@ impl ElementHasher for RpJive64_256 {
     }
 }
 
+impl AlgebraicSponge for RpJive64_256 {
+    const STATE_WIDTH: usize = STATE_WIDTH;
+    const RATE_RANGE: Range<usize> = RATE_RANGE;
+    const CAPACITY_RANGE: Range<usize> = CAPACITY_RANGE;
+
+    fn permute(state: &mut [BaseElement]) {
+        Self::apply_permutation(state.try_into().expect("invalid state width"));
+    }
+
+    fn digest_elements(digest: &Self::Digest) -> &[BaseElement] {
+        digest.as_elements()
+    }
+}
+
 // HASH FUNCTION IMPLEMENTATION
 // ================================================================================================
 
@@ -11,6 +11,9 @@ use utils::ByteWriter;
 
 use super::{ByteDigest, ElementHasher, Hasher};
 
+#[cfg(test)]
+mod tests;
+
 // SHA3 WITH 256-BIT OUTPUT
 // ================================================================================================
 
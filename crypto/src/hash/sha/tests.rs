@@ -0,0 +1,56 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::vec::Vec;
+
+use math::{fields::f64::BaseElement, FieldElement};
+use rand_utils::rand_array;
+use utils::{Deserializable, Serializable};
+
+use super::{ElementHasher, Hasher, Sha3_256};
+use crate::hash::ByteDigest;
+
+type Sha3 = Sha3_256<BaseElement>;
+
+#[test]
+fn hash_test_vectors() {
+    // test vectors from NIST FIPS 202 examples
+    let expected = "a7ffc6f8bf1ed76651c14756a061d662f580ff4de43b49fa82d80a4b80f8434a";
+    assert_eq!(decode_hex(expected), Sha3::hash(b"").0);
+
+    let expected = "3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532";
+    assert_eq!(decode_hex(expected), Sha3::hash(b"abc").0);
+}
+
+#[test]
+fn merge_test_vector() {
+    let digest_0 = ByteDigest::read_from_bytes(&[1_u8; 32]).unwrap();
+    let digest_1 = ByteDigest::read_from_bytes(&[2_u8; 32]).unwrap();
+
+    // merging two digests is the same as hashing their concatenation
+    let expected = "adcb9583b7caa53aff2b8f4ed8aaf407399051b1b4f5a39aa2c97d5069b50657";
+    assert_eq!(decode_hex(expected), Sha3::merge(&[digest_0, digest_1]).0);
+    assert_eq!(Sha3::merge(&[digest_0, digest_1]), Sha3::merge_many(&[digest_0, digest_1]));
+}
+
+#[test]
+fn hash_elements_vs_hash() {
+    // elements of the 64-bit field are not stored in canonical form, and thus, they must be
+    // serialized before being hashed
+    let elements: [BaseElement; 4] = rand_array();
+    assert_eq!(Sha3::hash(&elements.to_bytes()), Sha3::hash_elements(&elements));
+
+    // adding a zero element at the end of a list of elements should result in a different hash
+    let padded = [elements[0], elements[1], elements[2], elements[3], BaseElement::ZERO];
+    assert_ne!(Sha3::hash_elements(&elements), Sha3::hash_elements(&padded));
+}
+
+fn decode_hex(value: &str) -> [u8; 32] {
+    let bytes = (0..value.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&value[i..i + 2], 16).unwrap())
+        .collect::<Vec<_>>();
+    bytes.try_into().unwrap()
+}
@@ -0,0 +1,138 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use core::marker::PhantomData;
+
+use math::FieldElement;
+
+use super::{ByteDigest, Digest, ElementHasher, Hasher};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Smallest supported size of a truncated digest (in bytes).
+const MIN_DIGEST_SIZE: usize = 16;
+
+/// Largest supported size of a truncated digest (in bytes).
+const MAX_DIGEST_SIZE: usize = 32;
+
+// TRUNCATED HASHER
+// ================================================================================================
+
+/// Implementation of the [Hasher](super::Hasher) trait which truncates digests of the underlying
+/// hash function `H` to `N` bytes.
+///
+/// Truncated digests reduce the size of Merkle authentication paths, and thus, the size of STARK
+/// proofs, at the expense of collision resistance: collision resistance of the truncated hash
+/// function is at most `N * 4` bits.
+///
+/// All inputs, including digests passed to [Hasher::merge()], are hashed as sequences of bytes
+/// using [Hasher::hash()] of the underlying hash function. For hash functions with digests smaller
+/// than `N` bytes, digests are padded with zeros.
+///
+/// `N` must be between 16 and 32 (both inclusive).
+pub struct Truncated<H: Hasher, const N: usize>(PhantomData<H>);
+
+impl<H: Hasher, const N: usize> Truncated<H, N> {
+    /// Causes a compile-time error when the digest size is not supported.
+    const VALID_DIGEST_SIZE: () = assert!(
+        N >= MIN_DIGEST_SIZE && N <= MAX_DIGEST_SIZE,
+        "truncated digest size must be between 16 and 32 bytes"
+    );
+
+    /// Returns the first `N` bytes of the provided digest.
+    fn truncate(digest: H::Digest) -> ByteDigest<N> {
+        #[allow(clippy::let_unit_value)]
+        let _ = Self::VALID_DIGEST_SIZE;
+        ByteDigest(digest.as_bytes()[..N].try_into().unwrap())
+    }
+}
+
+impl<H: Hasher, const N: usize> Hasher for Truncated<H, N> {
+    type Digest = ByteDigest<N>;
+
+    const COLLISION_RESISTANCE: u32 = if H::COLLISION_RESISTANCE < (N * 4) as u32 {
+        H::COLLISION_RESISTANCE
+    } else {
+        (N * 4) as u32
+    };
+
+    fn hash(bytes: &[u8]) -> Self::Digest {
+        Self::truncate(H::hash(bytes))
+    }
+
+    fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
+        Self::truncate(H::hash(ByteDigest::digests_as_bytes(values)))
+    }
+
+    fn merge_many(values: &[Self::Digest]) -> Self::Digest {
+        Self::truncate(H::hash(ByteDigest::digests_as_bytes(values)))
+    }
+
+    fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
+        let mut data = [0; MAX_DIGEST_SIZE + 8];
+        data[..N].copy_from_slice(&seed.0);
+        data[N..N + 8].copy_from_slice(&value.to_le_bytes());
+        Self::truncate(H::hash(&data[..N + 8]))
+    }
+}
+
+impl<H: ElementHasher, const N: usize> ElementHasher for Truncated<H, N> {
+    type BaseField = H::BaseField;
+
+    fn hash_elements<E: FieldElement<BaseField = Self::BaseField>>(elements: &[E]) -> Self::Digest {
+        Self::truncate(H::hash_elements(elements))
+    }
+
+    fn hash_elements_batch<E, const B: usize>(elements: [&[E]; B]) -> [Self::Digest; B]
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        H::hash_elements_batch(elements).map(Self::truncate)
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use math::fields::f128::BaseElement;
+
+    use super::{super::Blake3_256, ByteDigest, Digest, ElementHasher, Hasher, Truncated};
+
+    type Blake3 = Blake3_256<BaseElement>;
+    type Blake3_160 = Truncated<Blake3, 20>;
+
+    #[test]
+    fn truncated_collision_resistance() {
+        assert_eq!(80, Blake3_160::COLLISION_RESISTANCE);
+        assert_eq!(96, Truncated::<Blake3, 24>::COLLISION_RESISTANCE);
+        assert_eq!(128, Truncated::<Blake3, 32>::COLLISION_RESISTANCE);
+    }
+
+    #[test]
+    fn truncated_hash() {
+        let bytes = [1_u8, 2, 3, 4, 5];
+        assert_eq!(Blake3::hash(&bytes).as_bytes()[..20], Blake3_160::hash(&bytes).0);
+
+        let elements = [BaseElement::new(1), BaseElement::new(2)];
+        assert_eq!(
+            Blake3::hash_elements(&elements).as_bytes()[..20],
+            Blake3_160::hash_elements(&elements).0
+        );
+
+        // merging is equivalent to hashing concatenated digests
+        let values = [ByteDigest::new([1; 20]), ByteDigest::new([2; 20])];
+        let mut data = [1_u8; 40];
+        data[20..].fill(2);
+        assert_eq!(Blake3_160::hash(&data), Blake3_160::merge(&values));
+        assert_eq!(Blake3_160::hash(&data), Blake3_160::merge_many(&values));
+
+        let mut data = [1_u8; 28];
+        data[20..].copy_from_slice(&5_u64.to_le_bytes());
+        assert_eq!(Blake3_160::hash(&data), Blake3_160::merge_with_int(values[0], 5));
+    }
+}
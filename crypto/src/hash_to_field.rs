@@ -0,0 +1,197 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Hash-to-field utilities following [RFC 9380](https://www.rfc-editor.org/rfc/rfc9380).
+//!
+//! These functions can be used to map arbitrary byte strings (e.g., public inputs or external
+//! data) to field elements in a deterministic way which is compatible with other implementations
+//! of the RFC.
+
+use alloc::vec::Vec;
+
+use math::{FieldElement, StarkField};
+use sha3::{
+    digest::{ExtendableOutput, Update, XofReader},
+    Shake128,
+};
+
+use crate::errors::HashToFieldError;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Target security level (in bits) for hash-to-field operations; this determines how many extra
+/// bytes are drawn per element to make the bias of the modular reduction negligible.
+const SECURITY_LEVEL: u32 = 128;
+
+/// Maximum length of a domain separation tag; longer tags are hashed first as per Section 5.3.3
+/// of RFC 9380.
+const MAX_DST_LENGTH: usize = 255;
+
+/// Maximum number of bytes which can be produced by [expand_message_xof()].
+const MAX_OUTPUT_LENGTH: usize = u16::MAX as usize;
+
+/// Prefix used when hashing domain separation tags which are too long.
+const OVERSIZE_DST_PREFIX: &[u8] = b"H2C-OVERSIZE-DST-";
+
+// PUBLIC FUNCTIONS
+// ================================================================================================
+
+/// Expands the provided message into `len_in_bytes` uniformly random bytes using
+/// `expand_message_xof` with SHAKE128 as described in Section 5.3.2 of RFC 9380.
+///
+/// # Errors
+/// Returns an error if:
+/// - `dst` is empty.
+/// - `len_in_bytes` is zero or greater than 65535.
+pub fn expand_message_xof(
+    msg: &[u8],
+    dst: &[u8],
+    len_in_bytes: usize,
+) -> Result<Vec<u8>, HashToFieldError> {
+    if dst.is_empty() {
+        return Err(HashToFieldError::EmptyDomainSeparationTag);
+    }
+    if len_in_bytes == 0 || len_in_bytes > MAX_OUTPUT_LENGTH {
+        return Err(HashToFieldError::InvalidOutputLength(len_in_bytes));
+    }
+
+    // reduce the DST if it is too long; for SHAKE128, the reduced DST is 2 * k / 8 = 32 bytes
+    let mut reduced_dst = [0u8; 32];
+    let dst = if dst.len() > MAX_DST_LENGTH {
+        let mut hasher = Shake128::default();
+        hasher.update(OVERSIZE_DST_PREFIX);
+        hasher.update(dst);
+        hasher.finalize_xof().read(&mut reduced_dst);
+        &reduced_dst[..]
+    } else {
+        dst
+    };
+
+    // msg_prime = msg || I2OSP(len_in_bytes, 2) || DST || I2OSP(len(DST), 1)
+    let mut hasher = Shake128::default();
+    hasher.update(msg);
+    hasher.update(&(len_in_bytes as u16).to_be_bytes());
+    hasher.update(dst);
+    hasher.update(&[dst.len() as u8]);
+
+    let mut result = vec![0u8; len_in_bytes];
+    hasher.finalize_xof().read(&mut result);
+    Ok(result)
+}
+
+/// Hashes the provided message into `count` field elements as described in Section 5.2 of
+/// RFC 9380, using [expand_message_xof()] as the message expansion function.
+///
+/// Each base field element is derived from L = ceil((ceil(log2(p)) + 128) / 8) bytes of the
+/// expanded message interpreted as a big-endian integer and reduced modulo the field modulus p.
+/// For extension fields, each element is built from `E::EXTENSION_DEGREE` consecutive base field
+/// elements.
+///
+/// # Errors
+/// Returns an error if:
+/// - `dst` is empty.
+/// - The number of bytes needed to derive `count` elements is zero or greater than 65535.
+pub fn hash_to_field<E: FieldElement>(
+    msg: &[u8],
+    dst: &[u8],
+    count: usize,
+) -> Result<Vec<E>, HashToFieldError> {
+    let element_len = get_element_length::<E::BaseField>();
+    let num_base_elements = count * E::EXTENSION_DEGREE;
+
+    let uniform_bytes = expand_message_xof(msg, dst, num_base_elements * element_len)?;
+    let base_elements = uniform_bytes
+        .chunks(element_len)
+        .map(reduce_be_bytes::<E::BaseField>)
+        .collect::<Vec<_>>();
+
+    Ok(E::slice_from_base_elements(&base_elements).to_vec())
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the number of bytes needed to derive a single element of the field `B`.
+fn get_element_length<B: StarkField>() -> usize {
+    (B::MODULUS_BITS + SECURITY_LEVEL).div_ceil(8) as usize
+}
+
+/// Interprets the provided bytes as an integer in big-endian byte order and reduces it modulo the
+/// field modulus.
+fn reduce_be_bytes<B: StarkField>(bytes: &[u8]) -> B {
+    let base = B::from(256u32);
+    bytes.iter().fold(B::ZERO, |acc, &byte| acc * base + B::from(byte))
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::String;
+
+    use math::{
+        fields::{f64::BaseElement, QuadExtension},
+        FieldElement, StarkField,
+    };
+
+    use super::{expand_message_xof, hash_to_field, HashToFieldError};
+
+    const DST: &[u8] = b"QUUX-V01-CS02-with-expander-SHAKE128";
+
+    #[test]
+    fn expand_message_xof_test_vectors() {
+        // test vectors from Section K.5 of RFC 9380
+        let result = expand_message_xof(b"", DST, 0x20).unwrap();
+        assert_eq!(
+            "86518c9cd86581486e9485aa74ab35ba150d1c75c88e26b7043e44e2acd735a2",
+            to_hex(&result)
+        );
+
+        let result = expand_message_xof(b"abc", DST, 0x20).unwrap();
+        assert_eq!(
+            "8696af52a4d862417c0763556073f47bc9b9ba43c99b505305cb1ec04a9ab468",
+            to_hex(&result)
+        );
+    }
+
+    #[test]
+    fn expand_message_xof_errors() {
+        assert_eq!(
+            Err(HashToFieldError::EmptyDomainSeparationTag),
+            expand_message_xof(b"", b"", 32)
+        );
+        assert_eq!(Err(HashToFieldError::InvalidOutputLength(0)), expand_message_xof(b"", DST, 0));
+        assert_eq!(
+            Err(HashToFieldError::InvalidOutputLength(65536)),
+            expand_message_xof(b"", DST, 65536)
+        );
+
+        // long DSTs are hashed before being used
+        assert!(expand_message_xof(b"", &[1u8; 256], 32).is_ok());
+    }
+
+    #[test]
+    fn hash_to_field_f64() {
+        // each element is derived from 24 bytes
+        let elements = hash_to_field::<BaseElement>(b"abc", DST, 2).unwrap();
+        let bytes = expand_message_xof(b"abc", DST, 48).unwrap();
+        for (element, chunk) in elements.iter().zip(bytes.chunks(24)) {
+            let value = chunk.iter().fold(0u128, |acc, &byte| {
+                ((acc << 8) | byte as u128) % BaseElement::MODULUS as u128
+            });
+            assert_eq!(BaseElement::new(value as u64), *element);
+        }
+
+        // extension field elements are built from consecutive base field elements
+        let ext_elements = hash_to_field::<QuadExtension<BaseElement>>(b"abc", DST, 1).unwrap();
+        assert_eq!(elements, QuadExtension::slice_as_base_elements(&ext_elements));
+    }
+
+    fn to_hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+    }
+}
@@ -15,30 +15,51 @@
 //! * **PRNG** - which is used to generate pseudo-random elements in a finite field. The
 //!   [RandomCoin] implementation uses a cryptographic hash function to generate pseudo-random
 //!   elements form a seed.
+//! * **Hash-to-field** - which is used to deterministically map arbitrary data into field
+//!   elements. The [hash_to_field()] function follows
+//!   [RFC 9380](https://www.rfc-editor.org/rfc/rfc9380).
 
 #![no_std]
 
+#[cfg(feature = "std")]
+extern crate std;
+
 #[macro_use]
 extern crate alloc;
 
 mod hash;
-pub use hash::{Digest, ElementHasher, Hasher};
+pub use hash::{
+    AlgebraicSponge, ByteDigest, Digest, ElementHasher, Hasher, RescueParams, RescuePermutation,
+    HASH_BATCH_SIZE,
+};
 pub mod hashers {
     //! Contains implementations of currently supported hash functions.
 
-    pub use super::hash::{Blake3_192, Blake3_256, Rp62_248, Rp64_256, RpJive64_256, Sha3_256};
+    pub use super::hash::{
+        Blake2s256, Blake3_192, Blake3_256, Griffin64_256, Keccak256, Poseidon2_64_256,
+        Poseidon64_256, Rp62_248, Rp64_256, RpJive64_256, Sha3_256, Truncated,
+    };
 }
 
 mod merkle;
 #[cfg(feature = "concurrent")]
 pub use merkle::concurrent;
-pub use merkle::{build_merkle_nodes, BatchMerkleProof, MerkleTree};
+pub use merkle::{
+    build_merkle_nodes, BatchMerkleProof, CpuLayerHasher, KaryBatchMerkleProof, KaryMerkleTree,
+    LayerHashedMerkleTree, MerkleForest, MerkleForestProof, MerkleLayerHasher, MerkleTree,
+    MerkleTreeBuilder, SaltedMerkleTree, SaltedMerkleTreeOptions,
+};
+#[cfg(feature = "std")]
+pub use merkle::{DiskMerkleTree, DiskMerkleTreeOptions};
 
 mod random;
-pub use random::{DefaultRandomCoin, RandomCoin};
+pub use random::{transcript_labels, DefaultRandomCoin, RandomCoin, SpongeCoin, TranscriptCoin};
+
+mod hash_to_field;
+pub use hash_to_field::{expand_message_xof, hash_to_field};
 
 mod errors;
-pub use errors::{MerkleTreeError, RandomCoinError};
+pub use errors::{HashToFieldError, MerkleTreeError, RandomCoinError, RescueParamsError};
 
 mod commitment;
 pub use commitment::VectorCommitment;
@@ -0,0 +1,134 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::vec::Vec;
+
+use super::MerkleTree;
+use crate::{Hasher, MerkleTreeError};
+
+// MERKLE TREE BUILDER
+// ================================================================================================
+
+/// Builds a [MerkleTree] from leaves provided in chunks.
+///
+/// Internal nodes of the tree are computed as soon as all leaves under them have been appended.
+/// Thus, leaves can be hashed into the tree while the rest of the leaves is still being computed
+/// (e.g., while the remaining rows of an extended execution trace are being evaluated), rather
+/// than only after all leaves are available.
+///
+/// # Examples
+/// ```
+/// # use winter_crypto::{MerkleTree, MerkleTreeBuilder, Hasher, hashers::Blake3_256};
+/// # use math::fields::f128::BaseElement;
+/// type Blake3 = Blake3_256::<BaseElement>;
+///
+/// let leaves = (0..8_u8).map(|i| Blake3::hash(&[i])).collect::<Vec<_>>();
+///
+/// let mut builder = MerkleTreeBuilder::<Blake3>::new(8).unwrap();
+/// builder.append(&leaves[..3]).unwrap();
+/// builder.append(&leaves[3..]).unwrap();
+/// let tree = builder.build().unwrap();
+///
+/// assert_eq!(MerkleTree::<Blake3>::new(leaves).unwrap().root(), tree.root());
+/// ```
+#[derive(Debug)]
+pub struct MerkleTreeBuilder<H: Hasher> {
+    nodes: Vec<H::Digest>,
+    leaves: Vec<H::Digest>,
+    /// Number of nodes computed so far at each level of the tree, starting with the parents of
+    /// the leaves.
+    num_computed: Vec<usize>,
+}
+
+impl<H: Hasher> MerkleTreeBuilder<H> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new builder for a Merkle tree with the specified number of leaves.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The number of leaves is smaller than two.
+    /// * The number of leaves is not a power of two.
+    pub fn new(num_leaves: usize) -> Result<Self, MerkleTreeError> {
+        if num_leaves < 2 {
+            return Err(MerkleTreeError::TooFewLeaves(2, num_leaves));
+        }
+        if !num_leaves.is_power_of_two() {
+            return Err(MerkleTreeError::NumberOfLeavesNotPowerOfTwo(num_leaves));
+        }
+
+        Ok(Self {
+            nodes: vec![H::Digest::default(); num_leaves],
+            leaves: Vec::with_capacity(num_leaves),
+            num_computed: vec![0; num_leaves.ilog2() as usize],
+        })
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of leaves in the tree being built.
+    pub fn num_leaves(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns the number of leaves appended to this builder so far.
+    pub fn num_appended(&self) -> usize {
+        self.leaves.len()
+    }
+
+    // STATE MUTATORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Appends the provided leaves to the tree, and computes all internal nodes for which all
+    /// leaves have been appended.
+    ///
+    /// # Errors
+    /// Returns an error if the number of appended leaves would exceed the number of leaves in
+    /// the tree; in such a case, none of the provided leaves are appended.
+    pub fn append(&mut self, leaves: &[H::Digest]) -> Result<(), MerkleTreeError> {
+        let num_appended = self.leaves.len() + leaves.len();
+        if num_appended > self.num_leaves() {
+            return Err(MerkleTreeError::LeafIndexOutOfBounds(self.num_leaves(), num_appended - 1));
+        }
+        self.leaves.extend_from_slice(leaves);
+
+        // compute parents of the leaves
+        let offset = self.num_leaves() / 2;
+        let num_parents = self.leaves.len() / 2;
+        for i in self.num_computed[0]..num_parents {
+            self.nodes[offset + i] = H::merge(&[self.leaves[2 * i], self.leaves[2 * i + 1]]);
+        }
+        self.num_computed[0] = num_parents;
+
+        // compute all other internal nodes level by level; nodes at a level start at the offset
+        // equal to the number of nodes at this level, and the children of node j are located at
+        // positions 2j and 2j + 1
+        for level in 1..self.num_computed.len() {
+            let offset = self.num_leaves() >> (level + 1);
+            let num_parents = self.num_computed[level - 1] / 2;
+            for i in self.num_computed[level]..num_parents {
+                let j = offset + i;
+                self.nodes[j] = H::merge(&[self.nodes[2 * j], self.nodes[2 * j + 1]]);
+            }
+            self.num_computed[level] = num_parents;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the Merkle tree built from all appended leaves.
+    ///
+    /// # Errors
+    /// Returns an error if fewer leaves than the number of leaves in the tree have been
+    /// appended.
+    pub fn build(self) -> Result<MerkleTree<H>, MerkleTreeError> {
+        if self.leaves.len() < self.num_leaves() {
+            return Err(MerkleTreeError::TooFewLeaves(self.num_leaves(), self.leaves.len()));
+        }
+        MerkleTree::from_raw_parts(self.nodes, self.leaves)
+    }
+}
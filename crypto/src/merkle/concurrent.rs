@@ -13,6 +13,9 @@ use crate::Hasher;
 // CONSTANTS
 // ================================================================================================
 
+/// Minimum number of leaves for which a Merkle tree is built using multiple threads; this is
+/// also the default number of leaf pairs hashed by a single task when building the parents of
+/// the leaves.
 pub const MIN_CONCURRENT_LEAVES: usize = 1024;
 
 // PUBLIC FUNCTIONS
@@ -24,6 +27,23 @@ pub const MIN_CONCURRENT_LEAVES: usize = 1024;
 /// results in a single vector such that root of the tree is at position 1, nodes immediately
 /// under the root is at positions 2 and 3 etc.
 pub fn build_merkle_nodes<H: Hasher>(leaves: &[H::Digest]) -> Vec<H::Digest> {
+    build_merkle_nodes_with_chunk_size::<H>(leaves, MIN_CONCURRENT_LEAVES)
+}
+
+/// Returns internal nodes of a Merkle tree constructed from the provided leaves, with parents of
+/// the leaves computed in tasks of `chunk_size` nodes each.
+///
+/// Smaller chunks balance the work across threads better when hashing is expensive (e.g., for
+/// algebraic hash functions), while larger chunks reduce scheduling overhead. The resulting nodes
+/// are the same as the nodes returned by [build_merkle_nodes()].
+///
+/// # Panics
+/// Panics if `chunk_size` is zero.
+pub fn build_merkle_nodes_with_chunk_size<H: Hasher>(
+    leaves: &[H::Digest],
+    chunk_size: usize,
+) -> Vec<H::Digest> {
+    assert!(chunk_size > 0, "chunk size must be greater than zero");
     let n = leaves.len() / 2;
 
     // create un-initialized array to hold all intermediate nodes
@@ -34,9 +54,9 @@ pub fn build_merkle_nodes<H: Hasher>(leaves: &[H::Digest]) -> Vec<H::Digest> {
     // build first row of internal nodes (parents of leaves)
     let two_leaves = unsafe { slice::from_raw_parts(leaves.as_ptr() as *const [H::Digest; 2], n) };
     nodes[n..]
-        .par_iter_mut()
-        .zip(two_leaves.par_iter())
-        .for_each(|(target, source)| *target = H::merge(source));
+        .par_chunks_mut(chunk_size)
+        .zip(two_leaves.par_chunks(chunk_size))
+        .for_each(|(target, source)| super::merge_pairs::<H>(source, target));
 
     // calculate all other tree nodes, we can't use regular iterators  here because
     // access patterns are rather complicated - so, we use regular threads instead
@@ -92,5 +112,17 @@ mod tests {
             let concurrent = super::build_merkle_nodes::<Sha3_256<BaseElement>>(&leaves);
             assert_eq!(concurrent, sequential);
         }
+
+        #[test]
+        fn build_merkle_nodes_with_chunk_size(
+            ref data in vec(any::<[u8; 32]>(), 256..257).no_shrink(),
+            chunk_size in 1_usize..200,
+        ) {
+            let leaves = ByteDigest::bytes_as_digests(data).to_vec();
+            let sequential = super::super::build_merkle_nodes::<Sha3_256<BaseElement>>(&leaves);
+            let concurrent =
+                super::build_merkle_nodes_with_chunk_size::<Sha3_256<BaseElement>>(&leaves, chunk_size);
+            assert_eq!(concurrent, sequential);
+        }
     }
 }
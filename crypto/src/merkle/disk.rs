@@ -0,0 +1,360 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::{string::ToString, vec::Vec};
+use std::{
+    fs::File,
+    io::{Read, Seek, SeekFrom, Write},
+    path::PathBuf,
+    sync::Mutex,
+};
+
+use utils::{Deserializable, Serializable};
+
+use super::{as_pairs, map_indexes, merge_pairs, BatchMerkleProof, MerkleTree, MerkleTreeOpening};
+use crate::{Hasher, MerkleTreeError, VectorCommitment};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Default number of the topmost layers of internal nodes kept in memory.
+const DEFAULT_CACHED_LAYERS: usize = 16;
+
+/// Number of digests read from or written to the storage file at once during tree construction.
+const IO_BATCH_SIZE: usize = 1 << 14;
+
+// DISK MERKLE TREE
+// ================================================================================================
+
+/// A fully-balanced Merkle tree with leaves and internal nodes stored in a file.
+///
+/// The tree is identical to the [MerkleTree] built from the same leaves: it has the same root,
+/// and produces the same opening proofs (which can be verified via [MerkleTree::verify()] and
+/// [MerkleTree::verify_batch()]). However, only the topmost layers of the tree are kept in
+/// memory; all other nodes, as well as the leaves, are written to a temporary file during tree
+/// construction and are read back when opening proofs are generated. Thus, trees with 2^26 or
+/// more leaves can be built using a small amount of memory. Opening a leaf requires at most one
+/// read per layer which is not kept in memory; frequently accessed parts of the file are cached
+/// by the operating system.
+///
+/// Tree construction reads and writes the file sequentially, one layer at a time. The storage
+/// file is created in the directory specified via [DiskMerkleTreeOptions] (the temporary
+/// directory of the operating system by default), and is deleted when the tree is dropped.
+///
+/// This type is available only when the crate is compiled with the `std` feature.
+#[derive(Debug)]
+pub struct DiskMerkleTree<H: Hasher> {
+    file: Mutex<File>,
+    /// Nodes of the topmost layers of the tree; the root is stored at position 1.
+    cached_nodes: Vec<H::Digest>,
+    num_leaves: usize,
+    digest_size: usize,
+}
+
+/// Options for building a [DiskMerkleTree].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiskMerkleTreeOptions {
+    dir: Option<PathBuf>,
+    num_cached_layers: usize,
+}
+
+impl Default for DiskMerkleTreeOptions {
+    fn default() -> Self {
+        Self {
+            dir: None,
+            num_cached_layers: DEFAULT_CACHED_LAYERS,
+        }
+    }
+}
+
+impl DiskMerkleTreeOptions {
+    /// Returns options which store tree nodes in a file created in the specified directory.
+    pub fn with_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.dir = Some(dir.into());
+        self
+    }
+
+    /// Returns options which keep the specified number of the topmost layers of internal nodes
+    /// in memory.
+    pub const fn with_cached_layers(mut self, num_cached_layers: usize) -> Self {
+        self.num_cached_layers = num_cached_layers;
+        self
+    }
+}
+
+impl<H: Hasher> DiskMerkleTree<H> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new Merkle tree built from the provided leaves and stored in a file.
+    ///
+    /// Leaves are consumed from the iterator in chunks, and thus, do not need to be materialized
+    /// in memory.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * Fewer than two leaves were provided.
+    /// * Number of leaves is not a power of two.
+    /// * The storage file could not be created, written, or read.
+    pub fn new<I>(leaves: I, options: DiskMerkleTreeOptions) -> Result<Self, MerkleTreeError>
+    where
+        I: IntoIterator<Item = H::Digest>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        let mut leaves = leaves.into_iter();
+        let num_leaves = leaves.len();
+        if num_leaves < 2 {
+            return Err(MerkleTreeError::TooFewLeaves(2, num_leaves));
+        }
+        if !num_leaves.is_power_of_two() {
+            return Err(MerkleTreeError::NumberOfLeavesNotPowerOfTwo(num_leaves));
+        }
+
+        let dir = options.dir.unwrap_or_else(std::env::temp_dir);
+        let file = tempfile::tempfile_in(dir).map_err(storage_error)?;
+        let mut tree = Self {
+            file: Mutex::new(file),
+            cached_nodes: Vec::new(),
+            num_leaves,
+            digest_size: H::Digest::default().to_bytes().len(),
+        };
+
+        // nodes are stored in the file at positions [1, n), and leaves at positions [n, 2n)
+        // where n is the number of leaves; the parents of leaves are computed as the leaves are
+        // written to the file
+        let batch_size = IO_BATCH_SIZE.min(num_leaves);
+        let mut batch = Vec::with_capacity(batch_size);
+        let mut parents = vec![H::Digest::default(); batch_size / 2];
+        for offset in (0..num_leaves).step_by(batch_size) {
+            batch.clear();
+            batch.extend(leaves.by_ref().take(batch_size));
+            if batch.len() != batch_size {
+                return Err(MerkleTreeError::TooFewLeaves(num_leaves, offset + batch.len()));
+            }
+            tree.write_nodes(num_leaves + offset, &batch)?;
+            merge_pairs::<H>(as_pairs(&batch), &mut parents);
+            tree.write_nodes((num_leaves + offset) / 2, &parents)?;
+        }
+
+        // compute all other layers; nodes of a layer are located at positions [k, 2k), and their
+        // children at positions [2k, 4k)
+        let mut layer_len = num_leaves / 4;
+        while layer_len >= 1 {
+            let batch_size = IO_BATCH_SIZE.min(layer_len);
+            let mut parents = vec![H::Digest::default(); batch_size];
+            for offset in (0..layer_len).step_by(batch_size) {
+                let children = tree.read_nodes(2 * (layer_len + offset), 2 * batch_size)?;
+                merge_pairs::<H>(as_pairs(&children), &mut parents);
+                tree.write_nodes(layer_len + offset, &parents)?;
+            }
+            layer_len /= 2;
+        }
+
+        // load the topmost layers into memory; the root is always kept in memory
+        let num_cached_nodes = 1 << options.num_cached_layers.clamp(1, tree.depth());
+        let mut cached_nodes = vec![H::Digest::default()];
+        cached_nodes.extend(tree.read_nodes(1, num_cached_nodes - 1)?);
+        tree.cached_nodes = cached_nodes;
+
+        Ok(tree)
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the root of the tree.
+    pub fn root(&self) -> &H::Digest {
+        &self.cached_nodes[1]
+    }
+
+    /// Returns the depth of the tree.
+    ///
+    /// The depth of a tree is defined as the number of layers in the tree minus one.
+    pub fn depth(&self) -> usize {
+        self.num_leaves.ilog2() as usize
+    }
+
+    /// Returns the number of leaves in the tree.
+    pub fn num_leaves(&self) -> usize {
+        self.num_leaves
+    }
+
+    /// Returns the leaf at the specified `index`.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The specified index is greater than or equal to the number of leaves in the tree.
+    /// * The storage file could not be read.
+    pub fn leaf(&self, index: usize) -> Result<H::Digest, MerkleTreeError> {
+        if index >= self.num_leaves {
+            return Err(MerkleTreeError::LeafIndexOutOfBounds(self.num_leaves, index));
+        }
+        self.get_node(self.num_leaves + index)
+    }
+
+    // PROVING METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a Merkle proof to a leaf at the specified `index`.
+    ///
+    /// The leaf itself will be the first element of the returned tuple.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The specified index is greater than or equal to the number of leaves in the tree.
+    /// * The storage file could not be read.
+    pub fn prove(&self, index: usize) -> Result<MerkleTreeOpening<H>, MerkleTreeError> {
+        let leaf = self.leaf(index)?;
+
+        let mut index = self.num_leaves + index;
+        let mut proof = Vec::with_capacity(self.depth());
+        while index > 1 {
+            proof.push(self.get_node(index ^ 1)?);
+            index >>= 1;
+        }
+
+        Ok((leaf, proof))
+    }
+
+    /// Computes Merkle proofs for the provided indexes, compresses the proofs into a single batch
+    /// and returns the batch proof alongside the leaves at the provided indexes.
+    ///
+    /// The batch proof is the same as the one generated by [MerkleTree::prove_batch()].
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * No indexes were provided (i.e., `indexes` is an empty slice).
+    /// * Any of the provided indexes are greater than or equal to the number of leaves in the
+    ///   tree.
+    /// * List of indexes contains duplicates.
+    /// * The storage file could not be read.
+    pub fn prove_batch(
+        &self,
+        indexes: &[usize],
+    ) -> Result<(Vec<H::Digest>, BatchMerkleProof<H>), MerkleTreeError> {
+        if indexes.is_empty() {
+            return Err(MerkleTreeError::TooFewLeafIndexes);
+        }
+        map_indexes(indexes, self.depth())?;
+
+        let openings =
+            indexes.iter().map(|&index| self.prove(index)).collect::<Result<Vec<_>, _>>()?;
+        let leaves = openings.iter().map(|(leaf, _)| *leaf).collect();
+        Ok((leaves, BatchMerkleProof::from_single_proofs(&openings, indexes)))
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the node at the specified position, where positions [1, n) refer to internal
+    /// nodes and positions [n, 2n) refer to leaves.
+    fn get_node(&self, position: usize) -> Result<H::Digest, MerkleTreeError> {
+        match self.cached_nodes.get(position) {
+            Some(node) => Ok(*node),
+            None => Ok(self.read_nodes(position, 1)?[0]),
+        }
+    }
+
+    /// Reads `num_nodes` nodes starting at the specified position from the storage file.
+    fn read_nodes(
+        &self,
+        position: usize,
+        num_nodes: usize,
+    ) -> Result<Vec<H::Digest>, MerkleTreeError> {
+        let mut bytes = vec![0; num_nodes * self.digest_size];
+        {
+            let mut file = self.file.lock().map_err(|err| storage_error(err.to_string()))?;
+            file.seek(SeekFrom::Start((position * self.digest_size) as u64))
+                .map_err(storage_error)?;
+            file.read_exact(&mut bytes).map_err(storage_error)?;
+        }
+
+        bytes
+            .chunks(self.digest_size)
+            .map(|chunk| H::Digest::read_from_bytes(chunk).map_err(storage_error))
+            .collect()
+    }
+
+    /// Writes the provided nodes into the storage file starting at the specified position.
+    fn write_nodes(&mut self, position: usize, nodes: &[H::Digest]) -> Result<(), MerkleTreeError> {
+        let mut bytes = Vec::with_capacity(nodes.len() * self.digest_size);
+        nodes.iter().for_each(|node| node.write_into(&mut bytes));
+
+        let file = self.file.get_mut().map_err(|err| storage_error(err.to_string()))?;
+        file.seek(SeekFrom::Start((position * self.digest_size) as u64))
+            .map_err(storage_error)?;
+        file.write_all(&bytes).map_err(storage_error)
+    }
+}
+
+// VECTOR COMMITMENT IMPLEMENTATION
+// ================================================================================================
+
+impl<H: Hasher> VectorCommitment<H> for DiskMerkleTree<H> {
+    type Options = DiskMerkleTreeOptions;
+
+    type Proof = Vec<H::Digest>;
+
+    type MultiProof = BatchMerkleProof<H>;
+
+    type Error = MerkleTreeError;
+
+    fn with_options(items: Vec<H::Digest>, options: Self::Options) -> Result<Self, Self::Error> {
+        Self::new(items, options)
+    }
+
+    fn commitment(&self) -> H::Digest {
+        *self.root()
+    }
+
+    fn domain_len(&self) -> usize {
+        self.num_leaves
+    }
+
+    fn get_proof_domain_len(proof: &Self::Proof) -> usize {
+        1 << proof.len()
+    }
+
+    fn get_multiproof_domain_len(proof: &Self::MultiProof) -> usize {
+        1 << proof.depth
+    }
+
+    fn open(&self, index: usize) -> Result<(H::Digest, Self::Proof), Self::Error> {
+        self.prove(index)
+    }
+
+    fn open_many(
+        &self,
+        indexes: &[usize],
+    ) -> Result<(Vec<H::Digest>, Self::MultiProof), Self::Error> {
+        self.prove_batch(indexes)
+    }
+
+    fn verify(
+        commitment: H::Digest,
+        index: usize,
+        item: H::Digest,
+        proof: &Self::Proof,
+    ) -> Result<(), Self::Error> {
+        MerkleTree::<H>::verify(commitment, index, item, proof)
+    }
+
+    fn verify_many(
+        commitment: H::Digest,
+        indexes: &[usize],
+        items: &[H::Digest],
+        proof: &Self::MultiProof,
+    ) -> Result<(), Self::Error> {
+        MerkleTree::<H>::verify_batch(&commitment, indexes, items, proof)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns a storage error with the description of the specified underlying error.
+fn storage_error(err: impl ToString) -> MerkleTreeError {
+    MerkleTreeError::StorageError(err.to_string())
+}
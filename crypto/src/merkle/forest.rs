@@ -0,0 +1,189 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::vec::Vec;
+
+use utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+
+use super::{BatchMerkleProof, MerkleTree};
+use crate::{Digest, Hasher, MerkleTreeError};
+
+// MERKLE FOREST
+// ================================================================================================
+
+/// A collection of Merkle trees committed to under a single root.
+///
+/// The root of a forest of trees with roots (r_0, ..., r_{k-1}) is computed as
+/// hash(r_0 || ... || r_{k-1}), where roots are concatenated in their byte representation. Thus,
+/// several commitments (e.g., to the extended execution trace, to the constraint evaluations, and
+/// to FRI layers) can be bound together and checked against a single digest.
+///
+/// Openings can be requested for any subset of trees at once, and a different set of leaves can
+/// be opened in every tree. A forest proof contains the roots of all trees in the forest, and a
+/// batch Merkle proof for every tree in which at least one leaf was opened.
+#[derive(Debug)]
+pub struct MerkleForest<H: Hasher> {
+    trees: Vec<MerkleTree<H>>,
+    root: H::Digest,
+}
+
+/// A batch proof of openings in one or more trees of a [MerkleForest].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MerkleForestProof<H: Hasher> {
+    /// Roots of all trees in the forest.
+    pub roots: Vec<H::Digest>,
+    /// Batch proofs of the leaves opened in each tree, or `None` if no leaves were opened in a
+    /// tree.
+    pub proofs: Vec<Option<BatchMerkleProof<H>>>,
+}
+
+impl<H: Hasher> MerkleForest<H> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new forest committing to the provided trees.
+    ///
+    /// # Errors
+    /// Returns an error if no trees were provided.
+    pub fn new(trees: Vec<MerkleTree<H>>) -> Result<Self, MerkleTreeError> {
+        if trees.is_empty() {
+            return Err(MerkleTreeError::EmptyForest);
+        }
+        let roots: Vec<H::Digest> = trees.iter().map(|tree| *tree.root()).collect();
+        let root = hash_roots::<H>(&roots);
+        Ok(Self { trees, root })
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the root of the forest.
+    pub fn root(&self) -> &H::Digest {
+        &self.root
+    }
+
+    /// Returns the number of trees in the forest.
+    pub fn num_trees(&self) -> usize {
+        self.trees.len()
+    }
+
+    /// Returns the trees of the forest.
+    pub fn trees(&self) -> &[MerkleTree<H>] {
+        &self.trees
+    }
+
+    // PROVING METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Computes a batch proof for the leaves at the specified indexes, and returns it alongside
+    /// the opened leaves.
+    ///
+    /// `indexes` must contain a list of leaf indexes for every tree in the forest; an empty list
+    /// means that no leaves are opened in the corresponding tree. Opened leaves are returned in
+    /// the same layout.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The number of index lists is not equal to the number of trees in the forest.
+    /// * Any list of indexes contains out-of-bounds indexes or duplicates.
+    #[allow(clippy::type_complexity)]
+    pub fn prove_batch(
+        &self,
+        indexes: &[Vec<usize>],
+    ) -> Result<(Vec<Vec<H::Digest>>, MerkleForestProof<H>), MerkleTreeError> {
+        if indexes.len() != self.trees.len() {
+            return Err(MerkleTreeError::InvalidNumberOfTrees(self.trees.len(), indexes.len()));
+        }
+
+        let mut leaves = Vec::with_capacity(self.trees.len());
+        let mut proofs = Vec::with_capacity(self.trees.len());
+        for (tree, indexes) in self.trees.iter().zip(indexes) {
+            if indexes.is_empty() {
+                leaves.push(Vec::new());
+                proofs.push(None);
+            } else {
+                let (tree_leaves, proof) = tree.prove_batch(indexes)?;
+                leaves.push(tree_leaves);
+                proofs.push(Some(proof));
+            }
+        }
+
+        let roots = self.trees.iter().map(|tree| *tree.root()).collect();
+        Ok((leaves, MerkleForestProof { roots, proofs }))
+    }
+
+    // VERIFICATION METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Checks whether the forest `proof` contains proofs resolving to `root` for the provided
+    /// `leaves` at the specified `indexes`.
+    ///
+    /// `indexes` and `leaves` must be laid out as the inputs and outputs of
+    /// [MerkleForest::prove_batch()].
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The number of index lists, leaf lists, or tree roots in the proof is not the same.
+    /// * The roots of the trees in the proof do not hash to `root`.
+    /// * Any of the batch proofs does not resolve to the root of its tree.
+    pub fn verify_batch(
+        root: &H::Digest,
+        indexes: &[Vec<usize>],
+        leaves: &[Vec<H::Digest>],
+        proof: &MerkleForestProof<H>,
+    ) -> Result<(), MerkleTreeError> {
+        let num_trees = proof.roots.len();
+        if indexes.len() != num_trees {
+            return Err(MerkleTreeError::InvalidNumberOfTrees(num_trees, indexes.len()));
+        }
+        if leaves.len() != num_trees || proof.proofs.len() != num_trees {
+            return Err(MerkleTreeError::InvalidProof);
+        }
+        if *root != hash_roots::<H>(&proof.roots) {
+            return Err(MerkleTreeError::InvalidProof);
+        }
+
+        for (((tree_root, indexes), leaves), tree_proof) in
+            proof.roots.iter().zip(indexes).zip(leaves).zip(proof.proofs.iter())
+        {
+            match tree_proof {
+                Some(tree_proof) => {
+                    MerkleTree::<H>::verify_batch(tree_root, indexes, leaves, tree_proof)?
+                },
+                None if indexes.is_empty() && leaves.is_empty() => (),
+                None => return Err(MerkleTreeError::InvalidProof),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// SERIALIZATION / DESERIALIZATION
+// ================================================================================================
+
+impl<H: Hasher> Serializable for MerkleForestProof<H> {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.roots.write_into(target);
+        self.proofs.write_into(target);
+    }
+}
+
+impl<H: Hasher> Deserializable for MerkleForestProof<H> {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let roots = Vec::<_>::read_from(source)?;
+        let proofs = Vec::<_>::read_from(source)?;
+        Ok(Self { roots, proofs })
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns hash(r_0 || ... || r_{k-1}) for the specified tree roots.
+fn hash_roots<H: Hasher>(roots: &[H::Digest]) -> H::Digest {
+    let bytes: Vec<u8> = roots.iter().flat_map(|root| root.as_bytes()).collect();
+    H::hash(&bytes)
+}
@@ -0,0 +1,468 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    vec::Vec,
+};
+
+#[cfg(feature = "concurrent")]
+use utils::iterators::*;
+use utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+
+use crate::{Hasher, MerkleTreeError, VectorCommitment};
+
+// K-ARY MERKLE TREE
+// ================================================================================================
+
+/// A Merkle tree in which every internal node has `ARITY` children.
+///
+/// Each internal node is computed by hashing its children using [Hasher::merge_many()]. As
+/// compared to a binary [MerkleTree](super::MerkleTree), the depth of the tree (and thus, the
+/// number of hashes needed to verify an authentication path) is reduced by a factor of
+/// log2(`ARITY`), at the expense of including `ARITY - 1` siblings per level in authentication
+/// paths. This pairs naturally with algebraic hash functions which absorb many elements per
+/// permutation: for example, hashing 4 digests of [Rp64_256](crate::hashers::Rp64_256) with rate
+/// 8 requires 2 permutations, as compared to 3 permutations required to compute 3 2-to-1 hashes.
+///
+/// `ARITY` must be a power of two greater than or equal to 2. The number of leaves must be a power
+/// of two, but does not need to be a power of `ARITY`: when a level of the tree contains fewer than
+/// `ARITY` nodes, the root of the tree is computed by hashing all nodes of this level together.
+///
+/// Batch opening proofs for multiple leaves are provided by [KaryBatchMerkleProof].
+#[derive(Debug)]
+pub struct KaryMerkleTree<H: Hasher, const ARITY: usize> {
+    /// Levels of the tree from the leaves (at index 0) to the root (at the last index).
+    levels: Vec<Vec<H::Digest>>,
+}
+
+impl<H: Hasher, const ARITY: usize> KaryMerkleTree<H, ARITY> {
+    /// Causes a compile-time error when the arity is not supported.
+    const VALID_ARITY: () =
+        assert!(ARITY >= 2 && ARITY.is_power_of_two(), "arity must be a power of two");
+
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new Merkle tree built from the provided leaves using hash function specified by
+    /// the `H` generic parameter.
+    ///
+    /// When `concurrent` feature is enabled, the tree is built using multiple threads.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * Fewer than two leaves were provided.
+    /// * Number of leaves is not a power of two.
+    pub fn new(leaves: Vec<H::Digest>) -> Result<Self, MerkleTreeError> {
+        #[allow(clippy::let_unit_value)]
+        let _ = Self::VALID_ARITY;
+
+        if leaves.len() < 2 {
+            return Err(MerkleTreeError::TooFewLeaves(2, leaves.len()));
+        }
+        if !leaves.len().is_power_of_two() {
+            return Err(MerkleTreeError::NumberOfLeavesNotPowerOfTwo(leaves.len()));
+        }
+
+        let mut levels = vec![leaves];
+        loop {
+            let level = &levels[levels.len() - 1];
+            if level.len() == 1 {
+                break;
+            }
+            let group_size = get_group_size::<ARITY>(level.len());
+
+            #[cfg(not(feature = "concurrent"))]
+            let parents = level.chunks(group_size).map(H::merge_many).collect();
+
+            #[cfg(feature = "concurrent")]
+            let parents = level.par_chunks(group_size).map(H::merge_many).collect();
+
+            levels.push(parents);
+        }
+
+        Ok(Self { levels })
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the root of the tree.
+    pub fn root(&self) -> &H::Digest {
+        &self.levels[self.levels.len() - 1][0]
+    }
+
+    /// Returns the number of levels of internal nodes in the tree.
+    ///
+    /// For example, a 4-ary tree with 16 leaves has depth 2, while a 4-ary tree with 32 leaves
+    /// has depth 3.
+    pub fn depth(&self) -> usize {
+        self.levels.len() - 1
+    }
+
+    /// Returns leaf nodes of the tree.
+    pub fn leaves(&self) -> &[H::Digest] {
+        &self.levels[0]
+    }
+
+    // PROVING METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a Merkle proof to a leaf at the specified `index`.
+    ///
+    /// The proof contains the siblings of all nodes on the path from the leaf to the root, level
+    /// by level and in ascending order of their indexes within each level. The leaf itself will
+    /// be the first element of the returned tuple.
+    ///
+    /// # Errors
+    /// Returns an error if the specified index is greater than or equal to the number of leaves
+    /// in the tree.
+    pub fn prove(&self, index: usize) -> Result<(H::Digest, Vec<H::Digest>), MerkleTreeError> {
+        let num_leaves = self.leaves().len();
+        if index >= num_leaves {
+            return Err(MerkleTreeError::LeafIndexOutOfBounds(num_leaves, index));
+        }
+
+        let mut proof = Vec::new();
+        let mut node_index = index;
+        for level in &self.levels[..self.depth()] {
+            let group_size = get_group_size::<ARITY>(level.len());
+            let group_start = node_index - node_index % group_size;
+            for i in (group_start..group_start + group_size).filter(|&i| i != node_index) {
+                proof.push(level[i]);
+            }
+            node_index /= group_size;
+        }
+
+        Ok((self.levels[0][index], proof))
+    }
+
+    /// Computes Merkle proofs for the provided indexes, compresses the proofs into a single batch
+    /// and returns the batch proof alongside the leaves at the provided indexes.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * No indexes were provided (i.e., `indexes` is an empty slice).
+    /// * Any of the provided indexes are greater than or equal to the number of leaves in the
+    ///   tree.
+    /// * List of indexes contains duplicates.
+    pub fn prove_batch(
+        &self,
+        indexes: &[usize],
+    ) -> Result<(Vec<H::Digest>, KaryBatchMerkleProof<H>), MerkleTreeError> {
+        let leaves = self.leaves();
+        let mut known = get_index_set(indexes, leaves.len())?;
+
+        let mut nodes = Vec::with_capacity(self.depth());
+        for level in &self.levels[..self.depth()] {
+            let group_size = get_group_size::<ARITY>(level.len());
+            let mut level_nodes = Vec::new();
+            let mut parents = BTreeSet::new();
+            for &index in known.iter() {
+                let parent = index / group_size;
+                if parents.insert(parent) {
+                    let group_start = parent * group_size;
+                    let group = &level[group_start..group_start + group_size];
+                    for (i, &node) in (group_start..).zip(group) {
+                        if !known.contains(&i) {
+                            level_nodes.push(node);
+                        }
+                    }
+                }
+            }
+            nodes.push(level_nodes);
+            known = parents;
+        }
+
+        let proof = KaryBatchMerkleProof {
+            nodes,
+            log_num_leaves: leaves.len().ilog2() as u8,
+        };
+        Ok((indexes.iter().map(|&i| leaves[i]).collect(), proof))
+    }
+
+    // VERIFICATION METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Checks whether the `proof` for the given `leaf` at the specified `index` is valid.
+    ///
+    /// # Errors
+    /// Returns an error if the specified `proof` (which is a Merkle path) does not resolve to the
+    /// specified `root`.
+    pub fn verify(
+        root: H::Digest,
+        index: usize,
+        leaf: H::Digest,
+        proof: &[H::Digest],
+    ) -> Result<(), MerkleTreeError> {
+        let log_num_leaves =
+            get_path_log_num_leaves::<ARITY>(proof.len()).ok_or(MerkleTreeError::InvalidProof)?;
+        if index >= 1 << log_num_leaves {
+            return Err(MerkleTreeError::InvalidProof);
+        }
+
+        let mut siblings = proof.iter();
+        let mut buf = Vec::with_capacity(ARITY);
+        let mut node = leaf;
+        let mut node_index = index;
+        let mut level_size = 1 << log_num_leaves;
+        while level_size > 1 {
+            let group_size = get_group_size::<ARITY>(level_size);
+            buf.clear();
+            for i in 0..group_size {
+                if i == node_index % group_size {
+                    buf.push(node);
+                } else {
+                    buf.push(*siblings.next().ok_or(MerkleTreeError::InvalidProof)?);
+                }
+            }
+            node = H::merge_many(&buf);
+            node_index /= group_size;
+            level_size /= group_size;
+        }
+
+        if node != root {
+            return Err(MerkleTreeError::InvalidProof);
+        }
+        Ok(())
+    }
+
+    /// Checks whether the batch `proof` contains Merkle proofs resolving to `root` for
+    /// the provided `leaves` at the specified `indexes`.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * No indexes were provided (i.e., `indexes` is an empty slice).
+    /// * Any of the specified `indexes` is greater than or equal to the number of leaves in the
+    ///   tree from which the batch proof was generated.
+    /// * List of indexes contains duplicates.
+    /// * Any of the proofs in the batch proof does not resolve to the specified `root`.
+    pub fn verify_batch(
+        root: &H::Digest,
+        indexes: &[usize],
+        leaves: &[H::Digest],
+        proof: &KaryBatchMerkleProof<H>,
+    ) -> Result<(), MerkleTreeError> {
+        if *root != proof.get_root::<ARITY>(indexes, leaves)? {
+            return Err(MerkleTreeError::InvalidProof);
+        }
+        Ok(())
+    }
+}
+
+// VECTOR COMMITMENT IMPLEMENTATION
+// ================================================================================================
+
+impl<H: Hasher, const ARITY: usize> VectorCommitment<H> for KaryMerkleTree<H, ARITY> {
+    type Options = ();
+
+    type Proof = Vec<H::Digest>;
+
+    type MultiProof = KaryBatchMerkleProof<H>;
+
+    type Error = MerkleTreeError;
+
+    fn with_options(items: Vec<H::Digest>, _options: Self::Options) -> Result<Self, Self::Error> {
+        KaryMerkleTree::new(items)
+    }
+
+    fn commitment(&self) -> H::Digest {
+        *self.root()
+    }
+
+    fn domain_len(&self) -> usize {
+        self.leaves().len()
+    }
+
+    fn get_proof_domain_len(proof: &Self::Proof) -> usize {
+        get_path_log_num_leaves::<ARITY>(proof.len())
+            .map_or(0, |log_num_leaves| 1 << log_num_leaves)
+    }
+
+    fn get_multiproof_domain_len(proof: &Self::MultiProof) -> usize {
+        1 << proof.log_num_leaves
+    }
+
+    fn open(&self, index: usize) -> Result<(H::Digest, Self::Proof), Self::Error> {
+        self.prove(index)
+    }
+
+    fn open_many(
+        &self,
+        indexes: &[usize],
+    ) -> Result<(Vec<H::Digest>, Self::MultiProof), Self::Error> {
+        self.prove_batch(indexes)
+    }
+
+    fn verify(
+        commitment: H::Digest,
+        index: usize,
+        item: H::Digest,
+        proof: &Self::Proof,
+    ) -> Result<(), Self::Error> {
+        KaryMerkleTree::<H, ARITY>::verify(commitment, index, item, proof)
+    }
+
+    fn verify_many(
+        commitment: H::Digest,
+        indexes: &[usize],
+        items: &[H::Digest],
+        proof: &Self::MultiProof,
+    ) -> Result<(), Self::Error> {
+        KaryMerkleTree::<H, ARITY>::verify_batch(&commitment, indexes, items, proof)
+    }
+}
+
+// K-ARY BATCH MERKLE PROOF
+// ================================================================================================
+
+/// Multiple Merkle proofs for a [KaryMerkleTree] aggregated into a single proof.
+///
+/// For each level of the tree (starting with the leaves), the proof contains the nodes which are
+/// required to compute the parents of the opened nodes at this level, but which cannot be computed
+/// from the opened leaves. The nodes are listed in ascending order of their indexes within the
+/// level. Thus, the proof contains the smallest set of nodes required to compute the root of the
+/// tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KaryBatchMerkleProof<H: Hasher> {
+    /// Nodes required to compute the root, grouped by tree level starting with the leaves.
+    pub nodes: Vec<Vec<H::Digest>>,
+    /// Base 2 logarithm of the number of leaves in the tree.
+    pub log_num_leaves: u8,
+}
+
+impl<H: Hasher> KaryBatchMerkleProof<H> {
+    /// Computes a node to which all Merkle proofs aggregated in this proof resolve, assuming the
+    /// proof was generated for a tree with the specified arity.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * No indexes were provided (i.e., `indexes` is an empty slice).
+    /// * Any of the specified `indexes` is greater than or equal to the number of leaves in the
+    ///   tree for which this batch proof was generated.
+    /// * List of indexes contains duplicates.
+    /// * The proof does not contain exactly the nodes required to compute the root.
+    pub fn get_root<const ARITY: usize>(
+        &self,
+        indexes: &[usize],
+        leaves: &[H::Digest],
+    ) -> Result<H::Digest, MerkleTreeError> {
+        let num_leaves = 1_usize
+            .checked_shl(self.log_num_leaves as u32)
+            .ok_or(MerkleTreeError::InvalidProof)?;
+        get_index_set(indexes, num_leaves)?;
+        if indexes.len() != leaves.len() {
+            return Err(MerkleTreeError::InvalidProof);
+        }
+
+        let mut known: BTreeMap<usize, H::Digest> =
+            indexes.iter().copied().zip(leaves.iter().copied()).collect();
+        let mut buf = Vec::with_capacity(ARITY);
+        let mut level_size = num_leaves;
+        let mut levels = self.nodes.iter();
+        while level_size > 1 {
+            let group_size = get_group_size::<ARITY>(level_size);
+            let mut level_nodes = levels.next().ok_or(MerkleTreeError::InvalidProof)?.iter();
+            let mut parents = BTreeMap::new();
+            for &index in known.keys() {
+                let parent = index / group_size;
+                if parents.contains_key(&parent) {
+                    continue;
+                }
+
+                buf.clear();
+                let group_start = parent * group_size;
+                for i in group_start..group_start + group_size {
+                    let node = match known.get(&i) {
+                        Some(node) => *node,
+                        None => *level_nodes.next().ok_or(MerkleTreeError::InvalidProof)?,
+                    };
+                    buf.push(node);
+                }
+                parents.insert(parent, H::merge_many(&buf));
+            }
+
+            // all nodes provided for this level must have been consumed
+            if level_nodes.next().is_some() {
+                return Err(MerkleTreeError::InvalidProof);
+            }
+            known = parents;
+            level_size /= group_size;
+        }
+
+        if levels.next().is_some() {
+            return Err(MerkleTreeError::InvalidProof);
+        }
+        known.remove(&0).ok_or(MerkleTreeError::InvalidProof)
+    }
+}
+
+// SERIALIZATION / DESERIALIZATION
+// --------------------------------------------------------------------------------------------
+
+impl<H: Hasher> Serializable for KaryBatchMerkleProof<H> {
+    /// Writes all proof nodes into the provided target.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u8(self.log_num_leaves);
+        self.nodes.write_into(target);
+    }
+}
+
+impl<H: Hasher> Deserializable for KaryBatchMerkleProof<H> {
+    /// Parses proof nodes from the provided `source`, and constructs a batch Merkle proof from
+    /// these nodes.
+    ///
+    /// # Errors
+    /// Returns an error if `source` could not be deserialized into a valid set of proof nodes.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let log_num_leaves = source.read_u8()?;
+        if log_num_leaves as u32 >= usize::BITS {
+            return Err(DeserializationError::InvalidValue(format!(
+                "number of leaves 2^{log_num_leaves} is too large"
+            )));
+        }
+        let nodes = Vec::<Vec<_>>::read_from(source)?;
+        Ok(Self { nodes, log_num_leaves })
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the number of nodes hashed together into a single parent at a level of the specified
+/// size.
+fn get_group_size<const ARITY: usize>(level_size: usize) -> usize {
+    ARITY.min(level_size)
+}
+
+/// Returns base 2 logarithm of the number of leaves in a tree for which an authentication path
+/// has the specified length, or `None` if no tree has authentication paths of this length.
+fn get_path_log_num_leaves<const ARITY: usize>(path_len: usize) -> Option<u32> {
+    let (num_full_levels, remainder) = (path_len / (ARITY - 1), path_len % (ARITY - 1));
+    if !(remainder + 1).is_power_of_two() {
+        return None;
+    }
+    Some(num_full_levels as u32 * ARITY.ilog2() + (remainder + 1).ilog2())
+        .filter(|&log_num_leaves| log_num_leaves > 0 && log_num_leaves < usize::BITS)
+}
+
+/// Returns the provided indexes as a set, checking that the indexes are not empty, unique, and
+/// smaller than the number of leaves.
+fn get_index_set(indexes: &[usize], num_leaves: usize) -> Result<BTreeSet<usize>, MerkleTreeError> {
+    if indexes.is_empty() {
+        return Err(MerkleTreeError::TooFewLeafIndexes);
+    }
+
+    let mut set = BTreeSet::new();
+    for &index in indexes {
+        if index >= num_leaves {
+            return Err(MerkleTreeError::LeafIndexOutOfBounds(num_leaves, index));
+        }
+        if !set.insert(index) {
+            return Err(MerkleTreeError::DuplicateLeafIndex);
+        }
+    }
+    Ok(set)
+}
@@ -0,0 +1,165 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+#[cfg(feature = "concurrent")]
+use utils::iterators::*;
+
+#[cfg(feature = "concurrent")]
+use super::concurrent::MIN_CONCURRENT_LEAVES;
+use super::{merge_pairs, BatchMerkleProof, MerkleTree};
+use crate::{Hasher, MerkleTreeError, VectorCommitment};
+
+// MERKLE LAYER HASHER
+// ================================================================================================
+
+/// Defines how a single layer of a Merkle tree is hashed.
+///
+/// Every node of a layer is computed as the merge of its two children, independently of other
+/// nodes of the same layer. Thus, layers can be hashed on hardware which processes many hashes
+/// at once (e.g., on a GPU). Implementations of this trait are used by
+/// [MerkleTree::with_layer_hasher()](super::MerkleTree::with_layer_hasher) which invokes
+/// [MerkleLayerHasher::hash_layer()] once for every layer of the tree, starting with the parents
+/// of the leaves and ending with the root.
+///
+/// To use a layer hasher in the STARK prover, specify [LayerHashedMerkleTree] parameterized by
+/// the layer hasher as the vector commitment scheme of the prover.
+///
+/// Implementations must produce exactly the same nodes as [Hasher::merge()] does; otherwise,
+/// trees built with them will not verify.
+///
+/// This crate provides only [CpuLayerHasher]; hashers which offload layers to a GPU or other
+/// accelerators are expected to be implemented outside of this crate (e.g., together with the
+/// device kernels they depend on).
+pub trait MerkleLayerHasher<H: Hasher> {
+    /// Writes merges of all pairs in `children` into `parents`.
+    ///
+    /// The number of pairs in `children` is guaranteed to be equal to the length of `parents`.
+    fn hash_layer(&self, children: &[[H::Digest; 2]], parents: &mut [H::Digest]);
+}
+
+// CPU LAYER HASHER
+// ================================================================================================
+
+/// A [MerkleLayerHasher] which hashes layers on the CPU.
+///
+/// When the crate is compiled with `concurrent` feature enabled, nodes of a layer are hashed in
+/// multiple threads.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CpuLayerHasher;
+
+impl<H: Hasher> MerkleLayerHasher<H> for CpuLayerHasher {
+    fn hash_layer(&self, children: &[[H::Digest; 2]], parents: &mut [H::Digest]) {
+        debug_assert_eq!(children.len(), parents.len());
+
+        #[cfg(not(feature = "concurrent"))]
+        merge_pairs::<H>(children, parents);
+
+        #[cfg(feature = "concurrent")]
+        parents
+            .par_chunks_mut(MIN_CONCURRENT_LEAVES)
+            .zip(children.par_chunks(MIN_CONCURRENT_LEAVES))
+            .for_each(|(parents, children)| merge_pairs::<H>(children, parents));
+    }
+}
+
+// LAYER HASHED MERKLE TREE
+// ================================================================================================
+
+/// A [MerkleTree] with internal nodes hashed by a [MerkleLayerHasher] of type `L`.
+///
+/// This vector commitment scheme is the way to plug a custom layer hasher (e.g., one which hashes
+/// layers on a GPU) into the STARK prover: the prover builds commitments via
+/// [VectorCommitment::new()], which for this scheme builds the tree via
+/// [MerkleTree::with_layer_hasher()] using the default instance of `L`; a specific instance of
+/// `L` can be provided via [VectorCommitment::with_options()].
+///
+/// The tree is identical to the [MerkleTree] built from the same leaves, and its opening proofs
+/// are [MerkleTree] opening proofs. Thus, proofs generated with this scheme can be verified using
+/// [MerkleTree] as the vector commitment scheme.
+pub struct LayerHashedMerkleTree<H: Hasher, L> {
+    tree: MerkleTree<H>,
+    _layer_hasher: PhantomData<fn() -> L>,
+}
+
+impl<H: Hasher, L> LayerHashedMerkleTree<H, L> {
+    /// Returns the underlying Merkle tree.
+    pub fn tree(&self) -> &MerkleTree<H> {
+        &self.tree
+    }
+
+    /// Consumes this commitment and returns the underlying Merkle tree.
+    pub fn into_tree(self) -> MerkleTree<H> {
+        self.tree
+    }
+}
+
+impl<H, L> VectorCommitment<H> for LayerHashedMerkleTree<H, L>
+where
+    H: Hasher,
+    L: MerkleLayerHasher<H> + Default,
+{
+    type Options = L;
+
+    type Proof = Vec<H::Digest>;
+
+    type MultiProof = BatchMerkleProof<H>;
+
+    type Error = MerkleTreeError;
+
+    fn with_options(items: Vec<H::Digest>, options: Self::Options) -> Result<Self, Self::Error> {
+        Ok(Self {
+            tree: MerkleTree::with_layer_hasher(items, &options)?,
+            _layer_hasher: PhantomData,
+        })
+    }
+
+    fn commitment(&self) -> H::Digest {
+        *self.tree.root()
+    }
+
+    fn domain_len(&self) -> usize {
+        self.tree.domain_len()
+    }
+
+    fn get_proof_domain_len(proof: &Self::Proof) -> usize {
+        MerkleTree::<H>::get_proof_domain_len(proof)
+    }
+
+    fn get_multiproof_domain_len(proof: &Self::MultiProof) -> usize {
+        MerkleTree::<H>::get_multiproof_domain_len(proof)
+    }
+
+    fn open(&self, index: usize) -> Result<(H::Digest, Self::Proof), Self::Error> {
+        self.tree.prove(index)
+    }
+
+    fn open_many(
+        &self,
+        indexes: &[usize],
+    ) -> Result<(Vec<H::Digest>, Self::MultiProof), Self::Error> {
+        self.tree.prove_batch(indexes)
+    }
+
+    fn verify(
+        commitment: H::Digest,
+        index: usize,
+        item: H::Digest,
+        proof: &Self::Proof,
+    ) -> Result<(), Self::Error> {
+        MerkleTree::<H>::verify(commitment, index, item, proof)
+    }
+
+    fn verify_many(
+        commitment: H::Digest,
+        indexes: &[usize],
+        items: &[H::Digest],
+        proof: &Self::MultiProof,
+    ) -> Result<(), Self::Error> {
+        MerkleTree::<H>::verify_batch(&commitment, indexes, items, proof)
+    }
+}
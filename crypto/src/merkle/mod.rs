@@ -12,7 +12,27 @@ use core::slice;
 mod proofs;
 pub use proofs::BatchMerkleProof;
 
-use crate::{Hasher, MerkleTreeError, VectorCommitment};
+mod builder;
+pub use builder::MerkleTreeBuilder;
+
+#[cfg(feature = "std")]
+mod disk;
+#[cfg(feature = "std")]
+pub use disk::{DiskMerkleTree, DiskMerkleTreeOptions};
+
+mod forest;
+pub use forest::{MerkleForest, MerkleForestProof};
+
+mod layer;
+pub use layer::{CpuLayerHasher, LayerHashedMerkleTree, MerkleLayerHasher};
+
+mod kary;
+pub use kary::{KaryBatchMerkleProof, KaryMerkleTree};
+
+mod salted;
+pub use salted::{SaltedMerkleTree, SaltedMerkleTreeOptions};
+
+use crate::{Hasher, MerkleTreeError, VectorCommitment, HASH_BATCH_SIZE};
 
 #[cfg(feature = "concurrent")]
 pub mod concurrent;
@@ -134,6 +154,85 @@ impl<H: Hasher> MerkleTree<H> {
         Ok(MerkleTree { nodes, leaves })
     }
 
+    /// Returns new Merkle tree built from the provided leaves, with parents of the leaves
+    /// computed in tasks of `chunk_size` nodes each.
+    ///
+    /// This is the same as [MerkleTree::new()] but allows tuning the granularity of work when the
+    /// tree is built using multiple threads (see `concurrent::build_merkle_nodes_with_chunk_size()`).
+    /// When `concurrent` feature is not enabled, `chunk_size` is ignored.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * Fewer than two leaves were provided.
+    /// * Number of leaves is not a power of two.
+    ///
+    /// # Panics
+    /// Panics if `chunk_size` is zero.
+    pub fn with_chunk_size(
+        leaves: Vec<H::Digest>,
+        chunk_size: usize,
+    ) -> Result<Self, MerkleTreeError> {
+        assert!(chunk_size > 0, "chunk size must be greater than zero");
+        if leaves.len() < 2 {
+            return Err(MerkleTreeError::TooFewLeaves(2, leaves.len()));
+        }
+        if !leaves.len().is_power_of_two() {
+            return Err(MerkleTreeError::NumberOfLeavesNotPowerOfTwo(leaves.len()));
+        }
+
+        #[cfg(not(feature = "concurrent"))]
+        let nodes = build_merkle_nodes::<H>(&leaves);
+
+        #[cfg(feature = "concurrent")]
+        let nodes = if leaves.len() <= concurrent::MIN_CONCURRENT_LEAVES {
+            build_merkle_nodes::<H>(&leaves)
+        } else {
+            concurrent::build_merkle_nodes_with_chunk_size::<H>(&leaves, chunk_size)
+        };
+
+        Ok(MerkleTree { nodes, leaves })
+    }
+
+    /// Returns new Merkle tree built from the provided leaves with every layer of internal nodes
+    /// hashed by the specified `layer_hasher`.
+    ///
+    /// This allows delegating tree construction to hardware which can hash many nodes at once
+    /// (e.g., a GPU) by providing a custom implementation of [MerkleLayerHasher]. The resulting
+    /// tree is identical to the tree built by [MerkleTree::new()]. The STARK prover builds trees
+    /// this way when [LayerHashedMerkleTree] is used as its vector commitment scheme.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * Fewer than two leaves were provided.
+    /// * Number of leaves is not a power of two.
+    pub fn with_layer_hasher<L: MerkleLayerHasher<H>>(
+        leaves: Vec<H::Digest>,
+        layer_hasher: &L,
+    ) -> Result<Self, MerkleTreeError> {
+        if leaves.len() < 2 {
+            return Err(MerkleTreeError::TooFewLeaves(2, leaves.len()));
+        }
+        if !leaves.len().is_power_of_two() {
+            return Err(MerkleTreeError::NumberOfLeavesNotPowerOfTwo(leaves.len()));
+        }
+
+        // build first layer of internal nodes (parents of leaves)
+        let mut nodes = vec![H::Digest::default(); leaves.len()];
+        let mut layer_len = leaves.len() / 2;
+        layer_hasher.hash_layer(as_pairs(&leaves), &mut nodes[layer_len..]);
+
+        // build all other layers; nodes of a layer are located at positions [k, 2k), and their
+        // children at positions [2k, 4k)
+        while layer_len > 1 {
+            let (parents, children) = nodes.split_at_mut(layer_len);
+            layer_hasher
+                .hash_layer(as_pairs(&children[..layer_len]), &mut parents[layer_len / 2..]);
+            layer_len /= 2;
+        }
+
+        Ok(MerkleTree { nodes, leaves })
+    }
+
     /// Forms a MerkleTree from a list of nodes and leaves.
     ///
     /// Nodes are supplied as a vector where the root is stored at position 1.
@@ -159,6 +258,56 @@ impl<H: Hasher> MerkleTree<H> {
         Ok(MerkleTree { nodes, leaves })
     }
 
+    /// Returns a new Merkle tree assembled from the provided subtrees.
+    ///
+    /// The subtrees are assumed to be built over consecutive slices of the leaves (i.e., the
+    /// leaves of the first subtree come first, followed by the leaves of the second subtree etc.).
+    /// The resulting tree is identical to the tree built by [MerkleTree::new()] from the leaves of
+    /// all subtrees. Thus, hashing of a large tree can be split across multiple machines: each
+    /// machine builds a subtree over a contiguous slice of the leaves, and the subtrees are then
+    /// combined into a single tree by hashing only the topmost `log2(subtrees.len())` layers.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * No subtrees were provided, or the number of subtrees is not a power of two.
+    /// * Not all subtrees have the same depth.
+    pub fn from_subtrees(subtrees: Vec<MerkleTree<H>>) -> Result<Self, MerkleTreeError> {
+        let num_subtrees = subtrees.len();
+        if !num_subtrees.is_power_of_two() {
+            return Err(MerkleTreeError::InvalidNumberOfSubtrees(num_subtrees));
+        }
+        let subtree_depth = subtrees[0].depth();
+        for subtree in subtrees.iter() {
+            if subtree.depth() != subtree_depth {
+                return Err(MerkleTreeError::SubtreeDepthMismatch(subtree_depth, subtree.depth()));
+            }
+        }
+
+        let num_leaves = num_subtrees << subtree_depth;
+        let mut nodes = vec![H::Digest::default(); num_leaves];
+        let mut leaves = Vec::with_capacity(num_leaves);
+
+        // copy nodes of each subtree into the corresponding positions of the combined tree; a
+        // layer of 2^d nodes in a subtree is a part of a layer of k * 2^d nodes in the combined
+        // tree, where k is the number of subtrees
+        for (i, subtree) in subtrees.into_iter().enumerate() {
+            for d in 0..subtree_depth {
+                let layer_len = 1 << d;
+                let source = &subtree.nodes[layer_len..2 * layer_len];
+                let offset = num_subtrees * layer_len + i * layer_len;
+                nodes[offset..offset + layer_len].copy_from_slice(source);
+            }
+            leaves.extend_from_slice(&subtree.leaves);
+        }
+
+        // hash the topmost layers of the combined tree
+        for i in (1..num_subtrees).rev() {
+            nodes[i] = H::merge(&[nodes[2 * i], nodes[2 * i + 1]]);
+        }
+
+        Ok(MerkleTree { nodes, leaves })
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -312,13 +461,22 @@ impl<H: Hasher> MerkleTree<H> {
     /// Checks whether the batch `proof` contains Merkle proofs resolving to `root` for
     /// the provided `leaves` at the specified `indexes`.
     ///
+    /// This can be used to verify batch proofs generated by [MerkleTree::prove_batch()] outside
+    /// of the STARK protocol. Errors identify the reason for which the proof was rejected, and
+    /// for incomplete proofs, the leaf which could not be authenticated.
+    ///
     /// # Errors
     /// Returns an error if:
     /// * No indexes were provided (i.e., `indexes` is an empty slice).
+    /// * The number of `leaves` is not equal to the number of `indexes`.
     /// * Any of the specified `indexes` is greater than or equal to the number of leaves in the
     ///   tree from which the batch proof was generated.
     /// * List of indexes contains duplicates.
-    /// * Any of the proofs in the batch proof does not resolve to the specified `root`.
+    /// * The proof does not contain the nodes needed to authenticate one of the leaves; the error
+    ///   contains the index of this leaf.
+    /// * The proof resolves to a root different from the specified `root`. Since the paths of
+    ///   all leaves in a batch proof share nodes, the leaf or node which caused the mismatch
+    ///   cannot be identified in this case.
     pub fn verify_batch(
         root: &H::Digest,
         indexes: &[usize],
@@ -353,9 +511,7 @@ pub fn build_merkle_nodes<H: Hasher>(leaves: &[H::Digest]) -> Vec<H::Digest> {
     let two_leaves = unsafe { slice::from_raw_parts(leaves.as_ptr() as *const [H::Digest; 2], n) };
 
     // build first row of internal nodes (parents of leaves)
-    for (i, j) in (0..n).zip(n..nodes.len()) {
-        nodes[j] = H::merge(&two_leaves[i]);
-    }
+    merge_pairs::<H>(two_leaves, &mut nodes[n..]);
 
     // re-interpret nodes as an array of two nodes fused together
     let two_nodes = unsafe { slice::from_raw_parts(nodes.as_ptr() as *const [H::Digest; 2], n) };
@@ -368,6 +524,26 @@ pub fn build_merkle_nodes<H: Hasher>(leaves: &[H::Digest]) -> Vec<H::Digest> {
     nodes
 }
 
+/// Writes merges of all `pairs` into `nodes`, merging [HASH_BATCH_SIZE] pairs at a time.
+pub(crate) fn merge_pairs<H: Hasher>(pairs: &[[H::Digest; 2]], nodes: &mut [H::Digest]) {
+    debug_assert_eq!(pairs.len(), nodes.len());
+    let pair_batches = pairs.chunks_exact(HASH_BATCH_SIZE);
+    let remainder = pair_batches.remainder();
+    let mut node_batches = nodes.chunks_exact_mut(HASH_BATCH_SIZE);
+    for (pairs, nodes) in pair_batches.zip(&mut node_batches) {
+        nodes.copy_from_slice(&H::merge_batch::<HASH_BATCH_SIZE>(pairs.try_into().unwrap()));
+    }
+    for (pair, node) in remainder.iter().zip(node_batches.into_remainder()) {
+        *node = H::merge(pair);
+    }
+}
+
+/// Re-interprets the provided nodes as an array of two nodes fused together.
+fn as_pairs<D>(nodes: &[D]) -> &[[D; 2]] {
+    debug_assert_eq!(nodes.len() % 2, 0);
+    unsafe { slice::from_raw_parts(nodes.as_ptr() as *const [D; 2], nodes.len() / 2) }
+}
+
 fn map_indexes(
     indexes: &[usize],
     tree_depth: usize,
@@ -19,6 +19,10 @@ use crate::{errors::MerkleTreeError, Hasher};
 /// it is possible to achieve non-negligible compression as compared to naively concatenating
 /// individual Merkle proofs. The algorithm is for aggregation is a variation of
 /// [Octopus](https://eprint.iacr.org/2017/933).
+///
+/// Specifically, sibling nodes shared between multiple paths are included only once, and nodes
+/// which can be computed from the opened leaves (or from the nodes of other paths) are omitted.
+/// Thus, the proof contains the smallest set of nodes required to compute the root of the tree.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct BatchMerkleProof<H: Hasher> {
     /// Hashes of Merkle Tree proof values above the leaf layer
@@ -103,9 +107,12 @@ impl<H: Hasher> BatchMerkleProof<H> {
     /// # Errors
     /// Returns an error if:
     /// * No indexes were provided (i.e., `indexes` is an empty slice).
+    /// * The number of `leaves` is not equal to the number of `indexes`.
     /// * Any of the specified `indexes` is greater than or equal to the number of leaves in the
     ///   tree for which this batch proof was generated.
     /// * List of indexes contains duplicates.
+    /// * The proof does not contain the nodes needed to authenticate one of the leaves; the error
+    ///   contains the index of this leaf.
     /// * The proof does not resolve to a single root.
     pub fn get_root(
         &self,
@@ -115,6 +122,9 @@ impl<H: Hasher> BatchMerkleProof<H> {
         if indexes.is_empty() {
             return Err(MerkleTreeError::TooFewLeafIndexes);
         }
+        if indexes.len() != leaves.len() {
+            return Err(MerkleTreeError::LeafCountMismatch(indexes.len(), leaves.len()));
+        }
 
         let mut buf = [H::Digest::default(); 2];
         let mut v = BTreeMap::new();
@@ -130,7 +140,17 @@ impl<H: Hasher> BatchMerkleProof<H> {
         let offset = 2usize.pow(self.depth as u32);
         let mut next_indexes: Vec<usize> = Vec::new();
         let mut proof_pointers: Vec<usize> = Vec::with_capacity(indexes.len());
+        // for each node being computed, the index of an opened leaf in the subtree of this node;
+        // this is used to report which leaf cannot be authenticated if the proof is incomplete
+        let mut leaf_indexes: Vec<usize> = Vec::with_capacity(indexes.len());
         for (i, index) in indexes.into_iter().enumerate() {
+            let leaf_index = if index_map.contains_key(&index) {
+                index
+            } else {
+                index + 1
+            };
+            leaf_indexes.push(leaf_index);
+
             // copy values of leaf sibling leaf nodes into the buffer
             match index_map.get(&index) {
                 Some(&index1) => {
@@ -148,7 +168,7 @@ impl<H: Hasher> BatchMerkleProof<H> {
                         },
                         None => {
                             if self.nodes[i].is_empty() {
-                                return Err(MerkleTreeError::InvalidProof);
+                                return Err(MerkleTreeError::MissingProofNodes(leaf_index));
                             }
                             buf[1] = self.nodes[i][0];
                             proof_pointers.push(1);
@@ -157,7 +177,7 @@ impl<H: Hasher> BatchMerkleProof<H> {
                 },
                 None => {
                     if self.nodes[i].is_empty() {
-                        return Err(MerkleTreeError::InvalidProof);
+                        return Err(MerkleTreeError::MissingProofNodes(leaf_index));
                     }
                     buf[0] = self.nodes[i][0];
                     match index_map.get(&(index + 1)) {
@@ -185,6 +205,7 @@ impl<H: Hasher> BatchMerkleProof<H> {
         for _ in 1..self.depth {
             let indexes = next_indexes.clone();
             next_indexes.truncate(0);
+            let subtree_leaf_indexes = core::mem::take(&mut leaf_indexes);
 
             let mut i = 0;
             while i < indexes.len() {
@@ -202,7 +223,7 @@ impl<H: Hasher> BatchMerkleProof<H> {
                 } else {
                     let pointer = proof_pointers[i];
                     if self.nodes[i].len() <= pointer {
-                        return Err(MerkleTreeError::InvalidProof);
+                        return Err(MerkleTreeError::MissingProofNodes(subtree_leaf_indexes[i]));
                     }
                     sibling = self.nodes[i][pointer];
                     proof_pointers[i] += 1;
@@ -228,6 +249,7 @@ impl<H: Hasher> BatchMerkleProof<H> {
                 let parent_index = node_index >> 1;
                 v.insert(parent_index, parent);
                 next_indexes.push(parent_index);
+                leaf_indexes.push(subtree_leaf_indexes[i]);
 
                 i += 1;
             }
@@ -411,12 +433,7 @@ impl<H: Hasher> Deserializable for BatchMerkleProof<H> {
         let depth = source.read_u8()?;
         let num_node_vectors = source.read_usize()?;
 
-        let mut nodes = Vec::with_capacity(num_node_vectors);
-        for _ in 0..num_node_vectors {
-            // read the digests and add them to the node vector
-            let digests = Vec::<_>::read_from(source)?;
-            nodes.push(digests);
-        }
+        let nodes = source.read_many(num_node_vectors)?;
 
         Ok(BatchMerkleProof { nodes, depth })
     }
@@ -0,0 +1,200 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::vec::Vec;
+
+use super::{BatchMerkleProof, MerkleTree};
+use crate::{Hasher, MerkleTreeError, VectorCommitment};
+
+// SALTED MERKLE TREE
+// ================================================================================================
+
+/// A Merkle tree in which every leaf is hashed together with a random salt.
+///
+/// Given a vector of items (v_0, ..., v_{n-1}), the leaves of the tree are computed as
+/// hash(v_i || s_i), where s_i is a salt derived from a secret 32-byte seed as
+/// hash(hash(seed) || i). Salts are revealed only for the positions which are opened. Thus,
+/// neither the commitment nor the opening proofs leak any information about the items at
+/// positions which have not been opened; this makes the tree a hiding vector commitment.
+///
+/// When used as the vector commitment scheme of a STARK prover, this hides values of the
+/// extended execution trace and of the constraint evaluations at positions which have not been
+/// queried. FRI layers are committed to using the same scheme, and thus, are salted as well.
+///
+/// By default, the salt seed is drawn from the randomness of the operating system. This requires
+/// the `std` feature; without it, the seed must be provided explicitly via
+/// [SaltedMerkleTreeOptions::with_seed()]. A seed must never be reused for different vectors.
+#[derive(Debug)]
+pub struct SaltedMerkleTree<H: Hasher> {
+    tree: MerkleTree<H>,
+    items: Vec<H::Digest>,
+    salt_seed: H::Digest,
+}
+
+/// Options for building a [SaltedMerkleTree].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SaltedMerkleTreeOptions {
+    seed: Option<[u8; 32]>,
+}
+
+impl SaltedMerkleTreeOptions {
+    /// Returns options which derive the salts of a tree from the specified seed.
+    pub const fn with_seed(seed: [u8; 32]) -> Self {
+        Self { seed: Some(seed) }
+    }
+}
+
+impl<H: Hasher> SaltedMerkleTree<H> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new salted Merkle tree committing to the provided items, with salts derived from
+    /// the specified `seed`.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * Fewer than two items were provided.
+    /// * Number of items is not a power of two.
+    pub fn with_seed(items: Vec<H::Digest>, seed: [u8; 32]) -> Result<Self, MerkleTreeError> {
+        let salt_seed = H::hash(&seed);
+        let leaves = items
+            .iter()
+            .enumerate()
+            .map(|(i, &item)| H::merge(&[item, get_salt::<H>(salt_seed, i)]))
+            .collect();
+        let tree = MerkleTree::new(leaves)?;
+
+        Ok(Self { tree, items, salt_seed })
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the root of the tree.
+    pub fn root(&self) -> &H::Digest {
+        self.tree.root()
+    }
+
+    /// Returns the items committed to by this tree.
+    pub fn items(&self) -> &[H::Digest] {
+        &self.items
+    }
+
+    /// Returns the salt of the item at the specified `index`.
+    ///
+    /// # Errors
+    /// Returns an error if the specified index is greater than or equal to the number of items
+    /// in the tree.
+    pub fn salt(&self, index: usize) -> Result<H::Digest, MerkleTreeError> {
+        if index >= self.items.len() {
+            return Err(MerkleTreeError::LeafIndexOutOfBounds(self.items.len(), index));
+        }
+        Ok(get_salt::<H>(self.salt_seed, index))
+    }
+}
+
+// VECTOR COMMITMENT IMPLEMENTATION
+// ================================================================================================
+
+impl<H: Hasher> VectorCommitment<H> for SaltedMerkleTree<H> {
+    type Options = SaltedMerkleTreeOptions;
+
+    /// Salt of the opened item and a Merkle path from the salted leaf to the root.
+    type Proof = (H::Digest, Vec<H::Digest>);
+
+    /// Salts of the opened items and a batch Merkle proof for the salted leaves.
+    type MultiProof = (Vec<H::Digest>, BatchMerkleProof<H>);
+
+    type Error = MerkleTreeError;
+
+    const IS_HIDING: bool = true;
+
+    fn with_options(items: Vec<H::Digest>, options: Self::Options) -> Result<Self, Self::Error> {
+        let seed = match options.seed {
+            Some(seed) => seed,
+            None => random_seed()?,
+        };
+        Self::with_seed(items, seed)
+    }
+
+    fn commitment(&self) -> H::Digest {
+        *self.root()
+    }
+
+    fn domain_len(&self) -> usize {
+        self.items.len()
+    }
+
+    fn get_proof_domain_len(proof: &Self::Proof) -> usize {
+        1 << proof.1.len()
+    }
+
+    fn get_multiproof_domain_len(proof: &Self::MultiProof) -> usize {
+        1 << proof.1.depth
+    }
+
+    fn open(&self, index: usize) -> Result<(H::Digest, Self::Proof), Self::Error> {
+        let (_, path) = self.tree.prove(index)?;
+        Ok((self.items[index], (self.salt(index)?, path)))
+    }
+
+    fn open_many(
+        &self,
+        indexes: &[usize],
+    ) -> Result<(Vec<H::Digest>, Self::MultiProof), Self::Error> {
+        let (_, proof) = self.tree.prove_batch(indexes)?;
+        let items = indexes.iter().map(|&i| self.items[i]).collect();
+        let salts = indexes.iter().map(|&i| get_salt::<H>(self.salt_seed, i)).collect();
+        Ok((items, (salts, proof)))
+    }
+
+    fn verify(
+        commitment: H::Digest,
+        index: usize,
+        item: H::Digest,
+        proof: &Self::Proof,
+    ) -> Result<(), Self::Error> {
+        let (salt, path) = proof;
+        MerkleTree::<H>::verify(commitment, index, H::merge(&[item, *salt]), path)
+    }
+
+    fn verify_many(
+        commitment: H::Digest,
+        indexes: &[usize],
+        items: &[H::Digest],
+        proof: &Self::MultiProof,
+    ) -> Result<(), Self::Error> {
+        let (salts, proof) = proof;
+        if salts.len() != items.len() {
+            return Err(MerkleTreeError::InvalidProof);
+        }
+        let leaves: Vec<_> =
+            items.iter().zip(salts).map(|(&item, &salt)| H::merge(&[item, salt])).collect();
+        MerkleTree::<H>::verify_batch(&commitment, indexes, &leaves, proof)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the salt for the item at the specified index.
+fn get_salt<H: Hasher>(salt_seed: H::Digest, index: usize) -> H::Digest {
+    H::merge_with_int(salt_seed, index as u64)
+}
+
+/// Returns a seed drawn from the randomness of the operating system.
+#[cfg(feature = "std")]
+fn random_seed() -> Result<[u8; 32], MerkleTreeError> {
+    let mut seed = [0; 32];
+    getrandom::getrandom(&mut seed).map_err(|_| MerkleTreeError::SaltSeedUnavailable)?;
+    Ok(seed)
+}
+
+/// Returns an error as randomness of the operating system is not available without the `std`
+/// feature.
+#[cfg(not(feature = "std"))]
+fn random_seed() -> Result<[u8; 32], MerkleTreeError> {
+    Err(MerkleTreeError::SaltSeedUnavailable)
+}
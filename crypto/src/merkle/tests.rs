@@ -3,10 +3,14 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use core::cell::RefCell;
+
 use math::fields::f128::BaseElement;
 use proptest::prelude::*;
+use utils::{Deserializable, Serializable};
 
 use super::*;
+use crate::Digest;
 
 type Digest256 = crate::hash::ByteDigest<32>;
 type Blake3_256 = crate::hash::Blake3_256<BaseElement>;
@@ -210,6 +214,31 @@ fn verify_batch() {
     assert!(
         MerkleTree::verify_batch(tree.root(), &[0, 1, 2, 3, 4, 5, 6, 7], &leaves, &proof).is_ok()
     );
+
+    // errors identify why the proof was rejected
+    let (leaves, mut proof) = tree.prove_batch(&[1, 6]).unwrap();
+    assert_eq!(
+        Err(MerkleTreeError::LeafCountMismatch(2, 1)),
+        MerkleTree::verify_batch(tree.root(), &[1, 6], &leaves[..1], &proof)
+    );
+    assert_eq!(
+        Err(MerkleTreeError::LeafIndexOutOfBounds(8, 9)),
+        MerkleTree::verify_batch(tree.root(), &[1, 9], &leaves, &proof)
+    );
+    assert_eq!(
+        Err(MerkleTreeError::InvalidProof),
+        MerkleTree::verify_batch(tree.root(), &[6, 1], &leaves, &proof)
+    );
+    proof.nodes[1].pop();
+    assert_eq!(
+        Err(MerkleTreeError::MissingProofNodes(6)),
+        MerkleTree::verify_batch(tree.root(), &[1, 6], &leaves, &proof)
+    );
+    proof.nodes[0].clear();
+    assert_eq!(
+        Err(MerkleTreeError::MissingProofNodes(1)),
+        MerkleTree::verify_batch(tree.root(), &[1, 6], &leaves, &proof)
+    );
 }
 
 #[test]
@@ -254,6 +283,366 @@ fn from_proofs() {
     assert_eq!(proof1.depth, proof2.depth);
 }
 
+#[test]
+fn batch_proof_serialization() {
+    let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+    let tree = MerkleTree::<Blake3_256>::new(leaves).unwrap();
+    let (_, proof) = tree.prove_batch(&[1, 2, 6]).unwrap();
+    let bytes = proof.to_bytes();
+    assert_eq!(proof, BatchMerkleProof::<Blake3_256>::read_from_bytes(&bytes).unwrap());
+
+    // the number of node vectors is read from the untrusted source; a huge number results in an
+    // error once the source is exhausted rather than in an attempt to allocate memory upfront
+    let mut bytes = vec![proof.depth];
+    bytes.extend_from_slice(&(usize::MAX >> 8).to_bytes());
+    bytes.extend_from_slice(&proof.to_bytes()[1..]);
+    assert!(BatchMerkleProof::<Blake3_256>::read_from_bytes(&bytes).is_err());
+}
+
+#[test]
+fn tree_builder() {
+    let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+    let expected = MerkleTree::<Blake3_256>::new(leaves.clone()).unwrap();
+
+    // leaves can be appended in chunks of any size
+    for chunks in [vec![8], vec![1, 1, 1, 1, 1, 1, 1, 1], vec![3, 0, 4, 1], vec![5, 3]] {
+        let mut builder = MerkleTreeBuilder::<Blake3_256>::new(8).unwrap();
+        let mut offset = 0;
+        for chunk in chunks {
+            builder.append(&leaves[offset..offset + chunk]).unwrap();
+            offset += chunk;
+            assert_eq!(offset, builder.num_appended());
+        }
+        let tree = builder.build().unwrap();
+        assert_eq!(expected.root(), tree.root());
+        assert_eq!(expected.leaves(), tree.leaves());
+        assert_eq!(expected.prove_batch(&[1, 6]).unwrap(), tree.prove_batch(&[1, 6]).unwrap());
+    }
+
+    // too many or too few leaves
+    let mut builder = MerkleTreeBuilder::<Blake3_256>::new(8).unwrap();
+    builder.append(&leaves[..5]).unwrap();
+    assert_eq!(Err(MerkleTreeError::LeafIndexOutOfBounds(8, 9)), builder.append(&leaves[..5]));
+    assert_eq!(5, builder.num_appended());
+    assert_eq!(Err(MerkleTreeError::TooFewLeaves(8, 5)), builder.build().map(|_| ()));
+
+    assert_eq!(
+        Err(MerkleTreeError::NumberOfLeavesNotPowerOfTwo(6)),
+        MerkleTreeBuilder::<Blake3_256>::new(6).map(|_| ())
+    );
+}
+
+#[test]
+fn tree_from_subtrees() {
+    let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+    let expected = MerkleTree::<Blake3_256>::new(leaves.clone()).unwrap();
+
+    for num_subtrees in [1, 2, 4] {
+        let subtrees = leaves
+            .chunks(8 / num_subtrees)
+            .map(|chunk| MerkleTree::<Blake3_256>::new(chunk.to_vec()).unwrap())
+            .collect();
+        let tree = MerkleTree::from_subtrees(subtrees).unwrap();
+        assert_eq!(expected.root(), tree.root());
+        assert_eq!(expected.leaves(), tree.leaves());
+        assert_eq!(expected.prove_batch(&[1, 6]).unwrap(), tree.prove_batch(&[1, 6]).unwrap());
+    }
+
+    // invalid number of subtrees or subtrees of different depths
+    assert_eq!(
+        Err(MerkleTreeError::InvalidNumberOfSubtrees(0)),
+        MerkleTree::<Blake3_256>::from_subtrees(Vec::new()).map(|_| ())
+    );
+    let subtrees = vec![
+        MerkleTree::<Blake3_256>::new(leaves[..4].to_vec()).unwrap(),
+        MerkleTree::<Blake3_256>::new(leaves[4..6].to_vec()).unwrap(),
+    ];
+    assert_eq!(
+        Err(MerkleTreeError::SubtreeDepthMismatch(2, 1)),
+        MerkleTree::from_subtrees(subtrees).map(|_| ())
+    );
+}
+
+#[test]
+fn tree_with_layer_hasher() {
+    struct CountingLayerHasher(RefCell<Vec<usize>>);
+
+    impl MerkleLayerHasher<Blake3_256> for CountingLayerHasher {
+        fn hash_layer(&self, children: &[[Digest256; 2]], parents: &mut [Digest256]) {
+            self.0.borrow_mut().push(parents.len());
+            MerkleLayerHasher::<Blake3_256>::hash_layer(&CpuLayerHasher, children, parents);
+        }
+    }
+
+    let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+    let expected = MerkleTree::<Blake3_256>::new(leaves.clone()).unwrap();
+
+    let tree =
+        MerkleTree::<Blake3_256>::with_layer_hasher(leaves.clone(), &CpuLayerHasher).unwrap();
+    assert_eq!(expected.nodes, tree.nodes);
+
+    // layers are hashed bottom up, one layer per call
+    let layer_hasher = CountingLayerHasher(RefCell::new(Vec::new()));
+    let tree = MerkleTree::with_layer_hasher(leaves, &layer_hasher).unwrap();
+    assert_eq!(expected.nodes, tree.nodes);
+    assert_eq!(vec![4, 2, 1], layer_hasher.0.into_inner());
+}
+
+#[test]
+fn layer_hashed_tree_commitment() {
+    type LayerHashedTree = LayerHashedMerkleTree<Blake3_256, CpuLayerHasher>;
+
+    let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+    let expected = MerkleTree::<Blake3_256>::new(leaves.clone()).unwrap();
+    let tree = LayerHashedTree::new(leaves).unwrap();
+    assert_eq!(*expected.root(), tree.commitment());
+    assert_eq!(expected.nodes, tree.tree().nodes);
+
+    // opening proofs are Merkle tree proofs, and thus, can be verified by either scheme
+    let indexes = [1, 2, 6];
+    let (values, proof) = tree.open_many(&indexes).unwrap();
+    let (expected_values, expected_proof) = expected.prove_batch(&indexes).unwrap();
+    assert_eq!(expected_values, values);
+    assert_eq!(expected_proof, proof);
+    assert!(LayerHashedTree::verify_many(tree.commitment(), &indexes, &values, &proof).is_ok());
+    assert!(<MerkleTree<Blake3_256> as VectorCommitment<Blake3_256>>::verify_many(
+        tree.commitment(),
+        &indexes,
+        &values,
+        &proof
+    )
+    .is_ok());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn disk_tree() {
+    // use enough leaves for the tree to be built in several batches
+    let leaves = (0..1_u32 << 15).map(|i| Blake3_256::hash(&i.to_le_bytes())).collect::<Vec<_>>();
+    let expected = MerkleTree::<Blake3_256>::new(leaves.clone()).unwrap();
+
+    for num_cached_layers in [0, 4, 64] {
+        let options = DiskMerkleTreeOptions::default().with_cached_layers(num_cached_layers);
+        let tree = DiskMerkleTree::<Blake3_256>::new(leaves.iter().copied(), options).unwrap();
+        assert_eq!(expected.root(), tree.root());
+        assert_eq!(expected.depth(), tree.depth());
+
+        for index in [0, 1, 12345, (1 << 15) - 1] {
+            assert_eq!(leaves[index], tree.leaf(index).unwrap());
+            assert_eq!(expected.prove(index).unwrap(), tree.prove(index).unwrap());
+        }
+
+        let indexes = [20000, 3, 2, 9, 1 << 14];
+        assert_eq!(expected.prove_batch(&indexes).unwrap(), tree.prove_batch(&indexes).unwrap());
+    }
+
+    let tree = DiskMerkleTree::<Blake3_256>::new(leaves, Default::default()).unwrap();
+    assert_eq!(
+        Err(MerkleTreeError::LeafIndexOutOfBounds(1 << 15, 1 << 15)),
+        tree.prove(1 << 15)
+    );
+    assert_eq!(Err(MerkleTreeError::DuplicateLeafIndex), tree.prove_batch(&[1, 2, 1]));
+    assert_eq!(
+        Err(MerkleTreeError::NumberOfLeavesNotPowerOfTwo(3)),
+        DiskMerkleTree::<Blake3_256>::new(
+            vec![Digest256::default(); 3],
+            DiskMerkleTreeOptions::default()
+        )
+        .map(|_| ())
+    );
+}
+
+#[test]
+fn merkle_forest() {
+    let trees: Vec<_> = [4_u32, 8, 2]
+        .into_iter()
+        .map(|n| {
+            let leaves = (0..n).map(|i| Blake3_256::hash(&(n * 100 + i).to_le_bytes())).collect();
+            MerkleTree::<Blake3_256>::new(leaves).unwrap()
+        })
+        .collect();
+    let roots: Vec<u8> = trees.iter().flat_map(|tree| tree.root().as_bytes()).collect();
+    let forest = MerkleForest::new(trees).unwrap();
+    assert_eq!(3, forest.num_trees());
+    assert_eq!(Blake3_256::hash(&roots), *forest.root());
+
+    // leaves can be opened in any subset of trees
+    let indexes = vec![vec![1, 3], vec![], vec![0]];
+    let (leaves, proof) = forest.prove_batch(&indexes).unwrap();
+    assert_eq!(vec![forest.trees()[0].leaves()[1], forest.trees()[0].leaves()[3]], leaves[0]);
+    assert!(leaves[1].is_empty());
+    assert!(proof.proofs[1].is_none());
+    assert!(MerkleForest::verify_batch(forest.root(), &indexes, &leaves, &proof).is_ok());
+
+    // the proof is bound to the forest root and to the opened leaves
+    let wrong_root = Blake3_256::hash(&[1, 2, 3]);
+    assert!(MerkleForest::verify_batch(&wrong_root, &indexes, &leaves, &proof).is_err());
+    let mut wrong_leaves = leaves.clone();
+    wrong_leaves[2][0] = wrong_root;
+    assert!(MerkleForest::verify_batch(forest.root(), &indexes, &wrong_leaves, &proof).is_err());
+    let wrong_indexes = vec![vec![1, 3], vec![2], vec![0]];
+    assert!(MerkleForest::verify_batch(forest.root(), &wrong_indexes, &leaves, &proof).is_err());
+
+    // the proof survives serialization
+    let bytes = proof.to_bytes();
+    let mut proof = MerkleForestProof::<Blake3_256>::read_from_bytes(&bytes).unwrap();
+    assert!(MerkleForest::verify_batch(forest.root(), &indexes, &leaves, &proof).is_ok());
+    proof.roots.swap(0, 1);
+    assert!(MerkleForest::verify_batch(forest.root(), &indexes, &leaves, &proof).is_err());
+
+    assert_eq!(
+        Err(MerkleTreeError::InvalidNumberOfTrees(3, 2)),
+        forest.prove_batch(&indexes[..2]).map(|_| ())
+    );
+    assert_eq!(
+        Err(MerkleTreeError::EmptyForest),
+        MerkleForest::<Blake3_256>::new(Vec::new()).map(|_| ())
+    );
+}
+
+#[test]
+fn salted_tree() {
+    type SaltedTree = SaltedMerkleTree<Blake3_256>;
+
+    let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+    let options = SaltedMerkleTreeOptions::with_seed([7; 32]);
+    let tree = SaltedTree::with_options(leaves.clone(), options).unwrap();
+    assert_eq!(8, tree.domain_len());
+    assert_eq!(&leaves, tree.items());
+
+    // the same seed results in the same commitment, while a different seed or a random seed
+    // results in a different commitment
+    let tree2 = SaltedTree::with_options(leaves.clone(), options).unwrap();
+    assert_eq!(tree.commitment(), tree2.commitment());
+    let tree3 = SaltedTree::with_seed(leaves.clone(), [8; 32]).unwrap();
+    assert_ne!(tree.commitment(), tree3.commitment());
+    let tree4 = SaltedTree::new(leaves.clone()).unwrap();
+    assert_ne!(tree.commitment(), tree4.commitment());
+
+    // the commitment differs from a commitment to unsalted leaves
+    let unsalted = MerkleTree::<Blake3_256>::new(leaves.clone()).unwrap();
+    assert_ne!(*unsalted.root(), tree.commitment());
+
+    // single openings reveal the salt of the opened item only
+    let (item, proof) = tree.open(5).unwrap();
+    assert_eq!(leaves[5], item);
+    assert_eq!(tree.salt(5).unwrap(), proof.0);
+    assert_eq!(8, SaltedTree::get_proof_domain_len(&proof));
+    assert!(SaltedTree::verify(tree.commitment(), 5, item, &proof).is_ok());
+    assert!(SaltedTree::verify(tree.commitment(), 5, leaves[4], &proof).is_err());
+    assert!(SaltedTree::verify(tree.commitment(), 4, item, &proof).is_err());
+    assert!(
+        SaltedTree::verify(tree.commitment(), 5, item, &(proof.0, proof.1[1..].to_vec())).is_err()
+    );
+
+    // batch openings
+    let indexes = [1, 4, 6];
+    let (items, proof) = tree.open_many(&indexes).unwrap();
+    assert_eq!(vec![leaves[1], leaves[4], leaves[6]], items);
+    assert_eq!(
+        vec![tree.salt(1).unwrap(), tree.salt(4).unwrap(), tree.salt(6).unwrap()],
+        proof.0
+    );
+    assert_eq!(8, SaltedTree::get_multiproof_domain_len(&proof));
+    assert!(SaltedTree::verify_many(tree.commitment(), &indexes, &items, &proof).is_ok());
+
+    // verification fails for a wrong item, a wrong salt, or missing salts
+    let mut bad_items = items.clone();
+    bad_items[1] = leaves[5];
+    assert!(SaltedTree::verify_many(tree.commitment(), &indexes, &bad_items, &proof).is_err());
+    let mut proof = proof;
+    let salts = proof.0.clone();
+    proof.0[1] = salts[0];
+    assert!(SaltedTree::verify_many(tree.commitment(), &indexes, &items, &proof).is_err());
+    proof.0 = salts[..2].to_vec();
+    assert_eq!(
+        Err(MerkleTreeError::InvalidProof),
+        SaltedTree::verify_many(tree.commitment(), &indexes, &items, &proof)
+    );
+
+    // salts of items outside of the tree cannot be retrieved
+    assert_eq!(Err(MerkleTreeError::LeafIndexOutOfBounds(8, 8)), tree.salt(8));
+}
+
+#[test]
+fn kary_tree() {
+    let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+
+    // a 4-ary tree with 8 leaves hashes 2 nodes together at the top level
+    let tree = KaryMerkleTree::<Blake3_256, 4>::new(leaves.clone()).unwrap();
+    assert_eq!(2, tree.depth());
+    let node0 = Blake3_256::merge_many(&leaves[..4]);
+    let node1 = Blake3_256::merge_many(&leaves[4..]);
+    assert_eq!(Blake3_256::merge(&[node0, node1]), *tree.root());
+
+    let (leaf, proof) = tree.prove(6).unwrap();
+    assert_eq!(leaves[6], leaf);
+    assert_eq!(vec![leaves[4], leaves[5], leaves[7], node0], proof);
+    assert_eq!(8, KaryMerkleTree::<Blake3_256, 4>::get_proof_domain_len(&proof));
+    assert!(KaryMerkleTree::<Blake3_256, 4>::verify(*tree.root(), 6, leaf, &proof).is_ok());
+    assert!(KaryMerkleTree::<Blake3_256, 4>::verify(*tree.root(), 5, leaf, &proof).is_err());
+    assert!(KaryMerkleTree::<Blake3_256, 4>::verify(*tree.root(), 6, leaves[5], &proof).is_err());
+
+    // an 8-ary tree with 8 leaves hashes all leaves into the root
+    let tree = KaryMerkleTree::<Blake3_256, 8>::new(leaves.clone()).unwrap();
+    assert_eq!(1, tree.depth());
+    assert_eq!(Blake3_256::merge_many(&leaves), *tree.root());
+
+    // invalid trees
+    assert_eq!(
+        Err(MerkleTreeError::TooFewLeaves(2, 1)),
+        KaryMerkleTree::<Blake3_256, 4>::new(leaves[..1].to_vec()).map(|_| ())
+    );
+    assert_eq!(
+        Err(MerkleTreeError::NumberOfLeavesNotPowerOfTwo(6)),
+        KaryMerkleTree::<Blake3_256, 4>::new(leaves[..6].to_vec()).map(|_| ())
+    );
+}
+
+#[test]
+fn kary_prove_batch() {
+    let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+    let tree = KaryMerkleTree::<Blake3_256, 4>::new(leaves.clone()).unwrap();
+    let node1 = Blake3_256::merge_many(&leaves[4..]);
+
+    // leaves 1 and 2 share the same parent; the parent of leaves 4..8 is included in the proof
+    let (values, proof) = tree.prove_batch(&[2, 1]).unwrap();
+    assert_eq!(vec![leaves[2], leaves[1]], values);
+    assert_eq!(vec![vec![leaves[0], leaves[3]], vec![node1]], proof.nodes);
+    assert_eq!(3, proof.log_num_leaves);
+    assert!(
+        KaryMerkleTree::<Blake3_256, 4>::verify_batch(tree.root(), &[2, 1], &values, &proof)
+            .is_ok()
+    );
+
+    // wrong leaves, indexes, or proofs are rejected
+    let wrong_values = vec![leaves[1], leaves[2]];
+    assert_eq!(
+        Err(MerkleTreeError::InvalidProof),
+        KaryMerkleTree::<Blake3_256, 4>::verify_batch(tree.root(), &[2, 1], &wrong_values, &proof)
+    );
+    assert_eq!(
+        Err(MerkleTreeError::InvalidProof),
+        KaryMerkleTree::<Blake3_256, 4>::verify_batch(tree.root(), &[2, 5], &values, &proof)
+    );
+    let mut proof = proof;
+    proof.nodes[1].push(node1);
+    assert_eq!(
+        Err(MerkleTreeError::InvalidProof),
+        KaryMerkleTree::<Blake3_256, 4>::verify_batch(tree.root(), &[2, 1], &values, &proof)
+    );
+    proof.nodes[1].pop();
+    assert_eq!(Err(MerkleTreeError::DuplicateLeafIndex), tree.prove_batch(&[1, 1]).map(|_| ()));
+    assert_eq!(
+        Err(MerkleTreeError::LeafIndexOutOfBounds(8, 8)),
+        tree.prove_batch(&[1, 8]).map(|_| ())
+    );
+
+    // serialization round-trip
+    let bytes = proof.to_bytes();
+    assert_eq!(proof, KaryBatchMerkleProof::<Blake3_256>::read_from_bytes(&bytes).unwrap());
+}
+
 proptest! {
     #[test]
     fn prove_n_verify(tree in random_blake3_merkle_tree(128),
@@ -275,6 +664,27 @@ proptest! {
         prop_assert!(MerkleTree::verify_batch(tree.root(), &indices[..], &leaves,  &proof).is_ok());
     }
 
+    #[test]
+    fn prove_batch_minimal_nodes(tree in random_blake3_merkle_tree(128),
+                      proof_indices in prop::collection::vec(any::<prop::sample::Index>(), 10..60)
+    )  {
+        let mut indices: Vec<usize> = proof_indices.iter().map(|idx| idx.index(128)).collect();
+        indices.sort_unstable(); indices.dedup();
+        let (_, proof) = tree.prove_batch(&indices[..]).unwrap();
+
+        // a node must be included in the proof only if it is a sibling of a node on one of the
+        // opened paths and it cannot be computed from the other opened paths
+        let mut known: BTreeSet<usize> = indices.iter().cloned().collect();
+        let mut expected_num_nodes = 0;
+        for _ in 0..tree.depth() {
+            expected_num_nodes += known.iter().filter(|&&i| !known.contains(&(i ^ 1))).count();
+            known = known.iter().map(|&i| i >> 1).collect();
+        }
+
+        let num_nodes: usize = proof.nodes.iter().map(|nodes| nodes.len()).sum();
+        prop_assert_eq!(expected_num_nodes, num_nodes);
+    }
+
     #[test]
     fn batch_proof_from_proofs(tree in random_blake3_merkle_tree(128),
                       proof_indices in prop::collection::vec(any::<prop::sample::Index>(), 10..20)
@@ -309,6 +719,42 @@ proptest! {
 
         prop_assert!(proofs_expected == proofs);
     }
+
+    #[test]
+    fn tree_builder_chunks(leaves in prop::collection::vec(any::<[u8; 32]>(), 128),
+                           chunk_size in 1_usize..40
+    )  {
+        let leaves = Digest256::bytes_as_digests(&leaves).to_vec();
+        let mut builder = MerkleTreeBuilder::<Blake3_256>::new(128).unwrap();
+        for chunk in leaves.chunks(chunk_size) {
+            builder.append(chunk).unwrap();
+        }
+        let expected = MerkleTree::<Blake3_256>::new(leaves).unwrap();
+        let tree = builder.build().unwrap();
+        prop_assert_eq!(expected.root(), tree.root());
+    }
+
+    #[test]
+    fn kary_prove_batch_n_verify(leaves in prop::collection::vec(any::<[u8; 32]>(), 128),
+                                 proof_indices in prop::collection::vec(any::<prop::sample::Index>(), 1..30)
+    )  {
+        let leaves = Digest256::bytes_as_digests(&leaves).to_vec();
+        let mut indices: Vec<usize> = proof_indices.iter().map(|idx| idx.index(128)).collect();
+        indices.sort_unstable(); indices.dedup();
+
+        let tree = KaryMerkleTree::<Blake3_256, 4>::new(leaves.clone()).unwrap();
+        let (values, proof) = tree.prove_batch(&indices).unwrap();
+        prop_assert!(KaryMerkleTree::<Blake3_256, 4>::verify_batch(tree.root(), &indices, &values, &proof).is_ok());
+
+        // the batch proof contains only the nodes which cannot be computed from opened leaves
+        let num_paths_nodes: usize = indices.iter().map(|&i| tree.prove(i).unwrap().1.len()).sum();
+        let num_proof_nodes: usize = proof.nodes.iter().map(|nodes| nodes.len()).sum();
+        prop_assert!(num_proof_nodes <= num_paths_nodes);
+        for &i in indices.iter() {
+            let (leaf, path) = tree.prove(i).unwrap();
+            prop_assert!(KaryMerkleTree::<Blake3_256, 4>::verify(*tree.root(), i, leaf, &path).is_ok());
+        }
+    }
 }
 
 // HELPER FUNCTIONS
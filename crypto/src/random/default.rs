@@ -28,6 +28,19 @@ use crate::{errors::RandomCoinError, Digest, ElementHasher, RandomCoin};
 /// - We can also re-seed the coin with a new value. During the reseeding procedure, the
 ///   seed is set to hash(`old_seed` || `new_seed`), and the counter is reset to 0.
 ///
+/// All values drawn from the coin are statistically unbiased. The exact derivation (which other
+/// implementations need to follow to be compatible with this coin) is as follows:
+/// - A field element is derived from the first `E::ELEMENT_BYTES` bytes of hash(`seed` ||
+///   `counter`). The bytes are interpreted as little-endian encodings of the element's base field
+///   coefficients, and the candidate is rejected (i.e., the next counter value is tried) if any of
+///   the coefficients is not smaller than the field modulus.
+/// - To draw integers from [0, domain_size), the seed is first set to hash(`seed` || `nonce`)
+///   and the counter is reset to 0. Each integer is then derived from the first 8 bytes of
+///   hash(`seed` || `counter`) interpreted as a little-endian `u64` value, and masked to the
+///   number of bits needed to represent `domain_size - 1`. If the result is not smaller than
+///   `domain_size` it is rejected and the next counter value is tried; for domains whose size is
+///   a power of two, no values are ever rejected.
+///
 /// # Examples
 /// ```
 /// # use winter_crypto::{RandomCoin, DefaultRandomCoin, Hasher, hashers::Blake3_256};
@@ -157,14 +170,15 @@ impl<B: StarkField, H: ElementHasher<BaseField = B>> RandomCoin for DefaultRando
     /// Returns a vector of integers selected from the range [0, domain_size) after reseeding
     /// the PRNG with the specified `nonce` by setting the new seed to hash(`seed` || `nonce`).
     ///
+    /// Integers are drawn via rejection sampling, and thus are distributed uniformly over the
+    /// range even when `domain_size` is not a power of two.
+    ///
     /// # Errors
     /// Returns an error if the specified number of integers could not be generated after 1000
     /// calls to the PRNG.
     ///
     /// # Panics
-    /// Panics if:
-    /// - `domain_size` is not a power of two.
-    /// - `num_values` is greater than or equal to `domain_size`.
+    /// Panics if `num_values` is greater than or equal to `domain_size`.
     ///
     /// # Examples
     /// ```
@@ -193,7 +207,6 @@ impl<B: StarkField, H: ElementHasher<BaseField = B>> RandomCoin for DefaultRando
         domain_size: usize,
         nonce: u64,
     ) -> Result<Vec<usize>, RandomCoinError> {
-        assert!(domain_size.is_power_of_two(), "domain size must be a power of two");
         assert!(num_values < domain_size, "number of values must be smaller than domain size");
 
         // reseed with nonce
@@ -201,9 +214,9 @@ impl<B: StarkField, H: ElementHasher<BaseField = B>> RandomCoin for DefaultRando
         self.counter = 0;
 
         // determine how many bits are needed to represent valid values in the domain
-        let v_mask = (domain_size - 1) as u64;
+        let v_mask = (domain_size.next_power_of_two() - 1) as u64;
 
-        // draw values from PRNG until we get as many unique values as specified by num_queries
+        // draw values from PRNG until we get as many values as specified by num_values
         let mut values = Vec::new();
         for _ in 0..1000 {
             // get the next pseudo-random value and read the first 8 bytes from it
@@ -213,6 +226,12 @@ impl<B: StarkField, H: ElementHasher<BaseField = B>> RandomCoin for DefaultRando
             // into the specified domain
             let value = (u64::from_le_bytes(bytes) & v_mask) as usize;
 
+            // reject values outside of the domain to avoid biasing the distribution; this can
+            // happen only if the domain size is not a power of two
+            if value >= domain_size {
+                continue;
+            }
+
             values.push(value);
             if values.len() == num_values {
                 break;
@@ -226,3 +245,57 @@ impl<B: StarkField, H: ElementHasher<BaseField = B>> RandomCoin for DefaultRando
         Ok(values)
     }
 }
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use math::fields::f128::BaseElement;
+
+    use super::{DefaultRandomCoin, RandomCoin, Vec};
+    use crate::{hashers::Blake3_256, Digest, ElementHasher, Hasher};
+
+    type Blake3 = Blake3_256<BaseElement>;
+
+    #[test]
+    fn draw_integers_power_of_two_domain() {
+        let seed = [BaseElement::new(1), BaseElement::new(2)];
+        let mut coin = DefaultRandomCoin::<Blake3>::new(&seed);
+        let values = coin.draw_integers(16, 64, 42).unwrap();
+
+        // for power of two domains, each value is derived from a single PRNG output
+        let seed = Blake3::merge_with_int(Blake3::hash_elements(&seed), 42);
+        for (i, &value) in values.iter().enumerate() {
+            let bytes = Blake3::merge_with_int(seed, i as u64 + 1).as_bytes();
+            let expected = u64::from_le_bytes(bytes[..8].try_into().unwrap()) % 64;
+            assert_eq!(expected as usize, value);
+        }
+    }
+
+    #[test]
+    fn draw_integers_non_power_of_two_domain() {
+        let seed = [BaseElement::new(1), BaseElement::new(2)];
+        let mut coin = DefaultRandomCoin::<Blake3>::new(&seed);
+
+        let domain_size = 40;
+        let values = coin.draw_integers(32, domain_size, 0).unwrap();
+        assert_eq!(32, values.len());
+        assert!(values.iter().all(|&value| value < domain_size));
+
+        // values which are out of range are rejected rather than reduced
+        let seed = Blake3::merge_with_int(Blake3::hash_elements(&seed), 0);
+        let mut expected = Vec::new();
+        let mut counter = 0;
+        while expected.len() < values.len() {
+            counter += 1;
+            let bytes = Blake3::merge_with_int(seed, counter).as_bytes();
+            let value = u64::from_le_bytes(bytes[..8].try_into().unwrap()) % 64;
+            if value < domain_size as u64 {
+                expected.push(value as usize);
+            }
+        }
+        assert_eq!(expected, values);
+        assert!(counter > values.len() as u64);
+    }
+}
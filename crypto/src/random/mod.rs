@@ -12,6 +12,12 @@ use crate::{errors::RandomCoinError, ElementHasher, Hasher};
 mod default;
 pub use default::DefaultRandomCoin;
 
+mod sponge;
+pub use sponge::SpongeCoin;
+
+mod transcript;
+pub use transcript::{transcript_labels, TranscriptCoin};
+
 // RANDOM COIN TRAIT
 // ================================================================================================
 
@@ -44,26 +50,39 @@ pub trait RandomCoin: Sync {
 
     /// Returns the next pseudo-random field element.
     ///
+    /// The element must be drawn uniformly at random from the field `E`.
+    ///
     /// # Errors
     /// Returns an error if a valid field element could not be generated after 1000 calls to the
     /// PRNG.
     fn draw<E: FieldElement<BaseField = Self::BaseField>>(&mut self) -> Result<E, RandomCoinError>;
 
-    /// Returns a vector of integers selected from the range [0, domain_size) after it reseeds
-    /// the coin with a nonce.
+    /// Returns a vector of integers selected uniformly at random from the range
+    /// [0, domain_size) after it reseeds the coin with a nonce.
     ///
     /// # Errors
     /// Returns an error if the specified number of integers could not be generated after 1000
     /// calls to the PRNG.
     ///
     /// # Panics
-    /// Panics if:
-    /// - `domain_size` is not a power of two.
-    /// - `num_values` is greater than or equal to `domain_size`.
+    /// Panics if `num_values` is greater than or equal to `domain_size`.
     fn draw_integers(
         &mut self,
         num_values: usize,
         domain_size: usize,
         nonce: u64,
     ) -> Result<Vec<usize>, RandomCoinError>;
+
+    // PROVIDED METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Reseeds the coin with the specified data absorbed as a message with the specified label.
+    ///
+    /// Labels used by the STARK protocol are listed in [transcript_labels]. By default, the label
+    /// is ignored and this is equivalent to [RandomCoin::reseed()]; coins which provide domain
+    /// separation between messages (e.g., [TranscriptCoin]) override this method.
+    fn reseed_with_label(&mut self, label: &str, data: <Self::Hasher as Hasher>::Digest) {
+        let _ = label;
+        self.reseed(data);
+    }
 }
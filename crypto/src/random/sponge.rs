@@ -0,0 +1,282 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::{vec, vec::Vec};
+
+use math::{FieldElement, StarkField};
+
+use crate::{errors::RandomCoinError, AlgebraicSponge, RandomCoin};
+
+// SPONGE COIN
+// ================================================================================================
+
+/// Pseudo-random element generator for finite fields built as a duplex sponge over the
+/// permutation of an algebraic hash function.
+///
+/// Unlike [DefaultRandomCoin](crate::DefaultRandomCoin), this coin never hashes intermediate
+/// seeds or serializes field elements into bytes: messages are absorbed into the sponge state
+/// and challenges are squeezed out of it directly. Thus, the whole transcript can be re-derived
+/// inside an AIR (e.g., by a recursive verifier) with one permutation per absorbed or squeezed
+/// block of [AlgebraicSponge::RATE_RANGE] elements.
+///
+/// The coin works as follows:
+/// - The state is initialized to all zeros.
+/// - A sequence of elements is absorbed by adding its length to the first capacity element of the
+///   state, and then, for every chunk of at most rate-many elements, overwriting the first
+///   elements of the rate portion of the state with the chunk and applying the permutation. An
+///   empty sequence is absorbed with a single permutation. The coin is instantiated by absorbing
+///   `seed`, and reseeded by absorbing the elements of the digest.
+/// - Field elements are squeezed from the rate portion of the state one by one, in order; once
+///   all rate elements have been squeezed, the permutation is applied again. Elements in an
+///   extension field are built from consecutive squeezed base field elements.
+/// - A nonce is absorbed as a sequence of two elements containing its lower and upper 32 bits.
+///   Integers are derived from the lower bits of squeezed elements.
+///
+/// # Examples
+/// ```
+/// # use winter_crypto::{RandomCoin, SpongeCoin, Hasher, hashers::Rp64_256};
+/// # use math::fields::f64::BaseElement;
+/// let seed = &[BaseElement::new(1), BaseElement::new(2)];
+///
+/// let mut coin1 = SpongeCoin::<Rp64_256>::new(seed);
+/// let mut coin2 = SpongeCoin::<Rp64_256>::new(seed);
+///
+/// // should draw the same element from both coins
+/// assert_eq!(coin1.draw::<BaseElement>().unwrap(), coin2.draw::<BaseElement>().unwrap());
+///
+/// // after reseeding should draw different elements
+/// coin2.reseed(Rp64_256::hash(&[1, 2, 3]));
+/// assert_ne!(coin1.draw::<BaseElement>().unwrap(), coin2.draw::<BaseElement>().unwrap());
+/// ```
+pub struct SpongeCoin<H: AlgebraicSponge> {
+    state: Vec<H::BaseField>,
+    output_idx: usize,
+}
+
+impl<H: AlgebraicSponge> SpongeCoin<H> {
+    /// Absorbs the provided elements into the state; after this, outputs are squeezed starting
+    /// with the first element of the rate portion of the state.
+    fn absorb(&mut self, elements: &[H::BaseField]) {
+        self.state[H::CAPACITY_RANGE.start] += H::BaseField::from(elements.len() as u32);
+
+        let rate_width = H::RATE_RANGE.len();
+        let rate_start = H::RATE_RANGE.start;
+        if elements.is_empty() {
+            H::permute(&mut self.state);
+        }
+        for chunk in elements.chunks(rate_width) {
+            self.state[rate_start..rate_start + chunk.len()].copy_from_slice(chunk);
+            H::permute(&mut self.state);
+        }
+
+        self.output_idx = rate_start;
+    }
+
+    /// Returns the next element squeezed from the state.
+    fn squeeze(&mut self) -> H::BaseField {
+        if self.output_idx == H::RATE_RANGE.end {
+            H::permute(&mut self.state);
+            self.output_idx = H::RATE_RANGE.start;
+        }
+        let result = self.state[self.output_idx];
+        self.output_idx += 1;
+        result
+    }
+
+    /// Absorbs the specified `nonce`.
+    fn absorb_nonce(&mut self, nonce: u64) {
+        let nonce = [H::BaseField::from(nonce as u32), H::BaseField::from((nonce >> 32) as u32)];
+        self.absorb(&nonce);
+    }
+}
+
+impl<B, H> RandomCoin for SpongeCoin<H>
+where
+    B: StarkField<PositiveInteger = u64>,
+    H: AlgebraicSponge<BaseField = B>,
+{
+    type BaseField = B;
+    type Hasher = H;
+
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new random coin instantiated by absorbing the provided `seed` into an all-zero
+    /// sponge state.
+    fn new(seed: &[Self::BaseField]) -> Self {
+        let mut coin = Self {
+            state: vec![B::ZERO; H::STATE_WIDTH],
+            output_idx: H::RATE_RANGE.start,
+        };
+        coin.absorb(seed);
+        coin
+    }
+
+    // RESEEDING
+    // --------------------------------------------------------------------------------------------
+
+    /// Reseeds the coin by absorbing the elements of the specified digest.
+    fn reseed(&mut self, data: H::Digest) {
+        self.absorb(H::digest_elements(&data));
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Absorbs `value` into a copy of the state, and returns the number of trailing zeros in the
+    /// canonical representation of the first element squeezed from it.
+    fn check_leading_zeros(&self, value: u64) -> u32 {
+        let mut coin = Self {
+            state: self.state.clone(),
+            output_idx: self.output_idx,
+        };
+        coin.absorb_nonce(value);
+        coin.squeeze().as_int().trailing_zeros()
+    }
+
+    // DRAW METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the next pseudo-random field element built from the next `E::EXTENSION_DEGREE`
+    /// elements squeezed from the sponge.
+    ///
+    /// Squeezed elements are uniformly distributed over the base field, and thus, this method
+    /// never fails.
+    fn draw<E: FieldElement<BaseField = Self::BaseField>>(&mut self) -> Result<E, RandomCoinError> {
+        let elements: Vec<B> = (0..E::EXTENSION_DEGREE).map(|_| self.squeeze()).collect();
+        Ok(E::slice_from_base_elements(&elements)[0])
+    }
+
+    /// Returns a vector of integers selected from the range [0, domain_size) after absorbing the
+    /// specified `nonce`.
+    ///
+    /// Each integer is read from the lower bits of a squeezed element; integers outside of the
+    /// range are rejected, and thus are distributed uniformly over the range even when
+    /// `domain_size` is not a power of two.
+    ///
+    /// # Errors
+    /// Returns an error if the specified number of integers could not be generated after 1000
+    /// squeezed elements.
+    ///
+    /// # Panics
+    /// Panics if `num_values` is greater than or equal to `domain_size`.
+    fn draw_integers(
+        &mut self,
+        num_values: usize,
+        domain_size: usize,
+        nonce: u64,
+    ) -> Result<Vec<usize>, RandomCoinError> {
+        assert!(num_values < domain_size, "number of values must be smaller than domain size");
+
+        self.absorb_nonce(nonce);
+
+        // determine how many bits are needed to represent valid values in the domain
+        let v_mask = (domain_size.next_power_of_two() - 1) as u64;
+
+        let mut values = Vec::new();
+        for _ in 0..1000 {
+            let value = (self.squeeze().as_int() & v_mask) as usize;
+            if value >= domain_size {
+                continue;
+            }
+
+            values.push(value);
+            if values.len() == num_values {
+                break;
+            }
+        }
+
+        if values.len() < num_values {
+            return Err(RandomCoinError::FailedToDrawIntegers(num_values, values.len(), 1000));
+        }
+
+        Ok(values)
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use math::{fields::f64::BaseElement, FieldElement};
+
+    use super::{RandomCoin, SpongeCoin};
+    use crate::{
+        hash::{Rp62_248, Rp64_256},
+        Hasher,
+    };
+
+    #[test]
+    fn sponge_coin_draw() {
+        let seed = [BaseElement::new(1), BaseElement::new(2)];
+        let mut coin = SpongeCoin::<Rp64_256>::new(&seed);
+
+        // the coin squeezes elements directly from the state of the sponge
+        let mut state = [BaseElement::ZERO; Rp64_256::STATE_WIDTH];
+        state[Rp64_256::CAPACITY_RANGE.start] = BaseElement::new(2);
+        state[Rp64_256::RATE_RANGE.start..Rp64_256::RATE_RANGE.start + 2].copy_from_slice(&seed);
+        Rp64_256::apply_permutation(&mut state);
+        for &expected in &state[Rp64_256::RATE_RANGE] {
+            assert_eq!(expected, coin.draw::<BaseElement>().unwrap());
+        }
+
+        // once the rate is exhausted the permutation is applied again
+        Rp64_256::apply_permutation(&mut state);
+        assert_eq!(state[Rp64_256::RATE_RANGE.start], coin.draw::<BaseElement>().unwrap());
+    }
+
+    #[test]
+    fn sponge_coin_reseed() {
+        let seed = [BaseElement::new(1), BaseElement::new(2)];
+        let mut coin1 = SpongeCoin::<Rp64_256>::new(&seed);
+        let mut coin2 = SpongeCoin::<Rp64_256>::new(&seed);
+
+        // reseeding resets the output position, so the number of elements drawn before does
+        // not matter, but the absorbed digest does
+        coin1.draw::<BaseElement>().unwrap();
+        coin1.reseed(Rp64_256::hash(&[1, 2, 3]));
+        coin2.reseed(Rp64_256::hash(&[1, 2, 3]));
+        assert_eq!(coin1.draw::<BaseElement>().unwrap(), coin2.draw::<BaseElement>().unwrap());
+
+        coin1.reseed(Rp64_256::hash(&[1, 2, 3]));
+        coin2.reseed(Rp64_256::hash(&[3, 2, 1]));
+        assert_ne!(coin1.draw::<BaseElement>().unwrap(), coin2.draw::<BaseElement>().unwrap());
+    }
+
+    #[test]
+    fn sponge_coin_draw_integers() {
+        let seed = [BaseElement::new(1), BaseElement::new(2)];
+        let mut coin = SpongeCoin::<Rp64_256>::new(&seed);
+
+        let domain_size = 40;
+        let values = coin.draw_integers(32, domain_size, 42).unwrap();
+        assert_eq!(32, values.len());
+        assert!(values.iter().all(|&value| value < domain_size));
+
+        // the same nonce results in the same values only from the same state
+        let mut coin = SpongeCoin::<Rp64_256>::new(&seed);
+        assert_eq!(values, coin.draw_integers(32, domain_size, 42).unwrap());
+        assert_ne!(values, coin.draw_integers(32, domain_size, 42).unwrap());
+
+        // grinding checks do not change the state
+        let mut coin1 = SpongeCoin::<Rp64_256>::new(&seed);
+        let mut coin2 = SpongeCoin::<Rp64_256>::new(&seed);
+        assert_eq!(coin1.check_leading_zeros(7), coin2.check_leading_zeros(7));
+        coin1.check_leading_zeros(8);
+        assert_eq!(coin1.draw::<BaseElement>().unwrap(), coin2.draw::<BaseElement>().unwrap());
+    }
+
+    #[test]
+    fn sponge_coin_rp62_248() {
+        use math::fields::f62;
+
+        let seed = [f62::BaseElement::new(1)];
+        let mut coin = SpongeCoin::<Rp62_248>::new(&seed);
+        let values = coin.draw_integers(8, 16, 0).unwrap();
+        assert!(values.iter().all(|&value| value < 16));
+        let _ = coin.draw::<f62::BaseElement>().unwrap();
+    }
+}
@@ -0,0 +1,183 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::vec::Vec;
+
+use math::{FieldElement, StarkField};
+
+use super::DefaultRandomCoin;
+use crate::{errors::RandomCoinError, ElementHasher, RandomCoin};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Domain separator absorbed into the transcript right after it is instantiated.
+const DOMAIN_SEPARATOR: &str = "winterfell/transcript/v1";
+
+// TRANSCRIPT LABELS
+// ================================================================================================
+
+pub mod transcript_labels {
+    //! Labels of the messages which the STARK prover and verifier absorb into a random coin.
+    //!
+    //! Messages are absorbed in the following order: [MAIN_TRACE_ROOT], [AUX_TRACE_ROOT] (for
+    //! computations with an auxiliary trace segment), [CONSTRAINT_ROOT], [OOD_TRACE_FRAME],
+    //! [OOD_CONSTRAINT_EVALUATIONS], and [FRI_LAYER_ROOT] for every committed FRI layer.
+    //! Challenges are drawn from the coin in between (see [RandomCoin](crate::RandomCoin)).
+    //! Standalone batched FRI proofs absorb [BATCH_ROOT] followed by [FRI_LAYER_ROOT] for every
+    //! committed FRI layer.
+    //! When DEEP folding is enabled, every [FRI_LAYER_ROOT] except the last one is followed by
+    //! [FRI_DEEP_EVALUATIONS].
+
+    /// Commitment to the extended main trace segment.
+    pub const MAIN_TRACE_ROOT: &str = "main_trace_root";
+    /// Commitment to the extended auxiliary trace segment.
+    pub const AUX_TRACE_ROOT: &str = "aux_trace_root";
+    /// Commitment to the evaluations of the constraint composition polynomial.
+    pub const CONSTRAINT_ROOT: &str = "constraint_root";
+    /// Hash of the trace polynomial evaluations over the out-of-domain evaluation frame.
+    pub const OOD_TRACE_FRAME: &str = "ood_trace_frame";
+    /// Hash of the constraint composition polynomial column evaluations at the out-of-domain
+    /// point.
+    pub const OOD_CONSTRAINT_EVALUATIONS: &str = "ood_constraint_evaluations";
+    /// Commitment to a FRI layer.
+    pub const FRI_LAYER_ROOT: &str = "fri_layer_root";
+    /// Hash of the FRI layer polynomial evaluations at an out-of-domain coset (DEEP folding).
+    pub const FRI_DEEP_EVALUATIONS: &str = "fri_deep_evaluations";
+    /// Commitment to the evaluations of polynomials batched into a single FRI instance.
+    pub const BATCH_ROOT: &str = "batch_root";
+}
+
+// TRANSCRIPT COIN
+// ================================================================================================
+
+/// A random coin which absorbs labeled messages with explicit domain separation.
+///
+/// The coin extends [DefaultRandomCoin] as follows:
+/// - Right after instantiation, the seed is set to hash(`seed` || hash(`DOMAIN_SEPARATOR`)),
+///   where `DOMAIN_SEPARATOR` is the string "winterfell/transcript/v1".
+/// - A message `data` with label `label` is absorbed by setting the seed to hash(`seed` ||
+///   hash(hash(`label`) || `data`)), and resetting the counter to 0. Messages absorbed via
+///   [RandomCoin::reseed()] are treated as messages with an empty label.
+/// - Field elements and integers are drawn exactly as in [DefaultRandomCoin].
+///
+/// Thus, a message absorbed under one label can never be confused with a message absorbed under a
+/// different label, and the transcript does not depend on the layout of the prover's messages
+/// beyond their labels and order. Labels used by the STARK protocol are listed in
+/// [transcript_labels](crate::transcript_labels).
+///
+/// # Examples
+/// ```
+/// # use winter_crypto::{RandomCoin, TranscriptCoin, Hasher, hashers::Blake3_256};
+/// # use math::fields::f128::BaseElement;
+/// type Blake3 = Blake3_256<BaseElement>;
+/// let seed = &[BaseElement::new(1), BaseElement::new(2)];
+///
+/// let mut coin1 = TranscriptCoin::<Blake3>::new(seed);
+/// let mut coin2 = TranscriptCoin::<Blake3>::new(seed);
+///
+/// // the same message absorbed under different labels results in different challenges
+/// coin1.reseed_with_label("trace_root", Blake3::hash(&[1, 2, 3]));
+/// coin2.reseed_with_label("constraint_root", Blake3::hash(&[1, 2, 3]));
+/// assert_ne!(coin1.draw::<BaseElement>().unwrap(), coin2.draw::<BaseElement>().unwrap());
+/// ```
+pub struct TranscriptCoin<H: ElementHasher> {
+    coin: DefaultRandomCoin<H>,
+}
+
+impl<B: StarkField, H: ElementHasher<BaseField = B>> RandomCoin for TranscriptCoin<H> {
+    type BaseField = B;
+    type Hasher = H;
+
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new transcript instantiated with the provided `seed`.
+    fn new(seed: &[Self::BaseField]) -> Self {
+        let mut coin = DefaultRandomCoin::new(seed);
+        coin.reseed(H::hash(DOMAIN_SEPARATOR.as_bytes()));
+        Self { coin }
+    }
+
+    // RESEEDING
+    // --------------------------------------------------------------------------------------------
+
+    /// Absorbs the specified data as a message with an empty label.
+    fn reseed(&mut self, data: H::Digest) {
+        self.reseed_with_label("", data);
+    }
+
+    /// Absorbs the specified data as a message with the specified label by setting the new seed
+    /// to hash(`seed` || hash(hash(`label`) || `data`)).
+    fn reseed_with_label(&mut self, label: &str, data: H::Digest) {
+        let message = H::merge(&[H::hash(label.as_bytes()), data]);
+        self.coin.reseed(message);
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    fn check_leading_zeros(&self, value: u64) -> u32 {
+        self.coin.check_leading_zeros(value)
+    }
+
+    // DRAW METHODS
+    // --------------------------------------------------------------------------------------------
+
+    fn draw<E: FieldElement<BaseField = Self::BaseField>>(&mut self) -> Result<E, RandomCoinError> {
+        self.coin.draw()
+    }
+
+    fn draw_integers(
+        &mut self,
+        num_values: usize,
+        domain_size: usize,
+        nonce: u64,
+    ) -> Result<Vec<usize>, RandomCoinError> {
+        self.coin.draw_integers(num_values, domain_size, nonce)
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use math::fields::f128::BaseElement;
+
+    use super::{DefaultRandomCoin, RandomCoin, TranscriptCoin};
+    use crate::{hash::Blake3_256, Hasher};
+
+    type Blake3 = Blake3_256<BaseElement>;
+
+    #[test]
+    fn transcript_labels() {
+        let seed = [BaseElement::new(1), BaseElement::new(2)];
+        let data = Blake3::hash(&[1, 2, 3]);
+
+        // unlabeled messages are absorbed with an empty label
+        let mut coin1 = TranscriptCoin::<Blake3>::new(&seed);
+        let mut coin2 = TranscriptCoin::<Blake3>::new(&seed);
+        coin1.reseed(data);
+        coin2.reseed_with_label("", data);
+        assert_eq!(coin1.draw::<BaseElement>().unwrap(), coin2.draw::<BaseElement>().unwrap());
+
+        // different labels result in different challenges
+        coin1.reseed_with_label("a", data);
+        coin2.reseed_with_label("b", data);
+        assert_ne!(coin1.draw::<BaseElement>().unwrap(), coin2.draw::<BaseElement>().unwrap());
+
+        // the transcript is domain separated from the default coin
+        let mut coin1 = TranscriptCoin::<Blake3>::new(&seed);
+        let mut coin2 = DefaultRandomCoin::<Blake3>::new(&seed);
+        assert_ne!(coin1.draw::<BaseElement>().unwrap(), coin2.draw::<BaseElement>().unwrap());
+
+        // the default coin ignores labels
+        let mut coin1 = DefaultRandomCoin::<Blake3>::new(&seed);
+        coin1.reseed_with_label("a", data);
+        coin2.reseed(data);
+        assert_eq!(coin1.draw::<BaseElement>().unwrap(), coin2.draw::<BaseElement>().unwrap());
+    }
+}
@@ -24,6 +24,8 @@ impl Air for FibAir {
     type GkrProof = ();
     type GkrVerifier = ();
 
+    const ID: Option<u32> = Some(1);
+
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
     fn new(trace_info: TraceInfo, pub_inputs: Self::BaseField, options: ProofOptions) -> Self {
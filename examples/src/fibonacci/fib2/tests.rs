@@ -22,3 +22,29 @@ fn fib2_test_basic_proof_verification_fail() {
     let fib = Box::new(super::FibExample::<Blake3_256>::new(16, build_proof_options(false)));
     crate::tests::test_basic_proof_verification_fail(fib);
 }
+
+#[test]
+fn fib2_test_basic_proof_verification_expanded_coefficients() {
+    let options = build_proof_options(true)
+        .with_coefficient_derivation(winterfell::CoefficientDerivation::Expanded);
+    let fib = Box::new(super::FibExample::<Blake3_256>::new(16, options));
+    crate::tests::test_basic_proof_verification(fib);
+}
+
+#[test]
+fn fib2_test_canonical_proof_bytes() {
+    let options = winterfell::ProofOptions::new(28, 8, 8, winterfell::FieldExtension::None, 4, 7)
+        .with_canonical_proving(true);
+    let fib = Box::new(super::FibExample::<Blake3_256>::new(16, options));
+    crate::tests::test_canonical_proof_bytes(fib);
+}
+
+#[test]
+fn fib2_test_canonical_proof_bytes_across_threads() {
+    crate::tests::test_canonical_proof_bytes_across_threads(|| {
+        let options =
+            winterfell::ProofOptions::new(28, 8, 12, winterfell::FieldExtension::None, 4, 7)
+                .with_canonical_proving(true);
+        Box::new(super::FibExample::<Blake3_256>::new(1024, options))
+    });
+}
@@ -25,6 +25,8 @@ impl Air for Fib8Air {
     type GkrProof = ();
     type GkrVerifier = ();
 
+    const ID: Option<u32> = Some(2);
+
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
     fn new(trace_info: TraceInfo, pub_inputs: Self::BaseField, options: ProofOptions) -> Self {
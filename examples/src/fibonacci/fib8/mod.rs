@@ -14,10 +14,12 @@ use winterfell::{
 };
 
 use super::utils::compute_fib_term;
-use crate::{Blake3_192, Blake3_256, Example, ExampleOptions, HashFunction, Sha3_256};
+use crate::{
+    Blake2s256, Blake3_192, Blake3_256, Example, ExampleOptions, HashFunction, Keccak256, Sha3_256,
+};
 
 mod air;
-use air::Fib8Air;
+pub(super) use air::Fib8Air;
 
 mod prover;
 use prover::Fib8Prover;
@@ -49,6 +51,12 @@ pub fn get_example(
         HashFunction::Sha3_256 => {
             Ok(Box::new(Fib8Example::<Sha3_256>::new(sequence_length, options)))
         },
+        HashFunction::Keccak256 => {
+            Ok(Box::new(Fib8Example::<Keccak256>::new(sequence_length, options)))
+        },
+        HashFunction::Blake2s256 => {
+            Ok(Box::new(Fib8Example::<Blake2s256>::new(sequence_length, options)))
+        },
         _ => Err("The specified hash function cannot be used with this example.".to_string()),
     }
 }
@@ -6,7 +6,8 @@
 use winterfell::{
     crypto::MerkleTree, matrix::ColMatrix, AuxRandElements, CompositionPoly, CompositionPolyTrace,
     ConstraintCompositionCoefficients, DefaultConstraintCommitment, DefaultConstraintEvaluator,
-    DefaultTraceLde, PartitionOptions, StarkDomain, Trace, TraceInfo, TracePolyTable, TraceTable,
+    DefaultTraceLde, PartitionOptions, StarkDomain, Trace, TraceInfo, TraceLdeStorage,
+    TracePolyTable, TraceTable,
 };
 
 use super::{
@@ -72,6 +73,8 @@ where
     type Trace = TraceTable<BaseElement>;
     type HashFn = H;
     type VC = MerkleTree<H>;
+    type FriHashFn = Self::HashFn;
+    type FriVC = Self::VC;
     type RandomCoin = DefaultRandomCoin<Self::HashFn>;
     type TraceLde<E: FieldElement<BaseField = Self::BaseField>> =
         DefaultTraceLde<E, Self::HashFn, Self::VC>;
@@ -95,8 +98,9 @@ where
         main_trace: &ColMatrix<Self::BaseField>,
         domain: &StarkDomain<Self::BaseField>,
         partition_option: PartitionOptions,
+        storage: TraceLdeStorage,
     ) -> (Self::TraceLde<E>, TracePolyTable<E>) {
-        DefaultTraceLde::new(trace_info, main_trace, domain, partition_option)
+        DefaultTraceLde::with_storage(trace_info, main_trace, domain, partition_option, storage)
     }
 
     fn new_evaluator<'a, E: FieldElement<BaseField = Self::BaseField>>(
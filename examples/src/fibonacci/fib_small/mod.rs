@@ -33,8 +33,13 @@ const TRACE_WIDTH: usize = 2;
 type Blake3_192 = winterfell::crypto::hashers::Blake3_192<BaseElement>;
 type Blake3_256 = winterfell::crypto::hashers::Blake3_256<BaseElement>;
 type Sha3_256 = winterfell::crypto::hashers::Sha3_256<BaseElement>;
+type Keccak256 = winterfell::crypto::hashers::Keccak256<BaseElement>;
+type Blake2s256 = winterfell::crypto::hashers::Blake2s256<BaseElement>;
 type Rp64_256 = winterfell::crypto::hashers::Rp64_256;
 type RpJive64_256 = winterfell::crypto::hashers::RpJive64_256;
+type Poseidon64_256 = winterfell::crypto::hashers::Poseidon64_256;
+type Poseidon2_64_256 = winterfell::crypto::hashers::Poseidon2_64_256;
+type Griffin64_256 = winterfell::crypto::hashers::Griffin64_256;
 
 // FIBONACCI EXAMPLE
 // ================================================================================================
@@ -55,12 +60,27 @@ pub fn get_example(
         HashFunction::Sha3_256 => {
             Ok(Box::new(FibExample::<Sha3_256>::new(sequence_length, options)))
         },
+        HashFunction::Keccak256 => {
+            Ok(Box::new(FibExample::<Keccak256>::new(sequence_length, options)))
+        },
+        HashFunction::Blake2s256 => {
+            Ok(Box::new(FibExample::<Blake2s256>::new(sequence_length, options)))
+        },
         HashFunction::Rp64_256 => {
             Ok(Box::new(FibExample::<Rp64_256>::new(sequence_length, options)))
         },
         HashFunction::RpJive64_256 => {
             Ok(Box::new(FibExample::<RpJive64_256>::new(sequence_length, options)))
         },
+        HashFunction::Poseidon64_256 => {
+            Ok(Box::new(FibExample::<Poseidon64_256>::new(sequence_length, options)))
+        },
+        HashFunction::Poseidon2_64_256 => {
+            Ok(Box::new(FibExample::<Poseidon2_64_256>::new(sequence_length, options)))
+        },
+        HashFunction::Griffin64_256 => {
+            Ok(Box::new(FibExample::<Griffin64_256>::new(sequence_length, options)))
+        },
     }
 }
 
@@ -3,7 +3,15 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::{super::utils::build_proof_options, Rp64_256};
+use winterfell::{crypto::hashers::Truncated, VerifierError};
+
+use super::{
+    super::utils::build_proof_options, Blake2s256, Blake3_256, Griffin64_256, Keccak256,
+    Poseidon2_64_256, Poseidon64_256, Rp64_256,
+};
+use crate::Example;
+
+type Blake3_160 = Truncated<Blake3_256, 20>;
 
 #[test]
 fn fib_small_test_basic_proof_verification() {
@@ -22,3 +30,57 @@ fn fib_small_test_basic_proof_verification_fail() {
     let fib = Box::new(super::FibExample::<Rp64_256>::new(128, build_proof_options(false)));
     crate::tests::test_basic_proof_verification_fail(fib);
 }
+
+#[test]
+fn fib_small_test_basic_proof_verification_poseidon() {
+    let fib = Box::new(super::FibExample::<Poseidon64_256>::new(128, build_proof_options(false)));
+    crate::tests::test_basic_proof_verification(fib);
+}
+
+#[test]
+fn fib_small_test_basic_proof_verification_poseidon2() {
+    let fib = Box::new(super::FibExample::<Poseidon2_64_256>::new(128, build_proof_options(false)));
+    crate::tests::test_basic_proof_verification(fib);
+}
+
+#[test]
+fn fib_small_test_basic_proof_verification_griffin() {
+    let fib = Box::new(super::FibExample::<Griffin64_256>::new(128, build_proof_options(false)));
+    crate::tests::test_basic_proof_verification(fib);
+}
+
+#[test]
+fn fib_small_test_basic_proof_verification_keccak() {
+    let fib = Box::new(super::FibExample::<Keccak256>::new(128, build_proof_options(false)));
+    crate::tests::test_basic_proof_verification(fib);
+}
+
+#[test]
+fn fib_small_test_basic_proof_verification_blake2s() {
+    let fib = Box::new(super::FibExample::<Blake2s256>::new(128, build_proof_options(false)));
+    crate::tests::test_basic_proof_verification(fib);
+}
+
+#[test]
+fn fib_small_test_basic_proof_verification_truncated_digest() {
+    let options = build_proof_options(false).with_digest_size(20);
+    let fib = Box::new(super::FibExample::<Blake3_160>::new(128, options));
+    crate::tests::test_basic_proof_verification(fib);
+}
+
+#[test]
+fn fib_small_test_truncated_digest_size_mismatch() {
+    let options = build_proof_options(false).with_digest_size(20);
+    let proof = super::FibExample::<Blake3_160>::new(128, options.clone()).prove();
+
+    // a verifier using untruncated digests rejects the proof
+    let fib = super::FibExample::<Blake3_256>::new(128, options);
+    assert_eq!(Err(VerifierError::InconsistentDigestSize(20, 32)), fib.verify(proof));
+}
+
+#[test]
+fn fib_small_test_basic_proof_verification_trace_domain_query_exclusion() {
+    let options = build_proof_options(false).with_trace_domain_query_exclusion();
+    let fib = Box::new(super::FibExample::<Rp64_256>::new(128, options));
+    crate::tests::test_basic_proof_verification(fib);
+}
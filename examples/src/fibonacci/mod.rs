@@ -10,3 +10,6 @@ pub mod mulfib2;
 pub mod mulfib8;
 
 mod utils;
+
+#[cfg(test)]
+mod tests;
@@ -14,7 +14,9 @@ use winterfell::{
 };
 
 use super::utils::compute_mulfib_term;
-use crate::{Blake3_192, Blake3_256, Example, ExampleOptions, HashFunction, Sha3_256};
+use crate::{
+    Blake2s256, Blake3_192, Blake3_256, Example, ExampleOptions, HashFunction, Keccak256, Sha3_256,
+};
 
 mod air;
 use air::MulFib8Air;
@@ -49,6 +51,12 @@ pub fn get_example(
         HashFunction::Sha3_256 => {
             Ok(Box::new(MulFib8Example::<Sha3_256>::new(sequence_length, options)))
         },
+        HashFunction::Keccak256 => {
+            Ok(Box::new(MulFib8Example::<Keccak256>::new(sequence_length, options)))
+        },
+        HashFunction::Blake2s256 => {
+            Ok(Box::new(MulFib8Example::<Blake2s256>::new(sequence_length, options)))
+        },
         _ => Err("The specified hash function cannot be used with this example.".to_string()),
     }
 }
@@ -0,0 +1,299 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use core_utils::Serializable;
+use winterfell::{
+    crypto::{DefaultRandomCoin, Digest, MerkleTree},
+    math::{fields::f128::BaseElement, FieldElement},
+    AcceptableOptions, Air, FieldExtension, OptionsExtension, ProofContainer, ProofOptions,
+    ProofSizeEstimate, PublicInputs, SoundnessMode, UnknownExtensionPolicy, VerifierError,
+    VerifierRegistry,
+};
+
+use super::{fib2, fib8, utils::build_proof_options, utils::compute_fib_term};
+use crate::{Blake3_256, Example};
+
+type Hasher = Blake3_256;
+
+#[test]
+fn verifier_registry_dispatch() {
+    let mut registry = VerifierRegistry::new();
+    registry.register::<fib2::FibAir, Hasher, DefaultRandomCoin<Hasher>, MerkleTree<Hasher>>(
+        AcceptableOptions::OptionSet(vec![build_proof_options(false)]),
+    );
+    registry.register::<fib8::Fib8Air, Hasher, DefaultRandomCoin<Hasher>, MerkleTree<Hasher>>(
+        AcceptableOptions::OptionSet(vec![build_proof_options(false)]),
+    );
+    assert_eq!(vec![1, 2], registry.air_ids().collect::<Vec<_>>());
+
+    let fib2_proof = fib2::FibExample::<Hasher>::new(16, build_proof_options(false)).prove();
+    let fib8_proof = fib8::Fib8Example::<Hasher>::new(64, build_proof_options(false)).prove();
+    let fib2_result = compute_fib_term::<BaseElement>(16).to_bytes();
+    let fib8_result = compute_fib_term::<BaseElement>(64).to_bytes();
+
+    // each proof is dispatched to the AIR identified in its context
+    assert_eq!(Ok(()), registry.verify_any(&fib2_proof.to_bytes(), &fib2_result));
+    assert_eq!(Ok(()), registry.verify_any(&fib8_proof.to_bytes(), &fib8_result));
+
+    // proofs do not verify against wrong public inputs
+    assert!(registry.verify_any(&fib2_proof.to_bytes(), &fib8_result).is_err());
+    let wrong_result = (compute_fib_term::<BaseElement>(16) + BaseElement::ONE).to_bytes();
+    assert!(registry.verify_any(&fib2_proof.to_bytes(), &wrong_result).is_err());
+
+    // public inputs must be encoded exactly
+    let mut long_result = fib2_result.clone();
+    long_result.push(0);
+    assert!(matches!(
+        registry.verify_any(&fib2_proof.to_bytes(), &long_result),
+        Err(VerifierError::PublicInputsDeserializationError(_))
+    ));
+
+    // proofs without an AIR identifier cannot be dispatched
+    let mut proof = fib2_proof.clone();
+    proof.context = proof.context.with_air_id(None);
+    assert_eq!(Err(VerifierError::MissingAirId), registry.verify(proof, &fib2_result));
+
+    // proofs for unregistered AIRs are rejected
+    let mut registry = VerifierRegistry::new();
+    registry.register::<fib2::FibAir, Hasher, DefaultRandomCoin<Hasher>, MerkleTree<Hasher>>(
+        AcceptableOptions::OptionSet(vec![build_proof_options(false)]),
+    );
+    assert_eq!(
+        Err(VerifierError::UnregisteredAir(2)),
+        registry.verify_any(&fib8_proof.to_bytes(), &fib8_result)
+    );
+}
+
+#[test]
+fn verify_rejects_inconsistent_air_id() {
+    let fib8_proof = fib8::Fib8Example::<Hasher>::new(64, build_proof_options(false)).prove();
+    let result =
+        winterfell::verify::<fib2::FibAir, Hasher, DefaultRandomCoin<Hasher>, MerkleTree<Hasher>>(
+            fib8_proof,
+            compute_fib_term(64),
+            &AcceptableOptions::OptionSet(vec![build_proof_options(false)]),
+        );
+    assert_eq!(Err(VerifierError::InconsistentAirId), result);
+}
+
+#[test]
+fn verify_unknown_options_extension() {
+    let options = build_proof_options(false).with_extension(OptionsExtension::new(0xff00, vec![1]));
+    let proof = fib2::FibExample::<Hasher>::new(16, options.clone()).prove();
+    let acceptable_options = AcceptableOptions::OptionSet(vec![options]);
+
+    // by default, proofs with unknown extensions are rejected
+    let result = winterfell::verify::<
+        fib2::FibAir,
+        Hasher,
+        DefaultRandomCoin<Hasher>,
+        MerkleTree<Hasher>,
+    >(proof.clone(), compute_fib_term(16), &acceptable_options);
+    assert_eq!(Err(VerifierError::UnknownOptionsExtension(0xff00)), result);
+
+    // but they can be accepted if the verifier chooses to ignore unknown extensions
+    let result =
+        winterfell::verify_with_extension_policy::<
+            fib2::FibAir,
+            Hasher,
+            DefaultRandomCoin<Hasher>,
+            MerkleTree<Hasher>,
+        >(proof, compute_fib_term(16), &acceptable_options, UnknownExtensionPolicy::Ignore);
+    assert_eq!(Ok(()), result);
+}
+
+#[test]
+fn proof_container_round_trip() {
+    let proof = fib2::FibExample::<Hasher>::new(16, build_proof_options(false)).prove();
+    let result = compute_fib_term::<BaseElement>(16);
+    let pub_inputs_digest = result.hash::<Hasher>();
+
+    let container = ProofContainer::new(&proof, 0, &pub_inputs_digest.as_bytes(), 1_700_000_000);
+    let bytes = container.to_bytes();
+
+    // metadata can be read without parsing the proof
+    let header = ProofContainer::read_header(&bytes).unwrap();
+    assert_eq!(Some(1), header.air_id());
+    assert_eq!(pub_inputs_digest.as_bytes(), header.pub_inputs_digest());
+
+    // the proof read from the container is the same as the original proof
+    let container = ProofContainer::from_bytes(&bytes).unwrap();
+    let proof = container.proof().unwrap();
+    let result =
+        winterfell::verify::<fib2::FibAir, Hasher, DefaultRandomCoin<Hasher>, MerkleTree<Hasher>>(
+            proof,
+            result,
+            &AcceptableOptions::OptionSet(vec![build_proof_options(false)]),
+        );
+    assert_eq!(Ok(()), result);
+}
+
+#[test]
+fn extended_fri_remainder() {
+    let options = build_proof_options(false).with_fri_remainder_max_degree(511);
+    let proof = fib2::FibExample::<Hasher>::new(4096, options.clone()).prove();
+
+    // a larger remainder replaces the last FRI layers
+    let default_proof = fib2::FibExample::<Hasher>::new(4096, build_proof_options(false)).prove();
+    assert!(proof.fri_proof.num_layers() < default_proof.fri_proof.num_layers());
+
+    let result = winterfell::verify::<
+        fib2::FibAir,
+        Hasher,
+        DefaultRandomCoin<Hasher>,
+        MerkleTree<Hasher>,
+    >(proof, compute_fib_term(4096), &AcceptableOptions::OptionSet(vec![options]));
+    assert_eq!(Ok(()), result);
+}
+
+#[test]
+fn deep_fri() {
+    let options = build_proof_options(false).with_deep_fri();
+    let proof = fib2::FibExample::<Hasher>::new(1024, options.clone()).prove();
+    assert!(proof.fri_proof.num_deep_evaluations::<BaseElement>() > 0);
+
+    let result =
+        winterfell::verify::<fib2::FibAir, Hasher, DefaultRandomCoin<Hasher>, MerkleTree<Hasher>>(
+            proof.clone(),
+            compute_fib_term(1024),
+            &AcceptableOptions::OptionSet(vec![options]),
+        );
+    assert_eq!(Ok(()), result);
+
+    // the DEEP FRI option is bound to the proof, so it is not accepted with other options
+    let result =
+        winterfell::verify::<fib2::FibAir, Hasher, DefaultRandomCoin<Hasher>, MerkleTree<Hasher>>(
+            proof,
+            compute_fib_term(1024),
+            &AcceptableOptions::OptionSet(vec![build_proof_options(false)]),
+        );
+    assert!(result.is_err());
+}
+
+#[test]
+fn mixed_fri_folding() {
+    let options = build_proof_options(false).with_fri_folding_schedule(&[16, 8]);
+    let proof = fib2::FibExample::<Hasher>::new(4096, options.clone()).prove();
+
+    // folding the first layers by larger factors results in fewer FRI layers
+    let default_proof = fib2::FibExample::<Hasher>::new(4096, build_proof_options(false)).prove();
+    assert!(proof.fri_proof.num_layers() < default_proof.fri_proof.num_layers());
+
+    let result =
+        winterfell::verify::<fib2::FibAir, Hasher, DefaultRandomCoin<Hasher>, MerkleTree<Hasher>>(
+            proof.clone(),
+            compute_fib_term(4096),
+            &AcceptableOptions::OptionSet(vec![options]),
+        );
+    assert_eq!(Ok(()), result);
+
+    // the folding schedule is bound to the proof, so it is not accepted with other options
+    let result =
+        winterfell::verify::<fib2::FibAir, Hasher, DefaultRandomCoin<Hasher>, MerkleTree<Hasher>>(
+            proof,
+            compute_fib_term(4096),
+            &AcceptableOptions::OptionSet(vec![build_proof_options(false)]),
+        );
+    assert!(result.is_err());
+}
+
+#[test]
+fn fri_layer_skipping() {
+    let options = build_proof_options(false).with_fri_layer_skipping(255);
+
+    // for a short trace, the polynomial is sent to the verifier directly, and the resulting proof
+    // is smaller than a proof with FRI layers
+    let proof = fib2::FibExample::<Hasher>::new(256, options.clone()).prove();
+    let default_proof = fib2::FibExample::<Hasher>::new(256, build_proof_options(false)).prove();
+    assert_eq!(0, proof.fri_proof.num_layers());
+    assert!(proof.to_bytes().len() < default_proof.to_bytes().len());
+
+    let result =
+        winterfell::verify::<fib2::FibAir, Hasher, DefaultRandomCoin<Hasher>, MerkleTree<Hasher>>(
+            proof,
+            compute_fib_term(256),
+            &AcceptableOptions::OptionSet(vec![options.clone()]),
+        );
+    assert_eq!(Ok(()), result);
+
+    // proofs for long traces are not affected
+    let proof = fib2::FibExample::<Hasher>::new(4096, options.clone()).prove();
+    let default_proof = fib2::FibExample::<Hasher>::new(4096, build_proof_options(false)).prove();
+    assert_eq!(default_proof.fri_proof.num_layers(), proof.fri_proof.num_layers());
+
+    let result = winterfell::verify::<
+        fib2::FibAir,
+        Hasher,
+        DefaultRandomCoin<Hasher>,
+        MerkleTree<Hasher>,
+    >(proof, compute_fib_term(4096), &AcceptableOptions::OptionSet(vec![options]));
+    assert_eq!(Ok(()), result);
+}
+
+#[test]
+fn soundness_mode() {
+    let trace_length = 1024;
+    let base_options = build_proof_options(false);
+
+    // proofs generated with enough queries for the declared security level are accepted
+    let mode = SoundnessMode::Proven;
+    let num_queries = base_options.min_num_queries::<BaseElement>(mode, trace_length, 50).unwrap();
+    let options = ProofOptions::new(num_queries, 8, 0, FieldExtension::None, 4, 7)
+        .with_soundness_mode(mode, 50);
+    let proof = fib2::FibExample::<Hasher>::new(trace_length, options.clone()).prove();
+    let result =
+        winterfell::verify::<fib2::FibAir, Hasher, DefaultRandomCoin<Hasher>, MerkleTree<Hasher>>(
+            proof,
+            compute_fib_term(trace_length),
+            &AcceptableOptions::OptionSet(vec![options]),
+        );
+    assert_eq!(Ok(()), result);
+
+    // proofs which do not achieve the declared security level are rejected
+    let options = ProofOptions::new(num_queries - 1, 8, 0, FieldExtension::None, 4, 7)
+        .with_soundness_mode(mode, 50);
+    let proof = fib2::FibExample::<Hasher>::new(trace_length, options.clone()).prove();
+    let security_level = proof.security_level::<Hasher>(false);
+    let result =
+        winterfell::verify::<fib2::FibAir, Hasher, DefaultRandomCoin<Hasher>, MerkleTree<Hasher>>(
+            proof,
+            compute_fib_term(trace_length),
+            &AcceptableOptions::OptionSet(vec![options]),
+        );
+    assert_eq!(Err(VerifierError::InsufficientProvenSecurity(50, security_level)), result);
+}
+
+#[test]
+fn proof_size_estimate() {
+    let configs = [
+        build_proof_options(false),
+        build_proof_options(true),
+        build_proof_options(false).with_deep_fri(),
+        build_proof_options(false).with_fri_remainder_max_degree(255),
+        build_proof_options(false).with_fri_folding_schedule(&[16]).with_deep_fri(),
+        build_proof_options(false).with_fri_layer_skipping(1023),
+    ];
+    for options in configs {
+        let proof = fib2::FibExample::<Hasher>::new(4096, options.clone()).prove();
+        let air =
+            fib2::FibAir::new(proof.trace_info().clone(), compute_fib_term(4096), options.clone());
+        let estimate = ProofSizeEstimate::new(air.context(), fib2::FibAir::ID, 32);
+
+        // components which do not depend on query positions are estimated exactly
+        assert_eq!(proof.context.to_bytes().len(), estimate.context);
+        assert_eq!(proof.commitments.to_bytes().len(), estimate.commitments);
+        assert_eq!(proof.ood_frame.to_bytes().len(), estimate.ood_frame);
+        assert_eq!(proof.fri_proof.num_layers(), estimate.fri_proof.layers.len());
+
+        // the total size is estimated to within a few percent
+        let actual = proof.to_bytes().len();
+        let error = actual.abs_diff(estimate.total()) as f64 / actual as f64;
+        assert!(
+            error < 0.05,
+            "estimated {} bytes, but proof was {actual} bytes",
+            estimate.total()
+        );
+    }
+}
@@ -0,0 +1,100 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use winterfell::{math::ToElements, Air, AirContext, Assertion, EvaluationFrame, TraceInfo};
+
+use super::{BaseElement, FieldElement, KeccakF, ProofOptions, NUM_LANES};
+use crate::utils::{are_equal, not, EvaluationResult};
+
+// KECCAK AIR
+// ================================================================================================
+
+pub struct PublicInputs {
+    pub seed: [u64; NUM_LANES],
+    pub result: [u64; NUM_LANES],
+}
+
+impl ToElements<BaseElement> for PublicInputs {
+    fn to_elements(&self) -> Vec<BaseElement> {
+        self.seed
+            .iter()
+            .chain(self.result.iter())
+            .map(|&lane| BaseElement::from(lane))
+            .collect()
+    }
+}
+
+/// AIR for a chain of Keccak-f[25 * W] permutations, where W is the lane width in bits.
+pub struct KeccakAir<const W: usize> {
+    context: AirContext<BaseElement>,
+    seed: [u64; NUM_LANES],
+    result: [u64; NUM_LANES],
+}
+
+impl<const W: usize> Air for KeccakAir<W> {
+    type BaseField = BaseElement;
+    type PublicInputs = PublicInputs;
+    type GkrProof = ();
+    type GkrVerifier = ();
+
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    fn new(trace_info: TraceInfo, pub_inputs: PublicInputs, options: ProofOptions) -> Self {
+        let degrees = KeccakF::<W>::get_constraint_degrees();
+        assert_eq!(KeccakF::<W>::TRACE_WIDTH, trace_info.width());
+        KeccakAir {
+            context: AirContext::new(trace_info, degrees, 2 * KeccakF::<W>::STATE_WIDTH, options),
+            seed: pub_inputs.seed,
+            result: pub_inputs.result,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        debug_assert_eq!(KeccakF::<W>::TRACE_WIDTH, current.len());
+        debug_assert_eq!(KeccakF::<W>::TRACE_WIDTH, next.len());
+
+        // split periodic values into round flag and round constant bits
+        let round_flag = periodic_values[0];
+        let rc_bits = &periodic_values[1..];
+
+        // when round_flag = 1, constraints for a round of Keccak-f permutation are enforced
+        KeccakF::<W>::enforce_round(result, current, next, rc_bits, round_flag);
+
+        // when round_flag = 0, the state is copied to the next step
+        let copy_flag = not(round_flag);
+        for i in 0..KeccakF::<W>::STATE_WIDTH {
+            result.agg_constraint(i, copy_flag, are_equal(current[i], next[i]));
+        }
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        // assert the bits of the starting and ending states of the permutation chain
+        let last_step = self.trace_length() - 1;
+        let seed = KeccakF::<W>::get_state_bits::<BaseElement>(&self.seed);
+        let result = KeccakF::<W>::get_state_bits::<BaseElement>(&self.result);
+
+        let mut assertions = Vec::with_capacity(2 * KeccakF::<W>::STATE_WIDTH);
+        for (column, (&seed, &result)) in seed.iter().zip(result.iter()).enumerate() {
+            assertions.push(Assertion::single(column, 0, seed));
+            assertions.push(Assertion::single(column, last_step, result));
+        }
+        assertions
+    }
+
+    fn get_periodic_column_values(&self) -> Vec<Vec<Self::BaseField>> {
+        KeccakF::<W>::get_periodic_column_values()
+    }
+}
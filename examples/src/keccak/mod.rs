@@ -0,0 +1,175 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! An example of proving a chain of Keccak-f permutations using the Keccak-f gadget from
+//! [utils::keccak](crate::utils::keccak).
+//!
+//! The example can prove chains of Keccak-f[1600] permutations (which underlie SHA3 and
+//! Keccak-256), as well as of Keccak-f[200] permutations. Keccak-f[200] is the member of the
+//! Keccak-f family with 8-bit lanes; it uses the same round function as Keccak-f[1600], but its
+//! execution trace is 8 times narrower, and thus, its proofs are much faster to generate.
+
+use core::marker::PhantomData;
+use std::time::Instant;
+
+use tracing::{field, info_span};
+use winterfell::{
+    crypto::{DefaultRandomCoin, ElementHasher, MerkleTree},
+    math::{fields::f128::BaseElement, FieldElement},
+    Proof, ProofOptions, Prover, Trace, VerifierError,
+};
+
+use crate::{
+    utils::keccak::{KeccakF, CYCLE_LENGTH, NUM_LANES},
+    Blake2s256, Blake3_192, Blake3_256, Example, ExampleOptions, HashFunction, Keccak256, Sha3_256,
+};
+
+mod air;
+use air::{KeccakAir, PublicInputs};
+
+mod prover;
+use prover::KeccakProver;
+
+#[cfg(test)]
+mod tests;
+
+// KECCAK PERMUTATION CHAIN EXAMPLE
+// ================================================================================================
+
+pub fn get_example(
+    options: &ExampleOptions,
+    chain_length: usize,
+    lane_width: usize,
+) -> Result<Box<dyn Example>, String> {
+    match lane_width {
+        8 => get_example_for_lane_width::<8>(options, chain_length),
+        64 => get_example_for_lane_width::<64>(options, chain_length),
+        _ => Err(format!("Lane width must be either 8 or 64 bits, but was {lane_width}.")),
+    }
+}
+
+fn get_example_for_lane_width<const W: usize>(
+    options: &ExampleOptions,
+    chain_length: usize,
+) -> Result<Box<dyn Example>, String> {
+    let (options, hash_fn) = options.to_proof_options(28, 8);
+
+    match hash_fn {
+        HashFunction::Blake3_192 => {
+            Ok(Box::new(KeccakExample::<Blake3_192, W>::new(chain_length, options)))
+        },
+        HashFunction::Blake3_256 => {
+            Ok(Box::new(KeccakExample::<Blake3_256, W>::new(chain_length, options)))
+        },
+        HashFunction::Sha3_256 => {
+            Ok(Box::new(KeccakExample::<Sha3_256, W>::new(chain_length, options)))
+        },
+        HashFunction::Keccak256 => {
+            Ok(Box::new(KeccakExample::<Keccak256, W>::new(chain_length, options)))
+        },
+        HashFunction::Blake2s256 => {
+            Ok(Box::new(KeccakExample::<Blake2s256, W>::new(chain_length, options)))
+        },
+        _ => Err("The specified hash function cannot be used with this example.".to_string()),
+    }
+}
+
+/// Proves a chain of Keccak-f[25 * W] permutations, where W is the lane width in bits.
+pub struct KeccakExample<H: ElementHasher, const W: usize> {
+    options: ProofOptions,
+    chain_length: usize,
+    seed: [u64; NUM_LANES],
+    result: [u64; NUM_LANES],
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher, const W: usize> KeccakExample<H, W> {
+    pub fn new(chain_length: usize, options: ProofOptions) -> Self {
+        assert!(chain_length.is_power_of_two(), "chain length must a power of 2");
+        let mut seed = [0; NUM_LANES];
+        for (i, lane) in seed.iter_mut().enumerate() {
+            *lane = i as u64;
+        }
+
+        // compute the result of applying the permutation repeatedly to the seed
+        let now = Instant::now();
+        let mut result = seed;
+        for _ in 0..chain_length {
+            KeccakF::<W>::apply_permutation(&mut result);
+        }
+        println!(
+            "Computed a chain of {} Keccak-f[{}] permutations in {} ms",
+            chain_length,
+            KeccakF::<W>::STATE_WIDTH,
+            now.elapsed().as_millis(),
+        );
+
+        KeccakExample {
+            options,
+            chain_length,
+            seed,
+            result,
+            _hasher: PhantomData,
+        }
+    }
+}
+
+// EXAMPLE IMPLEMENTATION
+// ================================================================================================
+
+impl<H: ElementHasher, const W: usize> Example for KeccakExample<H, W>
+where
+    H: ElementHasher<BaseField = BaseElement> + Sync,
+{
+    fn prove(&self) -> Proof {
+        println!(
+            "Generating proof for computing a chain of {} Keccak-f[{}] permutations",
+            self.chain_length,
+            KeccakF::<W>::STATE_WIDTH,
+        );
+
+        // create a prover
+        let prover = KeccakProver::<H, W>::new(self.options.clone());
+
+        // generate execution trace
+        let trace = info_span!(
+            "generate_execution_trace",
+            num_cols = KeccakF::<W>::TRACE_WIDTH,
+            steps = field::Empty
+        )
+        .in_scope(|| {
+            let trace = prover.build_trace(self.seed, self.chain_length);
+            tracing::Span::current().record("steps", trace.length());
+            trace
+        });
+
+        // generate the proof
+        prover.prove(trace).unwrap()
+    }
+
+    fn verify(&self, proof: Proof) -> Result<(), VerifierError> {
+        let pub_inputs = PublicInputs { seed: self.seed, result: self.result };
+        let acceptable_options =
+            winterfell::AcceptableOptions::OptionSet(vec![proof.options().clone()]);
+        winterfell::verify::<KeccakAir<W>, H, DefaultRandomCoin<H>, MerkleTree<H>>(
+            proof,
+            pub_inputs,
+            &acceptable_options,
+        )
+    }
+
+    fn verify_with_wrong_inputs(&self, proof: Proof) -> Result<(), VerifierError> {
+        let mut result = self.result;
+        result[0] ^= 1;
+        let pub_inputs = PublicInputs { seed: self.seed, result };
+        let acceptable_options =
+            winterfell::AcceptableOptions::OptionSet(vec![proof.options().clone()]);
+        winterfell::verify::<KeccakAir<W>, H, DefaultRandomCoin<H>, MerkleTree<H>>(
+            proof,
+            pub_inputs,
+            &acceptable_options,
+        )
+    }
+}
@@ -0,0 +1,142 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use winterfell::{
+    crypto::MerkleTree, matrix::ColMatrix, AuxRandElements, CompositionPoly, CompositionPolyTrace,
+    ConstraintCompositionCoefficients, DefaultConstraintCommitment, DefaultConstraintEvaluator,
+    DefaultTraceLde, PartitionOptions, StarkDomain, Trace, TraceInfo, TraceLdeStorage,
+    TracePolyTable, TraceTable,
+};
+
+use super::{
+    BaseElement, DefaultRandomCoin, ElementHasher, FieldElement, KeccakAir, KeccakF, PhantomData,
+    ProofOptions, Prover, PublicInputs, CYCLE_LENGTH, NUM_LANES,
+};
+
+// KECCAK PROVER
+// ================================================================================================
+
+pub struct KeccakProver<H: ElementHasher, const W: usize> {
+    options: ProofOptions,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher, const W: usize> KeccakProver<H, W> {
+    pub fn new(options: ProofOptions) -> Self {
+        Self { options, _hasher: PhantomData }
+    }
+
+    pub fn build_trace(
+        &self,
+        seed: [u64; NUM_LANES],
+        iterations: usize,
+    ) -> TraceTable<BaseElement> {
+        // allocate memory to hold the trace table
+        let trace_length = iterations * CYCLE_LENGTH;
+        let mut trace = TraceTable::new(KeccakF::<W>::TRACE_WIDTH, trace_length);
+
+        let mut state = seed;
+        trace.fill(
+            |row| {
+                // initialize first state of the computation
+                KeccakF::<W>::fill_row(&seed, row);
+            },
+            |step, row| {
+                // for the first rounds in every cycle, apply a single round of the permutation;
+                // for the remaining steps, just carry over the state to the next step
+                let round = step % CYCLE_LENGTH;
+                if round < KeccakF::<W>::NUM_ROUNDS {
+                    KeccakF::<W>::apply_round(&mut state, round);
+                    KeccakF::<W>::fill_row(&state, row);
+                }
+            },
+        );
+
+        trace
+    }
+}
+
+impl<H: ElementHasher, const W: usize> Prover for KeccakProver<H, W>
+where
+    H: ElementHasher<BaseField = BaseElement> + Sync,
+{
+    type BaseField = BaseElement;
+    type Air = KeccakAir<W>;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = H;
+    type VC = MerkleTree<H>;
+    type FriHashFn = Self::HashFn;
+    type FriVC = Self::VC;
+    type RandomCoin = DefaultRandomCoin<Self::HashFn>;
+    type TraceLde<E: FieldElement<BaseField = Self::BaseField>> =
+        DefaultTraceLde<E, Self::HashFn, Self::VC>;
+    type ConstraintCommitment<E: FieldElement<BaseField = Self::BaseField>> =
+        DefaultConstraintCommitment<E, H, Self::VC>;
+    type ConstraintEvaluator<'a, E: FieldElement<BaseField = Self::BaseField>> =
+        DefaultConstraintEvaluator<'a, Self::Air, E>;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> PublicInputs {
+        let last_step = trace.length() - 1;
+        PublicInputs {
+            seed: read_state::<W>(trace, 0),
+            result: read_state::<W>(trace, last_step),
+        }
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    fn new_trace_lde<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        trace_info: &TraceInfo,
+        main_trace: &ColMatrix<Self::BaseField>,
+        domain: &StarkDomain<Self::BaseField>,
+        partition_option: PartitionOptions,
+        storage: TraceLdeStorage,
+    ) -> (Self::TraceLde<E>, TracePolyTable<E>) {
+        DefaultTraceLde::with_storage(trace_info, main_trace, domain, partition_option, storage)
+    }
+
+    fn new_evaluator<'a, E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        air: &'a Self::Air,
+        aux_rand_elements: Option<AuxRandElements<E>>,
+        composition_coefficients: ConstraintCompositionCoefficients<E>,
+    ) -> Self::ConstraintEvaluator<'a, E> {
+        DefaultConstraintEvaluator::new(air, aux_rand_elements, composition_coefficients)
+    }
+
+    fn build_constraint_commitment<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        composition_poly_trace: CompositionPolyTrace<E>,
+        num_constraint_composition_columns: usize,
+        domain: &StarkDomain<Self::BaseField>,
+        partition_options: PartitionOptions,
+    ) -> (Self::ConstraintCommitment<E>, CompositionPoly<E>) {
+        DefaultConstraintCommitment::new(
+            composition_poly_trace,
+            num_constraint_composition_columns,
+            domain,
+            partition_options,
+        )
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Reads the Keccak state from the specified row of the trace.
+fn read_state<const W: usize>(trace: &TraceTable<BaseElement>, step: usize) -> [u64; NUM_LANES] {
+    let mut state = [0; NUM_LANES];
+    for (i, lane) in state.iter_mut().enumerate() {
+        for z in 0..W {
+            if trace.get(i * W + z, step) == BaseElement::ONE {
+                *lane |= 1 << z;
+            }
+        }
+    }
+    state
+}
@@ -0,0 +1,107 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use rand_utils::rand_array;
+use winterfell::{FieldExtension, ProofOptions};
+
+use super::{BaseElement, Blake3_256, FieldElement, KeccakF, CYCLE_LENGTH, NUM_LANES};
+
+#[test]
+fn keccak_test_basic_proof_verification() {
+    let keccak_eg = Box::new(super::KeccakExample::<Blake3_256, 8>::new(2, build_options(false)));
+    crate::tests::test_basic_proof_verification(keccak_eg);
+}
+
+#[test]
+fn keccak_test_basic_proof_verification_extension() {
+    let keccak_eg = Box::new(super::KeccakExample::<Blake3_256, 8>::new(2, build_options(true)));
+    crate::tests::test_basic_proof_verification(keccak_eg);
+}
+
+#[test]
+fn keccak_test_basic_proof_verification_fail() {
+    let keccak_eg = Box::new(super::KeccakExample::<Blake3_256, 8>::new(2, build_options(false)));
+    crate::tests::test_basic_proof_verification_fail(keccak_eg);
+}
+
+#[test]
+fn keccak_f1600_test_basic_proof_verification() {
+    let keccak_eg = Box::new(super::KeccakExample::<Blake3_256, 64>::new(1, build_options(false)));
+    crate::tests::test_basic_proof_verification(keccak_eg);
+}
+
+#[test]
+fn keccak_f1600_test_basic_proof_verification_fail() {
+    let keccak_eg = Box::new(super::KeccakExample::<Blake3_256, 64>::new(1, build_options(false)));
+    crate::tests::test_basic_proof_verification_fail(keccak_eg);
+}
+
+#[test]
+fn keccak_f200_permutation() {
+    let mut state = rand_array::<u8, NUM_LANES>();
+    let mut expected = state;
+    keccak::f200(&mut expected);
+
+    let mut lanes = state.map(u64::from);
+    KeccakF::<8>::apply_permutation(&mut lanes);
+    state.iter_mut().zip(lanes).for_each(|(byte, lane)| *byte = lane as u8);
+    assert_eq!(expected, state);
+}
+
+#[test]
+fn keccak_f1600_permutation() {
+    let mut state = [0u64; NUM_LANES];
+    KeccakF::<64>::apply_permutation(&mut state);
+    assert_eq!(0xf1258f7940e1dde7, state[0]);
+    assert_eq!(0x84d5ccf933c0478a, state[1]);
+
+    let mut expected = state;
+    keccak::f1600(&mut expected);
+    KeccakF::<64>::apply_permutation(&mut state);
+    assert_eq!(expected, state);
+}
+
+#[test]
+fn keccak_f1600_round_constraints() {
+    type Keccak1600 = KeccakF<64>;
+    let periodic_values = Keccak1600::get_periodic_column_values::<BaseElement>();
+
+    let mut state = rand_array::<u64, NUM_LANES>();
+    let mut current = vec![BaseElement::ZERO; Keccak1600::TRACE_WIDTH];
+    let mut next = vec![BaseElement::ZERO; Keccak1600::TRACE_WIDTH];
+    Keccak1600::fill_row(&state, &mut current);
+
+    for round in 0..Keccak1600::NUM_ROUNDS {
+        Keccak1600::apply_round(&mut state, round);
+        Keccak1600::fill_row(&state, &mut next);
+
+        // all constraints must be satisfied by a valid transition
+        let flag = periodic_values[0][round];
+        let rc_bits = periodic_values[1..].iter().map(|column| column[round]).collect::<Vec<_>>();
+        let mut result = vec![BaseElement::ZERO; Keccak1600::NUM_CONSTRAINTS];
+        Keccak1600::enforce_round(&mut result, &current, &next, &rc_bits, flag);
+        assert!(result.iter().all(|&value| value == BaseElement::ZERO));
+
+        // flipping a single bit of the next state must violate a constraint
+        let idx = round * 7 % Keccak1600::STATE_WIDTH;
+        let mut invalid_next = next.clone();
+        invalid_next[idx] = BaseElement::ONE - invalid_next[idx];
+        let mut result = vec![BaseElement::ZERO; Keccak1600::NUM_CONSTRAINTS];
+        Keccak1600::enforce_round(&mut result, &current, &invalid_next, &rc_bits, flag);
+        assert_ne!(BaseElement::ZERO, result[idx]);
+
+        core::mem::swap(&mut current, &mut next);
+    }
+    assert_eq!(BaseElement::ZERO, periodic_values[0][CYCLE_LENGTH - 1]);
+}
+
+fn build_options(use_extension_field: bool) -> ProofOptions {
+    let extension = if use_extension_field {
+        FieldExtension::Quadratic
+    } else {
+        FieldExtension::None
+    };
+    ProofOptions::new(28, 8, 0, extension, 4, 31)
+}
@@ -16,7 +16,9 @@ use winterfell::{
 use super::{
     message_to_elements, rescue, Example, PrivateKey, Signature, CYCLE_LENGTH, NUM_HASH_ROUNDS,
 };
-use crate::{Blake3_192, Blake3_256, ExampleOptions, HashFunction, Sha3_256};
+use crate::{
+    Blake2s256, Blake3_192, Blake3_256, ExampleOptions, HashFunction, Keccak256, Sha3_256,
+};
 
 mod air;
 use air::{LamportAggregateAir, PublicInputs};
@@ -48,6 +50,12 @@ pub fn get_example(
         HashFunction::Sha3_256 => {
             Ok(Box::new(LamportAggregateExample::<Sha3_256>::new(num_signatures, options)))
         },
+        HashFunction::Keccak256 => {
+            Ok(Box::new(LamportAggregateExample::<Keccak256>::new(num_signatures, options)))
+        },
+        HashFunction::Blake2s256 => {
+            Ok(Box::new(LamportAggregateExample::<Blake2s256>::new(num_signatures, options)))
+        },
         _ => Err("The specified hash function cannot be used with this example.".to_string()),
     }
 }
@@ -5,16 +5,18 @@
 
 use structopt::StructOpt;
 use winterfell::{
-    crypto::hashers::{Rp64_256, RpJive64_256},
+    crypto::hashers::{Griffin64_256, Poseidon2_64_256, Poseidon64_256, Rp64_256, RpJive64_256},
     math::fields::f128::BaseElement,
     FieldExtension, Proof, ProofOptions, VerifierError,
 };
 
 pub mod fibonacci;
+pub mod keccak;
 #[cfg(feature = "std")]
 pub mod lamport;
 #[cfg(feature = "std")]
 pub mod merkle;
+pub mod range_check;
 pub mod rescue;
 #[cfg(feature = "std")]
 pub mod rescue_raps;
@@ -30,6 +32,8 @@ mod tests;
 pub type Blake3_192 = winterfell::crypto::hashers::Blake3_192<BaseElement>;
 pub type Blake3_256 = winterfell::crypto::hashers::Blake3_256<BaseElement>;
 pub type Sha3_256 = winterfell::crypto::hashers::Sha3_256<BaseElement>;
+pub type Keccak256 = winterfell::crypto::hashers::Keccak256<BaseElement>;
+pub type Blake2s256 = winterfell::crypto::hashers::Blake2s256<BaseElement>;
 
 pub trait Example {
     fn prove(&self) -> Proof;
@@ -86,8 +90,13 @@ impl ExampleOptions {
             "blake3_192" => HashFunction::Blake3_192,
             "blake3_256" => HashFunction::Blake3_256,
             "sha3_256" => HashFunction::Sha3_256,
+            "keccak_256" => HashFunction::Keccak256,
+            "blake2s_256" => HashFunction::Blake2s256,
             "rp64_256" => HashFunction::Rp64_256,
             "rp_jive64_256" => HashFunction::RpJive64_256,
+            "poseidon64_256" => HashFunction::Poseidon64_256,
+            "poseidon2_64_256" => HashFunction::Poseidon2_64_256,
+            "griffin64_256" => HashFunction::Griffin64_256,
             val => panic!("'{val}' is not a valid hash function option"),
         };
 
@@ -110,8 +119,13 @@ impl ExampleOptions {
             "blake3_192" => proof.security_level::<Blake3_192>(conjectured),
             "blake3_256" => proof.security_level::<Blake3_256>(conjectured),
             "sha3_256" => proof.security_level::<Sha3_256>(conjectured),
+            "keccak_256" => proof.security_level::<Keccak256>(conjectured),
+            "blake2s_256" => proof.security_level::<Blake2s256>(conjectured),
             "rp64_256" => proof.security_level::<Rp64_256>(conjectured),
             "rp_jive64_256" => proof.security_level::<RpJive64_256>(conjectured),
+            "poseidon64_256" => proof.security_level::<Poseidon64_256>(conjectured),
+            "poseidon2_64_256" => proof.security_level::<Poseidon2_64_256>(conjectured),
+            "griffin64_256" => proof.security_level::<Griffin64_256>(conjectured),
             val => panic!("'{val}' is not a valid hash function option"),
         };
 
@@ -164,6 +178,21 @@ pub enum ExampleType {
         #[structopt(short = "n", default_value = "1048575")]
         num_steps: usize,
     },
+    /// Range-check a list of random values using a permutation bus in an auxiliary trace segment
+    RangeCheck {
+        /// Number of values to range-check
+        #[structopt(short = "n", default_value = "65536")]
+        num_values: usize,
+    },
+    /// Compute a chain of Keccak-f permutations using the Keccak-f gadget
+    Keccak {
+        /// Length of the permutation chain; must be a power of two
+        #[structopt(short = "n", default_value = "64")]
+        chain_length: usize,
+        /// Lane width in bits; 8 for Keccak-f[200], or 64 for Keccak-f[1600]
+        #[structopt(short = "w", default_value = "8")]
+        lane_width: usize,
+    },
     /// Compute a hash chain using Rescue hash function
     Rescue {
         /// Length of the hash chain; must be a power of two
@@ -224,6 +253,16 @@ pub enum HashFunction {
     /// When this function is used in the STARK protocol, proof security cannot exceed 128 bits.
     Sha3_256,
 
+    /// Keccak hash function with 256 bit output (as used by the EVM).
+    ///
+    /// When this function is used in the STARK protocol, proof security cannot exceed 128 bits.
+    Keccak256,
+
+    /// BLAKE2s hash function with 256 bit output.
+    ///
+    /// When this function is used in the STARK protocol, proof security cannot exceed 128 bits.
+    Blake2s256,
+
     /// Rescue Prime hash function with 256 bit output. It only works in `f64` field.
     ///
     /// When this function is used in the STARK protocol, proof security cannot exceed 128 bits.
@@ -234,4 +273,19 @@ pub enum HashFunction {
     ///
     /// When this function is used in the STARK protocol, proof security cannot exceed 128 bits.
     RpJive64_256,
+
+    /// Poseidon hash function with 256 bit output. It only works in `f64` field.
+    ///
+    /// When this function is used in the STARK protocol, proof security cannot exceed 128 bits.
+    Poseidon64_256,
+
+    /// Poseidon2 hash function with 256 bit output. It only works in `f64` field.
+    ///
+    /// When this function is used in the STARK protocol, proof security cannot exceed 128 bits.
+    Poseidon2_64_256,
+
+    /// Griffin hash function with 256 bit output. It only works in `f64` field.
+    ///
+    /// When this function is used in the STARK protocol, proof security cannot exceed 128 bits.
+    Griffin64_256,
 }
@@ -5,7 +5,7 @@
 
 use std::time::Instant;
 
-use examples::{fibonacci, rescue, vdf, ExampleOptions, ExampleType};
+use examples::{fibonacci, keccak, range_check, rescue, vdf, ExampleOptions, ExampleType};
 #[cfg(feature = "std")]
 use examples::{lamport, merkle, rescue_raps};
 use structopt::StructOpt;
@@ -67,6 +67,10 @@ fn main() {
         },
         ExampleType::Vdf { num_steps } => vdf::regular::get_example(&options, num_steps),
         ExampleType::VdfExempt { num_steps } => vdf::exempt::get_example(&options, num_steps),
+        ExampleType::RangeCheck { num_values } => range_check::get_example(&options, num_values),
+        ExampleType::Keccak { chain_length, lane_width } => {
+            keccak::get_example(&options, chain_length, lane_width)
+        },
         ExampleType::Rescue { chain_length } => rescue::get_example(&options, chain_length),
         #[cfg(feature = "std")]
         ExampleType::RescueRaps { chain_length } => {
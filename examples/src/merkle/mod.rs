@@ -19,7 +19,7 @@ use crate::{
         self, Hash, Rescue128, CYCLE_LENGTH as HASH_CYCLE_LEN, NUM_ROUNDS as NUM_HASH_ROUNDS,
         STATE_WIDTH as HASH_STATE_WIDTH,
     },
-    Blake3_192, Blake3_256, Example, ExampleOptions, HashFunction, Sha3_256,
+    Blake2s256, Blake3_192, Blake3_256, Example, ExampleOptions, HashFunction, Keccak256, Sha3_256,
 };
 
 mod air;
@@ -52,6 +52,12 @@ pub fn get_example(
             Ok(Box::new(MerkleExample::<Blake3_256>::new(tree_depth, options)))
         },
         HashFunction::Sha3_256 => Ok(Box::new(MerkleExample::<Sha3_256>::new(tree_depth, options))),
+        HashFunction::Keccak256 => {
+            Ok(Box::new(MerkleExample::<Keccak256>::new(tree_depth, options)))
+        },
+        HashFunction::Blake2s256 => {
+            Ok(Box::new(MerkleExample::<Blake2s256>::new(tree_depth, options)))
+        },
         _ => Err("The specified hash function cannot be used with this example.".to_string()),
     }
 }
@@ -0,0 +1,152 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use winterfell::{
+    Air, AirContext, Assertion, AuxRandElements, EvaluationFrame, TraceInfo,
+    TransitionConstraintDegree,
+};
+
+use super::{BaseElement, FieldElement, ProofOptions, AUX_TRACE_WIDTH, RANGE_SIZE, TRACE_WIDTH};
+use crate::utils::are_equal;
+
+// RANGE CHECK AIR
+// ================================================================================================
+
+/// AIR for the range check example.
+///
+/// The main trace segment consists of the following columns:
+/// - `v` (column 0): values to be range-checked.
+/// - `w` (column 1): the same values sorted in ascending order.
+/// - `s` (column 2): running sum of values in `v`, such that s_0 = 0 and s_{i+1} = s_i + v_i.
+///
+/// The auxiliary trace segment consists of a single column `z` which is a running product
+/// enforcing that `w` is a permutation of `v` (see the module-level documentation).
+///
+/// Note that transition constraints are applied to all rows except for the last one. Thus, the
+/// running product in `z` covers only the first n - 1 values of `v` and `w`. To make sure the last
+/// row is also covered, we assert that the last values in both `v` and `w` are 255.
+pub struct RangeCheckAir {
+    context: AirContext<BaseElement>,
+    sum: BaseElement,
+}
+
+impl Air for RangeCheckAir {
+    type BaseField = BaseElement;
+    type PublicInputs = BaseElement;
+    type GkrProof = ();
+    type GkrVerifier = ();
+
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    fn new(trace_info: TraceInfo, pub_inputs: BaseElement, options: ProofOptions) -> Self {
+        assert_eq!(TRACE_WIDTH, trace_info.main_trace_width());
+        assert_eq!(AUX_TRACE_WIDTH, trace_info.aux_segment_width());
+
+        // (w' - w) * (w' - w - 1) = 0 has degree 2, and s' = s + v has degree 1
+        let main_degrees =
+            vec![TransitionConstraintDegree::new(2), TransitionConstraintDegree::new(1)];
+
+        // z' * (α - w) = z * (α - v) has degree 2: each side is a product of an auxiliary column
+        // and an expression over a main column
+        let aux_degrees = vec![TransitionConstraintDegree::new(2)];
+
+        RangeCheckAir {
+            context: AirContext::new_multi_segment(
+                trace_info,
+                main_degrees,
+                aux_degrees,
+                5,
+                2,
+                None,
+                options,
+            ),
+            sum: pub_inputs,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    // MAIN SEGMENT CONSTRAINTS
+    // --------------------------------------------------------------------------------------------
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        debug_assert_eq!(TRACE_WIDTH, current.len());
+        debug_assert_eq!(TRACE_WIDTH, next.len());
+
+        // consecutive values in the sorted column must either be the same or differ by one
+        let delta = next[1] - current[1];
+        result[0] = delta * (delta - E::ONE);
+
+        // the running sum must be incremented by the current value
+        result[1] = are_equal(next[2], current[2] + current[0]);
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        let last_step = self.trace_length() - 1;
+        let max_value = BaseElement::from((RANGE_SIZE - 1) as u32);
+        vec![
+            // the sorted column must start at 0 and end at 255; together with the transition
+            // constraint this means that all values in the sorted column are in the range
+            Assertion::single(1, 0, BaseElement::ZERO),
+            Assertion::single(1, last_step, max_value),
+            // the last value must be the same in both the original and the sorted columns, as
+            // this row is not covered by the permutation argument
+            Assertion::single(0, last_step, max_value),
+            // the running sum must start at 0 and must sum up to the public input; since the
+            // last value is not included in the running sum, we subtract it from the public input
+            Assertion::single(2, 0, BaseElement::ZERO),
+            Assertion::single(2, last_step, self.sum - max_value),
+        ]
+    }
+
+    // AUXILIARY SEGMENT CONSTRAINTS
+    // --------------------------------------------------------------------------------------------
+
+    fn evaluate_aux_transition<F, E>(
+        &self,
+        main_frame: &EvaluationFrame<F>,
+        aux_frame: &EvaluationFrame<E>,
+        _periodic_values: &[F],
+        aux_rand_elements: &AuxRandElements<E>,
+        result: &mut [E],
+    ) where
+        F: FieldElement<BaseField = Self::BaseField>,
+        E: FieldElement<BaseField = Self::BaseField> + winterfell::math::ExtensionOf<F>,
+    {
+        let main_current = main_frame.current();
+        let z_current = aux_frame.current()[0];
+        let z_next = aux_frame.next()[0];
+
+        // the random challenge α was drawn from the public coin after the main trace segment was
+        // committed to; thus, the prover could not have chosen values of `v` and `w` to depend
+        // on it
+        let alpha = aux_rand_elements.rand_elements()[0];
+
+        // we multiply both sides of z' = z * (α - v) / (α - w) by (α - w) to avoid division;
+        // note that main trace values must be lifted into the extension field explicitly
+        let v: E = main_current[0].into();
+        let w: E = main_current[1].into();
+        result[0] = are_equal(z_next * (alpha - w), z_current * (alpha - v));
+    }
+
+    fn get_aux_assertions<E>(&self, _aux_rand_elements: &AuxRandElements<E>) -> Vec<Assertion<E>>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        // the running product must start and end at 1; if it ends at 1, the multisets
+        // {v_0, ..., v_{n-2}} and {w_0, ..., w_{n-2}} are the same with high probability
+        let last_step = self.trace_length() - 1;
+        vec![Assertion::single(0, 0, E::ONE), Assertion::single(0, last_step, E::ONE)]
+    }
+}
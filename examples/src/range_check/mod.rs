@@ -0,0 +1,173 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! An end-to-end example of a range check implemented via a permutation bus in an auxiliary trace
+//! segment.
+//!
+//! The example proves that the prover knows a list of values, all of which are in the range
+//! [0, 256), which sum up to a publicly known value. The range check is done as follows:
+//!
+//! 1. The main trace segment contains a column `v` with the values to be range-checked and a column
+//!    `w` which contains the same values sorted in ascending order. Transition constraints enforce
+//!    that consecutive values in `w` differ by either 0 or 1, and boundary constraints enforce that
+//!    `w` starts at 0 and ends at 255. Together, these imply that every value in `w` is in the
+//!    range [0, 256).
+//! 2. To make sure that `w` is a permutation of `v`, the prover commits to the main trace segment,
+//!    and only then draws a random challenge α from the public coin. Using this challenge, the
+//!    prover builds an auxiliary column `z` (the "bus") such that z_0 = 1 and
+//!    z_{i+1} = z_i * (α - v_i) / (α - w_i). If `v` and `w` contain the same multiset of values,
+//!    the last value of `z` will be 1; otherwise, it will be 1 only with negligible probability.
+//!
+//! Since `w` must contain every value in [0, 256) for the unit-step constraint to be satisfied,
+//! the prover pads the list of values with all values of the range.
+
+use core::marker::PhantomData;
+use std::time::Instant;
+
+use rand_utils::rand_vector;
+use tracing::{field, info_span};
+use winterfell::{
+    crypto::{DefaultRandomCoin, ElementHasher, MerkleTree},
+    math::{fields::f128::BaseElement, FieldElement},
+    Proof, ProofOptions, Prover, Trace, VerifierError,
+};
+
+use crate::{
+    Blake2s256, Blake3_192, Blake3_256, Example, ExampleOptions, HashFunction, Keccak256, Sha3_256,
+};
+
+mod air;
+use air::RangeCheckAir;
+
+mod prover;
+use prover::RangeCheckProver;
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of columns in the main segment of the execution trace.
+const TRACE_WIDTH: usize = 3;
+
+/// Number of columns in the auxiliary segment of the execution trace.
+const AUX_TRACE_WIDTH: usize = 1;
+
+/// All values must be smaller than this bound.
+const RANGE_SIZE: usize = 256;
+
+// RANGE CHECK EXAMPLE
+// ================================================================================================
+
+pub fn get_example(
+    options: &ExampleOptions,
+    num_values: usize,
+) -> Result<Box<dyn Example>, String> {
+    let (options, hash_fn) = options.to_proof_options(28, 8);
+
+    match hash_fn {
+        HashFunction::Blake3_192 => {
+            Ok(Box::new(RangeCheckExample::<Blake3_192>::new(num_values, options)))
+        },
+        HashFunction::Blake3_256 => {
+            Ok(Box::new(RangeCheckExample::<Blake3_256>::new(num_values, options)))
+        },
+        HashFunction::Sha3_256 => {
+            Ok(Box::new(RangeCheckExample::<Sha3_256>::new(num_values, options)))
+        },
+        HashFunction::Keccak256 => {
+            Ok(Box::new(RangeCheckExample::<Keccak256>::new(num_values, options)))
+        },
+        HashFunction::Blake2s256 => {
+            Ok(Box::new(RangeCheckExample::<Blake2s256>::new(num_values, options)))
+        },
+        _ => Err("The specified hash function cannot be used with this example.".to_string()),
+    }
+}
+
+pub struct RangeCheckExample<H: ElementHasher> {
+    options: ProofOptions,
+    values: Vec<u8>,
+    sum: BaseElement,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> RangeCheckExample<H> {
+    pub fn new(num_values: usize, options: ProofOptions) -> Self {
+        assert!(num_values > 0, "number of values must be greater than zero");
+
+        // generate random values and compute their sum; the sum also includes values which the
+        // prover will use to pad the trace, so that the verifier does not need to know how many
+        // values were range-checked
+        let now = Instant::now();
+        let values: Vec<u8> = rand_vector(num_values);
+        let sum = prover::pad_values(&values)
+            .into_iter()
+            .fold(BaseElement::ZERO, |acc, v| acc + BaseElement::from(v));
+        println!(
+            "Generated {} random values to be range-checked in {} ms",
+            num_values,
+            now.elapsed().as_millis()
+        );
+
+        RangeCheckExample {
+            options,
+            values,
+            sum,
+            _hasher: PhantomData,
+        }
+    }
+}
+
+// EXAMPLE IMPLEMENTATION
+// ================================================================================================
+
+impl<H: ElementHasher> Example for RangeCheckExample<H>
+where
+    H: ElementHasher<BaseField = BaseElement> + Sync,
+{
+    fn prove(&self) -> Proof {
+        println!("Generating proof for range-checking {} values", self.values.len());
+
+        // create a prover
+        let prover = RangeCheckProver::<H>::new(self.options.clone());
+
+        // generate the main segment of the execution trace; the auxiliary segment will be built
+        // by the prover once the main segment has been committed to
+        let trace =
+            info_span!("generate_execution_trace", num_cols = TRACE_WIDTH, steps = field::Empty)
+                .in_scope(|| {
+                    let trace = prover.build_trace(&self.values);
+                    tracing::Span::current().record("steps", trace.length());
+                    trace
+                });
+
+        // generate the proof
+        prover.prove(trace).unwrap()
+    }
+
+    fn verify(&self, proof: Proof) -> Result<(), VerifierError> {
+        let acceptable_options =
+            winterfell::AcceptableOptions::OptionSet(vec![proof.options().clone()]);
+
+        winterfell::verify::<RangeCheckAir, H, DefaultRandomCoin<H>, MerkleTree<H>>(
+            proof,
+            self.sum,
+            &acceptable_options,
+        )
+    }
+
+    fn verify_with_wrong_inputs(&self, proof: Proof) -> Result<(), VerifierError> {
+        let acceptable_options =
+            winterfell::AcceptableOptions::OptionSet(vec![proof.options().clone()]);
+
+        winterfell::verify::<RangeCheckAir, H, DefaultRandomCoin<H>, MerkleTree<H>>(
+            proof,
+            self.sum + BaseElement::ONE,
+            &acceptable_options,
+        )
+    }
+}
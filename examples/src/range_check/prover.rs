@@ -0,0 +1,215 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use std::borrow::Cow;
+
+use winterfell::{
+    crypto::MerkleTree, matrix::ColMatrix, AuxRandElements, CompositionPoly, CompositionPolyTrace,
+    ConstraintCompositionCoefficients, DefaultConstraintCommitment, DefaultConstraintEvaluator,
+    DefaultTraceLde, EvaluationFrame, PartitionOptions, StarkDomain, Trace, TraceInfo,
+    TraceLdeStorage, TracePolyTable,
+};
+
+use super::{
+    BaseElement, DefaultRandomCoin, ElementHasher, FieldElement, PhantomData, ProofOptions, Prover,
+    RangeCheckAir, AUX_TRACE_WIDTH, RANGE_SIZE, TRACE_WIDTH,
+};
+
+// RANGE CHECK TRACE
+// ================================================================================================
+
+/// Execution trace for the range check example.
+///
+/// [TraceTable](winterfell::TraceTable) does not describe auxiliary trace segments, and thus we
+/// need a custom implementation of the [Trace] trait. The [TraceInfo] of this trace specifies that
+/// the trace has a single auxiliary column, and that a single random element must be drawn from
+/// the public coin to build this column.
+pub struct RangeCheckTrace {
+    info: TraceInfo,
+    main_trace: ColMatrix<BaseElement>,
+}
+
+impl RangeCheckTrace {
+    fn new(columns: Vec<Vec<BaseElement>>) -> Self {
+        let trace_length = columns[0].len();
+        Self {
+            info: TraceInfo::new_multi_segment(
+                TRACE_WIDTH,
+                AUX_TRACE_WIDTH,
+                1,
+                trace_length,
+                vec![],
+            ),
+            main_trace: ColMatrix::new(columns),
+        }
+    }
+}
+
+impl Trace for RangeCheckTrace {
+    type BaseField = BaseElement;
+
+    fn info(&self) -> &TraceInfo {
+        &self.info
+    }
+
+    fn main_segment(&self) -> Cow<'_, ColMatrix<BaseElement>> {
+        Cow::Borrowed(&self.main_trace)
+    }
+
+    fn read_main_frame(&self, row_idx: usize, frame: &mut EvaluationFrame<BaseElement>) {
+        let next_row_idx = (row_idx + 1) % self.info.length();
+        self.main_trace.read_row_into(row_idx, frame.current_mut());
+        self.main_trace.read_row_into(next_row_idx, frame.next_mut());
+    }
+}
+
+// RANGE CHECK PROVER
+// ================================================================================================
+
+pub struct RangeCheckProver<H: ElementHasher> {
+    options: ProofOptions,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> RangeCheckProver<H> {
+    pub fn new(options: ProofOptions) -> Self {
+        Self { options, _hasher: PhantomData }
+    }
+
+    /// Builds the main segment of the execution trace for range-checking the provided values.
+    pub fn build_trace(&self, values: &[u8]) -> RangeCheckTrace {
+        let values = pad_values(values);
+        let trace_length = values.len();
+
+        // column 0 contains the values in their original order
+        let v = values.iter().map(|&v| BaseElement::from(v)).collect::<Vec<_>>();
+
+        // column 1 contains the values sorted in ascending order
+        let mut sorted_values = values.clone();
+        sorted_values.sort_unstable();
+        let w = sorted_values.into_iter().map(BaseElement::from).collect::<Vec<_>>();
+
+        // column 2 contains the running sum of the values
+        let mut s = Vec::with_capacity(trace_length);
+        s.push(BaseElement::ZERO);
+        for i in 0..trace_length - 1 {
+            s.push(s[i] + v[i]);
+        }
+
+        RangeCheckTrace::new(vec![v, w, s])
+    }
+}
+
+impl<H: ElementHasher> Prover for RangeCheckProver<H>
+where
+    H: ElementHasher<BaseField = BaseElement> + Sync,
+{
+    type BaseField = BaseElement;
+    type Air = RangeCheckAir;
+    type Trace = RangeCheckTrace;
+    type HashFn = H;
+    type VC = MerkleTree<H>;
+    type FriHashFn = Self::HashFn;
+    type FriVC = Self::VC;
+    type RandomCoin = DefaultRandomCoin<Self::HashFn>;
+    type TraceLde<E: FieldElement<BaseField = Self::BaseField>> =
+        DefaultTraceLde<E, Self::HashFn, Self::VC>;
+    type ConstraintCommitment<E: FieldElement<BaseField = Self::BaseField>> =
+        DefaultConstraintCommitment<E, H, Self::VC>;
+    type ConstraintEvaluator<'a, E: FieldElement<BaseField = Self::BaseField>> =
+        DefaultConstraintEvaluator<'a, Self::Air, E>;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> BaseElement {
+        let last_step = trace.length() - 1;
+        let main_trace = trace.main_segment();
+        main_trace.get(2, last_step) + main_trace.get(0, last_step)
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    fn new_trace_lde<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        trace_info: &TraceInfo,
+        main_trace: &ColMatrix<Self::BaseField>,
+        domain: &StarkDomain<Self::BaseField>,
+        partition_option: PartitionOptions,
+        storage: TraceLdeStorage,
+    ) -> (Self::TraceLde<E>, TracePolyTable<E>) {
+        DefaultTraceLde::with_storage(trace_info, main_trace, domain, partition_option, storage)
+    }
+
+    fn new_evaluator<'a, E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        air: &'a Self::Air,
+        aux_rand_elements: Option<AuxRandElements<E>>,
+        composition_coefficients: ConstraintCompositionCoefficients<E>,
+    ) -> Self::ConstraintEvaluator<'a, E> {
+        DefaultConstraintEvaluator::new(air, aux_rand_elements, composition_coefficients)
+    }
+
+    fn build_constraint_commitment<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        composition_poly_trace: CompositionPolyTrace<E>,
+        num_constraint_composition_columns: usize,
+        domain: &StarkDomain<Self::BaseField>,
+        partition_options: PartitionOptions,
+    ) -> (Self::ConstraintCommitment<E>, CompositionPoly<E>) {
+        DefaultConstraintCommitment::new(
+            composition_poly_trace,
+            num_constraint_composition_columns,
+            domain,
+            partition_options,
+        )
+    }
+
+    /// Builds the running product column once the main trace segment has been committed to and
+    /// the random challenge α has been drawn from the public coin.
+    fn build_aux_trace<E>(
+        &self,
+        trace: &Self::Trace,
+        aux_rand_elements: &AuxRandElements<E>,
+    ) -> ColMatrix<E>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        let main_trace = trace.main_segment();
+        let alpha = aux_rand_elements.rand_elements()[0];
+        let v = main_trace.get_column(0);
+        let w = main_trace.get_column(1);
+
+        // z_{i+1} = z_i * (α - v_i) / (α - w_i); instead of inverting each denominator
+        // separately, we compute all of them first, and then invert them in a single batch
+        let denominators = w.iter().map(|&w| alpha - E::from(w)).collect::<Vec<_>>();
+        let inv_denominators = winterfell::math::batch_inversion(&denominators);
+
+        let mut z = Vec::with_capacity(trace.length());
+        z.push(E::ONE);
+        for i in 0..trace.length() - 1 {
+            z.push(z[i] * (alpha - E::from(v[i])) * inv_denominators[i]);
+        }
+
+        ColMatrix::new(vec![z])
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Pads the provided values so that they can be range-checked in an execution trace.
+///
+/// The padded list contains the original values followed by all values in the range (which is
+/// needed for the sorted column to increase in unit steps from 0 to 255), and is then extended
+/// with the largest value in the range until its length is a power of two. Thus, the last value
+/// of the padded list is always 255.
+pub fn pad_values(values: &[u8]) -> Vec<u8> {
+    let trace_length = (values.len() + RANGE_SIZE).next_power_of_two();
+    let mut result = Vec::with_capacity(trace_length);
+    result.extend_from_slice(values);
+    result.extend((0..RANGE_SIZE).map(|v| v as u8));
+    result.resize(trace_length, (RANGE_SIZE - 1) as u8);
+    result
+}
@@ -13,7 +13,9 @@ use winterfell::{
     Proof, ProofOptions, Prover, Trace, VerifierError,
 };
 
-use crate::{Blake3_192, Blake3_256, Example, ExampleOptions, HashFunction, Sha3_256};
+use crate::{
+    Blake2s256, Blake3_192, Blake3_256, Example, ExampleOptions, HashFunction, Keccak256, Sha3_256,
+};
 
 #[allow(clippy::module_inception)]
 pub(crate) mod rescue;
@@ -53,6 +55,12 @@ pub fn get_example(
         HashFunction::Sha3_256 => {
             Ok(Box::new(RescueExample::<Sha3_256>::new(chain_length, options)))
         },
+        HashFunction::Keccak256 => {
+            Ok(Box::new(RescueExample::<Keccak256>::new(chain_length, options)))
+        },
+        HashFunction::Blake2s256 => {
+            Ok(Box::new(RescueExample::<Blake2s256>::new(chain_length, options)))
+        },
         _ => Err("The specified hash function cannot be used with this example.".to_string()),
     }
 }
@@ -3,6 +3,8 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use std::borrow::Cow;
+
 use core_utils::uninit_vector;
 use winterfell::{math::StarkField, matrix::ColMatrix, EvaluationFrame, Trace, TraceInfo};
 
@@ -40,7 +42,7 @@ impl<B: StarkField> RapTraceTable<B> {
     ///
     /// # Panics
     /// Panics if:
-    /// * `width` is zero or greater than 255.
+    /// * `width` is zero or greater than 65535.
     /// * `length` is smaller than 8, greater than biggest multiplicative subgroup in the field
     ///   `B`, or is not a power of two.
     pub fn new(width: usize, length: usize) -> Self {
@@ -55,7 +57,7 @@ impl<B: StarkField> RapTraceTable<B> {
     ///
     /// # Panics
     /// Panics if:
-    /// * `width` is zero or greater than 255.
+    /// * `width` is zero or greater than 65535.
     /// * `length` is smaller than 8, greater than the biggest multiplicative subgroup in the
     ///   field `B`, or is not a power of two.
     /// * Length of `meta` is greater than 65535;
@@ -163,7 +165,7 @@ impl<B: StarkField> Trace for RapTraceTable<B> {
         self.trace.read_row_into(next_row_idx, frame.next_mut());
     }
 
-    fn main_segment(&self) -> &ColMatrix<B> {
-        &self.trace
+    fn main_segment(&self) -> Cow<'_, ColMatrix<B>> {
+        Cow::Borrowed(&self.trace)
     }
 }
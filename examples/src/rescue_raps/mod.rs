@@ -14,7 +14,9 @@ use winterfell::{
     Proof, ProofOptions, Prover, Trace, VerifierError,
 };
 
-use crate::{Blake3_192, Blake3_256, Example, ExampleOptions, HashFunction, Sha3_256};
+use crate::{
+    Blake2s256, Blake3_192, Blake3_256, Example, ExampleOptions, HashFunction, Keccak256, Sha3_256,
+};
 
 mod custom_trace_table;
 pub use custom_trace_table::RapTraceTable;
@@ -56,6 +58,12 @@ pub fn get_example(
         HashFunction::Sha3_256 => {
             Ok(Box::new(RescueRapsExample::<Sha3_256>::new(chain_length, options)))
         },
+        HashFunction::Keccak256 => {
+            Ok(Box::new(RescueRapsExample::<Keccak256>::new(chain_length, options)))
+        },
+        HashFunction::Blake2s256 => {
+            Ok(Box::new(RescueRapsExample::<Blake2s256>::new(chain_length, options)))
+        },
         _ => Err("The specified hash function cannot be used with this example.".to_string()),
     }
 }
@@ -15,3 +15,26 @@ pub fn test_basic_proof_verification_fail(e: Box<dyn Example>) {
     let verified = e.verify_with_wrong_inputs(proof);
     assert!(verified.is_err());
 }
+
+pub fn test_canonical_proof_bytes(e: Box<dyn Example>) {
+    let proof1 = e.prove();
+    let proof2 = e.prove();
+    assert!(proof1.options().is_canonical());
+    assert_eq!(proof1.to_bytes(), proof2.to_bytes());
+    assert!(e.verify(proof1).is_ok());
+}
+
+/// Generates proofs for the example built by `build_example` in a single-threaded and in a
+/// multi-threaded thread pool, and checks that the proofs are byte-for-byte identical. When the
+/// `concurrent` feature is not enabled, proofs are always generated on the calling thread.
+pub fn test_canonical_proof_bytes_across_threads(build_example: fn() -> Box<dyn Example>) {
+    let prove_with_threads = |num_threads| {
+        let pool = rayon::ThreadPoolBuilder::new().num_threads(num_threads).build().unwrap();
+        pool.install(|| build_example().prove().to_bytes())
+    };
+
+    let proof_bytes = prove_with_threads(1);
+    for num_threads in [2, 4, 8] {
+        assert_eq!(proof_bytes, prove_with_threads(num_threads));
+    }
+}
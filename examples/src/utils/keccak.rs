@@ -0,0 +1,332 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! A gadget for proving Keccak-f permutations in an execution trace.
+//!
+//! The gadget works for any member of the Keccak-f[25 * w] family, where the lane width w is one
+//! of 1, 2, 4, 8, 16, 32, or 64 bits. A single round of the permutation is computed per row of
+//! the trace, and each row consists of:
+//! - 25 * w columns holding the bits of the state at the start of the round. Bit z of lane
+//!   (x, y) is located in column (x + 5 * y) * w + z.
+//! - 5 * w columns holding the bits of the theta effect D[x][z] = C[x - 1][z] ^ C[x + 1][z - 1],
+//!   where C[x][z] is the parity of column (x, z) of the state.
+//!
+//! Committing to D (rather than to the column parities C, or to the state after theta) keeps both
+//! the trace width and the constraint degree low: D is tied to the state via a single degree 6
+//! constraint which checks that the sum of the 10 bits it depends on has the same parity as D,
+//! and since D is a trace column, each bit of the state after theta is a degree 2 expression. The
+//! rho and pi steps are pure bit permutations and thus do not need any columns, and chi and iota
+//! are enforced directly against the bits of the next row, which results in degree 6 constraints.
+//!
+//! Round constants are supplied via periodic columns; since only bits at positions 2^j - 1 of
+//! Keccak round constants can be non-zero, only log2(w) + 1 such columns are needed.
+//!
+//! For Keccak-f[1600], the layout requires 1920 columns, and for Keccak-f[200], 240 columns.
+
+use winterfell::{
+    math::{FieldElement, StarkField},
+    TransitionConstraintDegree,
+};
+
+use crate::utils::{is_binary, EvaluationResult};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of lanes in the Keccak state.
+pub const NUM_LANES: usize = 25;
+
+/// Minimum cycle length required to describe a Keccak-f permutation; this covers 24 rounds of
+/// Keccak-f[1600], and all other members of the family need fewer rounds.
+pub const CYCLE_LENGTH: usize = 32;
+
+/// Rotation offsets for the rho step, indexed by x + 5 * y.
+const ROTATIONS: [u32; NUM_LANES] = [
+    0, 1, 62, 28, 27, 36, 44, 6, 55, 20, 3, 10, 43, 25, 39, 41, 45, 15, 21, 8, 18, 2, 61, 56, 14,
+];
+
+/// Round constants for the iota step of Keccak-f[1600]; for narrower permutations, the constants
+/// are truncated to the lane width.
+const ROUND_CONSTANTS: [u64; 24] = [
+    0x0000000000000001,
+    0x0000000000008082,
+    0x800000000000808a,
+    0x8000000080008000,
+    0x000000000000808b,
+    0x0000000080000001,
+    0x8000000080008081,
+    0x8000000000008009,
+    0x000000000000008a,
+    0x0000000000000088,
+    0x0000000080008009,
+    0x000000008000000a,
+    0x000000008000808b,
+    0x800000000000008b,
+    0x8000000000008089,
+    0x8000000000008003,
+    0x8000000000008002,
+    0x8000000000000080,
+    0x000000000000800a,
+    0x800000008000000a,
+    0x8000000080008081,
+    0x8000000000008080,
+    0x0000000080000001,
+    0x8000000080008008,
+];
+
+// KECCAK-F GADGET
+// ================================================================================================
+
+/// Keccak-f[25 * W] permutation gadget, where W is the lane width in bits.
+///
+/// The state of the permutation is represented as 25 lanes, each stored in the lower W bits of a
+/// `u64` value. Lane (x, y) is located at index x + 5 * y.
+pub struct KeccakF<const W: usize>;
+
+impl<const W: usize> KeccakF<W> {
+    /// Number of rounds in a single permutation.
+    pub const NUM_ROUNDS: usize = 12 + 2 * W.trailing_zeros() as usize;
+
+    /// Number of trace columns holding the bits of the state.
+    pub const STATE_WIDTH: usize = NUM_LANES * W;
+
+    /// Number of trace columns used by the gadget.
+    pub const TRACE_WIDTH: usize = Self::STATE_WIDTH + 5 * W;
+
+    /// Number of transition constraints enforced by the gadget. The first
+    /// [STATE_WIDTH](Self::STATE_WIDTH) constraints describe the next state of the permutation.
+    pub const NUM_CONSTRAINTS: usize = Self::STATE_WIDTH + 10 * W;
+
+    /// Number of periodic columns holding round constant bits.
+    pub const NUM_ROUND_CONSTANT_COLUMNS: usize = W.trailing_zeros() as usize + 1;
+
+    /// Mask for the bits of a lane.
+    const LANE_MASK: u64 = if W == 64 { u64::MAX } else { (1 << W) - 1 };
+
+    // PERMUTATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Applies Keccak-f permutation to the provided state.
+    pub fn apply_permutation(state: &mut [u64; NUM_LANES]) {
+        for round in 0..Self::NUM_ROUNDS {
+            Self::apply_round(state, round);
+        }
+    }
+
+    /// Applies a single round of Keccak-f permutation to the provided state.
+    pub fn apply_round(state: &mut [u64; NUM_LANES], round: usize) {
+        const { assert!(W.is_power_of_two() && W <= 64, "invalid lane width") };
+
+        // theta
+        let d = Self::get_theta_effect(state);
+        for (i, lane) in state.iter_mut().enumerate() {
+            *lane ^= d[i % 5];
+        }
+
+        // rho and pi
+        let mut b = [0; NUM_LANES];
+        for (i, &lane) in state.iter().enumerate() {
+            let (x, y) = (i % 5, i / 5);
+            b[y + 5 * ((2 * x + 3 * y) % 5)] = Self::rotate_left(lane, ROTATIONS[i]);
+        }
+
+        // chi
+        for (i, lane) in state.iter_mut().enumerate() {
+            let (x, y) = (i % 5, i / 5);
+            let b1 = b[(x + 1) % 5 + 5 * y];
+            let b2 = b[(x + 2) % 5 + 5 * y];
+            *lane = (b[i] ^ (!b1 & b2)) & Self::LANE_MASK;
+        }
+
+        // iota
+        state[0] ^= ROUND_CONSTANTS[round] & Self::LANE_MASK;
+    }
+
+    // TRACE FILLING
+    // --------------------------------------------------------------------------------------------
+
+    /// Writes the provided state into the first [TRACE_WIDTH](Self::TRACE_WIDTH) cells of the
+    /// specified row. This includes the bits of the state as well as the bits of the theta effect
+    /// needed to compute the next round of the permutation.
+    pub fn fill_row<E: FieldElement>(state: &[u64; NUM_LANES], row: &mut [E]) {
+        let (state_bits, theta_bits) = row[..Self::TRACE_WIDTH].split_at_mut(Self::STATE_WIDTH);
+        for (lane, bits) in state.iter().zip(state_bits.chunks_mut(W)) {
+            Self::write_bits(*lane, bits);
+        }
+
+        let d = Self::get_theta_effect(state);
+        for (lane, bits) in d.iter().zip(theta_bits.chunks_mut(W)) {
+            Self::write_bits(*lane, bits);
+        }
+    }
+
+    /// Returns the bits of the provided state in the same order in which they are laid out in the
+    /// execution trace.
+    pub fn get_state_bits<B: StarkField>(state: &[u64; NUM_LANES]) -> Vec<B> {
+        let mut result = vec![B::ZERO; Self::STATE_WIDTH];
+        for (lane, bits) in state.iter().zip(result.chunks_mut(W)) {
+            Self::write_bits(*lane, bits);
+        }
+        result
+    }
+
+    // CONSTRAINTS
+    // --------------------------------------------------------------------------------------------
+
+    /// When flag = 1, enforces constraints for a single round of Keccak-f permutation.
+    ///
+    /// `rc_bits` must contain the values of the round constant periodic columns; these values are
+    /// assumed to be zero whenever the flag is zero.
+    pub fn enforce_round<E: FieldElement>(
+        result: &mut [E],
+        current: &[E],
+        next: &[E],
+        rc_bits: &[E],
+        flag: E,
+    ) {
+        let state = &current[..Self::STATE_WIDTH];
+        let theta = &current[Self::STATE_WIDTH..Self::TRACE_WIDTH];
+        let bit = |x: usize, y: usize, z: usize| state[(x + 5 * y) * W + z];
+
+        // theta effect columns must be binary and must have the same parity as the sum of the
+        // bits they depend on
+        let (theta_result, binary_result) =
+            result[Self::STATE_WIDTH..Self::NUM_CONSTRAINTS].split_at_mut(5 * W);
+        for x in 0..5 {
+            for z in 0..W {
+                let mut sum = E::ZERO;
+                for y in 0..5 {
+                    sum += bit((x + 4) % 5, y, z) + bit((x + 1) % 5, y, (z + W - 1) % W);
+                }
+                let d = theta[x * W + z];
+                theta_result.agg_constraint(x * W + z, flag, is_even_up_to_10(sum - d));
+                binary_result.agg_constraint(x * W + z, flag, is_binary(d));
+            }
+        }
+
+        // apply theta, rho and pi; bit z of the rotated lane is bit z - r of the original lane
+        let mut b = vec![E::ZERO; Self::STATE_WIDTH];
+        for x in 0..5 {
+            for y in 0..5 {
+                let rotation = ROTATIONS[x + 5 * y] as usize % W;
+                let target = (y + 5 * ((2 * x + 3 * y) % 5)) * W;
+                for z in 0..W {
+                    let source = (z + W - rotation) % W;
+                    b[target + z] = xor(bit(x, y, source), theta[x * W + source]);
+                }
+            }
+        }
+
+        // apply chi and iota, and make sure the result is equal to the next state
+        for x in 0..5 {
+            for y in 0..5 {
+                for z in 0..W {
+                    let b0 = b[(x + 5 * y) * W + z];
+                    let b1 = b[((x + 1) % 5 + 5 * y) * W + z];
+                    let b2 = b[((x + 2) % 5 + 5 * y) * W + z];
+                    let chi = xor(b0, (E::ONE - b1) * b2);
+
+                    let idx = (x + 5 * y) * W + z;
+                    result.agg_constraint(idx, flag, next[idx] - chi);
+
+                    // round constant bits are zero whenever the flag is zero, and thus we can
+                    // apply them without multiplying by the flag; this keeps constraint degrees
+                    // lower
+                    if x == 0 && y == 0 && (z + 1).is_power_of_two() {
+                        let rc = rc_bits[(z + 1).trailing_zeros() as usize];
+                        result[idx] -= rc * (E::ONE - chi.double());
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns degrees of the transition constraints enforced by [enforce_round()](Self::enforce_round),
+    /// assuming the flag and round constants are supplied via periodic columns of length
+    /// [CYCLE_LENGTH].
+    pub fn get_constraint_degrees() -> Vec<TransitionConstraintDegree> {
+        let mut result = Vec::with_capacity(Self::NUM_CONSTRAINTS);
+        for _ in 0..Self::STATE_WIDTH + 5 * W {
+            result.push(TransitionConstraintDegree::with_cycles(6, vec![CYCLE_LENGTH]));
+        }
+        for _ in 0..5 * W {
+            result.push(TransitionConstraintDegree::with_cycles(2, vec![CYCLE_LENGTH]));
+        }
+        result
+    }
+
+    // PERIODIC COLUMNS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a periodic column which is set to 1 on the steps at which a round of the
+    /// permutation is applied, followed by the periodic columns with round constant bits.
+    pub fn get_periodic_column_values<B: StarkField>() -> Vec<Vec<B>> {
+        let mut round_flags = vec![B::ZERO; CYCLE_LENGTH];
+        round_flags[..Self::NUM_ROUNDS].fill(B::ONE);
+
+        let mut result = vec![round_flags];
+        for j in 0..Self::NUM_ROUND_CONSTANT_COLUMNS {
+            let position = (1 << j) - 1;
+            let mut column = vec![B::ZERO; CYCLE_LENGTH];
+            for (value, &rc) in column.iter_mut().zip(&ROUND_CONSTANTS[..Self::NUM_ROUNDS]) {
+                *value = B::from(((rc >> position) & 1) as u8);
+            }
+            result.push(column);
+        }
+        result
+    }
+
+    // HELPER FUNCTIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns D[x] = C[x - 1] ^ rot(C[x + 1], 1), where C[x] is the parity of column x.
+    fn get_theta_effect(state: &[u64; NUM_LANES]) -> [u64; 5] {
+        let mut c = [0; 5];
+        for (i, &lane) in state.iter().enumerate() {
+            c[i % 5] ^= lane;
+        }
+
+        let mut d = [0; 5];
+        for (x, d) in d.iter_mut().enumerate() {
+            *d = c[(x + 4) % 5] ^ Self::rotate_left(c[(x + 1) % 5], 1);
+        }
+        d
+    }
+
+    fn rotate_left(lane: u64, rotation: u32) -> u64 {
+        let rotation = rotation as usize % W;
+        if rotation == 0 {
+            lane
+        } else {
+            ((lane << rotation) | (lane >> (W - rotation))) & Self::LANE_MASK
+        }
+    }
+
+    fn write_bits<E: FieldElement>(lane: u64, bits: &mut [E]) {
+        for (z, bit) in bits.iter_mut().enumerate() {
+            *bit = E::from(((lane >> z) & 1) as u8);
+        }
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns a xor b; assumes that a and b are binary values.
+fn xor<E: FieldElement>(a: E, b: E) -> E {
+    a + b - (a * b).double()
+}
+
+/// Returns zero only when a is one of 0, 2, 4, 6, 8, 10.
+fn is_even_up_to_10<E: FieldElement>(a: E) -> E {
+    let two = E::from(2u8);
+    let mut result = a;
+    let mut value = a;
+    for _ in 0..5 {
+        value -= two;
+        result *= value;
+    }
+    result
+}
@@ -10,6 +10,7 @@ use winterfell::{
     Trace, TraceTable,
 };
 
+pub mod keccak;
 pub mod rescue;
 
 // CONSTRAINT EVALUATION HELPERS
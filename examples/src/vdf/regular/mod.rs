@@ -13,7 +13,9 @@ use winterfell::{
     Proof, ProofOptions, Prover, Trace, VerifierError,
 };
 
-use crate::{Blake3_192, Blake3_256, Example, ExampleOptions, HashFunction, Sha3_256};
+use crate::{
+    Blake2s256, Blake3_192, Blake3_256, Example, ExampleOptions, HashFunction, Keccak256, Sha3_256,
+};
 
 mod air;
 use air::{VdfAir, VdfInputs};
@@ -42,6 +44,8 @@ pub fn get_example(options: &ExampleOptions, num_steps: usize) -> Result<Box<dyn
         HashFunction::Blake3_192 => Ok(Box::new(VdfExample::<Blake3_192>::new(num_steps, options))),
         HashFunction::Blake3_256 => Ok(Box::new(VdfExample::<Blake3_256>::new(num_steps, options))),
         HashFunction::Sha3_256 => Ok(Box::new(VdfExample::<Sha3_256>::new(num_steps, options))),
+        HashFunction::Keccak256 => Ok(Box::new(VdfExample::<Keccak256>::new(num_steps, options))),
+        HashFunction::Blake2s256 => Ok(Box::new(VdfExample::<Blake2s256>::new(num_steps, options))),
         _ => Err("The specified hash function cannot be used with this example.".to_string()),
     }
 }
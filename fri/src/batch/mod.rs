@@ -0,0 +1,322 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Contains an implementation of batched FRI over random linear combinations of polynomials.
+
+use alloc::{string::ToString, vec::Vec};
+use core::marker::PhantomData;
+
+use crypto::{transcript_labels, ElementHasher, RandomCoin, VectorCommitment};
+use math::FieldElement;
+#[cfg(feature = "concurrent")]
+use utils::iterators::*;
+use utils::{
+    iter_mut, uninit_vector, ByteReader, ByteWriter, Deserializable, DeserializationError,
+    Serializable,
+};
+
+use crate::{
+    utils::dedup_positions, DefaultProverChannel, DefaultVerifierChannel, FriOptions, FriProof,
+    FriProver, FriVerifier, VerifierError,
+};
+
+#[cfg(test)]
+mod tests;
+
+// BATCH FRI PROOF
+// ================================================================================================
+
+/// A proof that each of several committed polynomials has degree bounded by some *d*.
+///
+/// The proof consists of a commitment to the evaluations of all polynomials, a FRI proof for a
+/// random linear combination of these polynomials, and the evaluations of all polynomials at the
+/// queried positions together with an opening proof against the commitment.
+pub struct BatchFriProof<E, H, V>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    V: VectorCommitment<H>,
+{
+    /// Commitment to the evaluations of all polynomials; the i-th committed item is the hash of
+    /// the evaluations of all polynomials at the i-th point of the domain.
+    pub commitment: H::Digest,
+    /// Commitments to the FRI layers of the linear combination of polynomials.
+    pub layer_commitments: Vec<H::Digest>,
+    /// Evaluations of all polynomials at the queried positions; evaluations at every position are
+    /// stored next to each other.
+    pub queried_values: Vec<E>,
+    /// Opening proof for `queried_values` against `commitment`.
+    pub opening_proof: V::MultiProof,
+    /// FRI proof for the linear combination of polynomials.
+    pub fri_proof: FriProof,
+}
+
+impl<E, H, V> Serializable for BatchFriProof<E, H, V>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    V: VectorCommitment<H>,
+{
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.commitment.write_into(target);
+        self.layer_commitments.write_into(target);
+        self.queried_values.write_into(target);
+        self.opening_proof.write_into(target);
+        self.fri_proof.write_into(target);
+    }
+}
+
+impl<E, H, V> Deserializable for BatchFriProof<E, H, V>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    V: VectorCommitment<H>,
+{
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        Ok(Self {
+            commitment: Deserializable::read_from(source)?,
+            layer_commitments: Deserializable::read_from(source)?,
+            queried_values: Deserializable::read_from(source)?,
+            opening_proof: Deserializable::read_from(source)?,
+            fri_proof: Deserializable::read_from(source)?,
+        })
+    }
+}
+
+// BATCH FRI PROVER
+// ================================================================================================
+
+/// Generates a single low-degree proof for several polynomials.
+///
+/// The prover commits to the evaluations of all polynomials at once, draws a random coefficient
+/// for every polynomial from the public coin seeded with this commitment, and executes FRI for
+/// the resulting linear combination of polynomials. The cost of FRI is thus paid once for all
+/// polynomials, while each query requires only one additional opening of the batch commitment.
+///
+/// If any of the polynomials has degree greater than *d*, so does the linear combination with
+/// overwhelming probability, and thus, the proof is rejected by [BatchFriVerifier].
+pub struct BatchFriProver<E, H, R, V>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    R: RandomCoin<BaseField = E::BaseField, Hasher: ElementHasher<Digest: From<H::Digest>>>,
+    V: VectorCommitment<H>,
+{
+    options: FriOptions,
+    num_queries: usize,
+    _phantom: PhantomData<(E, H, R, V)>,
+}
+
+impl<E, H, R, V> BatchFriProver<E, H, R, V>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    R: RandomCoin<BaseField = E::BaseField, Hasher: ElementHasher<Digest: From<H::Digest>>>,
+    V: VectorCommitment<H>,
+{
+    /// Returns a new batch prover instantiated with the provided `options` which queries the
+    /// committed polynomials at `num_queries` positions.
+    ///
+    /// # Panics
+    /// Panics if `num_queries` is zero.
+    pub fn new(options: FriOptions, num_queries: usize) -> Self {
+        assert!(num_queries > 0, "number of queries must be greater than zero");
+        Self {
+            options,
+            num_queries,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Returns a proof that all polynomials with the specified `evaluations` have degree smaller
+    /// than the size of their evaluation domain divided by the blowup factor.
+    ///
+    /// `evaluations` must contain evaluations of every polynomial over the same domain, shifted
+    /// by the domain offset specified in the options of this prover.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `evaluations` is empty.
+    /// * Evaluations of the polynomials have different lengths.
+    /// * The number of evaluations is smaller than 8 or is not a power of two.
+    pub fn prove(&self, evaluations: &[Vec<E>]) -> BatchFriProof<E, H, V> {
+        assert!(!evaluations.is_empty(), "at least one polynomial must be provided");
+        let domain_size = evaluations[0].len();
+        assert!(
+            evaluations.iter().all(|poly| poly.len() == domain_size),
+            "evaluations of all polynomials must have the same length"
+        );
+
+        // commit to the evaluations of all polynomials
+        let mut leaves: Vec<H::Digest> = unsafe { uninit_vector(domain_size) };
+        iter_mut!(leaves, 1024).enumerate().for_each(|(i, leaf)| {
+            let row = evaluations.iter().map(|poly| poly[i]).collect::<Vec<_>>();
+            *leaf = H::hash_elements(&row);
+        });
+        let commitment = V::new(leaves).expect("failed to build batch commitment");
+
+        // combine the polynomials using coefficients drawn from the public coin
+        let mut public_coin = R::new(&[]);
+        public_coin
+            .reseed_with_label(transcript_labels::BATCH_ROOT, commitment.commitment().into());
+        let coefficients = draw_coefficients::<E, R>(&mut public_coin, evaluations.len())
+            .expect("failed to draw batching coefficients");
+        let mut combination: Vec<E> = unsafe { uninit_vector(domain_size) };
+        iter_mut!(combination, 1024).enumerate().for_each(|(i, result)| {
+            *result = evaluations
+                .iter()
+                .zip(coefficients.iter())
+                .fold(E::ZERO, |acc, (poly, &coefficient)| acc + coefficient * poly[i]);
+        });
+
+        // run FRI for the linear combination
+        let mut channel = DefaultProverChannel::<E, H, R>::with_public_coin(
+            public_coin,
+            domain_size,
+            self.num_queries,
+        );
+        let mut fri_prover = FriProver::<E, _, H, V>::new(self.options.clone());
+        fri_prover.build_layers(&mut channel, combination);
+        let positions = dedup_positions(channel.draw_query_positions(0));
+
+        // open the batch commitment at the queried positions
+        let (_, opening_proof) =
+            commitment.open_many(&positions).expect("failed to open batch commitment");
+        let queried_values = positions
+            .iter()
+            .flat_map(|&position| evaluations.iter().map(move |poly| poly[position]))
+            .collect();
+
+        BatchFriProof {
+            commitment: commitment.commitment(),
+            layer_commitments: channel.layer_commitments().to_vec(),
+            queried_values,
+            opening_proof,
+            fri_proof: fri_prover.build_proof(&positions),
+        }
+    }
+}
+
+// BATCH FRI VERIFIER
+// ================================================================================================
+
+/// Verifies proofs generated by [BatchFriProver].
+pub struct BatchFriVerifier<E, H, R, V>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    R: RandomCoin<BaseField = E::BaseField, Hasher: ElementHasher<Digest: From<H::Digest>>>,
+    V: VectorCommitment<H>,
+{
+    options: FriOptions,
+    num_queries: usize,
+    _phantom: PhantomData<(E, H, R, V)>,
+}
+
+impl<E, H, R, V> BatchFriVerifier<E, H, R, V>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    R: RandomCoin<BaseField = E::BaseField, Hasher: ElementHasher<Digest: From<H::Digest>>>,
+    V: VectorCommitment<H>,
+{
+    /// Returns a new batch verifier instantiated with the provided `options` which expects the
+    /// committed polynomials to be queried at `num_queries` positions.
+    ///
+    /// # Panics
+    /// Panics if `num_queries` is zero.
+    pub fn new(options: FriOptions, num_queries: usize) -> Self {
+        assert!(num_queries > 0, "number of queries must be greater than zero");
+        Self {
+            options,
+            num_queries,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Returns `Ok(())` if the specified `proof` attests that each of `num_polys` committed
+    /// polynomials has degree at most `max_poly_degree`.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The number of queried values in the proof is inconsistent with `num_polys` and the
+    ///   number of queried positions.
+    /// * Queried values do not match the batch commitment.
+    /// * The FRI proof for the linear combination of the polynomials is not valid.
+    ///
+    /// # Panics
+    /// Panics if `num_polys` is zero.
+    pub fn verify(
+        &self,
+        proof: BatchFriProof<E, H, V>,
+        num_polys: usize,
+        max_poly_degree: usize,
+    ) -> Result<(), VerifierError> {
+        assert!(num_polys > 0, "at least one polynomial must be verified");
+        let domain_size = (max_poly_degree + 1).next_power_of_two() * self.options.blowup_factor();
+
+        // draw the coefficients of the linear combination
+        let mut public_coin = R::new(&[]);
+        public_coin.reseed_with_label(transcript_labels::BATCH_ROOT, proof.commitment.into());
+        let coefficients = draw_coefficients::<E, R>(&mut public_coin, num_polys)
+            .map_err(VerifierError::RandomCoinError)?;
+
+        // execute the commit phase of FRI and draw query positions
+        let mut channel = DefaultVerifierChannel::<E, H, V>::new(
+            proof.fri_proof,
+            proof.layer_commitments,
+            domain_size,
+            &self.options,
+        )
+        .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
+        let fri_verifier = FriVerifier::<E, _, H, R, V>::new(
+            &mut channel,
+            &mut public_coin,
+            self.options.clone(),
+            max_poly_degree,
+        )?;
+        let positions = dedup_positions(
+            public_coin
+                .draw_integers(self.num_queries, domain_size, 0)
+                .map_err(VerifierError::RandomCoinError)?,
+        );
+
+        // check the queried values against the batch commitment
+        if proof.queried_values.len() != positions.len() * num_polys {
+            return Err(VerifierError::NumPositionEvaluationMismatch(
+                positions.len() * num_polys,
+                proof.queried_values.len(),
+            ));
+        }
+        let rows = proof.queried_values.chunks(num_polys).collect::<Vec<_>>();
+        let leaves = rows.iter().map(|row| H::hash_elements(row)).collect::<Vec<_>>();
+        V::verify_many(proof.commitment, &positions, &leaves, &proof.opening_proof)
+            .map_err(|_| VerifierError::BatchCommitmentMismatch)?;
+
+        // verify the FRI proof against the linear combination of the queried values
+        let evaluations = rows
+            .iter()
+            .map(|row| {
+                row.iter().zip(coefficients.iter()).fold(E::ZERO, |acc, (&v, &c)| acc + c * v)
+            })
+            .collect::<Vec<_>>();
+        fri_verifier.verify(&mut channel, &evaluations, &positions)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Draws a random coefficient for each of `num_polys` polynomials from the specified coin.
+fn draw_coefficients<E, R>(
+    public_coin: &mut R,
+    num_polys: usize,
+) -> Result<Vec<E>, crypto::RandomCoinError>
+where
+    E: FieldElement,
+    R: RandomCoin<BaseField = E::BaseField>,
+{
+    (0..num_polys).map(|_| public_coin.draw()).collect()
+}
@@ -0,0 +1,91 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::vec::Vec;
+
+use crypto::{hashers::Blake3_256, DefaultRandomCoin, MerkleTree};
+use math::{fft, fields::f128::BaseElement, FieldElement};
+use rand_utils::rand_vector;
+use utils::{Deserializable, Serializable};
+
+use super::{BatchFriProof, BatchFriProver, BatchFriVerifier};
+use crate::{FriOptions, VerifierError};
+
+type Blake3 = Blake3_256<BaseElement>;
+type Prover = BatchFriProver<BaseElement, Blake3, DefaultRandomCoin<Blake3>, MerkleTree<Blake3>>;
+type Verifier =
+    BatchFriVerifier<BaseElement, Blake3, DefaultRandomCoin<Blake3>, MerkleTree<Blake3>>;
+type Proof = BatchFriProof<BaseElement, Blake3, MerkleTree<Blake3>>;
+
+const POLY_SIZE: usize = 1 << 8;
+const BLOWUP: usize = 8;
+const NUM_QUERIES: usize = 32;
+
+#[test]
+fn batch_fri_prove_verify() {
+    let options = FriOptions::new(BLOWUP, 4, 7);
+    let evaluations = vec![
+        build_evaluations(POLY_SIZE),
+        build_evaluations(POLY_SIZE / 2),
+        build_evaluations(3),
+    ];
+
+    let proof = Prover::new(options.clone(), NUM_QUERIES).prove(&evaluations);
+    let verifier = Verifier::new(options, NUM_QUERIES);
+
+    // the proof survives a serialization round trip
+    let proof = Proof::read_from_bytes(&proof.to_bytes()).unwrap();
+    assert_eq!(Ok(()), verifier.verify(proof, 3, POLY_SIZE - 1));
+}
+
+#[test]
+fn batch_fri_high_degree_polynomial() {
+    let options = FriOptions::new(BLOWUP, 4, 7);
+
+    // the second polynomial is not of low degree, and thus, neither is the linear combination
+    let evaluations = vec![build_evaluations(POLY_SIZE), rand_vector(POLY_SIZE * BLOWUP)];
+    let proof = Prover::new(options.clone(), NUM_QUERIES).prove(&evaluations);
+    let verifier = Verifier::new(options, NUM_QUERIES);
+    assert!(verifier.verify(proof, 2, POLY_SIZE - 1).is_err());
+}
+
+#[test]
+fn batch_fri_invalid_queried_values() {
+    let options = FriOptions::new(BLOWUP, 4, 7);
+    let evaluations = vec![build_evaluations(POLY_SIZE), build_evaluations(POLY_SIZE)];
+    let prover = Prover::new(options.clone(), NUM_QUERIES);
+    let verifier = Verifier::new(options, NUM_QUERIES);
+
+    // values which do not match the batch commitment
+    let mut proof = prover.prove(&evaluations);
+    proof.queried_values[1] += BaseElement::ONE;
+    assert_eq!(
+        Err(VerifierError::BatchCommitmentMismatch),
+        verifier.verify(proof, 2, POLY_SIZE - 1)
+    );
+
+    // values for a different number of polynomials
+    let proof = prover.prove(&evaluations);
+    let num_values = proof.queried_values.len();
+    assert_eq!(
+        Err(VerifierError::NumPositionEvaluationMismatch(num_values / 2 * 3, num_values)),
+        verifier.verify(proof, 3, POLY_SIZE - 1)
+    );
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns evaluations of a polynomial with `num_coeffs` coefficients over a domain of size
+/// `POLY_SIZE * BLOWUP`.
+fn build_evaluations(num_coeffs: usize) -> Vec<BaseElement> {
+    let mut p: Vec<BaseElement> = rand_vector(num_coeffs);
+    let domain_size = POLY_SIZE * BLOWUP;
+    p.resize(domain_size, BaseElement::ZERO);
+
+    let twiddles = fft::get_twiddles::<BaseElement>(domain_size);
+    fft::evaluate_poly(&mut p, &twiddles);
+    p
+}
@@ -3,6 +3,7 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use alloc::string::String;
 use core::fmt;
 
 use crypto::RandomCoinError;
@@ -18,16 +19,34 @@ pub enum VerifierError {
     /// Folding factor specified for the protocol is not supported. Currently, supported folding
     /// factors are: 4, 8, and 16.
     UnsupportedFoldingFactor(usize),
+    /// FRI proof data read from the channel could not be parsed.
+    ProofDeserializationError(String),
     /// Number of query positions does not match the number of provided evaluations.
     NumPositionEvaluationMismatch(usize, usize),
-    /// Evaluations at queried positions did not match layer commitment made by the prover.
-    LayerCommitmentMismatch,
-    /// Degree-respecting projection was not performed correctly at one of the layers.
-    InvalidLayerFolding(usize),
+    /// Evaluations at queried positions did not match layer commitment made by the prover at
+    /// the specified layer (i.e., opening proofs for the layer queries were invalid).
+    LayerCommitmentMismatch(usize),
+    /// Evaluations of batched polynomials at queried positions did not match the batch
+    /// commitment made by the prover.
+    BatchCommitmentMismatch,
+    /// The first FRI layer commitment in a standalone FRI proof did not match the commitment to
+    /// the codeword being verified.
+    CodewordCommitmentMismatch,
+    /// Number of out-of-domain evaluations of FRI layer polynomials does not match the number
+    /// expected for the number of FRI layers and the folding factor.
+    NumDeepEvaluationsMismatch(usize, usize),
+    /// Degree-respecting projection was not performed correctly at one of the layers; the values
+    /// are the index of the layer and the index of the first query (in the list of positions
+    /// provided to the verifier) for which the evaluation folded from the previous layer did not
+    /// match the evaluation opened at this layer.
+    InvalidLayerFolding(usize, usize),
     /// FRI remainder did not match the commitment.
     RemainderCommitmentMismatch,
-    /// Degree-respecting projection was not performed correctly at the last layer.
-    InvalidRemainderFolding,
+    /// Degree-respecting projection was not performed correctly at the last layer; the value is
+    /// the index of the first query (in the list of positions provided to the verifier) for which
+    /// the evaluation folded from the last layer did not match the evaluation of the remainder
+    /// polynomial.
+    InvalidRemainderFolding(usize),
     /// FRI remainder expected degree is greater than number of remainder values.
     RemainderDegreeNotValid,
     /// FRI remainder degree is greater than the polynomial degree expected for the last layer.
@@ -46,20 +65,32 @@ impl fmt::Display for VerifierError {
             Self::UnsupportedFoldingFactor(value) => {
                 write!(f, "folding factor {value} is not currently supported")
             }
+            Self::ProofDeserializationError(msg) => {
+                write!(f, "FRI proof deserialization failed: {msg}")
+            }
             Self::NumPositionEvaluationMismatch(num_positions, num_evaluations) => write!(f,
                 "the number of query positions must be the same as the number of polynomial evaluations, but {num_positions} and {num_evaluations} were provided"
             ),
-            Self::LayerCommitmentMismatch => {
-                write!(f, "FRI queries did not match layer commitment made by the prover")
+            Self::LayerCommitmentMismatch(layer) => {
+                write!(f, "FRI queries did not match layer commitment made by the prover at layer {layer}")
+            }
+            Self::BatchCommitmentMismatch => {
+                write!(f, "batched polynomial queries did not match the batch commitment made by the prover")
+            }
+            Self::CodewordCommitmentMismatch => {
+                write!(f, "FRI proof was generated for a codeword with a different commitment")
+            }
+            Self::NumDeepEvaluationsMismatch(expected, actual) => {
+                write!(f, "expected {expected} FRI DEEP evaluations, but received {actual}")
             }
-            Self::InvalidLayerFolding(layer) => {
-                write!(f, "degree-respecting projection is not consistent at layer {layer}")
+            Self::InvalidLayerFolding(layer, query) => {
+                write!(f, "degree-respecting projection is not consistent at layer {layer} for query {query}")
             }
             Self::RemainderCommitmentMismatch => {
                 write!(f, "FRI remainder did not match the commitment")
             }
-            Self::InvalidRemainderFolding => {
-                write!(f, "degree-respecting projection is inconsistent at the last FRI layer")
+            Self::InvalidRemainderFolding(query) => {
+                write!(f, "degree-respecting projection is inconsistent at the last FRI layer for query {query}")
             }
             Self::RemainderDegreeNotValid => {
                 write!(f, "FRI remainder expected degree is greater than number of remainder values")
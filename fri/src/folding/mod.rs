@@ -16,7 +16,7 @@ use math::{
 };
 #[cfg(feature = "concurrent")]
 use utils::iterators::*;
-use utils::{iter_mut, uninit_vector};
+use utils::{iter, iter_mut, uninit_vector};
 
 // DEGREE-RESPECTING PROJECTION
 // ================================================================================================
@@ -117,6 +117,67 @@ where
     result
 }
 
+// DEEP FOLDING
+// ================================================================================================
+/// Computes the value of the folded polynomial at `point`^N given the evaluations of the source
+/// polynomial at `point` * ω^j for j = 0, ..., N - 1, where ω is an N-th root of unity and N is
+/// the number of provided `evaluations`.
+///
+/// This is the out-of-domain counterpart of [apply_drp()]: for the same `alpha`, the returned
+/// value is equal to what [apply_drp()] would have computed for a domain row containing these
+/// evaluations.
+///
+/// # Panics
+/// Panics if the number of `evaluations` is not a power of two.
+pub fn fold_deep_evaluations<B, E>(evaluations: &[E], point: E, alpha: E) -> E
+where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+{
+    let inv_twiddles = get_inv_twiddles::<B>(evaluations.len());
+    let mut poly = evaluations.to_vec();
+    serial_fft(&mut poly, &inv_twiddles);
+
+    let inv_point = point.inv();
+    let mut offset = E::inv((evaluations.len() as u32).into());
+    for coeff in poly.iter_mut() {
+        *coeff *= offset;
+        offset *= inv_point;
+    }
+
+    polynom::eval(&poly, alpha)
+}
+
+/// Replaces folded `evaluations` with evaluations of the DEEP quotient (f(y) - v) / (y - z),
+/// where `z` is the `folded_point` and `v` is the `folded_value` of the folded polynomial at
+/// this point (see [fold_deep_evaluations()]).
+///
+/// The evaluations are expected to be over the folded domain, i.e., the domain generated by the
+/// root of unity of order `evaluations.len()` and shifted by `domain_offset`^N, where N is the
+/// `folding_factor`.
+pub fn apply_deep_quotient<B, E>(
+    evaluations: &mut [E],
+    domain_offset: B,
+    folding_factor: usize,
+    folded_point: E,
+    folded_value: E,
+) where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+{
+    let g = B::get_root_of_unity(evaluations.len().ilog2());
+    let offset = domain_offset.exp_vartime((folding_factor as u32).into());
+    let domain = get_power_series_with_offset(g, offset, evaluations.len());
+    let denominators = iter!(domain, 1024).map(|&y| E::from(y) - folded_point).collect::<Vec<_>>();
+    let inv_denominators = math::batch_inversion(&denominators);
+
+    iter_mut!(evaluations)
+        .zip(inv_denominators)
+        .for_each(|(value, inv_denominator)| {
+            *value = (*value - folded_value) * inv_denominator;
+        });
+}
+
 // POSITION FOLDING
 // ================================================================================================
 /// Maps positions in the source domain, to positions in the folded domain.
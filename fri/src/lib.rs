@@ -44,6 +44,43 @@
 //!    are provided to the [verify()](FriVerifier::verify()) function directly. The values at
 //!    remaining layers, the verifier reads from the specified verifier channel.
 //!
+//! # Standalone usage
+//! FRI can be used independently of the STARK protocol to prove that a committed codeword is
+//! close to evaluations of a low-degree polynomial via [StandaloneFriProver] and
+//! [StandaloneFriVerifier]:
+//!
+//! ```
+//! # use winter_fri::{FriOptions, StandaloneFriProver, StandaloneFriVerifier};
+//! # use crypto::{hashers::Blake3_256, DefaultRandomCoin, MerkleTree};
+//! # use math::{fft, fields::f128::BaseElement, FieldElement};
+//! type Hasher = Blake3_256<BaseElement>;
+//! type Prover =
+//!     StandaloneFriProver<BaseElement, Hasher, DefaultRandomCoin<Hasher>, MerkleTree<Hasher>>;
+//! type Verifier =
+//!     StandaloneFriVerifier<BaseElement, Hasher, DefaultRandomCoin<Hasher>, MerkleTree<Hasher>>;
+//!
+//! // evaluate a polynomial of degree 63 over a domain of 512 elements (blowup factor 8)
+//! let mut codeword = (0..64_u32).map(BaseElement::from).collect::<Vec<_>>();
+//! codeword.resize(512, BaseElement::ZERO);
+//! fft::evaluate_poly(&mut codeword, &fft::get_twiddles::<BaseElement>(512));
+//!
+//! // commit to the codeword and prove that it is of low degree
+//! let options = FriOptions::new(8, 4, 7);
+//! let prover = Prover::new(options.clone(), 32);
+//! let commitment = prover.commit(&codeword);
+//! let proof = prover.prove(codeword);
+//!
+//! // verify the proof against the commitment
+//! let verifier = Verifier::new(options, 32);
+//! assert!(verifier.verify(commitment, proof, 63).is_ok());
+//! ```
+//!
+//! # Pluggable low-degree testing
+//! [FriProver] and [FriVerifier] implement the [LowDegreeProver] and [LowDegreeVerifier] traits
+//! respectively. The STARK prover and verifier execute the low-degree testing step via these
+//! traits, and thus, alternative low-degree testing protocols (e.g., STIR) can be used in place
+//! of FRI by implementing them.
+//!
 //! # Protocol parameters
 //! The current implementation supports executing FRI protocol with dynamically configurable
 //! parameters including:
@@ -53,7 +90,8 @@
 //! * Domain blowup factor,
 //! * Hash function (used for building vector commitments),
 //! * Folding factor (used for degree reduction for each FRI layer),
-//! * Maximum size of the last FRI layer.
+//! * Maximum size of the last FRI layer,
+//! * DEEP folding (see [FriOptions::with_deep_folding()]).
 //!
 //! # References
 //! * StarkWare's blog post on [Low Degree Testing](https://medium.com/starkware/low-degree-testing-f7614f5172db)
@@ -66,19 +104,31 @@
 #[macro_use]
 extern crate alloc;
 
+#[cfg(feature = "std")]
+extern crate std;
+
 pub mod folding;
 
 mod prover;
-pub use prover::{DefaultProverChannel, FriProver, ProverChannel};
+pub use prover::{DefaultProverChannel, FriLayerStorage, FriProver, ProverChannel};
 
 mod verifier;
 pub use verifier::{DefaultVerifierChannel, FriVerifier, VerifierChannel};
 
+mod batch;
+pub use batch::{BatchFriProof, BatchFriProver, BatchFriVerifier};
+
+mod standalone;
+pub use standalone::{StandaloneFriProof, StandaloneFriProver, StandaloneFriVerifier};
+
+mod low_degree;
+pub use low_degree::{LowDegreeProver, LowDegreeVerifier};
+
 mod options;
 pub use options::FriOptions;
 
 mod proof;
-pub use proof::FriProof;
+pub use proof::{FriLayerReader, FriProof};
 
 mod errors;
 pub use errors::VerifierError;
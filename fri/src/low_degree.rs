@@ -0,0 +1,132 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Contains traits which abstract the low-degree testing step of the STARK protocol.
+
+use alloc::vec::Vec;
+
+use crypto::{ElementHasher, RandomCoin, VectorCommitment};
+use math::FieldElement;
+
+use crate::{
+    FriOptions, FriProof, FriProver, FriVerifier, ProverChannel, VerifierChannel, VerifierError,
+};
+
+// LOW-DEGREE PROVER
+// ================================================================================================
+
+/// Defines the prover side of a protocol for proving that a committed function is a polynomial
+/// of bounded degree.
+///
+/// The protocol is executed in two phases: in the commit phase, the prover writes commitments
+/// into the channel and draws challenges from it; in the query phase, the prover opens these
+/// commitments at the positions selected by the verifier. [FriProver] is the default
+/// implementation of this trait; alternative protocols (e.g., STIR) can be used in place of FRI
+/// by implementing this trait (together with [LowDegreeVerifier]).
+///
+/// Proofs are returned as instances of [FriProof]. This struct stores the proof as a sequence of
+/// layers (each consisting of queried values and an opening proof) followed by a final
+/// polynomial, and thus, can carry proofs of other protocols with a similar structure.
+pub trait LowDegreeProver<E, C>
+where
+    E: FieldElement,
+    C: ProverChannel<E>,
+{
+    /// Executes the commit phase of the protocol for the specified `evaluations`.
+    ///
+    /// All commitments are written into the `channel`, and all challenges are drawn from it.
+    fn build_layers(&mut self, channel: &mut C, evaluations: Vec<E>);
+
+    /// Executes the query phase of the protocol and returns a proof opening the commitments made
+    /// during the commit phase at the specified `positions`.
+    fn build_proof(&mut self, positions: &[usize]) -> FriProof;
+}
+
+impl<E, C, H, V> LowDegreeProver<E, C> for FriProver<E, C, H, V>
+where
+    E: FieldElement,
+    C: ProverChannel<E, Hasher = H>,
+    H: ElementHasher<BaseField = E::BaseField>,
+    V: VectorCommitment<H>,
+{
+    fn build_layers(&mut self, channel: &mut C, evaluations: Vec<E>) {
+        FriProver::build_layers(self, channel, evaluations)
+    }
+
+    fn build_proof(&mut self, positions: &[usize]) -> FriProof {
+        FriProver::build_proof(self, positions)
+    }
+}
+
+// LOW-DEGREE VERIFIER
+// ================================================================================================
+
+/// Defines the verifier side of a protocol for proving that a committed function is a
+/// polynomial of bounded degree.
+///
+/// [FriVerifier] is the default implementation of this trait.
+pub trait LowDegreeVerifier<E, C, R>: Sized
+where
+    E: FieldElement,
+    C: VerifierChannel<E>,
+    R: RandomCoin<BaseField = E::BaseField>,
+{
+    /// Executes the commit phase of the protocol from the verifier's perspective, and returns a
+    /// verifier for a polynomial of degree at most `max_poly_degree`.
+    ///
+    /// Commitments are read from the `channel` and absorbed into the `public_coin`, and all
+    /// challenges are drawn from the `public_coin`.
+    ///
+    /// # Errors
+    /// Returns an error if the commitments read from the channel are inconsistent with the
+    /// specified `options` and `max_poly_degree`, or if a challenge could not be drawn.
+    fn new(
+        channel: &mut C,
+        public_coin: &mut R,
+        options: FriOptions,
+        max_poly_degree: usize,
+    ) -> Result<Self, VerifierError>;
+
+    /// Executes the query phase of the protocol.
+    ///
+    /// Returns `Ok(())` if `evaluations` are consistent with evaluations of a polynomial of
+    /// degree at most `max_poly_degree` at the specified `positions`.
+    ///
+    /// # Errors
+    /// Returns an error if the proof read from the `channel` is not valid.
+    fn verify(
+        &self,
+        channel: &mut C,
+        evaluations: &[E],
+        positions: &[usize],
+    ) -> Result<(), VerifierError>;
+}
+
+impl<E, C, H, R, V> LowDegreeVerifier<E, C, R> for FriVerifier<E, C, H, R, V>
+where
+    E: FieldElement,
+    C: VerifierChannel<E, Hasher = H, VectorCommitment = V>,
+    H: ElementHasher<BaseField = E::BaseField>,
+    R: RandomCoin<BaseField = E::BaseField, Hasher: ElementHasher<Digest: From<H::Digest>>>,
+    V: VectorCommitment<H>,
+{
+    fn new(
+        channel: &mut C,
+        public_coin: &mut R,
+        options: FriOptions,
+        max_poly_degree: usize,
+    ) -> Result<Self, VerifierError> {
+        FriVerifier::new(channel, public_coin, options, max_poly_degree)
+    }
+
+    fn verify(
+        &self,
+        channel: &mut C,
+        evaluations: &[E],
+        positions: &[usize],
+    ) -> Result<(), VerifierError> {
+        FriVerifier::verify(self, channel, evaluations, positions)
+    }
+}
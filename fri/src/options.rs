@@ -3,6 +3,9 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use alloc::vec::Vec;
+use core::cmp;
+
 use math::StarkField;
 
 // FRI OPTIONS
@@ -12,8 +15,11 @@ use math::StarkField;
 #[derive(Clone, PartialEq, Eq)]
 pub struct FriOptions {
     folding_factor: usize,
+    folding_schedule: Vec<usize>,
     remainder_max_degree: usize,
+    skipping_max_degree: Option<usize>,
     blowup_factor: usize,
+    deep_folding: bool,
 }
 
 impl FriOptions {
@@ -29,20 +35,72 @@ impl FriOptions {
             blowup_factor.is_power_of_two(),
             "blowup factor must be a power of two, but was {blowup_factor}"
         );
-        assert!(
-            folding_factor == 2
-                || folding_factor == 4
-                || folding_factor == 8
-                || folding_factor == 16,
-            "folding factor {folding_factor} is not supported"
-        );
+        assert_supported_folding_factor(folding_factor);
         FriOptions {
             folding_factor,
+            folding_schedule: Vec::new(),
             remainder_max_degree,
+            skipping_max_degree: None,
             blowup_factor,
+            deep_folding: false,
         }
     }
 
+    /// Returns a new [FriOptions] struct with DEEP folding enabled.
+    ///
+    /// In DEEP mode, before drawing the folding challenge for a layer, the prover is asked to
+    /// evaluate the polynomial of that layer over a coset of size `folding_factor` derived from a
+    /// point sampled from the entire field (i.e., outside of the evaluation domain). The folded
+    /// layer is then replaced by its quotient with respect to the value implied by these
+    /// evaluations, which improves proven soundness per query at the cost of `folding_factor`
+    /// additional field elements per layer in the proof.
+    pub fn with_deep_folding(mut self) -> Self {
+        self.deep_folding = true;
+        self
+    }
+
+    /// Returns a new [FriOptions] struct with the specified folding schedule.
+    ///
+    /// The schedule specifies folding factors for the first FRI layers: the i-th layer is folded
+    /// by `schedule[i]`, and all layers beyond the schedule are folded by the `folding_factor`
+    /// of these options. For example, with a schedule of `[8, 8]` and a folding factor of 4, the
+    /// first two layers are folded by 8, and all subsequent layers are folded by 4. This allows
+    /// folding by large factors while the layers are large (reducing the number of layers, and
+    /// thus, the number of authentication paths per query), and by smaller factors once the
+    /// layers shrink (reducing the number of values per query).
+    ///
+    /// # Panics
+    /// Panics if any of the folding factors in the `schedule` is not 2, 4, 8, or 16.
+    pub fn with_folding_schedule(mut self, schedule: Vec<usize>) -> Self {
+        schedule
+            .iter()
+            .for_each(|&folding_factor| assert_supported_folding_factor(folding_factor));
+        self.folding_schedule = schedule;
+        self
+    }
+
+    /// Returns a new [FriOptions] struct with layer skipping enabled for polynomials of small
+    /// degree.
+    ///
+    /// Without layer skipping, FRI folds a polynomial until its degree drops to
+    /// `remainder_max_degree`, regardless of how small the polynomial was to begin with. Thus, for
+    /// short codewords, most of the FRI proof consists of layer openings which are larger than
+    /// the polynomial itself. With layer skipping, if the degree of the tested polynomial is at
+    /// most `max_degree`, FRI does not fold the polynomial at all and sends it to the verifier
+    /// directly; otherwise, if the degree of the polynomial folded once is at most `max_degree`,
+    /// FRI terminates after a single layer. Polynomials of larger degrees are folded as usual.
+    ///
+    /// # Panics
+    /// Panics if `max_degree` is not one less than a power of two.
+    pub fn with_layer_skipping(mut self, max_degree: usize) -> Self {
+        assert!(
+            (max_degree + 1).is_power_of_two(),
+            "layer skipping degree must be one less than a power of two, but was {max_degree}"
+        );
+        self.skipping_max_degree = Some(max_degree);
+        self
+    }
+
     /// Returns the offset by which the evaluation domain is shifted.
     ///
     /// The domain is shifted by multiplying every element in the domain by this offset.
@@ -53,7 +111,8 @@ impl FriOptions {
         B::GENERATOR
     }
 
-    /// Returns the factor by which the degree of a polynomial is reduced with each FRI layer.
+    /// Returns the factor by which the degree of a polynomial is reduced with each FRI layer
+    /// beyond the folding schedule.
     ///
     /// In combination with `remainder_max_degree_plus_1` this property defines how many FRI layers are
     /// needed for an evaluation domain of a given size.
@@ -61,6 +120,18 @@ impl FriOptions {
         self.folding_factor
     }
 
+    /// Returns folding factors of the first FRI layers; this is empty if all layers are folded
+    /// by the same `folding_factor`.
+    pub fn folding_schedule(&self) -> &[usize] {
+        &self.folding_schedule
+    }
+
+    /// Returns the factor by which the degree of a polynomial is reduced at the FRI layer with
+    /// the specified index.
+    pub fn layer_folding_factor(&self, layer_idx: usize) -> usize {
+        self.folding_schedule.get(layer_idx).copied().unwrap_or(self.folding_factor)
+    }
+
     /// Returns maximum allowed remainder polynomial degree.
     ///
     /// In combination with `folding_factor` this property defines how many FRI layers are needed
@@ -69,6 +140,17 @@ impl FriOptions {
         self.remainder_max_degree
     }
 
+    /// Returns the maximum degree of polynomials for which FRI layers are skipped, or `None` if
+    /// layer skipping is not enabled.
+    pub fn skipping_max_degree(&self) -> Option<usize> {
+        self.skipping_max_degree
+    }
+
+    /// Returns true if folding challenges are sampled using the DEEP variant of FRI.
+    pub fn deep_folding(&self) -> bool {
+        self.deep_folding
+    }
+
     /// Returns a blowup factor of the evaluation domain.
     ///
     /// Specifically, if the polynomial for which the FRI protocol is executed is of degree `d`
@@ -80,15 +162,38 @@ impl FriOptions {
 
     /// Computes and return the number of FRI layers required for a domain of the specified size.
     ///
-    /// The number of layers for a given domain size is defined by the `folding_factor` and
-    /// `remainder_max_degree` and `blowup_factor` settings.
-    pub fn num_fri_layers(&self, mut domain_size: usize) -> usize {
+    /// The number of layers for a given domain size is defined by the `folding_factor`,
+    /// `folding_schedule`, `remainder_max_degree`, and `blowup_factor` settings, as well as by
+    /// the layer skipping setting (see [FriOptions::with_layer_skipping()]).
+    pub fn num_fri_layers(&self, domain_size: usize) -> usize {
         let mut result = 0;
+        let mut layer_domain_size = domain_size;
         let max_remainder_size = (self.remainder_max_degree + 1) * self.blowup_factor;
-        while domain_size > max_remainder_size {
-            domain_size /= self.folding_factor;
+        while layer_domain_size > max_remainder_size {
+            layer_domain_size /= self.layer_folding_factor(result);
             result += 1;
         }
+
+        // for small polynomials, terminate after zero or one layers
+        if let Some(max_degree) = self.skipping_max_degree {
+            let max_skipped_size = (max_degree + 1) * self.blowup_factor;
+            if domain_size <= max_skipped_size {
+                return 0;
+            } else if domain_size / self.layer_folding_factor(0) <= max_skipped_size {
+                return cmp::min(result, 1);
+            }
+        }
+
         result
     }
 }
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn assert_supported_folding_factor(folding_factor: usize) {
+    assert!(
+        folding_factor == 2 || folding_factor == 4 || folding_factor == 8 || folding_factor == 16,
+        "folding factor {folding_factor} is not supported"
+    );
+}
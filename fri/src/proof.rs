@@ -3,7 +3,11 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use alloc::{string::ToString, vec::Vec};
+use alloc::{
+    string::ToString,
+    vec::{self, Vec},
+};
+use core::marker::PhantomData;
 
 use crypto::{ElementHasher, Hasher, VectorCommitment};
 use math::FieldElement;
@@ -11,6 +15,8 @@ use utils::{
     ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable, SliceReader,
 };
 
+use crate::FriOptions;
+
 // FRI PROOF
 // ================================================================================================
 
@@ -24,16 +30,40 @@ use utils::{
 /// A proof consists of zero or more layers and a remainder polynomial. Each layer contains a set of
 /// polynomial evaluations at positions queried by the verifier, a vector commitment to LDE of
 /// each polynomial, as well as opening proofs for the evaluations against the vector commitments.
-/// The remainder polynomial is given by its list of coefficients i.e. field elements.
+/// The remainder polynomial is given by its list of coefficients i.e. field elements. Proofs
+/// generated with DEEP folding also contain evaluations of every layer polynomial (except for
+/// the remainder) over an out-of-domain coset.
 ///
 /// All values in a proof are stored as vectors of bytes. Thus, the values must be parsed before
 /// they can be returned to the user. To do this, [parse_layers()](FriProof::parse_layers())
-/// and [parse_remainder()](FriProof::parse_remainder()) methods can be used.
+/// (or [into_layer_reader()](FriProof::into_layer_reader()) to parse layers one at a time) and
+/// [parse_remainder()](FriProof::parse_remainder()) methods can be used.
+///
+/// # Serialization
+/// A serialized proof has the following byte layout, where `vint` denotes a `usize` encoded in
+/// [vint64](https://docs.rs/vint64/latest/vint64/) format (1 byte for values below 128):
+///
+/// | field                       | encoding                                               |
+/// | --------------------------- | ------------------------------------------------------ |
+/// | number of layers            | `u8`                                                   |
+/// | layers                      | for each layer: `vint` length of query values, query   |
+/// |                             | values, `vint` length of opening proof, opening proof  |
+/// | remainder                   | `vint` length, followed by remainder coefficients      |
+/// | number of partitions        | `u8` (log2 of the number of partitions)                |
+/// | DEEP evaluations            | `vint` length, followed by evaluations                 |
+///
+/// Query positions are not part of the proof: they are drawn by the verifier from its public
+/// coin and are shared across all layers (positions for the next layer are obtained by folding
+/// positions of the current layer). Thus, the query values of a layer are stored as a contiguous
+/// sequence of rows (one row of `folding_factor` field elements per unique folded position,
+/// in the order of the first occurrence of the position), and an opening proof is stored in the
+/// encoding of the vector commitment used for the layer.
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct FriProof {
     layers: Vec<FriProofLayer>,
     remainder: Vec<u8>,
     num_partitions: u8, // stored as power of 2
+    deep_evaluations: Vec<u8>,
 }
 
 impl FriProof {
@@ -69,15 +99,26 @@ impl FriProof {
             layers,
             remainder: remainder_bytes,
             num_partitions: num_partitions.trailing_zeros() as u8,
+            deep_evaluations: Vec::new(),
         }
     }
 
+    /// Returns this proof with the provided out-of-domain evaluations of FRI layer polynomials
+    /// attached to it.
+    pub(crate) fn with_deep_evaluations<E: FieldElement>(mut self, evaluations: &[E]) -> Self {
+        let mut deep_evaluations = Vec::with_capacity(E::ELEMENT_BYTES * evaluations.len());
+        deep_evaluations.write_many(evaluations);
+        self.deep_evaluations = deep_evaluations;
+        self
+    }
+
     /// Creates a dummy `FriProof` for use in tests.
     pub fn new_dummy() -> Self {
         Self {
             layers: Vec::new(),
             remainder: Vec::new(),
             num_partitions: 0,
+            deep_evaluations: Vec::new(),
         }
     }
 
@@ -102,12 +143,22 @@ impl FriProof {
         2usize.pow(self.num_partitions as u32)
     }
 
+    /// Returns the number of out-of-domain evaluations of FRI layer polynomials in this proof.
+    ///
+    /// This is zero unless the proof was generated with DEEP folding.
+    pub fn num_deep_evaluations<E: FieldElement>(&self) -> usize {
+        self.deep_evaluations.len() / E::ELEMENT_BYTES
+    }
+
     /// Returns the size of this proof in bytes.
     pub fn size(&self) -> usize {
-        // +1 for number of layers, +1 for remainder length, +1 for number of partitions
+        // +1 for number of layers, +1 for number of partitions
+        let remainder = self.remainder.len().get_size_hint() + self.remainder.len();
+        let deep_evaluations =
+            self.deep_evaluations.len().get_size_hint() + self.deep_evaluations.len();
         self.layers
             .iter()
-            .fold(self.remainder.len() + 3, |acc, layer| acc + layer.size())
+            .fold(remainder + deep_evaluations + 2, |acc, layer| acc + layer.size())
     }
 
     // PARSING
@@ -116,49 +167,36 @@ impl FriProof {
     /// Decomposes this proof into vectors of query values for each layer and corresponding batch
     /// opening proofs.
     ///
+    /// This parses all layers at once; to parse layers one at a time (e.g., to bound the amount
+    /// of memory needed by the verifier), [into_layer_reader()](FriProof::into_layer_reader())
+    /// can be used instead.
+    ///
+    /// Folding factors of the layers are defined by the specified `options`.
+    ///
     /// # Panics
-    /// Panics if:
-    /// * `domain_size` is not a power of two.
-    /// * `folding_factor` is smaller than two or is not a power of two.
+    /// Panics if `domain_size` is not a power of two.
     ///
     /// # Errors
     /// Returns an error if:
-    /// * This proof is not consistent with the specified `domain_size` and `folding_factor`.
+    /// * This proof is not consistent with the specified `domain_size` and `options`.
     /// * Any of the layers could not be parsed successfully.
     #[allow(clippy::type_complexity)]
     pub fn parse_layers<E, H, V>(
         self,
-        mut domain_size: usize,
-        folding_factor: usize,
+        domain_size: usize,
+        options: &FriOptions,
     ) -> Result<(Vec<Vec<E>>, Vec<<V as VectorCommitment<H>>::MultiProof>), DeserializationError>
     where
         E: FieldElement,
         H: ElementHasher<BaseField = E::BaseField>,
         V: VectorCommitment<H>,
     {
-        assert!(domain_size.is_power_of_two(), "domain size must be a power of two");
-        assert!(folding_factor.is_power_of_two(), "folding factor must be a power of two");
-        assert!(folding_factor > 1, "folding factor must be greater than 1");
-
         let mut layer_proofs = Vec::new();
         let mut layer_queries = Vec::new();
 
         // parse all layers
-        for (i, layer) in self.layers.into_iter().enumerate() {
-            domain_size /= folding_factor;
-            let (qv, op) = layer.parse::<_, H, V>(folding_factor).map_err(|err| {
-                DeserializationError::InvalidValue(format!("failed to parse FRI layer {i}: {err}"))
-            })?;
-
-            // check that the opening proof matches the domain length
-            if <V as VectorCommitment<H>>::get_multiproof_domain_len(&op) != domain_size {
-                return Err(DeserializationError::InvalidValue(format!(
-                    "expected a domain of size {} but was {}",
-                    domain_size,
-                    <V as VectorCommitment<H>>::get_multiproof_domain_len(&op),
-                )));
-            }
-
+        for layer in self.into_layer_reader::<E, H, V>(domain_size, options) {
+            let (qv, op) = layer?;
             layer_proofs.push(op);
             layer_queries.push(qv);
         }
@@ -166,6 +204,39 @@ impl FriProof {
         Ok((layer_queries, layer_proofs))
     }
 
+    /// Consumes this proof and returns a [FriLayerReader] which parses the layers of this proof
+    /// one at a time.
+    ///
+    /// The bytes of each layer are released as soon as the layer is parsed. Thus, the memory
+    /// needed to hold parsed query values and opening proofs is bounded by the size of a single
+    /// layer. The remainder is not retained and should be parsed via
+    /// [parse_remainder()](FriProof::parse_remainder()) before calling this method.
+    ///
+    /// Folding factors of the layers are defined by the specified `options`.
+    ///
+    /// # Panics
+    /// Panics if `domain_size` is not a power of two.
+    pub fn into_layer_reader<E, H, V>(
+        self,
+        domain_size: usize,
+        options: &FriOptions,
+    ) -> FriLayerReader<E, H, V>
+    where
+        E: FieldElement,
+        H: ElementHasher<BaseField = E::BaseField>,
+        V: VectorCommitment<H>,
+    {
+        assert!(domain_size.is_power_of_two(), "domain size must be a power of two");
+
+        FriLayerReader {
+            layers: self.layers.into_iter(),
+            domain_size,
+            options: options.clone(),
+            depth: 0,
+            _phantom: PhantomData,
+        }
+    }
+
     /// Returns a vector of remainder values (last FRI layer) parsed from this proof.
     ///
     /// # Errors
@@ -190,6 +261,26 @@ impl FriProof {
         }
         Ok(remainder)
     }
+
+    /// Returns a vector of out-of-domain evaluations of FRI layer polynomials parsed from this
+    /// proof.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * Any of the values could not be parsed correctly.
+    /// * Not all bytes have been consumed while parsing the values.
+    pub fn parse_deep_evaluations<E: FieldElement>(&self) -> Result<Vec<E>, DeserializationError> {
+        let mut reader = SliceReader::new(&self.deep_evaluations);
+        let evaluations = reader.read_many(self.num_deep_evaluations::<E>()).map_err(|err| {
+            DeserializationError::InvalidValue(format!(
+                "failed to parse FRI DEEP evaluations: {err}"
+            ))
+        })?;
+        if reader.has_more_bytes() {
+            return Err(DeserializationError::UnconsumedBytes);
+        }
+        Ok(evaluations)
+    }
 }
 
 // SERIALIZATION / DESERIALIZATION
@@ -205,11 +296,15 @@ impl Serializable for FriProof {
         }
 
         // write remainder
-        target.write_u16(self.remainder.len() as u16);
+        target.write_usize(self.remainder.len());
         target.write_bytes(&self.remainder);
 
         // write number of partitions
         target.write_u8(self.num_partitions);
+
+        // write DEEP evaluations
+        target.write_usize(self.deep_evaluations.len());
+        target.write_bytes(&self.deep_evaluations);
     }
 }
 
@@ -224,13 +319,104 @@ impl Deserializable for FriProof {
         let layers = source.read_many(num_layers)?;
 
         // read remainder
-        let num_remainder_bytes = source.read_u16()? as usize;
+        let num_remainder_bytes = source.read_usize()?;
         let remainder = source.read_vec(num_remainder_bytes)?;
 
         // read number of partitions
         let num_partitions = source.read_u8()?;
 
-        Ok(FriProof { layers, remainder, num_partitions })
+        // read DEEP evaluations
+        let num_deep_evaluation_bytes = source.read_usize()?;
+        let deep_evaluations = source.read_vec(num_deep_evaluation_bytes)?;
+
+        Ok(FriProof {
+            layers,
+            remainder,
+            num_partitions,
+            deep_evaluations,
+        })
+    }
+}
+
+// FRI LAYER READER
+// ================================================================================================
+
+/// An iterator which parses layers of a [FriProof] one at a time.
+///
+/// Each item is a tuple of query values and the corresponding batch opening proof for the next
+/// FRI layer. Raw bytes of a layer are dropped as soon as the layer is parsed, and parsed values
+/// are owned by the caller; thus, a verifier which processes one layer before reading the next
+/// one holds at most a single parsed layer in memory.
+///
+/// Parsing is fallible on a per-layer basis: an error in one layer does not affect the layers
+/// read before it, and the layers after it are never parsed. Thus, a verifier which checks each
+/// layer against its commitment before reading the next one aborts on the first inconsistent
+/// layer without parsing (or allocating memory for the values of) the rest of the proof.
+///
+/// A reader can be obtained via [FriProof::into_layer_reader()].
+pub struct FriLayerReader<E, H, V>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    V: VectorCommitment<H>,
+{
+    layers: vec::IntoIter<FriProofLayer>,
+    domain_size: usize,
+    options: FriOptions,
+    depth: usize,
+    _phantom: PhantomData<(E, H, V)>,
+}
+
+impl<E, H, V> FriLayerReader<E, H, V>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    V: VectorCommitment<H>,
+{
+    /// Returns the number of layers which have not been parsed yet.
+    pub fn num_remaining_layers(&self) -> usize {
+        self.layers.len()
+    }
+}
+
+impl<E, H, V> Iterator for FriLayerReader<E, H, V>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    V: VectorCommitment<H>,
+{
+    type Item = Result<(Vec<E>, <V as VectorCommitment<H>>::MultiProof), DeserializationError>;
+
+    /// Parses the next layer of the proof.
+    ///
+    /// Returns an error if the layer could not be parsed, or if its opening proof is not
+    /// consistent with the domain size expected at this layer.
+    fn next(&mut self) -> Option<Self::Item> {
+        let layer = self.layers.next()?;
+        let depth = self.depth;
+        let folding_factor = self.options.layer_folding_factor(depth);
+        self.depth += 1;
+        self.domain_size /= folding_factor;
+
+        let result = layer.parse::<E, H, V>(folding_factor).map_err(|err| {
+            DeserializationError::InvalidValue(format!("failed to parse FRI layer {depth}: {err}"))
+        });
+
+        // check that the opening proof matches the domain length
+        Some(result.and_then(|(qv, op)| {
+            let domain_len = <V as VectorCommitment<H>>::get_multiproof_domain_len(&op);
+            if domain_len != self.domain_size {
+                return Err(DeserializationError::InvalidValue(format!(
+                    "expected a domain of size {} but was {}",
+                    self.domain_size, domain_len,
+                )));
+            }
+            Ok((qv, op))
+        }))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.layers.size_hint()
     }
 }
 
@@ -273,8 +459,10 @@ impl FriProofLayer {
 
     /// Returns the size of this proof layer in bytes.
     pub fn size(&self) -> usize {
-        // +4 for length of values, +4 for length of paths
-        self.values.len() + 4 + self.paths.len() + 4
+        self.values.len().get_size_hint()
+            + self.values.len()
+            + self.paths.len().get_size_hint()
+            + self.paths.len()
     }
 
     // PARSING
@@ -344,11 +532,11 @@ impl Serializable for FriProofLayer {
     /// Serializes this proof layer and writes the resulting bytes to the specified `target`.
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
         // write value bytes
-        target.write_u32(self.values.len() as u32);
+        target.write_usize(self.values.len());
         target.write_bytes(&self.values);
 
         // write path bytes
-        target.write_u32(self.paths.len() as u32);
+        target.write_usize(self.paths.len());
         target.write_bytes(&self.paths);
     }
 }
@@ -360,17 +548,17 @@ impl Deserializable for FriProofLayer {
     /// Returns an error if a valid layer could not be read from the specified source.
     fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
         // read values
-        let num_value_bytes = source.read_u32()?;
+        let num_value_bytes = source.read_usize()?;
         if num_value_bytes == 0 {
             return Err(DeserializationError::InvalidValue(
                 "a FRI proof layer must contain at least one queried evaluation".to_string(),
             ));
         }
-        let values = source.read_vec(num_value_bytes as usize)?;
+        let values = source.read_vec(num_value_bytes)?;
 
         // read paths
-        let num_paths_bytes = source.read_u32()?;
-        let paths = source.read_vec(num_paths_bytes as usize)?;
+        let num_paths_bytes = source.read_usize()?;
+        let paths = source.read_vec(num_paths_bytes)?;
 
         Ok(FriProofLayer { values, paths })
     }
@@ -6,7 +6,7 @@
 use alloc::vec::Vec;
 use core::marker::PhantomData;
 
-use crypto::{ElementHasher, Hasher, RandomCoin};
+use crypto::{transcript_labels, ElementHasher, Hasher, RandomCoin};
 use math::FieldElement;
 
 // PROVER CHANNEL TRAIT
@@ -44,6 +44,17 @@ pub trait ProverChannel<E: FieldElement> {
     /// prover, in the non-interactive version, the α is pseudo-randomly generated based on the
     /// values the prover previously wrote into the channel.
     fn draw_fri_alpha(&mut self) -> E;
+
+    /// Returns a random point z drawn uniformly at random from the entire field.
+    ///
+    /// This is used only when DEEP folding is enabled: the prover evaluates the polynomial of
+    /// the last committed FRI layer over the coset generated by z and sends these evaluations to
+    /// the verifier via [ProverChannel::commit_fri_deep_evaluations()].
+    fn draw_fri_deep_point(&mut self) -> E;
+
+    /// Sends evaluations of the polynomial of the last committed FRI layer at the out-of-domain
+    /// coset generated by the most recently drawn DEEP point to the verifier.
+    fn commit_fri_deep_evaluations(&mut self, evaluations: &[E]);
 }
 
 // DEFAULT PROVER CHANNEL IMPLEMENTATION
@@ -57,7 +68,7 @@ pub struct DefaultProverChannel<E, H, R>
 where
     E: FieldElement,
     H: ElementHasher<BaseField = E::BaseField>,
-    R: RandomCoin<BaseField = E::BaseField, Hasher = H>,
+    R: RandomCoin<BaseField = E::BaseField, Hasher: ElementHasher<Digest: From<H::Digest>>>,
 {
     public_coin: R,
     commitments: Vec<H::Digest>,
@@ -70,7 +81,7 @@ impl<E, H, R> DefaultProverChannel<E, H, R>
 where
     E: FieldElement,
     H: ElementHasher<BaseField = E::BaseField>,
-    R: RandomCoin<BaseField = E::BaseField, Hasher = H>,
+    R: RandomCoin<BaseField = E::BaseField, Hasher: ElementHasher<Digest: From<H::Digest>>>,
 {
     /// Returns a new prover channel instantiated from the specified parameters.
     ///
@@ -79,6 +90,20 @@ where
     /// * `domain_size` is smaller than 8 or is not a power of two.
     /// * `num_queries` is zero.
     pub fn new(domain_size: usize, num_queries: usize) -> Self {
+        Self::with_public_coin(RandomCoin::new(&[]), domain_size, num_queries)
+    }
+
+    /// Returns a new prover channel which draws FRI challenges and query positions from the
+    /// provided public coin.
+    ///
+    /// This allows FRI to be bound to the messages which have already been absorbed into the
+    /// coin (e.g., commitments to the polynomials FRI is executed for).
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `domain_size` is smaller than 8 or is not a power of two.
+    /// * `num_queries` is zero.
+    pub fn with_public_coin(public_coin: R, domain_size: usize, num_queries: usize) -> Self {
         assert!(domain_size >= 8, "domain size must be at least 8, but was {domain_size}");
         assert!(
             domain_size.is_power_of_two(),
@@ -86,7 +111,7 @@ where
         );
         assert!(num_queries > 0, "number of queries must be greater than zero");
         DefaultProverChannel {
-            public_coin: RandomCoin::new(&[]),
+            public_coin,
             commitments: Vec::new(),
             domain_size,
             num_queries,
@@ -120,16 +145,28 @@ impl<E, H, R> ProverChannel<E> for DefaultProverChannel<E, H, R>
 where
     E: FieldElement,
     H: ElementHasher<BaseField = E::BaseField>,
-    R: RandomCoin<BaseField = E::BaseField, Hasher = H>,
+    R: RandomCoin<BaseField = E::BaseField, Hasher: ElementHasher<Digest: From<H::Digest>>>,
 {
     type Hasher = H;
 
     fn commit_fri_layer(&mut self, layer_root: H::Digest) {
         self.commitments.push(layer_root);
-        self.public_coin.reseed(layer_root);
+        self.public_coin
+            .reseed_with_label(transcript_labels::FRI_LAYER_ROOT, layer_root.into());
     }
 
     fn draw_fri_alpha(&mut self) -> E {
         self.public_coin.draw().expect("failed to draw FRI alpha")
     }
+
+    fn draw_fri_deep_point(&mut self) -> E {
+        self.public_coin.draw().expect("failed to draw FRI DEEP point")
+    }
+
+    fn commit_fri_deep_evaluations(&mut self, evaluations: &[E]) {
+        self.public_coin.reseed_with_label(
+            transcript_labels::FRI_DEEP_EVALUATIONS,
+            R::Hasher::hash_elements(evaluations),
+        );
+    }
 }
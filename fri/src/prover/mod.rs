@@ -7,15 +7,16 @@ use alloc::vec::Vec;
 use core::marker::PhantomData;
 
 use crypto::{ElementHasher, Hasher, VectorCommitment};
-use math::{fft, FieldElement};
+use math::{fft, get_power_series, polynom, FieldElement, StarkField};
 #[cfg(feature = "concurrent")]
 use utils::iterators::*;
 use utils::{
-    flatten_vector_elements, group_slice_elements, iter_mut, transpose_slice, uninit_vector,
+    flatten_vector_elements, group_slice_elements, iter_mut, transpose_slice, transpose_slice_into,
+    uninit_vector, BufferPool,
 };
 
 use crate::{
-    folding::{apply_drp, fold_positions},
+    folding::{apply_deep_quotient, apply_drp, fold_deep_evaluations, fold_positions},
     proof::{FriProof, FriProofLayer},
     FriOptions,
 };
@@ -23,9 +24,21 @@ use crate::{
 mod channel;
 pub use channel::{DefaultProverChannel, ProverChannel};
 
+mod storage;
+pub use storage::FriLayerStorage;
+use storage::LayerEvaluations;
+
 #[cfg(test)]
 mod tests;
 
+// CONSTANTS
+// ================================================================================================
+
+/// Number of rows of polynomial coefficients summed up in a single thread when a FRI layer is
+/// evaluated over a DEEP coset.
+#[cfg(feature = "concurrent")]
+const COSET_SUM_CHUNK_SIZE: usize = 1024;
+
 // TYPES AND INTERFACES
 // ================================================================================================
 
@@ -52,9 +65,10 @@ mod tests;
 /// During the commit phase, which is executed via [build_layers()](FriProver::build_layers())
 /// function, the prover repeatedly applies a degree-respecting projection (DRP) to `evaluations`
 /// (see [folding](crate::folding)). With every application of the DRP, the degree of the function
-/// *f* (and size of the domain over which it is evaluated) is reduced by the `folding_factor`
-/// until the remaining evaluations correspond to a polynomial, called remainder polynomial, with
-/// a number of coefficients less than or equal to `remainder_max_degree_plus_1`.
+/// *f* (and size of the domain over which it is evaluated) is reduced by the folding factor of
+/// the current layer (see [FriOptions::layer_folding_factor()]) until the remaining evaluations
+/// correspond to a polynomial, called remainder polynomial, with a number of coefficients less
+/// than or equal to `remainder_max_degree_plus_1`.
 ///
 /// At each layer of reduction, the prover commits to the current set of evaluations. This is done
 /// by building a vector commitment to hashed evaluations and sending the commitment string
@@ -70,8 +84,17 @@ mod tests;
 /// sends it to the prover. In the non-interactive version, α is pseudo-randomly generated based
 /// on the values the prover has written into the channel up to that point.
 ///
+/// If DEEP folding is enabled in the options, the prover additionally draws a point z from the
+/// channel before drawing α, and sends evaluations of the current layer polynomial over the coset
+/// z * ⟨ω⟩ (where ω is a root of unity of order `folding_factor`) to the verifier. These
+/// evaluations determine the value v of the folded polynomial at z^`folding_factor`, and the next
+/// FRI layer is built from the quotient (f'(y) - v) / (y - z^`folding_factor`) instead of the
+/// folded polynomial f' itself.
+///
 /// The prover keeps all FRI layers (consisting of evaluations and corresponding vector
-/// commitments) in its internal state.
+/// commitments) in its internal state. By default, layer evaluations are kept in memory; they can
+/// be moved to disk as soon as each layer is folded via
+/// [with_layer_storage()](FriProver::with_layer_storage()).
 ///
 /// # Query phase
 /// In the query phase, which is executed via [build_proof()](FriProver::build_proof()) function,
@@ -100,14 +123,18 @@ where
     V: VectorCommitment<H>,
 {
     options: FriOptions,
+    storage: FriLayerStorage,
+    buffer_pool: Option<BufferPool<E::BaseField>>,
     layers: Vec<FriLayer<E, H, V>>,
     remainder_poly: FriRemainder<E>,
+    deep_evaluations: Vec<E>,
     _channel: PhantomData<C>,
 }
 
 struct FriLayer<E: FieldElement, H: Hasher, V: VectorCommitment<H>> {
     commitment: V,
-    evaluations: Vec<E>,
+    evaluations: LayerEvaluations<E>,
+    domain_size: usize,
     _h: PhantomData<H>,
 }
 
@@ -129,16 +156,44 @@ where
     pub fn new(options: FriOptions) -> Self {
         FriProver {
             options,
+            storage: FriLayerStorage::default(),
+            buffer_pool: None,
             layers: Vec::new(),
             remainder_poly: FriRemainder(vec![]),
+            deep_evaluations: Vec::new(),
             _channel: PhantomData,
         }
     }
 
+    /// Returns this prover with evaluations of FRI layers kept in the specified `storage`.
+    ///
+    /// With [FriLayerStorage::Disk], evaluations of each layer are written to a file once the
+    /// layer has been folded, and only evaluations at the queried positions are read back during
+    /// the query phase.
+    pub fn with_layer_storage(mut self, storage: FriLayerStorage) -> Self {
+        self.storage = storage;
+        self
+    }
+
+    /// Returns this prover with evaluations of FRI layers kept in buffers taken from the specified
+    /// `pool`.
+    ///
+    /// Buffers holding evaluations of FRI layers kept in memory are returned to the pool once the
+    /// layers are discarded (i.e., after a proof has been built, or the prover has been reset).
+    /// Thus, when the same pool is used for generating multiple proofs, layers of subsequent
+    /// proofs do not need to be allocated anew.
+    pub fn with_buffer_pool(mut self, pool: BufferPool<E::BaseField>) -> Self {
+        self.buffer_pool = Some(pool);
+        self
+    }
+
     // ACCESSORS
     // --------------------------------------------------------------------------------------------
 
     /// Returns folding factor for this prover.
+    ///
+    /// This is the folding factor of the FRI layers beyond the folding schedule (see
+    /// [FriOptions::with_folding_schedule()]).
     pub fn folding_factor(&self) -> usize {
         self.options.folding_factor()
     }
@@ -156,8 +211,16 @@ where
 
     /// Clears a vector of internally stored layers.
     pub fn reset(&mut self) {
+        // evaluations of layers kept in memory are returned to the buffer pool (if one is set)
+        if let Some(pool) = self.buffer_pool.as_ref() {
+            self.layers
+                .drain(..)
+                .filter_map(|layer| layer.evaluations.into_memory())
+                .for_each(|evaluations| pool.put(evaluations));
+        }
         self.layers.clear();
         self.remainder_poly.0.clear();
+        self.deep_evaluations.clear();
     }
 
     // COMMIT PHASE
@@ -182,15 +245,17 @@ where
             "a prior proof generation request has not been completed yet"
         );
 
-        // reduce the degree by folding_factor at each iteration until the remaining polynomial
+        // reduce the degree by the folding factor of each layer until the remaining polynomial
         // has small enough degree
-        for _ in 0..self.options.num_fri_layers(evaluations.len()) {
-            match self.folding_factor() {
+        for depth in 0..self.options.num_fri_layers(evaluations.len()) {
+            match self.options.layer_folding_factor(depth) {
                 2 => self.build_layer::<2>(channel, &mut evaluations),
                 4 => self.build_layer::<4>(channel, &mut evaluations),
                 8 => self.build_layer::<8>(channel, &mut evaluations),
                 16 => self.build_layer::<16>(channel, &mut evaluations),
-                _ => unimplemented!("folding factor {} is not supported", self.folding_factor()),
+                folding_factor => {
+                    unimplemented!("folding factor {folding_factor} is not supported")
+                },
             }
         }
 
@@ -204,23 +269,80 @@ where
         // evaluations into a matrix of N columns, then hashing each row into a digest, and finally
         // commiting to vector of these digests; we do this so that we could de-commit to N values
         // with a single opening proof.
-        let transposed_evaluations = transpose_slice(evaluations);
+        let transposed_evaluations = self.transpose_evaluations::<N>(evaluations);
+        if !self.options.deep_folding() {
+            // evaluations in the original order are needed only to evaluate the layer in DEEP
+            // mode; release them so that at most two copies of the layer are held at once
+            *evaluations = Vec::new();
+        }
         let evaluation_vector_commitment =
             build_layer_commitment::<_, _, V, N>(&transposed_evaluations)
                 .expect("failed to construct FRI layer commitment");
         channel.commit_fri_layer(evaluation_vector_commitment.commitment());
 
+        // in DEEP mode, evaluate the layer polynomial over an out-of-domain coset and send the
+        // evaluations to the verifier before the folding challenge is drawn
+        let deep_point = if self.options.deep_folding() {
+            let point = channel.draw_fri_deep_point();
+            let deep_evaluations = self.evaluate_deep_coset::<N>(evaluations, point);
+            channel.commit_fri_deep_evaluations(&deep_evaluations);
+            Some((point, deep_evaluations))
+        } else {
+            None
+        };
+
         // draw a pseudo-random coefficient from the channel, and use it in degree-respecting
         // projection to reduce the degree of evaluations by N
         let alpha = channel.draw_fri_alpha();
         *evaluations = apply_drp(&transposed_evaluations, self.domain_offset(), alpha);
+
+        // in DEEP mode, divide out the value of the folded polynomial at the DEEP point
+        if let Some((point, deep_evaluations)) = deep_point {
+            let folded_value = fold_deep_evaluations(&deep_evaluations, point, alpha);
+            let folded_point = point.exp_vartime((N as u32).into());
+            apply_deep_quotient(evaluations, self.domain_offset(), N, folded_point, folded_value);
+            self.deep_evaluations.extend_from_slice(&deep_evaluations);
+        }
+        let evaluations = flatten_vector_elements(transposed_evaluations);
         self.layers.push(FriLayer {
             commitment: evaluation_vector_commitment,
-            evaluations: flatten_vector_elements(transposed_evaluations),
+            domain_size: evaluations.len(),
+            evaluations: LayerEvaluations::new(&self.storage, evaluations),
             _h: PhantomData,
         });
     }
 
+    /// Transposes the `evaluations` into a matrix with `N` columns; when a buffer pool is set,
+    /// the matrix is written into a buffer taken from the pool.
+    fn transpose_evaluations<const N: usize>(&self, evaluations: &[E]) -> Vec<[E; N]> {
+        if let Some(pool) = self.buffer_pool.as_ref() {
+            let mut result = unsafe { pool.take(evaluations.len() / N) };
+            transpose_slice_into(evaluations, &mut result);
+            return result;
+        }
+        transpose_slice(evaluations)
+    }
+
+    /// Returns evaluations of the polynomial defined by `evaluations` at `point` * ω^j for
+    /// j = 0, ..., N - 1, where ω is an N-th root of unity.
+    ///
+    /// Since ω^N = 1, the value at `point` * ω^j is equal to sum_r s_r * ω^(j * r), where s_r is
+    /// the sum of all terms c_i * point^i of the polynomial with i = r mod N. Thus, the polynomial
+    /// is traversed only once to compute N partial sums, which are then evaluated at the powers of
+    /// ω.
+    fn evaluate_deep_coset<const N: usize>(&self, evaluations: &[E], point: E) -> Vec<E> {
+        let mut poly = evaluations.to_vec();
+        let inv_twiddles = fft::get_inv_twiddles(poly.len());
+        fft::interpolate_poly_with_offset(&mut poly, &inv_twiddles, self.domain_offset());
+
+        let partial_sums = sum_coset_terms::<E, N>(group_slice_elements(&poly), point);
+        let omega = E::BaseField::get_root_of_unity(N.ilog2());
+        get_power_series(omega, N)
+            .into_iter()
+            .map(|power| polynom::eval(&partial_sums, E::from(power)))
+            .collect()
+    }
+
     /// Creates remainder polynomial in coefficient form from a vector of `evaluations` over a domain.
     fn set_remainder(&mut self, channel: &mut C, evaluations: &mut [E]) {
         let inv_twiddles = fft::get_inv_twiddles(evaluations.len());
@@ -241,6 +363,10 @@ where
     /// proof against the sent vector commitment. For the remainder, we send the whole remainder
     /// polynomial resulting from interpolating the remainder layer evaluations.
     ///
+    /// Positions are deduplicated at every layer: positions which map onto the same position in
+    /// the folded domain (see [fold_positions]) are opened only once, and the verifier maps its
+    /// positions onto the folded domain in the same way.
+    ///
     /// # Panics
     /// Panics is the prover state is clean (no FRI layers have been build yet).
     pub fn build_proof(&mut self, positions: &[usize]) -> FriProof {
@@ -250,12 +376,12 @@ where
 
         if !self.layers.is_empty() {
             let mut positions = positions.to_vec();
-            let mut domain_size = self.layers[0].evaluations.len();
-            let folding_factor = self.options.folding_factor();
+            let mut domain_size = self.layers[0].domain_size;
 
             // for all FRI layers, except the last one, record tree root, determine a set of query
             // positions, and query the layer at these positions.
             for i in 0..self.layers.len() {
+                let folding_factor = self.options.layer_folding_factor(i);
                 positions = fold_positions(&positions, domain_size, folding_factor);
 
                 // sort of a static dispatch for folding_factor parameter
@@ -275,10 +401,12 @@ where
         // use the remaining polynomial values directly as proof
         let remainder = self.remainder_poly.0.clone();
 
+        let deep_evaluations = core::mem::take(&mut self.deep_evaluations);
+
         // clear layers so that another proof can be generated
         self.reset();
 
-        FriProof::new(layers, remainder, 1)
+        FriProof::new(layers, remainder, 1).with_deep_evaluations(&deep_evaluations)
     }
 }
 
@@ -300,14 +428,55 @@ fn query_layer<E: FieldElement, H: Hasher, V: VectorCommitment<H>, const N: usiz
     // build a list of polynomial evaluations at each position; since evaluations in FRI layers
     // are stored in transposed form, a position refers to N evaluations which are committed
     // in a single leaf
-    let evaluations: &[[E; N]] = group_slice_elements(&layer.evaluations);
-    let mut queried_values: Vec<[E; N]> = Vec::with_capacity(positions.len());
-    for &position in positions.iter() {
-        queried_values.push(evaluations[position]);
-    }
+    let queried_values = layer.evaluations.get_leaves::<N>(positions);
     FriProofLayer::new::<_, _, V, N>(queried_values, proof.1)
 }
 
+/// Returns sums s_r of terms c_i * x^i for i = r mod N, where c_i are the polynomial coefficients
+/// grouped into rows of N coefficients each.
+///
+/// When `concurrent` feature is enabled, the rows are split into chunks which are summed up in
+/// multiple threads.
+fn sum_coset_terms<E: FieldElement, const N: usize>(rows: &[[E; N]], x: E) -> [E; N] {
+    #[cfg(not(feature = "concurrent"))]
+    let result = sum_coset_terms_chunk(rows, x, E::ONE);
+
+    #[cfg(feature = "concurrent")]
+    let result = rows
+        .par_chunks(COSET_SUM_CHUNK_SIZE)
+        .enumerate()
+        .map(|(i, chunk)| {
+            let offset = x.exp_vartime(((i * COSET_SUM_CHUNK_SIZE * N) as u64).into());
+            sum_coset_terms_chunk(chunk, x, offset)
+        })
+        .reduce(
+            || [E::ZERO; N],
+            |mut result, sums| {
+                result.iter_mut().zip(sums).for_each(|(r, s)| *r += s);
+                result
+            },
+        );
+
+    result
+}
+
+/// Returns sums s_r of terms c_i * x^i * `offset` for i = r mod N.
+fn sum_coset_terms_chunk<E: FieldElement, const N: usize>(
+    rows: &[[E; N]],
+    x: E,
+    offset: E,
+) -> [E; N] {
+    let mut result = [E::ZERO; N];
+    let mut power = offset;
+    for row in rows {
+        for (r, &coeff) in result.iter_mut().zip(row) {
+            *r += coeff * power;
+            power *= x;
+        }
+    }
+    result
+}
+
 /// Hashes each of the arrays in the provided slice and returns a vector commitment to resulting
 /// hashes.
 pub fn build_layer_commitment<E, H, V, const N: usize>(
@@ -0,0 +1,117 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::{
+    fs::File,
+    io::{BufWriter, Read, Seek, SeekFrom, Write},
+    path::PathBuf,
+};
+
+use math::FieldElement;
+use utils::group_slice_elements;
+#[cfg(feature = "std")]
+use utils::{ByteReader, ByteWriter, SliceReader};
+
+// FRI LAYER STORAGE
+// ================================================================================================
+
+/// Defines where a [FriProver](super::FriProver) keeps evaluations of FRI layers between the
+/// commit and the query phases of the protocol.
+///
+/// Evaluations of a FRI layer are needed in the query phase only at the queried positions. Thus,
+/// instead of keeping all layers in memory, the prover can write each layer into a file as soon
+/// as the next layer has been computed, and read back only the queried evaluations when building
+/// the proof. Together with a vector commitment scheme which keeps its data on disk (e.g.,
+/// [DiskMerkleTree](crypto::DiskMerkleTree)), this bounds the memory needed by the prover to
+/// the memory needed for computing a single layer.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum FriLayerStorage {
+    /// Layer evaluations are kept in memory.
+    #[default]
+    Memory,
+    /// Layer evaluations are written to temporary files created in the specified directory (the
+    /// temporary directory of the operating system if `None`); the files are deleted when the
+    /// layers are discarded.
+    ///
+    /// This option is available only when the crate is compiled with the `std` feature.
+    #[cfg(feature = "std")]
+    Disk(Option<PathBuf>),
+}
+
+// LAYER EVALUATIONS
+// ================================================================================================
+
+/// Evaluations of a single FRI layer stored in the transposed form, such that the `N` evaluations
+/// committed to in a single leaf of the layer commitment are stored next to each other.
+pub(super) enum LayerEvaluations<E: FieldElement> {
+    Memory(Vec<E>),
+    #[cfg(feature = "std")]
+    Disk(File),
+}
+
+impl<E: FieldElement> LayerEvaluations<E> {
+    /// Moves the provided `evaluations` into the specified `storage`.
+    ///
+    /// # Panics
+    /// Panics if the evaluations could not be written into a file.
+    pub fn new(storage: &FriLayerStorage, evaluations: Vec<E>) -> Self {
+        match storage {
+            FriLayerStorage::Memory => Self::Memory(evaluations),
+            #[cfg(feature = "std")]
+            FriLayerStorage::Disk(dir) => {
+                let dir = dir.clone().unwrap_or_else(std::env::temp_dir);
+                let file = tempfile::tempfile_in(dir)
+                    .expect("failed to create a file for FRI layer evaluations");
+                let mut writer = BufWriter::new(file);
+                writer.write_many(&evaluations);
+                writer.flush().expect("failed to write FRI layer evaluations");
+                let file = writer.into_inner().expect("failed to write FRI layer evaluations");
+                Self::Disk(file)
+            },
+        }
+    }
+
+    /// Returns the vector holding the evaluations if they are kept in memory, and `None`
+    /// otherwise.
+    pub fn into_memory(self) -> Option<Vec<E>> {
+        match self {
+            Self::Memory(evaluations) => Some(evaluations),
+            #[cfg(feature = "std")]
+            Self::Disk(_) => None,
+        }
+    }
+
+    /// Returns the evaluations committed to in the leaves at the specified `positions`.
+    ///
+    /// # Panics
+    /// Panics if the evaluations could not be read from a file.
+    pub fn get_leaves<const N: usize>(&self, positions: &[usize]) -> Vec<[E; N]> {
+        match self {
+            Self::Memory(evaluations) => {
+                let evaluations: &[[E; N]] = group_slice_elements(evaluations);
+                positions.iter().map(|&position| evaluations[position]).collect()
+            },
+            #[cfg(feature = "std")]
+            Self::Disk(file) => {
+                let mut file = file;
+                let leaf_size = N * E::ELEMENT_BYTES;
+                let mut bytes = vec![0; leaf_size];
+                positions
+                    .iter()
+                    .map(|&position| {
+                        file.seek(SeekFrom::Start((position * leaf_size) as u64))
+                            .and_then(|_| file.read_exact(&mut bytes))
+                            .expect("failed to read FRI layer evaluations");
+                        SliceReader::new(&bytes)
+                            .read()
+                            .expect("failed to parse FRI layer evaluations")
+                    })
+                    .collect()
+            },
+        }
+    }
+}
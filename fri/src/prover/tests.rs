@@ -5,12 +5,16 @@
 
 use alloc::vec::Vec;
 
-use crypto::{hashers::Blake3_256, DefaultRandomCoin, Hasher, MerkleTree, RandomCoin};
-use math::{fft, fields::f128::BaseElement, FieldElement};
-use utils::{Deserializable, Serializable, SliceReader};
+use crypto::{
+    hashers::Blake3_256, DefaultRandomCoin, DiskMerkleTree, Hasher, MerkleTree, RandomCoin,
+};
+use math::{fft, fields::f128::BaseElement, polynom, FieldElement, StarkField};
+use rand_utils::{rand_value, rand_vector};
+use utils::{BufferPool, ByteReader, Deserializable, Serializable, SliceReader};
 
-use super::{DefaultProverChannel, FriProver};
+use super::{DefaultProverChannel, FriLayerStorage, FriProver};
 use crate::{
+    folding::fold_positions,
     verifier::{DefaultVerifierChannel, FriVerifier},
     FriOptions, FriProof, VerifierError,
 };
@@ -38,6 +42,430 @@ fn fri_folding_4() {
     fri_prove_verify(trace_length_e, lde_blowup_e, folding_factor_e, max_remainder_degree)
 }
 
+#[test]
+fn fri_deep_folding_2() {
+    let options = FriOptions::new(1 << 3, 2, 7).with_deep_folding();
+    fri_prove_verify_with_options(12, options)
+}
+
+#[test]
+fn fri_deep_folding_4() {
+    let options = FriOptions::new(1 << 2, 4, 31).with_deep_folding();
+    fri_prove_verify_with_options(12, options)
+}
+
+#[test]
+fn fri_mixed_folding() {
+    let options = FriOptions::new(1 << 3, 4, 7).with_folding_schedule(vec![8, 8]);
+    fri_prove_verify_with_options(12, options)
+}
+
+#[test]
+fn fri_deep_mixed_folding() {
+    let options = FriOptions::new(1 << 2, 2, 31)
+        .with_folding_schedule(vec![16, 4])
+        .with_deep_folding();
+    fri_prove_verify_with_options(12, options)
+}
+
+#[test]
+fn fri_mixed_folding_layers() {
+    let trace_length = 1 << 12;
+    let lde_blowup = 1 << 3;
+    let options = FriOptions::new(lde_blowup, 4, 7).with_folding_schedule(vec![16, 2]);
+    let mut channel = build_prover_channel(trace_length, &options);
+    let evaluations = build_evaluations(trace_length, lde_blowup);
+
+    let mut prover = FriProver::<_, _, _, MerkleTree<Blake3>>::new(options.clone());
+    prover.build_layers(&mut channel, evaluations.clone());
+    let positions = channel.draw_query_positions(0);
+    let proof = prover.build_proof(&positions);
+
+    // the domain of size 2^15 is folded by 16, then by 2, and then twice by 4 until its size
+    // drops to the remainder domain size of 64
+    let domain_size = trace_length * lde_blowup;
+    assert_eq!(4, options.num_fri_layers(domain_size));
+    assert_eq!(4, proof.num_layers());
+
+    // every layer contains as many values per query as the folding factor of the layer
+    let (layer_queries, _) = proof
+        .clone()
+        .parse_layers::<BaseElement, Blake3, MerkleTree<Blake3>>(domain_size, &options)
+        .unwrap();
+    let mut folded_positions = positions.clone();
+    let mut layer_domain_size = domain_size;
+    for (depth, queries) in layer_queries.into_iter().enumerate() {
+        let folding_factor = options.layer_folding_factor(depth);
+        folded_positions = fold_positions(&folded_positions, layer_domain_size, folding_factor);
+        assert_eq!(folded_positions.len() * folding_factor, queries.len());
+        layer_domain_size /= folding_factor;
+    }
+
+    // a verifier which expects a different folding schedule rejects the proof
+    let commitments = channel.layer_commitments().to_vec();
+    let result = verify_proof(
+        proof,
+        commitments,
+        &evaluations,
+        trace_length - 1,
+        domain_size,
+        &positions,
+        &FriOptions::new(lde_blowup, 4, 7),
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn fri_layer_skipping() {
+    // polynomials of degree at most 255 are sent directly, and polynomials of degree at most
+    // 1023 are folded once; polynomials of larger degrees are folded as usual
+    let options = FriOptions::new(1 << 3, 4, 7).with_layer_skipping(255);
+    assert_eq!(0, options.num_fri_layers(256 << 3));
+    assert_eq!(1, options.num_fri_layers(1024 << 3));
+    assert_eq!(4, options.num_fri_layers(2048 << 3));
+    assert_eq!(4, FriOptions::new(1 << 3, 4, 7).num_fri_layers(2048 << 3));
+
+    fri_prove_verify_with_options(8, options.clone());
+    fri_prove_verify_with_options(10, options.clone());
+    fri_prove_verify_with_options(11, options);
+}
+
+#[test]
+fn fri_deep_folding_invalid_evaluations() {
+    let trace_length = 1 << 10;
+    let lde_blowup = 1 << 3;
+    let options = FriOptions::new(lde_blowup, 4, 7).with_deep_folding();
+    let mut channel = build_prover_channel(trace_length, &options);
+    let evaluations = build_evaluations(trace_length, lde_blowup);
+
+    let mut prover = FriProver::<_, _, _, MerkleTree<Blake3>>::new(options.clone());
+    prover.build_layers(&mut channel, evaluations.clone());
+    let positions = channel.draw_query_positions(0);
+    let proof = prover.build_proof(&positions);
+    let commitments = channel.layer_commitments().to_vec();
+    let domain_size = trace_length * lde_blowup;
+    let num_deep_evaluations = 4 * options.num_fri_layers(domain_size);
+    assert_eq!(num_deep_evaluations, proof.num_deep_evaluations::<BaseElement>());
+
+    // replace the last DEEP evaluation, which is stored at the end of the serialized proof
+    let mut proof_bytes = proof.to_bytes();
+    let last = proof_bytes.len() - BaseElement::ELEMENT_BYTES;
+    proof_bytes[last..].copy_from_slice(&BaseElement::new(42).to_bytes());
+    let invalid_proof = FriProof::read_from_bytes(&proof_bytes).unwrap();
+    let result = verify_proof(
+        invalid_proof,
+        commitments.clone(),
+        &evaluations,
+        trace_length - 1,
+        domain_size,
+        &positions,
+        &options,
+    );
+    assert!(result.is_err());
+
+    // a verifier which does not expect DEEP evaluations rejects the proof
+    let result = verify_proof(
+        proof,
+        commitments,
+        &evaluations,
+        trace_length - 1,
+        domain_size,
+        &positions,
+        &FriOptions::new(lde_blowup, 4, 7),
+    );
+    assert_eq!(Err(VerifierError::NumDeepEvaluationsMismatch(0, num_deep_evaluations)), result);
+}
+
+#[test]
+fn fri_invalid_remainder() {
+    let trace_length = 1 << 10;
+    let lde_blowup = 1 << 3;
+    let options = FriOptions::new(lde_blowup, 4, 31);
+    let mut channel = build_prover_channel(trace_length, &options);
+    let evaluations = build_evaluations(trace_length, lde_blowup);
+
+    let mut prover = FriProver::<_, _, _, MerkleTree<Blake3>>::new(options.clone());
+    prover.build_layers(&mut channel, evaluations.clone());
+    let positions = channel.draw_query_positions(0);
+    let proof = prover.build_proof(&positions);
+    let commitments = channel.layer_commitments().to_vec();
+
+    // the remainder is followed by the number of partitions (1 byte) and the length of DEEP
+    // evaluations (1 byte since there are none); replace the first remainder coefficient
+    let mut proof_bytes = proof.to_bytes();
+    let num_remainder_bytes =
+        proof.num_remainder_elements::<BaseElement>() * BaseElement::ELEMENT_BYTES;
+    let start = proof_bytes.len() - 2 - num_remainder_bytes;
+    proof_bytes[start..start + BaseElement::ELEMENT_BYTES]
+        .copy_from_slice(&BaseElement::new(42).to_bytes());
+    let invalid_proof = FriProof::read_from_bytes(&proof_bytes).unwrap();
+
+    let result = verify_proof(
+        invalid_proof,
+        commitments,
+        &evaluations,
+        trace_length - 1,
+        trace_length * lde_blowup,
+        &positions,
+        &options,
+    );
+    assert_eq!(Err(VerifierError::RemainderCommitmentMismatch), result);
+}
+
+#[test]
+fn fri_verification_errors() {
+    let trace_length = 1 << 10;
+    let lde_blowup = 1 << 3;
+    let domain_size = trace_length * lde_blowup;
+    let options = FriOptions::new(lde_blowup, 4, 7);
+    let mut channel = build_prover_channel(trace_length, &options);
+    let evaluations = build_evaluations(trace_length, lde_blowup);
+
+    let mut prover = FriProver::<_, _, _, MerkleTree<Blake3>>::new(options.clone());
+    prover.build_layers(&mut channel, evaluations.clone());
+    let positions = channel.draw_query_positions(0);
+    let proof = prover.build_proof(&positions);
+    let commitments = channel.layer_commitments().to_vec();
+
+    // an evaluation which does not match the first layer is reported with its query index
+    let mut invalid_evaluations = evaluations.clone();
+    invalid_evaluations[positions[5]] += BaseElement::ONE;
+    let query_idx = positions.iter().position(|&p| p == positions[5]).unwrap();
+    let result = verify_proof(
+        proof.clone(),
+        commitments.clone(),
+        &invalid_evaluations,
+        trace_length - 1,
+        domain_size,
+        &positions,
+        &options,
+    );
+    assert_eq!(Err(VerifierError::InvalidLayerFolding(0, query_idx)), result);
+
+    // a layer value which does not match the layer commitment is reported with its layer
+    let mut proof_bytes = proof.to_bytes();
+    let start = first_layer_values_offset(&proof_bytes);
+    proof_bytes[start] ^= 1;
+    let invalid_proof = FriProof::read_from_bytes(&proof_bytes).unwrap();
+    let result = verify_proof(
+        invalid_proof,
+        commitments,
+        &evaluations,
+        trace_length - 1,
+        domain_size,
+        &positions,
+        &options,
+    );
+    assert_eq!(Err(VerifierError::LayerCommitmentMismatch(0)), result);
+
+    // without FRI layers, an evaluation which does not match the remainder is reported with its
+    // query index
+    let options = FriOptions::new(lde_blowup, 4, 7).with_layer_skipping(trace_length - 1);
+    let mut channel = build_prover_channel(trace_length, &options);
+    let mut prover = FriProver::<_, _, _, MerkleTree<Blake3>>::new(options.clone());
+    prover.build_layers(&mut channel, evaluations.clone());
+    let positions = channel.draw_query_positions(0);
+    let proof = prover.build_proof(&positions);
+    let commitments = channel.layer_commitments().to_vec();
+
+    let mut invalid_evaluations = evaluations;
+    invalid_evaluations[positions[3]] += BaseElement::ONE;
+    let query_idx = positions.iter().position(|&p| p == positions[3]).unwrap();
+    let result = verify_proof(
+        proof,
+        commitments,
+        &invalid_evaluations,
+        trace_length - 1,
+        domain_size,
+        &positions,
+        &options,
+    );
+    assert_eq!(Err(VerifierError::InvalidRemainderFolding(query_idx)), result);
+}
+
+#[test]
+fn fri_layer_queries_deduplicated() {
+    let trace_length = 1 << 6;
+    let lde_blowup = 1 << 3;
+    let folding_factor = 4;
+    let options = FriOptions::new(lde_blowup, folding_factor, 7);
+    let mut channel = build_prover_channel(trace_length, &options);
+    let evaluations = build_evaluations(trace_length, lde_blowup);
+
+    let mut prover = FriProver::<_, _, _, MerkleTree<Blake3>>::new(options.clone());
+    prover.build_layers(&mut channel, evaluations.clone());
+
+    // positions which collide in the first and in subsequent layers, including a duplicate
+    let domain_size = trace_length * lde_blowup;
+    let positions = vec![3, 3, 3 + domain_size / 4, 3 + domain_size / 16, 100, 200];
+    let proof = prover.build_proof(&positions);
+    let (layer_queries, _) = proof
+        .clone()
+        .parse_layers::<BaseElement, Blake3, MerkleTree<Blake3>>(domain_size, &options)
+        .unwrap();
+
+    // every layer contains values for unique folded positions only
+    let mut folded_positions = positions.clone();
+    let mut layer_domain_size = domain_size;
+    for queries in layer_queries {
+        folded_positions = fold_positions(&folded_positions, layer_domain_size, folding_factor);
+        assert_eq!(folded_positions.len() * folding_factor, queries.len());
+        layer_domain_size /= folding_factor;
+    }
+
+    // the verifier reconstructs the folded positions and accepts the proof
+    let commitments = channel.layer_commitments().to_vec();
+    let result = verify_proof(
+        proof,
+        commitments,
+        &evaluations,
+        trace_length - 1,
+        domain_size,
+        &positions,
+        &options,
+    );
+    assert_eq!(Ok(()), result);
+}
+
+#[test]
+fn fri_disk_layer_storage() {
+    let trace_length = 1 << 10;
+    let lde_blowup = 1 << 3;
+    let options = FriOptions::new(lde_blowup, 4, 7);
+    let evaluations = build_evaluations(trace_length, lde_blowup);
+
+    let mut channel = build_prover_channel(trace_length, &options);
+    let mut prover = FriProver::<_, _, _, MerkleTree<Blake3>>::new(options.clone());
+    prover.build_layers(&mut channel, evaluations.clone());
+    let positions = channel.draw_query_positions(0);
+    let expected = prover.build_proof(&positions);
+
+    // layer evaluations and layer commitments stored on disk yield the same proof
+    let mut channel = build_prover_channel(trace_length, &options);
+    let mut prover = FriProver::<_, _, _, DiskMerkleTree<Blake3>>::new(options.clone())
+        .with_layer_storage(FriLayerStorage::Disk(None));
+    prover.build_layers(&mut channel, evaluations.clone());
+    assert_eq!(positions, channel.draw_query_positions(0));
+    let proof = prover.build_proof(&positions);
+    assert_eq!(expected, proof);
+
+    let commitments = channel.layer_commitments().to_vec();
+    let domain_size = trace_length * lde_blowup;
+    let result = verify_proof(
+        proof,
+        commitments,
+        &evaluations,
+        trace_length - 1,
+        domain_size,
+        &positions,
+        &options,
+    );
+    assert_eq!(Ok(()), result);
+}
+
+#[test]
+fn fri_buffer_pool() {
+    let trace_length = 1 << 10;
+    let lde_blowup = 1 << 3;
+    let options = FriOptions::new(lde_blowup, 4, 7).with_deep_folding();
+    let evaluations = build_evaluations(trace_length, lde_blowup);
+
+    let mut channel = build_prover_channel(trace_length, &options);
+    let mut prover = FriProver::<_, _, _, MerkleTree<Blake3>>::new(options.clone());
+    prover.build_layers(&mut channel, evaluations.clone());
+    let positions = channel.draw_query_positions(0);
+    let expected = prover.build_proof(&positions);
+
+    // layer evaluations kept in pooled buffers yield the same proof; buffers are returned to the
+    // pool once the proof is built, and are reused when building the next proof
+    let pool = BufferPool::new();
+    for _ in 0..2 {
+        let mut channel = build_prover_channel(trace_length, &options);
+        let mut prover = FriProver::<_, _, _, MerkleTree<Blake3>>::new(options.clone())
+            .with_buffer_pool(pool.clone());
+        prover.build_layers(&mut channel, evaluations.clone());
+        assert_eq!(positions, channel.draw_query_positions(0));
+        assert_eq!(expected, prover.build_proof(&positions));
+        assert_eq!(options.num_fri_layers(trace_length * lde_blowup), pool.num_buffers());
+    }
+}
+
+#[test]
+fn fri_layer_reader() {
+    let trace_length = 1 << 10;
+    let lde_blowup = 1 << 3;
+    let options = FriOptions::new(lde_blowup, 4, 7);
+    let mut channel = build_prover_channel(trace_length, &options);
+    let evaluations = build_evaluations(trace_length, lde_blowup);
+
+    let mut prover = FriProver::<_, _, _, MerkleTree<Blake3>>::new(options.clone());
+    prover.build_layers(&mut channel, evaluations);
+    let positions = channel.draw_query_positions(0);
+    let proof = prover.build_proof(&positions);
+
+    // parsing layers one at a time should yield the same result as parsing them all at once
+    let domain_size = trace_length * lde_blowup;
+    let (layer_queries, layer_proofs) = proof
+        .clone()
+        .parse_layers::<BaseElement, Blake3, MerkleTree<Blake3>>(domain_size, &options)
+        .unwrap();
+
+    let num_layers = proof.num_layers();
+    let mut reader =
+        proof.into_layer_reader::<BaseElement, Blake3, MerkleTree<Blake3>>(domain_size, &options);
+    for (i, (queries, layer_proof)) in layer_queries.into_iter().zip(layer_proofs).enumerate() {
+        assert_eq!(num_layers - i, reader.num_remaining_layers());
+        let (parsed_queries, parsed_proof) = reader.next().unwrap().unwrap();
+        assert_eq!(queries, parsed_queries);
+        assert_eq!(layer_proof, parsed_proof);
+    }
+    assert!(reader.next().is_none());
+}
+
+#[test]
+fn fri_layer_reader_invalid_layer() {
+    let trace_length = 1 << 10;
+    let lde_blowup = 1 << 3;
+    let options = FriOptions::new(lde_blowup, 4, 7);
+    let mut channel = build_prover_channel(trace_length, &options);
+    let evaluations = build_evaluations(trace_length, lde_blowup);
+
+    let mut prover = FriProver::<_, _, _, MerkleTree<Blake3>>::new(options.clone());
+    prover.build_layers(&mut channel, evaluations);
+    let positions = channel.draw_query_positions(0);
+    let proof = prover.build_proof(&positions);
+    let num_layers = proof.num_layers();
+
+    // replace the first queried value of the first layer with bytes which do not encode a valid
+    // field element
+    let mut proof_bytes = proof.to_bytes();
+    let start = first_layer_values_offset(&proof_bytes);
+    proof_bytes[start..start + 16].fill(0xff);
+    let proof = FriProof::read_from_bytes(&proof_bytes).unwrap();
+
+    // parsing fails on the first layer, and the remaining layers are not parsed
+    let domain_size = trace_length * lde_blowup;
+    let mut reader =
+        proof.into_layer_reader::<BaseElement, Blake3, MerkleTree<Blake3>>(domain_size, &options);
+    let err = reader.next().unwrap().unwrap_err();
+    assert!(format!("{err}").contains("failed to parse FRI layer 0"));
+    assert_eq!(num_layers - 1, reader.num_remaining_layers());
+}
+
+#[test]
+fn fri_deep_coset_sums() {
+    // the polynomial spans several chunks of coefficient rows when summed up concurrently
+    let poly: Vec<BaseElement> = rand_vector(1 << 14);
+    let x: BaseElement = rand_value();
+
+    let sums = super::sum_coset_terms::<_, 4>(utils::group_slice_elements(&poly), x);
+    let omega = BaseElement::get_root_of_unity(2);
+    for j in 0..4_u64 {
+        let w = omega.exp(j.into());
+        assert_eq!(polynom::eval(&poly, x * w), polynom::eval(&sums, w));
+    }
+}
+
 // TEST UTILS
 // ================================================================================================
 
@@ -80,7 +508,7 @@ pub fn verify_proof(
         proof,
         commitments,
         domain_size,
-        options.folding_factor(),
+        options,
     )
     .unwrap();
     let mut coin = crypto::DefaultRandomCoin::<Blake3>::new(&[]);
@@ -95,11 +523,16 @@ fn fri_prove_verify(
     folding_factor_e: usize,
     max_remainder_degree: usize,
 ) {
-    let trace_length = 1 << trace_length_e;
     let lde_blowup = 1 << lde_blowup_e;
     let folding_factor = 1 << folding_factor_e;
-
     let options = FriOptions::new(lde_blowup, folding_factor, max_remainder_degree);
+    fri_prove_verify_with_options(trace_length_e, options)
+}
+
+fn fri_prove_verify_with_options(trace_length_e: usize, options: FriOptions) {
+    let trace_length = 1 << trace_length_e;
+    let lde_blowup = options.blowup_factor();
+
     let mut channel = build_prover_channel(trace_length, &options);
     let evaluations = build_evaluations(trace_length, lde_blowup);
 
@@ -135,3 +568,11 @@ fn fri_prove_verify(
     );
     assert!(result.is_err());
 }
+
+/// Returns the offset of the first queried value of the first layer in a serialized FRI proof;
+/// the value follows the number of layers (1 byte) and the number of value bytes in the layer
+/// (a variable-length integer).
+fn first_layer_values_offset(proof_bytes: &[u8]) -> usize {
+    let num_value_bytes = SliceReader::new(&proof_bytes[1..]).read_usize().unwrap();
+    1 + num_value_bytes.get_size_hint()
+}
@@ -0,0 +1,257 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Contains a standalone FRI prover and verifier for committed codewords.
+
+use alloc::{string::ToString, vec::Vec};
+use core::marker::PhantomData;
+
+use crypto::{ElementHasher, RandomCoin, VectorCommitment};
+use math::FieldElement;
+use utils::{
+    transpose_slice, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+};
+
+use crate::{
+    prover::build_layer_commitment, utils::dedup_positions, DefaultProverChannel,
+    DefaultVerifierChannel, FriOptions, FriProof, FriProver, FriVerifier, VerifierError,
+};
+
+#[cfg(test)]
+mod tests;
+
+// STANDALONE FRI PROOF
+// ================================================================================================
+
+/// A proof that a committed codeword is close to evaluations of a polynomial of degree bounded
+/// by some *d*.
+///
+/// The codeword is committed to as the first FRI layer: the codeword is split into rows of k
+/// evaluations, where k is the folding factor of the first layer, such that the i-th row
+/// contains evaluations at positions i, i + n / k, i + 2 * n / k etc., where n is the length of
+/// the codeword, and the i-th committed item is the hash of the i-th row. This commitment can be
+/// computed via [StandaloneFriProver::commit()].
+pub struct StandaloneFriProof<E, H>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    /// Commitments to the FRI layers; the first commitment is the commitment to the codeword.
+    pub layer_commitments: Vec<H::Digest>,
+    /// Evaluations of the codeword at the queried positions.
+    pub queried_values: Vec<E>,
+    /// FRI proof for the codeword.
+    pub fri_proof: FriProof,
+}
+
+impl<E, H> StandaloneFriProof<E, H>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    /// Returns the commitment to the codeword this proof was generated for.
+    ///
+    /// # Panics
+    /// Panics if the proof does not contain any layer commitments.
+    pub fn commitment(&self) -> H::Digest {
+        self.layer_commitments[0]
+    }
+}
+
+impl<E, H> Serializable for StandaloneFriProof<E, H>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.layer_commitments.write_into(target);
+        self.queried_values.write_into(target);
+        self.fri_proof.write_into(target);
+    }
+}
+
+impl<E, H> Deserializable for StandaloneFriProof<E, H>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        Ok(Self {
+            layer_commitments: Deserializable::read_from(source)?,
+            queried_values: Deserializable::read_from(source)?,
+            fri_proof: Deserializable::read_from(source)?,
+        })
+    }
+}
+
+// STANDALONE FRI PROVER
+// ================================================================================================
+
+/// Generates proofs that committed codewords are close to evaluations of low-degree polynomials,
+/// independently of the STARK protocol.
+///
+/// The prover executes FRI for the codeword with the public coin instantiated from an empty seed;
+/// the first message absorbed into the coin is the commitment to the codeword. Thus, a proof is
+/// bound to the codeword commitment, and can be verified by [StandaloneFriVerifier] given only
+/// this commitment.
+pub struct StandaloneFriProver<E, H, R, V>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    R: RandomCoin<BaseField = E::BaseField, Hasher: ElementHasher<Digest: From<H::Digest>>>,
+    V: VectorCommitment<H>,
+{
+    options: FriOptions,
+    num_queries: usize,
+    _phantom: PhantomData<(E, H, R, V)>,
+}
+
+impl<E, H, R, V> StandaloneFriProver<E, H, R, V>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    R: RandomCoin<BaseField = E::BaseField, Hasher: ElementHasher<Digest: From<H::Digest>>>,
+    V: VectorCommitment<H>,
+{
+    /// Returns a new standalone prover instantiated with the provided `options` which queries the
+    /// committed codeword at `num_queries` positions.
+    ///
+    /// # Panics
+    /// Panics if `num_queries` is zero.
+    pub fn new(options: FriOptions, num_queries: usize) -> Self {
+        assert!(num_queries > 0, "number of queries must be greater than zero");
+        Self {
+            options,
+            num_queries,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Returns a commitment to the specified `evaluations`.
+    ///
+    /// This is the same commitment as the one returned by
+    /// [StandaloneFriProof::commitment()] for a proof generated for these evaluations.
+    ///
+    /// # Panics
+    /// Panics if the number of evaluations is not a power of two or is smaller than the folding
+    /// factor of the first layer specified in the options of this prover.
+    pub fn commit(&self, evaluations: &[E]) -> H::Digest {
+        let commitment = match self.options.layer_folding_factor(0) {
+            2 => build_layer_commitment::<E, H, V, 2>(&transpose_slice(evaluations)),
+            4 => build_layer_commitment::<E, H, V, 4>(&transpose_slice(evaluations)),
+            8 => build_layer_commitment::<E, H, V, 8>(&transpose_slice(evaluations)),
+            16 => build_layer_commitment::<E, H, V, 16>(&transpose_slice(evaluations)),
+            folding_factor => unimplemented!("folding factor {folding_factor} is not supported"),
+        };
+        commitment.expect("failed to build codeword commitment").commitment()
+    }
+
+    /// Returns a proof that the specified `evaluations` are evaluations of a polynomial of degree
+    /// smaller than the number of evaluations divided by the blowup factor.
+    ///
+    /// `evaluations` must be in natural order over the domain shifted by the domain offset
+    /// specified in the options of this prover.
+    ///
+    /// # Panics
+    /// Panics if the number of evaluations is smaller than 8 or is not a power of two.
+    pub fn prove(&self, evaluations: Vec<E>) -> StandaloneFriProof<E, H> {
+        let domain_size = evaluations.len();
+        let mut channel = DefaultProverChannel::<E, H, R>::new(domain_size, self.num_queries);
+
+        // the first FRI layer is the commitment to the codeword
+        let queried_evaluations = evaluations.clone();
+        let mut fri_prover = FriProver::<E, _, H, V>::new(self.options.clone());
+        fri_prover.build_layers(&mut channel, evaluations);
+        let positions = dedup_positions(channel.draw_query_positions(0));
+
+        StandaloneFriProof {
+            layer_commitments: channel.layer_commitments().to_vec(),
+            queried_values: positions.iter().map(|&p| queried_evaluations[p]).collect(),
+            fri_proof: fri_prover.build_proof(&positions),
+        }
+    }
+}
+
+// STANDALONE FRI VERIFIER
+// ================================================================================================
+
+/// Verifies proofs generated by [StandaloneFriProver].
+pub struct StandaloneFriVerifier<E, H, R, V>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    R: RandomCoin<BaseField = E::BaseField, Hasher: ElementHasher<Digest: From<H::Digest>>>,
+    V: VectorCommitment<H>,
+{
+    options: FriOptions,
+    num_queries: usize,
+    _phantom: PhantomData<(E, H, R, V)>,
+}
+
+impl<E, H, R, V> StandaloneFriVerifier<E, H, R, V>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    R: RandomCoin<BaseField = E::BaseField, Hasher: ElementHasher<Digest: From<H::Digest>>>,
+    V: VectorCommitment<H>,
+{
+    /// Returns a new standalone verifier instantiated with the provided `options` which expects
+    /// the committed codeword to be queried at `num_queries` positions.
+    ///
+    /// # Panics
+    /// Panics if `num_queries` is zero.
+    pub fn new(options: FriOptions, num_queries: usize) -> Self {
+        assert!(num_queries > 0, "number of queries must be greater than zero");
+        Self {
+            options,
+            num_queries,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Returns `Ok(())` if the specified `proof` attests that the codeword committed to by
+    /// `commitment` is close to evaluations of a polynomial of degree at most `max_poly_degree`.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The proof was generated for a codeword with a different commitment.
+    /// * The FRI proof for the codeword is not valid.
+    pub fn verify(
+        &self,
+        commitment: H::Digest,
+        proof: StandaloneFriProof<E, H>,
+        max_poly_degree: usize,
+    ) -> Result<(), VerifierError> {
+        if proof.layer_commitments.first() != Some(&commitment) {
+            return Err(VerifierError::CodewordCommitmentMismatch);
+        }
+        let domain_size = (max_poly_degree + 1).next_power_of_two() * self.options.blowup_factor();
+
+        // execute the commit phase of FRI and draw query positions
+        let mut public_coin = R::new(&[]);
+        let mut channel = DefaultVerifierChannel::<E, H, V>::new(
+            proof.fri_proof,
+            proof.layer_commitments,
+            domain_size,
+            &self.options,
+        )
+        .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
+        let fri_verifier = FriVerifier::<E, _, H, R, V>::new(
+            &mut channel,
+            &mut public_coin,
+            self.options.clone(),
+            max_poly_degree,
+        )?;
+        let positions = dedup_positions(
+            public_coin
+                .draw_integers(self.num_queries, domain_size, 0)
+                .map_err(VerifierError::RandomCoinError)?,
+        );
+
+        // the queried values are checked against the codeword commitment as a part of verifying
+        // the first FRI layer
+        fri_verifier.verify(&mut channel, &proof.queried_values, &positions)
+    }
+}
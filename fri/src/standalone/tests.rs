@@ -0,0 +1,92 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::vec::Vec;
+
+use crypto::{hashers::Blake3_256, DefaultRandomCoin, MerkleTree};
+use math::{fft, fields::f128::BaseElement, FieldElement};
+use rand_utils::rand_vector;
+use utils::{Deserializable, Serializable};
+
+use super::{StandaloneFriProof, StandaloneFriProver, StandaloneFriVerifier};
+use crate::{FriOptions, VerifierError};
+
+type Blake3 = Blake3_256<BaseElement>;
+type Prover =
+    StandaloneFriProver<BaseElement, Blake3, DefaultRandomCoin<Blake3>, MerkleTree<Blake3>>;
+type Verifier =
+    StandaloneFriVerifier<BaseElement, Blake3, DefaultRandomCoin<Blake3>, MerkleTree<Blake3>>;
+type Proof = StandaloneFriProof<BaseElement, Blake3>;
+
+const POLY_SIZE: usize = 1 << 8;
+const BLOWUP: usize = 8;
+const NUM_QUERIES: usize = 32;
+
+#[test]
+fn standalone_fri_prove_verify() {
+    let options = FriOptions::new(BLOWUP, 4, 7);
+    let evaluations = build_evaluations(POLY_SIZE);
+    let prover = Prover::new(options.clone(), NUM_QUERIES);
+    let commitment = prover.commit(&evaluations);
+
+    let proof = prover.prove(evaluations);
+    assert_eq!(commitment, proof.commitment());
+
+    // the proof survives a serialization round trip
+    let proof = Proof::read_from_bytes(&proof.to_bytes()).unwrap();
+    let verifier = Verifier::new(options, NUM_QUERIES);
+    assert_eq!(Ok(()), verifier.verify(commitment, proof, POLY_SIZE - 1));
+}
+
+#[test]
+fn standalone_fri_high_degree_codeword() {
+    let options = FriOptions::new(BLOWUP, 2, 7);
+    let evaluations: Vec<BaseElement> = rand_vector(POLY_SIZE * BLOWUP);
+    let prover = Prover::new(options.clone(), NUM_QUERIES);
+    let commitment = prover.commit(&evaluations);
+
+    let proof = prover.prove(evaluations);
+    let verifier = Verifier::new(options, NUM_QUERIES);
+    assert!(verifier.verify(commitment, proof, POLY_SIZE - 1).is_err());
+}
+
+#[test]
+fn standalone_fri_invalid_commitment() {
+    let options = FriOptions::new(BLOWUP, 4, 7);
+    let prover = Prover::new(options.clone(), NUM_QUERIES);
+    let verifier = Verifier::new(options, NUM_QUERIES);
+    let evaluations = build_evaluations(POLY_SIZE);
+    let commitment = prover.commit(&evaluations);
+
+    // proof for a different codeword
+    let proof = prover.prove(build_evaluations(POLY_SIZE));
+    assert_eq!(
+        Err(VerifierError::CodewordCommitmentMismatch),
+        verifier.verify(commitment, proof, POLY_SIZE - 1)
+    );
+
+    // queried values which do not match the codeword commitment
+    let mut proof = prover.prove(evaluations);
+    proof.queried_values[2] += BaseElement::ONE;
+    assert_eq!(
+        Err(VerifierError::InvalidLayerFolding(0, 2)),
+        verifier.verify(commitment, proof, POLY_SIZE - 1)
+    );
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns evaluations of a random polynomial with `num_coeffs` coefficients over a domain of
+/// size `POLY_SIZE * BLOWUP`.
+fn build_evaluations(num_coeffs: usize) -> Vec<BaseElement> {
+    let mut p: Vec<BaseElement> = rand_vector(num_coeffs);
+    let domain_size = POLY_SIZE * BLOWUP;
+    p.resize(domain_size, BaseElement::ZERO);
+
+    let twiddles = fft::get_twiddles::<BaseElement>(domain_size);
+    fft::evaluate_poly(&mut p, &twiddles);
+    p
+}
@@ -31,3 +31,10 @@ pub fn map_positions_to_indexes(
 
     result
 }
+
+/// Sorts the provided query positions and removes duplicates from them.
+pub(crate) fn dedup_positions(mut positions: Vec<usize>) -> Vec<usize> {
+    positions.sort_unstable();
+    positions.dedup();
+    positions
+}
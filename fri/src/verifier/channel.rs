@@ -3,14 +3,13 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use alloc::vec::Vec;
-use core::marker::PhantomData;
+use alloc::{string::ToString, vec::Vec};
 
 use crypto::{ElementHasher, Hasher, VectorCommitment};
 use math::FieldElement;
 use utils::{group_slice_elements, DeserializationError};
 
-use crate::{FriProof, VerifierError};
+use crate::{FriLayerReader, FriOptions, FriProof, VerifierError};
 
 // VERIFIER CHANNEL TRAIT
 // ================================================================================================
@@ -45,48 +44,59 @@ pub trait VerifierChannel<E: FieldElement> {
     fn read_fri_layer_commitments(&mut self) -> Vec<<Self::Hasher as Hasher>::Digest>;
 
     /// Reads and removes from the channel evaluations of the polynomial at the queried positions
-    /// for the next FRI layer.
+    /// for the next FRI layer together with vector commitment opening proofs for these
+    /// evaluations.
     ///
-    /// In the interactive version of the protocol, these evaluations are sent from the prover to
-    /// the verifier during the query phase of the FRI protocol.
+    /// In the interactive version of the protocol, these evaluations and authentication paths are
+    /// sent from the prover to the verifier during the query phase of the FRI protocol.
     ///
     /// It is expected that layer queries and layer proofs at the same FRI layer are consistent.
-    /// That is, query values hash into the leaf nodes of corresponding vector commitment.
-    fn take_next_fri_layer_queries(&mut self) -> Vec<E>;
-
-    /// Reads and removes from the channel vector commitment opening proofs of queried evaluations
-    /// for the next FRI layer.
-    ///
-    /// In the interactive version of the protocol, these authentication paths are sent from the
-    /// prover to the verifier during the query phase of the FRI protocol.
-    ///
-    /// It is expected that layer proofs and layer queries at the same FRI layer are consistent.
     /// That is, query values hash into the elements of the vector committed to using the specified
     /// vector commitment scheme.
-    fn take_next_fri_layer_proof(
+    ///
+    /// Layers are read one at a time, and the verifier processes each layer before reading the
+    /// next one. Thus, implementations can parse layer data lazily so that only a single layer
+    /// needs to be held in memory at any given time.
+    ///
+    /// # Errors
+    /// Returns an error if there are no more layers in the channel, or if the data for the next
+    /// layer could not be parsed.
+    #[allow(clippy::type_complexity)]
+    fn take_next_fri_layer(
         &mut self,
-    ) -> <Self::VectorCommitment as VectorCommitment<Self::Hasher>>::MultiProof;
+    ) -> Result<
+        (Vec<E>, <Self::VectorCommitment as VectorCommitment<Self::Hasher>>::MultiProof),
+        VerifierError,
+    >;
 
     /// Reads and removes the remainder polynomial from the channel.
     fn take_fri_remainder(&mut self) -> Vec<E>;
 
+    /// Reads and removes out-of-domain evaluations of FRI layer polynomials from the channel.
+    ///
+    /// These evaluations are present only in proofs generated with DEEP folding; for each FRI
+    /// layer except for the remainder, the channel contains as many evaluations as the folding
+    /// factor of the layer, and evaluations for all layers are stored one after another.
+    fn take_fri_deep_evaluations(&mut self) -> Vec<E>;
+
     // PROVIDED METHODS
     // --------------------------------------------------------------------------------------------
 
     /// Returns FRI query values at the specified positions from the current FRI layer and advances
     /// layer pointer by one.
     ///
-    /// This also checks if the values are valid against the provided FRI layer commitment.
+    /// This also checks if the values are valid against the provided FRI layer commitment;
+    /// `layer_idx` is the index of the current FRI layer, and is used only to report errors.
     ///
     /// # Errors
     /// Returns an error if query values did not match layer commitment.
     fn read_layer_queries<const N: usize>(
         &mut self,
+        layer_idx: usize,
         positions: &[usize],
         commitment: &<Self::Hasher as Hasher>::Digest,
     ) -> Result<Vec<[E; N]>, VerifierError> {
-        let layer_proof = self.take_next_fri_layer_proof();
-        let layer_queries = self.take_next_fri_layer_queries();
+        let (layer_queries, layer_proof) = self.take_next_fri_layer()?;
         // build the values (i.e., polynomial evaluations over a coset of a multiplicative subgroup
         // of the current evaluation domain) corresponding to each leaf of the layer commitment
         let leaf_values = group_slice_elements(&layer_queries);
@@ -103,7 +113,7 @@ pub trait VerifierChannel<E: FieldElement> {
             &hashed_values,
             &layer_proof,
         )
-        .map_err(|_| VerifierError::LayerCommitmentMismatch)?;
+        .map_err(|_| VerifierError::LayerCommitmentMismatch(layer_idx))?;
 
         Ok(leaf_values.to_vec())
     }
@@ -121,7 +131,8 @@ pub trait VerifierChannel<E: FieldElement> {
 
 /// Provides a default implementation of the [VerifierChannel] trait.
 ///
-/// Default verifier channel can be instantiated directly from a [FriProof] struct.
+/// Default verifier channel can be instantiated directly from a [FriProof] struct. Layers of the
+/// proof are parsed only when they are read from the channel.
 ///
 /// Though this implementation is primarily intended for testing purposes, it can be used in
 /// production use cases as well.
@@ -131,11 +142,10 @@ pub struct DefaultVerifierChannel<
     V: VectorCommitment<H>,
 > {
     layer_commitments: Vec<H::Digest>,
-    layer_proofs: Vec<V::MultiProof>,
-    layer_queries: Vec<Vec<E>>,
+    layers: FriLayerReader<E, H, V>,
     remainder: Vec<E>,
+    deep_evaluations: Vec<E>,
     num_partitions: usize,
-    _h: PhantomData<H>,
 }
 
 impl<E, H, V> DefaultVerifierChannel<E, H, V>
@@ -147,26 +157,27 @@ where
     /// Builds a new verifier channel from the specified [FriProof].
     ///
     /// # Errors
-    /// Returns an error if the specified `proof` could not be parsed correctly.
+    /// Returns an error if the remainder or the DEEP evaluations of the specified `proof` could
+    /// not be parsed correctly.
+    /// Errors in layer data are reported when the corresponding layer is read from the channel.
     pub fn new(
         proof: FriProof,
         layer_commitments: Vec<H::Digest>,
         domain_size: usize,
-        folding_factor: usize,
+        options: &FriOptions,
     ) -> Result<Self, DeserializationError> {
         let num_partitions = proof.num_partitions();
 
         let remainder = proof.parse_remainder()?;
-        let (layer_queries, layer_proofs) =
-            proof.parse_layers::<E, H, V>(domain_size, folding_factor)?;
+        let deep_evaluations = proof.parse_deep_evaluations()?;
+        let layers = proof.into_layer_reader(domain_size, options);
 
         Ok(DefaultVerifierChannel {
             layer_commitments,
-            layer_proofs,
-            layer_queries,
+            layers,
             remainder,
+            deep_evaluations,
             num_partitions,
-            _h: PhantomData,
         })
     }
 }
@@ -188,15 +199,39 @@ where
         self.layer_commitments.drain(..).collect()
     }
 
-    fn take_next_fri_layer_proof(&mut self) -> V::MultiProof {
-        self.layer_proofs.remove(0)
-    }
-
-    fn take_next_fri_layer_queries(&mut self) -> Vec<E> {
-        self.layer_queries.remove(0)
+    fn take_next_fri_layer(&mut self) -> Result<(Vec<E>, V::MultiProof), VerifierError> {
+        read_next_layer(&mut self.layers)
     }
 
     fn take_fri_remainder(&mut self) -> Vec<E> {
         self.remainder.clone()
     }
+
+    fn take_fri_deep_evaluations(&mut self) -> Vec<E> {
+        core::mem::take(&mut self.deep_evaluations)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Parses the next layer from the provided layer reader.
+///
+/// # Errors
+/// Returns an error if the reader has no more layers, or if the next layer could not be parsed.
+#[allow(clippy::type_complexity)]
+fn read_next_layer<E, H, V>(
+    layers: &mut FriLayerReader<E, H, V>,
+) -> Result<(Vec<E>, V::MultiProof), VerifierError>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    V: VectorCommitment<H>,
+{
+    layers
+        .next()
+        .ok_or_else(|| {
+            VerifierError::ProofDeserializationError("no more FRI layers to read".to_string())
+        })?
+        .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))
 }
@@ -6,12 +6,16 @@
 //! Contains an implementation of FRI verifier and associated components.
 
 use alloc::vec::Vec;
-use core::{marker::PhantomData, mem};
+use core::marker::PhantomData;
 
-use crypto::{ElementHasher, RandomCoin, VectorCommitment};
-use math::{polynom, FieldElement, StarkField};
+use crypto::{transcript_labels, ElementHasher, RandomCoin, VectorCommitment};
+use math::{batch_inversion, polynom, FieldElement, StarkField};
 
-use crate::{folding::fold_positions, utils::map_positions_to_indexes, FriOptions, VerifierError};
+use crate::{
+    folding::{fold_deep_evaluations, fold_positions},
+    utils::map_positions_to_indexes,
+    FriOptions, VerifierError,
+};
 
 mod channel;
 pub use channel::{DefaultVerifierChannel, VerifierChannel};
@@ -62,7 +66,7 @@ where
     E: FieldElement,
     C: VerifierChannel<E, Hasher = H>,
     H: ElementHasher<BaseField = E::BaseField>,
-    R: RandomCoin<BaseField = E::BaseField, Hasher = H>,
+    R: RandomCoin<BaseField = E::BaseField, Hasher: ElementHasher<Digest: From<H::Digest>>>,
     V: VectorCommitment<H>,
 {
     max_poly_degree: usize,
@@ -70,6 +74,7 @@ where
     domain_generator: E::BaseField,
     layer_commitments: Vec<H::Digest>,
     layer_alphas: Vec<E>,
+    deep_folds: Vec<(E, E)>,
     options: FriOptions,
     num_partitions: usize,
     _channel: PhantomData<C>,
@@ -82,7 +87,7 @@ where
     E: FieldElement,
     C: VerifierChannel<E, Hasher = H, VectorCommitment = V>,
     H: ElementHasher<BaseField = E::BaseField>,
-    R: RandomCoin<BaseField = E::BaseField, Hasher = H>,
+    R: RandomCoin<BaseField = E::BaseField, Hasher: ElementHasher<Digest: From<H::Digest>>>,
     V: VectorCommitment<H>,
 {
     /// Returns a new instance of FRI verifier created from the specified parameters.
@@ -96,6 +101,12 @@ where
     /// and for each commitment, updates the `public_coin` with this commitment and then draws
     /// a random value α from the coin.
     ///
+    /// If DEEP folding is enabled in the `options`, the verifier also draws a point z for every
+    /// layer but the remainder before drawing α, and reads evaluations of the layer polynomial
+    /// at the coset generated by z from the `channel`. These evaluations are used to compute the
+    /// value of the folded polynomial at z^`folding_factor`, which is divided out of the folded
+    /// evaluations during the query phase.
+    ///
     /// The verifier stores layer commitments and corresponding α values in its internal state,
     /// and, thus, an instance of FRI verifier can be used to verify only a single proof.
     ///
@@ -103,6 +114,8 @@ where
     /// Returns an error if:
     /// * `max_poly_degree` is inconsistent with the number of FRI layers read from the channel
     ///   and `folding_factor` specified in the `options` parameter.
+    /// * The number of DEEP evaluations read from the channel is inconsistent with the number of
+    ///   FRI layers and `folding_factor` specified in the `options` parameter.
     /// * An error was encountered while drawing a random α value from the coin.
     pub fn new(
         channel: &mut C,
@@ -120,23 +133,62 @@ where
         let layer_commitments = channel.read_fri_layer_commitments();
         let mut layer_alphas = Vec::with_capacity(layer_commitments.len());
         let mut max_degree_plus_1 = max_poly_degree + 1;
+
+        // read out-of-domain evaluations of layer polynomials; these are present only in DEEP
+        // mode, in which case there must be as many evaluations as the folding factor of the
+        // layer for every layer except for the remainder
+        let deep_evaluations = channel.take_fri_deep_evaluations();
+        let num_deep_layers = if options.deep_folding() {
+            layer_commitments.len().saturating_sub(1)
+        } else {
+            0
+        };
+        let num_deep_evaluations =
+            (0..num_deep_layers).map(|depth| options.layer_folding_factor(depth)).sum();
+        if deep_evaluations.len() != num_deep_evaluations {
+            return Err(VerifierError::NumDeepEvaluationsMismatch(
+                num_deep_evaluations,
+                deep_evaluations.len(),
+            ));
+        }
+        let mut deep_folds = Vec::with_capacity(num_deep_layers);
+        let mut deep_evaluations = deep_evaluations.as_slice();
+
         for (depth, commitment) in layer_commitments.iter().enumerate() {
-            public_coin.reseed(*commitment);
+            let folding_factor = options.layer_folding_factor(depth);
+            public_coin.reseed_with_label(transcript_labels::FRI_LAYER_ROOT, (*commitment).into());
+
+            let deep_point = if depth < num_deep_layers {
+                let point: E = public_coin.draw().map_err(VerifierError::RandomCoinError)?;
+                let (evaluations, rest) = deep_evaluations.split_at(folding_factor);
+                deep_evaluations = rest;
+                public_coin.reseed_with_label(
+                    transcript_labels::FRI_DEEP_EVALUATIONS,
+                    R::Hasher::hash_elements(evaluations),
+                );
+                Some((point, evaluations))
+            } else {
+                None
+            };
+
             let alpha = public_coin.draw().map_err(VerifierError::RandomCoinError)?;
             layer_alphas.push(alpha);
 
+            if let Some((point, evaluations)) = deep_point {
+                let folded_point = point.exp_vartime((folding_factor as u32).into());
+                deep_folds.push((folded_point, fold_deep_evaluations(evaluations, point, alpha)));
+            }
+
             // make sure the degree can be reduced by the folding factor at all layers
             // but the remainder layer
-            if depth != layer_commitments.len() - 1
-                && max_degree_plus_1 % options.folding_factor() != 0
-            {
+            if depth != layer_commitments.len() - 1 && max_degree_plus_1 % folding_factor != 0 {
                 return Err(VerifierError::DegreeTruncation(
                     max_degree_plus_1 - 1,
-                    options.folding_factor(),
+                    folding_factor,
                     depth,
                 ));
             }
-            max_degree_plus_1 /= options.folding_factor();
+            max_degree_plus_1 /= folding_factor;
         }
 
         Ok(FriVerifier {
@@ -145,6 +197,7 @@ where
             domain_generator,
             layer_commitments,
             layer_alphas,
+            deep_folds,
             options,
             num_partitions,
             _channel: PhantomData,
@@ -205,8 +258,12 @@ where
     ///   FRI layers.
     /// * The verifier detects an error in how the degree-respecting projection was applied
     ///   at any of the FRI layers.
+    ///
+    /// Errors of the query checks identify the layer and the query (i.e., the index into
+    /// `positions`) for which a check failed, where available.
     /// * The degree of the remainder at the last FRI layer is greater than the degree implied by
     ///   `max_poly_degree` reduced by the folding factor at each FRI layer.
+    /// * The remainder coefficients do not match the remainder commitment made by the prover.
     pub fn verify(
         &self,
         channel: &mut C,
@@ -220,89 +277,36 @@ where
             ));
         }
 
-        // static dispatch for folding factor parameter
-        let folding_factor = self.options.folding_factor();
-        match folding_factor {
-            2 => self.verify_generic::<2>(channel, evaluations, positions),
-            4 => self.verify_generic::<4>(channel, evaluations, positions),
-            8 => self.verify_generic::<8>(channel, evaluations, positions),
-            16 => self.verify_generic::<16>(channel, evaluations, positions),
-            _ => Err(VerifierError::UnsupportedFoldingFactor(folding_factor)),
-        }
-    }
-
-    /// This is the actual implementation of the verification procedure described above, but it
-    /// also takes folding factor as a generic parameter N.
-    fn verify_generic<const N: usize>(
-        &self,
-        channel: &mut C,
-        evaluations: &[E],
-        positions: &[usize],
-    ) -> Result<(), VerifierError> {
-        // pre-compute roots of unity used in computing x coordinates in the folded domain
-        let folding_roots = (0..N)
-            .map(|i| self.domain_generator.exp_vartime(((self.domain_size / N * i) as u64).into()))
-            .collect::<Vec<_>>();
-
         // 1 ----- verify the recursive components of the FRI proof -----------------------------------
-        let mut domain_generator = self.domain_generator;
-        let mut domain_size = self.domain_size;
-        let mut max_degree_plus_1 = self.max_poly_degree + 1;
-        let mut positions = positions.to_vec();
-        let mut evaluations = evaluations.to_vec();
+        let mut state = QueryState {
+            domain_generator: self.domain_generator,
+            domain_size: self.domain_size,
+            max_degree_plus_1: self.max_poly_degree + 1,
+            positions: positions.to_vec(),
+            query_indexes: (0..positions.len()).collect(),
+            evaluations: evaluations.to_vec(),
+        };
 
         for depth in 0..self.options.num_fri_layers(self.domain_size) {
-            // determine which evaluations were queried in the folded layer
-            let mut folded_positions =
-                fold_positions(&positions, domain_size, self.options.folding_factor());
-            // determine where these evaluations are in the vector commitment
-            let position_indexes = map_positions_to_indexes(
-                &folded_positions,
-                domain_size,
-                self.options.folding_factor(),
-                self.num_partitions,
-            );
-            // read query values from the specified indexes
-            let layer_commitment = self.layer_commitments[depth];
-            // TODO: add layer depth to the potential error message
-            let layer_values = channel.read_layer_queries(&position_indexes, &layer_commitment)?;
-            let query_values =
-                get_query_values::<E, N>(&layer_values, &positions, &folded_positions, domain_size);
-            if evaluations != query_values {
-                return Err(VerifierError::InvalidLayerFolding(depth));
-            }
-
-            // build a set of x coordinates for each row polynomial
-            #[rustfmt::skip]
-            let xs = folded_positions.iter().map(|&i| {
-                let xe = domain_generator.exp_vartime((i as u64).into()) * self.options.domain_offset();
-                folding_roots.iter()
-                    .map(|&r| E::from(xe * r))
-                    .collect::<Vec<_>>().try_into().unwrap()
-            })
-            .collect::<Vec<_>>();
-
-            // interpolate x and y values into row polynomials
-            let row_polys = polynom::interpolate_batch(&xs, &layer_values);
-
-            // calculate the pseudo-random value used for linear combination in layer folding
-            let alpha = self.layer_alphas[depth];
-
-            // check that when the polynomials are evaluated at alpha, the result is equal to
-            // the corresponding column value
-            evaluations = row_polys.iter().map(|p| polynom::eval(p, alpha)).collect();
-
-            // make sure next degree reduction does not result in degree truncation
-            if max_degree_plus_1 % N != 0 {
-                return Err(VerifierError::DegreeTruncation(max_degree_plus_1 - 1, N, depth));
+            // static dispatch for folding factor parameter
+            match self.options.layer_folding_factor(depth) {
+                2 => self.verify_layer::<2>(channel, depth, &mut state)?,
+                4 => self.verify_layer::<4>(channel, depth, &mut state)?,
+                8 => self.verify_layer::<8>(channel, depth, &mut state)?,
+                16 => self.verify_layer::<16>(channel, depth, &mut state)?,
+                folding_factor => {
+                    return Err(VerifierError::UnsupportedFoldingFactor(folding_factor))
+                },
             }
-
-            // update variables for the next iteration of the loop
-            domain_generator = domain_generator.exp_vartime((N as u32).into());
-            max_degree_plus_1 /= N;
-            domain_size /= N;
-            mem::swap(&mut positions, &mut folded_positions);
         }
+        let QueryState {
+            domain_generator,
+            max_degree_plus_1,
+            positions,
+            query_indexes,
+            evaluations,
+            ..
+        } = state;
 
         // 2 ----- verify the remainder polynomial of the FRI proof -------------------------------
 
@@ -312,22 +316,154 @@ where
         if remainder_poly.len() > max_degree_plus_1 {
             return Err(VerifierError::RemainderDegreeMismatch(max_degree_plus_1 - 1));
         }
+
+        // the remainder coefficients must be the ones committed to by the prover before the query
+        // positions were drawn; the remainder commitment is the last FRI layer commitment
+        let remainder_commitment =
+            self.layer_commitments.last().expect("no remainder commitment was read");
+        if <H as ElementHasher>::hash_elements(&remainder_poly) != *remainder_commitment {
+            return Err(VerifierError::RemainderCommitmentMismatch);
+        }
         let offset: E::BaseField = self.options().domain_offset();
 
-        for (&position, evaluation) in positions.iter().zip(evaluations) {
+        for ((&position, evaluation), &query_idx) in
+            positions.iter().zip(evaluations).zip(query_indexes.iter())
+        {
             let comp_eval = eval_horner::<E>(
                 &remainder_poly,
                 offset * domain_generator.exp_vartime((position as u64).into()),
             );
             if comp_eval != evaluation {
-                return Err(VerifierError::InvalidRemainderFolding);
+                return Err(VerifierError::InvalidRemainderFolding(query_idx));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Verifies the queries against the FRI layer at the specified `depth`, and moves the
+    /// positions and evaluations of the queries in the `state` to the next layer. This takes the
+    /// folding factor of the layer as a generic parameter N.
+    fn verify_layer<const N: usize>(
+        &self,
+        channel: &mut C,
+        depth: usize,
+        state: &mut QueryState<E>,
+    ) -> Result<(), VerifierError> {
+        let domain_generator = state.domain_generator;
+        let domain_size = state.domain_size;
+
+        // pre-compute roots of unity used in computing x coordinates in the folded domain
+        let folding_roots = (0..N)
+            .map(|i| domain_generator.exp_vartime(((domain_size / N * i) as u64).into()))
+            .collect::<Vec<_>>();
+
+        // determine which evaluations were queried in the folded layer
+        let folded_positions = fold_positions(&state.positions, domain_size, N);
+        // determine where these evaluations are in the vector commitment
+        let position_indexes =
+            map_positions_to_indexes(&folded_positions, domain_size, N, self.num_partitions);
+        // read query values from the specified indexes
+        let layer_commitment = self.layer_commitments[depth];
+        let layer_values =
+            channel.read_layer_queries(depth, &position_indexes, &layer_commitment)?;
+        let query_values = get_query_values::<E, N>(
+            &layer_values,
+            &state.positions,
+            &folded_positions,
+            domain_size,
+        );
+        if let Some(i) = (0..query_values.len()).find(|&i| state.evaluations[i] != query_values[i])
+        {
+            return Err(VerifierError::InvalidLayerFolding(depth, state.query_indexes[i]));
+        }
+
+        // build a set of x coordinates for each row polynomial
+        #[rustfmt::skip]
+        let xs = folded_positions.iter().map(|&i| {
+            let xe = domain_generator.exp_vartime((i as u64).into()) * self.options.domain_offset();
+            folding_roots.iter()
+                .map(|&r| E::from(xe * r))
+                .collect::<Vec<_>>().try_into().unwrap()
+        })
+        .collect::<Vec<_>>();
+
+        // interpolate x and y values into row polynomials
+        let row_polys = polynom::interpolate_batch(&xs, &layer_values);
+
+        // calculate the pseudo-random value used for linear combination in layer folding
+        let alpha = self.layer_alphas[depth];
+
+        // check that when the polynomials are evaluated at alpha, the result is equal to
+        // the corresponding column value
+        let mut evaluations: Vec<E> = row_polys.iter().map(|p| polynom::eval(p, alpha)).collect();
+
+        // in DEEP mode, the next layer contains the quotient of the folded polynomial by
+        // (y - z^N), where z is the DEEP point of this layer; apply the same transformation
+        // to the folded evaluations
+        if let Some(&(folded_point, folded_value)) = self.deep_folds.get(depth) {
+            let offset = self.options.domain_offset::<E::BaseField>();
+            let denominators = folded_positions
+                .iter()
+                .map(|&i| {
+                    let xe = domain_generator.exp_vartime((i as u64).into()) * offset;
+                    E::from(xe.exp_vartime((N as u32).into())) - folded_point
+                })
+                .collect::<Vec<_>>();
+            for (evaluation, inv_denominator) in
+                evaluations.iter_mut().zip(batch_inversion(&denominators))
+            {
+                *evaluation = (*evaluation - folded_value) * inv_denominator;
             }
         }
 
+        // make sure next degree reduction does not result in degree truncation
+        if state.max_degree_plus_1 % N != 0 {
+            return Err(VerifierError::DegreeTruncation(state.max_degree_plus_1 - 1, N, depth));
+        }
+
+        // update the state for the next layer; every folded position is attributed to the first
+        // query which maps onto it
+        let row_length = domain_size / N;
+        state.query_indexes = folded_positions
+            .iter()
+            .map(|&folded_position| {
+                let i = state
+                    .positions
+                    .iter()
+                    .position(|&position| position % row_length == folded_position)
+                    .expect("folded position does not correspond to any query");
+                state.query_indexes[i]
+            })
+            .collect();
+        state.domain_generator = domain_generator.exp_vartime((N as u32).into());
+        state.max_degree_plus_1 /= N;
+        state.domain_size /= N;
+        state.positions = folded_positions;
+        state.evaluations = evaluations;
+
         Ok(())
     }
 }
 
+// QUERY STATE
+// ================================================================================================
+
+/// Positions and evaluations of queries at the current FRI layer, together with the parameters
+/// of the layer.
+///
+/// Query indexes map every position to the index of the query (in the list of positions
+/// provided to the verifier) from which the position was derived; these are used only to report
+/// errors.
+struct QueryState<E: FieldElement> {
+    domain_generator: E::BaseField,
+    domain_size: usize,
+    max_degree_plus_1: usize,
+    positions: Vec<usize>,
+    query_indexes: Vec<usize>,
+    evaluations: Vec<E>,
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 fn get_query_values<E: FieldElement, const N: usize>(
@@ -38,6 +38,37 @@ pub fn batch_inv(c: &mut Criterion) {
     group.finish();
 }
 
+// F64 MULTIPLICATION
+// ================================================================================================
+
+/// Compares multiplying f64 field elements one by one with multiplying them via
+/// `BaseElement::mul_batch()`; run with and without `f64-limbs` feature to compare `f64`-based
+/// multiplication against native 128-bit integer multiplication.
+pub fn f64_mul_batch(c: &mut Criterion) {
+    let mut group = c.benchmark_group("f64_mul_batch");
+    group.sample_size(10);
+
+    for &size in SIZES.iter() {
+        let a = rand_vector::<f64::BaseElement>(size);
+        let b = rand_vector::<f64::BaseElement>(size);
+        let mut result = vec![f64::BaseElement::ZERO; size];
+
+        group.bench_function(BenchmarkId::new("scalar", size), |bench| {
+            bench.iter(|| {
+                for ((&a, &b), result) in a.iter().zip(b.iter()).zip(result.iter_mut()) {
+                    *result = a * b;
+                }
+            });
+        });
+
+        group.bench_function(BenchmarkId::new("batch", size), |bench| {
+            bench.iter(|| f64::BaseElement::mul_batch(&a, &b, &mut result));
+        });
+    }
+
+    group.finish();
+}
+
 // SEQUENTIAL OPS
 // ================================================================================================
 pub fn field_ops<B>(c: &mut Criterion, field_name: &str)
@@ -265,5 +296,5 @@ fn bench_field_ops(c: &mut Criterion) {
 // CRITERION BOILERPLATE
 // ================================================================================================
 
-criterion_group!(field_group, batch_inv, bench_field_ops);
+criterion_group!(field_group, batch_inv, f64_mul_batch, bench_field_ops);
 criterion_main!(field_group);
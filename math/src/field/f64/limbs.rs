@@ -0,0 +1,157 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Experimental multiplication backend which delegates 64-bit integer multiplication to `f64`
+//! arithmetic.
+//!
+//! Each 64-bit operand is split into two limbs: a low limb with 52 bits and a high limb with 12
+//! bits. Both limbs are exactly representable as `f64` values, and products of limbs are computed
+//! exactly using error-free transformations (i.e., as a sum of a rounded product and its rounding
+//! error). Since this uses only `f64` additions and multiplications, the same approach maps
+//! directly onto wide floating-point lanes (e.g., AVX-512 with FMA), where it can outperform
+//! 64-bit integer multiplication in FFT-heavy workloads.
+//!
+//! This backend is enabled via the `f64-limbs` feature and is intended for experimentation only.
+//! The batched implementation ([mul_wide_lanes()]) performs the floating-point part of the
+//! computation on arrays of [LANES] values, which the compiler can map onto vector registers
+//! (e.g., when compiled with `-C target-cpu=native` on CPUs with AVX-512). However, both the
+//! scalar and the batched implementations are currently slower than native 128-bit integer
+//! multiplication; the `f64_mul_batch` benchmark of this crate can be used to compare them.
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of bits in the low limb of an operand.
+const LIMB_BITS: u32 = 52;
+
+/// Mask for extracting the low limb of an operand.
+const LIMB_MASK: u64 = (1 << LIMB_BITS) - 1;
+
+/// 2^52 and 2^-52 represented as `f64` values.
+const LIMB_SCALE: f64 = (1u64 << LIMB_BITS) as f64;
+const LIMB_SCALE_INV: f64 = 1.0 / LIMB_SCALE;
+
+/// Splitting constant 2^27 + 1 used to split 53-bit values into two 26-bit halves.
+const SPLITTER: f64 = 134217729.0;
+
+/// Number of values multiplied by [mul_wide_lanes()] at once; 8 lanes of `f64` values fill a
+/// single AVX-512 register.
+pub const LANES: usize = 8;
+
+// PUBLIC FUNCTIONS
+// ================================================================================================
+
+/// Computes the full 128-bit product of two 64-bit values using `f64` arithmetic.
+#[inline(always)]
+pub fn mul_wide(a: u64, b: u64) -> u128 {
+    let (a0, a1) = to_limbs(a);
+    let (b0, b1) = to_limbs(b);
+
+    // a * b = a0 * b0 + (a0 * b1 + a1 * b0) * 2^52 + a1 * b1 * 2^104; the product of the high
+    // limbs has at most 24 bits, and thus, is computed directly in integer arithmetic
+    let (h00, l00) = exact_product(a0, b0);
+    let (h01, l01) = exact_product(a0, b1);
+    let (h10, l10) = exact_product(a1, b0);
+    let t2 = ((a >> LIMB_BITS) * (b >> LIMB_BITS)) as i64;
+
+    recombine(l00, h00 + l01 + l10, h01 + h10 + t2)
+}
+
+/// Computes the full 128-bit products of [LANES] pairs of 64-bit values using `f64` arithmetic.
+///
+/// This computes the same values as [mul_wide()] applied to every pair of values, but all steps
+/// up to the conversion of limb products into 64-bit integers are performed lane-wise on arrays
+/// of values; only the final recombination of the limb products uses 128-bit arithmetic.
+#[inline(always)]
+pub fn mul_wide_lanes(a: &[u64; LANES], b: &[u64; LANES]) -> [u128; LANES] {
+    let (a0, a1) = to_limbs_lanes(a);
+    let (b0, b1) = to_limbs_lanes(b);
+
+    let (h00, l00) = exact_product_lanes(&a0, &b0);
+    let (h01, l01) = exact_product_lanes(&a0, &b1);
+    let (h10, l10) = exact_product_lanes(&a1, &b0);
+
+    let mut result = [0; LANES];
+    for i in 0..LANES {
+        let t2 = ((a[i] >> LIMB_BITS) * (b[i] >> LIMB_BITS)) as i64;
+        result[i] = recombine(l00[i], h00[i] + l01[i] + l10[i], h01[i] + h10[i] + t2);
+    }
+    result
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Splits the provided value into a 52-bit low limb and a 12-bit high limb.
+#[inline(always)]
+fn to_limbs(x: u64) -> (f64, f64) {
+    // both limbs are smaller than 2^63, and thus, can be converted via signed integers, which
+    // is cheaper than conversion of unsigned integers on most platforms
+    (((x & LIMB_MASK) as i64) as f64, ((x >> LIMB_BITS) as i64) as f64)
+}
+
+/// Splits every value of the provided array into a 52-bit low limb and a 12-bit high limb.
+#[inline(always)]
+fn to_limbs_lanes(x: &[u64; LANES]) -> ([f64; LANES], [f64; LANES]) {
+    let mut lo = [0.0; LANES];
+    let mut hi = [0.0; LANES];
+    for i in 0..LANES {
+        (lo[i], hi[i]) = to_limbs(x[i]);
+    }
+    (lo, hi)
+}
+
+/// Returns the exact product of two integers smaller than 2^52 represented as `f64` values.
+///
+/// The product is computed as p + e, where p is the rounded product and e is the rounding error
+/// computed via Dekker's algorithm. The product is returned as a pair (hi, lo) of 64-bit integers
+/// such that the product is equal to hi * 2^52 + lo, where lo may be negative.
+#[inline(always)]
+fn exact_product(x: f64, y: f64) -> (i64, i64) {
+    let p = x * y;
+
+    let (x_hi, x_lo) = split(x);
+    let (y_hi, y_lo) = split(y);
+    let e = (((x_hi * y_hi - p) + x_hi * y_lo) + x_lo * y_hi) + x_lo * y_lo;
+
+    // p is smaller than 2^104, and thus, p = hi * 2^52 + lo where both hi and lo are smaller than
+    // 2^52 and lo is computed without rounding errors; since the error e is smaller than 2^51,
+    // all values can be converted into 64-bit integers directly
+    // SAFETY: all converted values are finite integers smaller than 2^63 in absolute value
+    unsafe {
+        let hi = (p * LIMB_SCALE_INV).to_int_unchecked::<i64>();
+        let lo = p - (hi as f64) * LIMB_SCALE;
+        (hi, lo.to_int_unchecked::<i64>() + e.to_int_unchecked::<i64>())
+    }
+}
+
+/// Computes exact products of the respective values of the provided arrays as described in
+/// [exact_product()].
+#[inline(always)]
+fn exact_product_lanes(x: &[f64; LANES], y: &[f64; LANES]) -> ([i64; LANES], [i64; LANES]) {
+    let mut hi = [0; LANES];
+    let mut lo = [0; LANES];
+    for i in 0..LANES {
+        (hi[i], lo[i]) = exact_product(x[i], y[i]);
+    }
+    (hi, lo)
+}
+
+/// Splits a 53-bit value into two halves such that x = hi + lo and each half has at most 26
+/// significant bits.
+#[inline(always)]
+fn split(x: f64) -> (f64, f64) {
+    let c = SPLITTER * x;
+    let hi = c - (c - x);
+    (hi, x - hi)
+}
+
+/// Returns c0 + c1 * 2^52 + c2 * 2^104 reduced modulo 2^128.
+#[inline(always)]
+fn recombine(c0: i64, c1: i64, c2: i64) -> u128 {
+    (c0 as i128 as u128)
+        .wrapping_add((c1 as i128 as u128) << LIMB_BITS)
+        .wrapping_add((c2 as u128) << (2 * LIMB_BITS))
+}
@@ -36,6 +36,11 @@ use utils::{
 
 use super::{ExtensibleField, FieldElement, StarkField};
 
+#[cfg(any(feature = "f64-limbs", test))]
+mod limbs;
+#[cfg(feature = "f64-limbs")]
+use limbs::{mul_wide, mul_wide_lanes, LANES};
+
 #[cfg(test)]
 mod tests;
 
@@ -59,6 +64,7 @@ const ELEMENT_BYTES: usize = core::mem::size_of::<u64>();
 /// Internal values represent x * R mod M where R = 2^64 mod M and x in [0, M).
 /// The backing type is `u64` but the internal values are always in the range [0, M).
 #[derive(Copy, Clone, Default)]
+#[repr(transparent)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 #[cfg_attr(feature = "serde", serde(try_from = "u64", into = "u64"))]
 pub struct BaseElement(u64);
@@ -112,6 +118,99 @@ impl BaseElement {
 
         BaseElement::from_mont(res.wrapping_add(0u32.wrapping_sub(over as u32) as u64))
     }
+
+    /// Multiplies elements of `a` by the respective elements of `b`, and writes the products
+    /// into `result`.
+    ///
+    /// When `f64-limbs` feature is enabled, the products are computed in batches of 8 elements
+    /// using `f64` arithmetic, which the compiler can map onto vector registers (e.g., AVX-512).
+    /// Otherwise, this is equivalent to multiplying elements one by one.
+    ///
+    /// # Panics
+    /// Panics if `a`, `b`, and `result` do not have the same length.
+    pub fn mul_batch(a: &[Self], b: &[Self], result: &mut [Self]) {
+        assert_eq!(a.len(), b.len(), "operands must have the same length");
+        assert_eq!(a.len(), result.len(), "result must have the same length as the operands");
+
+        #[cfg(feature = "f64-limbs")]
+        let (a, b, result) = {
+            let a_chunks = a.chunks_exact(LANES);
+            let b_chunks = b.chunks_exact(LANES);
+            let (a_rem, b_rem) = (a_chunks.remainder(), b_chunks.remainder());
+            let mut result_chunks = result.chunks_exact_mut(LANES);
+            for ((a, b), result) in a_chunks.zip(b_chunks).zip(&mut result_chunks) {
+                let a = core::array::from_fn(|i| a[i].0);
+                let b = core::array::from_fn(|i| b[i].0);
+                for (result, product) in result.iter_mut().zip(mul_wide_lanes(&a, &b)) {
+                    *result = Self(mont_red_cst(product));
+                }
+            }
+            (a_rem, b_rem, result_chunks.into_remainder())
+        };
+
+        for ((&a, &b), result) in a.iter().zip(b).zip(result) {
+            *result = a * b;
+        }
+    }
+
+    // ZERO-COPY CONVERSIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a slice of field elements backed by the provided `u64` values. Assumes that the
+    /// values are already in Montgomery form (i.e., as returned by [BaseElement::inner()]).
+    ///
+    /// This does not copy the underlying data; the values are only checked for validity.
+    ///
+    /// # Errors
+    /// Returns an error if any of the values is greater than or equal to the field modulus.
+    pub fn slice_from_mont(values: &[u64]) -> Result<&[Self], DeserializationError> {
+        validate_mont_values(values)?;
+        // SAFETY: BaseElement is a transparent wrapper around u64 and all values are valid
+        // Montgomery representations of field elements
+        Ok(unsafe { slice::from_raw_parts(values.as_ptr() as *const Self, values.len()) })
+    }
+
+    /// Returns the Montgomery representations of the provided field elements as a slice of `u64`
+    /// values without copying the underlying data.
+    pub fn slice_as_mont(elements: &[Self]) -> &[u64] {
+        // SAFETY: BaseElement is a transparent wrapper around u64
+        unsafe { slice::from_raw_parts(elements.as_ptr() as *const u64, elements.len()) }
+    }
+
+    /// Converts a vector of `u64` values into a vector of field elements without copying or
+    /// re-allocating the underlying buffer. Assumes that the values are already in Montgomery
+    /// form (i.e., as returned by [BaseElement::inner()]).
+    ///
+    /// This can be used to build columns of an execution trace in plain `u64` buffers.
+    ///
+    /// # Errors
+    /// Returns an error if any of the values is greater than or equal to the field modulus.
+    pub fn vec_from_mont(values: Vec<u64>) -> Result<Vec<Self>, DeserializationError> {
+        validate_mont_values(&values)?;
+        // SAFETY: BaseElement is a transparent wrapper around u64 and all values are valid
+        // Montgomery representations of field elements
+        Ok(unsafe { transmute_vec(values) })
+    }
+
+    /// Converts a vector of canonical `u64` values into a vector of field elements in place, i.e.,
+    /// without re-allocating the underlying buffer.
+    ///
+    /// Values greater than or equal to the field modulus are silently reduced. Unlike
+    /// [BaseElement::vec_from_mont()], this requires a pass over the data to convert each value
+    /// into Montgomery form.
+    pub fn vec_from_canonical(mut values: Vec<u64>) -> Vec<Self> {
+        values.iter_mut().for_each(|value| *value = Self::new(*value).0);
+        // SAFETY: BaseElement is a transparent wrapper around u64 and all values have been
+        // converted into valid Montgomery representations of field elements
+        unsafe { transmute_vec(values) }
+    }
+
+    /// Converts a vector of field elements into a vector of their Montgomery representations
+    /// without copying or re-allocating the underlying buffer.
+    pub fn vec_into_mont(elements: Vec<Self>) -> Vec<u64> {
+        // SAFETY: BaseElement is a transparent wrapper around u64
+        unsafe { transmute_vec(elements) }
+    }
 }
 
 impl FieldElement for BaseElement {
@@ -355,7 +454,7 @@ impl Mul for BaseElement {
 
     #[inline]
     fn mul(self, rhs: Self) -> Self {
-        Self(mont_red_cst((self.0 as u128) * (rhs.0 as u128)))
+        Self(mont_red_cst(mul_wide(self.0, rhs.0)))
     }
 }
 
@@ -694,6 +793,16 @@ fn exp_acc<const N: usize>(base: BaseElement, tail: BaseElement) -> BaseElement
     result * tail
 }
 
+/// Computes the full 128-bit product of two 64-bit values.
+///
+/// When `f64-limbs` feature is enabled, this is replaced with an experimental implementation
+/// which relies on `f64` arithmetic (see `limbs` module).
+#[cfg(not(feature = "f64-limbs"))]
+#[inline(always)]
+const fn mul_wide(a: u64, b: u64) -> u128 {
+    (a as u128) * (b as u128)
+}
+
 /// Montgomery reduction (variable time)
 #[allow(dead_code)]
 #[inline(always)]
@@ -743,3 +852,26 @@ fn equals(lhs: u64, rhs: u64) -> u64 {
     let t = lhs ^ rhs;
     !((((t | t.wrapping_neg()) as i64) >> 63) as u64)
 }
+
+/// Returns an error if any of the provided values is not a valid Montgomery representation of a
+/// field element.
+fn validate_mont_values(values: &[u64]) -> Result<(), DeserializationError> {
+    match values.iter().position(|&value| value >= M) {
+        Some(position) => Err(DeserializationError::InvalidValue(format!(
+            "value at position {position} is not a valid field element in Montgomery form"
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Re-interprets a vector of `T` as a vector of `U` without re-allocating the underlying buffer.
+///
+/// # Safety
+/// `T` and `U` must have the same size and alignment, and every value of type `T` in the vector
+/// must be a valid value of type `U`.
+unsafe fn transmute_vec<T, U>(values: Vec<T>) -> Vec<U> {
+    debug_assert_eq!(mem::size_of::<T>(), mem::size_of::<U>());
+    debug_assert_eq!(mem::align_of::<T>(), mem::align_of::<U>());
+    let mut values = mem::ManuallyDrop::new(values);
+    Vec::from_raw_parts(values.as_mut_ptr() as *mut U, values.len(), values.capacity())
+}
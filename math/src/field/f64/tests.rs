@@ -3,9 +3,11 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use alloc::vec::Vec;
+
 use num_bigint::BigUint;
 use proptest::prelude::*;
-use rand_utils::rand_value;
+use rand_utils::{rand_value, rand_vector};
 
 use super::{BaseElement, DeserializationError, FieldElement, Serializable, StarkField, M};
 use crate::field::{CubeExtension, ExtensionOf, QuadExtension};
@@ -68,7 +70,7 @@ fn mul() {
     assert_eq!(BaseElement::new(m - 2), t * BaseElement::from(2u8));
     assert_eq!(BaseElement::new(m - 4), t * BaseElement::from(4u8));
 
-    let t = (m + 1) / 2;
+    let t = m.div_ceil(2);
     assert_eq!(BaseElement::ONE, BaseElement::new(t) * BaseElement::from(2u8));
 }
 
@@ -131,6 +133,39 @@ fn equals() {
     assert_eq!(a.to_bytes(), b.to_bytes());
 }
 
+#[test]
+fn mul_wide_f64_limbs() {
+    // values with all bits set in one or both limbs
+    let values = [0, 1, (1 << 52) - 1, 1 << 52, u64::MAX, M - 1, M];
+    for &a in values.iter() {
+        for &b in values.iter() {
+            assert_eq!((a as u128) * (b as u128), super::limbs::mul_wide(a, b));
+        }
+    }
+
+    // all pairs of the above values in lanes
+    let pairs = values.iter().flat_map(|&a| values.iter().map(move |&b| (a, b)));
+    let pairs = pairs.collect::<Vec<_>>();
+    for chunk in pairs.chunks_exact(super::limbs::LANES) {
+        let a = core::array::from_fn(|i| chunk[i].0);
+        let b = core::array::from_fn(|i| chunk[i].1);
+        let expected = core::array::from_fn(|i| (a[i] as u128) * (b[i] as u128));
+        assert_eq!(expected, super::limbs::mul_wide_lanes(&a, &b));
+    }
+}
+
+#[test]
+fn mul_batch() {
+    // the number of elements is not a multiple of the number of lanes
+    let a: Vec<BaseElement> = rand_vector(67);
+    let b: Vec<BaseElement> = rand_vector(67);
+    let mut result = vec![BaseElement::ZERO; 67];
+    BaseElement::mul_batch(&a, &b, &mut result);
+
+    let expected = a.iter().zip(b.iter()).map(|(&a, &b)| a * b).collect::<Vec<_>>();
+    assert_eq!(expected, result);
+}
+
 // ROOTS OF UNITY
 // ------------------------------------------------------------------------------------------------
 
@@ -214,6 +249,37 @@ fn bytes_as_elements() {
     assert!(matches!(result, Err(DeserializationError::InvalidValue(_))));
 }
 
+#[test]
+fn zero_copy_conversions() {
+    let elements = vec![BaseElement::new(1), BaseElement::new(M - 1), rand_value()];
+    let mont = elements.iter().map(|e| e.inner()).collect::<Vec<_>>();
+
+    // slices
+    assert_eq!(elements, BaseElement::slice_from_mont(&mont).unwrap());
+    assert_eq!(mont, BaseElement::slice_as_mont(&elements));
+    assert!(BaseElement::slice_from_mont(&[0, M]).is_err());
+
+    // vectors
+    let ptr = mont.as_ptr();
+    let result = BaseElement::vec_from_mont(mont).unwrap();
+    assert_eq!(elements, result);
+    assert_eq!(ptr as *const BaseElement, result.as_ptr());
+
+    let result = BaseElement::vec_into_mont(result);
+    assert_eq!(ptr, result.as_ptr());
+    assert!(BaseElement::vec_from_mont(vec![1, M, 2]).is_err());
+
+    let canonical = vec![0, 1, M - 1, M, u64::MAX];
+    let ptr = canonical.as_ptr();
+    let result = BaseElement::vec_from_canonical(canonical);
+    let expected = [0, 1, M - 1, 0, u64::MAX - M]
+        .iter()
+        .map(|&v| BaseElement::new(v))
+        .collect::<Vec<_>>();
+    assert_eq!(expected, result);
+    assert_eq!(ptr as *const BaseElement, result.as_ptr());
+}
+
 // QUADRATIC EXTENSION
 // ------------------------------------------------------------------------------------------------
 #[test]
@@ -390,6 +456,17 @@ proptest! {
         prop_assert_eq!(expected, (-v).as_int());
     }
 
+    #[test]
+    fn mul_wide_f64_limbs_proptest(a in any::<u64>(), b in any::<u64>()) {
+        prop_assert_eq!((a as u128) * (b as u128), super::limbs::mul_wide(a, b));
+    }
+
+    #[test]
+    fn mul_wide_lanes_f64_limbs_proptest(a in any::<[u64; 8]>(), b in any::<[u64; 8]>()) {
+        let expected: [u128; 8] = core::array::from_fn(|i| (a[i] as u128) * (b[i] as u128));
+        prop_assert_eq!(expected, super::limbs::mul_wide_lanes(&a, &b));
+    }
+
     #[test]
     fn mul_proptest(a in any::<u64>(), b in any::<u64>()) {
         let v1 = BaseElement::new(a);
@@ -9,6 +9,7 @@ pub use traits::{ExtensibleField, ExtensionOf, FieldElement, StarkField, ToEleme
 pub mod f128;
 pub mod f62;
 pub mod f64;
+pub mod secp256k1;
 
 mod extensions;
 pub use extensions::{CubeExtension, QuadExtension};
@@ -0,0 +1,582 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Implementations of the base field and the scalar field of the secp256k1 elliptic curve.
+//!
+//! The base field has modulus $p = 2^{256} - 2^{32} - 977$, and the scalar field has modulus
+//! $n$ equal to the order of the secp256k1 group. Both fields are described by a generic
+//! [Element] type parameterized by [FieldParams]; the [BaseElement] and [ScalarElement] aliases
+//! should be used to refer to the concrete fields.
+//!
+//! Neither of these fields is STARK-friendly: the two-adicity of the base field is 1, and the
+//! two-adicity of the scalar field is 6. Thus, FFT-based operations are not supported for these
+//! fields, and they cannot be used to generate STARK proofs directly. Instead, these fields are
+//! meant to be used for native computations over secp256k1 (e.g., for generating execution
+//! traces of AIRs which verify ECDSA signatures).
+//!
+//! Such AIRs usually emulate arithmetic in these fields using limbs of a smaller STARK field.
+//! To support this, the module provides CRT-based evaluation helpers: for a product
+//! $a \cdot b = q \cdot m + r$, [Element::mul_with_quotient()] returns the quotient $q$ and the
+//! remainder $r$, and [U256::residues()] reduces any of these values modulo a set of small moduli.
+//! An AIR can then check the relation modulo each of the small moduli; by the Chinese remainder
+//! theorem, this implies that the relation holds over the integers as long as the product of the
+//! moduli exceeds the magnitude of both sides of the relation.
+//!
+//! Internally, elements are stored in Montgomery form using four 64-bit limbs.
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{
+    fmt::{Debug, Display, Formatter},
+    marker::PhantomData,
+    mem,
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+    slice,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use utils::{
+    AsBytes, ByteReader, ByteWriter, Deserializable, DeserializationError, Randomizable,
+    Serializable,
+};
+
+use super::{FieldElement, StarkField};
+
+mod u256;
+use u256::mac;
+pub use u256::U256;
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+// Number of bytes needed to represent field element
+const ELEMENT_BYTES: usize = mem::size_of::<U256>();
+
+// FIELD PARAMETERS
+// ================================================================================================
+
+/// Defines parameters of a 256-bit prime field with elements in Montgomery form.
+///
+/// All values (except for `INV`) are specified in canonical (i.e., non-Montgomery) form.
+pub trait FieldParams:
+    Copy + Clone + Debug + Default + Eq + PartialEq + Send + Sync + 'static
+{
+    /// Field modulus; must be a prime greater than 2^255.
+    const MODULUS: U256;
+
+    /// -MODULUS^{-1} mod 2^64.
+    const INV: u64;
+
+    /// 2^512 mod MODULUS.
+    const R2: U256;
+
+    /// A multiplicative generator of the field.
+    const GENERATOR: U256;
+
+    /// Largest k such that 2^k divides MODULUS - 1.
+    const TWO_ADICITY: u32;
+
+    /// A root of unity of order 2^TWO_ADICITY.
+    const TWO_ADIC_ROOT_OF_UNITY: U256;
+}
+
+/// Parameters of the secp256k1 base field.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct BaseFieldParams;
+
+impl FieldParams for BaseFieldParams {
+    /// sage: MODULUS = 2^256 - 2^32 - 977 \
+    /// sage: GF(MODULUS).is_prime_field() \
+    /// True
+    const MODULUS: U256 = U256::from_limbs([
+        0xfffffffefffffc2f,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+    ]);
+    const INV: u64 = 0xd838091dd2253531;
+    const R2: U256 = U256::from_limbs([0x000007a2000e90a1, 0x1, 0x0, 0x0]);
+
+    /// sage: GF(MODULUS).primitive_element() \
+    /// 3
+    const GENERATOR: U256 = U256::from_limbs([3, 0, 0, 0]);
+
+    /// sage: is_odd((MODULUS - 1) / 2) \
+    /// True
+    const TWO_ADICITY: u32 = 1;
+
+    /// MODULUS - 1
+    const TWO_ADIC_ROOT_OF_UNITY: U256 = U256::from_limbs([
+        0xfffffffefffffc2e,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+    ]);
+}
+
+/// Parameters of the secp256k1 scalar field.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct ScalarFieldParams;
+
+impl FieldParams for ScalarFieldParams {
+    /// sage: MODULUS = 0xfffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141 \
+    /// sage: GF(MODULUS).is_prime_field() \
+    /// True
+    const MODULUS: U256 = U256::from_limbs([
+        0xbfd25e8cd0364141,
+        0xbaaedce6af48a03b,
+        0xfffffffffffffffe,
+        0xffffffffffffffff,
+    ]);
+    const INV: u64 = 0x4b0dff665588b13f;
+    const R2: U256 = U256::from_limbs([
+        0x896cf21467d7d140,
+        0x741496c20e7cf878,
+        0xe697f5e45bcd07c6,
+        0x9d671cd581c69bc5,
+    ]);
+
+    /// sage: GF(MODULUS).primitive_element() \
+    /// 7
+    const GENERATOR: U256 = U256::from_limbs([7, 0, 0, 0]);
+
+    /// sage: is_odd((MODULUS - 1) / 2^6) \
+    /// True
+    const TWO_ADICITY: u32 = 6;
+
+    /// sage: k = (MODULUS - 1) / 2^6 \
+    /// sage: GF(MODULUS).primitive_element()^k \
+    /// 5480320495727936603795231718619559942670027629901634955707709633242980176626
+    const TWO_ADIC_ROOT_OF_UNITY: U256 = U256::from_limbs([
+        0x992f4b5402b052f2,
+        0x98bdeab680756045,
+        0xdf9879a3fbc483a8,
+        0x0c1dc060e7a91986,
+    ]);
+}
+
+// FIELD ELEMENT
+// ================================================================================================
+
+/// An element of the secp256k1 base field.
+pub type BaseElement = Element<BaseFieldParams>;
+
+/// An element of the secp256k1 scalar field.
+pub type ScalarElement = Element<ScalarFieldParams>;
+
+/// Represents an element of a 256-bit prime field defined by [FieldParams].
+///
+/// Internal values represent x * R mod M where R = 2^256 mod M and x in [0, M).
+#[derive(Copy, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "U256", into = "U256", bound = ""))]
+pub struct Element<P: FieldParams>(U256, PhantomData<P>);
+
+impl<P: FieldParams> Element<P> {
+    /// Creates a new field element from the provided `value`; the value is converted into
+    /// Montgomery representation.
+    ///
+    /// If the value is greater than or equal to the field modulus, modular reduction is
+    /// silently performed.
+    pub const fn new(value: U256) -> Self {
+        // since the modulus is greater than 2^255, a single subtraction is sufficient to reduce
+        // any 256-bit value
+        let value = if value.ge(&P::MODULUS) {
+            value.sbb(&P::MODULUS).0
+        } else {
+            value
+        };
+        Self(mont_mul::<P>(&value, &P::R2), PhantomData)
+    }
+
+    /// Returns the canonical integer representation of this field element.
+    pub const fn as_int(&self) -> U256 {
+        mont_mul::<P>(&self.0, &U256::ONE)
+    }
+
+    /// Computes the product of `self` and `rhs` and returns the result together with the quotient
+    /// of the product over the integers.
+    ///
+    /// That is, for the returned `(r, q)` it holds that `a * b = q * M + r` over the integers,
+    /// where `a`, `b`, and `r` are canonical integer representations of `self`, `rhs`, and the
+    /// result respectively.
+    pub fn mul_with_quotient(self, rhs: Self) -> (Self, U256) {
+        let product = self.as_int().mul_wide(&rhs.as_int());
+        let (quotient, remainder) = U256::div_rem_wide(&product, &P::MODULUS);
+        (Self::new(remainder), quotient)
+    }
+
+    /// Returns residues of the canonical integer representation of this field element modulo each
+    /// of the provided `moduli`.
+    ///
+    /// # Panics
+    /// Panics if any of the moduli is zero.
+    pub fn residues(&self, moduli: &[u64]) -> Vec<u64> {
+        self.as_int().residues(moduli)
+    }
+}
+
+impl<P: FieldParams> FieldElement for Element<P> {
+    type PositiveInteger = U256;
+    type BaseField = Self;
+
+    const EXTENSION_DEGREE: usize = 1;
+
+    const ZERO: Self = Self(U256::ZERO, PhantomData);
+    const ONE: Self = Self::new(U256::ONE);
+
+    const ELEMENT_BYTES: usize = ELEMENT_BYTES;
+
+    const IS_CANONICAL: bool = false;
+
+    // ALGEBRA
+    // --------------------------------------------------------------------------------------------
+
+    fn inv(self) -> Self {
+        // by Fermat's little theorem, x^{-1} = x^{M - 2}; for x = 0, the result is 0
+        let power = P::MODULUS.sbb(&U256::from(2u32)).0;
+        let mut result = Self::ONE;
+        for i in (0..256).rev() {
+            result = result.square();
+            if (power.as_limbs()[i / 64] >> (i % 64)) & 1 == 1 {
+                result *= self;
+            }
+        }
+        result
+    }
+
+    fn conjugate(&self) -> Self {
+        *self
+    }
+
+    // BASE ELEMENT CONVERSIONS
+    // --------------------------------------------------------------------------------------------
+
+    fn base_element(&self, i: usize) -> Self::BaseField {
+        match i {
+            0 => *self,
+            _ => panic!("element index must be 0, but was {i}"),
+        }
+    }
+
+    fn slice_as_base_elements(elements: &[Self]) -> &[Self::BaseField] {
+        elements
+    }
+
+    fn slice_from_base_elements(elements: &[Self::BaseField]) -> &[Self] {
+        elements
+    }
+
+    // SERIALIZATION / DESERIALIZATION
+    // --------------------------------------------------------------------------------------------
+
+    fn elements_as_bytes(elements: &[Self]) -> &[u8] {
+        // TODO: take endianness into account
+        let p = elements.as_ptr();
+        let len = elements.len() * Self::ELEMENT_BYTES;
+        unsafe { slice::from_raw_parts(p as *const u8, len) }
+    }
+
+    unsafe fn bytes_as_elements(bytes: &[u8]) -> Result<&[Self], DeserializationError> {
+        if bytes.len() % Self::ELEMENT_BYTES != 0 {
+            return Err(DeserializationError::InvalidValue(format!(
+                "number of bytes ({}) does not divide into whole number of field elements",
+                bytes.len(),
+            )));
+        }
+
+        let p = bytes.as_ptr();
+        let len = bytes.len() / Self::ELEMENT_BYTES;
+
+        if (p as usize) % mem::align_of::<U256>() != 0 {
+            return Err(DeserializationError::InvalidValue(
+                "slice memory alignment is not valid for this field element type".to_string(),
+            ));
+        }
+
+        Ok(slice::from_raw_parts(p as *const Self, len))
+    }
+}
+
+impl<P: FieldParams> StarkField for Element<P> {
+    const MODULUS: Self::PositiveInteger = P::MODULUS;
+    const MODULUS_BITS: u32 = 256;
+    const GENERATOR: Self = Self::new(P::GENERATOR);
+    const TWO_ADICITY: u32 = P::TWO_ADICITY;
+    const TWO_ADIC_ROOT_OF_UNITY: Self = Self::new(P::TWO_ADIC_ROOT_OF_UNITY);
+
+    fn get_modulus_le_bytes() -> Vec<u8> {
+        P::MODULUS.to_le_bytes().to_vec()
+    }
+
+    #[inline]
+    fn as_int(&self) -> Self::PositiveInteger {
+        Element::as_int(self)
+    }
+}
+
+impl<P: FieldParams> Randomizable for Element<P> {
+    const VALUE_SIZE: usize = Self::ELEMENT_BYTES;
+
+    fn from_random_bytes(bytes: &[u8]) -> Option<Self> {
+        Self::try_from(bytes).ok()
+    }
+}
+
+impl<P: FieldParams> Debug for Element<P> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl<P: FieldParams> Display for Element<P> {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+        write!(f, "{}", self.as_int())
+    }
+}
+
+// OVERLOADED OPERATORS
+// ================================================================================================
+
+impl<P: FieldParams> Add for Element<P> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        let (sum, carry) = self.0.adc(&rhs.0);
+        if carry || sum.ge(&P::MODULUS) {
+            Self(sum.sbb(&P::MODULUS).0, PhantomData)
+        } else {
+            Self(sum, PhantomData)
+        }
+    }
+}
+
+impl<P: FieldParams> AddAssign for Element<P> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs
+    }
+}
+
+impl<P: FieldParams> Sub for Element<P> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        let (diff, borrow) = self.0.sbb(&rhs.0);
+        if borrow {
+            Self(diff.adc(&P::MODULUS).0, PhantomData)
+        } else {
+            Self(diff, PhantomData)
+        }
+    }
+}
+
+impl<P: FieldParams> SubAssign for Element<P> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<P: FieldParams> Mul for Element<P> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Self(mont_mul::<P>(&self.0, &rhs.0), PhantomData)
+    }
+}
+
+impl<P: FieldParams> MulAssign for Element<P> {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs
+    }
+}
+
+impl<P: FieldParams> Div for Element<P> {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self {
+        self * rhs.inv()
+    }
+}
+
+impl<P: FieldParams> DivAssign for Element<P> {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs
+    }
+}
+
+impl<P: FieldParams> Neg for Element<P> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::ZERO - self
+    }
+}
+
+// TYPE CONVERSIONS
+// ================================================================================================
+
+impl<P: FieldParams> From<u128> for Element<P> {
+    /// Converts a 128-bit value into a field element.
+    fn from(value: u128) -> Self {
+        Self::new(U256::from(value))
+    }
+}
+
+impl<P: FieldParams> From<u64> for Element<P> {
+    /// Converts a 64-bit value into a field element.
+    fn from(value: u64) -> Self {
+        Self::new(U256::from(value))
+    }
+}
+
+impl<P: FieldParams> From<u32> for Element<P> {
+    /// Converts a 32-bit value into a field element.
+    fn from(value: u32) -> Self {
+        Self::new(U256::from(value))
+    }
+}
+
+impl<P: FieldParams> From<u16> for Element<P> {
+    /// Converts a 16-bit value into a field element.
+    fn from(value: u16) -> Self {
+        Self::new(U256::from(value as u32))
+    }
+}
+
+impl<P: FieldParams> From<u8> for Element<P> {
+    /// Converts an 8-bit value into a field element.
+    fn from(value: u8) -> Self {
+        Self::new(U256::from(value as u32))
+    }
+}
+
+impl<P: FieldParams> TryFrom<U256> for Element<P> {
+    type Error = String;
+
+    fn try_from(value: U256) -> Result<Self, Self::Error> {
+        if value >= P::MODULUS {
+            Err(format!(
+                "invalid field element: value {value} is greater than or equal to the field modulus"
+            ))
+        } else {
+            Ok(Self::new(value))
+        }
+    }
+}
+
+impl<P: FieldParams> From<Element<P>> for U256 {
+    fn from(value: Element<P>) -> Self {
+        value.as_int()
+    }
+}
+
+impl<P: FieldParams> TryFrom<&'_ [u8]> for Element<P> {
+    type Error = String;
+
+    /// Converts a slice of bytes into a field element; returns error if the value encoded in bytes
+    /// is not a valid field element. The bytes are assumed to be in little-endian byte order.
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        let value =
+            bytes.try_into().map(U256::from_le_bytes).map_err(|error| format!("{error}"))?;
+        if value >= P::MODULUS {
+            return Err(format!(
+                "cannot convert bytes into a field element: \
+                value {value} is greater or equal to the field modulus"
+            ));
+        }
+        Ok(Self::new(value))
+    }
+}
+
+impl<P: FieldParams> AsBytes for Element<P> {
+    fn as_bytes(&self) -> &[u8] {
+        // TODO: take endianness into account
+        let self_ptr: *const Self = self;
+        unsafe { slice::from_raw_parts(self_ptr as *const u8, ELEMENT_BYTES) }
+    }
+}
+
+// SERIALIZATION / DESERIALIZATION
+// ------------------------------------------------------------------------------------------------
+
+impl<P: FieldParams> Serializable for Element<P> {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        // convert from Montgomery representation into canonical representation
+        target.write_bytes(&self.as_int().to_le_bytes());
+    }
+
+    fn get_size_hint(&self) -> usize {
+        ELEMENT_BYTES
+    }
+}
+
+impl<P: FieldParams> Deserializable for Element<P> {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let value = U256::from_le_bytes(source.read_array()?);
+        if value >= P::MODULUS {
+            return Err(DeserializationError::InvalidValue(format!(
+                "invalid field element: value {value} is greater than or equal to the field modulus"
+            )));
+        }
+        Ok(Self::new(value))
+    }
+}
+
+// FINITE FIELD ARITHMETIC
+// ================================================================================================
+
+/// Computes a * b * R^{-1} mod M using the CIOS method; a and b are assumed to be smaller than M.
+const fn mont_mul<P: FieldParams>(a: &U256, b: &U256) -> U256 {
+    let a = a.as_limbs();
+    let b = b.as_limbs();
+    let m = P::MODULUS.as_limbs();
+
+    let mut t = [0u64; 6];
+    let mut i = 0;
+    while i < 4 {
+        // t = t + a * b[i]
+        let mut carry = 0;
+        let mut j = 0;
+        while j < 4 {
+            let (lo, hi) = mac(t[j], a[j], b[i], carry);
+            t[j] = lo;
+            carry = hi;
+            j += 1;
+        }
+        let (lo, hi) = mac(t[4], 0, 0, carry);
+        t[4] = lo;
+        t[5] = hi;
+
+        // t = (t + k * m) / 2^64, where k is chosen so that the division is exact
+        let k = t[0].wrapping_mul(P::INV);
+        let (_, mut carry) = mac(t[0], k, m[0], 0);
+        let mut j = 1;
+        while j < 4 {
+            let (lo, hi) = mac(t[j], k, m[j], carry);
+            t[j - 1] = lo;
+            carry = hi;
+            j += 1;
+        }
+        let (lo, hi) = mac(t[4], 0, 0, carry);
+        t[3] = lo;
+        t[4] = t[5] + hi;
+        i += 1;
+    }
+
+    // the result is smaller than 2 * M, and thus a single subtraction is sufficient
+    let result = U256::from_limbs([t[0], t[1], t[2], t[3]]);
+    if t[4] != 0 || result.ge(&P::MODULUS) {
+        result.sbb(&P::MODULUS).0
+    } else {
+        result
+    }
+}
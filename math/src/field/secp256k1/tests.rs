@@ -0,0 +1,172 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::{string::ToString, vec::Vec};
+
+use num_bigint::BigUint;
+use rand_utils::{rand_value, rand_vector};
+use utils::{Deserializable, Serializable};
+
+use super::{
+    BaseElement, BaseFieldParams, Element, FieldElement, FieldParams, ScalarElement,
+    ScalarFieldParams, StarkField, U256,
+};
+
+// BASIC ALGEBRA
+// ================================================================================================
+
+#[test]
+fn add() {
+    check_add::<BaseFieldParams>();
+    check_add::<ScalarFieldParams>();
+}
+
+#[test]
+fn sub() {
+    check_sub::<BaseFieldParams>();
+    check_sub::<ScalarFieldParams>();
+}
+
+#[test]
+fn mul() {
+    check_mul::<BaseFieldParams>();
+    check_mul::<ScalarFieldParams>();
+}
+
+#[test]
+fn inv() {
+    check_inv::<BaseFieldParams>();
+    check_inv::<ScalarFieldParams>();
+}
+
+// ROOTS OF UNITY
+// ================================================================================================
+
+#[test]
+fn get_root_of_unity() {
+    let root = BaseElement::get_root_of_unity(1);
+    assert_eq!(-BaseElement::ONE, root);
+
+    let root_6 = ScalarElement::get_root_of_unity(6);
+    assert_eq!(ScalarElement::ONE, root_6.exp(U256::from(64u32)));
+    assert_ne!(ScalarElement::ONE, root_6.exp(U256::from(32u32)));
+
+    let root_5 = ScalarElement::get_root_of_unity(5);
+    assert_eq!(root_6.square(), root_5);
+}
+
+// CRT HELPERS
+// ================================================================================================
+
+#[test]
+fn mul_with_quotient() {
+    check_mul_with_quotient::<BaseFieldParams>();
+    check_mul_with_quotient::<ScalarFieldParams>();
+}
+
+#[test]
+fn residues() {
+    let moduli = [3, 0xffffffff00000001, 1 << 62];
+    let a: ScalarElement = rand_value();
+    let expected = moduli
+        .iter()
+        .map(|&m| (a.to_big_uint() % BigUint::from(m)).try_into().unwrap())
+        .collect::<Vec<u64>>();
+    assert_eq!(expected, a.residues(&moduli));
+}
+
+// SERIALIZATION / DESERIALIZATION
+// ================================================================================================
+
+#[test]
+fn serialization() {
+    let a: BaseElement = rand_value();
+    let bytes = a.to_bytes();
+    assert_eq!(a.as_int().to_le_bytes().to_vec(), bytes);
+    assert_eq!(a, BaseElement::read_from_bytes(&bytes).unwrap());
+
+    // modulus is not a valid element
+    let bytes = BaseElement::MODULUS.to_le_bytes();
+    assert!(BaseElement::read_from_bytes(&bytes).is_err());
+    assert!(BaseElement::try_from(bytes.as_slice()).is_err());
+}
+
+#[test]
+fn u256_display() {
+    let value = BaseElement::MODULUS;
+    assert_eq!(BigUint::from_bytes_le(&value.to_le_bytes()).to_string(), value.to_string());
+    assert_eq!("0", U256::ZERO.to_string());
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn check_add<P: FieldParams>() {
+    let t = Element::<P>::new(P::MODULUS.sbb(&U256::ONE).0);
+    assert_eq!(Element::<P>::ZERO, t + Element::ONE);
+
+    for _ in 0..100 {
+        let r1: Element<P> = rand_value();
+        let r2: Element<P> = rand_value();
+        let expected = (r1.to_big_uint() + r2.to_big_uint()) % modulus::<P>();
+        assert_eq!(expected, (r1 + r2).to_big_uint());
+    }
+}
+
+fn check_sub<P: FieldParams>() {
+    let t = Element::<P>::new(P::MODULUS.sbb(&U256::ONE).0);
+    assert_eq!(t, Element::ZERO - Element::ONE);
+
+    for _ in 0..100 {
+        let r1: Element<P> = rand_value();
+        let r2: Element<P> = rand_value();
+        let expected = (r1.to_big_uint() + modulus::<P>() - r2.to_big_uint()) % modulus::<P>();
+        assert_eq!(expected, (r1 - r2).to_big_uint());
+    }
+}
+
+fn check_mul<P: FieldParams>() {
+    let t = Element::<P>::new(P::MODULUS.sbb(&U256::ONE).0);
+    assert_eq!(Element::<P>::ONE, t * t);
+
+    for _ in 0..100 {
+        let r1: Element<P> = rand_value();
+        let r2: Element<P> = rand_value();
+        let expected = (r1.to_big_uint() * r2.to_big_uint()) % modulus::<P>();
+        assert_eq!(expected, (r1 * r2).to_big_uint());
+    }
+}
+
+fn check_inv<P: FieldParams>() {
+    assert_eq!(Element::<P>::ONE, Element::<P>::ONE.inv());
+    assert_eq!(Element::<P>::ZERO, Element::<P>::ZERO.inv());
+
+    let x: Vec<Element<P>> = rand_vector(100);
+    for i in x {
+        assert_eq!(Element::<P>::ONE, i * i.inv());
+    }
+}
+
+fn check_mul_with_quotient<P: FieldParams>() {
+    for _ in 0..100 {
+        let a: Element<P> = rand_value();
+        let b: Element<P> = rand_value();
+        let (r, q) = a.mul_with_quotient(b);
+        assert_eq!(a * b, r);
+
+        let q = BigUint::from_bytes_le(&q.to_le_bytes());
+        assert_eq!(a.to_big_uint() * b.to_big_uint(), q * modulus::<P>() + r.to_big_uint());
+    }
+}
+
+fn modulus<P: FieldParams>() -> BigUint {
+    BigUint::from_bytes_le(&P::MODULUS.to_le_bytes())
+}
+
+impl<P: FieldParams> Element<P> {
+    pub fn to_big_uint(&self) -> BigUint {
+        BigUint::from_bytes_le(&self.as_int().to_le_bytes())
+    }
+}
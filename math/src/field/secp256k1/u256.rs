@@ -0,0 +1,327 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::{string::String, vec::Vec};
+use core::{
+    cmp::Ordering,
+    fmt::{Debug, Display, Formatter},
+    ops::{BitAnd, Shl, Shr, ShrAssign},
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+// 256-BIT UNSIGNED INTEGER
+// ================================================================================================
+
+/// A 256-bit unsigned integer.
+///
+/// The value is stored as four 64-bit limbs in little-endian order. This type is used to describe
+/// field moduli and exponents for secp256k1 fields, and supports only the operations needed for
+/// this purpose.
+#[derive(Copy, Clone, PartialEq, Eq, Default, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct U256(pub(super) [u64; 4]);
+
+impl U256 {
+    /// The zero value.
+    pub const ZERO: Self = Self([0; 4]);
+
+    /// The value one.
+    pub const ONE: Self = Self([1, 0, 0, 0]);
+
+    /// Returns a new integer built from the provided limbs, which are assumed to be in
+    /// little-endian order.
+    pub const fn from_limbs(limbs: [u64; 4]) -> Self {
+        Self(limbs)
+    }
+
+    /// Returns limbs of this integer in little-endian order.
+    pub const fn as_limbs(&self) -> &[u64; 4] {
+        &self.0
+    }
+
+    /// Returns an integer encoded by the provided bytes in little-endian byte order.
+    pub fn from_le_bytes(bytes: [u8; 32]) -> Self {
+        let mut limbs = [0u64; 4];
+        for (limb, chunk) in limbs.iter_mut().zip(bytes.chunks_exact(8)) {
+            *limb = u64::from_le_bytes(chunk.try_into().unwrap());
+        }
+        Self(limbs)
+    }
+
+    /// Returns the memory representation of this integer as a byte array in little-endian byte
+    /// order.
+    pub fn to_le_bytes(&self) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        for (chunk, limb) in bytes.chunks_exact_mut(8).zip(self.0.iter()) {
+            chunk.copy_from_slice(&limb.to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Returns true if this integer is zero.
+    pub const fn is_zero(&self) -> bool {
+        self.0[0] == 0 && self.0[1] == 0 && self.0[2] == 0 && self.0[3] == 0
+    }
+
+    /// Returns the number of bits needed to represent this integer.
+    pub const fn bits(&self) -> u32 {
+        let mut i = 4;
+        while i > 0 {
+            i -= 1;
+            if self.0[i] != 0 {
+                return 64 * i as u32 + (64 - self.0[i].leading_zeros());
+            }
+        }
+        0
+    }
+
+    /// Returns the remainder of dividing this integer by `m`.
+    ///
+    /// # Panics
+    /// Panics if `m` is zero.
+    pub const fn rem_u64(&self, m: u64) -> u64 {
+        assert!(m != 0, "modulus cannot be zero");
+        let mut rem = 0u128;
+        let mut i = 4;
+        while i > 0 {
+            i -= 1;
+            rem = ((rem << 64) | self.0[i] as u128) % m as u128;
+        }
+        rem as u64
+    }
+
+    /// Returns residues of this integer modulo each of the provided `moduli`.
+    ///
+    /// # Panics
+    /// Panics if any of the moduli is zero.
+    pub fn residues(&self, moduli: &[u64]) -> Vec<u64> {
+        moduli.iter().map(|&m| self.rem_u64(m)).collect()
+    }
+
+    // ARITHMETIC
+    // --------------------------------------------------------------------------------------------
+
+    /// Computes `self + rhs` and returns the result together with the carry bit.
+    pub(super) const fn adc(&self, rhs: &Self) -> (Self, bool) {
+        let mut result = [0u64; 4];
+        let mut carry = false;
+        let mut i = 0;
+        while i < 4 {
+            let (s1, c1) = self.0[i].overflowing_add(rhs.0[i]);
+            let (s2, c2) = s1.overflowing_add(carry as u64);
+            result[i] = s2;
+            carry = c1 | c2;
+            i += 1;
+        }
+        (Self(result), carry)
+    }
+
+    /// Computes `self - rhs` and returns the result together with the borrow bit.
+    pub(super) const fn sbb(&self, rhs: &Self) -> (Self, bool) {
+        let mut result = [0u64; 4];
+        let mut borrow = false;
+        let mut i = 0;
+        while i < 4 {
+            let (d1, b1) = self.0[i].overflowing_sub(rhs.0[i]);
+            let (d2, b2) = d1.overflowing_sub(borrow as u64);
+            result[i] = d2;
+            borrow = b1 | b2;
+            i += 1;
+        }
+        (Self(result), borrow)
+    }
+
+    /// Returns true if `self` is greater than or equal to `rhs`.
+    pub(super) const fn ge(&self, rhs: &Self) -> bool {
+        !self.sbb(rhs).1
+    }
+
+    /// Computes the full 512-bit product of `self` and `rhs`; limbs of the result are in
+    /// little-endian order.
+    pub(super) const fn mul_wide(&self, rhs: &Self) -> [u64; 8] {
+        let mut result = [0u64; 8];
+        let mut i = 0;
+        while i < 4 {
+            let mut carry = 0u64;
+            let mut j = 0;
+            while j < 4 {
+                let (lo, hi) = mac(result[i + j], self.0[i], rhs.0[j], carry);
+                result[i + j] = lo;
+                carry = hi;
+                j += 1;
+            }
+            result[i + 4] = carry;
+            i += 1;
+        }
+        result
+    }
+
+    /// Divides a 512-bit integer `value` by `m`, and returns the quotient and the remainder.
+    ///
+    /// The quotient is assumed to fit into 256 bits; this is always the case when `value` is a
+    /// product of two integers smaller than `m`.
+    pub(super) fn div_rem_wide(value: &[u64; 8], m: &Self) -> (Self, Self) {
+        let mut quotient = Self::ZERO;
+        let mut rem = Self::ZERO;
+        for bit in (0..512).rev() {
+            // rem = (rem << 1) | bit; the remainder is always smaller than m, and thus the shifted
+            // value fits into 257 bits
+            let overflow = rem.0[3] >> 63 == 1;
+            rem = rem << 1;
+            rem.0[0] |= (value[bit / 64] >> (bit % 64)) & 1;
+
+            if overflow || rem.ge(m) {
+                rem = rem.sbb(m).0;
+                debug_assert!(bit < 256, "quotient does not fit into 256 bits");
+                quotient.0[bit / 64] |= 1 << (bit % 64);
+            }
+        }
+        (quotient, rem)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Computes `a + b * c + carry` and returns the result as (low, high) 64-bit words.
+#[inline(always)]
+pub(super) const fn mac(a: u64, b: u64, c: u64, carry: u64) -> (u64, u64) {
+    let ret = (a as u128) + (b as u128) * (c as u128) + (carry as u128);
+    (ret as u64, (ret >> 64) as u64)
+}
+
+// TRAIT IMPLEMENTATIONS
+// ================================================================================================
+
+impl PartialOrd for U256 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for U256 {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.iter().rev().cmp(other.0.iter().rev())
+    }
+}
+
+impl Shl<u32> for U256 {
+    type Output = Self;
+
+    fn shl(self, rhs: u32) -> Self {
+        if rhs >= 256 {
+            return Self::ZERO;
+        }
+        let limb_shift = (rhs / 64) as usize;
+        let bit_shift = rhs % 64;
+        let mut result = [0u64; 4];
+        for (i, limb) in result.iter_mut().enumerate().skip(limb_shift) {
+            *limb = self.0[i - limb_shift] << bit_shift;
+            if bit_shift > 0 && i > limb_shift {
+                *limb |= self.0[i - limb_shift - 1] >> (64 - bit_shift);
+            }
+        }
+        Self(result)
+    }
+}
+
+impl Shr<u32> for U256 {
+    type Output = Self;
+
+    fn shr(self, rhs: u32) -> Self {
+        if rhs >= 256 {
+            return Self::ZERO;
+        }
+        let limb_shift = (rhs / 64) as usize;
+        let bit_shift = rhs % 64;
+        let mut result = [0u64; 4];
+        for (i, limb) in result.iter_mut().enumerate().take(4 - limb_shift) {
+            *limb = self.0[i + limb_shift] >> bit_shift;
+            if bit_shift > 0 && i + limb_shift < 3 {
+                *limb |= self.0[i + limb_shift + 1] << (64 - bit_shift);
+            }
+        }
+        Self(result)
+    }
+}
+
+impl ShrAssign for U256 {
+    fn shr_assign(&mut self, rhs: Self) {
+        let shift = if rhs.bits() > 32 { u32::MAX } else { rhs.0[0] as u32 };
+        *self = *self >> shift;
+    }
+}
+
+impl BitAnd for U256 {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self {
+        Self([
+            self.0[0] & rhs.0[0],
+            self.0[1] & rhs.0[1],
+            self.0[2] & rhs.0[2],
+            self.0[3] & rhs.0[3],
+        ])
+    }
+}
+
+impl From<u32> for U256 {
+    fn from(value: u32) -> Self {
+        Self([value as u64, 0, 0, 0])
+    }
+}
+
+impl From<u64> for U256 {
+    fn from(value: u64) -> Self {
+        Self([value, 0, 0, 0])
+    }
+}
+
+impl From<u128> for U256 {
+    fn from(value: u128) -> Self {
+        Self([value as u64, (value >> 64) as u64, 0, 0])
+    }
+}
+
+impl Debug for U256 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl Display for U256 {
+    /// Formats this integer as a decimal number.
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+        const DIVISOR: u64 = 10_000_000_000_000_000_000; // 10^19
+
+        // split the value into 19-digit chunks starting with the least significant one
+        let mut chunks = Vec::new();
+        let mut value = *self;
+        loop {
+            let mut rem = 0u128;
+            for limb in value.0.iter_mut().rev() {
+                let cur = (rem << 64) | *limb as u128;
+                *limb = (cur / DIVISOR as u128) as u64;
+                rem = cur % DIVISOR as u128;
+            }
+            chunks.push(rem as u64);
+            if value.is_zero() {
+                break;
+            }
+        }
+
+        let mut result = String::new();
+        for (i, chunk) in chunks.iter().rev().enumerate() {
+            if i == 0 {
+                result.push_str(&format!("{chunk}"));
+            } else {
+                result.push_str(&format!("{chunk:019}"));
+            }
+        }
+        f.pad(&result)
+    }
+}
@@ -33,6 +33,11 @@
 //!   quadratic extension of this field. For higher levels of security, a cubic extension field
 //!   should be used.
 //!
+//! In addition, the [secp256k1](crate::fields::secp256k1) module provides the base field and the
+//! scalar field of the secp256k1 elliptic curve. These fields are not STARK-friendly, and are
+//! intended for native computations over secp256k1 (e.g., when building execution traces for
+//! ECDSA signature verification).
+//!
 //! ## Extension fields
 //!
 //! Currently, the library provides a generic way to create quadratic and cubic extensions of
@@ -103,9 +108,16 @@ pub mod fields {
     //! This module contains concrete implementations of base STARK fields as well as extensions
     //! of these field.
 
-    pub use super::field::{f128, f62, f64, CubeExtension, QuadExtension};
+    pub use super::field::{f128, f62, f64, secp256k1, CubeExtension, QuadExtension};
 }
 
+// CRATE FEATURES
+// ================================================================================================
+
+/// Indicates whether this crate was compiled with the `f64-limbs` feature, i.e., whether
+/// multiplication in the [f64](fields::f64) field relies on `f64` arithmetic.
+pub const F64_LIMBS_ENABLED: bool = cfg!(feature = "f64-limbs");
+
 mod utils;
 pub use crate::utils::{
     add_in_place, batch_inversion, get_power_series, get_power_series_with_offset, mul_acc,
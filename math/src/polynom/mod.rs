@@ -12,6 +12,9 @@
 //! - Synthetic polynomial division for efficient division by polynomials of the form
 //!   `x`^`a` - `b`.
 //!
+//! It also provides a [Polynomial] type which tracks an upper bound on the degree of a
+//! polynomial.
+//!
 //! In the context of this module any slice of field elements is considered to be a polynomial
 //! in reverse coefficient form. A few examples:
 //!
@@ -31,6 +34,9 @@ use utils::group_slice_elements;
 
 use crate::{field::FieldElement, utils::batch_inversion};
 
+mod polynomial;
+pub use polynomial::{Polynomial, PolynomialError};
+
 #[cfg(test)]
 mod tests;
 
@@ -0,0 +1,217 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::vec::Vec;
+use core::{
+    fmt,
+    ops::{Add, Mul, Sub},
+};
+
+use super::{add, degree_of, eval, mul, mul_by_scalar, sub};
+use crate::field::FieldElement;
+
+// POLYNOMIAL
+// ================================================================================================
+
+/// A polynomial in coefficient form together with an upper bound on its degree.
+///
+/// The degree bound is checked when a polynomial is constructed, and is propagated through
+/// arithmetic operations: the bound of a sum (or a difference) of two polynomials is the larger
+/// of their bounds, and the bound of a product is the sum of their bounds. Thus, any polynomial
+/// of this type is guaranteed to have a degree which does not exceed its degree bound.
+///
+/// This can be used to catch errors (e.g., mis-specified constraint degrees) which would
+/// otherwise result in high-degree terms of a polynomial being silently dropped.
+///
+/// # Examples
+/// ```
+/// # use winter_math::polynom::Polynomial;
+/// # use winter_math::{fields::{f128::BaseElement}, FieldElement};
+/// // p(x) = 2 * x + 1
+/// let p = Polynomial::new(vec![BaseElement::new(1), BaseElement::new(2)], 1).unwrap();
+///
+/// // q(x) = x^2
+/// let q = Polynomial::new(vec![BaseElement::ZERO, BaseElement::ZERO, BaseElement::ONE], 2);
+/// let q = q.unwrap();
+///
+/// let r = p * q;
+/// assert_eq!(3, r.degree_bound());
+/// assert_eq!(BaseElement::new(20), r.evaluate_at(BaseElement::new(2)));
+///
+/// // a polynomial of degree 2 cannot have a degree bound of 1
+/// let q = Polynomial::new(vec![BaseElement::ZERO, BaseElement::ZERO, BaseElement::ONE], 1);
+/// assert!(q.is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polynomial<E: FieldElement> {
+    coefficients: Vec<E>,
+    degree_bound: usize,
+}
+
+impl<E: FieldElement> Polynomial<E> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new polynomial instantiated from the provided coefficients (in reverse
+    /// coefficient form) with the specified degree bound.
+    ///
+    /// If there are more than `degree_bound + 1` coefficients, the extra coefficients must all be
+    /// zeros; they are removed from the polynomial.
+    ///
+    /// # Errors
+    /// Returns an error if the degree of the polynomial is greater than `degree_bound`.
+    pub fn new(mut coefficients: Vec<E>, degree_bound: usize) -> Result<Self, PolynomialError> {
+        let degree = degree_of(&coefficients);
+        if degree > degree_bound {
+            return Err(PolynomialError::DegreeBoundExceeded(degree, degree_bound));
+        }
+        coefficients.truncate(degree_bound + 1);
+
+        Ok(Self { coefficients, degree_bound })
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the upper bound on the degree of this polynomial.
+    pub fn degree_bound(&self) -> usize {
+        self.degree_bound
+    }
+
+    /// Returns the actual degree of this polynomial.
+    pub fn degree(&self) -> usize {
+        degree_of(&self.coefficients)
+    }
+
+    /// Returns the coefficients of this polynomial in reverse coefficient form.
+    ///
+    /// The number of returned coefficients is at most `degree_bound + 1`.
+    pub fn coefficients(&self) -> &[E] {
+        &self.coefficients
+    }
+
+    /// Returns the coefficients of this polynomial padded with zeros to `degree_bound + 1`
+    /// elements.
+    pub fn into_coefficients(mut self) -> Vec<E> {
+        self.coefficients.resize(self.degree_bound + 1, E::ZERO);
+        self.coefficients
+    }
+
+    /// Evaluates this polynomial at the specified point.
+    pub fn evaluate_at<F>(&self, x: F) -> F
+    where
+        F: FieldElement + From<E>,
+    {
+        eval(&self.coefficients, x)
+    }
+
+    // TRANSFORMATIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns this polynomial with its degree bound set to the specified value.
+    ///
+    /// # Errors
+    /// Returns an error if the degree of this polynomial is greater than `degree_bound`.
+    pub fn with_degree_bound(self, degree_bound: usize) -> Result<Self, PolynomialError> {
+        Self::new(self.coefficients, degree_bound)
+    }
+
+    /// Splits this polynomial into the specified number of segments such that
+    /// p(x) = p_0(x) + x^k * p_1(x) + ... + x^(k * (n - 1)) * p_{n-1}(x), where n is the number
+    /// of segments and k = (degree_bound + 1) / n. The degree bound of each segment is k - 1.
+    ///
+    /// # Panics
+    /// Panics if `degree_bound + 1` is not divisible by `num_segments`.
+    pub fn split(self, num_segments: usize) -> Vec<Self> {
+        let len = self.degree_bound + 1;
+        assert!(
+            num_segments > 0 && len % num_segments == 0,
+            "cannot split a polynomial with degree bound {} into {} segments",
+            self.degree_bound,
+            num_segments
+        );
+
+        let segment_len = len / num_segments;
+        self.into_coefficients()
+            .chunks(segment_len)
+            .map(|chunk| Self {
+                coefficients: chunk.to_vec(),
+                degree_bound: segment_len - 1,
+            })
+            .collect()
+    }
+
+    /// Returns the product of this polynomial and the provided scalar.
+    pub fn mul_by_scalar(&self, k: E) -> Self {
+        Self {
+            coefficients: mul_by_scalar(&self.coefficients, k),
+            degree_bound: self.degree_bound,
+        }
+    }
+}
+
+// OVERLOADED OPERATORS
+// ================================================================================================
+
+impl<E: FieldElement> Add for Polynomial<E> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self {
+            coefficients: add(&self.coefficients, &rhs.coefficients),
+            degree_bound: self.degree_bound.max(rhs.degree_bound),
+        }
+    }
+}
+
+impl<E: FieldElement> Sub for Polynomial<E> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self {
+            coefficients: sub(&self.coefficients, &rhs.coefficients),
+            degree_bound: self.degree_bound.max(rhs.degree_bound),
+        }
+    }
+}
+
+impl<E: FieldElement> Mul for Polynomial<E> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        let coefficients = if self.coefficients.is_empty() || rhs.coefficients.is_empty() {
+            Vec::new()
+        } else {
+            mul(&self.coefficients, &rhs.coefficients)
+        };
+
+        Self {
+            coefficients,
+            degree_bound: self.degree_bound + rhs.degree_bound,
+        }
+    }
+}
+
+// POLYNOMIAL ERROR
+// ================================================================================================
+
+/// Defines errors which can occur when working with degree-bounded polynomials.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PolynomialError {
+    /// The degree of a polynomial exceeds its degree bound.
+    DegreeBoundExceeded(usize, usize),
+}
+
+impl fmt::Display for PolynomialError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DegreeBoundExceeded(degree, bound) => {
+                write!(f, "polynomial degree {degree} exceeds degree bound {bound}")
+            },
+        }
+    }
+}
+
+impl core::error::Error for PolynomialError {}
@@ -5,7 +5,7 @@
 
 use alloc::vec::Vec;
 
-use super::remove_leading_zeros;
+use super::{remove_leading_zeros, Polynomial, PolynomialError};
 use crate::{
     field::{f128::BaseElement, FieldElement, StarkField},
     utils::get_power_series,
@@ -236,3 +236,54 @@ fn syn_div() {
     let result = super::syn_div(&poly, 4, root.exp(4));
     assert_eq!(poly, remove_leading_zeros(&super::mul(&result, &z_poly)));
 }
+
+#[test]
+fn degree_bounded_polynomial() {
+    let coefficients = (1u128..5).map(BaseElement::new).collect::<Vec<_>>();
+
+    // degree bound must not be smaller than the degree
+    assert_eq!(
+        Err(PolynomialError::DegreeBoundExceeded(3, 2)),
+        Polynomial::new(coefficients.clone(), 2)
+    );
+
+    // extra zero coefficients are removed
+    let mut padded = coefficients.clone();
+    padded.resize(8, BaseElement::ZERO);
+    let p = Polynomial::new(padded, 4).unwrap();
+    assert_eq!(3, p.degree());
+    assert_eq!(4, p.degree_bound());
+    assert_eq!(5, p.coefficients().len());
+
+    // degree bounds are propagated through arithmetic operations
+    let q = Polynomial::new(coefficients[..2].to_vec(), 1).unwrap();
+    assert_eq!(4, (p.clone() + q.clone()).degree_bound());
+    assert_eq!(4, (p.clone() - q.clone()).degree_bound());
+    assert_eq!(5, (p.clone() * q.clone()).degree_bound());
+
+    let x = BaseElement::new(7);
+    assert_eq!(p.evaluate_at(x) * q.evaluate_at(x), (p.clone() * q).evaluate_at(x));
+
+    // the degree bound can be tightened only down to the actual degree
+    assert!(p.clone().with_degree_bound(3).is_ok());
+    assert!(p.with_degree_bound(2).is_err());
+}
+
+#[test]
+fn degree_bounded_polynomial_split() {
+    let coefficients = (1u128..7).map(BaseElement::new).collect::<Vec<_>>();
+    let p = Polynomial::new(coefficients.clone(), 7).unwrap();
+    let x = BaseElement::new(11);
+
+    let segments = p.clone().split(4);
+    assert_eq!(4, segments.len());
+    assert!(segments.iter().all(|s| s.degree_bound() == 1));
+    assert_eq!(coefficients[4..6], segments[2].coefficients()[..]);
+    assert_eq!(vec![BaseElement::ZERO; 2], segments[3].clone().into_coefficients());
+
+    let expected = segments
+        .iter()
+        .enumerate()
+        .fold(BaseElement::ZERO, |acc, (i, s)| acc + x.exp(2 * i as u128) * s.evaluate_at(x));
+    assert_eq!(expected, p.evaluate_at(x));
+}
@@ -0,0 +1,221 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use std::time::Duration;
+
+use air::{
+    Air, AirContext, Assertion, ConstraintExpr, EvaluationFrame, FieldExtension, PartitionOptions,
+    ProofOptions, SymbolicConstraints, TraceInfo,
+};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use crypto::{hashers::Blake3_256, DefaultRandomCoin, MerkleTree, RandomCoin};
+use math::{fields::f64::BaseElement, FieldElement};
+use winter_prover::{
+    matrix::ColMatrix, ConstraintEvaluator, DefaultConstraintEvaluator, DefaultTraceLde,
+    StarkDomain,
+};
+
+type Hasher = Blake3_256<BaseElement>;
+
+const TRACE_LENS: [usize; 2] = [2_usize.pow(12), 2_usize.pow(16)];
+const TRACE_WIDTH: usize = 4;
+const MDS: [[u64; TRACE_WIDTH]; TRACE_WIDTH] =
+    [[2, 3, 1, 1], [1, 2, 3, 1], [1, 1, 2, 3], [3, 1, 1, 2]];
+
+/// Compares evaluation of main transition constraints via a hand-written
+/// [Air::evaluate_transition()], via [Air::evaluate_transition()] which evaluates symbolic
+/// constraints row by row, and via the batched interpreter which the default constraint evaluator
+/// runs when the AIR returns its symbolic constraints.
+fn evaluate_constraints(c: &mut Criterion) {
+    let mut group = c.benchmark_group("constraint_evaluation");
+    group.sample_size(10);
+    group.measurement_time(Duration::from_secs(10));
+
+    for &trace_len in TRACE_LENS.iter() {
+        let trace_info = TraceInfo::new(TRACE_WIDTH, trace_len);
+        let options = ProofOptions::new(32, 8, 0, FieldExtension::None, 4, 31);
+        let mut air = RoundAir::new(trace_info.clone(), (), options);
+
+        let trace = build_trace(trace_len);
+        let domain = StarkDomain::new(&air);
+        let (trace_lde, _) = DefaultTraceLde::<BaseElement, Hasher, MerkleTree<Hasher>>::new(
+            &trace_info,
+            &trace,
+            &domain,
+            PartitionOptions::default(),
+        );
+        let mut public_coin = DefaultRandomCoin::<Hasher>::new(&[BaseElement::ONE]);
+        let coefficients = air.get_constraint_composition_coefficients(&mut public_coin).unwrap();
+
+        for mode in
+            [EvaluationMode::Native, EvaluationMode::SymbolicPerRow, EvaluationMode::Batched]
+        {
+            air.mode = mode;
+            group.bench_function(BenchmarkId::new(mode.name(), trace_len), |bench| {
+                bench.iter(|| {
+                    DefaultConstraintEvaluator::<_, BaseElement>::new(
+                        &air,
+                        None,
+                        coefficients.clone(),
+                    )
+                    .evaluate(&trace_lde, &domain)
+                });
+            });
+        }
+    }
+
+    group.finish();
+}
+
+criterion_group!(constraint_evaluation_group, evaluate_constraints);
+criterion_main!(constraint_evaluation_group);
+
+// TRACE
+// =================================================================================================
+
+/// Builds a trace in which every row is obtained from the previous row by adding a round
+/// constant to each element, raising the results to the power 7, and multiplying them by an MDS
+/// matrix.
+fn build_trace(trace_len: usize) -> ColMatrix<BaseElement> {
+    let round_constants = round_constants();
+    let mut columns = vec![vec![BaseElement::ZERO; trace_len]; TRACE_WIDTH];
+    for (i, column) in columns.iter_mut().enumerate() {
+        column[0] = BaseElement::new(i as u64 + 1);
+    }
+
+    for step in 0..trace_len - 1 {
+        let k = round_constants[step % round_constants.len()];
+        let state: [BaseElement; TRACE_WIDTH] = core::array::from_fn(|i| columns[i][step]);
+        let next = apply_round(&state, k);
+        for (column, value) in columns.iter_mut().zip(next) {
+            column[step + 1] = value;
+        }
+    }
+
+    ColMatrix::new(columns)
+}
+
+fn round_constants() -> Vec<BaseElement> {
+    (0..8_u64).map(|i| BaseElement::new(i * i + 7)).collect()
+}
+
+fn apply_round<E: FieldElement<BaseField = BaseElement>>(
+    state: &[E; TRACE_WIDTH],
+    k: E,
+) -> [E; TRACE_WIDTH] {
+    let sbox = state.map(|x| {
+        let x = x + k;
+        let x2 = x.square();
+        x2.square() * x2 * x
+    });
+    MDS.map(|row| {
+        row.iter()
+            .zip(sbox)
+            .fold(E::ZERO, |acc, (&m, s)| acc + E::from(BaseElement::new(m)) * s)
+    })
+}
+
+// AIR
+// =================================================================================================
+
+#[derive(Clone, Copy)]
+enum EvaluationMode {
+    /// Constraints are evaluated by hand-written code in [Air::evaluate_transition()].
+    Native,
+    /// Symbolic constraints are evaluated row by row in [Air::evaluate_transition()].
+    SymbolicPerRow,
+    /// Symbolic constraints are returned to the prover, which evaluates them over batches of
+    /// rows.
+    Batched,
+}
+
+impl EvaluationMode {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Native => "evaluate_transition",
+            Self::SymbolicPerRow => "symbolic_per_row",
+            Self::Batched => "batched_interpreter",
+        }
+    }
+}
+
+struct RoundAir {
+    context: AirContext<BaseElement>,
+    constraints: SymbolicConstraints<BaseElement>,
+    mode: EvaluationMode,
+}
+
+impl Air for RoundAir {
+    type BaseField = BaseElement;
+    type GkrProof = ();
+    type GkrVerifier = ();
+
+    type PublicInputs = ();
+
+    fn new(trace_info: TraceInfo, _pub_inputs: Self::PublicInputs, options: ProofOptions) -> Self {
+        let k = ConstraintExpr::periodic(0);
+        let sbox: Vec<_> =
+            (0..TRACE_WIDTH).map(|i| (ConstraintExpr::current(i) + &k).exp(7)).collect();
+
+        let mut constraints = SymbolicConstraints::new();
+        for (i, row) in MDS.iter().enumerate() {
+            let mut expected = ConstraintExpr::constant(BaseElement::ZERO);
+            for (&m, s) in row.iter().zip(sbox.iter()) {
+                expected = expected + s * BaseElement::new(m);
+            }
+            let node = constraints.insert(&(ConstraintExpr::next(i) - expected));
+            constraints.enforce(node);
+        }
+
+        let context = AirContext::from_symbolic_constraints(
+            trace_info,
+            &constraints,
+            &[round_constants().len()],
+            1,
+            options,
+        );
+        Self {
+            context,
+            constraints,
+            mode: EvaluationMode::Native,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        if let EvaluationMode::Native = self.mode {
+            let state: [E; TRACE_WIDTH] = frame.current().try_into().unwrap();
+            let expected = apply_round(&state, periodic_values[0]);
+            for ((result, &next), expected) in result.iter_mut().zip(frame.next()).zip(expected) {
+                *result = next - expected;
+            }
+        } else {
+            self.constraints.evaluate(frame, periodic_values, result);
+        }
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        vec![Assertion::single(0, 0, BaseElement::ONE)]
+    }
+
+    fn get_periodic_column_values(&self) -> Vec<Vec<Self::BaseField>> {
+        vec![round_constants()]
+    }
+
+    fn get_symbolic_constraints(&self) -> Option<&SymbolicConstraints<Self::BaseField>> {
+        match self.mode {
+            EvaluationMode::Batched => Some(&self.constraints),
+            _ => None,
+        }
+    }
+}
@@ -3,7 +3,7 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use std::time::Duration;
+use std::{borrow::Cow, time::Duration};
 
 use air::{
     Air, AirContext, Assertion, AuxRandElements, ConstraintCompositionCoefficients,
@@ -16,7 +16,7 @@ use math::{fields::f64::BaseElement, ExtensionOf, FieldElement};
 use winter_prover::{
     matrix::ColMatrix, CompositionPoly, CompositionPolyTrace, DefaultConstraintCommitment,
     DefaultConstraintEvaluator, DefaultTraceLde, Prover, ProverGkrProof, StarkDomain, Trace,
-    TracePolyTable,
+    TraceLdeStorage, TracePolyTable,
 };
 
 const TRACE_LENS: [usize; 2] = [2_usize.pow(16), 2_usize.pow(20)];
@@ -78,8 +78,8 @@ impl Trace for LagrangeTrace {
         &self.info
     }
 
-    fn main_segment(&self) -> &ColMatrix<Self::BaseField> {
-        &self.main_trace
+    fn main_segment(&self) -> Cow<'_, ColMatrix<Self::BaseField>> {
+        Cow::Borrowed(&self.main_trace)
     }
 
     fn read_main_frame(&self, row_idx: usize, frame: &mut air::EvaluationFrame<Self::BaseField>) {
@@ -185,6 +185,8 @@ impl Prover for LagrangeProver {
     type Trace = LagrangeTrace;
     type HashFn = Blake3_256<BaseElement>;
     type VC = MerkleTree<Blake3_256<BaseElement>>;
+    type FriHashFn = Self::HashFn;
+    type FriVC = Self::VC;
     type RandomCoin = DefaultRandomCoin<Self::HashFn>;
     type TraceLde<E: FieldElement<BaseField = BaseElement>> =
         DefaultTraceLde<E, Self::HashFn, Self::VC>;
@@ -206,11 +208,12 @@ impl Prover for LagrangeProver {
         main_trace: &ColMatrix<Self::BaseField>,
         domain: &StarkDomain<Self::BaseField>,
         partition_option: PartitionOptions,
+        storage: TraceLdeStorage,
     ) -> (Self::TraceLde<E>, TracePolyTable<E>)
     where
         E: math::FieldElement<BaseField = Self::BaseField>,
     {
-        DefaultTraceLde::new(trace_info, main_trace, domain, partition_option)
+        DefaultTraceLde::with_storage(trace_info, main_trace, domain, partition_option, storage)
     }
 
     fn build_constraint_commitment<E: FieldElement<BaseField = Self::BaseField>>(
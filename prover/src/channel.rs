@@ -8,23 +8,28 @@ use core::marker::PhantomData;
 
 use air::{
     proof::{Commitments, Context, OodFrame, Proof, Queries, TraceOodFrame},
-    Air, ConstraintCompositionCoefficients, DeepCompositionCoefficients,
+    Air, ConstraintCompositionCoefficients, DeepCompositionCoefficients, LookupTable,
 };
-use crypto::{ElementHasher, RandomCoin, VectorCommitment};
+use crypto::{transcript_labels, ElementHasher, RandomCoin, VectorCommitment};
 use fri::FriProof;
 use math::{FieldElement, ToElements};
 #[cfg(feature = "concurrent")]
 use utils::iterators::*;
+use utils::Serializable;
 
 // TYPES AND INTERFACES
 // ================================================================================================
 
-pub struct ProverChannel<'a, A, E, H, R, V>
+pub struct ProverChannel<'a, A, E, H, FH, R, V>
 where
     A: Air,
     E: FieldElement<BaseField = A::BaseField>,
     H: ElementHasher<BaseField = A::BaseField>,
-    R: RandomCoin<BaseField = E::BaseField, Hasher = H>,
+    FH: ElementHasher<BaseField = A::BaseField>,
+    R: RandomCoin<
+        BaseField = E::BaseField,
+        Hasher: ElementHasher<Digest: From<H::Digest> + From<FH::Digest>>,
+    >,
     V: VectorCommitment<H>,
 {
     air: &'a A,
@@ -34,25 +39,46 @@ where
     ood_frame: OodFrame,
     pow_nonce: u64,
     _field_element: PhantomData<E>,
+    _hasher: PhantomData<H>,
+    _fri_hasher: PhantomData<FH>,
     _vector_commitment: PhantomData<V>,
 }
 
 // PROVER CHANNEL IMPLEMENTATION
 // ================================================================================================
 
-impl<'a, A, E, H, R, V> ProverChannel<'a, A, E, H, R, V>
+impl<'a, A, E, H, FH, R, V> ProverChannel<'a, A, E, H, FH, R, V>
 where
     A: Air,
     E: FieldElement<BaseField = A::BaseField>,
     H: ElementHasher<BaseField = A::BaseField>,
-    R: RandomCoin<BaseField = A::BaseField, Hasher = H>,
+    FH: ElementHasher<BaseField = A::BaseField>,
+    R: RandomCoin<
+        BaseField = A::BaseField,
+        Hasher: ElementHasher<Digest: From<H::Digest> + From<FH::Digest>>,
+    >,
     V: VectorCommitment<H>,
 {
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
     /// Creates a new prover channel for the specified `air` and public inputs.
-    pub fn new(air: &'a A, mut pub_inputs_elements: Vec<A::BaseField>) -> Self {
-        let context = Context::new::<A::BaseField>(air.trace_info().clone(), air.options().clone());
+    ///
+    /// The public coin of the channel is instantiated via `new_public_coin` from a seed
+    /// consisting of the proof context and the public inputs.
+    pub fn new(
+        air: &'a A,
+        mut pub_inputs_elements: Vec<A::BaseField>,
+        new_public_coin: impl FnOnce(&[A::BaseField]) -> R,
+    ) -> Self {
+        // commit to lookup tables (if any) so that the verifier can check that the proof was
+        // generated against the tables defined by the AIR
+        let lookup_tables = air.get_lookup_tables();
+        let lookup_table_commitment = (!lookup_tables.is_empty())
+            .then(|| LookupTable::commitment::<H>(lookup_tables).to_bytes());
+
+        let context = Context::new::<A::BaseField>(air.trace_info().clone(), air.options().clone())
+            .with_air_id(A::ID)
+            .with_lookup_table_commitment(lookup_table_commitment);
 
         // build a seed for the public coin; the initial seed is a hash of the proof context and
         // the public inputs, but as the protocol progresses, the coin will be reseeded with the
@@ -62,12 +88,14 @@ where
 
         ProverChannel {
             air,
-            public_coin: RandomCoin::new(&coin_seed_elements),
+            public_coin: new_public_coin(&coin_seed_elements),
             context,
             commitments: Commitments::default(),
             ood_frame: OodFrame::default(),
             pow_nonce: 0,
             _field_element: PhantomData,
+            _hasher: PhantomData,
+            _fri_hasher: PhantomData,
             _vector_commitment: PhantomData,
         }
     }
@@ -75,30 +103,43 @@ where
     // COMMITMENT METHODS
     // --------------------------------------------------------------------------------------------
 
-    /// Commits the prover the extended execution trace.
-    pub fn commit_trace(&mut self, trace_root: H::Digest) {
+    /// Commits the prover to the extended main trace segment.
+    pub fn commit_main_trace(&mut self, trace_root: H::Digest) {
         self.commitments.add::<H>(&trace_root);
-        self.public_coin.reseed(trace_root);
+        self.public_coin
+            .reseed_with_label(transcript_labels::MAIN_TRACE_ROOT, trace_root.into());
+    }
+
+    /// Commits the prover to the extended auxiliary trace segment.
+    pub fn commit_aux_trace(&mut self, trace_root: H::Digest) {
+        self.commitments.add::<H>(&trace_root);
+        self.public_coin
+            .reseed_with_label(transcript_labels::AUX_TRACE_ROOT, trace_root.into());
     }
 
     /// Commits the prover to the evaluations of the constraint composition polynomial.
     pub fn commit_constraints(&mut self, constraint_root: H::Digest) {
         self.commitments.add::<H>(&constraint_root);
-        self.public_coin.reseed(constraint_root);
+        self.public_coin
+            .reseed_with_label(transcript_labels::CONSTRAINT_ROOT, constraint_root.into());
     }
 
     /// Saves the evaluations of trace polynomials over the out-of-domain evaluation frame. This
     /// also reseeds the public coin with the hashes of the evaluation frame states.
     pub fn send_ood_trace_states(&mut self, trace_ood_frame: &TraceOodFrame<E>) {
-        let trace_states_hash = self.ood_frame.set_trace_states::<E, H>(trace_ood_frame);
-        self.public_coin.reseed(trace_states_hash);
+        let trace_states_hash = self.ood_frame.set_trace_states::<E, R::Hasher>(trace_ood_frame);
+        self.public_coin
+            .reseed_with_label(transcript_labels::OOD_TRACE_FRAME, trace_states_hash);
     }
 
     /// Saves the evaluations of constraint composition polynomial columns at the out-of-domain
     /// point. This also reseeds the public coin wit the hash of the evaluations.
     pub fn send_ood_constraint_evaluations(&mut self, evaluations: &[E]) {
         self.ood_frame.set_constraint_evaluations(evaluations);
-        self.public_coin.reseed(H::hash_elements(evaluations));
+        self.public_coin.reseed_with_label(
+            transcript_labels::OOD_CONSTRAINT_EVALUATIONS,
+            R::Hasher::hash_elements(evaluations),
+        );
     }
 
     // PUBLIC COIN METHODS
@@ -135,14 +176,12 @@ where
     /// Returns a set of positions in the LDE domain against which the evaluations of trace and
     /// constraint composition polynomials should be queried.
     ///
-    /// The positions are drawn from the public coin uniformly at random. Duplicate positions
-    /// are removed from the returned vector.
+    /// The positions are drawn from the public coin uniformly at random (see
+    /// [Air::get_query_positions()]). Duplicate positions are removed from the returned vector.
     pub fn get_query_positions(&mut self) -> Vec<usize> {
-        let num_queries = self.context.options().num_queries();
-        let lde_domain_size = self.context.lde_domain_size();
         let mut positions = self
-            .public_coin
-            .draw_integers(num_queries, lde_domain_size, self.pow_nonce)
+            .air
+            .get_query_positions(&mut self.public_coin, self.pow_nonce)
             .expect("failed to draw query position");
 
         // remove any duplicate positions from the list
@@ -155,6 +194,8 @@ where
     /// Determines a nonce, which when hashed with the current seed of the public coin results
     /// in a new seed with the number of leading zeros equal to the grinding_factor specified
     /// in the proof options.
+    ///
+    /// In canonical proving mode, the smallest such nonce is always selected.
     pub fn grind_query_seed(&mut self) {
         let grinding_factor = self.context.options().grinding_factor();
 
@@ -163,11 +204,19 @@ where
             .find(|&nonce| self.public_coin.check_leading_zeros(nonce) >= grinding_factor)
             .expect("nonce not found");
 
+        // in canonical mode, use find_first() to get the same nonce as a sequential search would
+        // find; otherwise, any of the threads may return the first nonce it finds
         #[cfg(feature = "concurrent")]
-        let nonce = (1..u64::MAX)
-            .into_par_iter()
-            .find_any(|&nonce| self.public_coin.check_leading_zeros(nonce) >= grinding_factor)
-            .expect("nonce not found");
+        let nonce = if self.context.options().is_canonical() {
+            (1..u64::MAX)
+                .into_par_iter()
+                .find_first(|&nonce| self.public_coin.check_leading_zeros(nonce) >= grinding_factor)
+        } else {
+            (1..u64::MAX)
+                .into_par_iter()
+                .find_any(|&nonce| self.public_coin.check_leading_zeros(nonce) >= grinding_factor)
+        }
+        .expect("nonce not found");
 
         self.pow_nonce = nonce;
     }
@@ -203,24 +252,42 @@ where
 // FRI PROVER CHANNEL IMPLEMENTATION
 // ================================================================================================
 
-impl<A, E, H, R, V> fri::ProverChannel<E> for ProverChannel<'_, A, E, H, R, V>
+impl<A, E, H, FH, R, V> fri::ProverChannel<E> for ProverChannel<'_, A, E, H, FH, R, V>
 where
     A: Air,
     E: FieldElement<BaseField = A::BaseField>,
     H: ElementHasher<BaseField = A::BaseField>,
-    R: RandomCoin<BaseField = A::BaseField, Hasher = H>,
+    FH: ElementHasher<BaseField = A::BaseField>,
+    R: RandomCoin<
+        BaseField = A::BaseField,
+        Hasher: ElementHasher<Digest: From<H::Digest> + From<FH::Digest>>,
+    >,
     V: VectorCommitment<H>,
 {
-    type Hasher = H;
+    type Hasher = FH;
 
     /// Commits the prover to a FRI layer.
-    fn commit_fri_layer(&mut self, layer_root: H::Digest) {
-        self.commitments.add::<H>(&layer_root);
-        self.public_coin.reseed(layer_root);
+    fn commit_fri_layer(&mut self, layer_root: FH::Digest) {
+        self.commitments.add::<FH>(&layer_root);
+        self.public_coin
+            .reseed_with_label(transcript_labels::FRI_LAYER_ROOT, layer_root.into());
     }
 
     /// Returns a new alpha drawn from the public coin.
     fn draw_fri_alpha(&mut self) -> E {
         self.public_coin.draw().expect("failed to draw FRI alpha")
     }
+
+    /// Returns a new DEEP folding point drawn from the public coin.
+    fn draw_fri_deep_point(&mut self) -> E {
+        self.public_coin.draw().expect("failed to draw FRI DEEP point")
+    }
+
+    /// Reseeds the public coin with the hash of the FRI layer evaluations at a DEEP coset.
+    fn commit_fri_deep_evaluations(&mut self, evaluations: &[E]) {
+        self.public_coin.reseed_with_label(
+            transcript_labels::FRI_DEEP_EVALUATIONS,
+            R::Hasher::hash_elements(evaluations),
+        );
+    }
 }
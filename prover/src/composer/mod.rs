@@ -54,7 +54,8 @@ impl<E: FieldElement> DeepCompositionPoly<E> {
     ///
     /// - Compute polynomials T'_i(x) = (T_i(x) - T_i(z)) / (x - z) and
     ///   T''_i(x) = (T_i(x) - T_i(z * g)) / (x - z * g) for all i, where T_i(x) is a trace
-    ///   polynomial for column i.
+    ///   polynomial for column i. When evaluation frames contain rows at other offsets, similar
+    ///   polynomials are computed for all points z * g^d, where d is a frame row offset.
     /// - Then, combine together all T'_i(x) and T''_i(x) polynomials using a random linear
     ///   combination as T(x) = sum((T'_i(x) + T''_i(x)) * cc_i) for all i, where cc_i is
     ///   the coefficient for the random linear combination drawn from the public coin.
@@ -68,8 +69,8 @@ impl<E: FieldElement> DeepCompositionPoly<E> {
     ///    ${(a, T_l(a)): a \in S}$.
     /// 4. $Z_S(X)$ is the polynomial of minimal degree vanishing over the set $S$.
     ///
-    /// Note that evaluations of T_i(z) and T_i(z * g) are passed in via the `ood_trace_state`
-    /// parameter.
+    /// Note that evaluations of T_i(z * g^d) for all frame row offsets d (i.e., T_i(z), T_i(z * g)
+    /// by default) are passed in via the `ood_trace_state` parameter.
     /// If a Lagrange kernel is present, the evaluations of $T_l$ over the set $S$ are provided
     /// separately via `ood_trace_state`.
     pub fn add_trace_polys(
@@ -79,15 +80,20 @@ impl<E: FieldElement> DeepCompositionPoly<E> {
     ) {
         assert!(self.coefficients.is_empty());
 
-        // compute a second out-of-domain point offset from z by exactly trace generator; this
-        // point defines the "next" computation state in relation to point z
+        // compute out-of-domain points offset from z by powers of the trace generator given by
+        // frame row offsets; these points define the "next" computation states in relation to
+        // point z
         let trace_length = trace_polys.poly_size();
-        let g = E::from(E::BaseField::get_root_of_unity(trace_length.ilog2()));
-        let next_z = self.z * g;
+        let g = E::BaseField::get_root_of_unity(trace_length.ilog2());
+        let ood_points: Vec<E> = ood_trace_states
+            .frame_offsets()
+            .iter()
+            .map(|&offset| self.z.mul_base(g.exp((offset as u64).into())))
+            .collect();
 
-        // combine trace polynomials into 2 composition polynomials T'(x) and T''(x)
-        let mut t1_composition = vec![E::ZERO; trace_length];
-        let mut t2_composition = vec![E::ZERO; trace_length];
+        // combine trace polynomials into one composition polynomial per out-of-domain point,
+        // i.e., T'(x), T''(x) etc.
+        let mut t_compositions = vec![vec![E::ZERO; trace_length]; ood_points.len()];
 
         // index of a trace polynomial; we declare it here so that we can maintain index continuity
         // across all trace segments
@@ -95,55 +101,40 @@ impl<E: FieldElement> DeepCompositionPoly<E> {
 
         // --- merge polynomials of the main trace segment ----------------------------------------
         for poly in trace_polys.main_trace_polys() {
-            // compute T'(x) = T(x) - T(z), multiply it by a pseudo-random coefficient,
-            // and add the result into composition polynomial
-            acc_trace_poly::<E::BaseField, E>(
-                &mut t1_composition,
-                poly,
-                ood_trace_states.current_row()[i],
-                self.cc.trace[i],
-            );
-
-            // compute T''(x) = T(x) - T(z * g), multiply it by a pseudo-random coefficient,
-            // and add the result into composition polynomial
-            acc_trace_poly::<E::BaseField, E>(
-                &mut t2_composition,
-                poly,
-                ood_trace_states.next_row()[i],
-                self.cc.trace[i],
-            );
+            // compute T(x) - T(z * g^j) for each out-of-domain point, multiply it by a
+            // pseudo-random coefficient, and add the result into composition polynomial
+            for (j, t_composition) in t_compositions.iter_mut().enumerate() {
+                acc_trace_poly::<E::BaseField, E>(
+                    t_composition,
+                    poly,
+                    ood_trace_states.row(j)[i],
+                    self.cc.trace[i],
+                );
+            }
 
             i += 1;
         }
 
         // --- merge polynomials of the auxiliary trace segment ----------------------------------
         for poly in trace_polys.aux_trace_polys() {
-            // compute T'(x) = T(x) - T(z), multiply it by a pseudo-random coefficient,
-            // and add the result into composition polynomial
-            acc_trace_poly::<E, E>(
-                &mut t1_composition,
-                poly,
-                ood_trace_states.current_row()[i],
-                self.cc.trace[i],
-            );
-
-            // compute T''(x) = T(x) - T(z * g), multiply it by a pseudo-random coefficient,
-            // and add the result into composition polynomial
-            acc_trace_poly::<E, E>(
-                &mut t2_composition,
-                poly,
-                ood_trace_states.next_row()[i],
-                self.cc.trace[i],
-            );
+            // compute T(x) - T(z * g^j) for each out-of-domain point, multiply it by a
+            // pseudo-random coefficient, and add the result into composition polynomial
+            for (j, t_composition) in t_compositions.iter_mut().enumerate() {
+                acc_trace_poly::<E, E>(
+                    t_composition,
+                    poly,
+                    ood_trace_states.row(j)[i],
+                    self.cc.trace[i],
+                );
+            }
 
             i += 1;
         }
 
-        // divide the composition polynomials by (x - z) and (x - z * g), respectively,
-        // and add the resulting polynomials together; the output of this step
-        // is a single trace polynomial T(x) and deg(T(x)) = trace_length - 2.
-        let mut trace_poly =
-            merge_trace_compositions(vec![t1_composition, t2_composition], vec![self.z, next_z]);
+        // divide the composition polynomials by (x - z), (x - z * g) etc., respectively, and add
+        // the resulting polynomials together; the output of this step is a single trace
+        // polynomial T(x) and deg(T(x)) = trace_length - 2.
+        let mut trace_poly = merge_trace_compositions(t_compositions, ood_points);
 
         // finally compose the final term associated to the Lagrange kernel trace polynomial if
         // there is one present.
@@ -6,7 +6,10 @@
 use alloc::vec::Vec;
 use core::marker::PhantomData;
 
-use air::{proof::Queries, PartitionOptions};
+use air::{
+    proof::{BatchOpening, Queries, QueryOpening},
+    PartitionOptions,
+};
 use crypto::{ElementHasher, VectorCommitment};
 use math::FieldElement;
 use tracing::info_span;
@@ -23,21 +26,26 @@ use crate::{CompositionPoly, CompositionPolyTrace, StarkDomain, DEFAULT_SEGMENT_
 /// * Evaluations of composition polynomial columns over the LDE domain.
 /// * Vector commitment where each vector element corresponds to the digest of a row in
 ///   the composition polynomial evaluation matrix.
+///
+/// Evaluations are opened at query positions using query opening scheme `O`.
 pub struct DefaultConstraintCommitment<
     E: FieldElement,
     H: ElementHasher<BaseField = E::BaseField>,
     V: VectorCommitment<H>,
+    O: QueryOpening<H, V> = BatchOpening,
 > {
     evaluations: RowMatrix<E>,
     vector_commitment: V,
     _h: PhantomData<H>,
+    _o: PhantomData<O>,
 }
 
-impl<E, H, V> DefaultConstraintCommitment<E, H, V>
+impl<E, H, V, O> DefaultConstraintCommitment<E, H, V, O>
 where
     E: FieldElement,
     H: ElementHasher<BaseField = E::BaseField>,
     V: VectorCommitment<H>,
+    O: QueryOpening<H, V>,
 {
     /// Creates a new constraint evaluation commitment from the provided composition polynomial
     /// evaluations and the corresponding vector commitment.
@@ -66,17 +74,19 @@ where
             evaluations,
             vector_commitment: commitment,
             _h: PhantomData,
+            _o: PhantomData,
         };
 
         (commitment, composition_poly)
     }
 }
 
-impl<E, H, V> ConstraintCommitment<E> for DefaultConstraintCommitment<E, H, V>
+impl<E, H, V, O> ConstraintCommitment<E> for DefaultConstraintCommitment<E, H, V, O>
 where
     E: FieldElement,
     H: ElementHasher<BaseField = E::BaseField> + core::marker::Sync,
     V: VectorCommitment<H> + core::marker::Sync,
+    O: QueryOpening<H, V>,
 {
     type HashFn = H;
     type VC = V;
@@ -86,15 +96,9 @@ where
         self.vector_commitment.commitment()
     }
 
-    /// Returns constraint evaluations at the specified positions along with a batch opening proof
+    /// Returns constraint evaluations at the specified positions along with an opening proof
     /// against the vector commitment.
     fn query(self, positions: &[usize]) -> Queries {
-        // build batch opening proof to the leaves specified by positions
-        let opening_proof = self
-            .vector_commitment
-            .open_many(positions)
-            .expect("failed to generate a batch opening proof for constraint queries");
-
         // determine a set of evaluations corresponding to each position
         let mut evaluations = Vec::new();
         for &position in positions {
@@ -102,7 +106,8 @@ where
             evaluations.push(row);
         }
 
-        Queries::new::<H, E, V>(opening_proof.1, evaluations)
+        // open the vector commitment at the specified positions
+        O::open(&self.vector_commitment, positions, evaluations)
     }
 }
 
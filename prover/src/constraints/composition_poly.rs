@@ -5,7 +5,7 @@
 
 use alloc::vec::Vec;
 
-use math::{fft, polynom::degree_of, FieldElement};
+use math::{fft, polynom::Polynomial, FieldElement};
 
 use super::{ColMatrix, StarkDomain};
 
@@ -13,7 +13,10 @@ use super::{ColMatrix, StarkDomain};
 // ================================================================================================
 
 /// Represents merged evaluations of all constraint evaluations.
-pub struct CompositionPolyTrace<E>(Vec<E>);
+pub struct CompositionPolyTrace<E> {
+    evaluations: Vec<E>,
+    mask: Option<CompositionMask<E>>,
+}
 
 impl<E: FieldElement> CompositionPolyTrace<E> {
     /// Returns a new instance of [CompositionPolyTrace] instantiated from the provided evaluations.
@@ -27,17 +30,71 @@ impl<E: FieldElement> CompositionPolyTrace<E> {
             evaluations.len(),
         );
 
-        Self(evaluations)
+        Self { evaluations, mask: None }
+    }
+
+    /// Attaches random polynomials which mask the constraint composition polynomial built from
+    /// this trace; this is used in zero-knowledge mode.
+    pub(crate) fn with_mask(mut self, mask: CompositionMask<E>) -> Self {
+        self.mask = Some(mask);
+        self
     }
 
     /// Returns the number of evaluations in this trace.
     pub fn num_rows(&self) -> usize {
-        self.0.len()
+        self.evaluations.len()
     }
 
     /// Returns the internal vector representing this trace.
     pub fn into_inner(self) -> Vec<E> {
-        self.0
+        self.evaluations
+    }
+}
+
+// CONSTRAINT COMPOSITION POLYNOMIAL MASK
+// ================================================================================================
+
+/// Random polynomials which mask a constraint composition polynomial in zero-knowledge mode.
+///
+/// The composition polynomial $H(x)$ is split into $k$ segments $H_i(x)$ of $m$ coefficients
+/// each, where $m$ is smaller than the trace length $n$. Each segment is then masked as
+/// $H_i'(x) = H_i(x) + s_i(x) - x^m \cdot s_{i + 1}(x)$, where $s_1, ..., s_{k - 1}$ are random
+/// polynomials of $n - m$ coefficients and $s_0 = s_k = 0$, so that
+/// $H(x) = \sum_{i=0}^{k-1} x^{i \cdot m} \cdot H_i'(x)$ still holds. Additionally, a random
+/// polynomial of degree $n - 1$ is appended as the last column; it masks evaluations of the
+/// DEEP composition polynomial revealed during the low-degree test.
+pub(crate) struct CompositionMask<E> {
+    segment_len: usize,
+    masks: Vec<Vec<E>>,
+    randomizer: Vec<E>,
+}
+
+impl<E: FieldElement> CompositionMask<E> {
+    /// Returns a new mask for composition polynomial segments of `segment_len` coefficients.
+    pub fn new(segment_len: usize, masks: Vec<Vec<E>>, randomizer: Vec<E>) -> Self {
+        debug_assert!(masks.iter().all(|mask| segment_len + mask.len() == randomizer.len()));
+        Self { segment_len, masks, randomizer }
+    }
+
+    /// Splits the composition polynomial into masked segments and appends the randomizer
+    /// polynomial; the returned columns have `trace_len` coefficients each.
+    fn apply(self, coefficients: Vec<E>, trace_len: usize) -> Vec<Vec<E>> {
+        let num_segments = self.masks.len() + 1;
+        let mut columns = segment(coefficients, self.segment_len, num_segments);
+        for (i, column) in columns.iter_mut().enumerate() {
+            column.resize(trace_len, E::ZERO);
+            if i > 0 {
+                column.iter_mut().zip(&self.masks[i - 1]).for_each(|(c, &s)| *c += s);
+            }
+            if i < self.masks.len() {
+                column[self.segment_len..]
+                    .iter_mut()
+                    .zip(&self.masks[i])
+                    .for_each(|(c, &s)| *c -= s);
+            }
+        }
+        columns.push(self.randomizer);
+        columns
     }
 }
 
@@ -63,14 +120,21 @@ impl<E: FieldElement> CompositionPoly<E> {
             "trace length must be smaller than length of composition polynomial trace"
         );
 
-        let mut trace = composition_trace.into_inner();
+        let CompositionPolyTrace { evaluations: mut trace, mask } = composition_trace;
 
         // at this point, combined_poly contains evaluations of the combined constraint polynomial;
         // we interpolate this polynomial to transform it into coefficient form.
         let inv_twiddles = fft::get_inv_twiddles::<E::BaseField>(trace.len());
         fft::interpolate_poly_with_offset(&mut trace, &inv_twiddles, domain.offset());
 
-        let polys = segment(trace, domain.trace_length(), num_cols);
+        // in zero-knowledge mode, the last column holds the randomizer polynomial
+        let polys = match mask {
+            Some(mask) => {
+                assert_eq!(mask.masks.len() + 2, num_cols, "invalid composition polynomial mask");
+                mask.apply(trace, domain.trace_length())
+            },
+            None => segment(trace, domain.trace_length(), num_cols),
+        };
 
         CompositionPoly { data: ColMatrix::new(polys) }
     }
@@ -118,18 +182,25 @@ impl<E: FieldElement> CompositionPoly<E> {
 /// in such a way that each resulting column has the same degree. For example, a polynomial
 /// a * x^3 + b * x^2 + c * x + d, can be rewritten as: (c * x + d) + x^2 * (a * x + b), and then
 /// the two columns will be: (c * x + d) and (a * x + b).
+///
+/// # Panics
+/// Panics if the degree of the polynomial is greater than or equal to `trace_len * num_cols`; in
+/// this case, the polynomial cannot be split into the specified number of columns without losing
+/// some of its coefficients. This usually means that transition constraint degrees were not
+/// specified correctly.
 fn segment<E: FieldElement>(
     coefficients: Vec<E>,
     trace_len: usize,
     num_cols: usize,
 ) -> Vec<Vec<E>> {
-    debug_assert!(degree_of(&coefficients) < trace_len * num_cols);
-
-    coefficients
-        .chunks(trace_len)
-        .take(num_cols)
-        .map(|slice| slice.to_vec())
-        .collect()
+    let poly = Polynomial::new(coefficients, trace_len * num_cols - 1).unwrap_or_else(|err| {
+        panic!(
+            "composition polynomial cannot be split into {num_cols} columns: {err}; this \
+            usually means that transition constraint degrees were not specified correctly"
+        )
+    });
+
+    poly.split(num_cols).into_iter().map(Polynomial::into_coefficients).collect()
 }
 
 // TESTS
@@ -140,7 +211,9 @@ mod tests {
 
     use alloc::vec::Vec;
 
-    use math::fields::f128::BaseElement;
+    use math::{fields::f128::BaseElement, polynom, FieldElement};
+
+    use super::CompositionMask;
 
     #[test]
     fn segment() {
@@ -157,4 +230,32 @@ mod tests {
 
         assert_eq!(expected, actual)
     }
+
+    #[test]
+    #[should_panic(expected = "composition polynomial cannot be split into 2 columns")]
+    fn segment_degree_too_high() {
+        let values = (1u128..10).map(BaseElement::new).collect::<Vec<_>>();
+        super::segment(values, 4, 2);
+    }
+
+    #[test]
+    fn segment_with_mask() {
+        let values = (0u128..8).map(BaseElement::new).collect::<Vec<_>>();
+        let masks = vec![vec![BaseElement::new(100)], vec![BaseElement::new(200)]];
+        let randomizer = (300u128..304).map(BaseElement::new).collect::<Vec<_>>();
+        let mask = CompositionMask::new(3, masks, randomizer.clone());
+        let columns = mask.apply(values.clone(), 4);
+
+        // the last column holds the randomizer polynomial
+        assert_eq!(4, columns.len());
+        assert_eq!(randomizer, columns[3]);
+
+        // the masked segments still recombine into the original polynomial
+        let x = BaseElement::new(7);
+        let expected = polynom::eval(&values, x);
+        let actual = columns[..3].iter().enumerate().fold(BaseElement::ZERO, |acc, (i, column)| {
+            acc + x.exp((3 * i) as u128) * polynom::eval(column, x)
+        });
+        assert_eq!(expected, actual);
+    }
 }
@@ -3,6 +3,8 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+#[cfg(debug_assertions)]
+use alloc::string::String;
 use alloc::vec::Vec;
 
 #[cfg(debug_assertions)]
@@ -12,7 +14,7 @@ use math::fft;
 use math::{batch_inversion, FieldElement, StarkField};
 #[cfg(feature = "concurrent")]
 use utils::iterators::*;
-use utils::{batch_iter_mut, iter_mut, uninit_vector};
+use utils::{batch_iter_mut, iter_mut, uninit_vector, BufferPool};
 
 use super::{ConstraintDivisor, StarkDomain};
 
@@ -24,35 +26,50 @@ const MIN_FRAGMENT_SIZE: usize = 16;
 // CONSTRAINT EVALUATION TABLE
 // ================================================================================================
 
+/// A table of merged constraint evaluations over the constraint evaluation domain.
+///
+/// The table has one column per distinct transition constraint divisor, each containing merged
+/// evaluations of transition constraints which share this divisor, followed by a single column
+/// which contains merged evaluations of boundary constraints (already divided by their respective
+/// divisors).
 pub struct ConstraintEvaluationTable<'a, E: FieldElement> {
     evaluations: Vec<Vec<E>>,
-    divisors: Vec<ConstraintDivisor<E::BaseField>>,
+    transition_divisors: Vec<ConstraintDivisor<E::BaseField>>,
     domain: &'a StarkDomain<E::BaseField>,
+    buffer_pool: Option<&'a BufferPool<E::BaseField>>,
 
     #[cfg(debug_assertions)]
     main_transition_evaluations: Vec<Vec<E::BaseField>>,
     #[cfg(debug_assertions)]
     aux_transition_evaluations: Vec<Vec<E>>,
     #[cfg(debug_assertions)]
+    transition_divisor_indices: Vec<usize>,
+    #[cfg(debug_assertions)]
     expected_transition_degrees: Vec<usize>,
 }
 
 impl<'a, E: FieldElement> ConstraintEvaluationTable<'a, E> {
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
-    /// Returns a new constraint evaluation table with number of columns equal to the number of
-    /// specified divisors, and number of rows equal to the size of constraint evaluation domain.
+    /// Returns a new constraint evaluation table with one column for each of the specified
+    /// transition constraint divisors and one column for boundary constraints, and number of rows
+    /// equal to the size of constraint evaluation domain.
+    ///
+    /// If a buffer pool is provided, the columns of the table are taken from the pool, and
+    /// columns of transition constraint evaluations are returned to the pool once they are
+    /// combined (see [ConstraintEvaluationTable::combine()]).
     #[cfg(not(debug_assertions))]
     pub fn new(
         domain: &'a StarkDomain<E::BaseField>,
-        divisors: Vec<ConstraintDivisor<E::BaseField>>,
+        transition_divisors: Vec<ConstraintDivisor<E::BaseField>>,
+        buffer_pool: Option<&'a BufferPool<E::BaseField>>,
     ) -> Self {
-        let num_columns = divisors.len();
         let num_rows = domain.ce_domain_size();
         ConstraintEvaluationTable {
-            evaluations: uninit_matrix(num_columns, num_rows),
-            divisors,
+            evaluations: uninit_matrix(transition_divisors.len() + 1, num_rows, buffer_pool),
+            transition_divisors,
             domain,
+            buffer_pool,
         }
     }
 
@@ -62,10 +79,10 @@ impl<'a, E: FieldElement> ConstraintEvaluationTable<'a, E> {
     #[cfg(debug_assertions)]
     pub fn new(
         domain: &'a StarkDomain<E::BaseField>,
-        divisors: Vec<ConstraintDivisor<E::BaseField>>,
+        transition_divisors: Vec<ConstraintDivisor<E::BaseField>>,
+        buffer_pool: Option<&'a BufferPool<E::BaseField>>,
         transition_constraints: &TransitionConstraints<E>,
     ) -> Self {
-        let num_columns = divisors.len();
         let num_rows = domain.ce_domain_size();
         let num_tm_columns = transition_constraints.num_main_constraints();
         let num_ta_columns = transition_constraints.num_aux_constraints();
@@ -74,13 +91,21 @@ impl<'a, E: FieldElement> ConstraintEvaluationTable<'a, E> {
         // degrees; we do this in debug mode only because this comparison is expensive
         let expected_transition_degrees =
             build_transition_constraint_degrees(transition_constraints, domain.trace_length());
+        let transition_divisor_indices = transition_constraints
+            .main_divisor_indices()
+            .iter()
+            .chain(transition_constraints.aux_divisor_indices())
+            .copied()
+            .collect();
 
         ConstraintEvaluationTable {
-            evaluations: uninit_matrix(num_columns, num_rows),
-            divisors,
+            evaluations: uninit_matrix(transition_divisors.len() + 1, num_rows, buffer_pool),
+            transition_divisors,
             domain,
-            main_transition_evaluations: uninit_matrix(num_tm_columns, num_rows),
-            aux_transition_evaluations: uninit_matrix(num_ta_columns, num_rows),
+            buffer_pool,
+            main_transition_evaluations: uninit_matrix(num_tm_columns, num_rows, None),
+            aux_transition_evaluations: uninit_matrix(num_ta_columns, num_rows, None),
+            transition_divisor_indices,
             expected_transition_degrees,
         }
     }
@@ -96,9 +121,10 @@ impl<'a, E: FieldElement> ConstraintEvaluationTable<'a, E> {
 
     /// Returns number of columns in this table.
     ///
-    /// The first column always contains the value of combined transition constraint evaluations;
-    /// the remaining columns contain values of assertion constraint evaluations combined based on
-    /// common divisors.
+    /// The first columns contain the values of combined transition constraint evaluations, one
+    /// column per distinct transition constraint divisor; the last column contains the values of
+    /// combined boundary constraint evaluations which have already been divided by their
+    /// respective divisors.
     #[allow(dead_code)]
     pub fn num_columns(&self) -> usize {
         self.evaluations.len()
@@ -109,7 +135,7 @@ impl<'a, E: FieldElement> ConstraintEvaluationTable<'a, E> {
 
     /// Break the table into the number of specified fragments. All fragments can be updated
     /// independently - e.g. in different threads.
-    pub fn fragments(&mut self, num_fragments: usize) -> Vec<EvaluationTableFragment<E>> {
+    pub fn fragments(&mut self, num_fragments: usize) -> Vec<EvaluationTableFragment<'_, E>> {
         let fragment_size = self.num_rows() / num_fragments;
         assert!(
             fragment_size >= MIN_FRAGMENT_SIZE,
@@ -158,18 +184,24 @@ impl<'a, E: FieldElement> ConstraintEvaluationTable<'a, E> {
 
     // CONSTRAINT COMPOSITION
     // --------------------------------------------------------------------------------------------
-    /// Divides constraint evaluation columns by their respective divisor (in evaluation form) and
-    /// combines the results into a single column.
-    pub fn combine(self) -> Vec<E> {
-        // allocate memory for the combined polynomial
-        let mut combined_poly = vec![E::ZERO; self.num_rows()];
-
-        // iterate over all columns of the constraint evaluation table, divide each column
-        // by the evaluations of its corresponding divisor, and add all resulting evaluations
-        // together into a single vector
-        for (column, divisor) in self.evaluations.into_iter().zip(self.divisors.iter()) {
-            // divide the column by the divisor and accumulate the result into combined_poly
-            acc_column(column, divisor, self.domain, &mut combined_poly);
+    /// Divides each transition constraint evaluation column by its transition constraint divisor
+    /// (in evaluation form) and combines the results with the boundary constraint evaluation
+    /// column into a single column.
+    pub fn combine(mut self) -> Vec<E> {
+        // boundary constraint evaluations have already been divided by their divisors during
+        // constraint evaluation, and thus, we use them as the starting point for the combined
+        // polynomial
+        let mut combined_poly = self.evaluations.pop().expect("no boundary constraint column");
+
+        // divide each transition column by its divisor and accumulate the result into
+        // combined_poly
+        for (transition_column, divisor) in
+            self.evaluations.into_iter().zip(self.transition_divisors.iter())
+        {
+            acc_column(&transition_column, divisor, self.domain, &mut combined_poly);
+            if let Some(pool) = self.buffer_pool {
+                pool.put(transition_column);
+            }
         }
 
         combined_poly
@@ -180,14 +212,16 @@ impl<'a, E: FieldElement> ConstraintEvaluationTable<'a, E> {
 
     #[cfg(debug_assertions)]
     pub fn validate_transition_degrees(&mut self) {
-        // evaluate transition constraint divisor (which is assumed to be the first one in the
-        // divisor list) over the constraint evaluation domain. this is used later to compute
-        // actual degrees of transition constraint evaluations.
-        let div_values = evaluate_divisor::<E::BaseField>(
-            &self.divisors[0],
-            self.num_rows(),
-            self.domain.offset(),
-        );
+        // evaluate transition constraint divisors over the constraint evaluation domain. these
+        // are used later to compute actual degrees of transition constraint evaluations.
+        let div_values = self
+            .transition_divisors
+            .iter()
+            .map(|divisor| {
+                evaluate_divisor::<E::BaseField>(divisor, self.num_rows(), self.domain.offset())
+            })
+            .collect::<Vec<_>>();
+        let mut divisor_indices = self.transition_divisor_indices.iter();
 
         // collect actual degrees for all transition constraints by interpolating saved
         // constraint evaluations into polynomials and checking their degree; also
@@ -198,32 +232,56 @@ impl<'a, E: FieldElement> ConstraintEvaluationTable<'a, E> {
 
         // first process transition constraint evaluations for the main trace segment
         for evaluations in self.main_transition_evaluations.iter() {
-            let degree = get_transition_poly_degree(evaluations, &inv_twiddles, &div_values);
+            let div_values = &div_values[*divisor_indices.next().expect("no divisor index")];
+            let degree = get_transition_poly_degree(evaluations, &inv_twiddles, div_values);
             actual_degrees.push(degree);
             max_degree = core::cmp::max(max_degree, degree);
         }
 
         // then process transition constraint evaluations for the auxiliary trace segment
         for evaluations in self.aux_transition_evaluations.iter() {
-            let degree = get_transition_poly_degree(evaluations, &inv_twiddles, &div_values);
+            let div_values = &div_values[*divisor_indices.next().expect("no divisor index")];
+            let degree = get_transition_poly_degree(evaluations, &inv_twiddles, div_values);
             actual_degrees.push(degree);
             max_degree = core::cmp::max(max_degree, degree);
         }
 
-        // make sure expected and actual degrees are equal
-        assert_eq!(
-            self.expected_transition_degrees, actual_degrees,
-            "transition constraint degrees didn't match\nexpected: {:>3?}\nactual:   {:>3?}",
-            self.expected_transition_degrees, actual_degrees
+        // make sure expected and actual degrees are equal; if they are not, list all constraints
+        // with mismatched degrees (constraints of the auxiliary segment are indexed after the
+        // constraints of the main segment)
+        let num_main_constraints = self.main_transition_evaluations.len();
+        let mismatches = self
+            .expected_transition_degrees
+            .iter()
+            .zip(actual_degrees.iter())
+            .enumerate()
+            .filter(|(_, (expected, actual))| expected != actual)
+            .map(|(i, (expected, actual))| {
+                let (segment, idx) = if i < num_main_constraints {
+                    ("main", i)
+                } else {
+                    ("auxiliary", i - num_main_constraints)
+                };
+                alloc::format!(
+                    "  {segment} transition constraint {idx}: expected degree {expected}, but was {actual}\n"
+                )
+            })
+            .collect::<String>();
+        assert!(
+            mismatches.is_empty(),
+            "transition constraint degrees didn't match\n{mismatches}\
+            expected: {:>3?}\nactual:   {:>3?}",
+            self.expected_transition_degrees,
+            actual_degrees
         );
 
-        // make sure evaluation domain size does not exceed the size required by max degree
+        // make sure evaluation domain is large enough for the max degree; the domain may be
+        // larger than required if the constraint evaluation blowup factor was set explicitly
         let expected_domain_size =
-            core::cmp::max(max_degree, self.domain.trace_length() + 1).next_power_of_two();
-        assert_eq!(
-            expected_domain_size,
-            self.num_rows(),
-            "incorrect constraint evaluation domain size; expected {}, but was {}",
+            core::cmp::max(max_degree + 1, self.domain.trace_length() + 1).next_power_of_two();
+        assert!(
+            expected_domain_size <= self.num_rows(),
+            "constraint evaluation domain too small; expected at least {}, but was {}",
             expected_domain_size,
             self.num_rows()
         );
@@ -286,9 +344,17 @@ impl<E: FieldElement> EvaluationTableFragment<'_, E> {
 // HELPER FUNCTIONS
 // ================================================================================================
 
-/// Allocates memory for a two-dimensional data structure without initializing it.
-fn uninit_matrix<E: FieldElement>(num_cols: usize, num_rows: usize) -> Vec<Vec<E>> {
-    unsafe { (0..num_cols).map(|_| uninit_vector(num_rows)).collect() }
+/// Allocates memory for a two-dimensional data structure without initializing it; if a buffer
+/// pool is provided, the memory is taken from the pool.
+fn uninit_matrix<E: FieldElement>(
+    num_cols: usize,
+    num_rows: usize,
+    buffer_pool: Option<&BufferPool<E::BaseField>>,
+) -> Vec<Vec<E>> {
+    match buffer_pool {
+        Some(pool) => unsafe { (0..num_cols).map(|_| pool.take(num_rows)).collect() },
+        None => unsafe { (0..num_cols).map(|_| uninit_vector(num_rows)).collect() },
+    }
 }
 
 /// Breaks the source data into a mutable set of fragments such that each fragment has the same
@@ -315,7 +381,7 @@ fn make_fragments<E: FieldElement>(
 
 #[allow(clippy::many_single_char_names)]
 fn acc_column<E: FieldElement>(
-    column: Vec<E>,
+    column: &[E],
     divisor: &ConstraintDivisor<E::BaseField>,
     domain: &StarkDomain<E::BaseField>,
     result: &mut [E],
@@ -357,7 +423,7 @@ fn acc_column<E: FieldElement>(
                     let x = domain.get_ce_x_at(batch_offset + i);
                     let e = divisor.evaluate_exemptions_at(x);
                     // determine which value of z corresponds to the current domain point
-                    let z = z[i % z.len()];
+                    let z = z[(batch_offset + i) % z.len()];
                     // compute value * e(x) * z and add it to the result
                     *acc_value += column[batch_offset + i].mul_base(z * e);
                 }
@@ -375,16 +441,6 @@ fn get_inv_evaluation<B: StarkField>(
     let a = numerator[0].0 as u64; // numerator degree
     let b = numerator[0].1;
 
-    // this guarantees that we can use get_ce_x_power_at() below but limits execution trace length
-    // to be at most 2^32. in the future, we should revisit this to allow execution traces of
-    // greater length.
-    assert!(
-        a <= u32::MAX as u64,
-        "constraint divisor numerator degree cannot exceed {}, but was {}",
-        u32::MAX,
-        a
-    );
-
     let n = domain.ce_domain_size() / a as usize;
     let domain_offset_exp = domain.offset().exp(a.into());
 
@@ -422,14 +478,31 @@ fn build_transition_constraint_degrees<E: FieldElement>(
     constraints: &TransitionConstraints<E>,
     trace_length: usize,
 ) -> Vec<usize> {
+    let divisors = constraints.divisors();
     let mut result = Vec::new();
 
-    for degree in constraints.main_constraint_degrees() {
-        result.push(degree.get_evaluation_degree(trace_length) - constraints.divisor().degree())
+    for (degree, &divisor_idx) in constraints
+        .main_constraint_degrees()
+        .iter()
+        .zip(constraints.main_divisor_indices())
+    {
+        result.push(
+            degree
+                .get_evaluation_degree(trace_length)
+                .saturating_sub(divisors[divisor_idx].degree()),
+        )
     }
 
-    for degree in constraints.aux_constraint_degrees() {
-        result.push(degree.get_evaluation_degree(trace_length) - constraints.divisor().degree())
+    for (degree, &divisor_idx) in constraints
+        .aux_constraint_degrees()
+        .iter()
+        .zip(constraints.aux_divisor_indices())
+    {
+        result.push(
+            degree
+                .get_evaluation_degree(trace_length)
+                .saturating_sub(divisors[divisor_idx].degree()),
+        )
     }
 
     result
@@ -0,0 +1,286 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::vec::Vec;
+
+use air::{EvaluationFrame, SymbolicConstraints};
+use math::StarkField;
+
+use super::{InterpretedConstraints, BATCH_SIZE};
+
+// CONSTRAINT EVALUATION BACKEND
+// ================================================================================================
+
+/// Defines how main transition constraints described symbolically by an AIR (see
+/// [Air::get_symbolic_constraints()](air::Air::get_symbolic_constraints)) are evaluated over
+/// blocks of consecutive rows of the constraint evaluation domain.
+///
+/// Evaluations of constraints at different rows are independent of each other. Thus, blocks can
+/// be evaluated on hardware which processes many rows at once: for example, a GPU backend can
+/// generate a kernel from the symbolic constraints in [ConstraintEvaluationBackend::compile()],
+/// and in [ConstraintEvaluationBackend::evaluate_block()], transfer the values of a block to the
+/// device, run the kernel, and transfer the evaluations back. Implementations of this trait are
+/// used by [DefaultConstraintEvaluator](super::DefaultConstraintEvaluator), which compiles the
+/// constraints once per proof, and then evaluates blocks of up to
+/// [ConstraintEvaluationBackend::block_size()] rows; the evaluations are merged using random
+/// linear combinations, combined with evaluations of all other constraints, and interpolated by
+/// the prover.
+///
+/// To use a backend in the STARK prover, specify
+/// [DefaultConstraintEvaluator](super::DefaultConstraintEvaluator) parameterized by
+/// the backend as the constraint evaluator of the prover, and provide the backend via
+/// [DefaultConstraintEvaluator::with_backend()](super::DefaultConstraintEvaluator::with_backend).
+///
+/// Implementations must produce exactly the same values as
+/// [SymbolicConstraints::evaluate()] does; otherwise, proof generation will fail or generated
+/// proofs will not verify.
+///
+/// This crate provides only [CpuEvaluationBackend]; backends which generate and run GPU kernels
+/// are expected to be implemented outside of this crate (e.g., together with the device runtime
+/// they depend on).
+pub trait ConstraintEvaluationBackend<B: StarkField>: Sync {
+    /// Constraints prepared for evaluation by this backend.
+    type Program: Sync;
+
+    /// Returns the maximum number of rows in a block passed to
+    /// [ConstraintEvaluationBackend::evaluate_block()].
+    fn block_size(&self) -> usize;
+
+    /// Prepares the specified constraints for evaluation over frames with the specified number
+    /// of rows and columns, and the specified number of periodic columns.
+    ///
+    /// Implementations should panic if any of the constraints refers to a row, a column, or a
+    /// periodic column outside of the specified bounds.
+    fn compile(
+        &self,
+        constraints: &SymbolicConstraints<B>,
+        num_frame_rows: usize,
+        num_columns: usize,
+        num_periodic_columns: usize,
+    ) -> Self::Program;
+
+    /// Returns the number of constraints evaluated by the specified program.
+    fn num_constraints(&self, program: &Self::Program) -> usize;
+
+    /// Evaluates the constraints of the `program` over all rows of the `block`, and writes the
+    /// evaluations into `evaluations` in row-major order (i.e., evaluations of all constraints
+    /// at the first row of the block come first).
+    ///
+    /// The length of `evaluations` is guaranteed to be equal to the number of rows in the block
+    /// multiplied by the number of constraints.
+    fn evaluate_block(&self, program: &Self::Program, block: &FrameBlock<B>, evaluations: &mut [B]);
+}
+
+// CPU EVALUATION BACKEND
+// ================================================================================================
+
+/// A [ConstraintEvaluationBackend] which evaluates constraints on the CPU.
+///
+/// Constraints are lowered into a sequence of register instructions, and these instructions are
+/// interpreted over batches of 64 rows at a time. When the crate is compiled with `concurrent`
+/// feature enabled, the constraint evaluator evaluates blocks of different fragments of the
+/// constraint evaluation domain in multiple threads.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CpuEvaluationBackend;
+
+impl<B: StarkField> ConstraintEvaluationBackend<B> for CpuEvaluationBackend {
+    type Program = InterpretedConstraints<B>;
+
+    fn block_size(&self) -> usize {
+        BATCH_SIZE
+    }
+
+    fn compile(
+        &self,
+        constraints: &SymbolicConstraints<B>,
+        num_frame_rows: usize,
+        num_columns: usize,
+        num_periodic_columns: usize,
+    ) -> Self::Program {
+        InterpretedConstraints::new(constraints, num_frame_rows, num_columns, num_periodic_columns)
+    }
+
+    fn num_constraints(&self, program: &Self::Program) -> usize {
+        program.num_constraints()
+    }
+
+    fn evaluate_block(
+        &self,
+        program: &Self::Program,
+        block: &FrameBlock<B>,
+        evaluations: &mut [B],
+    ) {
+        debug_assert_eq!(block.num_rows() * program.num_constraints(), evaluations.len());
+
+        let mut batch = program.new_batch();
+        let num_constraints = program.num_constraints().max(1);
+        for (i, evaluations) in evaluations.chunks_mut(BATCH_SIZE * num_constraints).enumerate() {
+            let offset = i * BATCH_SIZE;
+            let num_rows = BATCH_SIZE.min(block.num_rows() - offset);
+            program.load_rows(&mut batch, block, offset, num_rows);
+            program.evaluate(&mut batch, num_rows);
+            for (row, evaluations) in evaluations.chunks_mut(num_constraints).enumerate() {
+                program.read_row(&batch, row, evaluations);
+            }
+        }
+    }
+}
+
+// FRAME BLOCK
+// ================================================================================================
+
+/// Values of evaluation frames and periodic columns at a block of consecutive rows of the
+/// constraint evaluation domain.
+///
+/// Values are stored in column-major order: for every row of the evaluation frame and every
+/// column of the main trace segment, values at all rows of the block are kept in a single slice,
+/// and the same is true for every periodic column. Thus, the values of a block can be transferred
+/// to a device with a few contiguous copies.
+pub struct FrameBlock<B: StarkField> {
+    num_rows: usize,
+    num_frame_rows: usize,
+    num_columns: usize,
+    trace: Vec<Vec<B>>,
+    periodic: Vec<Vec<B>>,
+}
+
+impl<B: StarkField> FrameBlock<B> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new empty block which can hold up to `capacity` rows of frames with the
+    /// specified number of rows and columns, and values of the specified number of periodic
+    /// columns.
+    pub(crate) fn new(
+        capacity: usize,
+        num_frame_rows: usize,
+        num_columns: usize,
+        num_periodic_columns: usize,
+    ) -> Self {
+        Self {
+            num_rows: 0,
+            num_frame_rows,
+            num_columns,
+            trace: vec![vec![B::ZERO; capacity]; num_frame_rows * num_columns],
+            periodic: vec![vec![B::ZERO; capacity]; num_periodic_columns],
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of rows in this block.
+    pub fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    /// Returns the number of frame rows (i.e., of frame offsets) in this block.
+    pub fn num_frame_rows(&self) -> usize {
+        self.num_frame_rows
+    }
+
+    /// Returns the number of main trace columns in this block.
+    pub fn num_columns(&self) -> usize {
+        self.num_columns
+    }
+
+    /// Returns the number of periodic columns in this block.
+    pub fn num_periodic_columns(&self) -> usize {
+        self.periodic.len()
+    }
+
+    /// Returns values of the specified column at the specified row of the evaluation frames for
+    /// all rows of this block.
+    pub fn trace_column(&self, frame_row: usize, column: usize) -> &[B] {
+        &self.trace[frame_row * self.num_columns + column][..self.num_rows]
+    }
+
+    /// Returns values of the specified periodic column for all rows of this block.
+    pub fn periodic_column(&self, index: usize) -> &[B] {
+        &self.periodic[index][..self.num_rows]
+    }
+
+    // DATA MUTATORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Removes all rows from this block.
+    pub(crate) fn clear(&mut self) {
+        self.num_rows = 0;
+    }
+
+    /// Appends the specified frame and periodic values to this block.
+    pub(crate) fn push_row(&mut self, frame: &EvaluationFrame<B>, periodic_values: &[B]) {
+        let row = self.num_rows;
+        for frame_row in 0..self.num_frame_rows {
+            let columns = &mut self.trace[frame_row * self.num_columns..][..self.num_columns];
+            for (column, &value) in columns.iter_mut().zip(frame.row(frame_row)) {
+                column[row] = value;
+            }
+        }
+        for (column, &value) in self.periodic.iter_mut().zip(periodic_values) {
+            column[row] = value;
+        }
+        self.num_rows += 1;
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use air::{EvaluationFrame, SymbolicConstraints};
+    use math::{fields::f64::BaseElement, FieldElement};
+    use rand_utils::rand_vector;
+
+    use super::{ConstraintEvaluationBackend, CpuEvaluationBackend, FrameBlock, BATCH_SIZE};
+
+    #[test]
+    fn cpu_backend_evaluation() {
+        // a' = a^3 + k * b, b' = a * b
+        let mut c = SymbolicConstraints::<BaseElement>::new();
+        let (a, b, k) = (c.current(0), c.current(1), c.periodic(0));
+        let (a_next, b_next) = (c.next(0), c.next(1));
+        let a3 = c.exp(a, 3);
+        let kb = c.mul(k, b);
+        let sum = c.add(a3, kb);
+        let c0 = c.sub(a_next, sum);
+        c.enforce(c0);
+        let ab = c.mul(a, b);
+        let c1 = c.sub(b_next, ab);
+        c.enforce(c1);
+
+        let backend = CpuEvaluationBackend;
+        let program = backend.compile(&c, 2, 2, 1);
+        assert_eq!(2, backend.num_constraints(&program));
+
+        // the block spans several batches, and the last batch is partial
+        let num_rows = 2 * BATCH_SIZE + 5;
+        let frames = (0..num_rows)
+            .map(|_| EvaluationFrame::from_rows(rand_vector(2), rand_vector(2)))
+            .collect::<Vec<_>>();
+        let periodic_values = (0..num_rows).map(|_| rand_vector(1)).collect::<Vec<_>>();
+
+        let mut block = FrameBlock::new(num_rows, 2, 2, 1);
+        for (frame, periodic_values) in frames.iter().zip(periodic_values.iter()) {
+            block.push_row(frame, periodic_values);
+        }
+        assert_eq!(num_rows, block.num_rows());
+        assert_eq!(frames[7].next()[1], block.trace_column(1, 1)[7]);
+
+        let mut evaluations = vec![BaseElement::ZERO; num_rows * 2];
+        backend.evaluate_block(&program, &block, &mut evaluations);
+
+        let mut expected = vec![BaseElement::ZERO; 2];
+        for ((frame, periodic_values), actual) in
+            frames.iter().zip(periodic_values.iter()).zip(evaluations.chunks(2))
+        {
+            c.evaluate(frame, periodic_values, &mut expected);
+            assert_eq!(expected, actual);
+        }
+    }
+}
@@ -6,7 +6,9 @@
 use alloc::{collections::BTreeMap, vec::Vec};
 
 use air::{Air, AuxRandElements, ConstraintDivisor};
-use math::{fft, ExtensionOf, FieldElement};
+use math::{
+    batch_inversion, fft, get_power_series_with_offset, ExtensionOf, FieldElement, StarkField,
+};
 
 use super::StarkDomain;
 
@@ -17,6 +19,10 @@ use super::StarkDomain;
 /// larger polynomials all evaluations over the constraint evaluation domain will be pre-computed.
 const SMALL_POLY_DEGREE: usize = 63;
 
+/// Minimum number of single-step assertion groups for which constraints of these groups are
+/// aggregated by column (see [SparseBoundaryConstraints]), rather than evaluated group by group.
+const MIN_SPARSE_GROUPS: usize = 64;
+
 // BOUNDARY CONSTRAINTS
 // ================================================================================================
 
@@ -25,8 +31,21 @@ const SMALL_POLY_DEGREE: usize = 63;
 /// the auxiliary trace segment (if any).
 ///
 /// We transform the constraints defined in the [air] crate into specialized constraints here
-/// to make evaluation of these constraints more efficient in the prover context.
-pub struct BoundaryConstraints<E: FieldElement>(Vec<BoundaryConstraintGroup<E>>);
+/// to make evaluation of these constraints more efficient in the prover context. In particular,
+/// inverse evaluations of all constraint divisors over the constraint evaluation domain are
+/// computed once (via batch inversion) when the constraints are instantiated. Thus, evaluating
+/// boundary constraints at a given step of the domain requires only multiplications and additions.
+///
+/// When the computation has many assertions against single steps of the execution trace (e.g.,
+/// assertions describing public memory), each of these assertions forms a separate group, and
+/// evaluating all groups at every step becomes the dominant cost of boundary constraint
+/// evaluation. In such cases, these groups are aggregated by column (see
+/// [SparseBoundaryConstraints]) so that the evaluation cost depends on the number of distinct
+/// columns against which the assertions are made rather than on the number of assertions.
+pub struct BoundaryConstraints<E: FieldElement> {
+    groups: Vec<BoundaryConstraintGroup<E>>,
+    sparse: SparseBoundaryConstraints<E>,
+}
 
 impl<E: FieldElement> BoundaryConstraints<E> {
     // CONSTRUCTOR
@@ -73,15 +92,28 @@ impl<E: FieldElement> BoundaryConstraints<E> {
             };
         }
 
-        Self(result)
-    }
+        // evaluations of x^a over the constraint evaluation domain are cached by the degree a so
+        // that divisors of the same degree (e.g., for assertions against the first and the last
+        // steps of the trace) do not need to re-compute them.
+        let mut x_power_map = BTreeMap::new();
+
+        // if there are many groups of single-step assertions, aggregate them by column
+        let num_sparse_groups = result.iter().filter(|group| group.is_single_step()).count();
+        let sparse = if num_sparse_groups >= MIN_SPARSE_GROUPS {
+            let (sparse_groups, groups): (Vec<_>, Vec<_>) =
+                result.into_iter().partition(|group| group.is_single_step());
+            result = groups;
+            SparseBoundaryConstraints::new(&sparse_groups, air, &mut x_power_map)
+        } else {
+            SparseBoundaryConstraints::default()
+        };
 
-    // PUBLIC ACCESSORS
-    // --------------------------------------------------------------------------------------------
+        // compute inverse evaluations of divisors for all remaining groups
+        for group in result.iter_mut() {
+            group.inv_divisor = get_inv_divisor_evaluations(&group.divisor, air, &mut x_power_map);
+        }
 
-    /// Returns a vector of all boundary constraint divisors.
-    pub fn get_divisors(&self) -> Vec<ConstraintDivisor<E::BaseField>> {
-        self.0.iter().map(|g| g.divisor.clone()).collect()
+        Self { groups: result, sparse }
     }
 
     // EVALUATORS
@@ -89,33 +121,41 @@ impl<E: FieldElement> BoundaryConstraints<E> {
 
     /// Evaluates boundary constraints against the main segment of an execution trace at the
     /// specified step of constraint evaluation domain.
+    ///
+    /// The evaluations of all constraint groups are divided by their respective divisors and
+    /// merged into a single value.
     pub fn evaluate_main(
         &self,
         main_state: &[E::BaseField],
         domain: &StarkDomain<E::BaseField>,
         step: usize,
-        result: &mut [E],
-    ) {
+    ) -> E {
         let x = domain.get_ce_x_at(step);
-        for (group, result) in self.0.iter().zip(result.iter_mut()) {
-            *result = group.evaluate_main(main_state, step, x);
-        }
+        self.groups
+            .iter()
+            .fold(self.sparse.evaluate_main(main_state, step), |acc, group| {
+                acc + group.evaluate_main(main_state, step, x)
+            })
     }
 
     /// Evaluates boundary constraints against all segments of an execution trace at the
     /// specified step of constraint evaluation domain.
+    ///
+    /// The evaluations of all constraint groups are divided by their respective divisors and
+    /// merged into a single value.
     pub fn evaluate_all(
         &self,
         main_state: &[E::BaseField],
         aux_state: &[E],
         domain: &StarkDomain<E::BaseField>,
         step: usize,
-        result: &mut [E],
-    ) {
+    ) -> E {
         let x = domain.get_ce_x_at(step);
-        for (group, result) in self.0.iter().zip(result.iter_mut()) {
-            *result = group.evaluate_all(main_state, aux_state, step, x);
-        }
+        self.groups
+            .iter()
+            .fold(self.sparse.evaluate_all(main_state, aux_state, step), |acc, group| {
+                acc + group.evaluate_all(main_state, aux_state, step, x)
+            })
     }
 }
 
@@ -129,6 +169,11 @@ impl<E: FieldElement> BoundaryConstraints<E> {
 /// and the constraints against auxiliary segments of the execution trace (if any).
 pub struct BoundaryConstraintGroup<E: FieldElement> {
     divisor: ConstraintDivisor<E::BaseField>,
+    // evaluations of 1 / (x^a - b) over the constraint evaluation domain, where (x^a - b) is the
+    // numerator of the divisor; these evaluations repeat every ce_domain_size / a steps, and
+    // thus, only the first ce_domain_size / a of them are stored. the exemption points of the
+    // divisor (if any) are evaluated on the fly.
+    inv_divisor: Vec<E::BaseField>,
     // main trace constraints
     main_single_value: Vec<SingleValueConstraint<E::BaseField, E>>,
     main_small_poly: Vec<SmallPolyConstraint<E::BaseField, E>>,
@@ -148,6 +193,7 @@ impl<E: FieldElement> BoundaryConstraintGroup<E> {
     fn new(divisor: ConstraintDivisor<E::BaseField>) -> Self {
         Self {
             divisor,
+            inv_divisor: Vec::new(),
             main_single_value: Vec::new(),
             main_small_poly: Vec::new(),
             main_large_poly: Vec::new(),
@@ -235,34 +281,34 @@ impl<E: FieldElement> BoundaryConstraintGroup<E> {
         }
     }
 
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns true if this group describes assertions against a single step of the execution
+    /// trace, i.e., if the divisor of the group is (x - b), and all constraints in the group are
+    /// single-value constraints.
+    fn is_single_step(&self) -> bool {
+        matches!(self.divisor.numerator(), [(1, _)])
+            && self.divisor.exemptions().is_empty()
+            && self.main_small_poly.is_empty()
+            && self.main_large_poly.is_empty()
+            && self.aux_small_poly.is_empty()
+            && self.aux_large_poly.is_empty()
+    }
+
     // EVALUATORS
     // --------------------------------------------------------------------------------------------
 
     /// Evaluates the constraints against the main segment of the execution trace contained in
-    /// this group at the specified step of the trace.
+    /// this group at the specified step of the trace, and divides the result by the divisor of
+    /// this group.
     pub fn evaluate_main(&self, state: &[E::BaseField], ce_step: usize, x: E::BaseField) -> E {
-        let mut result = E::ZERO;
-
-        // evaluate all single-value constraints
-        for constraint in self.main_single_value.iter() {
-            result += constraint.evaluate(state);
-        }
-
-        // evaluate all small polynomial constraints
-        for constraint in self.main_small_poly.iter() {
-            result += constraint.evaluate(state, x);
-        }
-
-        // evaluate all large polynomial constraints
-        for constraint in self.main_large_poly.iter() {
-            result += constraint.evaluate(state, ce_step);
-        }
-
-        result
+        let z = self.get_inv_divisor(ce_step, x);
+        self.evaluate_main_numerator(state, ce_step, x).mul_base(z)
     }
 
     /// Evaluates all constraints contained in this group at the specified step of the
-    /// execution trace.
+    /// execution trace, and divides the result by the divisor of this group.
     pub fn evaluate_all(
         &self,
         main_state: &[E::BaseField],
@@ -270,7 +316,8 @@ impl<E: FieldElement> BoundaryConstraintGroup<E> {
         ce_step: usize,
         x: E::BaseField,
     ) -> E {
-        let mut result = self.evaluate_main(main_state, ce_step, x);
+        let z = self.get_inv_divisor(ce_step, x);
+        let mut result = self.evaluate_main_numerator(main_state, ce_step, x);
 
         // evaluate all single-value constraints
         for constraint in self.aux_single_value.iter() {
@@ -287,10 +334,181 @@ impl<E: FieldElement> BoundaryConstraintGroup<E> {
             result += constraint.evaluate(aux_state, ce_step);
         }
 
+        result.mul_base(z)
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the inverse of the divisor of this group evaluated at the specified step of the
+    /// constraint evaluation domain; `x` is the domain value at this step.
+    ///
+    /// The divisor has the form (x^a - b) / e(x), where e(x) describes the exemption points, and
+    /// thus, its inverse is computed as e(x) / (x^a - b).
+    #[inline(always)]
+    fn get_inv_divisor(&self, ce_step: usize, x: E::BaseField) -> E::BaseField {
+        let z = self.inv_divisor[ce_step % self.inv_divisor.len()];
+        if self.divisor.exemptions().is_empty() {
+            z
+        } else {
+            z * self.divisor.evaluate_exemptions_at(x)
+        }
+    }
+
+    /// Evaluates the numerators of the constraints against the main segment of the execution
+    /// trace contained in this group at the specified step of the trace.
+    fn evaluate_main_numerator(
+        &self,
+        state: &[E::BaseField],
+        ce_step: usize,
+        x: E::BaseField,
+    ) -> E {
+        let mut result = E::ZERO;
+
+        // evaluate all single-value constraints
+        for constraint in self.main_single_value.iter() {
+            result += constraint.evaluate(state);
+        }
+
+        // evaluate all small polynomial constraints
+        for constraint in self.main_small_poly.iter() {
+            result += constraint.evaluate(state, x);
+        }
+
+        // evaluate all large polynomial constraints
+        for constraint in self.main_large_poly.iter() {
+            result += constraint.evaluate(state, ce_step);
+        }
+
         result
     }
 }
 
+// SPARSE BOUNDARY CONSTRAINTS
+// ================================================================================================
+
+/// Single-value constraints of single-step assertion groups aggregated by column.
+///
+/// For a set of single-value constraints against column j with divisors (x - b_i), asserted
+/// values v_i, and composition coefficients c_i, the sum of the constraints can be written as:
+///
+/// sum_i c_i * (p_j(x) - v_i) / (x - b_i) = p_j(x) * sum_i c_i / (x - b_i) - sum_i c_i * v_i / (x - b_i)
+///
+/// Neither of the two sums depends on the execution trace, and thus, both can be evaluated over
+/// the constraint evaluation domain when the constraints are instantiated. Evaluating all such
+/// constraints against column j at a given step of the domain then requires only a single
+/// multiplication and subtraction.
+struct SparseBoundaryConstraints<E: FieldElement> {
+    main: Vec<AggregatedConstraint<E>>,
+    aux: Vec<AggregatedConstraint<E>>,
+}
+
+impl<E: FieldElement> SparseBoundaryConstraints<E> {
+    /// Returns constraints of the specified single-step assertion groups aggregated by column.
+    fn new<A: Air<BaseField = E::BaseField>>(
+        groups: &[BoundaryConstraintGroup<E>],
+        air: &A,
+        x_power_map: &mut BTreeMap<usize, Vec<E::BaseField>>,
+    ) -> Self {
+        let mut main = BTreeMap::new();
+        let mut aux = BTreeMap::new();
+        for group in groups {
+            debug_assert!(group.is_single_step(), "not a single-step assertion group");
+            let inv_divisor = get_inv_divisor_evaluations(&group.divisor, air, x_power_map);
+            for constraint in group.main_single_value.iter() {
+                main.entry(constraint.column)
+                    .or_insert_with(|| AggregatedConstraint::new(constraint.column, air))
+                    .add_constraint(constraint, &inv_divisor);
+            }
+            for constraint in group.aux_single_value.iter() {
+                aux.entry(constraint.column)
+                    .or_insert_with(|| AggregatedConstraint::new(constraint.column, air))
+                    .add_constraint(constraint, &inv_divisor);
+            }
+        }
+
+        Self {
+            main: main.into_values().collect(),
+            aux: aux.into_values().collect(),
+        }
+    }
+
+    /// Evaluates the aggregated constraints against the main segment of the execution trace at
+    /// the specified step of the constraint evaluation domain.
+    fn evaluate_main(&self, main_state: &[E::BaseField], ce_step: usize) -> E {
+        self.main
+            .iter()
+            .fold(E::ZERO, |acc, constraint| acc + constraint.evaluate(main_state, ce_step))
+    }
+
+    /// Evaluates all aggregated constraints at the specified step of the constraint evaluation
+    /// domain.
+    fn evaluate_all(&self, main_state: &[E::BaseField], aux_state: &[E], ce_step: usize) -> E {
+        self.aux
+            .iter()
+            .fold(self.evaluate_main(main_state, ce_step), |acc, constraint| {
+                acc + constraint.evaluate::<E>(aux_state, ce_step)
+            })
+    }
+}
+
+impl<E: FieldElement> Default for SparseBoundaryConstraints<E> {
+    fn default() -> Self {
+        Self { main: Vec::new(), aux: Vec::new() }
+    }
+}
+
+/// Single-value constraints against a single column aggregated as described in
+/// [SparseBoundaryConstraints].
+struct AggregatedConstraint<E: FieldElement> {
+    column: usize,
+    // evaluations of sum_i c_i / (x - b_i) over the constraint evaluation domain
+    coefficients: Vec<E>,
+    // evaluations of sum_i c_i * v_i / (x - b_i) over the constraint evaluation domain
+    values: Vec<E>,
+}
+
+impl<E: FieldElement> AggregatedConstraint<E> {
+    /// Returns a new aggregated constraint against the specified column which does not contain
+    /// any constraints yet.
+    fn new<A: Air<BaseField = E::BaseField>>(column: usize, air: &A) -> Self {
+        Self {
+            column,
+            coefficients: vec![E::ZERO; air.ce_domain_size()],
+            values: vec![E::ZERO; air.ce_domain_size()],
+        }
+    }
+
+    /// Adds the specified constraint with the specified evaluations of 1 / (x - b) over the
+    /// constraint evaluation domain to this aggregated constraint.
+    fn add_constraint<F>(
+        &mut self,
+        constraint: &SingleValueConstraint<F, E>,
+        inv_divisor: &[E::BaseField],
+    ) where
+        F: FieldElement<BaseField = E::BaseField>,
+        E: ExtensionOf<F>,
+    {
+        debug_assert_eq!(self.coefficients.len(), inv_divisor.len());
+        let value = constraint.coefficients.mul_base(constraint.value);
+        for ((coefficient, v), &z) in
+            self.coefficients.iter_mut().zip(self.values.iter_mut()).zip(inv_divisor)
+        {
+            *coefficient += constraint.coefficients.mul_base(z);
+            *v += value.mul_base(z);
+        }
+    }
+
+    /// Evaluates this constraint at the specified step of the constraint evaluation domain.
+    fn evaluate<F>(&self, state: &[F], ce_step: usize) -> E
+    where
+        F: FieldElement<BaseField = E::BaseField>,
+        E: ExtensionOf<F>,
+    {
+        self.coefficients[ce_step].mul_base(state[self.column]) - self.values[ce_step]
+    }
+}
+
 // CONSTRAINT SPECIALIZATIONS
 // ================================================================================================
 
@@ -452,3 +670,120 @@ where
         (self.coefficients).mul_base(evaluation)
     }
 }
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns evaluations of 1 / (x^a - b) over the constraint evaluation domain, where (x^a - b) is
+/// the numerator of the specified boundary constraint divisor. The exemption points of the
+/// divisor (if any) are not accounted for in the returned evaluations.
+///
+/// Since x^a repeats every ce_domain_size / a steps of the domain, only the first
+/// ce_domain_size / a evaluations are returned. Evaluations of x^a are looked up in (or added to)
+/// the provided map.
+fn get_inv_divisor_evaluations<A: Air>(
+    divisor: &ConstraintDivisor<A::BaseField>,
+    air: &A,
+    x_power_map: &mut BTreeMap<usize, Vec<A::BaseField>>,
+) -> Vec<A::BaseField> {
+    let numerator = divisor.numerator();
+    assert_eq!(numerator.len(), 1, "complex divisors are not yet supported");
+    let (a, b) = numerator[0];
+
+    // compute (s * g^i)^a = s^a * (g^a)^i for the first ce_domain_size / a steps of the domain
+    let x_powers = x_power_map.entry(a).or_insert_with(|| {
+        let ce_domain_size = air.ce_domain_size();
+        let g = A::BaseField::get_root_of_unity(ce_domain_size.ilog2());
+        let power = (a as u64).into();
+        get_power_series_with_offset(
+            g.exp(power),
+            air.domain_offset().exp(power),
+            ce_domain_size / a,
+        )
+    });
+
+    // compute 1 / (x^a - b)
+    let evaluations = x_powers.iter().map(|&x| x - b).collect::<Vec<_>>();
+    batch_inversion(&evaluations)
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use alloc::{collections::BTreeMap, vec::Vec};
+
+    use air::{Air, Assertion, ConstraintDivisor};
+    use math::{fields::f128::BaseElement, get_power_series_with_offset, FieldElement, StarkField};
+
+    use super::BoundaryConstraints;
+    use crate::{tests::MockAir, StarkDomain};
+
+    #[test]
+    fn inv_divisor_evaluations() {
+        let trace_length = 16;
+        let air = MockAir::with_trace_length(trace_length);
+        let domain = build_ce_domain(air.ce_domain_size(), air.domain_offset());
+
+        let assertions = [
+            Assertion::single(0, 0, BaseElement::ONE),
+            Assertion::single(0, trace_length - 1, BaseElement::ONE),
+            Assertion::periodic(0, 1, 4, BaseElement::ONE),
+        ];
+
+        let mut x_power_map = BTreeMap::new();
+        for assertion in assertions.iter() {
+            let divisor = ConstraintDivisor::from_assertion(assertion, trace_length);
+            let inv_divisor = super::get_inv_divisor_evaluations(&divisor, &air, &mut x_power_map);
+
+            // inverse evaluations repeat every ce_domain_size / degree steps
+            assert_eq!(air.ce_domain_size() / divisor.degree(), inv_divisor.len());
+            for (i, &x) in domain.iter().enumerate() {
+                let expected = divisor.evaluate_at(x).inv();
+                assert_eq!(expected, inv_divisor[i % inv_divisor.len()]);
+            }
+        }
+
+        // powers of x are computed only once for divisors of the same degree
+        assert_eq!(2, x_power_map.len());
+    }
+
+    #[test]
+    fn sparse_boundary_constraints() {
+        let trace_length = 128;
+
+        // assertions against distinct steps of the trace are aggregated by column, while the
+        // assertion against every 4th step of the trace is evaluated on its own
+        let mut assertions = (0..100)
+            .map(|i| Assertion::single(i % 3, i + 1, BaseElement::from(i as u32)))
+            .collect::<Vec<_>>();
+        assertions.push(Assertion::periodic(3, 0, 4, BaseElement::ONE));
+        let air = MockAir::with_assertions(assertions.clone(), trace_length);
+        let coefficients = (0..assertions.len())
+            .map(|i| BaseElement::from(i as u32 + 7))
+            .collect::<Vec<_>>();
+
+        let constraints = BoundaryConstraints::<BaseElement>::new(&air, None, &coefficients);
+        assert_eq!(1, constraints.groups.len());
+        assert_eq!(3, constraints.sparse.main.len());
+
+        // the result is the same as evaluating all constraints one by one
+        let domain = StarkDomain::new(&air);
+        let state = [3_u32, 1, 4, 1].map(BaseElement::from);
+        let boundary = air.get_boundary_constraints::<BaseElement>(None, &coefficients);
+        for step in 0..air.ce_domain_size() {
+            let x = domain.get_ce_x_at(step);
+            let expected = boundary
+                .main_constraints()
+                .iter()
+                .fold(BaseElement::ZERO, |acc, group| acc + group.evaluate_at(&state, x));
+            assert_eq!(expected, constraints.evaluate_main(&state, &domain, step));
+        }
+    }
+
+    fn build_ce_domain(domain_size: usize, domain_offset: BaseElement) -> Vec<BaseElement> {
+        let g = BaseElement::get_root_of_unity(domain_size.ilog2());
+        get_power_series_with_offset(g, domain_offset, domain_size)
+    }
+}
@@ -3,27 +3,36 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use alloc::vec::Vec;
+
 use air::{
     Air, AuxRandElements, ConstraintCompositionCoefficients, EvaluationFrame, TransitionConstraints,
 };
-use math::FieldElement;
+use math::{FieldElement, StarkField};
 use tracing::instrument;
-use utils::iter_mut;
+use utils::{iter_mut, BufferPool};
 #[cfg(feature = "concurrent")]
 use utils::{iterators::*, rayon};
 
 use super::{
     super::EvaluationTableFragment, lagrange::LagrangeKernelConstraintsBatchEvaluator,
-    BoundaryConstraints, CompositionPolyTrace, ConstraintEvaluationTable, ConstraintEvaluator,
+    BoundaryConstraints, CompositionPolyTrace, ConstraintEvaluationBackend,
+    ConstraintEvaluationTable, ConstraintEvaluator, CpuEvaluationBackend, FrameBlock,
     PeriodicValueTable, StarkDomain, TraceLde,
 };
+use crate::ProverContext;
 
 // CONSTANTS
 // ================================================================================================
 
-#[cfg(feature = "concurrent")]
+/// Constraint evaluation domains smaller than this are always evaluated in a single fragment.
 const MIN_CONCURRENT_DOMAIN_SIZE: usize = 8192;
 
+/// Estimated fixed cost of evaluating a fragment (e.g., scheduling the fragment on a thread and
+/// allocating per-fragment buffers), measured in the same units as the cost of evaluating a
+/// single row (i.e., in trace cells read plus constraints evaluated).
+const FRAGMENT_OVERHEAD: usize = 1 << 12;
+
 // DEFAULT CONSTRAINT EVALUATOR
 // ================================================================================================
 
@@ -35,20 +44,41 @@ const MIN_CONCURRENT_DOMAIN_SIZE: usize = 8192;
 ///
 /// When `concurrent` feature is enabled, the extended execution trace is split into sets of
 /// sequential evaluation frames (called fragments), and frames in each fragment are evaluated
-/// in separate threads.
-pub struct DefaultConstraintEvaluator<'a, A: Air, E: FieldElement<BaseField = A::BaseField>> {
+/// in separate threads. The number of fragments is chosen based on a simple cost model which
+/// takes into account the size of the constraint evaluation domain, the width of the execution
+/// trace, the number of transition constraints, and the number of available threads. The number
+/// of fragments can also be set explicitly via [DefaultConstraintEvaluator::with_num_fragments()].
+///
+/// If the AIR describes its main transition constraints symbolically (see
+/// [Air::get_symbolic_constraints()]), the constraints are compiled by a
+/// [ConstraintEvaluationBackend] of type `X` at the start of evaluation, and main transition
+/// constraints are evaluated by the backend over blocks of rows instead of calling
+/// [Air::evaluate_transition()] for every row. By default, the [CpuEvaluationBackend] is used,
+/// which lowers the constraints into a sequence of register instructions and interprets these
+/// instructions over batches of rows; a different backend (e.g., one which evaluates constraints
+/// on a GPU) can be provided via [DefaultConstraintEvaluator::with_backend()].
+pub struct DefaultConstraintEvaluator<
+    'a,
+    A: Air,
+    E: FieldElement<BaseField = A::BaseField>,
+    X = CpuEvaluationBackend,
+> {
     air: &'a A,
+    num_fragments: Option<usize>,
     boundary_constraints: BoundaryConstraints<E>,
     transition_constraints: TransitionConstraints<E>,
     lagrange_constraints_evaluator: Option<LagrangeKernelConstraintsBatchEvaluator<E>>,
     aux_rand_elements: Option<AuxRandElements<E>>,
     periodic_values: PeriodicValueTable<E::BaseField>,
+    backend: X,
+    buffer_pool: Option<BufferPool<E::BaseField>>,
 }
 
-impl<A, E> ConstraintEvaluator<E> for DefaultConstraintEvaluator<'_, A, E>
+impl<A, E, X> ConstraintEvaluator<E> for DefaultConstraintEvaluator<'_, A, E, X>
 where
     A: Air,
     E: FieldElement<BaseField = A::BaseField>,
+    X: ConstraintEvaluationBackend<A::BaseField>,
 {
     type Air = A;
 
@@ -70,34 +100,44 @@ where
             "extended trace length is not consistent with evaluation domain"
         );
 
-        // build a list of constraint divisors; currently, all transition constraints have the same
-        // divisor which we put at the front of the list; boundary constraint divisors are appended
-        // after that
-        let mut divisors = vec![self.transition_constraints.divisor().clone()];
-        divisors.append(&mut self.boundary_constraints.get_divisors());
+        // transition constraints are grouped by their divisors; boundary constraints are divided
+        // by their divisors during evaluation, and thus, no divisors are needed for them
+        let divisors = self.transition_constraints.divisors().to_vec();
 
         // allocate space for constraint evaluations; when we are in debug mode, we also allocate
         // memory to hold all transition constraint evaluations (before they are merged into a
         // single value) so that we can check their degrees later
+        let buffer_pool = self.buffer_pool.as_ref();
         #[cfg(not(debug_assertions))]
-        let mut evaluation_table = ConstraintEvaluationTable::<E>::new(domain, divisors);
-        #[cfg(debug_assertions)]
         let mut evaluation_table =
-            ConstraintEvaluationTable::<E>::new(domain, divisors, &self.transition_constraints);
+            ConstraintEvaluationTable::<E>::new(domain, divisors, buffer_pool);
+        #[cfg(debug_assertions)]
+        let mut evaluation_table = ConstraintEvaluationTable::<E>::new(
+            domain,
+            divisors,
+            buffer_pool,
+            &self.transition_constraints,
+        );
 
         // when `concurrent` feature is enabled, break the evaluation table into multiple fragments
-        // to evaluate them into multiple threads; unless the constraint evaluation domain is small,
-        // then don't bother with concurrent evaluation
+        // to evaluate them into multiple threads; the number of fragments is estimated from the
+        // amount of work per row unless it was set explicitly
 
         #[cfg(not(feature = "concurrent"))]
-        let num_fragments = 1;
+        let num_threads = 1;
 
         #[cfg(feature = "concurrent")]
-        let num_fragments = if domain.ce_domain_size() >= MIN_CONCURRENT_DOMAIN_SIZE {
-            rayon::current_num_threads().next_power_of_two()
-        } else {
-            1
-        };
+        let num_threads = rayon::current_num_threads();
+
+        let num_fragments = self.num_fragments.unwrap_or_else(|| {
+            let row_cost =
+                self.air.trace_info().width() + self.air.context().num_transition_constraints();
+            get_num_fragments(domain.ce_domain_size(), row_cost, num_threads)
+        });
+
+        // compile symbolic transition constraints, if the AIR provides them
+        let program = self.compile_constraints();
+        let program = program.as_ref();
 
         // evaluate constraints for each fragment; if the trace consist of multiple segments
         // we evaluate constraints for all segments. otherwise, we evaluate constraints only
@@ -105,9 +145,9 @@ where
         let mut fragments = evaluation_table.fragments(num_fragments);
         iter_mut!(fragments).for_each(|fragment| {
             if self.air.trace_info().is_multi_segment() {
-                self.evaluate_fragment_full(trace, domain, fragment);
+                self.evaluate_fragment_full(trace, domain, program, fragment);
             } else {
-                self.evaluate_fragment_main(trace, domain, fragment);
+                self.evaluate_fragment_main(trace, domain, program, fragment);
             }
         });
 
@@ -134,7 +174,7 @@ where
     A: Air,
     E: FieldElement<BaseField = A::BaseField>,
 {
-    // CONSTRUCTOR
+    // CONSTRUCTORS
     // --------------------------------------------------------------------------------------------
     /// Returns a new evaluator which can be used to evaluate transition and boundary constraints
     /// over extended execution trace.
@@ -142,13 +182,54 @@ where
         air: &'a A,
         aux_rand_elements: Option<AuxRandElements<E>>,
         composition_coefficients: ConstraintCompositionCoefficients<E>,
+    ) -> Self {
+        let periodic_values = PeriodicValueTable::new(air);
+        Self::with_periodic_values(
+            air,
+            aux_rand_elements,
+            composition_coefficients,
+            periodic_values,
+            None,
+        )
+    }
+
+    /// Returns a new evaluator which can be used to evaluate transition and boundary constraints
+    /// over extended execution trace, using the data pre-computed in the specified `context`.
+    ///
+    /// Memory for the constraint evaluation table is taken from the buffer pool of the `context`.
+    ///
+    /// # Panics
+    /// Panics if the `context` is not compatible with the specified `air`.
+    pub fn new_with_context(
+        air: &'a A,
+        context: &ProverContext<A::BaseField>,
+        aux_rand_elements: Option<AuxRandElements<E>>,
+        composition_coefficients: ConstraintCompositionCoefficients<E>,
+    ) -> Self {
+        assert!(context.is_compatible(air), "prover context is not compatible with the AIR");
+        let periodic_values = context.periodic_values().clone();
+        Self::with_periodic_values(
+            air,
+            aux_rand_elements,
+            composition_coefficients,
+            periodic_values,
+            Some(context.buffer_pool().clone()),
+        )
+    }
+
+    /// Returns a new evaluator with the specified table of periodic values and an optional pool
+    /// of buffers for the constraint evaluation table.
+    fn with_periodic_values(
+        air: &'a A,
+        aux_rand_elements: Option<AuxRandElements<E>>,
+        composition_coefficients: ConstraintCompositionCoefficients<E>,
+        periodic_values: PeriodicValueTable<E::BaseField>,
+        buffer_pool: Option<BufferPool<E::BaseField>>,
     ) -> Self {
         // build transition constraint groups; these will be used to compose transition constraint
         // evaluations
         let transition_constraints =
             air.get_transition_constraints(&composition_coefficients.transition);
-        // build periodic value table
-        let periodic_values = PeriodicValueTable::new(air);
 
         // build boundary constraint groups; these will be used to evaluate and compose boundary
         // constraint evaluations.
@@ -177,13 +258,62 @@ where
 
         DefaultConstraintEvaluator {
             air,
+            num_fragments: None,
             boundary_constraints,
             transition_constraints,
             lagrange_constraints_evaluator,
             aux_rand_elements,
             periodic_values,
+            backend: CpuEvaluationBackend,
+            buffer_pool,
         }
     }
+}
+
+impl<'a, A, E, X> DefaultConstraintEvaluator<'a, A, E, X>
+where
+    A: Air,
+    E: FieldElement<BaseField = A::BaseField>,
+    X: ConstraintEvaluationBackend<A::BaseField>,
+{
+    // CONFIGURATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns this evaluator with symbolic transition constraints evaluated by the specified
+    /// backend.
+    ///
+    /// The backend is used only if the AIR describes its main transition constraints
+    /// symbolically (see [Air::get_symbolic_constraints()]).
+    pub fn with_backend<Y>(self, backend: Y) -> DefaultConstraintEvaluator<'a, A, E, Y>
+    where
+        Y: ConstraintEvaluationBackend<A::BaseField>,
+    {
+        DefaultConstraintEvaluator {
+            air: self.air,
+            num_fragments: self.num_fragments,
+            boundary_constraints: self.boundary_constraints,
+            transition_constraints: self.transition_constraints,
+            lagrange_constraints_evaluator: self.lagrange_constraints_evaluator,
+            aux_rand_elements: self.aux_rand_elements,
+            periodic_values: self.periodic_values,
+            backend,
+            buffer_pool: self.buffer_pool,
+        }
+    }
+
+    /// Sets the number of fragments into which the constraint evaluation table is split during
+    /// evaluation, overriding the number of fragments estimated by this evaluator.
+    ///
+    /// # Panics
+    /// Panics if `num_fragments` is not a power of two.
+    pub fn with_num_fragments(mut self, num_fragments: usize) -> Self {
+        assert!(
+            num_fragments.is_power_of_two(),
+            "number of fragments must be a power of two, but was {num_fragments}"
+        );
+        self.num_fragments = Some(num_fragments);
+        self
+    }
 
     // EVALUATION HELPERS
     // --------------------------------------------------------------------------------------------
@@ -195,12 +325,20 @@ where
         &self,
         trace: &T,
         domain: &StarkDomain<A::BaseField>,
+        program: Option<&X::Program>,
         fragment: &mut EvaluationTableFragment<E>,
     ) {
         // initialize buffers to hold trace values and evaluation results at each step;
-        let mut main_frame = EvaluationFrame::new(trace.trace_info().main_trace_width());
+        let frame_offsets = self.air.context().frame_offsets();
+        let mut main_frame = EvaluationFrame::with_offsets(
+            trace.trace_info().main_trace_width(),
+            frame_offsets.to_vec(),
+        );
         let mut evaluations = vec![E::ZERO; fragment.num_columns()];
         let mut t_evaluations = vec![E::BaseField::ZERO; self.num_main_transition_constraints()];
+        let num_divisors = self.transition_constraints.divisors().len();
+        let mut block = program.map(|program| self.new_transition_block(program));
+        let block_size = self.backend.block_size();
 
         // this will be used to convert steps in constraint evaluation domain to steps in
         // LDE domain
@@ -209,29 +347,41 @@ where
         for i in 0..fragment.num_rows() {
             let step = i + fragment.offset();
 
+            // when constraints are compiled, evaluate main transition constraints for the next
+            // block of rows at the first row of the block
+            if let (Some(program), Some(block)) = (program, block.as_mut()) {
+                if i % block_size == 0 {
+                    self.evaluate_main_transition_block(trace, domain, fragment, i, program, block);
+                }
+            }
+
             // update evaluation frame buffer with data from the execution trace; this will
-            // read current and next rows from the trace into the buffer; data in the trace
+            // read the rows at all frame offsets from the trace into the buffer; data in the trace
             // table is extended over the LDE domain, so, we need to convert step in constraint
             // evaluation domain, into a step in LDE domain, in case these domains are different
             trace.read_main_trace_frame_into(step << lde_shift, &mut main_frame);
 
-            // evaluate transition constraints and save the merged result the first slot of the
-            // evaluations buffer
-            evaluations[0] = self.evaluate_main_transition(&main_frame, step, &mut t_evaluations);
+            // evaluate transition constraints and save the merged results into the first slots
+            // of the evaluations buffer (one slot per transition constraint divisor)
+            let (t_merged, b_merged) = evaluations.split_at_mut(num_divisors);
+            t_merged.fill(E::ZERO);
+            let block_row = block.as_ref().map(|block| block.row(i % block_size));
+            self.evaluate_main_transition(
+                &main_frame,
+                step,
+                block_row,
+                &mut t_evaluations,
+                t_merged,
+            );
 
             // when in debug mode, save transition constraint evaluations
             #[cfg(debug_assertions)]
             fragment.update_transition_evaluations(i, &t_evaluations, &[]);
 
-            // evaluate boundary constraints; the results go into remaining slots of the
+            // evaluate boundary constraints and save the merged result into the last slot of the
             // evaluations buffer
             let main_state = main_frame.current();
-            self.boundary_constraints.evaluate_main(
-                main_state,
-                domain,
-                step,
-                &mut evaluations[1..],
-            );
+            b_merged[0] = self.boundary_constraints.evaluate_main(main_state, domain, step);
 
             // record the result in the evaluation table
             fragment.update_row(i, &evaluations);
@@ -246,14 +396,25 @@ where
         &self,
         trace: &T,
         domain: &StarkDomain<A::BaseField>,
+        program: Option<&X::Program>,
         fragment: &mut EvaluationTableFragment<E>,
     ) {
         // initialize buffers to hold trace values and evaluation results at each step
-        let mut main_frame = EvaluationFrame::new(trace.trace_info().main_trace_width());
-        let mut aux_frame = EvaluationFrame::new(trace.trace_info().aux_segment_width());
+        let frame_offsets = self.air.context().frame_offsets();
+        let mut main_frame = EvaluationFrame::with_offsets(
+            trace.trace_info().main_trace_width(),
+            frame_offsets.to_vec(),
+        );
+        let mut aux_frame = EvaluationFrame::with_offsets(
+            trace.trace_info().aux_segment_width(),
+            frame_offsets.to_vec(),
+        );
         let mut tm_evaluations = vec![E::BaseField::ZERO; self.num_main_transition_constraints()];
         let mut ta_evaluations = vec![E::ZERO; self.num_aux_transition_constraints()];
         let mut evaluations = vec![E::ZERO; fragment.num_columns()];
+        let num_divisors = self.transition_constraints.divisors().len();
+        let mut block = program.map(|program| self.new_transition_block(program));
+        let block_size = self.backend.block_size();
 
         // this will be used to convert steps in constraint evaluation domain to steps in
         // LDE domain
@@ -262,33 +423,50 @@ where
         for i in 0..fragment.num_rows() {
             let step = i + fragment.offset();
 
+            // when constraints are compiled, evaluate main transition constraints for the next
+            // block of rows at the first row of the block
+            if let (Some(program), Some(block)) = (program, block.as_mut()) {
+                if i % block_size == 0 {
+                    self.evaluate_main_transition_block(trace, domain, fragment, i, program, block);
+                }
+            }
+
             // read both the main and the auxiliary evaluation frames from the trace
             trace.read_main_trace_frame_into(step << lde_shift, &mut main_frame);
             trace.read_aux_trace_frame_into(step << lde_shift, &mut aux_frame);
 
-            // evaluate transition constraints and save the merged result the first slot of the
-            // evaluations buffer; we evaluate and compose constraints in the same function, we
-            // can just add up the results of evaluating main and auxiliary constraints.
-            evaluations[0] = self.evaluate_main_transition(&main_frame, step, &mut tm_evaluations);
-
-            evaluations[0] +=
-                self.evaluate_aux_transition(&main_frame, &aux_frame, step, &mut ta_evaluations);
+            // evaluate transition constraints and save the merged results into the first slots
+            // of the evaluations buffer (one slot per transition constraint divisor); we evaluate
+            // and compose constraints in the same function, so we can just add up the results of
+            // evaluating main and auxiliary constraints.
+            let (t_merged, b_merged) = evaluations.split_at_mut(num_divisors);
+            t_merged.fill(E::ZERO);
+            let block_row = block.as_ref().map(|block| block.row(i % block_size));
+            self.evaluate_main_transition(
+                &main_frame,
+                step,
+                block_row,
+                &mut tm_evaluations,
+                t_merged,
+            );
+            self.evaluate_aux_transition(
+                &main_frame,
+                &aux_frame,
+                step,
+                &mut ta_evaluations,
+                t_merged,
+            );
 
             // when in debug mode, save transition constraint evaluations
             #[cfg(debug_assertions)]
             fragment.update_transition_evaluations(i, &tm_evaluations, &ta_evaluations);
 
-            // evaluate boundary constraints; the results go into remaining slots of the
+            // evaluate boundary constraints and save the merged result into the last slot of the
             // evaluations buffer
             let main_state = main_frame.current();
             let aux_state = aux_frame.current();
-            self.boundary_constraints.evaluate_all(
-                main_state,
-                aux_state,
-                domain,
-                step,
-                &mut evaluations[1..],
-            );
+            b_merged[0] =
+                self.boundary_constraints.evaluate_all(main_state, aux_state, domain, step);
 
             // record the result in the evaluation table
             fragment.update_row(i, &evaluations);
@@ -321,47 +499,92 @@ where
     // TRANSITION CONSTRAINT EVALUATORS
     // --------------------------------------------------------------------------------------------
 
+    /// Evaluates compiled transition constraints of the main execution trace over the block of
+    /// rows of the fragment starting at the specified row, and saves the evaluations into the
+    /// `block`.
+    fn evaluate_main_transition_block<T: TraceLde<E>>(
+        &self,
+        trace: &T,
+        domain: &StarkDomain<A::BaseField>,
+        fragment: &EvaluationTableFragment<E>,
+        block_start: usize,
+        program: &X::Program,
+        block: &mut TransitionBlock<E::BaseField>,
+    ) {
+        let mut main_frame = EvaluationFrame::with_offsets(
+            trace.trace_info().main_trace_width(),
+            self.air.context().frame_offsets().to_vec(),
+        );
+        let lde_shift = domain.ce_to_lde_blowup().trailing_zeros();
+        let num_rows = self.backend.block_size().min(fragment.num_rows() - block_start);
+        block.frames.clear();
+        for row in 0..num_rows {
+            let step = block_start + row + fragment.offset();
+            trace.read_main_trace_frame_into(step << lde_shift, &mut main_frame);
+            block.frames.push_row(&main_frame, self.periodic_values.get_row(step));
+        }
+
+        let evaluations = &mut block.evaluations[..num_rows * block.num_constraints];
+        self.backend.evaluate_block(program, &block.frames, evaluations);
+    }
+
     /// Evaluates transition constraints of the main execution trace at the specified step of the
     /// constraint evaluation domain.
     ///
-    /// `x` is the corresponding domain value at the specified step. That is, x = s * g^step,
-    /// where g is the generator of the constraint evaluation domain, and s is the domain offset.
+    /// If the constraints are compiled, their evaluations are taken from the specified row of
+    /// the evaluated block instead.
+    ///
+    /// The evaluations are merged using random linear combinations, one for each distinct
+    /// transition constraint divisor, and the merged values are added to the corresponding
+    /// slots of the `result` buffer.
     fn evaluate_main_transition(
         &self,
         main_frame: &EvaluationFrame<E::BaseField>,
         step: usize,
+        block_row: Option<&[E::BaseField]>,
         evaluations: &mut [E::BaseField],
-    ) -> E {
-        // TODO: use a more efficient way to zero out memory
-        evaluations.fill(E::BaseField::ZERO);
-
-        // get periodic values at the evaluation step
-        let periodic_values = self.periodic_values.get_row(step);
-
-        // evaluate transition constraints over the main segment of the execution trace and save
-        // the results into evaluations buffer
-        self.air.evaluate_transition(main_frame, periodic_values, evaluations);
+        result: &mut [E],
+    ) {
+        match block_row {
+            Some(block_row) => evaluations.copy_from_slice(block_row),
+            None => {
+                // TODO: use a more efficient way to zero out memory
+                evaluations.fill(E::BaseField::ZERO);
+
+                // get periodic values at the evaluation step
+                let periodic_values = self.periodic_values.get_row(step);
+
+                // evaluate transition constraints over the main segment of the execution trace
+                // and save the results into evaluations buffer
+                self.air.evaluate_transition(main_frame, periodic_values, evaluations);
+            },
+        }
 
-        // merge transition constraint evaluations into a single value and return it;
-        // we can do this here because all transition constraints have the same divisor.
-        evaluations
+        // merge transition constraint evaluations into a single value per divisor; we can do
+        // this here because all constraints in a group have the same divisor.
+        for ((&const_eval, &coef), &divisor_idx) in evaluations
             .iter()
             .zip(self.transition_constraints.main_constraint_coef().iter())
-            .fold(E::ZERO, |acc, (&const_eval, &coef)| acc + coef.mul_base(const_eval))
+            .zip(self.transition_constraints.main_divisor_indices())
+        {
+            result[divisor_idx] += coef.mul_base(const_eval);
+        }
     }
 
     /// Evaluates all transition constraints (i.e., for main and the auxiliary trace segment) at the
     /// specified step of the constraint evaluation domain.
     ///
-    /// `x` is the corresponding domain value at the specified step. That is, x = s * g^step,
-    /// where g is the generator of the constraint evaluation domain, and s is the domain offset.
+    /// The evaluations are merged using random linear combinations, one for each distinct
+    /// transition constraint divisor, and the merged values are added to the corresponding
+    /// slots of the `result` buffer.
     fn evaluate_aux_transition(
         &self,
         main_frame: &EvaluationFrame<E::BaseField>,
         aux_frame: &EvaluationFrame<E>,
         step: usize,
         evaluations: &mut [E],
-    ) -> E {
+        result: &mut [E],
+    ) {
         // TODO: use a more efficient way to zero out memory
         evaluations.fill(E::ZERO);
 
@@ -380,12 +603,54 @@ where
             evaluations,
         );
 
-        // merge transition constraint evaluations into a single value and return it;
-        // we can do this here because all transition constraints have the same divisor.
-        evaluations
+        // merge transition constraint evaluations into a single value per divisor; we can do
+        // this here because all constraints in a group have the same divisor.
+        for ((&const_eval, &coef), &divisor_idx) in evaluations
             .iter()
             .zip(self.transition_constraints.aux_constraint_coef().iter())
-            .fold(E::ZERO, |acc, (&const_eval, &coef)| acc + coef * const_eval)
+            .zip(self.transition_constraints.aux_divisor_indices())
+        {
+            result[divisor_idx] += coef * const_eval;
+        }
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Compiles symbolic transition constraints of the AIR using the backend of this evaluator,
+    /// or returns `None` if the AIR does not describe its constraints symbolically.
+    fn compile_constraints(&self) -> Option<X::Program> {
+        let constraints = self.air.get_symbolic_constraints()?;
+        let program = self.backend.compile(
+            constraints,
+            self.air.context().num_frame_rows(),
+            self.air.trace_info().main_trace_width(),
+            self.periodic_values.num_columns(),
+        );
+        assert_eq!(
+            self.backend.num_constraints(&program),
+            self.num_main_transition_constraints(),
+            "number of symbolic constraints must match the number of main transition constraints"
+        );
+        Some(program)
+    }
+
+    /// Returns a new block for evaluating the specified compiled constraints over blocks of rows
+    /// of a fragment.
+    fn new_transition_block(&self, program: &X::Program) -> TransitionBlock<E::BaseField> {
+        let block_size = self.backend.block_size();
+        assert!(block_size > 0, "block size of constraint evaluation backend must be positive");
+        let num_constraints = self.backend.num_constraints(program);
+        TransitionBlock {
+            frames: FrameBlock::new(
+                block_size,
+                self.air.context().num_frame_rows(),
+                self.air.trace_info().main_trace_width(),
+                self.periodic_values.num_columns(),
+            ),
+            evaluations: vec![E::BaseField::ZERO; block_size * num_constraints],
+            num_constraints,
+        }
     }
 
     // ACCESSORS
@@ -402,3 +667,74 @@ where
         self.transition_constraints.num_aux_constraints()
     }
 }
+
+// TRANSITION BLOCK
+// ================================================================================================
+
+/// Frames of a block of rows of a fragment, together with evaluations of compiled main
+/// transition constraints at these rows.
+struct TransitionBlock<B: StarkField> {
+    frames: FrameBlock<B>,
+    evaluations: Vec<B>,
+    num_constraints: usize,
+}
+
+impl<B: StarkField> TransitionBlock<B> {
+    /// Returns evaluations of all constraints at the specified row of this block.
+    fn row(&self, row: usize) -> &[B] {
+        &self.evaluations[row * self.num_constraints..][..self.num_constraints]
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the number of fragments into which a constraint evaluation table with the specified
+/// number of rows should be split when `num_threads` threads are available.
+///
+/// The number of fragments n is selected among powers of two up to `num_threads` rounded up to
+/// the next power of two as the one minimizing the estimated evaluation time
+/// ceil(n / num_threads) * (num_rows / n) * row_cost + n * FRAGMENT_OVERHEAD. Thus, the
+/// evaluation table is not split into more fragments than there are threads (fragments beyond
+/// that would run in additional rounds), and it is split into fewer fragments when rows are too
+/// cheap to amortize the per-fragment overhead.
+fn get_num_fragments(num_rows: usize, row_cost: usize, num_threads: usize) -> usize {
+    if num_rows < MIN_CONCURRENT_DOMAIN_SIZE {
+        return 1;
+    }
+
+    let estimate_cost = |num_fragments: usize| {
+        num_fragments.div_ceil(num_threads) * (num_rows / num_fragments) * row_cost
+            + num_fragments * FRAGMENT_OVERHEAD
+    };
+
+    let max_fragments = num_threads.next_power_of_two();
+    (0..=max_fragments.ilog2())
+        .map(|log_num_fragments| 1 << log_num_fragments)
+        .min_by_key(|&num_fragments| estimate_cost(num_fragments))
+        .expect("there is at least one candidate number of fragments")
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::get_num_fragments;
+
+    #[test]
+    fn num_fragments() {
+        // small domains are always evaluated in a single fragment
+        assert_eq!(1, get_num_fragments(4096, 100, 16));
+
+        // a single thread always results in a single fragment
+        assert_eq!(1, get_num_fragments(1 << 20, 100, 1));
+
+        // the number of fragments does not exceed the number of threads
+        assert_eq!(16, get_num_fragments(1 << 20, 100, 16));
+        assert_eq!(8, get_num_fragments(1 << 20, 100, 12));
+
+        // cheap rows in a small domain are not worth splitting across all threads
+        assert_eq!(2, get_num_fragments(8192, 4, 16));
+    }
+}
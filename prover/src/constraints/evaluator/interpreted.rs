@@ -0,0 +1,674 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::{collections::BTreeMap, vec::Vec};
+use core::mem;
+
+use air::{NodeId, SymbolicConstraints, SymbolicNode};
+use math::StarkField;
+
+use super::FrameBlock;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of rows of the constraint evaluation domain evaluated by a single pass over the
+/// instructions of interpreted constraints.
+pub const BATCH_SIZE: usize = 64;
+
+// INTERPRETED CONSTRAINTS
+// ================================================================================================
+
+/// Transition constraints lowered from their [SymbolicConstraints] into a sequence of register
+/// instructions, together with a batched interpreter for these instructions.
+///
+/// Lowering folds constant sub-expressions, eliminates common sub-expressions and nodes which
+/// do not contribute to any constraint, expands exponentiations into chains of squarings and
+/// multiplications, and assigns the remaining values to a small set of registers which are reused
+/// once the values they hold are no longer needed.
+///
+/// Each register holds values for a batch of up to [BATCH_SIZE] rows, and each instruction is
+/// applied to all rows of a batch at once. Thus, the cost of dispatching an instruction is
+/// amortized over the batch, and the inner loops are simple enough to be vectorized by the
+/// compiler. No native code is generated at runtime: the same interpreter executes the
+/// instructions of all constraint sets. The `constraint_evaluation` benchmark of this crate
+/// compares this interpreter with evaluation of constraints via
+/// [Air::evaluate_transition()](air::Air::evaluate_transition).
+pub struct InterpretedConstraints<B: StarkField> {
+    inputs: Vec<(usize, Input)>,
+    instructions: Vec<Instruction<B>>,
+    outputs: Vec<Output<B>>,
+    num_registers: usize,
+}
+
+impl<B: StarkField> InterpretedConstraints<B> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Lowers the specified symbolic constraints for evaluation over frames with the specified
+    /// number of rows and columns, and the specified number of periodic columns.
+    ///
+    /// # Panics
+    /// Panics if any of the nodes refers to a row, a column, or a periodic column outside of the
+    /// specified bounds.
+    pub fn new(
+        constraints: &SymbolicConstraints<B>,
+        num_frame_rows: usize,
+        num_columns: usize,
+        num_periodic_columns: usize,
+    ) -> Self {
+        for node in constraints.nodes() {
+            match *node {
+                SymbolicNode::Trace { row, column } => {
+                    assert!(
+                        row < num_frame_rows,
+                        "symbolic constraints refer to frame row {row}, but the frame has only \
+                        {num_frame_rows} rows"
+                    );
+                    assert!(
+                        column < num_columns,
+                        "symbolic constraints refer to column {column}, but the main trace has \
+                        only {num_columns} columns"
+                    );
+                },
+                SymbolicNode::Periodic(index) => assert!(
+                    index < num_periodic_columns,
+                    "symbolic constraints refer to periodic column {index}, but there are only \
+                    {num_periodic_columns} periodic columns"
+                ),
+                _ => (),
+            }
+        }
+
+        let program = Program::lower(constraints);
+        program.allocate_registers()
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of constraints evaluated by this program.
+    pub fn num_constraints(&self) -> usize {
+        self.outputs.len()
+    }
+
+    /// Returns the number of instructions executed per batch of rows.
+    #[cfg(test)]
+    pub fn num_instructions(&self) -> usize {
+        self.instructions.len()
+    }
+
+    // EVALUATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new batch of registers for evaluating these constraints.
+    pub fn new_batch(&self) -> ConstraintBatch<B> {
+        ConstraintBatch {
+            registers: vec![vec![B::ZERO; BATCH_SIZE]; self.num_registers],
+        }
+    }
+
+    /// Loads values of `num_rows` rows of the block starting at the specified offset into the
+    /// first rows of the batch.
+    pub fn load_rows(
+        &self,
+        batch: &mut ConstraintBatch<B>,
+        block: &FrameBlock<B>,
+        offset: usize,
+        num_rows: usize,
+    ) {
+        debug_assert!(num_rows <= BATCH_SIZE, "batch cannot have more than {BATCH_SIZE} rows");
+        for &(register, input) in self.inputs.iter() {
+            let values = match input {
+                Input::Trace { row, column } => block.trace_column(row, column),
+                Input::Periodic(index) => block.periodic_column(index),
+            };
+            batch.registers[register][..num_rows]
+                .copy_from_slice(&values[offset..offset + num_rows]);
+        }
+    }
+
+    /// Evaluates the constraints for the first `num_rows` rows of the batch by interpreting the
+    /// instructions over these rows.
+    pub fn evaluate(&self, batch: &mut ConstraintBatch<B>, num_rows: usize) {
+        debug_assert!(num_rows <= BATCH_SIZE, "batch cannot have more than {BATCH_SIZE} rows");
+        let registers = &mut batch.registers;
+        for instruction in self.instructions.iter() {
+            match *instruction {
+                Instruction::Add(dst, lhs, rhs) => {
+                    apply2(registers, dst, lhs, rhs, num_rows, |a, b| a + b)
+                },
+                Instruction::Sub(dst, lhs, rhs) => {
+                    apply2(registers, dst, lhs, rhs, num_rows, |a, b| a - b)
+                },
+                Instruction::Mul(dst, lhs, rhs) => {
+                    apply2(registers, dst, lhs, rhs, num_rows, |a, b| a * b)
+                },
+                Instruction::Neg(dst, src) => apply1(registers, dst, src, num_rows, |a| -a),
+                Instruction::Square(dst, src) => {
+                    apply1(registers, dst, src, num_rows, |a| a.square())
+                },
+                Instruction::AddConst(dst, src, c) => {
+                    apply1(registers, dst, src, num_rows, |a| a + c)
+                },
+                Instruction::SubFromConst(dst, src, c) => {
+                    apply1(registers, dst, src, num_rows, |a| c - a)
+                },
+                Instruction::MulConst(dst, src, c) => {
+                    apply1(registers, dst, src, num_rows, |a| a * c)
+                },
+            }
+        }
+    }
+
+    /// Writes evaluations of all constraints at the specified row of the batch into `result`.
+    pub fn read_row(&self, batch: &ConstraintBatch<B>, row: usize, result: &mut [B]) {
+        for (result, output) in result.iter_mut().zip(self.outputs.iter()) {
+            *result = match *output {
+                Output::Register(register) => batch.registers[register][row],
+                Output::Constant(value) => value,
+            };
+        }
+    }
+}
+
+// CONSTRAINT BATCH
+// ================================================================================================
+
+/// Registers holding intermediate values of [InterpretedConstraints] for a batch of rows.
+pub struct ConstraintBatch<B: StarkField> {
+    registers: Vec<Vec<B>>,
+}
+
+// INSTRUCTIONS
+// ================================================================================================
+
+/// A value loaded into a register before evaluation of a batch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Input {
+    Trace { row: usize, column: usize },
+    Periodic(usize),
+}
+
+/// An operation over registers; the first register is always the destination, and it is always
+/// different from the source registers.
+#[derive(Debug, Clone, Copy)]
+enum Instruction<B: StarkField> {
+    Add(usize, usize, usize),
+    Sub(usize, usize, usize),
+    Mul(usize, usize, usize),
+    Neg(usize, usize),
+    Square(usize, usize),
+    AddConst(usize, usize, B),
+    SubFromConst(usize, usize, B),
+    MulConst(usize, usize, B),
+}
+
+/// Location of a constraint evaluation.
+#[derive(Debug, Clone, Copy)]
+enum Output<B: StarkField> {
+    Register(usize),
+    Constant(B),
+}
+
+// INTERMEDIATE PROGRAM
+// ================================================================================================
+
+/// A value computed by the intermediate program: either a known constant, or a value defined by
+/// the operation at the specified index.
+#[derive(Debug, Clone, Copy)]
+enum Value<B: StarkField> {
+    Constant(B),
+    Defined(usize),
+}
+
+/// An operation of the intermediate program; operands refer to previously defined operations.
+#[derive(Debug, Clone, Copy)]
+enum Operation<B: StarkField> {
+    Load(Input),
+    Add(usize, usize),
+    Sub(usize, usize),
+    Mul(usize, usize),
+    Neg(usize),
+    Square(usize),
+    AddConst(usize, B),
+    SubFromConst(usize, B),
+    MulConst(usize, B),
+}
+
+impl<B: StarkField> Operation<B> {
+    /// Returns indexes of operations defining the operands of this operation.
+    fn operands(&self) -> ([usize; 2], usize) {
+        match *self {
+            Self::Load(_) => ([0, 0], 0),
+            Self::Add(a, b) | Self::Sub(a, b) | Self::Mul(a, b) => ([a, b], 2),
+            Self::Neg(a)
+            | Self::Square(a)
+            | Self::AddConst(a, _)
+            | Self::SubFromConst(a, _)
+            | Self::MulConst(a, _) => ([a, 0], 1),
+        }
+    }
+
+    /// Returns a key which is equal for operations computing the same value from the same
+    /// operands.
+    fn key(&self) -> OperationKey {
+        let constant = |value: B| B::elements_as_bytes(&[value]).to_vec();
+        match *self {
+            Self::Load(input) => OperationKey::Load(input),
+            Self::Add(a, b) => OperationKey::Binary(0, a.min(b), a.max(b)),
+            Self::Sub(a, b) => OperationKey::Binary(1, a, b),
+            Self::Mul(a, b) => OperationKey::Binary(2, a.min(b), a.max(b)),
+            Self::Neg(a) => OperationKey::Unary(0, a),
+            Self::Square(a) => OperationKey::Unary(1, a),
+            Self::AddConst(a, c) => OperationKey::Constant(0, a, constant(c)),
+            Self::SubFromConst(a, c) => OperationKey::Constant(1, a, constant(c)),
+            Self::MulConst(a, c) => OperationKey::Constant(2, a, constant(c)),
+        }
+    }
+}
+
+/// Key used to identify common sub-expressions.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum OperationKey {
+    Load(Input),
+    Unary(u8, usize),
+    Binary(u8, usize, usize),
+    Constant(u8, usize, Vec<u8>),
+}
+
+/// Symbolic constraints lowered into a list of operations in static single assignment form.
+struct Program<B: StarkField> {
+    operations: Vec<Operation<B>>,
+    outputs: Vec<Value<B>>,
+    defined: BTreeMap<OperationKey, usize>,
+}
+
+impl<B: StarkField> Program<B> {
+    /// Lowers the specified symbolic constraints into a program, folding constants and
+    /// eliminating common sub-expressions.
+    fn lower(constraints: &SymbolicConstraints<B>) -> Self {
+        let mut program = Program {
+            operations: Vec::new(),
+            outputs: Vec::new(),
+            defined: BTreeMap::new(),
+        };
+
+        let mut values: Vec<Value<B>> = Vec::with_capacity(constraints.nodes().len());
+        let value = |values: &[Value<B>], node: NodeId| values[node.index()];
+        for node in constraints.nodes() {
+            let result = match *node {
+                SymbolicNode::Constant(c) => Value::Constant(c),
+                SymbolicNode::Trace { row, column } => {
+                    program.define(Operation::Load(Input::Trace { row, column }))
+                },
+                SymbolicNode::Periodic(index) => {
+                    program.define(Operation::Load(Input::Periodic(index)))
+                },
+                SymbolicNode::Add(a, b) => program.add(value(&values, a), value(&values, b)),
+                SymbolicNode::Sub(a, b) => program.sub(value(&values, a), value(&values, b)),
+                SymbolicNode::Mul(a, b) => program.mul(value(&values, a), value(&values, b)),
+                SymbolicNode::Neg(a) => program.neg(value(&values, a)),
+                SymbolicNode::Exp(a, power) => program.exp(value(&values, a), power),
+            };
+            values.push(result);
+        }
+
+        program.outputs =
+            constraints.constraints().iter().map(|&node| value(&values, node)).collect();
+        program
+    }
+
+    /// Assigns registers to values of this program, and returns the resulting interpreted
+    /// constraints.
+    ///
+    /// Operations which do not contribute to any of the outputs are dropped. A register is
+    /// released after the last operation reading it, and released registers are reused by
+    /// subsequent operations.
+    fn allocate_registers(self) -> InterpretedConstraints<B> {
+        // find the last operation using each value; values used by outputs stay alive until the
+        // end of the program, and values without uses are not computed at all
+        let num_operations = self.operations.len();
+        let mut last_use: Vec<Option<usize>> = vec![None; num_operations];
+        for output in self.outputs.iter() {
+            if let Value::Defined(idx) = *output {
+                last_use[idx] = Some(num_operations);
+            }
+        }
+        for (idx, operation) in self.operations.iter().enumerate().rev() {
+            if last_use[idx].is_none() {
+                continue;
+            }
+            let (operands, num_operands) = operation.operands();
+            for &operand in operands[..num_operands].iter() {
+                last_use[operand] = Some(last_use[operand].map_or(idx, |last| last.max(idx)));
+            }
+        }
+
+        let mut registers: Vec<Option<usize>> = vec![None; num_operations];
+        let mut free_registers = Vec::new();
+        let mut num_registers = 0;
+        let mut allocate = |free_registers: &mut Vec<usize>| {
+            free_registers.pop().unwrap_or_else(|| {
+                num_registers += 1;
+                num_registers - 1
+            })
+        };
+
+        // all inputs are loaded before any instruction is executed, and thus, need distinct
+        // registers
+        let mut inputs = Vec::new();
+        for (idx, operation) in self.operations.iter().enumerate() {
+            if let (Operation::Load(input), Some(_)) = (operation, last_use[idx]) {
+                registers[idx] = Some(allocate(&mut free_registers));
+                inputs.push((registers[idx].unwrap(), *input));
+            }
+        }
+
+        let mut instructions = Vec::new();
+        for (idx, operation) in self.operations.iter().enumerate() {
+            if last_use[idx].is_none() || matches!(operation, Operation::Load(_)) {
+                continue;
+            }
+
+            // the destination is allocated before the operands are released, so that it never
+            // coincides with any of the source registers
+            let dst = allocate(&mut free_registers);
+            registers[idx] = Some(dst);
+            let reg = |operand: usize| registers[operand].expect("operand is not allocated");
+            instructions.push(match *operation {
+                Operation::Add(a, b) => Instruction::Add(dst, reg(a), reg(b)),
+                Operation::Sub(a, b) => Instruction::Sub(dst, reg(a), reg(b)),
+                Operation::Mul(a, b) => Instruction::Mul(dst, reg(a), reg(b)),
+                Operation::Neg(a) => Instruction::Neg(dst, reg(a)),
+                Operation::Square(a) => Instruction::Square(dst, reg(a)),
+                Operation::AddConst(a, c) => Instruction::AddConst(dst, reg(a), c),
+                Operation::SubFromConst(a, c) => Instruction::SubFromConst(dst, reg(a), c),
+                Operation::MulConst(a, c) => Instruction::MulConst(dst, reg(a), c),
+                Operation::Load(_) => unreachable!(),
+            });
+
+            let (mut operands, num_operands) = operation.operands();
+            let operands = &mut operands[..num_operands];
+            operands.sort_unstable();
+            for (i, &operand) in operands.iter().enumerate() {
+                if last_use[operand] == Some(idx) && (i == 0 || operands[i - 1] != operand) {
+                    free_registers.push(reg(operand));
+                }
+            }
+        }
+
+        let outputs = self
+            .outputs
+            .iter()
+            .map(|output| match *output {
+                Value::Constant(value) => Output::Constant(value),
+                Value::Defined(idx) => {
+                    Output::Register(registers[idx].expect("output is not allocated"))
+                },
+            })
+            .collect();
+
+        InterpretedConstraints {
+            inputs,
+            instructions,
+            outputs,
+            num_registers,
+        }
+    }
+
+    // OPERATIONS
+    // --------------------------------------------------------------------------------------------
+
+    fn add(&mut self, a: Value<B>, b: Value<B>) -> Value<B> {
+        match (a, b) {
+            (Value::Constant(a), Value::Constant(b)) => Value::Constant(a + b),
+            (Value::Defined(a), Value::Constant(c)) | (Value::Constant(c), Value::Defined(a)) => {
+                if c == B::ZERO {
+                    Value::Defined(a)
+                } else {
+                    self.define(Operation::AddConst(a, c))
+                }
+            },
+            (Value::Defined(a), Value::Defined(b)) => self.define(Operation::Add(a, b)),
+        }
+    }
+
+    fn sub(&mut self, a: Value<B>, b: Value<B>) -> Value<B> {
+        match (a, b) {
+            (Value::Constant(a), Value::Constant(b)) => Value::Constant(a - b),
+            (Value::Defined(_), Value::Constant(c)) => self.add(a, Value::Constant(-c)),
+            (Value::Constant(c), Value::Defined(b)) => {
+                if c == B::ZERO {
+                    self.define(Operation::Neg(b))
+                } else {
+                    self.define(Operation::SubFromConst(b, c))
+                }
+            },
+            (Value::Defined(a), Value::Defined(b)) => self.define(Operation::Sub(a, b)),
+        }
+    }
+
+    fn mul(&mut self, a: Value<B>, b: Value<B>) -> Value<B> {
+        match (a, b) {
+            (Value::Constant(a), Value::Constant(b)) => Value::Constant(a * b),
+            (Value::Defined(a), Value::Constant(c)) | (Value::Constant(c), Value::Defined(a)) => {
+                if c == B::ZERO {
+                    Value::Constant(B::ZERO)
+                } else if c == B::ONE {
+                    Value::Defined(a)
+                } else if c == -B::ONE {
+                    self.define(Operation::Neg(a))
+                } else {
+                    self.define(Operation::MulConst(a, c))
+                }
+            },
+            (Value::Defined(a), Value::Defined(b)) if a == b => self.define(Operation::Square(a)),
+            (Value::Defined(a), Value::Defined(b)) => self.define(Operation::Mul(a, b)),
+        }
+    }
+
+    fn neg(&mut self, a: Value<B>) -> Value<B> {
+        match a {
+            Value::Constant(a) => Value::Constant(-a),
+            Value::Defined(a) => self.define(Operation::Neg(a)),
+        }
+    }
+
+    /// Expands exponentiation into a chain of squarings and multiplications, processing bits of
+    /// the power from the most significant one.
+    fn exp(&mut self, base: Value<B>, power: u32) -> Value<B> {
+        match base {
+            Value::Constant(base) => Value::Constant(base.exp(power.into())),
+            Value::Defined(_) if power == 0 => Value::Constant(B::ONE),
+            Value::Defined(_) => {
+                let mut result = base;
+                for bit in (0..power.ilog2()).rev() {
+                    result = self.mul(result, result);
+                    if (power >> bit) & 1 == 1 {
+                        result = self.mul(result, base);
+                    }
+                }
+                result
+            },
+        }
+    }
+
+    /// Appends the specified operation to this program, unless an equivalent operation has
+    /// already been defined, and returns the value computed by the operation.
+    fn define(&mut self, operation: Operation<B>) -> Value<B> {
+        let key = operation.key();
+        let idx = *self.defined.entry(key).or_insert_with(|| {
+            self.operations.push(operation);
+            self.operations.len() - 1
+        });
+        Value::Defined(idx)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Computes `registers[dst][i] = op(registers[lhs][i], registers[rhs][i])` for the first
+/// `num_rows` rows; `dst` must be different from `lhs` and `rhs`.
+#[inline(always)]
+fn apply2<B: StarkField>(
+    registers: &mut [Vec<B>],
+    dst: usize,
+    lhs: usize,
+    rhs: usize,
+    num_rows: usize,
+    op: impl Fn(B, B) -> B,
+) {
+    let mut result = mem::take(&mut registers[dst]);
+    for ((result, &a), &b) in result[..num_rows]
+        .iter_mut()
+        .zip(registers[lhs][..num_rows].iter())
+        .zip(registers[rhs][..num_rows].iter())
+    {
+        *result = op(a, b);
+    }
+    registers[dst] = result;
+}
+
+/// Computes `registers[dst][i] = op(registers[src][i])` for the first `num_rows` rows; `dst`
+/// must be different from `src`.
+#[inline(always)]
+fn apply1<B: StarkField>(
+    registers: &mut [Vec<B>],
+    dst: usize,
+    src: usize,
+    num_rows: usize,
+    op: impl Fn(B) -> B,
+) {
+    let mut result = mem::take(&mut registers[dst]);
+    for (result, &a) in result[..num_rows].iter_mut().zip(registers[src][..num_rows].iter()) {
+        *result = op(a);
+    }
+    registers[dst] = result;
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use air::{EvaluationFrame, SymbolicConstraints};
+    use math::{fields::f64::BaseElement, FieldElement};
+    use rand_utils::rand_vector;
+
+    use super::{FrameBlock, InterpretedConstraints, BATCH_SIZE};
+
+    /// Builds constraints exercising all node types, including constant sub-expressions, common
+    /// sub-expressions, and nodes which are not used by any constraint.
+    fn build_constraints() -> SymbolicConstraints<BaseElement> {
+        let mut c = SymbolicConstraints::new();
+        let (a, b, c2) = (c.current(0), c.current(1), c.current(2));
+        let (a_next, b_next) = (c.next(0), c.next(1));
+        let k = c.periodic(0);
+
+        // a' = a^7 + k * b
+        let a7 = c.exp(a, 7);
+        let kb = c.mul(k, b);
+        let sum = c.add(a7, kb);
+        let c0 = c.sub(a_next, sum);
+        c.enforce(c0);
+
+        // b' = -(a * a) * 3 + (5 - c) ; includes a constant sub-expression (2 + 1)
+        let (two, one, five) = (
+            c.constant(BaseElement::new(2)),
+            c.constant(BaseElement::ONE),
+            c.constant(5u32.into()),
+        );
+        let three = c.add(two, one);
+        let aa = c.mul(a, a);
+        let neg_aa = c.neg(aa);
+        let t = c.mul(neg_aa, three);
+        let u = c.sub(five, c2);
+        let v = c.add(t, u);
+        let c1 = c.sub(b_next, v);
+        c.enforce(c1);
+
+        // the same expression as the first constraint, built anew
+        let a7 = c.exp(a, 7);
+        let kb = c.mul(b, k);
+        let sum = c.add(kb, a7);
+        let c2 = c.sub(a_next, sum);
+        c.enforce(c2);
+
+        // a constant constraint, and a node which is never used
+        let zero = c.constant(BaseElement::ZERO);
+        let zero = c.mul(a, zero);
+        c.enforce(zero);
+        let _unused = c.mul(b_next, b_next);
+
+        c
+    }
+
+    #[test]
+    fn interpreted_constraints_evaluation() {
+        let constraints = build_constraints();
+        let program = InterpretedConstraints::new(&constraints, 2, 3, 1);
+        assert_eq!(constraints.num_constraints(), program.num_constraints());
+
+        let num_rows = BATCH_SIZE - 3;
+        let frames = (0..num_rows)
+            .map(|_| EvaluationFrame::from_rows(rand_vector(3), rand_vector(3)))
+            .collect::<Vec<_>>();
+        let periodic_values = (0..num_rows).map(|_| rand_vector(1)).collect::<Vec<_>>();
+
+        let mut block = FrameBlock::new(BATCH_SIZE, 2, 3, 1);
+        for (frame, periodic_values) in frames.iter().zip(periodic_values.iter()) {
+            block.push_row(frame, periodic_values);
+        }
+        let mut batch = program.new_batch();
+        program.load_rows(&mut batch, &block, 0, num_rows);
+        program.evaluate(&mut batch, num_rows);
+
+        let mut expected = vec![BaseElement::ZERO; constraints.num_constraints()];
+        let mut actual = vec![BaseElement::ZERO; constraints.num_constraints()];
+        for (row, (frame, periodic_values)) in frames.iter().zip(periodic_values.iter()).enumerate()
+        {
+            constraints.evaluate(frame, periodic_values, &mut expected);
+            program.read_row(&batch, row, &mut actual);
+            assert_eq!(expected, actual);
+        }
+    }
+
+    #[test]
+    fn interpreted_constraints_simplification() {
+        let mut c = SymbolicConstraints::<BaseElement>::new();
+        let a = c.current(0);
+        let a_next = c.next(0);
+
+        // a' - a^2 is enforced twice, and a' * 0 + 7 folds into a constant
+        let a2 = c.exp(a, 2);
+        let c0 = c.sub(a_next, a2);
+        c.enforce(c0);
+        let a2 = c.mul(a, a);
+        let c1 = c.sub(a_next, a2);
+        c.enforce(c1);
+        let zero = c.constant(BaseElement::ZERO);
+        let seven = c.constant(BaseElement::new(7));
+        let t = c.mul(a_next, zero);
+        let c2 = c.add(t, seven);
+        c.enforce(c2);
+
+        // only a squaring and a subtraction are needed
+        let program = InterpretedConstraints::new(&c, 2, 1, 0);
+        assert_eq!(2, program.num_instructions());
+    }
+
+    #[test]
+    #[should_panic(expected = "refer to column 3, but the main trace has only 3 columns")]
+    fn interpreted_constraints_invalid_column() {
+        let mut c = SymbolicConstraints::<BaseElement>::new();
+        let a = c.current(3);
+        c.enforce(a);
+        InterpretedConstraints::new(&c, 2, 3, 0);
+    }
+}
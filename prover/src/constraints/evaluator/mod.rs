@@ -17,7 +17,13 @@ use boundary::BoundaryConstraints;
 mod lagrange;
 
 mod periodic_table;
-use periodic_table::PeriodicValueTable;
+pub(crate) use periodic_table::PeriodicValueTable;
+
+mod interpreted;
+use interpreted::{InterpretedConstraints, BATCH_SIZE};
+
+mod backend;
+pub use backend::{ConstraintEvaluationBackend, CpuEvaluationBackend, FrameBlock};
 
 // CONSTRAINT EVALUATOR TRAIT
 // ================================================================================================
@@ -27,6 +33,18 @@ use periodic_table::PeriodicValueTable;
 /// The logic for evaluating AIR constraints over a single evaluation frame is defined by the [Air]
 /// associated type, and the purpose of this trait is to execute this logic over all evaluation
 /// frames in an extended execution trace.
+///
+/// A custom implementation of this trait can be provided to the prover via
+/// [Prover::new_evaluator()](crate::Prover::new_evaluator). This can be used to delegate
+/// constraint evaluation to non-CPU hardware (e.g., to evaluate constraints of wide AIRs on a
+/// GPU); when constraints are described symbolically, it is usually enough to plug a
+/// [ConstraintEvaluationBackend] into the [DefaultConstraintEvaluator] instead. A custom
+/// implementation must return the same values as the [DefaultConstraintEvaluator]:
+/// for every point of the constraint evaluation domain, evaluations of transition constraints
+/// combined using the composition coefficients and divided by their divisors (see
+/// [TransitionConstraints](air::TransitionConstraints)), plus evaluations of boundary constraints
+/// combined and divided in the same way. Interpolation of the resulting evaluations and all
+/// subsequent steps of proof generation are performed by the prover.
 pub trait ConstraintEvaluator<E: FieldElement> {
     /// AIR constraints for the computation described by this evaluator.
     type Air: Air<BaseField = E::BaseField>;
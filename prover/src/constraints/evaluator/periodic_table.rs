@@ -9,6 +9,7 @@ use air::Air;
 use math::{fft, StarkField};
 use utils::uninit_vector;
 
+#[derive(Clone)]
 pub struct PeriodicValueTable<B: StarkField> {
     values: Vec<B>,
     length: usize,
@@ -75,6 +76,10 @@ impl<B: StarkField> PeriodicValueTable<B> {
         self.width == 0
     }
 
+    pub fn num_columns(&self) -> usize {
+        self.width
+    }
+
     pub fn get_row(&self, ce_step: usize) -> &[B] {
         if self.is_empty() {
             &[]
@@ -125,7 +130,7 @@ mod tests {
                 domain
                     .iter()
                     .map(|&x| {
-                        let x = x.exp((num_cycles as u32).into());
+                        let x = x.exp((num_cycles as u64).into());
                         polynom::eval(poly, x)
                     })
                     .collect::<Vec<_>>()
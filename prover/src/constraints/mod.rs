@@ -6,9 +6,14 @@
 use super::{ColMatrix, ConstraintDivisor, RowMatrix, StarkDomain};
 
 mod evaluator;
-pub use evaluator::{ConstraintEvaluator, DefaultConstraintEvaluator};
+pub(crate) use evaluator::PeriodicValueTable;
+pub use evaluator::{
+    ConstraintEvaluationBackend, ConstraintEvaluator, CpuEvaluationBackend,
+    DefaultConstraintEvaluator, FrameBlock,
+};
 
 mod composition_poly;
+pub(crate) use composition_poly::CompositionMask;
 pub use composition_poly::{CompositionPoly, CompositionPolyTrace};
 
 mod evaluation_table;
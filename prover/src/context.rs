@@ -0,0 +1,98 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use air::Air;
+use math::StarkField;
+use utils::BufferPool;
+
+use crate::{constraints::PeriodicValueTable, StarkDomain};
+
+// PROVER CONTEXT
+// ================================================================================================
+
+/// Pre-computed data which can be reused across proofs for the same AIR and proof options.
+///
+/// Building a context performs the part of proof setup which does not depend on the execution
+/// trace or on public inputs. Specifically, a context holds:
+/// * A [StarkDomain] (which includes twiddles for the trace domain and the constraint evaluation
+///   domain).
+/// * Evaluations of periodic columns of the AIR over the constraint evaluation domain.
+///
+/// A context also holds a [BufferPool] from which the default prover components take memory for
+/// their largest data structures (i.e., the constraint evaluation table, the LDEs of trace
+/// segments, and the FRI layers), and to which this memory is returned once a proof has been
+/// generated. Thus, a long-lived prover which reuses a context does not need to allocate these
+/// data structures anew for every proof. The trace LDE uses the pool only when instantiated via
+/// [DefaultTraceLde::new_with_buffer_pool()]. Clones of a context share the same pool.
+///
+/// A context can be used to generate proofs for any instance of the AIR which has the same trace
+/// length and proof options as the instance for which the context was built (see
+/// [ProverContext::is_compatible()]). To use a context, return it from
+/// [Prover::prover_context()](crate::Prover::prover_context) method, and instantiate the
+/// constraint evaluator via [DefaultConstraintEvaluator::new_with_context()] (when the default
+/// constraint evaluator is used).
+///
+/// Periodic column values are assumed to be the same for all instances of the AIR (i.e., they do
+/// not depend on public inputs).
+///
+/// [DefaultConstraintEvaluator::new_with_context()]:
+/// crate::DefaultConstraintEvaluator::new_with_context
+/// [DefaultTraceLde::new_with_buffer_pool()]: crate::DefaultTraceLde::new_with_buffer_pool
+#[derive(Clone)]
+pub struct ProverContext<B: StarkField> {
+    domain: StarkDomain<B>,
+    periodic_values: PeriodicValueTable<B>,
+    ce_blowup_factor: usize,
+    buffer_pool: BufferPool<B>,
+}
+
+impl<B: StarkField> ProverContext<B> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new context for the specified instance of an AIR.
+    pub fn new<A: Air<BaseField = B>>(air: &A) -> Self {
+        Self {
+            domain: StarkDomain::new(air),
+            periodic_values: PeriodicValueTable::new(air),
+            ce_blowup_factor: air.ce_blowup_factor(),
+            buffer_pool: BufferPool::new(),
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the STARK domain of this context.
+    pub fn domain(&self) -> &StarkDomain<B> {
+        &self.domain
+    }
+
+    /// Returns the pool of buffers which are reused across proofs generated with this context.
+    pub fn buffer_pool(&self) -> &BufferPool<B> {
+        &self.buffer_pool
+    }
+
+    /// Returns true if this context can be used to generate proofs for the specified instance of
+    /// an AIR.
+    ///
+    /// This is the case when the instance has the same trace length, constraint evaluation
+    /// domain, LDE domain, and domain offset as the instance for which this context was built.
+    pub fn is_compatible<A: Air<BaseField = B>>(&self, air: &A) -> bool {
+        self.domain.trace_length() == air.trace_length()
+            && self.domain.ce_domain_size() == air.ce_domain_size()
+            && self.domain.lde_domain_size() == air.lde_domain_size()
+            && self.domain.offset() == air.domain_offset()
+            && self.ce_blowup_factor == air.ce_blowup_factor()
+    }
+
+    // CRATE ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns evaluations of periodic columns over the constraint evaluation domain.
+    pub(crate) fn periodic_values(&self) -> &PeriodicValueTable<B> {
+        &self.periodic_values
+    }
+}
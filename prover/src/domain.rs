@@ -12,6 +12,7 @@ use math::{fft, get_power_series, StarkField};
 // ================================================================================================
 
 /// Info about domains related to specific instance of proof generation.
+#[derive(Clone)]
 pub struct StarkDomain<B: StarkField> {
     /// Twiddles which can be used to evaluate polynomials in the trace domain. Length of this
     /// vector is half the length of the trace domain size.
@@ -21,6 +21,26 @@ pub enum ProverError {
     /// This error occurs when the base field specified by the AIR does not support field extension
     /// of degree specified by proof options.
     UnsupportedFieldExtension(usize),
+    /// This error occurs when the size of digests produced by the hash function used by the
+    /// prover does not match the digest size specified by proof options.
+    InconsistentDigestSize(usize, usize),
+    /// This error occurs when the size of digests produced by the hash function used by the
+    /// prover for FRI layer commitments does not match the FRI digest size specified by proof
+    /// options.
+    InconsistentFriDigestSize(usize, usize),
+    /// This error occurs when proofs are to be generated in zero-knowledge mode, but the vector
+    /// commitment scheme used to commit to the trace and constraint evaluations is not hiding.
+    NonHidingVectorCommitment,
+    /// This error occurs when proofs are to be generated in zero-knowledge mode, but a seed for
+    /// the randomness used by the prover could not be obtained.
+    ZkSeedUnavailable,
+    /// This error occurs when proofs are to be generated in canonical mode, but zero-knowledge
+    /// mode or a hiding vector commitment scheme is used; both rely on fresh randomness, and
+    /// thus, cannot produce the same proof bytes for the same inputs.
+    RandomizedCanonicalProof,
+    /// This error occurs when the estimated amount of memory required to generate a proof exceeds
+    /// the memory budget of the prover for every available trace LDE storage option.
+    MemoryBudgetExceeded(usize, usize),
 }
 
 impl fmt::Display for ProverError {
@@ -36,6 +56,24 @@ impl fmt::Display for ProverError {
             Self::UnsupportedFieldExtension(degree) => {
                 write!(f, "field extension of degree {degree} is not supported for the specified base field")
             }
+            Self::InconsistentDigestSize(expected, actual) => {
+                write!(f, "proof options require digests of {expected} bytes, but the hash function produces digests of {actual} bytes")
+            }
+            Self::InconsistentFriDigestSize(expected, actual) => {
+                write!(f, "proof options require FRI layer digests of {expected} bytes, but the FRI hash function produces digests of {actual} bytes")
+            }
+            Self::NonHidingVectorCommitment => {
+                write!(f, "zero-knowledge mode requires a hiding vector commitment scheme")
+            }
+            Self::ZkSeedUnavailable => {
+                write!(f, "failed to obtain a random seed for zero-knowledge mode")
+            }
+            Self::RandomizedCanonicalProof => {
+                write!(f, "canonical proving cannot be combined with zero-knowledge mode or hiding vector commitments")
+            }
+            Self::MemoryBudgetExceeded(required, budget) => {
+                write!(f, "proof generation requires at least {required} bytes of memory, but the memory budget is {budget} bytes")
+            }
         }
     }
 }
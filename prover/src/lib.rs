@@ -39,19 +39,27 @@
 
 #![no_std]
 
+#[cfg(feature = "std")]
+extern crate std;
+
 #[macro_use]
 extern crate alloc;
 
+use alloc::borrow::Cow;
+
 pub use air::{
-    proof, proof::Proof, Air, AirContext, Assertion, BoundaryConstraint, BoundaryConstraintGroup,
-    ConstraintCompositionCoefficients, ConstraintDivisor, DeepCompositionCoefficients,
-    EvaluationFrame, FieldExtension, LagrangeKernelRandElements, ProofOptions, TraceInfo,
-    TransitionConstraintDegree,
+    pad_periodic_column, proof, proof::Proof, Air, AirContext, Assertion, BatchAir,
+    BatchPublicInputs, BoundaryConstraint, BoundaryConstraintGroup, CoefficientDerivation,
+    ComposedAir, ComposedPublicInputs, ConstraintCompositionCoefficients, ConstraintDivisor,
+    ConstraintExpr, DeepCompositionCoefficients, EvaluationFrame, ExprNode, FieldExtension,
+    LagrangeKernelRandElements, LookupTable, NodeId, Program, ProofOptions, PublicInputs,
+    SymbolicConstraints, SymbolicNode, TraceInfo, TransitionConstraintDegree,
 };
 use air::{AuxRandElements, GkrRandElements, PartitionOptions};
 pub use crypto;
-use crypto::{ElementHasher, RandomCoin, VectorCommitment};
-use fri::FriProver;
+use crypto::{ElementHasher, Hasher, RandomCoin, VectorCommitment};
+pub use fri;
+use fri::{FriOptions, FriProver, LowDegreeProver};
 pub use math;
 use math::{
     fft::infer_degree,
@@ -60,20 +68,27 @@ use math::{
 };
 use tracing::{event, info_span, instrument, Level};
 pub use utils::{
-    iterators, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
-    SliceReader,
+    iterators, BufferPool, ByteReader, ByteWriter, Deserializable, DeserializationError,
+    Serializable, SliceReader,
 };
 
 mod domain;
 pub use domain::StarkDomain;
 
+mod context;
+pub use context::ProverContext;
+
+mod memory;
+pub use memory::MemoryEstimate;
+
 pub mod matrix;
 use matrix::{ColMatrix, RowMatrix};
 
 mod constraints;
 pub use constraints::{
-    CompositionPoly, CompositionPolyTrace, ConstraintCommitment, ConstraintEvaluator,
-    DefaultConstraintCommitment, DefaultConstraintEvaluator,
+    CompositionPoly, CompositionPolyTrace, ConstraintCommitment, ConstraintEvaluationBackend,
+    ConstraintEvaluator, CpuEvaluationBackend, DefaultConstraintCommitment,
+    DefaultConstraintEvaluator, FrameBlock,
 };
 
 mod composer;
@@ -81,9 +96,11 @@ use composer::DeepCompositionPoly;
 
 mod trace;
 use maybe_async::{maybe_async, maybe_await};
+#[cfg(feature = "std")]
+pub use trace::DiskTraceLdeOptions;
 pub use trace::{
-    AuxTraceWithMetadata, DefaultTraceLde, Trace, TraceLde, TracePolyTable, TraceTable,
-    TraceTableFragment,
+    AuxTraceWithMetadata, DefaultTraceLde, Trace, TraceLde, TraceLdeStorage, TracePolyTable,
+    TraceTable, TraceTableFragment,
 };
 
 mod channel;
@@ -92,9 +109,32 @@ use channel::ProverChannel;
 mod errors;
 pub use errors::ProverError;
 
+mod phase;
+pub use phase::ProvingPhase;
+
+mod zk;
+use zk::ZkRandomness;
+
+#[cfg(feature = "async")]
+mod yield_now;
+
 #[cfg(test)]
 pub mod tests;
 
+// CRATE FEATURES
+// ================================================================================================
+
+/// Indicates whether this crate was compiled with the `std` feature.
+pub const STD_ENABLED: bool = cfg!(feature = "std");
+
+/// Indicates whether this crate was compiled with the `concurrent` feature, i.e., whether proofs
+/// are generated using multiple threads.
+pub const CONCURRENT_ENABLED: bool = cfg!(feature = "concurrent");
+
+/// Indicates whether this crate was compiled with the `async` feature, i.e., whether
+/// [Prover::prove()] is an `async` method.
+pub const ASYNC_ENABLED: bool = cfg!(feature = "async");
+
 // PROVER
 // ================================================================================================
 
@@ -104,6 +144,17 @@ const DEFAULT_SEGMENT_WIDTH: usize = 8;
 /// Accesses the `GkrProof` type in a [`Prover`].
 pub type ProverGkrProof<P> = <<P as Prover>::Air as Air>::GkrProof;
 
+/// Prover channel instantiated with the types specified by a [`Prover`].
+type ProverChannelOf<'a, P, E> = ProverChannel<
+    'a,
+    <P as Prover>::Air,
+    E,
+    <P as Prover>::HashFn,
+    <P as Prover>::FriHashFn,
+    <P as Prover>::RandomCoin,
+    <P as Prover>::VC,
+>;
+
 /// Defines a STARK prover for a computation.
 ///
 /// A STARK prover can be used to generate STARK proofs. The prover contains definitions of a
@@ -118,6 +169,9 @@ pub type ProverGkrProof<P> = <<P as Prover>::Air as Air>::GkrProof;
 /// 2. Invoke [Prover::prove()] method generate a proof using the trace from the previous step
 ///    as a witness.
 ///
+/// Provers which also implement the [TraceBuilder] trait can perform both steps via a single call
+/// to [TraceBuilder::build_and_prove()].
+///
 /// The generated proof is built using protocol parameters defined by the [ProofOptions] struct
 /// return from [Prover::options] method.
 ///
@@ -136,14 +190,39 @@ pub trait Prover {
     /// Execution trace of the computation described by this prover.
     type Trace: Trace<BaseField = Self::BaseField> + Send + Sync;
 
-    /// Hash function to be used.
+    /// Hash function to be used for committing to the extended execution trace and constraint
+    /// evaluations.
     type HashFn: ElementHasher<BaseField = Self::BaseField>;
 
-    /// Vector commitment scheme to be used.
+    /// Vector commitment scheme to be used for committing to the extended execution trace and
+    /// constraint evaluations.
     type VC: VectorCommitment<Self::HashFn>;
 
+    /// Hash function to be used for committing to FRI layers.
+    ///
+    /// In most cases, this should be the same as [HashFn](Prover::HashFn). A different hash
+    /// function can be used when FRI layers need to be reopened by a recursive verifier (e.g.,
+    /// an algebraic hash function for FRI layers and a faster hash function elsewhere); the
+    /// verifier must then be instantiated with the same hash function for FRI layers.
+    type FriHashFn: ElementHasher<BaseField = Self::BaseField>;
+
+    /// Vector commitment scheme to be used for committing to FRI layers.
+    type FriVC: VectorCommitment<Self::FriHashFn>;
+
     /// PRNG to be used for generating random field elements.
-    type RandomCoin: RandomCoin<BaseField = Self::BaseField, Hasher = Self::HashFn>;
+    ///
+    /// The hash function of the PRNG is used for the Fiat-Shamir transcript, and may differ from
+    /// [HashFn](Prover::HashFn) and [FriHashFn](Prover::FriHashFn): commitments are absorbed into
+    /// the PRNG after being converted into digests of its hash function via the [From] trait. For
+    /// example, a transcript can be built using an algebraic hash function while commitments are
+    /// built using a faster hash function.
+    type RandomCoin: RandomCoin<
+        BaseField = Self::BaseField,
+        Hasher: ElementHasher<
+            Digest: From<<Self::HashFn as Hasher>::Digest>
+                        + From<<Self::FriHashFn as Hasher>::Digest>,
+        >,
+    >;
 
     /// Trace low-degree extension for building the LDEs of trace segments and their commitments.
     type TraceLde<E>: TraceLde<E, HashFn = Self::HashFn, VC = Self::VC>
@@ -182,6 +261,14 @@ pub trait Prover {
     ///
     /// Returns a tuple containing a [TracePolyTable] with the trace polynomials for the main trace
     /// and a new [TraceLde] instance from which the LDE and trace commitments can be obtained.
+    ///
+    /// The returned polynomials are retained for the rest of proof generation: they are used to
+    /// compute the out-of-domain trace frame and to build the DEEP composition polynomial, and
+    /// thus trace columns never need to be interpolated a second time.
+    ///
+    /// The `storage` specifies where the LDE should be kept; the prover selects it based on
+    /// [Prover::memory_budget()]. Implementations which use [DefaultTraceLde] can pass it to
+    /// [DefaultTraceLde::with_storage()]; other implementations are free to ignore it.
     #[maybe_async]
     fn new_trace_lde<E>(
         &self,
@@ -189,6 +276,7 @@ pub trait Prover {
         main_trace: &ColMatrix<Self::BaseField>,
         domain: &StarkDomain<Self::BaseField>,
         partition_option: PartitionOptions,
+        storage: TraceLdeStorage,
     ) -> (Self::TraceLde<E>, TracePolyTable<E>)
     where
         E: FieldElement<BaseField = Self::BaseField>;
@@ -258,6 +346,144 @@ pub trait Prover {
         unimplemented!("`Prover::build_aux_trace` needs to be implemented when the trace has an auxiliary segment.")
     }
 
+    /// Returns a new low-degree prover which is used to prove that the DEEP composition
+    /// polynomial has the degree implied by the trace length.
+    ///
+    /// By default, this is a [FriProver] instantiated with the specified FRI `options`; when a
+    /// [ProverContext] is provided (see [Prover::prover_context()]), the FRI prover keeps its
+    /// layers in buffers taken from the buffer pool of the context. Overriding this method allows replacing FRI with a different low-degree testing protocol
+    /// without changing the rest of proof generation; proofs generated in this way must be
+    /// verified with the matching [LowDegreeVerifier](fri::LowDegreeVerifier).
+    fn new_low_degree_prover<E, C>(&self, options: FriOptions) -> impl LowDegreeProver<E, C>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+        C: fri::ProverChannel<E, Hasher = Self::FriHashFn>,
+    {
+        let fri_prover = FriProver::<E, C, Self::FriHashFn, Self::FriVC>::new(options);
+        match self.prover_context() {
+            Some(context) => fri_prover.with_buffer_pool(context.buffer_pool().clone()),
+            None => fri_prover,
+        }
+    }
+
+    /// Returns a new public coin instantiated with the specified `seed`.
+    ///
+    /// The public coin is used to draw all randomness which the verifier would send to the
+    /// prover in the interactive version of the protocol. The `seed` consists of the proof
+    /// context and the public inputs; as proof generation progresses, the coin is reseeded with
+    /// the commitments sent to the verifier.
+    ///
+    /// By default, the coin is instantiated via [RandomCoin::new()]. Overriding this method
+    /// allows customizing the coin (e.g., to mix values from an external randomness beacon into
+    /// the seed, or to match the transcript of another implementation). A custom coin must
+    /// still bind the `seed` into its state, as otherwise the proof would not be bound to the
+    /// computation and its public inputs; proofs generated in this way must be verified with a
+    /// coin instantiated in the same way (see `verify_with_public_coin()` in the verifier).
+    fn new_public_coin(&self, seed: &[Self::BaseField]) -> Self::RandomCoin {
+        Self::RandomCoin::new(seed)
+    }
+
+    /// Returns a context with pre-computed data which can be reused across proofs, or `None` if
+    /// no such context is available.
+    ///
+    /// When a context compatible with the instance of the AIR being proven is returned, the
+    /// prover uses the [StarkDomain] from the context instead of building a new domain for every
+    /// proof. See [ProverContext] for more details. By default, `None` is returned.
+    fn prover_context(&self) -> Option<&ProverContext<Self::BaseField>> {
+        None
+    }
+
+    /// Returns the maximum number of bytes of memory which proof generation is allowed to use, or
+    /// `None` if memory usage is not limited.
+    ///
+    /// When a budget is specified, the prover estimates the memory required to generate a proof
+    /// (see [MemoryEstimate]) before any work is done, and uses the estimate to select where the
+    /// trace LDE is kept: in memory if the estimate fits into the budget, and otherwise, in
+    /// memory until constraints have been evaluated (with rows at query positions recomputed
+    /// later) or on disk (see [TraceLdeStorage]). The selected storage is passed to
+    /// [Prover::new_trace_lde()]. If the estimate exceeds the budget with every storage option,
+    /// [ProverError::MemoryBudgetExceeded] is returned.
+    ///
+    /// Implementations of [Prover::build_constraint_commitment()] can also consult the budget to
+    /// decide how to store the data they build. By default, `None` is returned.
+    fn memory_budget(&self) -> Option<usize> {
+        None
+    }
+
+    /// Returns a secret seed for the randomness used to generate proofs in zero-knowledge mode,
+    /// or `None` if a seed cannot be obtained.
+    ///
+    /// The randomness derived from the seed is used to fill the random rows appended to the
+    /// execution trace and to mask the constraint composition polynomial. A seed must never be
+    /// reused across proofs, as this could reveal information about the witness.
+    ///
+    /// By default, the seed is obtained from the randomness of the operating system when the
+    /// crate is compiled with the `std` feature, and `None` is returned otherwise.
+    fn zk_seed(&self) -> Option<[u8; 32]> {
+        #[cfg(feature = "std")]
+        {
+            let mut seed = [0; 32];
+            getrandom::getrandom(&mut seed).ok().map(|_| seed)
+        }
+        #[cfg(not(feature = "std"))]
+        None
+    }
+
+    /// Called by the prover when the specified phase of proof generation starts.
+    ///
+    /// This method, together with [Prover::on_phase_end()], can be overridden to report progress
+    /// of long-running proofs (e.g., to record the time and the amount of memory used by every
+    /// phase). By default, this method does nothing.
+    #[allow(unused_variables)]
+    fn on_phase_start(&self, phase: ProvingPhase) {}
+
+    /// Called by the prover when the specified phase of proof generation ends.
+    ///
+    /// See [Prover::on_phase_start()] for more details. By default, this method does nothing.
+    #[allow(unused_variables)]
+    fn on_phase_end(&self, phase: ProvingPhase) {}
+
+    /// Called by the prover between consecutive phases of proof generation (e.g., after the
+    /// execution trace is committed to, and after constraints are evaluated).
+    ///
+    /// When the crate is compiled with the `async` feature, the default implementation yields
+    /// control back to the executor once, so that a long-running proof does not prevent other
+    /// tasks on the same executor from making progress. This method can be overridden to yield
+    /// via a runtime-specific mechanism (e.g., `tokio::task::yield_now()`). When the `async`
+    /// feature is not enabled, the default implementation does nothing.
+    ///
+    /// Yielding between phases does not move CPU-heavy work off the executor; see
+    /// [Prover::run_blocking()] for that.
+    #[maybe_async]
+    fn yield_between_phases(&self) {
+        #[cfg(feature = "async")]
+        yield_now::YieldNow::default().await;
+    }
+
+    /// Runs CPU-heavy work `f` performed by the prover during the specified phase of proof
+    /// generation, and returns its result.
+    ///
+    /// The prover runs the following work via this method: extension of and commitment to the
+    /// auxiliary trace segment, evaluation of constraints, construction and evaluation of the
+    /// DEEP composition polynomial, computation of FRI layers, proof-of-work grinding, and
+    /// construction of the proof object. The remaining CPU-heavy work is performed by async
+    /// methods of this trait (i.e., [Prover::new_trace_lde()] and
+    /// [Prover::build_constraint_commitment()]), which can be overridden directly.
+    ///
+    /// The default implementation calls `f` on the current thread. When the crate is compiled
+    /// with the `async` feature, this method can be overridden to run `f` via a runtime-specific
+    /// mechanism, so that proof generation does not block other tasks on the same executor
+    /// (e.g., `tokio::task::block_in_place(f)` on a multi-threaded tokio runtime). Note that `f`
+    /// is neither `Send` nor `'static`, as it borrows the state of the prover.
+    #[allow(unused_variables)]
+    #[maybe_async]
+    fn run_blocking<R, F>(&self, phase: ProvingPhase, f: F) -> R
+    where
+        F: FnOnce() -> R,
+    {
+        f()
+    }
+
     /// Returns a STARK proof attesting to a correct execution of a computation defined by the
     /// provided trace.
     ///
@@ -272,6 +498,36 @@ pub trait Prover {
         <Self::Air as Air>::PublicInputs: Send,
         <Self::Air as Air>::GkrProof: Send,
     {
+        // make sure the hash function produces digests of the size required by proof options
+        if let Some(digest_size) = self.options().digest_size() {
+            let hash_digest_size = <Self::HashFn as Hasher>::Digest::default().to_bytes().len();
+            if hash_digest_size != digest_size {
+                return Err(ProverError::InconsistentDigestSize(digest_size, hash_digest_size));
+            }
+        }
+        if let Some(digest_size) = self.options().fri_digest_size() {
+            let hash_digest_size = <Self::FriHashFn as Hasher>::Digest::default().to_bytes().len();
+            if hash_digest_size != digest_size {
+                return Err(ProverError::InconsistentFriDigestSize(digest_size, hash_digest_size));
+            }
+        }
+
+        // in zero-knowledge mode, the trace and constraint commitments must not reveal anything
+        // about the values which are not opened
+        if self.options().is_zk() && !<Self::VC as VectorCommitment<Self::HashFn>>::IS_HIDING {
+            return Err(ProverError::NonHidingVectorCommitment);
+        }
+
+        // zero-knowledge mode and hiding commitments rely on fresh randomness, and thus, cannot
+        // produce byte-stable proofs
+        if self.options().is_canonical()
+            && (self.options().is_zk()
+                || <Self::VC as VectorCommitment<Self::HashFn>>::IS_HIDING
+                || <Self::FriVC as VectorCommitment<Self::FriHashFn>>::IS_HIDING)
+        {
+            return Err(ProverError::RandomizedCanonicalProof);
+        }
+
         // figure out which version of the generic proof generation procedure to run. this is a sort
         // of static dispatch for selecting two generic parameter: extension field and hash
         // function.
@@ -317,32 +573,70 @@ pub trait Prover {
         // execution of the computation for the provided public inputs.
         let air = Self::Air::new(trace.info().clone(), pub_inputs, self.options().clone());
 
+        // make sure the data structures built during proof generation fit into the memory budget;
+        // if the trace LDE does not fit into memory, it is recomputed or kept on disk instead
+        let trace_lde_storage = match self.memory_budget() {
+            Some(budget) => MemoryEstimate::new::<_, E, Self::HashFn>(&air)
+                .select_trace_lde_storage(budget)
+                .map_err(|required| ProverError::MemoryBudgetExceeded(required, budget))?,
+            None => TraceLdeStorage::Memory,
+        };
+
         // create a channel which is used to simulate interaction between the prover and the
         // verifier; the channel will be used to commit to values and to draw randomness that
         // should come from the verifier.
-        let mut channel =
-            ProverChannel::<Self::Air, E, Self::HashFn, Self::RandomCoin, Self::VC>::new(
-                &air,
-                pub_inputs_elements,
-            );
+        let mut channel = ProverChannelOf::<Self, E>::new(&air, pub_inputs_elements, |seed| {
+            self.new_public_coin(seed)
+        });
+
+        // in zero-knowledge mode, instantiate the source of randomness used to randomize the
+        // trace and the constraint composition polynomial; this randomness is never revealed to
+        // the verifier
+        let mut zk_randomness = if air.options().is_zk() {
+            let seed = self.zk_seed().ok_or(ProverError::ZkSeedUnavailable)?;
+            Some(ZkRandomness::<Self::HashFn>::new(seed))
+        } else {
+            None
+        };
+        let num_random_rows = air.context().num_random_rows();
 
         // 1 ----- Commit to the execution trace --------------------------------------------------
+        self.on_phase_start(ProvingPhase::MainTraceCommitment);
 
         // build computation domain; this is used later for polynomial evaluations
         let lde_domain_size = air.lde_domain_size();
         let trace_length = air.trace_length();
-        let domain = info_span!("build_domain", trace_length, lde_domain_size)
-            .in_scope(|| StarkDomain::new(&air));
+        let domain = match self.prover_context().filter(|context| context.is_compatible(&air)) {
+            Some(context) => Cow::Borrowed(context.domain()),
+            None => Cow::Owned(
+                info_span!("build_domain", trace_length, lde_domain_size)
+                    .in_scope(|| StarkDomain::new(&air)),
+            ),
+        };
         assert_eq!(domain.lde_domain_size(), lde_domain_size);
         assert_eq!(domain.trace_length(), trace_length);
 
-        // commit to the main trace segment
-        let (mut trace_lde, mut trace_polys) =
-            maybe_await!(self.commit_to_main_trace_segment(&trace, &domain, &mut channel));
+        // commit to the main trace segment; in zero-knowledge mode, the last rows of the main
+        // trace segment are replaced with random values before the segment is committed to
+        let mut main_trace = trace.main_segment();
+        if let Some(zk_randomness) = zk_randomness.as_mut() {
+            zk_randomness.randomize_rows(main_trace.to_mut(), num_random_rows);
+        }
+        let (mut trace_lde, mut trace_polys) = maybe_await!(self.commit_to_main_trace_segment(
+            trace.info(),
+            &main_trace,
+            &domain,
+            trace_lde_storage,
+            &mut channel
+        ));
+        drop(main_trace);
+        self.on_phase_end(ProvingPhase::MainTraceCommitment);
+        maybe_await!(self.yield_between_phases());
 
         // build the auxiliary trace segment, and append the resulting segments to trace commitment
         // and trace polynomial table structs
         let aux_trace_with_metadata = if air.trace_info().is_multi_segment() {
+            self.on_phase_start(ProvingPhase::AuxTraceCommitment);
             let (gkr_proof, aux_rand_elements) = if air.context().has_lagrange_kernel_aux_column() {
                 let (gkr_proof, gkr_rand_elements) =
                     maybe_await!(self.generate_gkr_proof(&trace, channel.public_coin()));
@@ -363,18 +657,23 @@ pub trait Prover {
                 (None, AuxRandElements::new(rand_elements))
             };
 
-            let aux_trace = maybe_await!(self.build_aux_trace(&trace, &aux_rand_elements));
+            let mut aux_trace = maybe_await!(self.build_aux_trace(&trace, &aux_rand_elements));
+            if let Some(zk_randomness) = zk_randomness.as_mut() {
+                zk_randomness.randomize_rows(&mut aux_trace, num_random_rows);
+            }
 
             // commit to the auxiliary trace segment
             let aux_segment_polys = {
                 // extend the auxiliary trace segment and commit to the extended trace
                 let span = info_span!("commit_to_aux_trace_segment").entered();
-                let (aux_segment_polys, aux_segment_commitment) =
-                    trace_lde.set_aux_trace(&aux_trace, &domain);
+                let (aux_segment_polys, aux_segment_commitment) = maybe_await!(self
+                    .run_blocking(ProvingPhase::AuxTraceCommitment, || {
+                        trace_lde.set_aux_trace(&aux_trace, &domain)
+                    }));
 
                 // commit to the LDE of the extended auxiliary trace segment by writing its
                 // commitment into the channel
-                channel.commit_trace(aux_segment_commitment);
+                channel.commit_aux_trace(aux_segment_commitment);
 
                 drop(span);
                 aux_segment_polys
@@ -383,6 +682,8 @@ pub trait Prover {
             trace_polys
                 .add_aux_segment(aux_segment_polys, air.context().lagrange_kernel_aux_column_idx());
 
+            self.on_phase_end(ProvingPhase::AuxTraceCommitment);
+            maybe_await!(self.yield_between_phases());
             Some(AuxTraceWithMetadata { aux_trace, aux_rand_elements, gkr_proof })
         } else {
             None
@@ -408,86 +709,124 @@ pub trait Prover {
         // evaluate constraints specified by the AIR over the constraint evaluation domain, and
         // compute random linear combinations of these evaluations using coefficients drawn from
         // the channel
+        self.on_phase_start(ProvingPhase::ConstraintEvaluation);
         let ce_domain_size = air.ce_domain_size();
-        let composition_poly_trace = maybe_await!(self.new_evaluator(
+        let evaluator = maybe_await!(self.new_evaluator(
             &air,
             aux_rand_elements,
             channel.get_constraint_composition_coeffs()
-        ))
-        .evaluate(&trace_lde, &domain);
+        ));
+        let composition_poly_trace = maybe_await!(self
+            .run_blocking(ProvingPhase::ConstraintEvaluation, || {
+                evaluator.evaluate(&trace_lde, &domain)
+            }));
         assert_eq!(composition_poly_trace.num_rows(), ce_domain_size);
 
+        // the extended trace is not read after constraint evaluation; only rows at query
+        // positions are needed, and the trace LDE may choose to recompute them later
+        trace_lde.release_extended_trace();
+        self.on_phase_end(ProvingPhase::ConstraintEvaluation);
+        maybe_await!(self.yield_between_phases());
+
+        // in zero-knowledge mode, mask the constraint composition polynomial with random
+        // polynomials so that its evaluations reveal nothing about the trace
+        let composition_poly_trace = match zk_randomness.as_mut() {
+            Some(zk_randomness) => {
+                composition_poly_trace.with_mask(zk_randomness.draw_composition_mask(air.context()))
+            },
+            None => composition_poly_trace,
+        };
+
         // 3 ----- commit to constraint evaluations -----------------------------------------------
+        self.on_phase_start(ProvingPhase::ConstraintCommitment);
         let (constraint_commitment, composition_poly) = maybe_await!(self
             .commit_to_constraint_evaluations(&air, composition_poly_trace, &domain, &mut channel));
+        self.on_phase_end(ProvingPhase::ConstraintCommitment);
+        maybe_await!(self.yield_between_phases());
 
         // 4 ----- build DEEP composition polynomial ----------------------------------------------
-        let deep_composition_poly = {
-            let span = info_span!("build_deep_composition_poly").entered();
-            // draw an out-of-domain point z. Depending on the type of E, the point is drawn either
-            // from the base field or from an extension field defined by E.
-            //
-            // The purpose of sampling from the extension field here (instead of the base field) is
-            // to increase security. Soundness is limited by the size of the field that the random
-            // point is drawn from, and we can potentially save on performance by only drawing this
-            // point from an extension field, rather than increasing the size of the field overall.
-            let z = channel.get_ood_point();
-
-            // evaluate trace and constraint polynomials at the OOD point z, and send the results to
-            // the verifier. the trace polynomials are actually evaluated over two points: z and z *
-            // g, where g is the generator of the trace domain. Additionally, if the Lagrange kernel
-            // auxiliary column is present, we also evaluate that column over the points: z, z * g,
-            // z * g^2, z * g^4, ..., z * g^(2^(v-1)), where v = log(trace_len).
-            let ood_trace_states = trace_polys.get_ood_frame(z);
-            channel.send_ood_trace_states(&ood_trace_states);
-
-            let ood_evaluations = composition_poly.evaluate_at(z);
-            channel.send_ood_constraint_evaluations(&ood_evaluations);
-
-            // draw random coefficients to use during DEEP polynomial composition, and use them to
-            // initialize the DEEP composition polynomial
-            let deep_coefficients = channel.get_deep_composition_coeffs();
-            let mut deep_composition_poly = DeepCompositionPoly::new(z, deep_coefficients);
-
-            // combine all trace polynomials together and merge them into the DEEP composition
-            // polynomial
-            deep_composition_poly.add_trace_polys(trace_polys, ood_trace_states);
-
-            // merge columns of constraint composition polynomial into the DEEP composition
-            // polynomial
-            deep_composition_poly.add_composition_poly(composition_poly, ood_evaluations);
-
-            event!(Level::DEBUG, "degree: {}", deep_composition_poly.degree());
+        self.on_phase_start(ProvingPhase::DeepComposition);
+        let deep_composition_poly =
+            maybe_await!(self.run_blocking(ProvingPhase::DeepComposition, || {
+                let span = info_span!("build_deep_composition_poly").entered();
+                // draw an out-of-domain point z. Depending on the type of E, the point is drawn
+                // either from the base field or from an extension field defined by E.
+                //
+                // The purpose of sampling from the extension field here (instead of the base field)
+                // is to increase security. Soundness is limited by the size of the field that the
+                // random point is drawn from, and we can potentially save on performance by only
+                // drawing this point from an extension field, rather than increasing the size of
+                // the field overall.
+                let z = channel.get_ood_point();
+
+                // evaluate trace and constraint polynomials at the OOD point z, and send the
+                // results to the verifier. the trace polynomials are actually evaluated at points
+                // z * g^d for each evaluation frame row offset d (i.e., at z and z * g by default),
+                // where g is the generator of the trace domain. Additionally, if the Lagrange
+                // kernel auxiliary column is present, we also evaluate that column over the points:
+                // z, z * g, z * g^2, z * g^4, ..., z * g^(2^(v-1)), where v = log(trace_len).
+                let ood_trace_states = trace_polys.get_ood_frame(z, air.context().frame_offsets());
+                channel.send_ood_trace_states(&ood_trace_states);
+
+                let ood_evaluations = composition_poly.evaluate_at(z);
+                channel.send_ood_constraint_evaluations(&ood_evaluations);
+
+                // draw random coefficients to use during DEEP polynomial composition, and use them
+                // to initialize the DEEP composition polynomial
+                let deep_coefficients = channel.get_deep_composition_coeffs();
+                let mut deep_composition_poly = DeepCompositionPoly::new(z, deep_coefficients);
+
+                // combine all trace polynomials together and merge them into the DEEP composition
+                // polynomial
+                deep_composition_poly.add_trace_polys(trace_polys, ood_trace_states);
+
+                // merge columns of constraint composition polynomial into the DEEP composition
+                // polynomial
+                deep_composition_poly.add_composition_poly(composition_poly, ood_evaluations);
+
+                event!(Level::DEBUG, "degree: {}", deep_composition_poly.degree());
 
-            drop(span);
-            deep_composition_poly
-        };
+                drop(span);
+                deep_composition_poly
+            }));
 
         // make sure the degree of the DEEP composition polynomial is equal to trace polynomial
         // degree minus 1.
         assert_eq!(trace_length - 2, deep_composition_poly.degree());
 
         // 5 ----- evaluate DEEP composition polynomial over LDE domain ---------------------------
-        let deep_evaluations = {
-            let span = info_span!("evaluate_deep_composition_poly").entered();
-            let deep_evaluations = deep_composition_poly.evaluate(&domain);
-            // we check the following condition in debug mode only because infer_degree is an
-            // expensive operation
-            debug_assert_eq!(trace_length - 2, infer_degree(&deep_evaluations, domain.offset()));
+        let deep_evaluations =
+            maybe_await!(self.run_blocking(ProvingPhase::DeepComposition, || {
+                let span = info_span!("evaluate_deep_composition_poly").entered();
+                let deep_evaluations = deep_composition_poly.evaluate(&domain);
+                // we check the following condition in debug mode only because infer_degree is an
+                // expensive operation
+                debug_assert_eq!(
+                    trace_length - 2,
+                    infer_degree(&deep_evaluations, domain.offset())
+                );
 
-            drop(span);
-            deep_evaluations
-        };
+                drop(span);
+                deep_evaluations
+            }));
+        self.on_phase_end(ProvingPhase::DeepComposition);
+        maybe_await!(self.yield_between_phases());
 
         // 6 ----- compute FRI layers for the composition polynomial ------------------------------
+        self.on_phase_start(ProvingPhase::FriLayers);
         let fri_options = air.options().to_fri_options();
         let num_layers = fri_options.num_fri_layers(lde_domain_size);
-        let mut fri_prover = FriProver::<_, _, _, Self::VC>::new(fri_options);
-        info_span!("compute_fri_layers", num_layers)
-            .in_scope(|| fri_prover.build_layers(&mut channel, deep_evaluations));
+        let mut fri_prover = self.new_low_degree_prover(fri_options);
+        maybe_await!(self.run_blocking(ProvingPhase::FriLayers, || {
+            info_span!("compute_fri_layers", num_layers)
+                .in_scope(|| fri_prover.build_layers(&mut channel, deep_evaluations))
+        }));
+        self.on_phase_end(ProvingPhase::FriLayers);
+        maybe_await!(self.yield_between_phases());
 
         // 7 ----- determine query positions ------------------------------------------------------
-        let query_positions = {
+        self.on_phase_start(ProvingPhase::QueryPositions);
+        let query_positions = maybe_await!(self.run_blocking(ProvingPhase::QueryPositions, || {
             let grinding_factor = air.options().grinding_factor();
             let num_positions = air.options().num_queries();
             let span =
@@ -502,10 +841,12 @@ pub trait Prover {
 
             drop(span);
             query_positions
-        };
+        }));
+        self.on_phase_end(ProvingPhase::QueryPositions);
 
         // 8 ----- build proof object -------------------------------------------------------------
-        let proof = {
+        self.on_phase_start(ProvingPhase::ProofConstruction);
+        let proof = maybe_await!(self.run_blocking(ProvingPhase::ProofConstruction, || {
             let span = info_span!("build_proof_object").entered();
             // generate FRI proof
             let fri_proof = fri_prover.build_proof(&query_positions);
@@ -530,7 +871,8 @@ pub trait Prover {
 
             drop(span);
             proof
-        };
+        }));
+        self.on_phase_end(ProvingPhase::ProofConstruction);
 
         Ok(proof)
     }
@@ -540,19 +882,22 @@ pub trait Prover {
     #[maybe_async]
     fn commit_to_main_trace_segment<E>(
         &self,
-        trace: &Self::Trace,
+        trace_info: &TraceInfo,
+        main_trace: &ColMatrix<Self::BaseField>,
         domain: &StarkDomain<Self::BaseField>,
-        channel: &mut ProverChannel<'_, Self::Air, E, Self::HashFn, Self::RandomCoin, Self::VC>,
+        storage: TraceLdeStorage,
+        channel: &mut ProverChannelOf<'_, Self, E>,
     ) -> (Self::TraceLde<E>, TracePolyTable<E>)
     where
         E: FieldElement<BaseField = Self::BaseField>,
     {
         // extend the main execution trace and commit to the extended trace
         let (trace_lde, trace_polys) = maybe_await!(self.new_trace_lde(
-            trace.info(),
-            trace.main_segment(),
+            trace_info,
+            main_trace,
             domain,
             self.options().partition_options(),
+            storage,
         ));
 
         // get the commitment to the main trace segment LDE
@@ -560,7 +905,7 @@ pub trait Prover {
 
         // commit to the LDE of the main trace by writing the the commitment string into
         // the channel
-        channel.commit_trace(main_trace_commitment);
+        channel.commit_main_trace(main_trace_commitment);
 
         (trace_lde, trace_polys)
     }
@@ -573,7 +918,7 @@ pub trait Prover {
         air: &Self::Air,
         composition_poly_trace: CompositionPolyTrace<E>,
         domain: &StarkDomain<Self::BaseField>,
-        channel: &mut ProverChannel<'_, Self::Air, E, Self::HashFn, Self::RandomCoin, Self::VC>,
+        channel: &mut ProverChannelOf<'_, Self, E>,
     ) -> (Self::ConstraintCommitment<E>, CompositionPoly<E>)
     where
         E: FieldElement<BaseField = Self::BaseField>,
@@ -595,3 +940,38 @@ pub trait Prover {
         (constraint_commitment, composition_poly)
     }
 }
+
+// TRACE BUILDER TRAIT
+// ================================================================================================
+
+/// Defines how an execution trace of a computation is built from the inputs of the computation.
+///
+/// This trait extends [Prover] with witness generation, so that an application can encapsulate
+/// everything needed to prove a computation (i.e., the logic for building execution traces and
+/// the proof options) in a single struct. With a trace builder in place, generating a proof
+/// consists of a single call to [TraceBuilder::build_and_prove()], and cross-cutting features
+/// of proof generation (e.g., zero-knowledge, auxiliary trace segments) remain encapsulated
+/// behind the [Prover] trait.
+pub trait TraceBuilder: Prover {
+    /// Inputs of the computation from which an execution trace is built (e.g., a tuple of
+    /// public and secret inputs).
+    type Inputs;
+
+    /// Returns an execution trace of the computation for the specified inputs.
+    fn build_trace(&self, inputs: Self::Inputs) -> Self::Trace;
+
+    /// Builds an execution trace of the computation for the specified inputs, and returns a
+    /// STARK proof attesting to a correct execution of the computation.
+    ///
+    /// This is equivalent to calling [TraceBuilder::build_trace()] followed by
+    /// [Prover::prove()].
+    #[maybe_async]
+    fn build_and_prove(&self, inputs: Self::Inputs) -> Result<Proof, ProverError>
+    where
+        <Self::Air as Air>::PublicInputs: Send,
+        <Self::Air as Air>::GkrProof: Send,
+    {
+        let trace = self.build_trace(inputs);
+        maybe_await!(self.prove(trace))
+    }
+}
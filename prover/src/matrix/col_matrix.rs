@@ -57,6 +57,43 @@ impl<E: FieldElement> ColMatrix<E> {
         Self { columns }
     }
 
+    /// Returns a new [Matrix] with the specified number of columns and rows, filled with the rows
+    /// yielded by the provided iterator.
+    ///
+    /// Rows are consumed one by one and written directly into the columns of the matrix; thus,
+    /// rows generated on demand (e.g., by a virtual machine executing a program) never need to be
+    /// collected in a separate buffer.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `num_cols` is zero.
+    /// * `num_rows` is smaller than or equal to 1, or is not a power of two.
+    /// * The iterator does not yield exactly `num_rows` rows.
+    /// * Any of the yielded rows does not contain exactly `num_cols` elements.
+    pub fn from_rows<I, R>(num_cols: usize, num_rows: usize, rows: I) -> Self
+    where
+        I: IntoIterator<Item = R>,
+        R: AsRef<[E]>,
+    {
+        assert!(num_cols > 0, "a matrix must contain at least one column");
+        assert!(num_rows > 1, "number of rows in a matrix must be greater than one");
+        assert!(num_rows.is_power_of_two(), "number of rows in a matrix must be a power of 2");
+
+        let mut columns: Vec<Vec<E>> =
+            (0..num_cols).map(|_| Vec::with_capacity(num_rows)).collect();
+        for row in rows {
+            let row = row.as_ref();
+            assert_eq!(row.len(), num_cols, "all matrix rows must have {num_cols} elements");
+            assert!(columns[0].len() < num_rows, "expected {num_rows} rows, but got more");
+            for (column, &value) in columns.iter_mut().zip(row) {
+                column.push(value);
+            }
+        }
+        assert_eq!(columns[0].len(), num_rows, "expected {num_rows} rows");
+
+        Self { columns }
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -167,12 +204,12 @@ impl<E: FieldElement> ColMatrix<E> {
     // --------------------------------------------------------------------------------------------
 
     /// Returns an iterator over the columns of this matrix.
-    pub fn columns(&self) -> ColumnIter<E> {
+    pub fn columns(&self) -> ColumnIter<'_, E> {
         ColumnIter::new(self)
     }
 
     /// Returns a mutable iterator over the columns of this matrix.
-    pub fn columns_mut(&mut self) -> ColumnIterMut<E> {
+    pub fn columns_mut(&mut self) -> ColumnIterMut<'_, E> {
         ColumnIterMut::new(self)
     }
 
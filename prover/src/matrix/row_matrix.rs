@@ -6,15 +6,23 @@
 use alloc::vec::Vec;
 
 use air::PartitionOptions;
-use crypto::{ElementHasher, VectorCommitment};
+use crypto::{ElementHasher, VectorCommitment, HASH_BATCH_SIZE};
 use math::{fft, FieldElement, StarkField};
 #[cfg(feature = "concurrent")]
 use utils::iterators::*;
-use utils::{batch_iter_mut, flatten_vector_elements, uninit_vector};
+use utils::{flatten_vector_elements, uninit_vector, BufferPool};
 
 use super::{ColMatrix, Segment};
 use crate::StarkDomain;
 
+// CONSTANTS
+// ================================================================================================
+
+/// Target number of base field elements hashed by a single task when rows of a matrix are
+/// hashed concurrently.
+#[cfg(feature = "concurrent")]
+const ROW_HASHING_CHUNK_ELEMENTS: usize = 1 << 14;
+
 // ROW-MAJOR MATRIX
 // ================================================================================================
 
@@ -84,6 +92,31 @@ impl<E: FieldElement> RowMatrix<E> {
     pub fn evaluate_polys_over<const N: usize>(
         polys: &ColMatrix<E>,
         domain: &StarkDomain<E::BaseField>,
+    ) -> Self {
+        Self::evaluate_polys_over_into::<N>(polys, domain, None)
+    }
+
+    /// Returns a new [RowMatrix] constructed by evaluating the provided polynomials over the
+    /// specified [StarkDomain], using memory taken from the specified buffer `pool`.
+    ///
+    /// This is equivalent to [RowMatrix::evaluate_polys_over()], except that the buffers holding
+    /// the evaluations are taken from the `pool` (and new buffers are allocated only when the pool
+    /// does not contain suitable buffers). Intermediate buffers are returned to the pool once the
+    /// matrix has been built.
+    pub fn evaluate_polys_over_with_pool<const N: usize>(
+        polys: &ColMatrix<E>,
+        domain: &StarkDomain<E::BaseField>,
+        pool: &BufferPool<E::BaseField>,
+    ) -> Self {
+        Self::evaluate_polys_over_into::<N>(polys, domain, Some(pool))
+    }
+
+    /// Evaluates the provided polynomials over the specified [StarkDomain], taking the buffers for
+    /// the evaluations from the `pool` if one is provided.
+    fn evaluate_polys_over_into<const N: usize>(
+        polys: &ColMatrix<E>,
+        domain: &StarkDomain<E::BaseField>,
+        pool: Option<&BufferPool<E::BaseField>>,
     ) -> Self {
         assert!(N > 0, "batch size N must be greater than zero");
 
@@ -93,10 +126,15 @@ impl<E: FieldElement> RowMatrix<E> {
             get_evaluation_offsets::<E>(poly_size, domain.trace_to_lde_blowup(), domain.offset());
 
         // build matrix segments by evaluating all polynomials
-        let segments = build_segments::<E, N>(polys, domain.trace_twiddles(), &offsets);
+        let segments = match pool {
+            Some(pool) => {
+                build_segments_with_pool::<E, N>(polys, domain.trace_twiddles(), &offsets, pool)
+            },
+            None => build_segments::<E, N>(polys, domain.trace_twiddles(), &offsets),
+        };
 
         // transpose data in individual segments into a single row-major matrix
-        Self::from_segments(segments, polys.num_base_cols())
+        Self::from_segments_into(segments, polys.num_base_cols(), pool)
     }
 
     /// Returns a new [RowMatrix] instantiated from the specified matrix segments.
@@ -112,6 +150,17 @@ impl<E: FieldElement> RowMatrix<E> {
     pub fn from_segments<const N: usize>(
         segments: Vec<Segment<E::BaseField, N>>,
         elements_per_row: usize,
+    ) -> Self {
+        Self::from_segments_into(segments, elements_per_row, None)
+    }
+
+    /// Returns a new [RowMatrix] instantiated from the specified matrix segments, taking the
+    /// buffer for the matrix from the `pool` if one is provided; in this case, buffers of the
+    /// segments are returned to the pool.
+    fn from_segments_into<const N: usize>(
+        segments: Vec<Segment<E::BaseField, N>>,
+        elements_per_row: usize,
+        pool: Option<&BufferPool<E::BaseField>>,
     ) -> Self {
         assert!(N > 0, "batch size N must be greater than zero");
         assert!(!segments.is_empty(), "a list of segments cannot be empty");
@@ -124,7 +173,7 @@ impl<E: FieldElement> RowMatrix<E> {
         );
 
         // transpose the segments into a single vector of arrays
-        let result = transpose(segments);
+        let result = transpose(segments, pool);
 
         // flatten the result to be a simple vector of elements and return
         RowMatrix {
@@ -134,6 +183,28 @@ impl<E: FieldElement> RowMatrix<E> {
         }
     }
 
+    /// Returns a new [RowMatrix] holding the provided data, where every `row_width` consecutive
+    /// base field elements form a single row.
+    ///
+    /// # Panics
+    /// Panics if `row_width` is zero or is not a multiple of the extension degree of `E`, or if
+    /// the length of `data` is not a multiple of `row_width`.
+    #[cfg(feature = "std")]
+    pub(crate) fn from_data(data: Vec<E::BaseField>, row_width: usize) -> Self {
+        assert!(row_width > 0, "row width must be greater than zero");
+        assert_eq!(
+            row_width % E::EXTENSION_DEGREE,
+            0,
+            "row width must be a multiple of extension degree"
+        );
+        assert_eq!(data.len() % row_width, 0, "data length must be a multiple of row width");
+        RowMatrix {
+            data,
+            row_width,
+            elements_per_row: row_width,
+        }
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -170,6 +241,11 @@ impl<E: FieldElement> RowMatrix<E> {
         &self.data
     }
 
+    /// Removes the data from this matrix and returns it; the matrix is left without any rows.
+    pub(crate) fn take_data(&mut self) -> Vec<E::BaseField> {
+        core::mem::take(&mut self.data)
+    }
+
     // COMMITMENTS
     // --------------------------------------------------------------------------------------------
 
@@ -185,52 +261,93 @@ impl<E: FieldElement> RowMatrix<E> {
     where
         H: ElementHasher<BaseField = E::BaseField>,
         V: VectorCommitment<H>,
+    {
+        let row_hashes = self.hash_rows::<H>(partition_options);
+
+        // build the vector commitment to the hashed rows
+        V::new(row_hashes).expect("failed to construct trace vector commitment")
+    }
+
+    /// Returns hashes of all rows of this matrix.
+    ///
+    /// If the specified partition options define more than one partition per row, each partition
+    /// of a row is hashed separately and the partition hashes are then merged into the row hash.
+    pub(crate) fn hash_rows<H>(&self, partition_options: PartitionOptions) -> Vec<H::Digest>
+    where
+        H: ElementHasher<BaseField = E::BaseField>,
     {
         // allocate vector to store row hashes
         let mut row_hashes = unsafe { uninit_vector::<H::Digest>(self.num_rows()) };
         let partition_size = partition_options.partition_size::<E>(self.num_cols());
-
-        if partition_size == self.num_cols() {
-            // iterate though matrix rows, hashing each row
-            batch_iter_mut!(
-                &mut row_hashes,
-                128, // min batch size
-                |batch: &mut [H::Digest], batch_offset: usize| {
-                    for (i, row_hash) in batch.iter_mut().enumerate() {
-                        *row_hash = H::hash_elements(self.row(batch_offset + i));
-                    }
+        let num_partitions = partition_options.num_partitions::<E>(self.num_cols());
+
+        let hash_rows = |batch: &mut [H::Digest], batch_offset: usize| {
+            if partition_size == self.num_cols() {
+                // hash HASH_BATCH_SIZE rows at a time
+                let batch_end = batch_offset + batch.len();
+                let mut hash_batches = batch.chunks_exact_mut(HASH_BATCH_SIZE);
+                for (i, row_hashes) in (&mut hash_batches).enumerate() {
+                    let row_offset = batch_offset + i * HASH_BATCH_SIZE;
+                    let rows: [&[E]; HASH_BATCH_SIZE] =
+                        core::array::from_fn(|j| self.row(row_offset + j));
+                    row_hashes.copy_from_slice(&H::hash_elements_batch(rows));
                 }
-            );
-        } else {
-            let num_partitions = partition_options.num_partitions::<E>(self.num_cols());
-
-            // iterate though matrix rows, hashing each row
-            batch_iter_mut!(
-                &mut row_hashes,
-                128, // min batch size
-                |batch: &mut [H::Digest], batch_offset: usize| {
-                    let mut buffer = vec![H::Digest::default(); num_partitions];
-                    for (i, row_hash) in batch.iter_mut().enumerate() {
-                        self.row(batch_offset + i)
-                            .chunks(partition_size)
-                            .zip(buffer.iter_mut())
-                            .for_each(|(chunk, buf)| {
-                                *buf = H::hash_elements(chunk);
-                            });
-                        *row_hash = H::merge_many(&buffer);
-                    }
+                let remainder = hash_batches.into_remainder();
+                let row_offset = batch_end - remainder.len();
+                for (i, row_hash) in remainder.iter_mut().enumerate() {
+                    *row_hash = H::hash_elements(self.row(row_offset + i));
                 }
-            );
+            } else {
+                // hash each partition of a row separately, and then merge the partition hashes
+                let mut buffer = vec![H::Digest::default(); num_partitions];
+                for (i, row_hash) in batch.iter_mut().enumerate() {
+                    self.row(batch_offset + i)
+                        .chunks(partition_size)
+                        .zip(buffer.iter_mut())
+                        .for_each(|(chunk, buf)| {
+                            *buf = H::hash_elements(chunk);
+                        });
+                    *row_hash = H::merge_many(&buffer);
+                }
+            }
+        };
+
+        #[cfg(not(feature = "concurrent"))]
+        hash_rows(&mut row_hashes, 0);
+
+        // split rows into work units of roughly the same number of elements, so that all
+        // threads are kept busy regardless of the width of the matrix
+        #[cfg(feature = "concurrent")]
+        {
+            let rows_per_chunk = get_rows_per_hashing_chunk::<E>(self.num_cols());
+            row_hashes
+                .par_chunks_mut(rows_per_chunk)
+                .enumerate()
+                .for_each(|(i, batch)| hash_rows(batch, i * rows_per_chunk));
         }
 
-        // build the vector commitment to the hashed rows
-        V::new(row_hashes).expect("failed to construct trace vector commitment")
+        row_hashes
     }
 }
 
 // HELPER FUNCTIONS
 // ================================================================================================
 
+/// Returns the number of rows hashed by a single task when rows of a matrix with the specified
+/// number of columns are hashed concurrently.
+///
+/// The number of rows is chosen such that every task hashes roughly
+/// [ROW_HASHING_CHUNK_ELEMENTS] base field elements, and is rounded up to a multiple of
+/// [HASH_BATCH_SIZE]. Thus, for wide matrices, every task hashes only a few rows, and the
+/// rows are spread over all available threads.
+#[cfg(feature = "concurrent")]
+fn get_rows_per_hashing_chunk<E: FieldElement>(num_cols: usize) -> usize {
+    let row_elements = (num_cols * E::EXTENSION_DEGREE).max(1);
+    (ROW_HASHING_CHUNK_ELEMENTS / row_elements)
+        .max(1)
+        .next_multiple_of(HASH_BATCH_SIZE)
+}
+
 /// Returns a vector of offsets for an evaluation defined by the specified polynomial size, blowup
 /// factor and domain offset.
 ///
@@ -292,10 +409,40 @@ pub fn build_segments<E: FieldElement, const N: usize>(
         .collect()
 }
 
+/// Returns matrix segments constructed by evaluating polynomials in the specified matrix over the
+/// domain defined by twiddles and offsets, using buffers taken from the specified `pool`.
+fn build_segments_with_pool<E: FieldElement, const N: usize>(
+    polys: &ColMatrix<E>,
+    twiddles: &[E::BaseField],
+    offsets: &[E::BaseField],
+    pool: &BufferPool<E::BaseField>,
+) -> Vec<Segment<E::BaseField, N>> {
+    assert!(N > 0, "batch size N must be greater than zero");
+    debug_assert_eq!(polys.num_rows(), twiddles.len() * 2);
+    debug_assert_eq!(offsets.len() % polys.num_rows(), 0);
+
+    (0..polys.num_base_cols().div_ceil(N))
+        .map(|i| {
+            let mut buffer = unsafe { pool.take::<[E::BaseField; N]>(offsets.len()) };
+            if polys.num_base_cols() - i * N < N {
+                // if some columns in the segment will remain unfilled, they must be initialized
+                // to zeros to make sure we don't end up with memory with undefined values
+                buffer.fill([E::BaseField::ZERO; N]);
+            }
+            Segment::new_with_buffer(buffer, polys, i * N, offsets, twiddles)
+        })
+        .collect()
+}
+
 /// Transposes a vector of segments into a single vector of fixed-size arrays.
 ///
 /// When `concurrent` feature is enabled, transposition is performed in multiple threads.
-fn transpose<B: StarkField, const N: usize>(mut segments: Vec<Segment<B, N>>) -> Vec<[B; N]> {
+/// If a buffer `pool` is provided, the memory for the result is taken from the pool, and the
+/// buffers of the segments are returned to the pool once the transposition is complete.
+fn transpose<B: StarkField, const N: usize>(
+    mut segments: Vec<Segment<B, N>>,
+    pool: Option<&BufferPool<B>>,
+) -> Vec<[B; N]> {
     let num_rows = segments[0].num_rows();
     let num_segs = segments.len();
     let result_len = num_rows * num_segs;
@@ -308,7 +455,10 @@ fn transpose<B: StarkField, const N: usize>(mut segments: Vec<Segment<B, N>>) ->
 
     // allocate memory to hold the transposed result;
     // TODO: investigate transposing in-place
-    let mut result = unsafe { uninit_vector::<[B; N]>(result_len) };
+    let mut result = match pool {
+        Some(pool) => unsafe { pool.take::<[B; N]>(result_len) },
+        None => unsafe { uninit_vector::<[B; N]>(result_len) },
+    };
 
     // determine number of batches in which transposition will be preformed; if `concurrent`
     // feature is not enabled, the number of batches will always be 1
@@ -339,6 +489,10 @@ fn transpose<B: StarkField, const N: usize>(mut segments: Vec<Segment<B, N>>) ->
         .enumerate()
         .for_each(transpose_batch);
 
+    if let Some(pool) = pool {
+        segments.into_iter().for_each(|segment| pool.put(segment.into_data()));
+    }
+
     result
 }
 
@@ -180,10 +180,10 @@ impl<B: StarkField, const N: usize> Segment<B, N> {
         poly_offset: usize,
         offsets: &[B],
     ) {
-        for row_idx in 0..dest.len() {
-            for i in 0..N {
+        for (row_idx, row) in dest.iter_mut().enumerate() {
+            for (i, value) in row.iter_mut().take(N).enumerate() {
                 let coeff = polys.get_base_element(poly_offset + i, row_idx);
-                dest[row_idx][i] = coeff * offsets[row_idx];
+                *value = coeff * offsets[row_idx];
             }
         }
     }
@@ -200,10 +200,10 @@ impl<B: StarkField, const N: usize> Segment<B, N> {
         offsets: &[B],
     ) {
         debug_assert!(num_polys < N);
-        for row_idx in 0..dest.len() {
-            for i in 0..num_polys {
+        for (row_idx, row) in dest.iter_mut().enumerate() {
+            for (i, value) in row.iter_mut().take(num_polys).enumerate() {
                 let coeff = polys.get_base_element(poly_offset + i, row_idx);
-                dest[row_idx][i] = coeff * offsets[row_idx];
+                *value = coeff * offsets[row_idx];
             }
         }
     }
@@ -237,7 +237,7 @@ mod concurrent {
         // generator of the domain should be in the middle of twiddles
         let n = data.len();
         let g = twiddles[twiddles.len() / 2];
-        debug_assert_eq!(g.exp((n as u32).into()), B::ONE);
+        debug_assert_eq!(g.exp((n as u64).into()), B::ONE);
 
         let inner_len = 1_usize << (n.ilog2() / 2);
         let outer_len = n / inner_len;
@@ -259,7 +259,7 @@ mod concurrent {
         data.par_chunks_mut(outer_len).enumerate().for_each(|(i, row)| {
             if i > 0 {
                 let i = permute_index(inner_len, i);
-                let inner_twiddle = g.exp_vartime((i as u32).into());
+                let inner_twiddle = g.exp_vartime((i as u64).into());
                 let mut outer_twiddle = inner_twiddle;
                 for element in row.iter_mut().skip(1) {
                     for col_idx in 0..N {
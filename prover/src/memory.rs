@@ -0,0 +1,198 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use air::Air;
+use crypto::Hasher;
+use math::FieldElement;
+use utils::Serializable;
+
+use crate::TraceLdeStorage;
+
+// MEMORY ESTIMATE
+// ================================================================================================
+
+/// An estimate of the memory required to generate a proof for a specific instance of an AIR.
+///
+/// The estimate accounts for the largest data structures built by the default implementations of
+/// the prover components:
+/// * Low-degree extension of the execution trace, and the Merkle trees committing to it.
+/// * Evaluations of constraints over the constraint evaluation domain.
+/// * Low-degree extension of the constraint composition polynomial, and the Merkle tree
+///   committing to it.
+/// * Evaluations of the DEEP composition polynomial, and the FRI layers built from them.
+///
+/// The execution trace itself, polynomials in coefficient form, and temporary buffers are not
+/// included. Thus, the estimate should be treated as a lower bound on the peak memory usage of
+/// the prover, and a budget should leave some headroom above it.
+///
+/// Estimates can be used to pick a prover configuration before any work is done: for example,
+/// when the trace LDE does not fit into the available memory, a prover can commit to it via a
+/// disk-backed vector commitment (e.g., `DiskMerkleTree` from the `crypto` crate), or use a
+/// [TraceLde](crate::TraceLde) which keeps its data on disk or recomputes it on demand (see
+/// [MemoryEstimate::peak()]). See also [Prover::memory_budget()](crate::Prover::memory_budget).
+///
+/// All sizes saturate at `usize::MAX` rather than overflow; this may happen for large instances
+/// on 32-bit targets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryEstimate {
+    trace_lde: usize,
+    trace_commitment: usize,
+    constraint_evaluations: usize,
+    composition_poly_lde: usize,
+    composition_poly_commitment: usize,
+    fri_layers: usize,
+}
+
+impl MemoryEstimate {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a memory estimate for generating a proof for the specified instance of an AIR,
+    /// with computations performed in field `E` and commitments built using hash function `H`.
+    pub fn new<A, E, H>(air: &A) -> Self
+    where
+        A: Air<BaseField = E::BaseField>,
+        E: FieldElement,
+        H: Hasher,
+    {
+        let lde_domain_size = air.lde_domain_size();
+        let digest_size = H::Digest::default().to_bytes().len();
+        // a Merkle tree with n leaves contains n leaf digests and n - 1 internal nodes
+        let tree_size = lde_domain_size.saturating_mul(digest_size).saturating_mul(2);
+
+        let trace_info = air.trace_info();
+        let row_size = (trace_info.main_trace_width() * E::BaseField::ELEMENT_BYTES)
+            .saturating_add(trace_info.aux_segment_width() * E::ELEMENT_BYTES);
+        let num_segments = 1 + trace_info.num_aux_segments();
+
+        let composition_row_size =
+            air.context().num_constraint_composition_columns() * E::ELEMENT_BYTES;
+
+        // the sizes of FRI layers decrease geometrically, and thus, all layers together (and the
+        // trees committing to them) take at most twice as much space as the evaluations of the
+        // DEEP composition polynomial (and a tree committing to them)
+        let deep_composition_size = lde_domain_size.saturating_mul(E::ELEMENT_BYTES);
+
+        Self {
+            trace_lde: lde_domain_size.saturating_mul(row_size),
+            trace_commitment: tree_size.saturating_mul(num_segments),
+            constraint_evaluations: air.ce_domain_size().saturating_mul(E::ELEMENT_BYTES),
+            composition_poly_lde: lde_domain_size.saturating_mul(composition_row_size),
+            composition_poly_commitment: tree_size,
+            fri_layers: deep_composition_size.saturating_add(tree_size).saturating_mul(2),
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of bytes required to hold the low-degree extension of all segments of
+    /// the execution trace.
+    pub fn trace_lde(&self) -> usize {
+        self.trace_lde
+    }
+
+    /// Returns the number of bytes required to hold the Merkle trees committing to the low-degree
+    /// extension of the execution trace.
+    pub fn trace_commitment(&self) -> usize {
+        self.trace_commitment
+    }
+
+    /// Returns the number of bytes required to hold evaluations of constraints over the
+    /// constraint evaluation domain.
+    pub fn constraint_evaluations(&self) -> usize {
+        self.constraint_evaluations
+    }
+
+    /// Returns the number of bytes required to hold the low-degree extension of the constraint
+    /// composition polynomial.
+    pub fn composition_poly_lde(&self) -> usize {
+        self.composition_poly_lde
+    }
+
+    /// Returns the number of bytes required to hold the Merkle tree committing to the low-degree
+    /// extension of the constraint composition polynomial.
+    pub fn composition_poly_commitment(&self) -> usize {
+        self.composition_poly_commitment
+    }
+
+    /// Returns the number of bytes required to hold evaluations of the DEEP composition
+    /// polynomial and all FRI layers built from them.
+    pub fn fri_layers(&self) -> usize {
+        self.fri_layers
+    }
+
+    /// Returns the total number of bytes of all data structures included in this estimate.
+    ///
+    /// The low-degree extension of the execution trace and the commitments are kept in memory
+    /// until the proof is built, and thus, all of the included data structures may be alive at
+    /// the same time.
+    pub fn total(&self) -> usize {
+        self.trace_lde.saturating_add(self.total_without_trace_lde())
+    }
+
+    /// Returns the number of bytes of all data structures included in this estimate which are
+    /// alive at the same time when the trace LDE is kept in the specified `storage`.
+    ///
+    /// * With [TraceLdeStorage::Memory], this is the same as [MemoryEstimate::total()].
+    /// * With [TraceLdeStorage::Recompute], the trace LDE is released once constraints have been
+    ///   evaluated, and thus, it is never alive together with the data structures built after
+    ///   constraint evaluation.
+    /// * With [TraceLdeStorage::Disk], the trace LDE is not kept in memory at all (apart from a
+    ///   small cache of rows, which is not included in the estimate).
+    pub fn peak(&self, storage: &TraceLdeStorage) -> usize {
+        match storage {
+            TraceLdeStorage::Memory => self.total(),
+            TraceLdeStorage::Recompute => {
+                let before_release = self
+                    .trace_lde
+                    .saturating_add(self.trace_commitment)
+                    .saturating_add(self.constraint_evaluations);
+                before_release.max(self.total_without_trace_lde())
+            },
+            #[cfg(feature = "std")]
+            TraceLdeStorage::Disk(_) => self.total_without_trace_lde(),
+        }
+    }
+
+    /// Returns the trace LDE storage option which keeps the peak memory usage within the specified
+    /// `budget`.
+    ///
+    /// Options are tried in order of increasing cost: keeping the trace LDE in memory, recomputing
+    /// rows at query positions after constraint evaluation, and (with the `std` feature) keeping
+    /// the trace LDE on disk. If none of them fits into the budget, the smallest peak is returned
+    /// as an error.
+    pub(crate) fn select_trace_lde_storage(&self, budget: usize) -> Result<TraceLdeStorage, usize> {
+        let options = [
+            TraceLdeStorage::Memory,
+            TraceLdeStorage::Recompute,
+            #[cfg(feature = "std")]
+            TraceLdeStorage::Disk(Default::default()),
+        ];
+
+        let mut min_peak = usize::MAX;
+        for storage in options {
+            let peak = self.peak(&storage);
+            if peak <= budget {
+                return Ok(storage);
+            }
+            min_peak = min_peak.min(peak);
+        }
+        Err(min_peak)
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the total number of bytes of all data structures included in this estimate except
+    /// for the low-degree extension of the execution trace.
+    fn total_without_trace_lde(&self) -> usize {
+        self.trace_commitment
+            .saturating_add(self.constraint_evaluations)
+            .saturating_add(self.composition_poly_lde)
+            .saturating_add(self.composition_poly_commitment)
+            .saturating_add(self.fri_layers)
+    }
+}
@@ -0,0 +1,54 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use core::fmt;
+
+// PROVING PHASE
+// ================================================================================================
+
+/// Phases of proof generation, in the order in which they are executed.
+///
+/// The prover reports the start and the end of every phase via [Prover::on_phase_start()] and
+/// [Prover::on_phase_end()] methods.
+///
+/// [Prover::on_phase_start()]: crate::Prover::on_phase_start
+/// [Prover::on_phase_end()]: crate::Prover::on_phase_end
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ProvingPhase {
+    /// Extension of the main trace segment over the LDE domain and commitment to the extended
+    /// segment.
+    MainTraceCommitment,
+    /// Construction of the auxiliary trace segment, its extension over the LDE domain, and
+    /// commitment to the extended segment. This phase is executed only for multi-segment traces.
+    AuxTraceCommitment,
+    /// Evaluation of transition and boundary constraints over the constraint evaluation domain.
+    ConstraintEvaluation,
+    /// Extension of the constraint composition polynomial over the LDE domain and commitment to
+    /// its evaluations.
+    ConstraintCommitment,
+    /// Construction of the DEEP composition polynomial and its evaluation over the LDE domain.
+    DeepComposition,
+    /// Computation of FRI layers for the DEEP composition polynomial.
+    FriLayers,
+    /// Proof-of-work grinding and derivation of query positions.
+    QueryPositions,
+    /// Opening of commitments at query positions and construction of the proof object.
+    ProofConstruction,
+}
+
+impl fmt::Display for ProvingPhase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MainTraceCommitment => write!(f, "main trace commitment"),
+            Self::AuxTraceCommitment => write!(f, "auxiliary trace commitment"),
+            Self::ConstraintEvaluation => write!(f, "constraint evaluation"),
+            Self::ConstraintCommitment => write!(f, "constraint commitment"),
+            Self::DeepComposition => write!(f, "DEEP composition"),
+            Self::FriLayers => write!(f, "FRI layers"),
+            Self::QueryPositions => write!(f, "query positions"),
+            Self::ProofConstruction => write!(f, "proof construction"),
+        }
+    }
+}
@@ -67,6 +67,7 @@ impl MockAir {
             (),
             ProofOptions::new(32, 8, 0, FieldExtension::None, 4, 31),
         );
+        result.context = build_context(TraceInfo::new(4, trace_length), 8, assertions.len());
         result.assertions = assertions;
         result
     }
@@ -3,13 +3,17 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use alloc::{borrow::Cow, format, string::String};
+
 use air::{Air, AuxRandElements, EvaluationFrame, LagrangeKernelBoundaryConstraint, TraceInfo};
 use math::{polynom, FieldElement, StarkField};
 
 use super::ColMatrix;
 
 mod trace_lde;
-pub use trace_lde::{DefaultTraceLde, TraceLde};
+#[cfg(feature = "std")]
+pub use trace_lde::DiskTraceLdeOptions;
+pub use trace_lde::{DefaultTraceLde, TraceLde, TraceLdeStorage};
 
 mod poly_table;
 pub use poly_table::TracePolyTable;
@@ -63,10 +67,24 @@ pub trait Trace: Sized {
     /// Returns trace info for this trace.
     fn info(&self) -> &TraceInfo;
 
-    /// Returns a reference to a [Matrix] describing the main segment of this trace.
-    fn main_segment(&self) -> &ColMatrix<Self::BaseField>;
+    /// Returns a [ColMatrix] describing the main segment of this trace.
+    ///
+    /// Traces which keep the main segment in a [ColMatrix] (e.g., [TraceTable]) should return a
+    /// borrowed matrix. Traces which keep their data in other structures (e.g., arena-allocated
+    /// or memory-mapped buffers of a virtual machine) can return an owned matrix built on
+    /// demand; in this case, the data is copied only once, when the prover extends the main
+    /// segment of the trace. The prover calls this method once per proof, and otherwise accesses
+    /// the trace only via [Trace::read_main_frame()] and methods which implementers of the
+    /// [Prover](super::Prover) trait define themselves (e.g., for building the auxiliary trace
+    /// segment).
+    fn main_segment(&self) -> Cow<'_, ColMatrix<Self::BaseField>>;
 
     /// Reads an evaluation frame from the main trace segment at the specified row.
+    ///
+    /// The `i`-th row of the frame must be populated with the row at `row_idx + frame.offsets()[i]`
+    /// (wrapping around at the end of the trace). By default, frames contain the row at `row_idx`
+    /// and the row following it; other offsets can be requested by the AIR via
+    /// [AirContext::set_frame_offsets()](air::AirContext::set_frame_offsets).
     fn read_main_frame(&self, row_idx: usize, frame: &mut EvaluationFrame<Self::BaseField>);
 
     // PROVIDED METHODS
@@ -89,6 +107,11 @@ pub trait Trace: Sized {
 
     /// Checks if this trace is valid against the specified AIR, and panics if not.
     ///
+    /// When a transition constraint does not evaluate to ZERO, the panic message contains the
+    /// index of the constraint, the first step at which the constraint is violated, the value to
+    /// which the constraint evaluated, as well as the evaluation frame(s) and periodic values
+    /// used to evaluate the constraint at that step.
+    ///
     /// NOTE: this is a very expensive operation and is intended for use only in debug mode.
     fn validate<A, E>(
         &self,
@@ -109,11 +132,15 @@ pub trait Trace: Sized {
 
         // --- 1. make sure the assertions are valid ----------------------------------------------
 
-        // first, check assertions against the main segment of the execution trace
+        // first, check assertions against the main segment of the execution trace; asserted
+        // steps after the last step of the computation (i.e., on the random rows appended to the
+        // trace in zero-knowledge mode) are not enforced
+        let last_step = air.last_step();
+        let main_segment = self.main_segment();
         for assertion in air.get_assertions() {
             assertion.apply(self.length(), |step, value| {
                 assert!(
-                    value == self.main_segment().get(assertion.column(), step),
+                    step > last_step || value == main_segment.get(assertion.column(), step),
                     "trace does not satisfy assertion main_trace({}, {}) == {}",
                     assertion.column(),
                     step,
@@ -131,7 +158,7 @@ pub trait Trace: Sized {
                 // get the matrix and verify the assertion against it
                 assertion.apply(self.length(), |step, value| {
                     assert!(
-                        value == aux_trace.get(assertion.column(), step),
+                        step > last_step || value == aux_trace.get(assertion.column(), step),
                         "trace does not satisfy assertion aux_trace({}, {}) == {}",
                         assertion.column(),
                         step,
@@ -165,9 +192,14 @@ pub trait Trace: Sized {
 
         // initialize buffers to hold evaluation frames and results of constraint evaluations
         let mut x = Self::BaseField::ONE;
-        let mut main_frame = EvaluationFrame::new(self.main_trace_width());
+        let frame_offsets = air.context().frame_offsets();
+        let mut main_frame =
+            EvaluationFrame::with_offsets(self.main_trace_width(), frame_offsets.to_vec());
         let mut aux_frame = if air.trace_info().is_multi_segment() {
-            Some(EvaluationFrame::<E>::new(self.aux_trace_width()))
+            Some(EvaluationFrame::<E>::with_offsets(
+                self.aux_trace_width(),
+                frame_offsets.to_vec(),
+            ))
         } else {
             None
         };
@@ -175,13 +207,30 @@ pub trait Trace: Sized {
             vec![Self::BaseField::ZERO; air.context().num_main_transition_constraints()];
         let mut aux_evaluations = vec![E::ZERO; air.context().num_aux_transition_constraints()];
 
-        // we check transition constraints on all steps except the last k steps, where k is the
-        // number of steps exempt from transition constraints (guaranteed to be at least 1)
-        for step in 0..self.length() - air.context().num_transition_exemptions() {
+        // we check transition constraints with the default divisor on all steps except the last k
+        // steps, where k is the number of steps exempt from transition constraints (guaranteed to
+        // be at least 1); constraints with custom divisors are checked on the steps at which
+        // their divisors vanish
+        let context = air.context();
+        let num_main_constraints = context.num_main_transition_constraints();
+        let is_enforced = |constraint_idx: usize, step: usize, x: Self::BaseField| match context
+            .transition_divisor(constraint_idx)
+        {
+            Some(divisor) => divisor.vanishes_at(x),
+            None => step < self.length() - context.num_transition_exemptions(),
+        };
+        let has_custom_divisors = (0..context.num_transition_constraints())
+            .any(|constraint_idx| context.transition_divisor(constraint_idx).is_some());
+        let num_steps = if has_custom_divisors {
+            self.length()
+        } else {
+            self.length() - context.num_transition_exemptions()
+        };
+        for step in 0..num_steps {
             // build periodic values
             for (p, v) in periodic_values_polys.iter().zip(periodic_values.iter_mut()) {
                 let num_cycles = air.trace_length() / p.len();
-                let x = x.exp((num_cycles as u32).into());
+                let x = x.exp((num_cycles as u64).into());
                 *v = polynom::eval(p, x);
             }
 
@@ -191,8 +240,12 @@ pub trait Trace: Sized {
             air.evaluate_transition(&main_frame, &periodic_values, &mut main_evaluations);
             for (i, &evaluation) in main_evaluations.iter().enumerate() {
                 assert!(
-                    evaluation == Self::BaseField::ZERO,
-                    "main transition constraint {i} did not evaluate to ZERO at step {step}"
+                    evaluation == Self::BaseField::ZERO || !is_enforced(i, step, x),
+                    "main transition constraint {i} did not evaluate to ZERO at step {step}\n\
+                    evaluation: {evaluation}\n\
+                    main frame:\n{}\
+                    periodic values: {periodic_values:?}",
+                    describe_frame(&main_frame, step, self.length()),
                 );
             }
 
@@ -214,8 +267,14 @@ pub trait Trace: Sized {
                 );
                 for (i, &evaluation) in aux_evaluations.iter().enumerate() {
                     assert!(
-                        evaluation == E::ZERO,
-                        "auxiliary transition constraint {i} did not evaluate to ZERO at step {step}"
+                        evaluation == E::ZERO || !is_enforced(num_main_constraints + i, step, x),
+                        "auxiliary transition constraint {i} did not evaluate to ZERO at step {step}\n\
+                        evaluation: {evaluation}\n\
+                        main frame:\n{}\
+                        auxiliary frame:\n{}\
+                        periodic values: {periodic_values:?}",
+                        describe_frame(&main_frame, step, self.length()),
+                        describe_frame(aux_frame, step, self.length()),
                     );
                 }
             }
@@ -252,7 +311,12 @@ pub trait Trace: Sized {
 
                     assert!(
                         evaluation == E::ZERO,
-                        "Lagrange transition constraint {constraint_idx} did not evaluate to ZERO at step {x_current}"
+                        "Lagrange transition constraint {constraint_idx} did not evaluate to ZERO at step {x_current}\n\
+                        evaluation: {evaluation}\n\
+                        row {x_current}: {}\n\
+                        row {x_next}: {}",
+                        c[x_current],
+                        c[x_next],
                     );
                 }
             }
@@ -263,6 +327,22 @@ pub trait Trace: Sized {
 // HELPER FUNCTIONS
 // ================================================================================================
 
+/// Returns a description of the provided evaluation frame read at the specified step, listing
+/// every row of the frame (together with its index in the trace and its offset) on a separate
+/// line.
+fn describe_frame<E: FieldElement>(
+    frame: &EvaluationFrame<E>,
+    step: usize,
+    trace_length: usize,
+) -> String {
+    let mut result = String::new();
+    for (i, &offset) in frame.offsets().iter().enumerate() {
+        let row_idx = (step + offset) % trace_length;
+        result += &format!("  row {row_idx} (offset {offset}): {:?}\n", frame.row(i));
+    }
+    result
+}
+
 /// Reads an evaluation frame from the provided auxiliary segment.
 ///
 /// This is probably not the most efficient implementation, but since we call this function only
@@ -272,15 +352,11 @@ fn read_aux_frame<E>(aux_segment: &ColMatrix<E>, row_idx: usize, frame: &mut Eva
 where
     E: FieldElement,
 {
-    for (current_frame_cell, aux_segment_col) in
-        frame.current_mut().iter_mut().zip(aux_segment.columns())
-    {
-        *current_frame_cell = aux_segment_col[row_idx];
-    }
-
-    let next_row_idx = (row_idx + 1) % aux_segment.num_rows();
-    for (next_frame_cell, aux_segment_col) in frame.next_mut().iter_mut().zip(aux_segment.columns())
-    {
-        *next_frame_cell = aux_segment_col[next_row_idx];
+    for i in 0..frame.num_rows() {
+        let frame_row_idx = (row_idx + frame.offsets()[i]) % aux_segment.num_rows();
+        for (frame_cell, aux_segment_col) in frame.row_mut(i).iter_mut().zip(aux_segment.columns())
+        {
+            *frame_cell = aux_segment_col[frame_row_idx];
+        }
     }
 }
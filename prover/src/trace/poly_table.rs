@@ -3,37 +3,43 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use alloc::vec::Vec;
+use alloc::{sync::Arc, vec::Vec};
 
 use air::{proof::TraceOodFrame, LagrangeKernelEvaluationFrame};
-use math::{FieldElement, StarkField};
+use math::{polynom, FieldElement, StarkField};
 
 use crate::{matrix::ColumnIter, ColMatrix};
 
 // TRACE POLYNOMIAL TABLE
 // ================================================================================================
 
-/// Trace polynomials in coefficient from for all segments of the execution trace.
+/// Trace polynomials in coefficient form for all segments of the execution trace.
 ///
 /// Coefficients of the polynomials for the main trace segment are always in the base field.
 /// However, coefficients of the polynomials for the auxiliary trace segment (including
 /// the Lagrange kernel polynomial when present) may be either in the base field, or in
 /// the extension field, depending on whether extension field is being used.
+///
+/// The polynomials are computed once, when the trace LDE is built, and are then reused to
+/// evaluate the trace at the out-of-domain point and to construct DEEP quotients. The polynomial
+/// matrices are reference-counted so that they can be shared with the trace LDE (e.g., a trace
+/// LDE operating in low-memory mode recomputes rows at query positions from the same
+/// polynomials) without being copied.
 pub struct TracePolyTable<E: FieldElement> {
-    main_trace_polys: ColMatrix<E::BaseField>,
-    aux_trace_polys: Option<ColMatrix<E>>,
-    lagrange_kernel_poly: Option<Vec<E>>,
+    main_trace_polys: Arc<ColMatrix<E::BaseField>>,
+    aux_trace_polys: Option<Arc<ColMatrix<E>>>,
+    lagrange_kernel_column_idx: Option<usize>,
 }
 
 impl<E: FieldElement> TracePolyTable<E> {
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
     /// Creates a new table of trace polynomials from the provided main trace segment polynomials.
-    pub fn new(main_trace_polys: ColMatrix<E::BaseField>) -> Self {
+    pub fn new(main_trace_polys: impl Into<Arc<ColMatrix<E::BaseField>>>) -> Self {
         Self {
-            main_trace_polys,
+            main_trace_polys: main_trace_polys.into(),
             aux_trace_polys: None,
-            lagrange_kernel_poly: None,
+            lagrange_kernel_column_idx: None,
         }
     }
 
@@ -41,11 +47,15 @@ impl<E: FieldElement> TracePolyTable<E> {
     // --------------------------------------------------------------------------------------------
 
     /// Adds the provided auxiliary segment polynomials to this polynomial table.
+    ///
+    /// If `lagrange_kernel_column_idx` is provided, the polynomial at this index is treated as
+    /// the Lagrange kernel polynomial and is excluded from the auxiliary segment polynomials.
     pub fn add_aux_segment(
         &mut self,
-        aux_trace_polys: ColMatrix<E>,
+        aux_trace_polys: impl Into<Arc<ColMatrix<E>>>,
         lagrange_kernel_column_idx: Option<usize>,
     ) {
+        let aux_trace_polys = aux_trace_polys.into();
         assert!(self.aux_trace_polys.is_none());
         assert_eq!(
             self.main_trace_polys.num_rows(),
@@ -53,11 +63,14 @@ impl<E: FieldElement> TracePolyTable<E> {
             "polynomials in auxiliary segment must be of the same size as in the main segment"
         );
 
-        let mut aux_trace_polys = aux_trace_polys;
         if let Some(index) = lagrange_kernel_column_idx {
-            self.lagrange_kernel_poly = Some(aux_trace_polys.remove_column(index));
+            assert!(
+                index < aux_trace_polys.num_cols(),
+                "Lagrange kernel column index must be within the auxiliary segment"
+            );
         }
         self.aux_trace_polys = Some(aux_trace_polys);
+        self.lagrange_kernel_column_idx = lagrange_kernel_column_idx;
     }
 
     // PUBLIC ACCESSORS
@@ -71,34 +84,34 @@ impl<E: FieldElement> TracePolyTable<E> {
     /// Evaluates all trace polynomials (across all trace segments) at the specified point `x`.
     pub fn evaluate_at(&self, x: E) -> Vec<E> {
         let mut result = self.main_trace_polys.evaluate_columns_at(x);
-        for aux_polys in self.aux_trace_polys.iter() {
-            result.append(&mut aux_polys.evaluate_columns_at(x));
-        }
+        result.extend(self.aux_trace_polys().map(|poly| polynom::eval(poly, x)));
         result
     }
 
     /// Returns an out-of-domain evaluation frame constructed by evaluating trace polynomials for
-    /// all columns at points z and z * g, where g is the generator of the trace domain.
-    /// Additionally, if the Lagrange kernel auxiliary column is present, we also evaluate that
-    /// column over the points: z, z * g, z * g^2, z * g^4, ..., z * g^(2^(v-1)), where v =
-    /// log(trace_len).
-    pub fn get_ood_frame(&self, z: E) -> TraceOodFrame<E> {
+    /// all columns at points z * g^d for each frame row offset d, where g is the generator of the
+    /// trace domain. Additionally, if the Lagrange kernel
+    /// auxiliary column is present, we also evaluate that column over the points: z, z * g,
+    /// z * g^2, z * g^4, ..., z * g^(2^(v-1)), where v = log(trace_len).
+    pub fn get_ood_frame(&self, z: E, frame_offsets: &[usize]) -> TraceOodFrame<E> {
         let log_trace_len = self.poly_size().ilog2();
-        let g = E::from(E::BaseField::get_root_of_unity(log_trace_len));
-        let current_row = self.evaluate_at(z);
-        let next_row = self.evaluate_at(z * g);
-
-        let lagrange_kernel_frame =
-            self.lagrange_kernel_poly.as_ref().map(|lagrange_kernel_col_poly| {
-                LagrangeKernelEvaluationFrame::from_lagrange_kernel_column_poly(
-                    lagrange_kernel_col_poly,
-                    z,
-                )
-            });
+        let g = E::BaseField::get_root_of_unity(log_trace_len);
+        let rows = frame_offsets
+            .iter()
+            .map(|&offset| self.evaluate_at(z.mul_base(g.exp((offset as u64).into()))))
+            .collect();
+
+        let lagrange_kernel_frame = self.lagrange_kernel_poly().map(|lagrange_kernel_col_poly| {
+            LagrangeKernelEvaluationFrame::from_lagrange_kernel_column_poly(
+                lagrange_kernel_col_poly,
+                z,
+            )
+        });
 
         let main_trace_width = self.main_trace_polys.num_cols();
 
-        TraceOodFrame::new(current_row, next_row, main_trace_width, lagrange_kernel_frame)
+        TraceOodFrame::new(rows, main_trace_width, lagrange_kernel_frame)
+            .with_frame_offsets(frame_offsets.to_vec())
     }
 
     /// Returns an iterator over the polynomials of the main trace segment.
@@ -107,17 +120,24 @@ impl<E: FieldElement> TracePolyTable<E> {
     }
 
     /// Returns an iterator over the polynomials of the auxiliary trace segment.
+    ///
+    /// The Lagrange kernel polynomial (if any) is not included.
     pub fn aux_trace_polys(&self) -> impl Iterator<Item = &[E]> {
-        match self.aux_trace_polys {
+        let columns = match self.aux_trace_polys {
             Some(ref aux_segment_polys) => aux_segment_polys.columns(),
             None => ColumnIter::empty(),
-        }
+        };
+        columns
+            .enumerate()
+            .filter(|&(idx, _)| Some(idx) != self.lagrange_kernel_column_idx)
+            .map(|(_, poly)| poly)
     }
 
     /// Returns the polynomial of the auxiliary trace segment corresponding to the Lagrange kernel,
     /// if any.
     pub fn lagrange_kernel_poly(&self) -> Option<&[E]> {
-        self.lagrange_kernel_poly.as_deref()
+        let aux_segment_polys = self.aux_trace_polys.as_ref()?;
+        self.lagrange_kernel_column_idx.map(|idx| aux_segment_polys.get_column(idx))
     }
 
     // TEST HELPERS
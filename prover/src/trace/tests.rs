@@ -5,9 +5,12 @@
 
 use alloc::vec::Vec;
 
-use math::fields::f128::BaseElement;
+use math::{fields::f128::BaseElement, FieldElement};
+#[cfg(feature = "concurrent")]
+use utils::iterators::*;
+use utils::{Deserializable, Serializable};
 
-use crate::{tests::build_fib_trace, Trace};
+use crate::{tests::build_fib_trace, Trace, TraceTable};
 
 #[test]
 fn new_trace_table() {
@@ -29,3 +32,60 @@ fn new_trace_table() {
         .collect();
     assert_eq!(expected, trace.get_column(1));
 }
+
+#[test]
+fn split_trace_table() {
+    let trace_length = 16;
+    let expected = build_fib_trace(trace_length * 2);
+
+    // fill the trace one fragment at a time; each fragment starts from the row at which the
+    // previous fragment ended
+    let mut trace = TraceTable::new(2, trace_length);
+    let mut fragments = trace.split(4).collect::<Vec<_>>();
+    assert_eq!(4, fragments.len());
+
+    let mut state = [BaseElement::ONE; 2];
+    for (i, fragment) in fragments.iter_mut().enumerate() {
+        assert_eq!(i, fragment.index());
+        assert_eq!(i * 4, fragment.offset());
+        assert_eq!(4, fragment.length());
+        assert_eq!(2, fragment.width());
+
+        for row_idx in 0..fragment.length() {
+            fragment.set(0, row_idx, state[0]);
+            fragment.get_column_mut(1)[row_idx] = state[1];
+            state[0] += state[1];
+            state[1] += state[0];
+        }
+
+        let mut row = [BaseElement::ZERO; 2];
+        fragment.read_row_into(0, &mut row);
+        assert_eq!(expected.get(0, i * 4), row[0]);
+        assert_eq!(expected.get(1, i * 4), fragment.get(1, 0));
+    }
+    drop(fragments);
+
+    assert_eq!(expected.get_column(0), trace.get_column(0));
+    assert_eq!(expected.get_column(1), trace.get_column(1));
+}
+
+#[test]
+#[should_panic(expected = "number of fragments must be a power of 2, but was 3")]
+fn split_trace_table_invalid_num_fragments() {
+    let mut trace = TraceTable::<BaseElement>::new(2, 16);
+    let _ = trace.split(3);
+}
+
+#[test]
+fn trace_table_serialization() {
+    let trace = build_fib_trace(32);
+    let bytes = trace.to_bytes();
+
+    let result = TraceTable::<BaseElement>::read_from_bytes(&bytes).unwrap();
+    assert_eq!(trace.info(), result.info());
+    assert_eq!(trace.get_column(0), result.get_column(0));
+    assert_eq!(trace.get_column(1), result.get_column(1));
+
+    // truncated traces cannot be read
+    assert!(TraceTable::<BaseElement>::read_from_bytes(&bytes[..bytes.len() - 1]).is_err());
+}
@@ -0,0 +1,294 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::{collections::VecDeque, vec::Vec};
+use std::{
+    fs::File,
+    io::{BufWriter, Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    sync::{Mutex, MutexGuard},
+};
+
+use air::PartitionOptions;
+use crypto::{ElementHasher, VectorCommitment};
+use math::{FieldElement, StarkField};
+use utils::{flatten_vector_elements, ByteReader, ByteWriter, SliceReader};
+
+use super::{ColMatrix, StarkDomain};
+use crate::{
+    matrix::{get_evaluation_offsets, Segment},
+    RowMatrix, DEFAULT_SEGMENT_WIDTH,
+};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Default number of consecutive rows read from the storage files at once.
+const DEFAULT_ROWS_PER_PAGE: usize = 1 << 12;
+
+/// Default number of most recently read pages of rows kept in memory.
+const DEFAULT_CACHED_PAGES: usize = 64;
+
+// DISK TRACE LDE OPTIONS
+// ================================================================================================
+
+/// Options for keeping the low-degree extensions of trace segments on disk (see
+/// [DefaultTraceLde::new_on_disk()](super::DefaultTraceLde::new_on_disk)).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiskTraceLdeOptions {
+    dir: Option<PathBuf>,
+    rows_per_page: usize,
+    num_cached_pages: usize,
+}
+
+impl Default for DiskTraceLdeOptions {
+    fn default() -> Self {
+        Self {
+            dir: None,
+            rows_per_page: DEFAULT_ROWS_PER_PAGE,
+            num_cached_pages: DEFAULT_CACHED_PAGES,
+        }
+    }
+}
+
+impl DiskTraceLdeOptions {
+    /// Returns options which store segment LDEs in files created in the specified directory.
+    pub fn with_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.dir = Some(dir.into());
+        self
+    }
+
+    /// Returns options which read the specified number of consecutive rows from the storage files
+    /// at once; the number is rounded up to the next power of two.
+    pub const fn with_rows_per_page(mut self, rows_per_page: usize) -> Self {
+        self.rows_per_page = rows_per_page;
+        self
+    }
+
+    /// Returns options which keep the specified number of most recently read pages of rows in
+    /// memory.
+    pub const fn with_cached_pages(mut self, num_cached_pages: usize) -> Self {
+        self.num_cached_pages = num_cached_pages;
+        self
+    }
+}
+
+// DISK SEGMENT LDE
+// ================================================================================================
+
+/// Low-degree extension of a single trace segment stored in files.
+///
+/// The segment is extended [DEFAULT_SEGMENT_WIDTH] base field columns at a time, and the
+/// evaluations of each group of columns are written into a separate file in row-major order.
+/// Thus, only a single group of columns is kept in memory while the segment is being extended.
+///
+/// Rows are read back in pages of consecutive rows: a page is assembled by reading the rows from
+/// the files of all column groups, and a limited number of most recently read pages is cached.
+/// Since constraint evaluation reads the rows of every fragment of the LDE domain in order, most
+/// rows are read from the cache.
+pub(super) struct DiskSegmentLde<E: FieldElement> {
+    storage: Mutex<PageStorage<E>>,
+    num_rows: usize,
+    num_cols: usize,
+}
+
+impl<E: FieldElement> DiskSegmentLde<E> {
+    /// Evaluates the provided polynomials over the LDE domain and writes the evaluations into
+    /// files created as specified by the `options`.
+    ///
+    /// # Panics
+    /// Panics if the files could not be created or written.
+    pub fn new(
+        polys: &ColMatrix<E>,
+        domain: &StarkDomain<E::BaseField>,
+        options: &DiskTraceLdeOptions,
+    ) -> Self {
+        let num_rows = domain.lde_domain_size();
+        let offsets = get_evaluation_offsets::<E>(
+            polys.num_rows(),
+            domain.trace_to_lde_blowup(),
+            domain.offset(),
+        );
+
+        let dir = options.dir.clone().unwrap_or_else(std::env::temp_dir);
+        let files = (0..polys.num_base_cols())
+            .step_by(DEFAULT_SEGMENT_WIDTH)
+            .map(|poly_offset| {
+                let segment = Segment::<E::BaseField, DEFAULT_SEGMENT_WIDTH>::new(
+                    polys,
+                    poly_offset,
+                    &offsets,
+                    domain.trace_twiddles(),
+                );
+                write_column_group(&dir, segment)
+            })
+            .collect();
+
+        let storage = PageStorage {
+            files,
+            pages: VecDeque::new(),
+            row_width: polys.num_base_cols(),
+            rows_per_page: options.rows_per_page.next_power_of_two().min(num_rows),
+            num_cached_pages: options.num_cached_pages.max(1),
+        };
+
+        Self {
+            storage: Mutex::new(storage),
+            num_rows,
+            num_cols: polys.num_cols(),
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of rows in this segment LDE.
+    pub fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    /// Returns the number of columns in this segment LDE.
+    pub fn num_cols(&self) -> usize {
+        self.num_cols
+    }
+
+    /// Copies the row at the specified index into `row`.
+    pub fn read_row_into(&self, row_idx: usize, row: &mut [E]) {
+        row.copy_from_slice(self.lock().row(row_idx));
+    }
+
+    /// Returns the element located at the specified column and row indexes.
+    pub fn get(&self, col_idx: usize, row_idx: usize) -> E {
+        self.lock().row(row_idx)[col_idx]
+    }
+
+    /// Returns the rows at the specified positions.
+    ///
+    /// The rows are read from the storage files directly, and thus, the cache is left intact.
+    pub fn get_rows(&self, positions: &[usize]) -> Vec<Vec<E>> {
+        let mut storage = self.lock();
+        positions
+            .iter()
+            .map(|&position| storage.read_rows(position, 1).row(0).to_vec())
+            .collect()
+    }
+
+    // COMMITMENTS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a commitment to this segment LDE.
+    ///
+    /// The commitment is the same as the one built by [RowMatrix::commit_to_rows()] for the
+    /// same LDE; rows are hashed one page at a time.
+    pub fn commit_to_rows<H, V>(&self, partition_options: PartitionOptions) -> V
+    where
+        H: ElementHasher<BaseField = E::BaseField>,
+        V: VectorCommitment<H>,
+    {
+        let mut storage = self.lock();
+        let rows_per_page = storage.rows_per_page;
+        let mut row_hashes = Vec::with_capacity(self.num_rows);
+        for row_offset in (0..self.num_rows).step_by(rows_per_page) {
+            let page = storage.read_rows(row_offset, rows_per_page);
+            row_hashes.extend(page.hash_rows::<H>(partition_options));
+        }
+
+        V::new(row_hashes).expect("failed to construct trace vector commitment")
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    fn lock(&self) -> MutexGuard<'_, PageStorage<E>> {
+        self.storage.lock().expect("trace LDE storage lock is poisoned")
+    }
+}
+
+// PAGE STORAGE
+// ================================================================================================
+
+/// Files holding the groups of columns of a segment LDE, together with a cache of recently read
+/// pages of rows.
+struct PageStorage<E: FieldElement> {
+    files: Vec<File>,
+    /// Cached pages together with their indexes; the most recently read page is at the back.
+    pages: VecDeque<(usize, RowMatrix<E>)>,
+    /// Number of base field elements in a single row.
+    row_width: usize,
+    rows_per_page: usize,
+    num_cached_pages: usize,
+}
+
+impl<E: FieldElement> PageStorage<E> {
+    /// Returns the row at the specified index, reading the page containing it into the cache if
+    /// needed.
+    fn row(&mut self, row_idx: usize) -> &[E] {
+        let page_idx = row_idx / self.rows_per_page;
+        match self.pages.iter().position(|(idx, _)| *idx == page_idx) {
+            Some(position) => {
+                let page = self.pages.remove(position).expect("page position is out of bounds");
+                self.pages.push_back(page);
+            },
+            None => {
+                if self.pages.len() == self.num_cached_pages {
+                    self.pages.pop_front();
+                }
+                let page = self.read_rows(page_idx * self.rows_per_page, self.rows_per_page);
+                self.pages.push_back((page_idx, page));
+            },
+        }
+
+        let (_, page) = self.pages.back().expect("page cache is empty");
+        page.row(row_idx % self.rows_per_page)
+    }
+
+    /// Reads `num_rows` consecutive rows starting at `row_offset` from the storage files.
+    ///
+    /// # Panics
+    /// Panics if the rows could not be read from the files.
+    fn read_rows(&mut self, row_offset: usize, num_rows: usize) -> RowMatrix<E> {
+        let group_row_size = DEFAULT_SEGMENT_WIDTH * E::BaseField::ELEMENT_BYTES;
+        let mut bytes = vec![0; num_rows * group_row_size];
+        let mut data = vec![E::BaseField::ZERO; num_rows * self.row_width];
+
+        for (i, file) in self.files.iter_mut().enumerate() {
+            file.seek(SeekFrom::Start((row_offset * group_row_size) as u64))
+                .and_then(|_| file.read_exact(&mut bytes))
+                .expect("failed to read trace LDE");
+            let values: Vec<E::BaseField> = SliceReader::new(&bytes)
+                .read_many(num_rows * DEFAULT_SEGMENT_WIDTH)
+                .expect("failed to parse trace LDE");
+
+            // copy the columns of the group into the rows; the last group may be padded with
+            // columns which are not part of the segment
+            let col_offset = i * DEFAULT_SEGMENT_WIDTH;
+            let num_cols = DEFAULT_SEGMENT_WIDTH.min(self.row_width - col_offset);
+            for (row, group_row) in data
+                .chunks_exact_mut(self.row_width)
+                .zip(values.chunks_exact(DEFAULT_SEGMENT_WIDTH))
+            {
+                row[col_offset..col_offset + num_cols].copy_from_slice(&group_row[..num_cols]);
+            }
+        }
+
+        RowMatrix::from_data(data, self.row_width)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Writes the evaluations of a group of columns into a new file created in the specified
+/// directory; the file is deleted once it is closed.
+///
+/// # Panics
+/// Panics if the file could not be created or written.
+fn write_column_group<B: StarkField, const N: usize>(dir: &Path, segment: Segment<B, N>) -> File {
+    let file = tempfile::tempfile_in(dir).expect("failed to create a file for trace LDE");
+    let mut writer = BufWriter::new(file);
+    writer.write_many(flatten_vector_elements(segment.into_data()));
+    writer.flush().expect("failed to write trace LDE");
+    writer.into_inner().expect("failed to write trace LDE")
+}
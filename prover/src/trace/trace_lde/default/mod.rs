@@ -3,21 +3,59 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use alloc::vec::Vec;
-use core::marker::PhantomData;
+use alloc::{sync::Arc, vec::Vec};
+use core::{borrow::Borrow, marker::PhantomData};
 
-use air::{proof::Queries, LagrangeKernelEvaluationFrame, PartitionOptions, TraceInfo};
+use air::{
+    proof::{BatchOpening, Queries, QueryOpening},
+    LagrangeKernelEvaluationFrame, PartitionOptions, TraceInfo,
+};
 use crypto::VectorCommitment;
+use math::StarkField;
 use tracing::info_span;
+use utils::BufferPool;
 
 use super::{
     ColMatrix, ElementHasher, EvaluationFrame, FieldElement, StarkDomain, TraceLde, TracePolyTable,
 };
 use crate::{RowMatrix, DEFAULT_SEGMENT_WIDTH};
 
+#[cfg(feature = "std")]
+mod disk;
+#[cfg(feature = "std")]
+use disk::DiskSegmentLde;
+#[cfg(feature = "std")]
+pub use disk::DiskTraceLdeOptions;
+
 #[cfg(test)]
 mod tests;
 
+// TRACE LDE STORAGE
+// ================================================================================================
+
+/// Defines where a [DefaultTraceLde] keeps the low-degree extensions of trace segments.
+///
+/// The options trade memory for computation or I/O: segment LDEs can be kept in memory for the
+/// entire proving process, kept in memory only until constraints have been evaluated, or kept on
+/// disk. A prover chooses between them based on its memory budget (see
+/// [Prover::memory_budget()](crate::Prover::memory_budget)).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum TraceLdeStorage {
+    /// Segment LDEs are kept in memory (see [DefaultTraceLde::new()]).
+    #[default]
+    Memory,
+    /// Segment LDEs are kept in memory until constraints have been evaluated, and rows at query
+    /// positions are then recomputed from trace polynomials (see
+    /// [DefaultTraceLde::new_low_memory()]).
+    Recompute,
+    /// Segment LDEs are written into files and are paged into memory as rows are read (see
+    /// `DefaultTraceLde::new_on_disk()`).
+    ///
+    /// This option is available only when the crate is compiled with the `std` feature.
+    #[cfg(feature = "std")]
+    Disk(DiskTraceLdeOptions),
+}
+
 // TRACE LOW DEGREE EXTENSION
 // ================================================================================================
 /// Contains all segments of the extended execution trace, the commitments to these segments, the
@@ -28,30 +66,58 @@ mod tests;
 ///   will always be elements in the base field (even when an extension field is used).
 /// - Auxiliary segments: a list of 0 or more segments for traces generated after the prover
 ///   commits to the first trace segment. Currently, at most 1 auxiliary segment is possible.
+///
+/// Segments are opened at query positions using query opening scheme `O`.
+///
+/// A trace LDE created via [DefaultTraceLde::new_low_memory()] additionally shares the trace
+/// polynomials in coefficient form with the [TracePolyTable] returned alongside it. Once constraints have been evaluated, the extended segments
+/// of such a trace LDE are released (see [TraceLde::release_extended_trace()]) and the rows at
+/// query positions are recomputed from the trace polynomials; thus, only the polynomials and the
+/// segment commitments are kept in memory for the rest of the proving process.
+///
+/// A trace LDE created via [DefaultTraceLde::new_on_disk()] keeps the extended segments in files
+/// rather than in memory, and reads rows from the files as they are needed (see
+/// [DiskTraceLdeOptions]); this mode is available only when the crate is compiled with the `std`
+/// feature.
 pub struct DefaultTraceLde<
     E: FieldElement,
     H: ElementHasher<BaseField = E::BaseField>,
     V: VectorCommitment<H>,
+    O: QueryOpening<H, V> = BatchOpening,
 > {
     // low-degree extension of the main segment of the trace
-    main_segment_lde: RowMatrix<E::BaseField>,
+    main_segment_lde: SegmentLde<E::BaseField>,
     // commitment to the main segment of the trace
     main_segment_oracles: V,
     // low-degree extensions of the auxiliary segment of the trace
-    aux_segment_lde: Option<RowMatrix<E>>,
+    aux_segment_lde: Option<SegmentLde<E>>,
     // commitment to the auxiliary segment of the trace
     aux_segment_oracles: Option<V>,
+    // polynomials of the main and auxiliary segments of the trace from which the rows at query
+    // positions are recomputed once the segment LDEs have been released; these are kept only in
+    // low-memory mode, and are shared with the trace polynomial table
+    main_segment_polys: Option<Arc<ColMatrix<E::BaseField>>>,
+    aux_segment_polys: Option<Arc<ColMatrix<E>>>,
+    lde_domain_size: usize,
+    lde_domain_offset: E::BaseField,
     blowup: usize,
     trace_info: TraceInfo,
     partition_options: PartitionOptions,
+    // storage of the segment LDEs
+    storage: TraceLdeStorage,
+    // pool from which buffers for segment LDEs are taken, and to which they are returned when
+    // the segment LDEs are released or this trace LDE is dropped
+    buffer_pool: Option<BufferPool<E::BaseField>>,
     _h: PhantomData<H>,
+    _o: PhantomData<O>,
 }
 
-impl<E, H, V> DefaultTraceLde<E, H, V>
+impl<E, H, V, O> DefaultTraceLde<E, H, V, O>
 where
     E: FieldElement,
     H: ElementHasher<BaseField = E::BaseField>,
     V: VectorCommitment<H>,
+    O: QueryOpening<H, V>,
 {
     /// Takes the main trace segment columns as input, interpolates them into polynomials in
     /// coefficient form, evaluates the polynomials over the LDE domain, commits to the
@@ -65,26 +131,289 @@ where
         main_trace: &ColMatrix<E::BaseField>,
         domain: &StarkDomain<E::BaseField>,
         partition_options: PartitionOptions,
+    ) -> (Self, TracePolyTable<E>) {
+        Self::with_storage(
+            trace_info,
+            main_trace,
+            domain,
+            partition_options,
+            TraceLdeStorage::Memory,
+        )
+    }
+
+    /// Takes the main trace segment columns as input, interpolates them into polynomials in
+    /// coefficient form, evaluates the polynomials over the LDE domain, commits to the
+    /// polynomial evaluations, and creates a new [DefaultTraceLde] with the LDE of the main trace
+    /// segment and the commitment.
+    ///
+    /// The LDEs of all trace segments are kept in the specified `storage`; see
+    /// [TraceLdeStorage] for the available options.
+    ///
+    /// Returns a tuple containing a [TracePolyTable] with the trace polynomials for the main trace
+    /// segment and the new [DefaultTraceLde].
+    pub fn with_storage(
+        trace_info: &TraceInfo,
+        main_trace: &ColMatrix<E::BaseField>,
+        domain: &StarkDomain<E::BaseField>,
+        partition_options: PartitionOptions,
+        storage: TraceLdeStorage,
     ) -> (Self, TracePolyTable<E>) {
         // extend the main execution trace and build a commitment to the extended trace
-        let (main_segment_lde, main_segment_vector_com, main_segment_polys) =
-            build_trace_commitment::<E, E::BaseField, H, V>(main_trace, domain, partition_options);
+        let main_segment_polys = interpolate_trace(main_trace, ColMatrix::interpolate_columns);
+        Self::from_main_segment_polys(
+            trace_info,
+            main_segment_polys,
+            domain,
+            partition_options,
+            None,
+            storage,
+        )
+    }
+
+    /// Takes the main trace segment columns as input, interpolates them into polynomials in
+    /// coefficient form, evaluates the polynomials over the LDE domain, commits to the
+    /// polynomial evaluations, and creates a new [DefaultTraceLde] with the LDE of the main trace
+    /// segment and the commitment.
+    ///
+    /// This is equivalent to [DefaultTraceLde::new()], except that the LDEs of all trace segments
+    /// are built in buffers taken from the specified `pool`; the buffers are returned to the
+    /// pool once the returned [DefaultTraceLde] is dropped. Thus, when the same pool is used for
+    /// generating multiple proofs (e.g., the pool of a [ProverContext](crate::ProverContext)),
+    /// trace LDEs of subsequent proofs do not need to be allocated anew.
+    pub fn new_with_buffer_pool(
+        trace_info: &TraceInfo,
+        main_trace: &ColMatrix<E::BaseField>,
+        domain: &StarkDomain<E::BaseField>,
+        partition_options: PartitionOptions,
+        pool: &BufferPool<E::BaseField>,
+    ) -> (Self, TracePolyTable<E>) {
+        // extend the main execution trace and build a commitment to the extended trace
+        let main_segment_polys = interpolate_trace(main_trace, ColMatrix::interpolate_columns);
+        Self::from_main_segment_polys(
+            trace_info,
+            main_segment_polys,
+            domain,
+            partition_options,
+            Some(pool.clone()),
+            TraceLdeStorage::Memory,
+        )
+    }
 
+    /// Takes the main trace segment columns as input, interpolates them into polynomials in
+    /// coefficient form, evaluates the polynomials over the LDE domain, commits to the
+    /// polynomial evaluations, and creates a new [DefaultTraceLde] with the LDE of the main trace
+    /// segment and the commitment.
+    ///
+    /// This is equivalent to [DefaultTraceLde::new()], except that the returned [DefaultTraceLde]
+    /// operates in low-memory mode: it shares the trace polynomials with the returned
+    /// [TracePolyTable] (rather than keeping a copy of them), and once
+    /// [TraceLde::release_extended_trace()] is called, it drops the LDEs of all trace segments
+    /// and recomputes the rows at query positions by evaluating the trace polynomials. This
+    /// reduces peak memory usage of the prover after constraint evaluation at the cost of
+    /// `O(n)` field operations per queried row and column, where `n` is the trace length.
+    pub fn new_low_memory(
+        trace_info: &TraceInfo,
+        main_trace: &ColMatrix<E::BaseField>,
+        domain: &StarkDomain<E::BaseField>,
+        partition_options: PartitionOptions,
+    ) -> (Self, TracePolyTable<E>) {
+        Self::with_storage(
+            trace_info,
+            main_trace,
+            domain,
+            partition_options,
+            TraceLdeStorage::Recompute,
+        )
+    }
+
+    /// Takes the main trace segment columns as input, interpolates them into polynomials in
+    /// coefficient form, evaluates the polynomials over the LDE domain, commits to the
+    /// polynomial evaluations, and creates a new [DefaultTraceLde] with the LDE of the main trace
+    /// segment and the commitment.
+    ///
+    /// This is equivalent to [DefaultTraceLde::new()], except that the LDEs of all trace segments
+    /// are written into files (as specified by the `options`) rather than kept in memory: the
+    /// segments are extended a few columns at a time, and rows are read back from the files when
+    /// constraints are evaluated and when the segments are queried. Thus, traces whose extended
+    /// form does not fit into memory can be proven at the cost of extra I/O. The files are
+    /// deleted once the returned [DefaultTraceLde] is dropped.
+    ///
+    /// The trace polynomials are still kept in memory; to keep the commitments to the extended
+    /// segments on disk as well, a disk-backed vector commitment (e.g.,
+    /// [DiskMerkleTree](crypto::DiskMerkleTree)) can be used.
+    ///
+    /// # Panics
+    /// Panics if the files could not be created, written, or read.
+    #[cfg(feature = "std")]
+    pub fn new_on_disk(
+        trace_info: &TraceInfo,
+        main_trace: &ColMatrix<E::BaseField>,
+        domain: &StarkDomain<E::BaseField>,
+        partition_options: PartitionOptions,
+        options: DiskTraceLdeOptions,
+    ) -> (Self, TracePolyTable<E>) {
+        Self::with_storage(
+            trace_info,
+            main_trace,
+            domain,
+            partition_options,
+            TraceLdeStorage::Disk(options),
+        )
+    }
+
+    /// Takes an iterator over the rows of the main trace segment as input, interpolates the
+    /// columns of the segment into polynomials in coefficient form, evaluates the polynomials
+    /// over the LDE domain, commits to the polynomial evaluations, and creates a new
+    /// [DefaultTraceLde] with the LDE of the main trace segment and the commitment.
+    ///
+    /// Unlike [DefaultTraceLde::new()], this does not require the main trace segment to be built
+    /// upfront: rows are consumed one by one (e.g., as they are generated by executing a
+    /// computation) and written directly into the buffers which are then interpolated in place
+    /// into the trace polynomials. Thus, the trace and its polynomials are never kept in memory
+    /// at the same time.
+    ///
+    /// Returns a tuple containing a [TracePolyTable] with the trace polynomials for the main trace
+    /// segment and the new [DefaultTraceLde].
+    ///
+    /// # Panics
+    /// Panics if the iterator does not yield exactly `trace_info.length()` rows, or if any of the
+    /// rows does not contain exactly `trace_info.main_trace_width()` elements.
+    pub fn from_rows<I, R>(
+        trace_info: &TraceInfo,
+        rows: I,
+        domain: &StarkDomain<E::BaseField>,
+        partition_options: PartitionOptions,
+    ) -> (Self, TracePolyTable<E>)
+    where
+        I: IntoIterator<Item = R>,
+        R: AsRef<[E::BaseField]>,
+    {
+        // collect the rows into columns, extend the main execution trace, and build a commitment
+        // to the extended trace
+        let main_trace =
+            ColMatrix::from_rows(trace_info.main_trace_width(), trace_info.length(), rows);
+        let main_segment_polys = interpolate_trace(main_trace, ColMatrix::interpolate_columns_into);
+        Self::from_main_segment_polys(
+            trace_info,
+            main_segment_polys,
+            domain,
+            partition_options,
+            None,
+            TraceLdeStorage::Memory,
+        )
+    }
+
+    /// Evaluates the provided main trace segment polynomials over the LDE domain, commits to the
+    /// polynomial evaluations, and creates a new [DefaultTraceLde] with the LDE of the main trace
+    /// segment and the commitment.
+    ///
+    /// The segment LDEs are kept in the specified `storage`. If the LDEs are to be recomputed,
+    /// the returned [DefaultTraceLde] retains a reference to the trace polynomials in the
+    /// returned [TracePolyTable] so that rows at query positions can be recomputed after the
+    /// segment LDEs have been released.
+    fn from_main_segment_polys(
+        trace_info: &TraceInfo,
+        main_segment_polys: ColMatrix<E::BaseField>,
+        domain: &StarkDomain<E::BaseField>,
+        partition_options: PartitionOptions,
+        buffer_pool: Option<BufferPool<E::BaseField>>,
+        storage: TraceLdeStorage,
+    ) -> (Self, TracePolyTable<E>) {
+        let (main_segment_lde, main_segment_vector_com) =
+            build_trace_commitment::<E, E::BaseField, H, V>(
+                &main_segment_polys,
+                domain,
+                partition_options,
+                &storage,
+                buffer_pool.as_ref(),
+            );
+
+        let main_segment_polys = Arc::new(main_segment_polys);
+        let retained_polys =
+            (storage == TraceLdeStorage::Recompute).then(|| Arc::clone(&main_segment_polys));
         let trace_poly_table = TracePolyTable::new(main_segment_polys);
         let trace_lde = DefaultTraceLde {
             main_segment_lde,
             main_segment_oracles: main_segment_vector_com,
             aux_segment_lde: None,
             aux_segment_oracles: None,
+            main_segment_polys: retained_polys,
+            aux_segment_polys: None,
+            lde_domain_size: domain.lde_domain_size(),
+            lde_domain_offset: domain.offset(),
             blowup: domain.trace_to_lde_blowup(),
             trace_info: trace_info.clone(),
             partition_options,
+            storage,
+            buffer_pool,
             _h: PhantomData,
+            _o: PhantomData,
         };
 
         (trace_lde, trace_poly_table)
     }
 
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns true if the segment LDEs have been released and rows at query positions need to
+    /// be recomputed from the trace polynomials.
+    fn is_lde_released(&self) -> bool {
+        self.main_segment_lde.num_rows() == 0
+    }
+
+    /// Returns buffers holding the segment LDEs to the buffer pool (if one is set) and leaves
+    /// the segment LDEs without any rows.
+    fn release_segment_ldes(&mut self) {
+        let main_segment_data = self.main_segment_lde.take_data();
+        let aux_segment_data = self.aux_segment_lde.as_mut().and_then(SegmentLde::take_data);
+        if let Some(pool) = self.buffer_pool.as_ref() {
+            main_segment_data
+                .into_iter()
+                .chain(aux_segment_data)
+                .for_each(|data| pool.put(data));
+        }
+    }
+
+    /// Returns trace table rows at the specified positions along with an opening proof to these
+    /// rows; the rows are recomputed by evaluating the trace polynomials at the points of the LDE
+    /// domain corresponding to the positions.
+    fn query_from_polys(&self, positions: &[usize]) -> Vec<Queries> {
+        let g = E::BaseField::get_root_of_unity(self.lde_domain_size.ilog2());
+        let points = positions
+            .iter()
+            .map(|&pos| self.lde_domain_offset * g.exp_vartime((pos as u64).into()))
+            .collect::<Vec<_>>();
+
+        // build queries for the main trace segment
+        let main_segment_polys = self
+            .main_segment_polys
+            .as_ref()
+            .expect("expected main segment polys to be present");
+        let mut result = vec![build_segment_queries_from_polys::<E::BaseField, H, V, O>(
+            main_segment_polys,
+            &self.main_segment_oracles,
+            positions,
+            &points,
+        )];
+
+        // build queries for the auxiliary trace segment
+        if let Some(ref segment_oracles) = self.aux_segment_oracles {
+            let segment_polys = self
+                .aux_segment_polys
+                .as_ref()
+                .expect("expected aux segment polys to be present");
+            result.push(build_segment_queries_from_polys::<E, H, V, O>(
+                segment_polys,
+                segment_oracles,
+                positions,
+                &points,
+            ));
+        }
+
+        result
+    }
+
     // TEST HELPERS
     // --------------------------------------------------------------------------------------------
 
@@ -95,9 +424,16 @@ where
     }
 
     /// Returns a reference to [Matrix] representing the main trace segment.
+    ///
+    /// # Panics
+    /// Panics if the main trace segment is not kept in memory.
     #[cfg(test)]
     pub fn get_main_segment(&self) -> &RowMatrix<E::BaseField> {
-        &self.main_segment_lde
+        match self.main_segment_lde {
+            SegmentLde::Memory(ref segment_lde) => segment_lde,
+            #[cfg(feature = "std")]
+            SegmentLde::Disk(_) => panic!("main trace segment is not kept in memory"),
+        }
     }
 
     /// Returns the entire trace for the column at the specified index.
@@ -109,11 +445,25 @@ where
     }
 }
 
-impl<E, H, V> TraceLde<E> for DefaultTraceLde<E, H, V>
+impl<E, H, V, O> Drop for DefaultTraceLde<E, H, V, O>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    V: VectorCommitment<H>,
+    O: QueryOpening<H, V>,
+{
+    /// Returns buffers holding the segment LDEs to the buffer pool (if one is set).
+    fn drop(&mut self) {
+        self.release_segment_ldes();
+    }
+}
+
+impl<E, H, V, O> TraceLde<E> for DefaultTraceLde<E, H, V, O>
 where
     E: FieldElement,
     H: ElementHasher<BaseField = E::BaseField> + core::marker::Sync,
     V: VectorCommitment<H> + core::marker::Sync,
+    O: QueryOpening<H, V> + core::marker::Sync,
 {
     type HashFn = H;
     type VC = V;
@@ -141,10 +491,17 @@ where
         &mut self,
         aux_trace: &ColMatrix<E>,
         domain: &StarkDomain<E::BaseField>,
-    ) -> (ColMatrix<E>, H::Digest) {
+    ) -> (Arc<ColMatrix<E>>, H::Digest) {
         // extend the auxiliary trace segment and build a commitment to the extended trace
-        let (aux_segment_lde, aux_segment_oracles, aux_segment_polys) =
-            build_trace_commitment::<E, E, H, Self::VC>(aux_trace, domain, self.partition_options);
+        let aux_segment_polys =
+            Arc::new(interpolate_trace(aux_trace, ColMatrix::interpolate_columns));
+        let (aux_segment_lde, aux_segment_oracles) = build_trace_commitment::<E, E, H, Self::VC>(
+            &aux_segment_polys,
+            domain,
+            self.partition_options,
+            &self.storage,
+            self.buffer_pool.as_ref(),
+        );
 
         // check errors
         assert!(
@@ -157,41 +514,48 @@ where
             "the number of rows in the auxiliary segment must be the same as in the main segment"
         );
 
-        // save the lde and commitment
+        // save the lde and commitment; in low-memory mode, also keep a reference to the
+        // polynomials
         self.aux_segment_lde = Some(aux_segment_lde);
+        if self.main_segment_polys.is_some() {
+            self.aux_segment_polys = Some(Arc::clone(&aux_segment_polys));
+        }
         let commitment_string = aux_segment_oracles.commitment();
         self.aux_segment_oracles = Some(aux_segment_oracles);
 
         (aux_segment_polys, commitment_string)
     }
 
-    /// Reads current and next rows from the main trace segment into the specified frame.
+    /// Reads the rows of the frame (the rows at the frame offsets from the current row) from the
+    /// main trace segment into the specified frame.
     fn read_main_trace_frame_into(
         &self,
         lde_step: usize,
         frame: &mut EvaluationFrame<E::BaseField>,
     ) {
-        // at the end of the trace, next state wraps around and we read the first step again
-        let next_lde_step = (lde_step + self.blowup()) % self.trace_len();
-
-        // copy main trace segment values into the frame
-        frame.current_mut().copy_from_slice(self.main_segment_lde.row(lde_step));
-        frame.next_mut().copy_from_slice(self.main_segment_lde.row(next_lde_step));
+        // at the end of the trace, next states wrap around and we read the first steps again
+        for i in 0..frame.num_rows() {
+            let offset = frame.offsets()[i];
+            let row_lde_step = (lde_step + offset * self.blowup()) % self.trace_len();
+            self.main_segment_lde.read_row_into(row_lde_step, frame.row_mut(i));
+        }
     }
 
-    /// Reads current and next rows from the auxiliary trace segment into the specified frame.
+    /// Reads the rows of the frame (the rows at the frame offsets from the current row) from the
+    /// auxiliary trace segment into the specified frame.
     ///
     /// # Panics
     /// This currently assumes that there is exactly one auxiliary trace segment, and will panic
     /// otherwise.
     fn read_aux_trace_frame_into(&self, lde_step: usize, frame: &mut EvaluationFrame<E>) {
-        // at the end of the trace, next state wraps around and we read the first step again
-        let next_lde_step = (lde_step + self.blowup()) % self.trace_len();
-
-        // copy auxiliary trace segment values into the frame
         let segment = self.aux_segment_lde.as_ref().expect("expected aux segment to be present");
-        frame.current_mut().copy_from_slice(segment.row(lde_step));
-        frame.next_mut().copy_from_slice(segment.row(next_lde_step));
+
+        // at the end of the trace, next states wrap around and we read the first steps again
+        for i in 0..frame.num_rows() {
+            let offset = frame.offsets()[i];
+            let row_lde_step = (lde_step + offset * self.blowup()) % self.trace_len();
+            segment.read_row_into(row_lde_step, frame.row_mut(i));
+        }
     }
 
     fn read_lagrange_kernel_frame_into(
@@ -217,11 +581,26 @@ where
         }
     }
 
+    /// Releases the LDEs of all trace segments if this trace LDE operates in low-memory mode
+    /// (see [DefaultTraceLde::new_low_memory()]); otherwise, this does nothing.
+    ///
+    /// Once the LDEs have been released, trace frames can no longer be read, and rows at query
+    /// positions are recomputed from the trace polynomials.
+    fn release_extended_trace(&mut self) {
+        if self.main_segment_polys.is_some() {
+            self.release_segment_ldes();
+        }
+    }
+
     /// Returns trace table rows at the specified positions along with an opening proof to these
     /// rows againt the already computed commitment.
     fn query(&self, positions: &[usize]) -> Vec<Queries> {
+        if self.is_lde_released() {
+            return self.query_from_polys(positions);
+        }
+
         // build queries for the main trace segment
-        let mut result = vec![build_segment_queries::<E::BaseField, H, V>(
+        let mut result = vec![build_segment_queries::<E::BaseField, H, V, O>(
             &self.main_segment_lde,
             &self.main_segment_oracles,
             positions,
@@ -231,7 +610,11 @@ where
         if let Some(ref segment_oracles) = self.aux_segment_oracles {
             let segment_lde =
                 self.aux_segment_lde.as_ref().expect("expected aux segment to be present");
-            result.push(build_segment_queries::<E, H, V>(segment_lde, segment_oracles, positions));
+            result.push(build_segment_queries::<E, H, V, O>(
+                segment_lde,
+                segment_oracles,
+                positions,
+            ));
         }
 
         result
@@ -239,7 +622,7 @@ where
 
     /// Returns the number of rows in the execution trace.
     fn trace_len(&self) -> usize {
-        self.main_segment_lde.num_rows()
+        self.lde_domain_size
     }
 
     /// Returns blowup factor which was used to extend original execution trace into trace LDE.
@@ -253,46 +636,159 @@ where
     }
 }
 
+// SEGMENT LDE
+// ================================================================================================
+
+/// Low-degree extension of a single trace segment.
+enum SegmentLde<E: FieldElement> {
+    Memory(RowMatrix<E>),
+    #[cfg(feature = "std")]
+    Disk(DiskSegmentLde<E>),
+}
+
+impl<E: FieldElement> SegmentLde<E> {
+    /// Returns the number of rows in this segment LDE.
+    fn num_rows(&self) -> usize {
+        match self {
+            Self::Memory(segment_lde) => segment_lde.num_rows(),
+            #[cfg(feature = "std")]
+            Self::Disk(segment_lde) => segment_lde.num_rows(),
+        }
+    }
+
+    /// Returns the number of columns in this segment LDE.
+    fn num_cols(&self) -> usize {
+        match self {
+            Self::Memory(segment_lde) => segment_lde.num_cols(),
+            #[cfg(feature = "std")]
+            Self::Disk(segment_lde) => segment_lde.num_cols(),
+        }
+    }
+
+    /// Returns the element located at the specified column and row indexes.
+    fn get(&self, col_idx: usize, row_idx: usize) -> E {
+        match self {
+            Self::Memory(segment_lde) => segment_lde.get(col_idx, row_idx),
+            #[cfg(feature = "std")]
+            Self::Disk(segment_lde) => segment_lde.get(col_idx, row_idx),
+        }
+    }
+
+    /// Copies the row at the specified index into `row`.
+    fn read_row_into(&self, row_idx: usize, row: &mut [E]) {
+        match self {
+            Self::Memory(segment_lde) => row.copy_from_slice(segment_lde.row(row_idx)),
+            #[cfg(feature = "std")]
+            Self::Disk(segment_lde) => segment_lde.read_row_into(row_idx, row),
+        }
+    }
+
+    /// Returns the rows at the specified positions.
+    fn get_rows(&self, positions: &[usize]) -> Vec<Vec<E>> {
+        match self {
+            Self::Memory(segment_lde) => {
+                positions.iter().map(|&pos| segment_lde.row(pos).to_vec()).collect()
+            },
+            #[cfg(feature = "std")]
+            Self::Disk(segment_lde) => segment_lde.get_rows(positions),
+        }
+    }
+
+    /// Returns a commitment to this segment LDE built by hashing its rows and committing to the
+    /// row hashes using vector commitment scheme `V`.
+    fn commit_to_rows<H, V>(&self, partition_options: PartitionOptions) -> V
+    where
+        H: ElementHasher<BaseField = E::BaseField>,
+        V: VectorCommitment<H>,
+    {
+        match self {
+            Self::Memory(segment_lde) => segment_lde.commit_to_rows::<H, V>(partition_options),
+            #[cfg(feature = "std")]
+            Self::Disk(segment_lde) => segment_lde.commit_to_rows::<H, V>(partition_options),
+        }
+    }
+
+    /// Removes the data from this segment LDE if it is kept in memory and returns it; the segment
+    /// LDE is left without any rows.
+    fn take_data(&mut self) -> Option<Vec<E::BaseField>> {
+        match self {
+            Self::Memory(segment_lde) => Some(segment_lde.take_data()),
+            #[cfg(feature = "std")]
+            Self::Disk(_) => None,
+        }
+    }
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 
-/// Computes a low-degree extension (LDE) of the provided execution trace over the specified
-/// domain and builds a commitment to the extended trace.
+/// Interpolates the columns of the provided execution trace into polynomials in coefficient form
+/// using the specified interpolation function.
+///
+/// Each column of the execution trace is interpolated into a polynomial of degree
+/// trace_length - 1.
+pub(super) fn interpolate_trace<F, T>(
+    trace: T,
+    interpolate: impl FnOnce(T) -> ColMatrix<F>,
+) -> ColMatrix<F>
+where
+    F: FieldElement,
+    T: Borrow<ColMatrix<F>>,
+{
+    let trace_length = trace.borrow().num_rows();
+    let span = info_span!("interpolate_execution_trace", num_cols = trace.borrow().num_cols());
+    let trace_polys = span.in_scope(|| interpolate(trace));
+    assert_eq!(trace_polys.num_rows(), trace_length);
+    trace_polys
+}
+
+/// Computes a low-degree extension (LDE) of the execution trace described by the provided trace
+/// polynomials over the specified domain and builds a commitment to the extended trace.
 ///
-/// The extension is performed by interpolating each column of the execution trace into a
-/// polynomial of degree = trace_length - 1, and then evaluating the polynomial over the LDE
-/// domain.
+/// The extension is performed by evaluating each trace polynomial (of degree = trace_length - 1)
+/// over the LDE domain.
 ///
 /// The trace commitment is computed by building a vector containing the hashes of each row of
 /// the extended execution trace, then building a vector commitment to the resulting vector.
 fn build_trace_commitment<E, F, H, V>(
-    trace: &ColMatrix<F>,
+    trace_polys: &ColMatrix<F>,
     domain: &StarkDomain<E::BaseField>,
     partition_options: PartitionOptions,
-) -> (RowMatrix<F>, V, ColMatrix<F>)
+    storage: &TraceLdeStorage,
+    buffer_pool: Option<&BufferPool<E::BaseField>>,
+) -> (SegmentLde<F>, V)
 where
     E: FieldElement,
     F: FieldElement<BaseField = E::BaseField>,
     H: ElementHasher<BaseField = E::BaseField>,
     V: VectorCommitment<H>,
 {
-    // extend the execution trace
-    let (trace_lde, trace_polys) = {
-        let span = info_span!(
-            "extend_execution_trace",
-            num_cols = trace.num_cols(),
-            blowup = domain.trace_to_lde_blowup()
-        )
-        .entered();
-        let trace_polys = trace.interpolate_columns();
-        let trace_lde =
-            RowMatrix::evaluate_polys_over::<DEFAULT_SEGMENT_WIDTH>(&trace_polys, domain);
-        drop(span);
-
-        (trace_lde, trace_polys)
-    };
-    assert_eq!(trace_lde.num_cols(), trace.num_cols());
-    assert_eq!(trace_polys.num_rows(), trace.num_rows());
+    // extend the execution trace; when the extension is kept on disk, it is built a few columns
+    // at a time and the extended columns are written into files
+    let trace_lde = info_span!(
+        "extend_execution_trace",
+        num_cols = trace_polys.num_cols(),
+        blowup = domain.trace_to_lde_blowup()
+    )
+    .in_scope(|| match storage {
+        TraceLdeStorage::Memory | TraceLdeStorage::Recompute => {
+            SegmentLde::Memory(match buffer_pool {
+                Some(pool) => RowMatrix::evaluate_polys_over_with_pool::<DEFAULT_SEGMENT_WIDTH>(
+                    trace_polys,
+                    domain,
+                    pool,
+                ),
+                None => {
+                    RowMatrix::evaluate_polys_over::<DEFAULT_SEGMENT_WIDTH>(trace_polys, domain)
+                },
+            })
+        },
+        #[cfg(feature = "std")]
+        TraceLdeStorage::Disk(options) => {
+            SegmentLde::Disk(DiskSegmentLde::new(trace_polys, domain, options))
+        },
+    });
+    assert_eq!(trace_lde.num_cols(), trace_polys.num_cols());
     assert_eq!(trace_lde.num_rows(), domain.lde_domain_size());
 
     // build trace commitment
@@ -301,11 +797,11 @@ where
         .in_scope(|| trace_lde.commit_to_rows::<H, V>(partition_options));
     assert_eq!(trace_vector_com.domain_len(), commitment_domain_size);
 
-    (trace_lde, trace_vector_com, trace_polys)
+    (trace_lde, trace_vector_com)
 }
 
-fn build_segment_queries<E, H, V>(
-    segment_lde: &RowMatrix<E>,
+fn build_segment_queries<E, H, V, O>(
+    segment_lde: &SegmentLde<E>,
     segment_vector_com: &V,
     positions: &[usize],
 ) -> Queries
@@ -313,16 +809,35 @@ where
     E: FieldElement,
     H: ElementHasher<BaseField = E::BaseField>,
     V: VectorCommitment<H>,
+    O: QueryOpening<H, V>,
 {
     // for each position, get the corresponding row from the trace segment LDE and put all these
     // rows into a single vector
-    let trace_states =
-        positions.iter().map(|&pos| segment_lde.row(pos).to_vec()).collect::<Vec<_>>();
+    let trace_states = segment_lde.get_rows(positions);
 
-    // build a batch opening proof to the leaves specified by positions
-    let trace_proof = segment_vector_com
-        .open_many(positions)
-        .expect("failed to generate a batch opening proof for trace queries");
+    // open the segment commitment at the specified positions
+    O::open(segment_vector_com, positions, trace_states)
+}
 
-    Queries::new::<H, E, V>(trace_proof.1, trace_states)
+fn build_segment_queries_from_polys<E, H, V, O>(
+    segment_polys: &ColMatrix<E>,
+    segment_vector_com: &V,
+    positions: &[usize],
+    points: &[E::BaseField],
+) -> Queries
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    V: VectorCommitment<H>,
+    O: QueryOpening<H, V>,
+{
+    // for each position, evaluate the segment polynomials at the corresponding point of the LDE
+    // domain, which yields the row of the trace segment LDE at that position
+    let trace_states = points
+        .iter()
+        .map(|&x| segment_polys.evaluate_columns_at(E::from(x)))
+        .collect::<Vec<_>>();
+
+    // open the segment commitment at the specified positions
+    O::open(segment_vector_com, positions, trace_states)
 }
@@ -31,7 +31,7 @@ fn extend_trace_table() {
     // build the trace polynomials, extended trace, and commitment using the default TraceLde impl
     let (trace_lde, trace_polys) = DefaultTraceLde::<BaseElement, Blake3, MerkleTree<Blake3>>::new(
         trace.info(),
-        trace.main_segment(),
+        &trace.main_segment(),
         &domain,
         partition_option,
     );
@@ -83,7 +83,7 @@ fn commit_trace_table() {
     // build the trace polynomials, extended trace, and commitment using the default TraceLde impl
     let (trace_lde, _) = DefaultTraceLde::<BaseElement, Blake3, MerkleTree<Blake3>>::new(
         trace.info(),
-        trace.main_segment(),
+        &trace.main_segment(),
         &domain,
         partition_option,
     );
@@ -105,6 +105,154 @@ fn commit_trace_table() {
     assert_eq!(*expected_tree.root(), trace_lde.get_main_trace_commitment())
 }
 
+#[test]
+fn extend_trace_rows() {
+    // build the trace and the domain
+    let trace_length = 8;
+    let air = MockAir::with_trace_length(trace_length);
+    let trace = build_fib_trace(trace_length * 2);
+    let domain = StarkDomain::new(&air);
+    let partition_option = PartitionOptions::default();
+
+    // extend the trace from its columns and, in a streaming fashion, from its rows
+    let (expected_lde, expected_polys) =
+        DefaultTraceLde::<BaseElement, Blake3, MerkleTree<Blake3>>::new(
+            trace.info(),
+            &trace.main_segment(),
+            &domain,
+            partition_option,
+        );
+    let rows = (0..trace_length).map(|i| {
+        let mut row = [BaseElement::ZERO; 2];
+        trace.read_row_into(i, &mut row);
+        row
+    });
+    let (trace_lde, trace_polys) =
+        DefaultTraceLde::<BaseElement, Blake3, MerkleTree<Blake3>>::from_rows(
+            trace.info(),
+            rows,
+            &domain,
+            partition_option,
+        );
+
+    // make sure the trace polynomials, the extended trace, and the commitment are the same
+    assert_eq!(expected_polys.get_main_trace_poly(0), trace_polys.get_main_trace_poly(0));
+    assert_eq!(expected_polys.get_main_trace_poly(1), trace_polys.get_main_trace_poly(1));
+    assert_eq!(expected_lde.get_main_segment().data(), trace_lde.get_main_segment().data());
+    assert_eq!(expected_lde.get_main_trace_commitment(), trace_lde.get_main_trace_commitment());
+}
+
+#[test]
+fn query_low_memory_trace() {
+    // build the trace and the domain
+    let trace_length = 8;
+    let air = MockAir::with_trace_length(trace_length);
+    let trace = build_fib_trace(trace_length * 2);
+    let domain = StarkDomain::new(&air);
+    let partition_option = PartitionOptions::default();
+
+    let (expected_lde, _) = DefaultTraceLde::<BaseElement, Blake3, MerkleTree<Blake3>>::new(
+        trace.info(),
+        &trace.main_segment(),
+        &domain,
+        partition_option,
+    );
+    let (mut trace_lde, trace_polys) =
+        DefaultTraceLde::<BaseElement, Blake3, MerkleTree<Blake3>>::new_low_memory(
+            trace.info(),
+            &trace.main_segment(),
+            &domain,
+            partition_option,
+        );
+    assert_eq!(expected_lde.get_main_segment().data(), trace_lde.get_main_segment().data());
+
+    // the trace polynomials are shared with the polynomial table rather than copied
+    let retained_polys = trace_lde.main_segment_polys.as_ref().unwrap();
+    for i in 0..trace_polys.num_main_trace_polys() {
+        assert!(core::ptr::eq(trace_polys.get_main_trace_poly(i), retained_polys.get_column(i)));
+    }
+
+    // once the extended trace is released, rows at query positions are recomputed from trace
+    // polynomials, and are the same as the rows of the extended trace
+    trace_lde.release_extended_trace();
+    assert_eq!(0, trace_lde.get_main_segment().num_rows());
+    assert_eq!(expected_lde.trace_len(), trace_lde.trace_len());
+
+    let positions = [0, 1, 7, 22, 41, 63];
+    assert_eq!(expected_lde.query(&positions), trace_lde.query(&positions));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn extend_trace_on_disk() {
+    use air::TraceInfo;
+    use math::{
+        fft,
+        fields::{f64, QuadExtension},
+    };
+    use rand_utils::rand_vector;
+
+    use crate::{matrix::ColMatrix, DiskTraceLdeOptions, EvaluationFrame};
+
+    type B = f64::BaseElement;
+    type E = QuadExtension<B>;
+    type H = Blake3_256<B>;
+
+    // build a trace spanning several column groups, and a domain
+    let trace_length = 16;
+    let trace_info = TraceInfo::new_multi_segment(11, 3, 0, trace_length, vec![]);
+    let main_trace = ColMatrix::new((0..11).map(|_| rand_vector::<B>(trace_length)).collect());
+    let aux_trace = ColMatrix::new((0..3).map(|_| rand_vector::<E>(trace_length)).collect());
+    let domain = StarkDomain::from_twiddles(fft::get_twiddles(trace_length), 8, B::GENERATOR);
+    let lde_domain_size = domain.lde_domain_size();
+
+    for partition_options in [PartitionOptions::default(), PartitionOptions::new(3, 1)] {
+        let (mut expected_lde, _) = DefaultTraceLde::<E, H, MerkleTree<H>>::new(
+            &trace_info,
+            &main_trace,
+            &domain,
+            partition_options,
+        );
+
+        // keep only a couple of small pages in memory so that pages are evicted from the cache
+        let options = DiskTraceLdeOptions::default().with_rows_per_page(8).with_cached_pages(2);
+        let (mut trace_lde, _) = DefaultTraceLde::<E, H, MerkleTree<H>>::new_on_disk(
+            &trace_info,
+            &main_trace,
+            &domain,
+            partition_options,
+            options,
+        );
+
+        // commitments are the same as commitments to the extended trace kept in memory
+        assert_eq!(expected_lde.get_main_trace_commitment(), trace_lde.get_main_trace_commitment());
+        let (_, expected_aux_commitment) = expected_lde.set_aux_trace(&aux_trace, &domain);
+        let (_, aux_commitment) = trace_lde.set_aux_trace(&aux_trace, &domain);
+        assert_eq!(expected_aux_commitment, aux_commitment);
+
+        // frames read from the files are the same as frames read from memory
+        let mut expected_main_frame = EvaluationFrame::<B>::new(11);
+        let mut main_frame = EvaluationFrame::<B>::new(11);
+        let mut expected_aux_frame = EvaluationFrame::<E>::new(3);
+        let mut aux_frame = EvaluationFrame::<E>::new(3);
+        for step in (0..lde_domain_size).map(|i| (i * 37) % lde_domain_size) {
+            expected_lde.read_main_trace_frame_into(step, &mut expected_main_frame);
+            trace_lde.read_main_trace_frame_into(step, &mut main_frame);
+            assert_eq!(expected_main_frame.current(), main_frame.current());
+            assert_eq!(expected_main_frame.next(), main_frame.next());
+
+            expected_lde.read_aux_trace_frame_into(step, &mut expected_aux_frame);
+            trace_lde.read_aux_trace_frame_into(step, &mut aux_frame);
+            assert_eq!(expected_aux_frame.current(), aux_frame.current());
+            assert_eq!(expected_aux_frame.next(), aux_frame.next());
+        }
+
+        // so are the rows at query positions
+        let positions = [0, 1, 7, 22, 41, 63, 100, 127];
+        assert_eq!(expected_lde.query(&positions), trace_lde.query(&positions));
+    }
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 
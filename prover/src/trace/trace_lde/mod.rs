@@ -3,7 +3,7 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use alloc::vec::Vec;
+use alloc::{sync::Arc, vec::Vec};
 
 use air::{proof::Queries, LagrangeKernelEvaluationFrame, TraceInfo};
 use crypto::{ElementHasher, Hasher, VectorCommitment};
@@ -12,7 +12,9 @@ use super::{ColMatrix, EvaluationFrame, FieldElement, TracePolyTable};
 use crate::StarkDomain;
 
 mod default;
-pub use default::DefaultTraceLde;
+#[cfg(feature = "std")]
+pub use default::DiskTraceLdeOptions;
+pub use default::{DefaultTraceLde, TraceLdeStorage};
 
 // TRACE LOW DEGREE EXTENSION
 // ================================================================================================
@@ -23,6 +25,16 @@ pub use default::DefaultTraceLde;
 ///   will always be elements in the base field (even when an extension field is used).
 /// - Auxiliary segments: a list of 0 or more segments for traces generated after the prover
 ///   commits to the first trace segment. Currently, at most 1 auxiliary segment is possible.
+///
+/// The prover accesses the extended trace only via methods of this trait: frames are read one row
+/// at a time during constraint evaluation, and rows are read at query positions when building the
+/// proof. Thus, the extended trace does not need to reside in memory. [DefaultTraceLde] keeps all
+/// extended segments in memory by default; in low-memory mode (see
+/// [DefaultTraceLde::new_low_memory()]), it releases them once constraints have been evaluated,
+/// and when created via `DefaultTraceLde::new_on_disk()`, it keeps them in files and pages rows
+/// in as they are read, at the cost of extra I/O. Note that the main segment of the execution
+/// trace (see [Trace::main_segment()](crate::Trace::main_segment)) and trace polynomials are
+/// always kept in memory while the trace is being extended.
 pub trait TraceLde<E: FieldElement>: Sync {
     /// The hash function used for hashing the rows of trace segment LDEs.
     type HashFn: ElementHasher<BaseField = E::BaseField>;
@@ -38,7 +50,8 @@ pub trait TraceLde<E: FieldElement>: Sync {
     /// polynomial evaluations.
     ///
     /// Returns a tuple containing the column polynomials in coefficient form and the commitment
-    /// to the polynomial evaluations over the LDE domain.
+    /// to the polynomial evaluations over the LDE domain. The polynomials are reference-counted
+    /// so that an implementation can retain them without copying.
     ///
     /// # Panics
     ///
@@ -49,16 +62,18 @@ pub trait TraceLde<E: FieldElement>: Sync {
         &mut self,
         aux_trace: &ColMatrix<E>,
         domain: &StarkDomain<E::BaseField>,
-    ) -> (ColMatrix<E>, <Self::HashFn as Hasher>::Digest);
+    ) -> (Arc<ColMatrix<E>>, <Self::HashFn as Hasher>::Digest);
 
-    /// Reads current and next rows from the main trace segment into the specified frame.
+    /// Reads the rows of the frame (the rows at offsets `frame.offsets()` from the current row)
+    /// from the main trace segment into the specified frame.
     fn read_main_trace_frame_into(
         &self,
         lde_step: usize,
         frame: &mut EvaluationFrame<E::BaseField>,
     );
 
-    /// Reads current and next rows from the auxiliary trace segment into the specified frame.
+    /// Reads the rows of the frame (the rows at offsets `frame.offsets()` from the current row)
+    /// from the auxiliary trace segment into the specified frame.
     fn read_aux_trace_frame_into(&self, lde_step: usize, frame: &mut EvaluationFrame<E>);
 
     /// Populates the provided Lagrange kernel frame starting at the current row (as defined by
@@ -73,6 +88,15 @@ pub trait TraceLde<E: FieldElement>: Sync {
         frame: &mut LagrangeKernelEvaluationFrame<E>,
     );
 
+    /// Notifies this trace LDE that constraints have been evaluated over the extended trace.
+    ///
+    /// After this method is called, the prover no longer reads trace frames, and only
+    /// [TraceLde::query()] is invoked. Thus, implementations which can recompute rows at query
+    /// positions by other means (e.g., from trace polynomials, as [DefaultTraceLde] does in
+    /// low-memory mode) may release the extended trace here. The default implementation does
+    /// nothing.
+    fn release_extended_trace(&mut self) {}
+
     /// Returns trace table rows at the specified positions along with an opening proof to these
     /// rows.
     fn query(&self, positions: &[usize]) -> Vec<Queries>;
@@ -3,13 +3,15 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use alloc::vec::Vec;
+use alloc::{borrow::Cow, string::ToString, vec::Vec};
 
 use air::{EvaluationFrame, TraceInfo};
 use math::StarkField;
-use utils::uninit_vector;
 #[cfg(feature = "concurrent")]
 use utils::{iterators::*, rayon};
+use utils::{
+    uninit_vector, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+};
 
 use super::{ColMatrix, Trace};
 
@@ -58,6 +60,19 @@ const MIN_FRAGMENT_LENGTH: usize = 2;
 /// [fill()](TraceTableFragment::fill) method to fill all fragments with data in parallel.
 /// The semantics of the fragment's [TraceTableFragment::fill()] method are identical to the
 /// semantics of the [TraceTable::fill()] method.
+///
+/// Alternatively, [split()](TraceTable::split) method breaks the execution trace into the
+/// specified number of fragments (e.g., one per thread). Besides being filled via
+/// [TraceTableFragment::fill()], fragments can also be updated one cell or one row at a time via
+/// [TraceTableFragment::set()] and [TraceTableFragment::update_row()] methods.
+///
+/// # Checkpointing
+/// `TraceTable` implements [Serializable] and [Deserializable] traits. Thus, an execution trace
+/// which is expensive to generate can be written to persistent storage once it is built, and
+/// proof generation can be restarted from the stored trace (e.g., after the prover process has
+/// been interrupted) without re-executing the computation. Since proof generation is
+/// deterministic (in zero-knowledge mode, provided the same seed is used), the resulting proof
+/// is the same as the one which would have been generated from the original trace.
 #[derive(Debug, Clone)]
 pub struct TraceTable<B: StarkField> {
     info: TraceInfo,
@@ -75,7 +90,7 @@ impl<B: StarkField> TraceTable<B> {
     ///
     /// # Panics
     /// Panics if:
-    /// * `width` is zero or greater than 255.
+    /// * `width` is zero or greater than 65535.
     /// * `length` is smaller than 8, greater than biggest multiplicative subgroup in the field
     ///   `B`, or is not a power of two.
     pub fn new(width: usize, length: usize) -> Self {
@@ -90,7 +105,7 @@ impl<B: StarkField> TraceTable<B> {
     ///
     /// # Panics
     /// Panics if:
-    /// * `width` is zero or greater than 255.
+    /// * `width` is zero or greater than 65535.
     /// * `length` is smaller than 8, greater than the biggest multiplicative subgroup in the
     ///   field `B`, or is not a power of two.
     /// * Length of `meta` is greater than 65535;
@@ -112,7 +127,7 @@ impl<B: StarkField> TraceTable<B> {
     ///
     /// # Panics
     /// Panics if:
-    /// * The `columns` vector is empty or has over 255 columns.
+    /// * The `columns` vector is empty or has over 65535 columns.
     /// * Number of elements in any of the columns is smaller than 8, greater than the biggest
     ///   multiplicative subgroup in the field `B`, or is not a power of two.
     /// * Number of elements is not identical for all columns.
@@ -136,6 +151,35 @@ impl<B: StarkField> TraceTable<B> {
         Self { info, trace: ColMatrix::new(columns) }
     }
 
+    /// Creates a new execution trace by placing the main segments of the two provided traces
+    /// side by side.
+    ///
+    /// The columns of the `first` trace are followed by the columns of the `second` trace, and
+    /// the info of the resulting trace is built via [TraceInfo::compose()]. Such a trace can be
+    /// proven against a [ComposedAir](air::ComposedAir) of the AIRs of the two traces.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * Either of the traces has an auxiliary segment.
+    /// * The traces have different lengths.
+    /// * Total width of the traces is greater than 65535.
+    /// * Total length of the metadata of the traces is greater than 65531.
+    pub fn compose<T1, T2>(first: &T1, second: &T2) -> Self
+    where
+        T1: Trace<BaseField = B>,
+        T2: Trace<BaseField = B>,
+    {
+        let info = TraceInfo::compose(first.info(), second.info());
+        let columns = first
+            .main_segment()
+            .columns()
+            .chain(second.main_segment().columns())
+            .map(|column| column.to_vec())
+            .collect();
+
+        Self { info, trace: ColMatrix::new(columns) }
+    }
+
     // DATA MUTATORS
     // --------------------------------------------------------------------------------------------
 
@@ -196,7 +240,7 @@ impl<B: StarkField> TraceTable<B> {
     pub fn fragments(
         &mut self,
         fragment_length: usize,
-    ) -> alloc::vec::IntoIter<TraceTableFragment<B>> {
+    ) -> alloc::vec::IntoIter<TraceTableFragment<'_, B>> {
         self.build_fragments(fragment_length).into_iter()
     }
 
@@ -212,13 +256,60 @@ impl<B: StarkField> TraceTable<B> {
     pub fn fragments(
         &mut self,
         fragment_length: usize,
-    ) -> rayon::vec::IntoIter<TraceTableFragment<B>> {
+    ) -> rayon::vec::IntoIter<TraceTableFragment<'_, B>> {
         self.build_fragments(fragment_length).into_par_iter()
     }
 
+    /// Breaks the execution trace into the specified number of mutable fragments.
+    ///
+    /// All fragments cover disjoint sets of consecutive rows and have the same length. The
+    /// returned fragments can be used to update data in the trace from multiple threads.
+    ///
+    /// # Panics
+    /// Panics if `num_fragments` is not a power of two or if the resulting fragment length would
+    /// be smaller than 2.
+    #[cfg(not(feature = "concurrent"))]
+    pub fn split(
+        &mut self,
+        num_fragments: usize,
+    ) -> alloc::vec::IntoIter<TraceTableFragment<'_, B>> {
+        self.fragments(self.fragment_length(num_fragments))
+    }
+
+    /// Breaks the execution trace into the specified number of mutable fragments.
+    ///
+    /// All fragments cover disjoint sets of consecutive rows and have the same length. The
+    /// returned fragments can be used to update data in the trace from multiple threads.
+    ///
+    /// # Panics
+    /// Panics if `num_fragments` is not a power of two or if the resulting fragment length would
+    /// be smaller than 2.
+    #[cfg(feature = "concurrent")]
+    pub fn split(
+        &mut self,
+        num_fragments: usize,
+    ) -> rayon::vec::IntoIter<TraceTableFragment<'_, B>> {
+        self.fragments(self.fragment_length(num_fragments))
+    }
+
+    /// Returns the length of fragments when the trace is broken into `num_fragments` fragments.
+    fn fragment_length(&self, num_fragments: usize) -> usize {
+        assert!(
+            num_fragments.is_power_of_two(),
+            "number of fragments must be a power of 2, but was {num_fragments}"
+        );
+        assert!(
+            num_fragments <= self.info.length() / MIN_FRAGMENT_LENGTH,
+            "number of fragments cannot exceed {}, but was {}",
+            self.info.length() / MIN_FRAGMENT_LENGTH,
+            num_fragments
+        );
+        self.info.length() / num_fragments
+    }
+
     /// Returns a vector of trace fragments each covering the number of steps specified by the
     /// `fragment_length` parameter.
-    fn build_fragments(&mut self, fragment_length: usize) -> Vec<TraceTableFragment<B>> {
+    fn build_fragments(&mut self, fragment_length: usize) -> Vec<TraceTableFragment<'_, B>> {
         assert!(
             fragment_length >= MIN_FRAGMENT_LENGTH,
             "fragment length must be at least {MIN_FRAGMENT_LENGTH}, but was {fragment_length}"
@@ -285,13 +376,56 @@ impl<B: StarkField> Trace for TraceTable<B> {
     }
 
     fn read_main_frame(&self, row_idx: usize, frame: &mut EvaluationFrame<Self::BaseField>) {
-        let next_row_idx = (row_idx + 1) % self.info.length();
-        self.trace.read_row_into(row_idx, frame.current_mut());
-        self.trace.read_row_into(next_row_idx, frame.next_mut());
+        for i in 0..frame.num_rows() {
+            let frame_row_idx = (row_idx + frame.offsets()[i]) % self.info.length();
+            self.trace.read_row_into(frame_row_idx, frame.row_mut(i));
+        }
     }
 
-    fn main_segment(&self) -> &ColMatrix<B> {
-        &self.trace
+    fn main_segment(&self) -> Cow<'_, ColMatrix<B>> {
+        Cow::Borrowed(&self.trace)
+    }
+}
+
+// SERIALIZATION
+// ================================================================================================
+
+impl<B: StarkField> Serializable for TraceTable<B> {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.info.write_into(target);
+        for column in self.trace.columns() {
+            target.write_many(column);
+        }
+    }
+}
+
+impl<B: StarkField> Deserializable for TraceTable<B> {
+    /// Reads a [TraceTable] from the specified `source` and returns the result.
+    ///
+    /// # Errors
+    /// Returns an error if a valid [TraceTable] could not be read from the specified `source`.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let info = TraceInfo::read_from(source)?;
+        if info.is_multi_segment() {
+            return Err(DeserializationError::InvalidValue(
+                "trace table cannot contain auxiliary trace segments".to_string(),
+            ));
+        }
+        if info.length().ilog2() > B::TWO_ADICITY {
+            return Err(DeserializationError::InvalidValue(format!(
+                "execution trace length cannot exceed 2^{} steps, but was 2^{}",
+                B::TWO_ADICITY,
+                info.length().ilog2()
+            )));
+        }
+
+        let mut columns = Vec::with_capacity(info.main_trace_width());
+        for _ in 0..info.main_trace_width() {
+            columns.push(source.read_many(info.length())?);
+        }
+
+        Ok(Self { info, trace: ColMatrix::new(columns) })
     }
 }
 
@@ -338,6 +472,22 @@ impl<B: StarkField> TraceTableFragment<'_, B> {
         self.data.len()
     }
 
+    /// Returns value of the cell in the specified column at the specified row of this fragment.
+    ///
+    /// The row index is relative to the start of the fragment.
+    pub fn get(&self, column: usize, row_idx: usize) -> B {
+        self.data[column][row_idx]
+    }
+
+    /// Reads a single row of this fragment into the provided target.
+    ///
+    /// The row index is relative to the start of the fragment.
+    pub fn read_row_into(&self, row_idx: usize, target: &mut [B]) {
+        for (value, column) in target.iter_mut().zip(self.data.iter()) {
+            *value = column[row_idx];
+        }
+    }
+
     // DATA MUTATORS
     // --------------------------------------------------------------------------------------------
 
@@ -367,6 +517,19 @@ impl<B: StarkField> TraceTableFragment<'_, B> {
         }
     }
 
+    /// Sets the value of the cell in the specified column at the specified row of this fragment.
+    ///
+    /// The row index is relative to the start of the fragment.
+    pub fn set(&mut self, column: usize, row_idx: usize, value: B) {
+        self.data[column][row_idx] = value;
+    }
+
+    /// Returns a mutable reference to the portion of the specified column covered by this
+    /// fragment.
+    pub fn get_column_mut(&mut self, column: usize) -> &mut [B] {
+        self.data[column]
+    }
+
     /// Updates a single row in the fragment with provided data.
     pub fn update_row(&mut self, row_idx: usize, row_data: &[B]) {
         for (column, &value) in self.data.iter_mut().zip(row_data) {
@@ -0,0 +1,63 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use core::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+// YIELD NOW
+// ================================================================================================
+
+/// A future which yields control back to the executor once before completing.
+///
+/// The first time the future is polled, it wakes its task and returns [Poll::Pending]; this
+/// allows the executor to run other tasks before the future is polled again and completes.
+#[derive(Debug, Default)]
+pub(crate) struct YieldNow {
+    yielded: bool,
+}
+
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if self.yielded {
+            return Poll::Ready(());
+        }
+        self.yielded = true;
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use core::{
+        future::Future,
+        pin::pin,
+        ptr,
+        task::{Context, Poll, RawWaker, RawWakerVTable, Waker},
+    };
+
+    use super::YieldNow;
+
+    const VTABLE: RawWakerVTable =
+        RawWakerVTable::new(|_| RawWaker::new(ptr::null(), &VTABLE), |_| {}, |_| {}, |_| {});
+
+    #[test]
+    fn yield_now() {
+        let waker = unsafe { Waker::from_raw(RawWaker::new(ptr::null(), &VTABLE)) };
+        let mut cx = Context::from_waker(&waker);
+
+        let mut future = pin!(YieldNow::default());
+        assert_eq!(Poll::Pending, future.as_mut().poll(&mut cx));
+        assert_eq!(Poll::Ready(()), future.as_mut().poll(&mut cx));
+    }
+}
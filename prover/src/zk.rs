@@ -0,0 +1,69 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::vec::Vec;
+
+use air::AirContext;
+use crypto::{DefaultRandomCoin, ElementHasher, RandomCoin};
+use math::{FieldElement, StarkField};
+
+use crate::{constraints::CompositionMask, matrix::ColMatrix};
+
+// ZERO-KNOWLEDGE RANDOMNESS
+// ================================================================================================
+
+/// Source of the random values used by the prover in zero-knowledge mode.
+///
+/// Random values are derived from a secret seed; unlike the randomness drawn from the public
+/// coin, these values are never revealed to the verifier.
+pub(crate) struct ZkRandomness<H: ElementHasher> {
+    coin: DefaultRandomCoin<H>,
+}
+
+impl<H: ElementHasher> ZkRandomness<H> {
+    /// Returns a new source of randomness instantiated with the provided secret seed.
+    pub fn new(seed: [u8; 32]) -> Self {
+        let seed = seed
+            .chunks(H::BaseField::ELEMENT_BYTES - 1)
+            .map(H::BaseField::from_bytes_with_padding)
+            .collect::<Vec<_>>();
+        Self { coin: DefaultRandomCoin::new(&seed) }
+    }
+
+    /// Returns a new random field element.
+    pub fn draw<E: FieldElement<BaseField = H::BaseField>>(&mut self) -> E {
+        self.coin.draw().expect("failed to draw a random element")
+    }
+
+    /// Overwrites the last `num_rows` rows of the provided trace segment with random values.
+    pub fn randomize_rows<E: FieldElement<BaseField = H::BaseField>>(
+        &mut self,
+        trace: &mut ColMatrix<E>,
+        num_rows: usize,
+    ) {
+        let first_row = trace.num_rows() - num_rows;
+        for column in trace.columns_mut() {
+            for value in column[first_row..].iter_mut() {
+                *value = self.draw();
+            }
+        }
+    }
+
+    /// Returns random polynomials which mask the constraint composition polynomial for the
+    /// computation described by the provided context.
+    pub fn draw_composition_mask<E: FieldElement<BaseField = H::BaseField>>(
+        &mut self,
+        context: &AirContext<H::BaseField>,
+    ) -> CompositionMask<E> {
+        // the last column holds the randomizer polynomial, and thus, there is one fewer mask
+        // than there are composition polynomial segments
+        let num_masks = context.num_constraint_composition_columns() - 2;
+        let masks = (0..num_masks)
+            .map(|_| (0..context.num_random_rows()).map(|_| self.draw()).collect())
+            .collect();
+        let randomizer = (0..context.trace_len()).map(|_| self.draw()).collect();
+        CompositionMask::new(context.composition_segment_len(), masks, randomizer)
+    }
+}
@@ -26,6 +26,9 @@ pub use serde::{ByteReader, ByteWriter, Deserializable, Serializable, SliceReade
 mod errors;
 pub use errors::DeserializationError;
 
+mod pool;
+pub use pool::BufferPool;
+
 #[cfg(test)]
 mod tests;
 
@@ -164,6 +167,32 @@ pub fn flatten_vector_elements<T, const N: usize>(source: Vec<[T; N]>) -> Vec<T>
 /// assert_eq!(vec![[0, 4], [1, 5], [2, 6], [3, 7]], b);
 /// ```
 pub fn transpose_slice<T: Copy + Send + Sync, const N: usize>(source: &[T]) -> Vec<[T; N]> {
+    let mut result: Vec<[T; N]> = unsafe { uninit_vector(source.len() / N) };
+    transpose_slice_into(source, &mut result);
+    result
+}
+
+/// Transposes a slice of `n` elements into a matrix with `N` columns and `n`/`N` rows, writing
+/// the rows of the matrix into the provided `result` slice.
+///
+/// When `concurrent` feature is enabled, the slice will be transposed using multiple threads.
+///
+/// # Panics
+/// Panics if `n` is not divisible by `N`, or if the length of `result` is not `n`/`N`.
+///
+/// # Example
+/// ```
+/// # use winter_utils::transpose_slice_into;
+/// let a = [0_u32, 1, 2, 3, 4, 5, 6, 7];
+/// let mut b = [[0_u32; 2]; 4];
+/// transpose_slice_into(&a, &mut b);
+///
+/// assert_eq!([[0, 4], [1, 5], [2, 6], [3, 7]], b);
+/// ```
+pub fn transpose_slice_into<T: Copy + Send + Sync, const N: usize>(
+    source: &[T],
+    result: &mut [[T; N]],
+) {
     let row_count = source.len() / N;
     assert_eq!(
         row_count * N,
@@ -172,14 +201,19 @@ pub fn transpose_slice<T: Copy + Send + Sync, const N: usize>(source: &[T]) -> V
         N,
         source.len()
     );
+    assert_eq!(
+        result.len(),
+        row_count,
+        "result length must be {}, but was {}",
+        row_count,
+        result.len()
+    );
 
-    let mut result: Vec<[T; N]> = unsafe { uninit_vector(row_count) };
     iter_mut!(result, 1024).enumerate().for_each(|(i, element)| {
         for j in 0..N {
             element[j] = source[i + j * row_count]
         }
     });
-    result
 }
 
 // RANDOMNESS
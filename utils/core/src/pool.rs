@@ -0,0 +1,199 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::vec::Vec;
+#[cfg(not(feature = "std"))]
+use core::marker::PhantomData;
+use core::mem::{self, ManuallyDrop};
+#[cfg(feature = "std")]
+use std::sync::{Arc, Mutex};
+
+use super::uninit_vector;
+
+// BUFFER POOL
+// ================================================================================================
+
+/// A pool of memory buffers which can be reused across multiple computations.
+///
+/// The pool keeps released buffers in a list of allocations for elements of type `T`. A buffer
+/// can be taken from the pool as a vector of any type `U` which is composed of elements of type
+/// `T` (e.g., `T` itself, arrays of `T`, or elements of an extension field whose base field is
+/// `T`); a buffer is reused only if its capacity is sufficient and can be expressed exactly in
+/// elements of type `U`. When no such buffer is available, a new buffer is allocated.
+///
+/// This is useful for long-running processes which repeatedly perform the same memory-intensive
+/// computations (e.g., a proving service generating proofs for the same computation): once the
+/// pool has been filled during the first computation, subsequent computations do not need to
+/// allocate large buffers anew.
+///
+/// Cloning a pool returns a handle to the same underlying set of buffers. Buffers are kept in the
+/// pool until they are taken out or until the pool is cleared via [BufferPool::clear()].
+///
+/// Buffers are retained only when the crate is compiled with the `std` feature; otherwise,
+/// buffers returned to the pool are released immediately, and new buffers are always allocated.
+#[derive(Debug)]
+pub struct BufferPool<T> {
+    #[cfg(feature = "std")]
+    buffers: Arc<Mutex<Vec<Vec<T>>>>,
+    #[cfg(not(feature = "std"))]
+    _element: PhantomData<T>,
+}
+
+impl<T> BufferPool<T> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new empty buffer pool.
+    pub fn new() -> Self {
+        Self {
+            #[cfg(feature = "std")]
+            buffers: Arc::new(Mutex::new(Vec::new())),
+            #[cfg(not(feature = "std"))]
+            _element: PhantomData,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of buffers currently held in this pool.
+    pub fn num_buffers(&self) -> usize {
+        self.lock_buffers().len()
+    }
+
+    /// Returns the total size (in bytes) of all buffers currently held in this pool.
+    pub fn size(&self) -> usize {
+        self.lock_buffers()
+            .iter()
+            .map(|buffer| buffer.capacity() * mem::size_of::<T>())
+            .sum()
+    }
+
+    // BUFFER MANAGEMENT
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a vector of the specified length, reusing the smallest suitable buffer from this
+    /// pool if one is available, and allocating a new buffer otherwise.
+    ///
+    /// # Safety
+    /// The contents of the returned vector are not initialized (or contain values left over from
+    /// prior uses of the buffer). Thus, `U` must be composed of elements of type `T` such that
+    /// any bit pattern is valid for `U`, and all values of the vector must be initialized before
+    /// they are used.
+    ///
+    /// # Panics
+    /// Panics if the size of `U` is not a multiple of the size of `T`, or if alignments of `U` and
+    /// `T` are not the same.
+    pub unsafe fn take<U: Copy>(&self, length: usize) -> Vec<U> {
+        let ratio = element_ratio::<T, U>();
+
+        // find the smallest buffer with sufficient capacity which can be split into elements of
+        // type U exactly
+        let buffer = {
+            let mut buffers = self.lock_buffers();
+            buffers
+                .iter()
+                .enumerate()
+                .filter(|(_, buffer)| {
+                    buffer.capacity() % ratio == 0 && buffer.capacity() / ratio >= length
+                })
+                .min_by_key(|(_, buffer)| buffer.capacity())
+                .map(|(idx, _)| idx)
+                .map(|idx| buffers.swap_remove(idx))
+        };
+
+        match buffer {
+            Some(buffer) => {
+                let buffer = ManuallyDrop::new(buffer);
+                let capacity = buffer.capacity() / ratio;
+                Vec::from_raw_parts(buffer.as_ptr() as *mut U, length, capacity)
+            },
+            None => uninit_vector(length),
+        }
+    }
+
+    /// Returns the memory allocated for the provided vector to this pool; the contents of the
+    /// vector are discarded.
+    ///
+    /// Vectors without allocated memory are ignored.
+    ///
+    /// # Panics
+    /// Panics if the size of `U` is not a multiple of the size of `T`, or if alignments of `U` and
+    /// `T` are not the same.
+    pub fn put<U: Copy>(&self, buffer: Vec<U>) {
+        let ratio = element_ratio::<T, U>();
+        if buffer.capacity() == 0 {
+            return;
+        }
+
+        // the buffer is kept with zero length, and thus, none of its contents are ever read as
+        // elements of type T
+        let buffer = ManuallyDrop::new(buffer);
+        let capacity = buffer.capacity() * ratio;
+        let buffer = unsafe { Vec::from_raw_parts(buffer.as_ptr() as *mut T, 0, capacity) };
+        self.lock_buffers().push(buffer);
+    }
+
+    /// Removes all buffers from this pool and releases the memory allocated for them.
+    pub fn clear(&self) {
+        self.lock_buffers().clear();
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a guard over the list of buffers held in this pool.
+    #[cfg(feature = "std")]
+    fn lock_buffers(&self) -> std::sync::MutexGuard<'_, Vec<Vec<T>>> {
+        self.buffers.lock().expect("buffer pool lock is poisoned")
+    }
+
+    /// Returns an empty list of buffers, as buffers are not retained without the `std` feature.
+    #[cfg(not(feature = "std"))]
+    fn lock_buffers(&self) -> Vec<Vec<T>> {
+        Vec::new()
+    }
+}
+
+impl<T> Clone for BufferPool<T> {
+    fn clone(&self) -> Self {
+        Self {
+            #[cfg(feature = "std")]
+            buffers: self.buffers.clone(),
+            #[cfg(not(feature = "std"))]
+            _element: PhantomData,
+        }
+    }
+}
+
+impl<T> Default for BufferPool<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the number of elements of type `T` which form a single element of type `U`.
+///
+/// # Panics
+/// Panics if either of the types is zero-sized, if the size of `U` is not a multiple of the size of
+/// `T`, or if alignments of `U` and `T` are not the same.
+fn element_ratio<T, U>() -> usize {
+    assert_eq!(
+        mem::align_of::<U>(),
+        mem::align_of::<T>(),
+        "alignment of buffer elements must be the same as alignment of pool elements"
+    );
+    assert!(mem::size_of::<T>() > 0, "pool elements cannot be zero-sized");
+    assert!(mem::size_of::<U>() > 0, "buffer elements cannot be zero-sized");
+    assert_eq!(
+        mem::size_of::<U>() % mem::size_of::<T>(),
+        0,
+        "size of buffer elements must be a multiple of the size of pool elements"
+    );
+    mem::size_of::<U>() / mem::size_of::<T>()
+}
@@ -13,6 +13,13 @@ use std::io::BufRead;
 
 use super::{Deserializable, DeserializationError};
 
+// CONSTANTS
+// ================================================================================================
+
+/// Maximum number of bytes allocated upfront when reading a sequence of values whose length is
+/// specified in the source.
+const MAX_PREALLOCATED_BYTES: usize = 1 << 20;
+
 // BYTE READER TRAIT
 // ================================================================================================
 
@@ -191,7 +198,11 @@ pub trait ByteReader {
         Self: Sized,
         D: Deserializable,
     {
-        let mut result = Vec::with_capacity(num_elements);
+        // the number of elements is usually read from the source itself, and thus, cannot be
+        // trusted; limit the amount of memory allocated upfront so that malformed inputs result
+        // in a deserialization error rather than in an allocation failure
+        let max_preallocated = MAX_PREALLOCATED_BYTES / core::mem::size_of::<D>().max(1);
+        let mut result = Vec::with_capacity(num_elements.min(max_preallocated));
         for _ in 0..num_elements {
             let element = D::read_from(self)?;
             result.push(element)
@@ -11,7 +11,7 @@ use alloc::{
 
 use proptest::prelude::{any, proptest};
 
-use super::{ByteReader, ByteWriter, Serializable, SliceReader};
+use super::{BufferPool, ByteReader, ByteWriter, Deserializable, Serializable, SliceReader};
 
 // SLICE READER TESTS
 // ================================================================================================
@@ -73,6 +73,17 @@ fn read_u8_vec() {
     assert!(a.read_vec(2).is_err());
 }
 
+#[test]
+fn read_many_invalid_length() {
+    // a vector length which exceeds the available data results in an error rather than in an
+    // attempt to allocate memory for all elements
+    let mut source = Vec::new();
+    source.write_usize(usize::MAX >> 8);
+    source.write_u64(1);
+    let mut a = SliceReader::new(&source);
+    assert!(Vec::<u64>::read_from(&mut a).is_err());
+}
+
 // SERIALIZATION TESTS
 // ================================================================================================
 
@@ -238,6 +249,54 @@ fn size_hint_btree() {
     size_hint_matches_serialized_len(set);
 }
 
+// BUFFER POOL
+// ================================================================================================
+
+#[test]
+fn buffer_pool_reuse() {
+    let pool = BufferPool::<u64>::new();
+
+    // an empty pool allocates a new buffer; buffers are reused once they are returned
+    let buffer: Vec<u64> = unsafe { pool.take(16) };
+    assert_eq!(16, buffer.len());
+    let ptr = buffer.as_ptr();
+    pool.put(buffer);
+    assert_eq!(1, pool.num_buffers());
+    assert_eq!(16 * 8, pool.size());
+
+    // a buffer can be taken as a vector of elements composed of pool elements
+    let buffer: Vec<[u64; 2]> = unsafe { pool.take(8) };
+    assert_eq!(8, buffer.len());
+    assert_eq!(ptr as *const [u64; 2], buffer.as_ptr());
+    assert_eq!(0, pool.num_buffers());
+    pool.put(buffer);
+
+    // a buffer which is too small, or which cannot be split into elements exactly, is not reused
+    let buffer: Vec<u64> = unsafe { pool.take(32) };
+    assert_ne!(ptr, buffer.as_ptr());
+    let buffer: Vec<[u64; 3]> = unsafe { pool.take(4) };
+    assert_eq!(1, pool.num_buffers());
+    drop(buffer);
+
+    // clones share the same set of buffers
+    pool.clone().clear();
+    assert_eq!(0, pool.num_buffers());
+}
+
+#[test]
+fn buffer_pool_smallest_buffer() {
+    let pool = BufferPool::<u32>::new();
+    pool.put(Vec::<u32>::with_capacity(64));
+    pool.put(Vec::<u32>::with_capacity(16));
+    pool.put(Vec::<u32>::with_capacity(32));
+
+    let buffer: Vec<u32> = unsafe { pool.take(10) };
+    assert_eq!(16, buffer.capacity());
+    let buffer: Vec<u32> = unsafe { pool.take(20) };
+    assert_eq!(32, buffer.capacity());
+    assert_eq!(1, pool.num_buffers());
+}
+
 // UTILS - RANDOMIZED - UINT SERIALIZATION AND DESERIALIZATION
 // ================================================================================================
 proptest! {
@@ -7,11 +7,13 @@ use alloc::{string::ToString, vec::Vec};
 use core::marker::PhantomData;
 
 use air::{
-    proof::{Proof, Queries, Table, TraceOodFrame},
+    proof::{Proof, Queries, QueryOpening, QueryOpeningError, Table, TraceOodFrame},
     Air,
 };
 use crypto::{ElementHasher, VectorCommitment};
-use fri::VerifierChannel as FriVerifierChannel;
+use fri::{
+    FriLayerReader, VerifierChannel as FriVerifierChannel, VerifierError as FriVerifierError,
+};
 use math::{FieldElement, StarkField};
 
 use crate::VerifierError;
@@ -24,26 +26,41 @@ use crate::VerifierError;
 /// A channel is instantiated for a specific proof, which is parsed into structs over the
 /// appropriate field (specified by type parameter `E`). This also validates that the proof is
 /// well-formed in the context of the computation for the specified [Air].
+///
+/// To bound the amount of memory needed for verification, trace and constraint queries as well
+/// as FRI layers are parsed only when they are read from the channel. Since the verifier consumes
+/// each piece of data before reading the next one, at most one set of parsed openings needs to be
+/// held in memory at any given time.
+///
+/// Trace and constraint commitments are verified using hash function `H` and vector commitment
+/// scheme `V`, while FRI layer commitments are verified using hash function `FH` and vector
+/// commitment scheme `FV`. Trace and constraint queries are checked against their commitments
+/// using query opening scheme `O`.
 pub struct VerifierChannel<
     E: FieldElement,
     H: ElementHasher<BaseField = E::BaseField>,
     V: VectorCommitment<H>,
+    FH: ElementHasher<BaseField = E::BaseField>,
+    FV: VectorCommitment<FH>,
+    O: QueryOpening<H, V>,
 > {
     // trace queries
     trace_commitments: Vec<H::Digest>,
-    trace_queries: Option<TraceQueries<E, H, V>>,
+    trace_queries: Option<Vec<Queries>>,
     // constraint queries
     constraint_commitment: H::Digest,
-    constraint_queries: Option<ConstraintQueries<E, H, V>>,
+    constraint_queries: Option<Queries>,
+    // shape of the queried data
+    query_shape: QueryShape,
     // partition sizes for the rows of main, auxiliary and constraint traces rows
     partition_size_main: usize,
     partition_size_aux: usize,
     partition_size_constraint: usize,
     // FRI proof
-    fri_commitments: Option<Vec<H::Digest>>,
-    fri_layer_proofs: Vec<V::MultiProof>,
-    fri_layer_queries: Vec<Vec<E>>,
+    fri_commitments: Option<Vec<FH::Digest>>,
+    fri_layers: FriLayerReader<E, FH, FV>,
     fri_remainder: Option<Vec<E>>,
+    fri_deep_evaluations: Option<Vec<E>>,
     fri_num_partitions: usize,
     // out-of-domain frame
     ood_trace_frame: Option<TraceOodFrame<E>>,
@@ -51,13 +68,18 @@ pub struct VerifierChannel<
     // query proof-of-work
     pow_nonce: u64,
     gkr_proof: Option<Vec<u8>>,
+    _vector_commitment: PhantomData<V>,
+    _query_opening: PhantomData<O>,
 }
 
-impl<E, H, V> VerifierChannel<E, H, V>
+impl<E, H, V, FH, FV, O> VerifierChannel<E, H, V, FH, FV, O>
 where
     E: FieldElement,
     H: ElementHasher<BaseField = E::BaseField>,
     V: VectorCommitment<H>,
+    FH: ElementHasher<BaseField = E::BaseField>,
+    FV: VectorCommitment<FH>,
+    O: QueryOpening<H, V>,
 {
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
@@ -68,7 +90,7 @@ where
     ) -> Result<Self, VerifierError> {
         let Proof {
             context,
-            num_unique_queries,
+            num_unique_queries: _,
             commitments,
             trace_queries,
             constraint_queries,
@@ -93,30 +115,46 @@ where
 
         // --- parse commitments ------------------------------------------------------------------
         let (trace_commitments, constraint_commitment, fri_commitments) = commitments
-            .parse::<H>(num_trace_segments, fri_options.num_fri_layers(lde_domain_size))
+            .parse_with_fri_hasher::<H, FH>(
+                num_trace_segments,
+                fri_options.num_fri_layers(lde_domain_size),
+            )
             .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
 
-        // --- parse trace and constraint queries -------------------------------------------------
-        let trace_queries =
-            TraceQueries::<E, H, V>::new(trace_queries, air, num_unique_queries as usize)?;
-        let constraint_queries = ConstraintQueries::<E, H, V>::new(
-            constraint_queries,
-            air,
-            num_unique_queries as usize,
-        )?;
+        // --- record the shape of trace and constraint queries -----------------------------------
+        // queries are parsed only when they are read from the channel
+        assert_eq!(
+            trace_queries.len(),
+            num_trace_segments,
+            "expected {} trace segment queries, but received {}",
+            num_trace_segments,
+            trace_queries.len()
+        );
+        let query_shape = QueryShape {
+            lde_domain_size,
+            main_trace_width,
+            aux_trace_width,
+            constraint_frame_width,
+        };
 
         // --- parse FRI proofs -------------------------------------------------------------------
         let fri_num_partitions = fri_proof.num_partitions();
         let fri_remainder = fri_proof
             .parse_remainder()
             .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
-        let (fri_layer_queries, fri_layer_proofs) = fri_proof
-            .parse_layers::<E, H, V>(lde_domain_size, fri_options.folding_factor())
+        let fri_deep_evaluations = fri_proof
+            .parse_deep_evaluations()
             .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
+        let fri_layers = fri_proof.into_layer_reader(lde_domain_size, &fri_options);
 
         // --- parse out-of-domain evaluation frame -----------------------------------------------
         let (ood_trace_frame, ood_constraint_evaluations) = ood_frame
-            .parse(main_trace_width, aux_trace_width, constraint_frame_width)
+            .parse(
+                main_trace_width,
+                aux_trace_width,
+                air.context().frame_offsets(),
+                constraint_frame_width,
+            )
             .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
 
         // --- compute the partition size for each trace ------------------------------------------
@@ -134,15 +172,16 @@ where
             // constraint queries
             constraint_commitment,
             constraint_queries: Some(constraint_queries),
+            query_shape,
             // num partitions used in commitment
             partition_size_main,
             partition_size_aux,
             partition_size_constraint,
             // FRI proof
             fri_commitments: Some(fri_commitments),
-            fri_layer_proofs,
-            fri_layer_queries,
+            fri_layers,
             fri_remainder: Some(fri_remainder),
+            fri_deep_evaluations: Some(fri_deep_evaluations),
             fri_num_partitions,
             // out-of-domain evaluation
             ood_trace_frame: Some(ood_trace_frame),
@@ -150,6 +189,8 @@ where
             // query seed
             pow_nonce,
             gkr_proof,
+            _vector_commitment: PhantomData,
+            _query_opening: PhantomData,
         })
     }
 
@@ -197,6 +238,8 @@ where
     /// Returns trace states at the specified positions of the LDE domain. This also checks if
     /// the trace states are valid against the trace commitment sent by the prover.
     ///
+    /// Trace queries are parsed from the proof when this method is called.
+    ///
     /// For computations requiring multiple trace segments, trace states for auxiliary segments
     /// are also included as the second value of the returned tuple (trace states for all auxiliary
     /// segments are merged into a single table). Otherwise, the second value is None.
@@ -205,232 +248,133 @@ where
         &mut self,
         positions: &[usize],
     ) -> Result<(Table<E::BaseField>, Option<Table<E>>), VerifierError> {
-        let queries = self.trace_queries.take().expect("already read");
+        let mut queries = self.trace_queries.take().expect("already read");
+        let shape = &self.query_shape;
 
         // make sure the states included in the proof correspond to the trace commitment
-        let items: Vec<H::Digest> = queries
-            .main_states
-            .rows()
-            .map(|row| hash_row::<H, E::BaseField>(row, self.partition_size_main))
-            .collect();
-
-        <V as VectorCommitment<H>>::verify_many(
+        let partition_size = self.partition_size_main;
+        let main_states = O::verify(
             self.trace_commitments[0],
             positions,
-            &items,
-            &queries.query_proofs[0],
+            shape.lde_domain_size,
+            shape.main_trace_width,
+            queries.remove(0),
+            |row: &[E::BaseField]| hash_row::<H, _>(row, partition_size),
         )
-        .map_err(|_| VerifierError::TraceQueryDoesNotMatchCommitment)?;
-
-        if let Some(ref aux_states) = queries.aux_states {
-            let items: Vec<H::Digest> = aux_states
-                .rows()
-                .map(|row| hash_row::<H, E>(row, self.partition_size_aux))
-                .collect();
+        .map_err(|err| {
+            map_opening_error(
+                err,
+                "main trace segment",
+                VerifierError::TraceQueryDoesNotMatchCommitment,
+            )
+        })?;
 
-            <V as VectorCommitment<H>>::verify_many(
+        let aux_states = if shape.aux_trace_width > 0 {
+            let partition_size = self.partition_size_aux;
+            let aux_states = O::verify(
                 self.trace_commitments[1],
                 positions,
-                &items,
-                &queries.query_proofs[1],
+                shape.lde_domain_size,
+                shape.aux_trace_width,
+                queries.remove(0),
+                |row: &[E]| hash_row::<H, _>(row, partition_size),
             )
-            .map_err(|_| VerifierError::TraceQueryDoesNotMatchCommitment)?;
-        }
+            .map_err(|err| {
+                map_opening_error(
+                    err,
+                    "auxiliary trace segment",
+                    VerifierError::TraceQueryDoesNotMatchCommitment,
+                )
+            })?;
+
+            Some(aux_states)
+        } else {
+            None
+        };
 
-        Ok((queries.main_states, queries.aux_states))
+        Ok((main_states, aux_states))
     }
 
     /// Returns constraint evaluations at the specified positions of the LDE domain. This also
     /// checks if the constraint evaluations are valid against the constraint commitment sent by
     /// the prover.
+    ///
+    /// Constraint queries are parsed from the proof when this method is called.
     pub fn read_constraint_evaluations(
         &mut self,
         positions: &[usize],
     ) -> Result<Table<E>, VerifierError> {
-        let queries = self.constraint_queries.take().expect("already read");
-
-        let items: Vec<H::Digest> = queries
-            .evaluations
-            .rows()
-            .map(|row| hash_row::<H, E>(row, self.partition_size_constraint))
-            .collect();
-
-        <V as VectorCommitment<H>>::verify_many(
+        let partition_size = self.partition_size_constraint;
+        O::verify(
             self.constraint_commitment,
             positions,
-            &items,
-            &queries.query_proofs,
+            self.query_shape.lde_domain_size,
+            self.query_shape.constraint_frame_width,
+            self.constraint_queries.take().expect("already read"),
+            |row: &[E]| hash_row::<H, _>(row, partition_size),
         )
-        .map_err(|_| VerifierError::ConstraintQueryDoesNotMatchCommitment)?;
-
-        Ok(queries.evaluations)
+        .map_err(|err| {
+            map_opening_error(
+                err,
+                "constraint evaluation",
+                VerifierError::ConstraintQueryDoesNotMatchCommitment,
+            )
+        })
     }
 }
 
 // FRI VERIFIER CHANNEL IMPLEMENTATION
 // ================================================================================================
 
-impl<E, H, V> FriVerifierChannel<E> for VerifierChannel<E, H, V>
+impl<E, H, V, FH, FV, O> FriVerifierChannel<E> for VerifierChannel<E, H, V, FH, FV, O>
 where
     E: FieldElement,
     H: ElementHasher<BaseField = E::BaseField>,
     V: VectorCommitment<H>,
+    FH: ElementHasher<BaseField = E::BaseField>,
+    FV: VectorCommitment<FH>,
+    O: QueryOpening<H, V>,
 {
-    type Hasher = H;
-    type VectorCommitment = V;
+    type Hasher = FH;
+    type VectorCommitment = FV;
 
     fn read_fri_num_partitions(&self) -> usize {
         self.fri_num_partitions
     }
 
-    fn read_fri_layer_commitments(&mut self) -> Vec<H::Digest> {
+    fn read_fri_layer_commitments(&mut self) -> Vec<FH::Digest> {
         self.fri_commitments.take().expect("already read")
     }
 
-    fn take_next_fri_layer_proof(&mut self) -> V::MultiProof {
-        self.fri_layer_proofs.remove(0)
-    }
-
-    fn take_next_fri_layer_queries(&mut self) -> Vec<E> {
-        self.fri_layer_queries.remove(0)
+    fn take_next_fri_layer(&mut self) -> Result<(Vec<E>, FV::MultiProof), FriVerifierError> {
+        self.fri_layers
+            .next()
+            .ok_or_else(|| {
+                FriVerifierError::ProofDeserializationError(
+                    "no more FRI layers to read".to_string(),
+                )
+            })?
+            .map_err(|err| FriVerifierError::ProofDeserializationError(err.to_string()))
     }
 
     fn take_fri_remainder(&mut self) -> Vec<E> {
         self.fri_remainder.take().expect("already read")
     }
-}
-
-// TRACE QUERIES
-// ================================================================================================
 
-/// Container of trace query data, including:
-/// * Queried states for all trace segments.
-/// * Batch opening proof for all queries.
-///
-/// Trace states for all auxiliary segments are stored in a single table.
-struct TraceQueries<
-    E: FieldElement,
-    H: ElementHasher<BaseField = E::BaseField>,
-    V: VectorCommitment<H>,
-> {
-    query_proofs: Vec<V::MultiProof>,
-    main_states: Table<E::BaseField>,
-    aux_states: Option<Table<E>>,
-    _h: PhantomData<H>,
-}
-
-impl<E, H, V> TraceQueries<E, H, V>
-where
-    E: FieldElement,
-    H: ElementHasher<BaseField = E::BaseField>,
-    V: VectorCommitment<H>,
-{
-    /// Parses the provided trace queries into trace states in the specified field and
-    /// corresponding batch opening proof.
-    pub fn new<A: Air<BaseField = E::BaseField>>(
-        mut queries: Vec<Queries>,
-        air: &A,
-        num_queries: usize,
-    ) -> Result<Self, VerifierError> {
-        assert_eq!(
-            queries.len(),
-            air.trace_info().num_segments(),
-            "expected {} trace segment queries, but received {}",
-            air.trace_info().num_segments(),
-            queries.len()
-        );
-
-        // parse main trace segment queries
-        let main_segment_width = air.trace_info().main_trace_width();
-        let main_segment_queries = queries.remove(0);
-        let (main_segment_query_proofs, main_segment_states) = main_segment_queries
-            .parse::<E::BaseField, H, V>(air.lde_domain_size(), num_queries, main_segment_width)
-            .map_err(|err| {
-                VerifierError::ProofDeserializationError(format!(
-                    "main trace segment query deserialization failed: {err}"
-                ))
-            })?;
-
-        // all query proofs will be aggregated into a single vector
-        let mut query_proofs = vec![main_segment_query_proofs];
-
-        // parse auxiliary trace segment queries (if any), and merge resulting tables into a
-        // single table
-        let aux_trace_states = if air.trace_info().is_multi_segment() {
-            let mut aux_trace_states = Vec::new();
-            let segment_queries = queries.remove(0);
-            let segment_width = air.trace_info().get_aux_segment_width();
-            let (segment_query_proof, segment_trace_states) = segment_queries
-                .parse::<E, H, V>(air.lde_domain_size(), num_queries, segment_width)
-                .map_err(|err| {
-                    VerifierError::ProofDeserializationError(format!(
-                        "auxiliary trace segment query deserialization failed: {err}"
-                    ))
-                })?;
-
-            query_proofs.push(segment_query_proof);
-            aux_trace_states.push(segment_trace_states);
-
-            // merge tables for each auxiliary segment into a single table
-            Some(Table::merge(aux_trace_states))
-        } else {
-            None
-        };
-
-        Ok(Self {
-            query_proofs,
-            main_states: main_segment_states,
-            aux_states: aux_trace_states,
-            _h: PhantomData,
-        })
+    fn take_fri_deep_evaluations(&mut self) -> Vec<E> {
+        self.fri_deep_evaluations.take().expect("already read")
     }
 }
 
-// CONSTRAINT QUERIES
+// QUERY SHAPE
 // ================================================================================================
 
-/// Container of constraint evaluation query data, including:
-/// * Queried constraint evaluation values.
-/// * Batch opening proof for all queries.
-struct ConstraintQueries<
-    E: FieldElement,
-    H: ElementHasher<BaseField = E::BaseField>,
-    V: VectorCommitment<H>,
-> {
-    query_proofs: V::MultiProof,
-    evaluations: Table<E>,
-    _h: PhantomData<H>,
-}
-
-impl<E, H, V> ConstraintQueries<E, H, V>
-where
-    E: FieldElement,
-    H: ElementHasher<BaseField = E::BaseField>,
-    V: VectorCommitment<H>,
-{
-    /// Parses the provided constraint queries into evaluations in the specified field and
-    /// corresponding batch opening proof.
-    pub fn new<A: Air<BaseField = E::BaseField>>(
-        queries: Queries,
-        air: &A,
-        num_queries: usize,
-    ) -> Result<Self, VerifierError> {
-        let constraint_frame_width = air.context().num_constraint_composition_columns();
-
-        let (query_proofs, evaluations) = queries
-            .parse::<E, H, V>(air.lde_domain_size(), num_queries, constraint_frame_width)
-            .map_err(|err| {
-                VerifierError::ProofDeserializationError(format!(
-                    "constraint evaluation query deserialization failed: {err}"
-                ))
-            })?;
-
-        Ok(Self {
-            query_proofs,
-            evaluations,
-            _h: PhantomData,
-        })
-    }
+/// Dimensions needed to parse trace and constraint queries read from the channel.
+struct QueryShape {
+    lde_domain_size: usize,
+    main_trace_width: usize,
+    aux_trace_width: usize,
+    constraint_frame_width: usize,
 }
 
 // HELPER
@@ -455,3 +399,18 @@ where
         H::merge_many(&buffer)
     }
 }
+
+/// Maps an error returned by a query opening scheme for queries from the specified `source` into
+/// a verifier error; `mismatch` is returned when the queries do not match their commitment.
+fn map_opening_error(
+    err: QueryOpeningError,
+    source: &str,
+    mismatch: VerifierError,
+) -> VerifierError {
+    match err {
+        QueryOpeningError::DeserializationError(err) => VerifierError::ProofDeserializationError(
+            format!("{source} query deserialization failed: {err}"),
+        ),
+        QueryOpeningError::CommitmentMismatch => mismatch,
+    }
+}
@@ -16,7 +16,7 @@ use math::{batch_inversion, polynom, FieldElement};
 pub struct DeepComposer<E: FieldElement> {
     cc: DeepCompositionCoefficients<E>,
     x_coordinates: Vec<E>,
-    z: [E; 2],
+    z: Vec<E>,
     g_trace: E::BaseField,
     lagrange_kernel_column_idx: Option<usize>,
 }
@@ -38,10 +38,18 @@ impl<E: FieldElement> DeepComposer<E> {
             .collect();
         let g_trace = air.trace_domain_generator();
 
+        // out-of-domain points z * g^d for each evaluation frame row offset d
+        let z = air
+            .context()
+            .frame_offsets()
+            .iter()
+            .map(|&offset| z.mul_base(g_trace.exp((offset as u64).into())))
+            .collect();
+
         DeepComposer {
             cc,
             x_coordinates,
-            z: [z, z * E::from(g_trace)],
+            z,
             g_trace,
             lagrange_kernel_column_idx: air.context().lagrange_kernel_aux_column_idx(),
         }
@@ -53,7 +61,9 @@ impl<E: FieldElement> DeepComposer<E> {
     /// - Assume each column value is an evaluation of a trace polynomial T_i(x).
     /// - For each T_i(x) compute T'_i(x) = (T_i(x) - T_i(z)) / (x - z) and
     ///   T''_i = (T_i(x) - T_i(z * g)) / (x - z * g), where z is the out-of-domain point and
-    ///   g is the the LDE domain generator.
+    ///   g is the the LDE domain generator. When evaluation frames contain rows at other
+    ///   offsets, similar values are computed for all points z * g^d, where d is a frame row
+    ///   offset.
     /// - Then, combine all T'_i(x) and T''_i(x) values together by computing
     ///   T(x) = sum((T'_i(x) + T''_i(x)) * cc_i) for all i, where cc_i is the coefficient for
     ///   for the random linear combination drawn from the public coin.
@@ -67,8 +77,8 @@ impl<E: FieldElement> DeepComposer<E> {
     ///    ${(a, T_l(a)): a \in S}$.
     /// 4. $Z_S(X)$ is the polynomial of minimal degree vanishing over the set $S$.
     ///
-    /// Note that values of T_i(z) and T_i(z * g) are received from the prover and passed into
-    /// this function via the `ood_main_frame` and `ood_aux_frame` parameters.
+    /// Note that values of T_i(z * g^d) for all frame row offsets d are received from the prover
+    /// and passed into this function via the `ood_main_frame` and `ood_aux_frame` parameters.
     ///
     /// If a Lagrange kernel is present, the evaluations of $T_l$ over the set $S$ are received
     /// from the prover and passed separately via `ood_lagrange_kernel_frame`.
@@ -80,7 +90,9 @@ impl<E: FieldElement> DeepComposer<E> {
         ood_aux_frame: Option<EvaluationFrame<E>>,
         ood_lagrange_kernel_frame: Option<&LagrangeKernelEvaluationFrame<E>>,
     ) -> Vec<E> {
-        let ood_main_trace_states = [ood_main_frame.current(), ood_main_frame.next()];
+        let num_points = self.z.len();
+        let mut t_nums = vec![E::ZERO; num_points];
+        let mut t_dens = vec![E::ZERO; num_points];
 
         // compose columns of of the main trace segment; we do this separately for numerators of
         // each query; we also track common denominator for each query separately; this way we can
@@ -91,34 +103,32 @@ impl<E: FieldElement> DeepComposer<E> {
 
         for ((_, row), &x) in (0..n).zip(queried_main_trace_states.rows()).zip(&self.x_coordinates)
         {
-            let mut t1_num = E::ZERO;
-            let mut t2_num = E::ZERO;
-
-            for (i, &value) in row.iter().enumerate() {
-                let value = E::from(value);
-                // compute the numerator of T'_i(x) as (T_i(x) - T_i(z)), multiply it by a
-                // composition coefficient, and add the result to the numerator aggregator
-                t1_num += (value - ood_main_trace_states[0][i]) * self.cc.trace[i];
+            t_nums.fill(E::ZERO);
+            for (j, t_num) in t_nums.iter_mut().enumerate() {
+                let ood_trace_states = ood_main_frame.row(j);
+                for (i, &value) in row.iter().enumerate() {
+                    // compute the numerator of T'_i(x) as (T_i(x) - T_i(z * g^j)), multiply it
+                    // by a composition coefficient, and add the result to the numerator
+                    // aggregator
+                    *t_num += (E::from(value) - ood_trace_states[i]) * self.cc.trace[i];
+                }
+            }
 
-                // compute the numerator of T''_i(x) as (T_i(x) - T_i(z * g)), multiply it by a
-                // composition coefficient, and add the result to the numerator aggregator
-                t2_num += (value - ood_main_trace_states[1][i]) * self.cc.trace[i];
+            // compute the common denominator as (x - z) * (x - z * g) * ... and add the
+            // numerators together; we can do this because later on we'll use the common
+            // denominator.
+            for (t_den, &z) in t_dens.iter_mut().zip(&self.z) {
+                *t_den = x - z;
             }
-            // compute the common denominator as (x - z) * (x - z * g)
-            let t1_den = x - self.z[0];
-            let t2_den = x - self.z[1];
-            result_den.push(t1_den * t2_den);
-
-            // add the numerators of T'_i(x) and T''_i(x) together; we can do this because later on
-            // we'll use the common denominator computed above.
-            result_num.push(t1_num * t2_den + t2_num * t1_den);
+            let (num, den) = combine_fractions(&t_nums, &t_dens);
+            result_num.push(num);
+            result_den.push(den);
         }
 
         // if the trace has auxiliary segments, compose columns from these segments as well; we
         // also do this separately for numerators and denominators.
         if let Some(queried_aux_trace_states) = queried_aux_trace_states {
             let ood_aux_frame = ood_aux_frame.expect("missing auxiliary OOD frame");
-            let ood_aux_trace_states = [ood_aux_frame.current(), ood_aux_frame.next()];
 
             // we define this offset here because composition of the main trace columns has
             // consumed some number of composition coefficients already.
@@ -126,36 +136,37 @@ impl<E: FieldElement> DeepComposer<E> {
 
             // we treat the Lagrange column separately if present
             let lagrange_ker_col_idx =
-                self.lagrange_kernel_column_idx.unwrap_or(ood_aux_trace_states[0].len());
+                self.lagrange_kernel_column_idx.unwrap_or(ood_aux_frame.current().len());
 
-            for ((j, row), &x) in
+            for ((q, row), &x) in
                 (0..n).zip(queried_aux_trace_states.rows()).zip(&self.x_coordinates)
             {
-                let mut t1_num = E::ZERO;
-                let mut t2_num = E::ZERO;
-
                 let row = &row[..lagrange_ker_col_idx];
-                for (i, &value) in row.iter().enumerate() {
-                    // compute the numerator of T'_i(x) as (T_i(x) - T_i(z)), multiply it by a
-                    // composition coefficient, and add the result to the numerator aggregator
-                    t1_num += (value - ood_aux_trace_states[0][i]) * self.cc.trace[cc_offset + i];
-
-                    // compute the numerator of T''_i(x) as (T_i(x) - T_i(z * g)), multiply it by a
-                    // composition coefficient, and add the result to the numerator aggregator
-                    t2_num += (value - ood_aux_trace_states[1][i]) * self.cc.trace[cc_offset + i];
+                t_nums.fill(E::ZERO);
+                for (j, t_num) in t_nums.iter_mut().enumerate() {
+                    let ood_trace_states = ood_aux_frame.row(j);
+                    for (i, &value) in row.iter().enumerate() {
+                        // compute the numerator of T'_i(x) as (T_i(x) - T_i(z * g^j)), multiply
+                        // it by a composition coefficient, and add the result to the numerator
+                        // aggregator
+                        *t_num += (value - ood_trace_states[i]) * self.cc.trace[cc_offset + i];
+                    }
                 }
 
-                // compute the common denominators (x - z) and (x - z * g), and use the to aggregate
-                // numerators into the common numerator computed for the main trace of this query
-                let t1_den = x - self.z[0];
-                let t2_den = x - self.z[1];
-                result_num[j] += t1_num * t2_den + t2_num * t1_den;
+                // compute the common denominators (x - z), (x - z * g) etc., and use them to
+                // aggregate numerators into the common numerator computed for the main trace of
+                // this query
+                for (t_den, &z) in t_dens.iter_mut().zip(&self.z) {
+                    *t_den = x - z;
+                }
+                result_num[q] += combine_fractions(&t_nums, &t_dens).0;
             }
 
             // if a Lagrange kernel trace polynomial is present, we include its associated term
             // separately. Note that, for performance reasons, we divide by Z_{S^{'}} instead of
             // Z_S, where  $S^{'} := {z.g^2, ..., z.g^{2^{log_2(\nu) - 1}}}$. This is done as
-            // the final linear combination is divided by `(x - z) . (x - z.g)`.
+            // the final linear combination is divided by `(x - z) . (x - z.g)`; this is sound
+            // because Lagrange kernel columns are supported only for two-row evaluation frames.
             if let Some(ood_lagrange_kernel_frame) = ood_lagrange_kernel_frame {
                 let mut result_lag_num = Vec::<E>::with_capacity(n);
                 let mut result_lag_den = Vec::<E>::with_capacity(n);
@@ -255,3 +266,20 @@ impl<E: FieldElement> DeepComposer<E> {
         result
     }
 }
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Adds together fractions num_j / den_j and returns the numerator and the denominator of the
+/// result; the denominator is computed as the product of all den_j.
+fn combine_fractions<E: FieldElement>(nums: &[E], dens: &[E]) -> (E, E) {
+    debug_assert_eq!(nums.len(), dens.len());
+    let mut num = E::ZERO;
+    let mut den = E::ONE;
+    for (&n, &d) in nums.iter().zip(dens) {
+        // (num / den) + (n / d) = (num * d + n * den) / (den * d)
+        num = num * d + n * den;
+        den *= d;
+    }
+    (num, den)
+}
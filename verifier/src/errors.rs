@@ -23,6 +23,17 @@ pub enum VerifierError {
     UnsupportedFieldExtension(usize),
     /// This error occurs when a verifier cannot deserialize the specified proof.
     ProofDeserializationError(String),
+    /// This error occurs when public inputs cannot be deserialized from the provided bytes.
+    PublicInputsDeserializationError(String),
+    /// This error occurs when the AIR identifier in the proof context does not match the
+    /// identifier of the AIR with which the verifier was instantiated.
+    InconsistentAirId,
+    /// This error occurs when a verifier registry receives a proof which does not specify an AIR
+    /// identifier.
+    MissingAirId,
+    /// This error occurs when a verifier registry receives a proof for an AIR which has not been
+    /// registered with it.
+    UnregisteredAir(u32),
     /// This error occurs when a verifier fails to draw a random value from a random coin
     /// within a specified number of tries.
     RandomCoinError,
@@ -53,6 +64,29 @@ pub enum VerifierError {
     /// This error occurs when the parameters, that were used to generate the proof, do not match
     /// any of the set of parameters expected by the verifier.
     UnacceptableProofOptions,
+    /// This error occurs when the proof options carry an extension with the specified ID which
+    /// is not understood by the verifier, and the verifier is configured to reject such proofs.
+    UnknownOptionsExtension(u16),
+    /// This error occurs when the verifier expects a proof of execution of a specific program,
+    /// but the specified AIR does not define a program.
+    MissingProgram,
+    /// This error occurs when the hash of the program defined by the AIR does not match the
+    /// program hash expected by the verifier.
+    InconsistentProgramHash,
+    /// This error occurs when the lookup table commitment in the proof context does not match
+    /// the commitment to the lookup tables of the AIR with which the verifier was instantiated.
+    InconsistentLookupTables,
+    /// This error occurs when the size of digests produced by the hash function with which the
+    /// verifier was instantiated does not match the digest size specified by the proof options.
+    InconsistentDigestSize(usize, usize),
+    /// This error occurs when the size of digests produced by the hash function with which the
+    /// verifier checks FRI layer commitments does not match the FRI digest size specified by the
+    /// proof options.
+    InconsistentFriDigestSize(usize, usize),
+    /// This error occurs when the LDE domain of the proof is larger than the largest
+    /// multiplicative subgroup of the base field of the AIR with which the verifier was
+    /// instantiated.
+    LdeDomainTooLarge(u32, u32),
 }
 
 impl fmt::Display for VerifierError {
@@ -71,6 +105,18 @@ impl fmt::Display for VerifierError {
             Self::ProofDeserializationError(msg) => {
                 write!(f, "proof deserialization failed: {msg}")
             }
+            Self::PublicInputsDeserializationError(msg) => {
+                write!(f, "public inputs deserialization failed: {msg}")
+            }
+            Self::InconsistentAirId => {
+                write!(f, "AIR identifier of the proof does not match identifier of the specified AIR")
+            }
+            Self::MissingAirId => {
+                write!(f, "proof does not specify an AIR identifier")
+            }
+            Self::UnregisteredAir(air_id) => {
+                write!(f, "no verifier has been registered for AIR with identifier {air_id}")
+            }
             Self::RandomCoinError => {
                 write!(f, "failed to draw a random value from a random coin")
             }
@@ -96,6 +142,27 @@ impl fmt::Display for VerifierError {
                 write!(f, "insufficient proof security level: expected at least {minimal_security} bits of proven security, but was {proof_security} bits")
             }
             Self::UnacceptableProofOptions => {write!(f, "invalid proof options: security parameters do not match the acceptable parameter set")}
+            Self::UnknownOptionsExtension(id) => {
+                write!(f, "proof options contain an unknown extension with ID {id}")
+            }
+            Self::MissingProgram => {
+                write!(f, "the specified AIR does not define a program")
+            }
+            Self::InconsistentProgramHash => {
+                write!(f, "hash of the program defined by the AIR does not match the expected program hash")
+            }
+            Self::InconsistentLookupTables => {
+                write!(f, "lookup table commitment in the proof context does not match lookup tables defined by the AIR")
+            }
+            Self::InconsistentDigestSize(expected, actual) => {
+                write!(f, "proof options require digests of {expected} bytes, but the hash function produces digests of {actual} bytes")
+            }
+            Self::InconsistentFriDigestSize(expected, actual) => {
+                write!(f, "proof options require FRI layer digests of {expected} bytes, but the FRI hash function produces digests of {actual} bytes")
+            }
+            Self::LdeDomainTooLarge(lde_domain_size, max_domain_size) => {
+                write!(f, "LDE domain of the proof has 2^{lde_domain_size} elements, but the base field supports domains of at most 2^{max_domain_size} elements")
+            }
         }
     }
 }
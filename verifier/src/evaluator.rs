@@ -35,7 +35,7 @@ pub fn evaluate_constraints<A: Air, E: FieldElement<BaseField = A::BaseField>>(
         .iter()
         .map(|poly| {
             let num_cycles = air.trace_length() / poly.len();
-            let x = x.exp_vartime((num_cycles as u32).into());
+            let x = x.exp_vartime((num_cycles as u64).into());
             polynom::eval(poly, x)
         })
         .collect::<Vec<_>>();
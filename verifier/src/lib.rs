@@ -15,6 +15,10 @@
 //! 2. Execute [verify()] function and supply the AIR of your computation together with the
 //!    [Proof] and related public inputs as parameters.
 //!
+//! To accept proofs for any one of several computations, the AIRs of these computations can be
+//! given distinct identifiers (see [Air::ID]) and registered with a [VerifierRegistry]. The
+//! registry reads the identifier from a proof and dispatches verification to the matching AIR.
+//!
 //! # Performance
 //! Proof verification is extremely fast and is nearly independent of the complexity of the
 //! computation being verified. In vast majority of cases proofs can be verified in 3 - 5 ms
@@ -34,18 +38,23 @@ extern crate alloc;
 use alloc::{string::ToString, vec::Vec};
 
 pub use air::{
-    proof::Proof, Air, AirContext, Assertion, BoundaryConstraint, BoundaryConstraintGroup,
-    ConstraintCompositionCoefficients, ConstraintDivisor, DeepCompositionCoefficients,
-    EvaluationFrame, FieldExtension, ProofOptions, TraceInfo, TransitionConstraintDegree,
+    pad_periodic_column, proof::Proof, Air, AirContext, Assertion, BoundaryConstraint,
+    BoundaryConstraintGroup, CoefficientDerivation, ConstraintCompositionCoefficients,
+    ConstraintDivisor, DeepCompositionCoefficients, EvaluationFrame, FieldExtension, LookupTable,
+    OptionsExtension, Program, ProofOptions, SoundnessMode, TraceInfo, TransitionConstraintDegree,
+    UnknownExtensionPolicy,
+};
+use air::{
+    proof::{BatchOpening, QueryOpening},
+    AuxRandElements, GkrVerifier,
 };
-use air::{AuxRandElements, GkrVerifier};
 pub use crypto;
-use crypto::{ElementHasher, Hasher, RandomCoin, VectorCommitment};
-use fri::FriVerifier;
+use crypto::{transcript_labels, ElementHasher, Hasher, RandomCoin, VectorCommitment};
+use fri::{FriVerifier, LowDegreeVerifier};
 pub use math;
 use math::{
     fields::{CubeExtension, QuadExtension},
-    FieldElement, ToElements,
+    FieldElement, StarkField, ToElements,
 };
 pub use utils::{
     ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable, SliceReader,
@@ -63,6 +72,15 @@ use composer::DeepComposer;
 mod errors;
 pub use errors::VerifierError;
 
+mod registry;
+pub use registry::VerifierRegistry;
+
+// CRATE FEATURES
+// ================================================================================================
+
+/// Indicates whether this crate was compiled with the `std` feature.
+pub const STD_ENABLED: bool = cfg!(feature = "std");
+
 // VERIFIER
 // ================================================================================================
 
@@ -72,12 +90,18 @@ pub use errors::VerifierError;
 /// the provided `proof` attests to the correct execution of the computation against public inputs
 /// specified by `pub_inputs`. If the verification is successful, `Ok(())` is returned.
 ///
+/// Commitments are verified using `HashFn` and the `VC` vector commitment scheme, while the
+/// Fiat-Shamir transcript is built using the hash function of `RandCoin`; the two hash functions
+/// must match the ones used by the prover.
+///
 /// # Errors
 /// Returns an error if combination of the provided proof and public inputs does not attest to
 /// a correct execution of the computation. This could happen for many various reasons, including:
 /// - The specified proof was generated for a different computation.
 /// - The specified proof was generated for this computation but for different public inputs.
 /// - The specified proof was generated with parameters not providing an acceptable security level.
+/// - The AIR identifier bound into the proof does not match [Air::ID] of the specified AIR.
+/// - The proof options carry an extension which is not understood by the verifier.
 pub fn verify<AIR, HashFn, RandCoin, VC>(
     proof: Proof,
     pub_inputs: AIR::PublicInputs,
@@ -86,12 +110,270 @@ pub fn verify<AIR, HashFn, RandCoin, VC>(
 where
     AIR: Air,
     HashFn: ElementHasher<BaseField = AIR::BaseField>,
-    RandCoin: RandomCoin<BaseField = AIR::BaseField, Hasher = HashFn>,
+    RandCoin:
+        RandomCoin<BaseField = AIR::BaseField, Hasher: ElementHasher<Digest: From<HashFn::Digest>>>,
+    VC: VectorCommitment<HashFn>,
+{
+    verify_with_extension_policy::<AIR, HashFn, RandCoin, VC>(
+        proof,
+        pub_inputs,
+        acceptable_options,
+        UnknownExtensionPolicy::Reject,
+    )
+}
+
+/// Verifies that the specified computation was executed correctly against the specified inputs
+/// using the specified policy for proof options extensions not understood by the verifier.
+///
+/// This is the same as [verify()], except that proofs carrying unknown proof options extensions
+/// are accepted when `extension_policy` is set to [UnknownExtensionPolicy::Ignore].
+///
+/// # Errors
+/// Returns an error under the same conditions as [verify()].
+pub fn verify_with_extension_policy<AIR, HashFn, RandCoin, VC>(
+    proof: Proof,
+    pub_inputs: AIR::PublicInputs,
+    acceptable_options: &AcceptableOptions,
+    extension_policy: UnknownExtensionPolicy,
+) -> Result<(), VerifierError>
+where
+    AIR: Air,
+    HashFn: ElementHasher<BaseField = AIR::BaseField>,
+    RandCoin:
+        RandomCoin<BaseField = AIR::BaseField, Hasher: ElementHasher<Digest: From<HashFn::Digest>>>,
+    VC: VectorCommitment<HashFn>,
+{
+    verify_proof::<AIR, HashFn, HashFn, RandCoin, VC, VC, BatchOpening>(
+        proof,
+        pub_inputs,
+        acceptable_options,
+        extension_policy,
+        None,
+        RandCoin::new,
+    )
+}
+
+/// Verifies that the specified computation was executed correctly against the specified inputs
+/// for a proof in which FRI layers are committed to using a separate hash function.
+///
+/// This is the same as [verify()], except that FRI layer commitments are verified using
+/// `FriHashFn` and the `FriVC` vector commitment scheme, while commitments to the execution trace
+/// and constraint evaluations are verified using `HashFn` and `VC`. Both hash functions must
+/// match the ones used by the prover (see `Prover::FriHashFn`), and the security level of the
+/// proof is limited by the collision resistance of both.
+///
+/// # Errors
+/// Returns an error under the same conditions as [verify()], and also if the FRI digest size
+/// specified by the proof options does not match the size of digests produced by `FriHashFn`.
+pub fn verify_with_fri_hasher<AIR, HashFn, FriHashFn, RandCoin, VC, FriVC>(
+    proof: Proof,
+    pub_inputs: AIR::PublicInputs,
+    acceptable_options: &AcceptableOptions,
+) -> Result<(), VerifierError>
+where
+    AIR: Air,
+    HashFn: ElementHasher<BaseField = AIR::BaseField>,
+    FriHashFn: ElementHasher<BaseField = AIR::BaseField>,
+    RandCoin: RandomCoin<
+        BaseField = AIR::BaseField,
+        Hasher: ElementHasher<Digest: From<HashFn::Digest> + From<FriHashFn::Digest>>,
+    >,
+    VC: VectorCommitment<HashFn>,
+    FriVC: VectorCommitment<FriHashFn>,
+{
+    verify_proof::<AIR, HashFn, FriHashFn, RandCoin, VC, FriVC, BatchOpening>(
+        proof,
+        pub_inputs,
+        acceptable_options,
+        UnknownExtensionPolicy::Reject,
+        None,
+        RandCoin::new,
+    )
+}
+
+/// Verifies that the specified computation was executed correctly against the specified inputs
+/// for a proof in which trace and constraint queries are opened using a custom scheme.
+///
+/// This is the same as [verify()], except that trace and constraint queries are checked against
+/// their commitments using the `QueryOpen` scheme rather than via batch opening proofs of `VC`;
+/// the scheme must match the one used by the prover (see [QueryOpening]).
+///
+/// # Errors
+/// Returns an error under the same conditions as [verify()].
+pub fn verify_with_query_opening<AIR, HashFn, RandCoin, VC, QueryOpen>(
+    proof: Proof,
+    pub_inputs: AIR::PublicInputs,
+    acceptable_options: &AcceptableOptions,
+) -> Result<(), VerifierError>
+where
+    AIR: Air,
+    HashFn: ElementHasher<BaseField = AIR::BaseField>,
+    RandCoin:
+        RandomCoin<BaseField = AIR::BaseField, Hasher: ElementHasher<Digest: From<HashFn::Digest>>>,
+    VC: VectorCommitment<HashFn>,
+    QueryOpen: QueryOpening<HashFn, VC>,
+{
+    verify_proof::<AIR, HashFn, HashFn, RandCoin, VC, VC, QueryOpen>(
+        proof,
+        pub_inputs,
+        acceptable_options,
+        UnknownExtensionPolicy::Reject,
+        None,
+        RandCoin::new,
+    )
+}
+
+/// Verifies that the specified computation was executed correctly against the specified inputs,
+/// and that the executed program has the specified hash.
+///
+/// This is the same as [verify()], but is intended for AIRs describing virtual machines which can
+/// execute arbitrary programs (see [Air::get_program()]). The program is usually a part of the
+/// public inputs; this function makes sure that it hashes to `program_hash` before verifying the
+/// proof, and thus, allows the verifier to pin a specific program.
+///
+/// # Errors
+/// Returns an error under the same conditions as [verify()], and also if:
+/// - The AIR instantiated from the specified public inputs does not define a program.
+/// - The hash of the program defined by the AIR is not equal to `program_hash`.
+pub fn verify_program<AIR, HashFn, RandCoin, VC>(
+    proof: Proof,
+    pub_inputs: AIR::PublicInputs,
+    program_hash: &HashFn::Digest,
+    acceptable_options: &AcceptableOptions,
+) -> Result<(), VerifierError>
+where
+    AIR: Air,
+    HashFn: ElementHasher<BaseField = AIR::BaseField>,
+    RandCoin:
+        RandomCoin<BaseField = AIR::BaseField, Hasher: ElementHasher<Digest: From<HashFn::Digest>>>,
     VC: VectorCommitment<HashFn>,
 {
+    verify_proof::<AIR, HashFn, HashFn, RandCoin, VC, VC, BatchOpening>(
+        proof,
+        pub_inputs,
+        acceptable_options,
+        UnknownExtensionPolicy::Reject,
+        Some(program_hash),
+        RandCoin::new,
+    )
+}
+
+/// Verifies that the specified computation was executed correctly against the specified inputs
+/// using a public coin instantiated via `new_public_coin`.
+///
+/// This is the same as [verify()], except that the public coin used to build the Fiat-Shamir
+/// transcript is instantiated by calling `new_public_coin` with a seed consisting of the proof
+/// context and the public inputs (rather than via [RandomCoin::new()]). The coin must be
+/// instantiated in the same way as by the prover (see `Prover::new_public_coin()`).
+///
+/// # Errors
+/// Returns an error under the same conditions as [verify()].
+pub fn verify_with_public_coin<AIR, HashFn, RandCoin, VC>(
+    proof: Proof,
+    pub_inputs: AIR::PublicInputs,
+    acceptable_options: &AcceptableOptions,
+    new_public_coin: impl FnOnce(&[AIR::BaseField]) -> RandCoin,
+) -> Result<(), VerifierError>
+where
+    AIR: Air,
+    HashFn: ElementHasher<BaseField = AIR::BaseField>,
+    RandCoin:
+        RandomCoin<BaseField = AIR::BaseField, Hasher: ElementHasher<Digest: From<HashFn::Digest>>>,
+    VC: VectorCommitment<HashFn>,
+{
+    verify_proof::<AIR, HashFn, HashFn, RandCoin, VC, VC, BatchOpening>(
+        proof,
+        pub_inputs,
+        acceptable_options,
+        UnknownExtensionPolicy::Reject,
+        None,
+        new_public_coin,
+    )
+}
+
+/// Checks the proof against the verifier's expectations and runs the verification procedure
+/// for the field extension specified in the proof.
+fn verify_proof<AIR, HashFn, FriHashFn, RandCoin, VC, FriVC, QueryOpen>(
+    proof: Proof,
+    pub_inputs: AIR::PublicInputs,
+    acceptable_options: &AcceptableOptions,
+    extension_policy: UnknownExtensionPolicy,
+    program_hash: Option<&HashFn::Digest>,
+    new_public_coin: impl FnOnce(&[AIR::BaseField]) -> RandCoin,
+) -> Result<(), VerifierError>
+where
+    AIR: Air,
+    HashFn: ElementHasher<BaseField = AIR::BaseField>,
+    FriHashFn: ElementHasher<BaseField = AIR::BaseField>,
+    RandCoin: RandomCoin<
+        BaseField = AIR::BaseField,
+        Hasher: ElementHasher<Digest: From<HashFn::Digest> + From<FriHashFn::Digest>>,
+    >,
+    VC: VectorCommitment<HashFn>,
+    FriVC: VectorCommitment<FriHashFn>,
+    QueryOpen: QueryOpening<HashFn, VC>,
+{
+    // make sure the LDE domain of the proof can be built in the base field of the AIR
+    let lde_domain_size = proof.lde_domain_size().ilog2();
+    if lde_domain_size > AIR::BaseField::TWO_ADICITY {
+        return Err(VerifierError::LdeDomainTooLarge(lde_domain_size, AIR::BaseField::TWO_ADICITY));
+    }
+
     // check that `proof` was generated with an acceptable set of parameters from the point of view
-    // of the verifier
+    // of the verifier; security of the proof is limited by the hash functions used for
+    // commitments and the hash function used by the public coin
     acceptable_options.validate::<HashFn>(&proof)?;
+    acceptable_options.validate::<FriHashFn>(&proof)?;
+    acceptable_options.validate::<RandCoin::Hasher>(&proof)?;
+    if extension_policy == UnknownExtensionPolicy::Reject {
+        if let Some(extension) = proof.options().unknown_extensions().next() {
+            return Err(VerifierError::UnknownOptionsExtension(extension.id()));
+        }
+    }
+
+    // if the proof options declare a soundness mode, make sure the proof achieves the declared
+    // security level under the soundness bounds of this mode
+    if let Some((mode, security_level)) = proof.options().soundness_mode() {
+        let conjectured = mode == SoundnessMode::Conjectured;
+        let proof_security_level = [
+            proof.security_level::<HashFn>(conjectured),
+            proof.security_level::<FriHashFn>(conjectured),
+            proof.security_level::<RandCoin::Hasher>(conjectured),
+        ]
+        .into_iter()
+        .min()
+        .expect("security levels are not empty");
+        if proof_security_level < security_level {
+            return Err(match mode {
+                SoundnessMode::Conjectured => VerifierError::InsufficientConjecturedSecurity(
+                    security_level,
+                    proof_security_level,
+                ),
+                SoundnessMode::Proven => {
+                    VerifierError::InsufficientProvenSecurity(security_level, proof_security_level)
+                },
+            });
+        }
+    }
+
+    // make sure the hash function produces digests of the size specified by the proof options
+    if let Some(digest_size) = proof.options().digest_size() {
+        let hash_digest_size = HashFn::Digest::default().to_bytes().len();
+        if hash_digest_size != digest_size {
+            return Err(VerifierError::InconsistentDigestSize(digest_size, hash_digest_size));
+        }
+    }
+    if let Some(digest_size) = proof.options().fri_digest_size() {
+        let hash_digest_size = FriHashFn::Digest::default().to_bytes().len();
+        if hash_digest_size != digest_size {
+            return Err(VerifierError::InconsistentFriDigestSize(digest_size, hash_digest_size));
+        }
+    }
+
+    // make sure the proof was generated for the computation described by the specified AIR
+    if proof.context.air_id() != AIR::ID {
+        return Err(VerifierError::InconsistentAirId);
+    }
 
     // build a seed for the public coin; the initial seed is a hash of the proof context and the
     // public inputs, but as the protocol progresses, the coin will be reseeded with the info
@@ -102,41 +384,74 @@ where
     // create AIR instance for the computation specified in the proof
     let air = AIR::new(proof.trace_info().clone(), pub_inputs, proof.options().clone());
 
+    // if the verifier expects a specific program, make sure the AIR executes this program
+    if let Some(program_hash) = program_hash {
+        let program = air.get_program().ok_or(VerifierError::MissingProgram)?;
+        if program.hash::<HashFn>() != *program_hash {
+            return Err(VerifierError::InconsistentProgramHash);
+        }
+    }
+
+    // make sure the proof was generated against the lookup tables defined by the AIR
+    let lookup_tables = air.get_lookup_tables();
+    let lookup_table_commitment = (!lookup_tables.is_empty())
+        .then(|| LookupTable::commitment::<HashFn>(lookup_tables).to_bytes());
+    if proof.context.lookup_table_commitment() != lookup_table_commitment.as_deref() {
+        return Err(VerifierError::InconsistentLookupTables);
+    }
+
+    let public_coin = new_public_coin(&public_coin_seed);
+
     // figure out which version of the generic proof verification procedure to run. this is a sort
     // of static dispatch for selecting two generic parameter: extension field and hash function.
     match air.options().field_extension() {
         FieldExtension::None => {
-            let public_coin = RandCoin::new(&public_coin_seed);
             let channel = VerifierChannel::new(&air, proof)?;
-            perform_verification::<AIR, AIR::BaseField, HashFn, RandCoin, VC>(
-                air,
-                channel,
-                public_coin,
-            )
+            perform_verification::<
+                AIR,
+                AIR::BaseField,
+                HashFn,
+                RandCoin,
+                VC,
+                FriHashFn,
+                FriVC,
+                QueryOpen,
+                FriVerifier<_, _, _, _, _>,
+            >(air, channel, public_coin)
         },
         FieldExtension::Quadratic => {
             if !<QuadExtension<AIR::BaseField>>::is_supported() {
                 return Err(VerifierError::UnsupportedFieldExtension(2));
             }
-            let public_coin = RandCoin::new(&public_coin_seed);
             let channel = VerifierChannel::new(&air, proof)?;
-            perform_verification::<AIR, QuadExtension<AIR::BaseField>, HashFn, RandCoin, VC>(
-                air,
-                channel,
-                public_coin,
-            )
+            perform_verification::<
+                AIR,
+                QuadExtension<AIR::BaseField>,
+                HashFn,
+                RandCoin,
+                VC,
+                FriHashFn,
+                FriVC,
+                QueryOpen,
+                FriVerifier<_, _, _, _, _>,
+            >(air, channel, public_coin)
         },
         FieldExtension::Cubic => {
             if !<CubeExtension<AIR::BaseField>>::is_supported() {
                 return Err(VerifierError::UnsupportedFieldExtension(3));
             }
-            let public_coin = RandCoin::new(&public_coin_seed);
             let channel = VerifierChannel::new(&air, proof)?;
-            perform_verification::<AIR, CubeExtension<AIR::BaseField>, HashFn, RandCoin, VC>(
-                air,
-                channel,
-                public_coin,
-            )
+            perform_verification::<
+                AIR,
+                CubeExtension<AIR::BaseField>,
+                HashFn,
+                RandCoin,
+                VC,
+                FriHashFn,
+                FriVC,
+                QueryOpen,
+                FriVerifier<_, _, _, _, _>,
+            >(air, channel, public_coin)
         },
     }
 }
@@ -145,17 +460,27 @@ where
 // ================================================================================================
 /// Performs the actual verification by reading the data from the `channel` and making sure it
 /// attests to a correct execution of the computation specified by the provided `air`.
-fn perform_verification<A, E, H, R, V>(
+///
+/// The low-degree test for the DEEP composition polynomial is executed via the low-degree
+/// verifier specified by type parameter `L`.
+fn perform_verification<A, E, H, R, V, FH, FV, O, L>(
     air: A,
-    mut channel: VerifierChannel<E, H, V>,
+    mut channel: VerifierChannel<E, H, V, FH, FV, O>,
     mut public_coin: R,
 ) -> Result<(), VerifierError>
 where
     E: FieldElement<BaseField = A::BaseField>,
     A: Air,
     H: ElementHasher<BaseField = A::BaseField>,
-    R: RandomCoin<BaseField = A::BaseField, Hasher = H>,
+    R: RandomCoin<
+        BaseField = A::BaseField,
+        Hasher: ElementHasher<Digest: From<H::Digest> + From<FH::Digest>>,
+    >,
     V: VectorCommitment<H>,
+    FH: ElementHasher<BaseField = A::BaseField>,
+    FV: VectorCommitment<FH>,
+    O: QueryOpening<H, V>,
+    L: LowDegreeVerifier<E, VerifierChannel<E, H, V, FH, FV, O>, R>,
 {
     // 1 ----- trace commitment -------------------------------------------------------------------
     // Read the commitments to evaluations of the trace polynomials over the LDE domain sent by the
@@ -171,7 +496,10 @@ where
     let trace_commitments = channel.read_trace_commitments();
 
     // reseed the coin with the commitment to the main trace segment
-    public_coin.reseed(trace_commitments[MAIN_TRACE_IDX]);
+    public_coin.reseed_with_label(
+        transcript_labels::MAIN_TRACE_ROOT,
+        trace_commitments[MAIN_TRACE_IDX].into(),
+    );
 
     // process auxiliary trace segments (if any), to build a set of random elements for each segment
     let aux_trace_rand_elements = if air.trace_info().is_multi_segment() {
@@ -193,7 +521,10 @@ where
                 "failed to generate the random elements needed to build the auxiliary trace",
             );
 
-            public_coin.reseed(trace_commitments[AUX_TRACE_IDX]);
+            public_coin.reseed_with_label(
+                transcript_labels::AUX_TRACE_ROOT,
+                trace_commitments[AUX_TRACE_IDX].into(),
+            );
 
             Some(AuxRandElements::new_with_gkr(rand_elements, gkr_rand_elements))
         } else {
@@ -201,7 +532,10 @@ where
                 "failed to generate the random elements needed to build the auxiliary trace",
             );
 
-            public_coin.reseed(trace_commitments[AUX_TRACE_IDX]);
+            public_coin.reseed_with_label(
+                transcript_labels::AUX_TRACE_ROOT,
+                trace_commitments[AUX_TRACE_IDX].into(),
+            );
 
             Some(AuxRandElements::new(rand_elements))
         }
@@ -221,7 +555,7 @@ where
     // to the prover, and the prover evaluates trace and constraint composition polynomials at z,
     // and sends the results back to the verifier.
     let constraint_commitment = channel.read_constraint_commitment();
-    public_coin.reseed(constraint_commitment);
+    public_coin.reseed_with_label(transcript_labels::CONSTRAINT_ROOT, constraint_commitment.into());
     let z = public_coin.draw::<E>().map_err(|_| VerifierError::RandomCoinError)?;
 
     // 3 ----- OOD consistency check --------------------------------------------------------------
@@ -244,24 +578,34 @@ where
         aux_trace_rand_elements.as_ref(),
         z,
     );
-    public_coin.reseed(ood_trace_frame.hash::<H>());
+    public_coin
+        .reseed_with_label(transcript_labels::OOD_TRACE_FRAME, ood_trace_frame.hash::<R::Hasher>());
 
     // read evaluations of composition polynomial columns sent by the prover, and reduce them into
     // a single value by computing \sum_{i=0}^{m-1}(z^(i * l) * value_i), where value_i is the
-    // evaluation of the ith column polynomial H_i(X) at z, l is the trace length and m is
-    // the number of composition column polynomials. This computes H(z) (i.e.
-    // the evaluation of the composition polynomial at z) using the fact that
-    // H(X) = \sum_{i=0}^{m-1} X^{i * l} H_i(X).
+    // evaluation of the ith column polynomial H_i(X) at z, l is the composition segment length
+    // (equal to the trace length outside of zero-knowledge mode) and m is the number of
+    // composition column polynomials. This computes H(z) (i.e. the evaluation of the composition
+    // polynomial at z) using the fact that H(X) = \sum_{i=0}^{m-1} X^{i * l} H_i(X). In
+    // zero-knowledge mode, the last column holds a random polynomial which is not a part of H(X).
     // Also, reseed the public coin with the OOD constraint evaluations received from the prover.
     let ood_constraint_evaluations = channel.read_ood_constraint_evaluations();
-    let ood_constraint_evaluation_2 =
-        ood_constraint_evaluations
-            .iter()
-            .enumerate()
-            .fold(E::ZERO, |result, (i, &value)| {
-                result + z.exp_vartime(((i * (air.trace_length())) as u32).into()) * value
-            });
-    public_coin.reseed(H::hash_elements(&ood_constraint_evaluations));
+    let num_segments = if air.options().is_zk() {
+        ood_constraint_evaluations.len() - 1
+    } else {
+        ood_constraint_evaluations.len()
+    };
+    let segment_len = air.context().composition_segment_len();
+    let ood_constraint_evaluation_2 = ood_constraint_evaluations[..num_segments]
+        .iter()
+        .enumerate()
+        .fold(E::ZERO, |result, (i, &value)| {
+            result + z.exp_vartime(((i * segment_len) as u64).into()) * value
+        });
+    public_coin.reseed_with_label(
+        transcript_labels::OOD_CONSTRAINT_EVALUATIONS,
+        R::Hasher::hash_elements(&ood_constraint_evaluations),
+    );
 
     // finally, make sure the values are the same
     if ood_constraint_evaluation_1 != ood_constraint_evaluation_2 {
@@ -282,7 +626,7 @@ where
     // The verifier uses these commitments to update the public coin and draw random points alpha
     // from them; in the interactive version of the protocol, the verifier sends these alphas to
     // the prover, and the prover uses them to compute and commit to the subsequent FRI layers.
-    let fri_verifier = FriVerifier::new(
+    let fri_verifier = L::new(
         &mut channel,
         &mut public_coin,
         air.options().to_fri_options(),
@@ -304,8 +648,8 @@ where
     // interactive version of the protocol, the verifier sends these query positions to the prover,
     // and the prover responds with decommitments against these positions for trace and constraint
     // composition polynomial evaluations.
-    let mut query_positions = public_coin
-        .draw_integers(air.options().num_queries(), air.lde_domain_size(), pow_nonce)
+    let mut query_positions = air
+        .get_query_positions(&mut public_coin, pow_nonce)
         .map_err(|_| VerifierError::RandomCoinError)?;
 
     // remove any potential duplicates from the positions as the prover will send openings only
@@ -313,15 +657,16 @@ where
     query_positions.sort_unstable();
     query_positions.dedup();
 
-    // read evaluations of trace and constraint composition polynomials at the queried positions;
-    // this also checks that the read values are valid against trace and constraint commitments
-    let (queried_main_trace_states, queried_aux_trace_states) =
-        channel.read_queried_trace_states(&query_positions)?;
-    let queried_constraint_evaluations = channel.read_constraint_evaluations(&query_positions)?;
+    // read evaluations of trace and constraint composition polynomials at the queried positions
+    // and compute evaluations of the DEEP composition polynomial at these positions; reading the
+    // values also checks that they are valid against trace and constraint commitments. trace
+    // states are consumed before constraint evaluations are read so that only one set of
+    // openings is held in memory at a time.
+    let composer = DeepComposer::new(&air, &query_positions, z, deep_coefficients);
 
     // 6 ----- DEEP composition -------------------------------------------------------------------
-    // compute evaluations of the DEEP composition polynomial at the queried positions
-    let composer = DeepComposer::new(&air, &query_positions, z, deep_coefficients);
+    let (queried_main_trace_states, queried_aux_trace_states) =
+        channel.read_queried_trace_states(&query_positions)?;
     let t_composition = composer.compose_trace_columns(
         queried_main_trace_states,
         queried_aux_trace_states,
@@ -329,6 +674,8 @@ where
         ood_aux_trace_frame,
         ood_lagrange_kernel_frame,
     );
+
+    let queried_constraint_evaluations = channel.read_constraint_evaluations(&query_positions)?;
     let c_composition = composer
         .compose_constraint_evaluations(queried_constraint_evaluations, ood_constraint_evaluations);
     let deep_evaluations = composer.combine_compositions(t_composition, c_composition);
@@ -0,0 +1,152 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use alloc::{boxed::Box, collections::BTreeMap, string::ToString};
+
+use air::{proof::Proof, Air};
+use crypto::{ElementHasher, RandomCoin, VectorCommitment};
+use utils::{ByteReader, Deserializable, SliceReader};
+
+use crate::{verify, AcceptableOptions, VerifierError};
+
+// VERIFIER REGISTRY
+// ================================================================================================
+
+/// A verification function for a single registered AIR.
+type VerifyFn = Box<dyn Fn(Proof, &[u8]) -> Result<(), VerifierError> + Send + Sync>;
+
+/// A collection of verifiers for several computations, indexed by AIR identifiers.
+///
+/// Each AIR registered with the registry must specify a unique identifier via [Air::ID]. Since
+/// this identifier is bound into the context of every proof generated for the AIR, the registry
+/// can determine which computation a proof attests to, and dispatch the verification to the
+/// corresponding AIR without the caller needing to know the computation in advance.
+///
+/// # Examples
+/// ```ignore
+/// let mut registry = VerifierRegistry::new();
+/// registry.register::<FooAir, Blake3_256<BaseElement>, DefaultRandomCoin<_>, MerkleTree<_>>(
+///     AcceptableOptions::MinConjecturedSecurity(95),
+/// );
+/// registry.register::<BarAir, Blake3_256<BaseElement>, DefaultRandomCoin<_>, MerkleTree<_>>(
+///     AcceptableOptions::MinConjecturedSecurity(95),
+/// );
+///
+/// // verifies the proof against either FooAir or BarAir depending on the identifier in the proof
+/// registry.verify_any(&proof_bytes, &pub_inputs_bytes)?;
+/// ```
+#[derive(Default)]
+pub struct VerifierRegistry {
+    verifiers: BTreeMap<u32, VerifyFn>,
+}
+
+impl VerifierRegistry {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // REGISTRATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Registers a verifier for the computation described by `AIR` using the specified hash
+    /// function, random coin, and vector commitment types.
+    ///
+    /// Proofs dispatched to this verifier are checked against the provided `acceptable_options`,
+    /// and public inputs for them are deserialized into `AIR::PublicInputs`.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `AIR` does not specify an identifier.
+    /// * An AIR with the same identifier has already been registered.
+    pub fn register<AIR, HashFn, RandCoin, VC>(&mut self, acceptable_options: AcceptableOptions)
+    where
+        AIR: Air + 'static,
+        AIR::PublicInputs: Deserializable,
+        HashFn: ElementHasher<BaseField = AIR::BaseField> + 'static,
+        RandCoin: RandomCoin<
+                BaseField = AIR::BaseField,
+                Hasher: ElementHasher<Digest: From<HashFn::Digest>>,
+            > + 'static,
+        VC: VectorCommitment<HashFn> + 'static,
+    {
+        let air_id = AIR::ID.expect("only AIRs with identifiers can be registered");
+        assert!(
+            !self.verifiers.contains_key(&air_id),
+            "AIR with identifier {air_id} has already been registered"
+        );
+
+        let verifier = move |proof: Proof, pub_inputs_bytes: &[u8]| {
+            let pub_inputs = read_pub_inputs::<AIR::PublicInputs>(pub_inputs_bytes)?;
+            verify::<AIR, HashFn, RandCoin, VC>(proof, pub_inputs, &acceptable_options)
+        };
+        self.verifiers.insert(air_id, Box::new(verifier));
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns true if a verifier for the AIR with the specified identifier has been registered.
+    pub fn contains(&self, air_id: u32) -> bool {
+        self.verifiers.contains_key(&air_id)
+    }
+
+    /// Returns an iterator over identifiers of all registered AIRs in ascending order.
+    pub fn air_ids(&self) -> impl Iterator<Item = u32> + '_ {
+        self.verifiers.keys().copied()
+    }
+
+    // VERIFICATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Verifies the specified `proof` using the verifier registered for the AIR identified in the
+    /// proof context.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The proof does not specify an AIR identifier.
+    /// * No verifier has been registered for the AIR specified in the proof.
+    /// * Public inputs could not be deserialized from `pub_inputs_bytes`.
+    /// * The proof is not valid against the public inputs for the specified AIR.
+    pub fn verify(&self, proof: Proof, pub_inputs_bytes: &[u8]) -> Result<(), VerifierError> {
+        let air_id = proof.context.air_id().ok_or(VerifierError::MissingAirId)?;
+        let verifier = self.verifiers.get(&air_id).ok_or(VerifierError::UnregisteredAir(air_id))?;
+        verifier(proof, pub_inputs_bytes)
+    }
+
+    /// Deserializes a proof from `proof_bytes` and verifies it using the verifier registered for
+    /// the AIR identified in the proof context.
+    ///
+    /// # Errors
+    /// Returns an error if the proof could not be deserialized, or for any of the reasons listed
+    /// for [verify()](VerifierRegistry::verify).
+    pub fn verify_any(
+        &self,
+        proof_bytes: &[u8],
+        pub_inputs_bytes: &[u8],
+    ) -> Result<(), VerifierError> {
+        let proof = Proof::from_bytes(proof_bytes)
+            .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
+        self.verify(proof, pub_inputs_bytes)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Reads public inputs from the provided bytes making sure that all bytes are consumed.
+fn read_pub_inputs<P: Deserializable>(bytes: &[u8]) -> Result<P, VerifierError> {
+    let mut reader = SliceReader::new(bytes);
+    let pub_inputs = P::read_from(&mut reader)
+        .map_err(|err| VerifierError::PublicInputsDeserializationError(err.to_string()))?;
+    if reader.has_more_bytes() {
+        return Err(VerifierError::PublicInputsDeserializationError(
+            "not all bytes were consumed".to_string(),
+        ));
+    }
+    Ok(pub_inputs)
+}
@@ -0,0 +1,229 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+// CONSTANTS
+// ================================================================================================
+
+/// Returns names of the specified items of the specified module as a slice of string literals.
+///
+/// The items are imported from the module; thus, renaming or removing any of them results in a
+/// compilation error rather than in an outdated list of names.
+macro_rules! item_names {
+    ($($module:ident)::+ => [$($item:ident),* $(,)?]) => {{
+        #[allow(unused_imports)]
+        use $($module)::+::{$($item),*};
+        &[$(stringify!($item)),*]
+    }};
+}
+
+/// Names of hash functions available in [crypto::hashers](crate::crypto::hashers).
+const HASHERS: &[&str] = item_names!(crate::crypto::hashers => [
+    Blake2s256,
+    Blake3_192,
+    Blake3_256,
+    Griffin64_256,
+    Keccak256,
+    Poseidon2_64_256,
+    Poseidon64_256,
+    Rp62_248,
+    Rp64_256,
+    RpJive64_256,
+    Sha3_256,
+]);
+
+/// Names of fields available in [math::fields](crate::math::fields).
+const FIELDS: &[&str] = item_names!(crate::math::fields => [f62, f64, f128, secp256k1]);
+
+/// Names of Merkle layer hashers available in [crypto](crate::crypto).
+const LAYER_HASHERS: &[&str] = item_names!(crate::crypto => [CpuLayerHasher]);
+
+/// Names of constraint evaluation backends available in this crate.
+const EVALUATION_BACKENDS: &[&str] = item_names!(crate => [CpuEvaluationBackend]);
+
+// CAPABILITIES
+// ================================================================================================
+
+/// Optional capabilities supported by this build of the library.
+///
+/// Features of the prover and the verifier crates are unified by Cargo across the entire
+/// dependency graph; thus, a feature may be enabled even when it was not requested for this
+/// crate directly (or vice versa, when this crate is compiled with `default-features = false`).
+/// Capabilities are reported for the prover and the verifier crates as they were actually
+/// compiled, so that applications and tests can adapt to them at runtime.
+///
+/// # Examples
+/// ```
+/// use winterfell::Capabilities;
+///
+/// let capabilities = Capabilities::detect();
+/// assert!(capabilities.supports_hasher("Blake3_256"));
+/// assert!(capabilities.supports_field("f64"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Capabilities {
+    std: bool,
+    concurrent: bool,
+    async_prover: bool,
+    disk_trace_lde: bool,
+    f64_limbs: bool,
+}
+
+impl Capabilities {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns capabilities of this build of the library.
+    pub const fn detect() -> Self {
+        Self {
+            std: prover::STD_ENABLED && verifier::STD_ENABLED,
+            concurrent: prover::CONCURRENT_ENABLED,
+            async_prover: prover::ASYNC_ENABLED,
+            disk_trace_lde: prover::STD_ENABLED,
+            f64_limbs: crate::math::F64_LIMBS_ENABLED,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns true if both the prover and the verifier were compiled with the `std` feature.
+    pub const fn std(&self) -> bool {
+        self.std
+    }
+
+    /// Returns true if the prover was compiled with the `concurrent` feature, i.e., if proofs are
+    /// generated using multiple threads.
+    pub const fn concurrent(&self) -> bool {
+        self.concurrent
+    }
+
+    /// Returns true if the prover was compiled with the `async` feature, i.e., if
+    /// [Prover::prove()](crate::Prover::prove) is an `async` method.
+    pub const fn async_prover(&self) -> bool {
+        self.async_prover
+    }
+
+    /// Returns true if the prover can keep the extended execution trace on disk, i.e., if
+    /// [TraceLdeStorage::Disk](crate::TraceLdeStorage::Disk) is available. This requires the
+    /// prover to be compiled with the `std` feature.
+    pub const fn disk_trace_lde(&self) -> bool {
+        self.disk_trace_lde
+    }
+
+    /// Returns true if the math crate was compiled with the `f64-limbs` feature, i.e., if
+    /// multiplication in the [f64](crate::math::fields::f64) field relies on `f64` arithmetic.
+    pub const fn f64_limbs(&self) -> bool {
+        self.f64_limbs
+    }
+
+    /// Returns names of all hash functions available in this build of the library.
+    pub const fn hashers(&self) -> &'static [&'static str] {
+        HASHERS
+    }
+
+    /// Returns true if the hash function with the specified name is available in this build of
+    /// the library.
+    pub fn supports_hasher(&self, name: &str) -> bool {
+        HASHERS.contains(&name)
+    }
+
+    /// Returns names of all fields available in this build of the library.
+    pub const fn fields(&self) -> &'static [&'static str] {
+        FIELDS
+    }
+
+    /// Returns true if the field with the specified name is available in this build of the
+    /// library.
+    pub fn supports_field(&self, name: &str) -> bool {
+        FIELDS.contains(&name)
+    }
+
+    /// Returns names of all Merkle layer hashers available in this build of the library (see
+    /// [MerkleLayerHasher](crate::crypto::MerkleLayerHasher)).
+    pub const fn layer_hashers(&self) -> &'static [&'static str] {
+        LAYER_HASHERS
+    }
+
+    /// Returns names of all constraint evaluation backends available in this build of the library
+    /// (see [ConstraintEvaluationBackend](crate::ConstraintEvaluationBackend)).
+    pub const fn evaluation_backends(&self) -> &'static [&'static str] {
+        EVALUATION_BACKENDS
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use core::any::type_name;
+
+    use super::Capabilities;
+    use crate::{
+        crypto::{hashers::*, CpuLayerHasher},
+        math::fields::{f128, f62, f64, secp256k1},
+        CpuEvaluationBackend,
+    };
+
+    /// Returns the name of the specified type without its path and generic parameters.
+    fn short_name<T: ?Sized>() -> &'static str {
+        let name = type_name::<T>();
+        let name = name.split('<').next().unwrap();
+        name.rsplit("::").next().unwrap()
+    }
+
+    /// Returns the name of the module in which the specified type is defined.
+    fn module_name<T: ?Sized>() -> &'static str {
+        let name = type_name::<T>().split('<').next().unwrap();
+        let mut path = name.rsplit("::");
+        path.next();
+        path.next().unwrap()
+    }
+
+    #[test]
+    fn capabilities_hashers() {
+        let capabilities = Capabilities::detect();
+        let hashers = [
+            short_name::<Blake2s256<f64::BaseElement>>(),
+            short_name::<Blake3_192<f64::BaseElement>>(),
+            short_name::<Blake3_256<f64::BaseElement>>(),
+            short_name::<Griffin64_256>(),
+            short_name::<Keccak256<f64::BaseElement>>(),
+            short_name::<Poseidon2_64_256>(),
+            short_name::<Poseidon64_256>(),
+            short_name::<Rp62_248>(),
+            short_name::<Rp64_256>(),
+            short_name::<RpJive64_256>(),
+            short_name::<Sha3_256<f64::BaseElement>>(),
+        ];
+        assert_eq!(hashers.len(), capabilities.hashers().len());
+        for hasher in hashers {
+            assert!(capabilities.supports_hasher(hasher), "hasher {hasher} is not reported");
+        }
+        assert!(!capabilities.supports_hasher("Truncated"));
+    }
+
+    #[test]
+    fn capabilities_fields() {
+        let capabilities = Capabilities::detect();
+        let fields = [
+            module_name::<f62::BaseElement>(),
+            module_name::<f64::BaseElement>(),
+            module_name::<f128::BaseElement>(),
+            module_name::<secp256k1::BaseElement>(),
+        ];
+        assert_eq!(fields.len(), capabilities.fields().len());
+        for field in fields {
+            assert!(capabilities.supports_field(field), "field {field} is not reported");
+        }
+    }
+
+    #[test]
+    fn capabilities_backends() {
+        let capabilities = Capabilities::detect();
+        assert_eq!(&[short_name::<CpuLayerHasher>()], capabilities.layer_hashers());
+        assert_eq!(&[short_name::<CpuEvaluationBackend>()], capabilities.evaluation_backends());
+    }
+}
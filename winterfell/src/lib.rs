@@ -263,7 +263,7 @@
 //!     matrix::ColMatrix,
 //!     CompositionPoly, CompositionPolyTrace, DefaultConstraintCommitment,
 //!     DefaultTraceLde, ProofOptions, Prover, StarkDomain, Trace,
-//!     TracePolyTable, TraceTable,
+//!     TraceLdeStorage, TracePolyTable, TraceTable,
 //! };
 //!
 //! # use winterfell::{
@@ -350,6 +350,8 @@
 //!     type Trace = TraceTable<Self::BaseField>;
 //!     type HashFn = Blake3_256<Self::BaseField>;
 //!     type VC = MerkleTree<Self::HashFn>;
+//!     type FriHashFn = Self::HashFn;
+//!     type FriVC = Self::VC;
 //!     type RandomCoin = DefaultRandomCoin<Self::HashFn>;
 //!     type TraceLde<E: FieldElement<BaseField = Self::BaseField>> = DefaultTraceLde<E, Self::HashFn, Self::VC>;
 //!     type ConstraintCommitment<E: FieldElement<BaseField = Self::BaseField>> =
@@ -376,8 +378,9 @@
 //!         main_trace: &ColMatrix<Self::BaseField>,
 //!         domain: &StarkDomain<Self::BaseField>,
 //!         partition_option: PartitionOptions,
+//!         storage: TraceLdeStorage,
 //!     ) -> (Self::TraceLde<E>, TracePolyTable<E>) {
-//!         DefaultTraceLde::new(trace_info, main_trace, domain, partition_option)
+//!         DefaultTraceLde::with_storage(trace_info, main_trace, domain, partition_option, storage)
 //!     }
 //!
 //!     fn build_constraint_commitment<E: FieldElement<BaseField = Self::BaseField>>(
@@ -421,7 +424,7 @@
 //! #    Air, AirContext, Assertion, AuxRandElements, ByteWriter, CompositionPoly, CompositionPolyTrace,
 //! #    DefaultConstraintEvaluator, DefaultConstraintCommitment, DefaultTraceLde, EvaluationFrame,
 //! #    TraceInfo, TransitionConstraintDegree, TraceTable, FieldExtension, PartitionOptions, Prover,
-//! #    ProofOptions, StarkDomain, Proof, Trace, TracePolyTable,
+//! #    ProofOptions, StarkDomain, Proof, Trace, TraceLdeStorage, TracePolyTable,
 //! # };
 //! #
 //! # pub fn build_do_work_trace(start: BaseElement, n: usize) -> TraceTable<BaseElement> {
@@ -512,6 +515,8 @@
 //! #    type Trace = TraceTable<Self::BaseField>;
 //! #    type HashFn = Blake3_256<Self::BaseField>;
 //! #    type VC = MerkleTree<Self::HashFn>;
+//! #    type FriHashFn = Self::HashFn;
+//! #    type FriVC = Self::VC;
 //! #    type RandomCoin = DefaultRandomCoin<Self::HashFn>;
 //! #    type TraceLde<E: FieldElement<BaseField = Self::BaseField>> = DefaultTraceLde<E, Self::HashFn, Self::VC>;
 //! #    type ConstraintCommitment<E: FieldElement<BaseField = Self::BaseField>> =
@@ -537,8 +542,9 @@
 //! #        main_trace: &ColMatrix<Self::BaseField>,
 //! #        domain: &StarkDomain<Self::BaseField>,
 //! #        partition_option: PartitionOptions,
+//! #        storage: TraceLdeStorage,
 //! #    ) -> (Self::TraceLde<E>, TracePolyTable<E>) {
-//! #        DefaultTraceLde::new(trace_info, main_trace, domain, partition_option)
+//! #        DefaultTraceLde::with_storage(trace_info, main_trace, domain, partition_option, storage)
 //! #    }
 //! #
 //! #    fn build_constraint_commitment<E: FieldElement<BaseField = Self::BaseField>>(
@@ -632,17 +638,37 @@
 #[cfg(test)]
 extern crate std;
 
-pub use air::{AuxRandElements, GkrVerifier, PartitionOptions};
+mod capabilities;
+pub use capabilities::Capabilities;
+
+pub use air::{
+    proof::{
+        BatchOpening, FriProofSizeEstimate, ProofContainer, ProofSizeEstimate, QueriesSizeEstimate,
+        QueryOpening, QueryOpeningError,
+    },
+    Accumulator, AccumulatorKind, AccumulatorTerm, AuxRandElements, GkrVerifier, LogUp,
+    OptionsExtension, PartitionOptions, Permutation, SoundnessMode, UnknownExtensionPolicy,
+};
+#[cfg(feature = "std")]
+pub use prover::DiskTraceLdeOptions;
 pub use prover::{
-    crypto, iterators, math, matrix, Air, AirContext, Assertion, AuxTraceWithMetadata,
-    BoundaryConstraint, BoundaryConstraintGroup, CompositionPoly, CompositionPolyTrace,
-    ConstraintCompositionCoefficients, ConstraintDivisor, ConstraintEvaluator,
+    crypto, fri, iterators, math, matrix, pad_periodic_column, Air, AirContext, Assertion,
+    AuxTraceWithMetadata, BatchAir, BatchPublicInputs, BoundaryConstraint, BoundaryConstraintGroup,
+    BufferPool, CoefficientDerivation, ComposedAir, ComposedPublicInputs, CompositionPoly,
+    CompositionPolyTrace, ConstraintCompositionCoefficients, ConstraintDivisor,
+    ConstraintEvaluationBackend, ConstraintEvaluator, ConstraintExpr, CpuEvaluationBackend,
     DeepCompositionCoefficients, DefaultConstraintCommitment, DefaultConstraintEvaluator,
-    DefaultTraceLde, EvaluationFrame, FieldExtension, Proof, ProofOptions, Prover, ProverError,
-    ProverGkrProof, StarkDomain, Trace, TraceInfo, TraceLde, TracePolyTable, TraceTable,
+    DefaultTraceLde, EvaluationFrame, ExprNode, FieldExtension, FrameBlock, LookupTable,
+    MemoryEstimate, NodeId, Program, Proof, ProofOptions, Prover, ProverContext, ProverError,
+    ProverGkrProof, ProvingPhase, PublicInputs, StarkDomain, SymbolicConstraints, SymbolicNode,
+    Trace, TraceBuilder, TraceInfo, TraceLde, TraceLdeStorage, TracePolyTable, TraceTable,
     TraceTableFragment, TransitionConstraintDegree,
 };
-pub use verifier::{verify, AcceptableOptions, ByteWriter, VerifierError};
+pub use verifier::{
+    verify, verify_program, verify_with_extension_policy, verify_with_fri_hasher,
+    verify_with_public_coin, verify_with_query_opening, AcceptableOptions, ByteWriter,
+    VerifierError, VerifierRegistry,
+};
 
 #[cfg(test)]
 mod tests;
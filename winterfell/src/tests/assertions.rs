@@ -0,0 +1,174 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::*;
+
+#[test]
+fn test_periodic_and_sequence_assertions() {
+    type Hasher = Blake3_256<BaseElement>;
+
+    // without zero-knowledge, periodic assertions are enforced on all asserted steps; the trace
+    // has 64 steps, and thus, sequence assertions with stride 16 can have up to 4 values
+    let options = ProofOptions::new(2, 8, 0, FieldExtension::Quadratic, 2, 1);
+    let prover = ResetCounterProver::<MerkleTree<Hasher>>::new(options);
+    prove_and_verify(&prover, prover.build_trace(64));
+
+    // in zero-knowledge mode, the asserted steps which fall on the random rows are exempted
+    let options = ProofOptions::new(2, 8, 0, FieldExtension::Quadratic, 2, 1).with_zk();
+    let air = ResetCounterAir::new(TraceInfo::new(2, 64), (), options.clone());
+    assert_eq!(57, air.last_step());
+
+    let prover = ResetCounterProver::<SaltedMerkleTree<Hasher>>::new(options);
+    prove_and_verify(&prover, prover.build_trace(64));
+
+    // constraints can be evaluated over a domain larger than the one implied by their degrees
+    let options = ProofOptions::new(2, 8, 0, FieldExtension::Quadratic, 2, 1);
+    let prover = ResetCounterProver::<MerkleTree<Hasher>>::new(options.clone());
+    let air = ResetCounterAir::new(TraceInfo::with_meta(2, 64, vec![4]), (), options);
+    assert_eq!(4, air.ce_blowup_factor());
+    assert_eq!(8, air.lde_blowup_factor());
+
+    let trace = prover.build_trace(64);
+    let mut trace_with_meta = TraceTable::with_meta(2, 64, vec![4]);
+    trace_with_meta.fill(
+        |state| trace.read_row_into(0, state),
+        |step, state| trace.read_row_into(step + 1, state),
+    );
+    prove_and_verify(&prover, trace_with_meta);
+}
+
+// ResetCounterAir
+// ================================================================================================
+
+/// Number of steps after which the accumulator in [ResetCounterAir] is reset to zero.
+const RESET_CYCLE_LEN: usize = 8;
+
+/// An AIR for an accumulator of step numbers which is reset to zero at the last step of every
+/// 8-step cycle; the step number is kept in the second column of the trace. The resets are
+/// enforced by a transition constraint together with a periodic assertion, and the step numbers
+/// are bound to the trace via a sequence assertion with three values (padded to four values
+/// internally).
+struct ResetCounterAir {
+    context: AirContext<BaseElement>,
+}
+
+impl Air for ResetCounterAir {
+    type BaseField = BaseElement;
+    type PublicInputs = ();
+    type GkrProof = ();
+    type GkrVerifier = ();
+
+    fn new(trace_info: TraceInfo, _pub_inputs: Self::PublicInputs, options: ProofOptions) -> Self {
+        let degrees = vec![
+            TransitionConstraintDegree::with_cycles(1, vec![RESET_CYCLE_LEN]),
+            TransitionConstraintDegree::new(1),
+        ];
+        // a constraint evaluation blowup factor can be specified via trace metadata
+        let ce_blowup_factor = trace_info.meta().first().copied();
+        let mut context = AirContext::new(trace_info, degrees, 2, options);
+        if let Some(ce_blowup_factor) = ce_blowup_factor {
+            context = context.set_ce_blowup_factor(ce_blowup_factor as usize);
+        }
+        Self { context }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        result[0] = next[0] - (current[0] + current[1]) * (E::ONE - periodic_values[0]);
+        result[1] = next[1] - current[1] - E::ONE;
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        vec![
+            Assertion::periodic(0, RESET_CYCLE_LEN - 1, RESET_CYCLE_LEN, BaseElement::ZERO),
+            Assertion::sequence(
+                1,
+                0,
+                16,
+                vec![0_u32, 16, 32].into_iter().map(BaseElement::from).collect(),
+            ),
+        ]
+    }
+
+    fn get_periodic_column_values(&self) -> Vec<Vec<Self::BaseField>> {
+        // the flag is set one step before the reset
+        let mut reset_flags = vec![BaseElement::ZERO; RESET_CYCLE_LEN];
+        reset_flags[RESET_CYCLE_LEN - 2] = BaseElement::ONE;
+        vec![reset_flags]
+    }
+}
+
+// ResetCounterProver
+// ================================================================================================
+
+struct ResetCounterProver<V> {
+    options: ProofOptions,
+    _vc: PhantomData<V>,
+}
+
+impl<V> ResetCounterProver<V> {
+    fn new(options: ProofOptions) -> Self {
+        Self { options, _vc: PhantomData }
+    }
+
+    fn build_trace(&self, trace_len: usize) -> TraceTable<BaseElement> {
+        let mut trace = TraceTable::new(2, trace_len);
+        trace.fill(
+            |state| {
+                state[0] = BaseElement::ONE;
+                state[1] = BaseElement::ZERO;
+            },
+            |step, state| {
+                state[0] = if step % RESET_CYCLE_LEN == RESET_CYCLE_LEN - 2 {
+                    BaseElement::ZERO
+                } else {
+                    state[0] + state[1]
+                };
+                state[1] += BaseElement::ONE;
+            },
+        );
+        trace
+    }
+}
+
+impl<V> Prover for ResetCounterProver<V>
+where
+    V: VectorCommitment<Blake3_256<BaseElement>> + Sync,
+{
+    type BaseField = BaseElement;
+    type Air = ResetCounterAir;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = Blake3_256<BaseElement>;
+    type VC = V;
+    type FriHashFn = Self::HashFn;
+    type FriVC = Self::VC;
+    type RandomCoin = DefaultRandomCoin<Self::HashFn>;
+    type TraceLde<E: FieldElement<BaseField = BaseElement>> =
+        DefaultTraceLde<E, Self::HashFn, Self::VC>;
+    type ConstraintCommitment<E: FieldElement<BaseField = Self::BaseField>> =
+        DefaultConstraintCommitment<E, Self::HashFn, Self::VC>;
+    type ConstraintEvaluator<'a, E: FieldElement<BaseField = BaseElement>> =
+        DefaultConstraintEvaluator<'a, ResetCounterAir, E>;
+
+    fn get_pub_inputs(&self, _trace: &Self::Trace) {}
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    default_trace_lde!();
+    default_constraint_commitment!();
+    default_evaluator!();
+}
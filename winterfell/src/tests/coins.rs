@@ -0,0 +1,97 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::*;
+
+#[test]
+fn test_transcript_coin() {
+    type Hasher = Blake3_256<BaseElement>;
+    type RandCoin = TranscriptCoin<Hasher>;
+
+    let program = Program::new(vec![[3_u32, 1, 4, 1, 5, 9, 2, 6].map(BaseElement::from).to_vec()]);
+    let prover = ProgramProver::<MerkleTree<Hasher>, RandCoin>::new(program);
+    let (proof, pub_inputs) = prove_and_verify(&prover, prover.build_trace());
+
+    // the proof does not verify if challenges are derived without domain separation
+    let result = verify::<ProgramAir, Hasher, DefaultRandomCoin<Hasher>, MerkleTree<Hasher>>(
+        proof,
+        pub_inputs,
+        &ACCEPTABLE_OPTIONS,
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_sponge_coin() {
+    type Hasher = Blake3_256<BaseElement>;
+    type RandCoin = SpongeCoin<Rp64_256>;
+
+    let program = Program::new(vec![[3_u32, 1, 4, 1, 5, 9, 2, 6].map(BaseElement::from).to_vec()]);
+    let prover = ProgramProver::<MerkleTree<Hasher>, RandCoin>::new(program);
+    let (proof, pub_inputs) = prove_and_verify(&prover, prover.build_trace());
+
+    // the proof does not verify if challenges are derived using a different coin
+    let result = verify::<ProgramAir, Hasher, DefaultRandomCoin<Rp64_256>, MerkleTree<Hasher>>(
+        proof,
+        pub_inputs,
+        &ACCEPTABLE_OPTIONS,
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_custom_public_coin() {
+    type Hasher = Blake3_256<BaseElement>;
+    type RandCoin = DefaultRandomCoin<Hasher>;
+    type VC = MerkleTree<Hasher>;
+
+    let options = ProofOptions::new(2, 8, 0, FieldExtension::None, 2, 1);
+    let beacon = BaseElement::new(42);
+
+    let prover = RoundConstantProver::new(options).with_beacon(beacon);
+    let (proof, result) = prove(&prover, prover.build_trace(64));
+
+    // the proof verifies only with a coin instantiated in the same way as by the prover
+    verify_with_public_coin::<RoundConstantAir, Hasher, RandCoin, VC>(
+        proof.clone(),
+        result,
+        &ACCEPTABLE_OPTIONS,
+        |seed| beacon_coin(seed, Some(beacon)),
+    )
+    .unwrap();
+
+    let result_default = verify_proof::<RoundConstantProver>(proof.clone(), result);
+    assert!(result_default.is_err());
+
+    let result_other_beacon = verify_with_public_coin::<RoundConstantAir, Hasher, RandCoin, VC>(
+        proof,
+        result,
+        &ACCEPTABLE_OPTIONS,
+        |seed| beacon_coin(seed, Some(beacon + BaseElement::ONE)),
+    );
+    assert!(result_other_beacon.is_err());
+
+    // proofs generated with the default coin verify via the default coin constructor
+    let prover = RoundConstantProver::new(ProofOptions::new(2, 8, 0, FieldExtension::None, 2, 1));
+    let proof = prover.prove(prover.build_trace(64)).unwrap();
+    verify_with_public_coin::<RoundConstantAir, Hasher, RandCoin, VC>(
+        proof,
+        result,
+        &ACCEPTABLE_OPTIONS,
+        RandCoin::new,
+    )
+    .unwrap();
+}
+
+/// Returns a public coin seeded with the specified seed followed by the value of an external
+/// randomness beacon (if any).
+pub(super) fn beacon_coin(
+    seed: &[BaseElement],
+    beacon: Option<BaseElement>,
+) -> DefaultRandomCoin<Blake3_256<BaseElement>> {
+    let mut seed = seed.to_vec();
+    seed.extend(beacon);
+    DefaultRandomCoin::new(&seed)
+}
@@ -0,0 +1,184 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::*;
+
+#[test]
+fn test_salted_commitments() {
+    type Hasher = Blake3_256<BaseElement>;
+    type RandCoin = DefaultRandomCoin<Hasher>;
+
+    let program = Program::new(vec![[3_u32, 1, 4, 1, 5, 9, 2, 6].map(BaseElement::from).to_vec()]);
+    let prover = ProgramProver::<SaltedMerkleTree<Hasher>>::new(program.clone());
+    let trace = prover.build_trace();
+
+    // a proof with salted commitments verifies against salted commitments
+    let (proof, pub_inputs) = prove_and_verify(&prover, trace.clone());
+
+    // salts are random, and thus, proofs for the same trace differ
+    let other_proof = prover.prove(trace).unwrap();
+    assert_ne!(proof.commitments, other_proof.commitments);
+
+    // a proof with salted commitments does not verify against unsalted commitments; salted
+    // openings parsed as Merkle proofs contain arbitrary lengths which must not be allocated for
+    let result = verify::<ProgramAir, Hasher, RandCoin, MerkleTree<Hasher>>(
+        proof,
+        pub_inputs,
+        &ACCEPTABLE_OPTIONS,
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_kary_commitments() {
+    type Hasher = Blake3_256<BaseElement>;
+    type RandCoin = DefaultRandomCoin<Hasher>;
+
+    let program = Program::new(vec![[3_u32, 1, 4, 1, 5, 9, 2, 6].map(BaseElement::from).to_vec()]);
+    let prover = ProgramProver::<KaryMerkleTree<Hasher, 4>>::new(program);
+    let (proof, pub_inputs) = prove_and_verify(&prover, prover.build_trace());
+
+    // the proof does not verify against commitments of a different arity
+    let result = verify::<ProgramAir, Hasher, RandCoin, KaryMerkleTree<Hasher, 8>>(
+        proof,
+        pub_inputs,
+        &ACCEPTABLE_OPTIONS,
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_disk_commitments() {
+    type Hasher = Blake3_256<BaseElement>;
+
+    let program = Program::new(vec![[3_u32, 1, 4, 1, 5, 9, 2, 6].map(BaseElement::from).to_vec()]);
+    let prover = ProgramProver::<DiskMerkleTree<Hasher>>::new(program.clone());
+    let trace = prover.build_trace();
+    let (proof, pub_inputs) = prove(&prover, trace.clone());
+
+    // trees stored on disk produce the same proofs as trees stored in memory
+    let expected = ProgramProver::<MerkleTree<Hasher>>::new(program).prove(trace).unwrap();
+    assert_eq!(expected, proof);
+
+    verify_proof::<ProgramProver<MerkleTree<Hasher>>>(proof, pub_inputs).unwrap();
+}
+
+#[test]
+fn test_layer_hashed_commitments() {
+    type Hasher = Blake3_256<BaseElement>;
+
+    /// Counts the number of layers hashed by all instances of this hasher.
+    #[derive(Default)]
+    struct CountingLayerHasher;
+
+    static NUM_HASHED_LAYERS: AtomicUsize = AtomicUsize::new(0);
+
+    impl MerkleLayerHasher<Hasher> for CountingLayerHasher {
+        fn hash_layer(&self, children: &[[ByteDigest<32>; 2]], parents: &mut [ByteDigest<32>]) {
+            NUM_HASHED_LAYERS.fetch_add(1, Ordering::Relaxed);
+            MerkleLayerHasher::<Hasher>::hash_layer(&CpuLayerHasher, children, parents);
+        }
+    }
+
+    // trees of the trace, the constraint evaluations, and the FRI layers are built by the layer
+    // hasher, and the proof is the same as the proof built with regular Merkle trees
+    let program = Program::new(vec![[3_u32, 1, 4, 1, 5, 9, 2, 6].map(BaseElement::from).to_vec()]);
+    let prover =
+        ProgramProver::<LayerHashedMerkleTree<Hasher, CountingLayerHasher>>::new(program.clone());
+    let trace = prover.build_trace();
+    let (proof, pub_inputs) = prove(&prover, trace.clone());
+    assert!(NUM_HASHED_LAYERS.load(Ordering::Relaxed) > 0);
+
+    let expected = ProgramProver::<MerkleTree<Hasher>>::new(program).prove(trace).unwrap();
+    assert_eq!(expected, proof);
+
+    verify_proof::<ProgramProver<MerkleTree<Hasher>>>(proof, pub_inputs).unwrap();
+}
+
+#[test]
+fn test_separate_transcript_hash() {
+    type Hasher = Blake3_256<BaseElement>;
+    type RandCoin = DefaultRandomCoin<Rp64_256>;
+
+    let program = Program::new(vec![[3_u32, 1, 4, 1, 5, 9, 2, 6].map(BaseElement::from).to_vec()]);
+    let prover = ProgramProver::<MerkleTree<Hasher>, RandCoin>::new(program);
+    let (proof, pub_inputs) = prove_and_verify(&prover, prover.build_trace());
+
+    // the proof does not verify if the transcript is built using a different hash function
+    let result = verify::<ProgramAir, Hasher, DefaultRandomCoin<Hasher>, MerkleTree<Hasher>>(
+        proof,
+        pub_inputs,
+        &ACCEPTABLE_OPTIONS,
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_separate_fri_hash() {
+    type Hasher = Blake3_256<BaseElement>;
+    type FriHasher = Rp64_256;
+    type RandCoin = DefaultRandomCoin<Rp64_256>;
+
+    let program = Program::new(vec![[3_u32, 1, 4, 1, 5, 9, 2, 6].map(BaseElement::from).to_vec()]);
+    let prover =
+        ProgramProver::<MerkleTree<Hasher>, RandCoin, FriHasher, MerkleTree<FriHasher>>::new(
+            program.clone(),
+        );
+    let trace = prover.build_trace();
+    let (proof, pub_inputs) = prove(&prover, trace.clone());
+
+    verify_with_fri_hasher::<
+        ProgramAir,
+        Hasher,
+        FriHasher,
+        RandCoin,
+        MerkleTree<Hasher>,
+        MerkleTree<FriHasher>,
+    >(proof.clone(), pub_inputs.clone(), &ACCEPTABLE_OPTIONS)
+    .unwrap();
+
+    // the proof does not verify if FRI layer commitments are checked using the trace hash
+    let result = verify::<ProgramAir, Hasher, RandCoin, MerkleTree<Hasher>>(
+        proof,
+        pub_inputs,
+        &ACCEPTABLE_OPTIONS,
+    );
+    assert!(result.is_err());
+
+    // the FRI hash function must produce digests of the size specified by proof options
+    let mut prover =
+        ProgramProver::<MerkleTree<Hasher>, RandCoin, FriHasher, MerkleTree<FriHasher>>::new(
+            program,
+        );
+    prover.options = prover.options.with_fri_digest_size(20);
+    assert_eq!(Err(ProverError::InconsistentFriDigestSize(20, 32)), prover.prove(trace));
+}
+
+#[test]
+fn test_query_opening() {
+    type Hasher = Blake3_256<BaseElement>;
+    type RandCoin = DefaultRandomCoin<Hasher>;
+    type ProverWithOpening =
+        ProgramProver<MerkleTree<Hasher>, RandCoin, Hasher, MerkleTree<Hasher>, IndividualOpening>;
+
+    let program = Program::new(vec![[3_u32, 1, 4, 1, 5, 9, 2, 6].map(BaseElement::from).to_vec()]);
+    let prover = ProverWithOpening::new(program);
+    let (proof, pub_inputs) = prove(&prover, prover.build_trace());
+
+    verify_with_query_opening::<ProgramAir, Hasher, RandCoin, MerkleTree<Hasher>, IndividualOpening>(
+        proof.clone(),
+        pub_inputs.clone(),
+        &ACCEPTABLE_OPTIONS,
+    )
+    .unwrap();
+
+    // the proof does not verify if queries are expected to be opened via batch opening proofs
+    let result = verify::<ProgramAir, Hasher, RandCoin, MerkleTree<Hasher>>(
+        proof,
+        pub_inputs,
+        &ACCEPTABLE_OPTIONS,
+    );
+    assert!(matches!(result, Err(VerifierError::ProofDeserializationError(_))));
+}
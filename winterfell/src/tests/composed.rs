@@ -0,0 +1,91 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::*;
+
+#[test]
+fn test_composed_air() {
+    type Air = ComposedAir<RoundConstantAir, SparseSquareAir>;
+
+    let options = ProofOptions::new(2, 8, 0, FieldExtension::None, 2, 1);
+
+    let prover = ComposedProver::new(options.clone());
+    let trace = prover.build_trace(64);
+    assert_eq!(5, trace.width());
+    let pub_inputs = prover.get_pub_inputs(&trace);
+
+    let air = Air::new(trace.info().clone(), pub_inputs.clone(), options);
+    assert_eq!(2, air.first().trace_info().main_trace_width());
+    assert_eq!(3, air.second().trace_info().main_trace_width());
+    assert_eq!(5, air.context().num_main_transition_constraints());
+    assert_eq!(6, air.get_assertions().len());
+    assert_eq!(2, air.get_assertions()[3].column());
+    assert_eq!(1, air.get_periodic_column_values().len());
+
+    let (proof, _) = prove_and_verify(&prover, trace);
+
+    // the proof does not verify if public inputs of any component are different
+    let tampered =
+        ComposedPublicInputs::new(*pub_inputs.first() + BaseElement::ONE, *pub_inputs.second());
+    let result = verify_proof::<ComposedProver>(proof.clone(), tampered);
+    assert!(result.is_err());
+
+    let tampered =
+        ComposedPublicInputs::new(*pub_inputs.first(), *pub_inputs.second() + BaseElement::ONE);
+    let result = verify_proof::<ComposedProver>(proof, tampered);
+    assert!(result.is_err());
+}
+
+// ComposedProver
+// ================================================================================================
+
+/// A prover for a composition of [RoundConstantAir] and [SparseSquareAir] executed side by side.
+struct ComposedProver {
+    options: ProofOptions,
+}
+
+impl ComposedProver {
+    fn new(options: ProofOptions) -> Self {
+        Self { options }
+    }
+
+    fn build_trace(&self, trace_len: usize) -> TraceTable<BaseElement> {
+        let first = RoundConstantProver::new(self.options.clone()).build_trace(trace_len);
+        let second = SparseSquareProver::new(self.options.clone()).build_trace(trace_len);
+        TraceTable::compose(&first, &second)
+    }
+}
+
+impl Prover for ComposedProver {
+    type BaseField = BaseElement;
+    type Air = ComposedAir<RoundConstantAir, SparseSquareAir>;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = Blake3_256<BaseElement>;
+    type VC = MerkleTree<Self::HashFn>;
+    type FriHashFn = Self::HashFn;
+    type FriVC = Self::VC;
+    type RandomCoin = DefaultRandomCoin<Self::HashFn>;
+    type TraceLde<E: FieldElement<BaseField = BaseElement>> =
+        DefaultTraceLde<E, Self::HashFn, Self::VC>;
+    type ConstraintCommitment<E: FieldElement<BaseField = Self::BaseField>> =
+        DefaultConstraintCommitment<E, Self::HashFn, Self::VC>;
+    type ConstraintEvaluator<'a, E: FieldElement<BaseField = BaseElement>> =
+        DefaultConstraintEvaluator<'a, Self::Air, E>;
+
+    fn get_pub_inputs(
+        &self,
+        trace: &Self::Trace,
+    ) -> ComposedPublicInputs<BaseElement, BaseElement> {
+        ComposedPublicInputs::new(trace.get(0, trace.length() - 1), trace.get(2, 0))
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    default_trace_lde!();
+    default_constraint_commitment!();
+    default_evaluator!();
+}
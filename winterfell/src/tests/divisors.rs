@@ -0,0 +1,149 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::*;
+
+#[test]
+fn test_custom_transition_divisors() {
+    let options = ProofOptions::new(2, 8, 0, FieldExtension::None, 2, 1);
+    let prover = SparseSquareProver::new(options);
+    prove_and_verify(&prover, prover.build_trace(64));
+}
+
+// SparseSquareAir
+// ================================================================================================
+
+/// Number of steps between consecutive squarings in the first column of [SparseSquareAir].
+const SQUARING_STRIDE: usize = 8;
+
+/// Step after which the counter in the third column of [SparseSquareAir] is reset to 5.
+const RESET_STEP: usize = 31;
+
+/// An AIR for a computation which squares the value in the first column only on every 8th step
+/// (the value may change arbitrarily on other steps), keeps a step counter in the second column,
+/// and keeps a counter which is reset in the middle of the trace in the third column.
+pub(super) struct SparseSquareAir {
+    context: AirContext<BaseElement>,
+    start: BaseElement,
+}
+
+impl Air for SparseSquareAir {
+    type BaseField = BaseElement;
+    type PublicInputs = BaseElement;
+    type GkrProof = ();
+    type GkrVerifier = ();
+
+    fn new(trace_info: TraceInfo, pub_inputs: Self::PublicInputs, options: ProofOptions) -> Self {
+        let trace_length = trace_info.length();
+        let degrees = vec![
+            TransitionConstraintDegree::new(2),
+            TransitionConstraintDegree::new(1),
+            TransitionConstraintDegree::new(1),
+        ];
+        let context = AirContext::new(trace_info, degrees, 3, options)
+            .set_transition_period(0, 0, SQUARING_STRIDE)
+            .set_transition_divisor(
+                2,
+                ConstraintDivisor::from_transition_with_exemptions(
+                    trace_length,
+                    &[RESET_STEP, trace_length - 1],
+                ),
+            );
+        Self { context, start: pub_inputs }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        result[0] = next[0] - current[0] * current[0];
+        result[1] = next[1] - current[1] - E::ONE;
+        result[2] = next[2] - current[2] - E::ONE;
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        vec![
+            Assertion::single(0, 0, self.start),
+            Assertion::single(1, 0, BaseElement::ZERO),
+            Assertion::single(2, 0, BaseElement::ZERO),
+        ]
+    }
+}
+
+// SparseSquareProver
+// ================================================================================================
+
+pub(super) struct SparseSquareProver {
+    options: ProofOptions,
+}
+
+impl SparseSquareProver {
+    pub(super) fn new(options: ProofOptions) -> Self {
+        Self { options }
+    }
+
+    pub(super) fn build_trace(&self, trace_len: usize) -> TraceTable<BaseElement> {
+        let mut trace = TraceTable::new(3, trace_len);
+        trace.fill(
+            |state| {
+                state[0] = BaseElement::new(3);
+                state[1] = BaseElement::ZERO;
+                state[2] = BaseElement::ZERO;
+            },
+            |step, state| {
+                // off the squaring steps, the first column is not constrained
+                if step % SQUARING_STRIDE == 0 {
+                    state[0] = state[0].square();
+                } else {
+                    state[0] += BaseElement::new(step as u64);
+                }
+                state[1] += BaseElement::ONE;
+                state[2] = if step == RESET_STEP {
+                    BaseElement::new(5)
+                } else {
+                    state[2] + BaseElement::ONE
+                };
+            },
+        );
+        trace
+    }
+}
+
+impl Prover for SparseSquareProver {
+    type BaseField = BaseElement;
+    type Air = SparseSquareAir;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = Blake3_256<BaseElement>;
+    type VC = MerkleTree<Self::HashFn>;
+    type FriHashFn = Self::HashFn;
+    type FriVC = Self::VC;
+    type RandomCoin = DefaultRandomCoin<Self::HashFn>;
+    type TraceLde<E: FieldElement<BaseField = BaseElement>> =
+        DefaultTraceLde<E, Self::HashFn, Self::VC>;
+    type ConstraintCommitment<E: FieldElement<BaseField = Self::BaseField>> =
+        DefaultConstraintCommitment<E, Self::HashFn, Self::VC>;
+    type ConstraintEvaluator<'a, E: FieldElement<BaseField = BaseElement>> =
+        DefaultConstraintEvaluator<'a, SparseSquareAir, E>;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> BaseElement {
+        trace.get(0, 0)
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    default_trace_lde!();
+    default_constraint_commitment!();
+    default_evaluator!();
+}
@@ -0,0 +1,221 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::*;
+
+#[test]
+fn test_multi_row_frames() {
+    type Hasher = Blake3_256<BaseElement>;
+
+    let options = ProofOptions::new(2, 8, 0, FieldExtension::Quadratic, 2, 1);
+    let air = FibFrameAir::<2>::new(TraceInfo::new(1, 64), BaseElement::ONE, options.clone());
+    assert_eq!(3, air.context().num_frame_rows());
+    assert_eq!(2, air.context().num_transition_exemptions());
+
+    let prover = FibFrameProver::<MerkleTree<Hasher>, 2>::new(options);
+    let trace = FibFrameTrace::new(64, air.last_step() + 1, 2);
+    let (proof, result) = prove_and_verify(&prover, trace);
+
+    // the proof does not verify against a different result
+    let result =
+        verify_proof::<FibFrameProver<MerkleTree<Hasher>, 2>>(proof, result + BaseElement::ONE);
+    assert!(result.is_err());
+
+    // wider frames also require more random rows in zero-knowledge mode
+    let options = ProofOptions::new(2, 8, 0, FieldExtension::Quadratic, 2, 1).with_zk();
+    let air = FibFrameAir::<2>::new(TraceInfo::new(1, 64), BaseElement::ONE, options.clone());
+    assert_eq!(9, air.context().num_random_rows());
+
+    let prover = FibFrameProver::<SaltedMerkleTree<Hasher>, 2>::new(options);
+    prove_and_verify(&prover, FibFrameTrace::new(64, air.last_step() + 1, 2));
+}
+
+#[test]
+fn test_frame_offsets() {
+    type Hasher = Blake3_256<BaseElement>;
+    type RandCoin = DefaultRandomCoin<Hasher>;
+    type VC = MerkleTree<Hasher>;
+
+    // frames contain rows i, i + 1, and i + 8
+    let options = ProofOptions::new(2, 8, 0, FieldExtension::Quadratic, 2, 1);
+    let air = FibFrameAir::<8>::new(TraceInfo::new(1, 64), BaseElement::ONE, options.clone());
+    assert_eq!(&[0, 1, 8], air.context().frame_offsets());
+    assert_eq!(8, air.context().num_transition_exemptions());
+
+    let prover = FibFrameProver::<VC, 8>::new(options);
+    let trace = FibFrameTrace::new(64, air.last_step() + 1, 8);
+    let (proof, result) = prove_and_verify(&prover, trace);
+
+    // the proof does not verify against an AIR with different frame offsets
+    let result = verify::<FibFrameAir<4>, Hasher, RandCoin, VC>(proof, result, &ACCEPTABLE_OPTIONS);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_transition_constraint_diagnostics() {
+    let options = ProofOptions::new(2, 8, 0, FieldExtension::None, 2, 1);
+    let mut trace = FibFrameTrace::new(64, 64, 8);
+    let air = FibFrameAir::<8>::new(TraceInfo::new(1, 64), trace.result(), options);
+
+    // corrupt row 20 of the trace; the first transition reading this row is the one at step 12,
+    // for which the row is at offset 8 of the frame
+    let value = trace.main_trace.get(0, 20) + BaseElement::ONE;
+    trace.main_trace.set(0, 20, value);
+
+    let panic = std::panic::catch_unwind(|| trace.validate::<_, BaseElement>(&air, None))
+        .expect_err("trace validation should fail");
+    let message = panic.downcast_ref::<String>().expect("panic message should be a string");
+
+    assert!(message.contains("main transition constraint 0 did not evaluate to ZERO at step 12"));
+    assert!(message.contains("evaluation: 1\n"));
+    assert!(message.contains(&format!("row 12 (offset 0): [{}]", trace.main_trace.get(0, 12))));
+    assert!(message.contains(&format!("row 13 (offset 1): [{}]", trace.main_trace.get(0, 13))));
+    assert!(message.contains(&format!("row 20 (offset 8): [{value}]")));
+    assert!(message.contains("periodic values: []"));
+}
+
+// FibFrameTrace
+// ================================================================================================
+
+/// A trace of a single column containing a lagged Fibonacci sequence x_{i + lag} = x_i + x_{i + 1};
+/// each transition relates rows i, i + 1, and i + lag of the column.
+#[derive(Clone, Debug)]
+struct FibFrameTrace {
+    main_trace: ColMatrix<BaseElement>,
+    info: TraceInfo,
+    num_steps: usize,
+}
+
+impl FibFrameTrace {
+    /// Builds a trace of `trace_len` rows for a computation of `num_steps` steps; the rows
+    /// beyond the last step of the computation continue the sequence.
+    fn new(trace_len: usize, num_steps: usize, lag: usize) -> Self {
+        let mut column = vec![BaseElement::ONE; lag];
+        for i in lag..trace_len {
+            column.push(column[i - lag] + column[i - lag + 1]);
+        }
+
+        Self {
+            main_trace: ColMatrix::new(vec![column]),
+            info: TraceInfo::new(1, trace_len),
+            num_steps,
+        }
+    }
+
+    /// Returns the value at the last step of the computation.
+    fn result(&self) -> BaseElement {
+        self.main_trace.get(0, self.num_steps - 1)
+    }
+}
+
+impl Trace for FibFrameTrace {
+    type BaseField = BaseElement;
+
+    fn info(&self) -> &TraceInfo {
+        &self.info
+    }
+
+    fn main_segment(&self) -> Cow<'_, ColMatrix<Self::BaseField>> {
+        Cow::Borrowed(&self.main_trace)
+    }
+
+    fn read_main_frame(&self, row_idx: usize, frame: &mut EvaluationFrame<Self::BaseField>) {
+        for i in 0..frame.num_rows() {
+            let row_idx = (row_idx + frame.offsets()[i]) % self.main_trace.num_rows();
+            self.main_trace.read_row_into(row_idx, frame.row_mut(i));
+        }
+    }
+}
+
+// FibFrameAir
+// ================================================================================================
+
+struct FibFrameAir<const LAG: usize> {
+    context: AirContext<BaseElement>,
+    result: BaseElement,
+}
+
+impl<const LAG: usize> Air for FibFrameAir<LAG> {
+    type BaseField = BaseElement;
+    type PublicInputs = BaseElement;
+    type GkrProof = ();
+    type GkrVerifier = ();
+
+    fn new(trace_info: TraceInfo, pub_inputs: Self::PublicInputs, options: ProofOptions) -> Self {
+        let degrees = vec![TransitionConstraintDegree::new(1)];
+        let context = AirContext::new(trace_info, degrees, LAG + 1, options);
+        Self {
+            context: context.set_frame_offsets(vec![0, 1, LAG]),
+            result: pub_inputs,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        // the row at offset LAG is the third row of the frame
+        result[0] = frame.row(2)[0] - frame.next()[0] - frame.current()[0];
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        let mut assertions: Vec<_> =
+            (0..LAG).map(|step| Assertion::single(0, step, BaseElement::ONE)).collect();
+        assertions.push(Assertion::single(0, self.last_step(), self.result));
+        assertions
+    }
+}
+
+// FibFrameProver
+// ================================================================================================
+
+struct FibFrameProver<V, const LAG: usize> {
+    options: ProofOptions,
+    _vc: PhantomData<V>,
+}
+
+impl<V, const LAG: usize> FibFrameProver<V, LAG> {
+    fn new(options: ProofOptions) -> Self {
+        Self { options, _vc: PhantomData }
+    }
+}
+
+impl<V, const LAG: usize> Prover for FibFrameProver<V, LAG>
+where
+    V: VectorCommitment<Blake3_256<BaseElement>> + Sync,
+{
+    type BaseField = BaseElement;
+    type Air = FibFrameAir<LAG>;
+    type Trace = FibFrameTrace;
+    type HashFn = Blake3_256<BaseElement>;
+    type VC = V;
+    type FriHashFn = Self::HashFn;
+    type FriVC = Self::VC;
+    type RandomCoin = DefaultRandomCoin<Self::HashFn>;
+    type TraceLde<E: FieldElement<BaseField = BaseElement>> =
+        DefaultTraceLde<E, Self::HashFn, Self::VC>;
+    type ConstraintCommitment<E: FieldElement<BaseField = Self::BaseField>> =
+        DefaultConstraintCommitment<E, Self::HashFn, Self::VC>;
+    type ConstraintEvaluator<'a, E: FieldElement<BaseField = BaseElement>> =
+        DefaultConstraintEvaluator<'a, FibFrameAir<LAG>, E>;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> BaseElement {
+        trace.result()
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    default_trace_lde!();
+    default_constraint_commitment!();
+    default_evaluator!();
+}
@@ -3,20 +3,9 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use std::{vec, vec::Vec};
-
-use air::{GkrRandElements, LagrangeKernelRandElements};
-use crypto::MerkleTree;
-use prover::{
-    crypto::{hashers::Blake3_256, DefaultRandomCoin, RandomCoin},
-    math::{fields::f64::BaseElement, ExtensionOf, FieldElement},
-    matrix::ColMatrix,
-    CompositionPoly, DefaultConstraintCommitment,
-};
-
 use super::*;
 
-const AUX_TRACE_WIDTH: usize = 2;
+pub(super) const AUX_TRACE_WIDTH: usize = 2;
 
 #[test]
 fn test_complex_lagrange_kernel_air() {
@@ -31,7 +20,7 @@ fn test_complex_lagrange_kernel_air() {
         Blake3_256<BaseElement>,
         DefaultRandomCoin<Blake3_256<BaseElement>>,
         MerkleTree<Blake3_256<BaseElement>>,
-    >(proof, (), &AcceptableOptions::MinConjecturedSecurity(0))
+    >(proof, (), &ACCEPTABLE_OPTIONS)
     .unwrap()
 }
 
@@ -39,14 +28,14 @@ fn test_complex_lagrange_kernel_air() {
 // =================================================================================================
 
 #[derive(Clone, Debug)]
-struct LagrangeComplexTrace {
+pub(super) struct LagrangeComplexTrace {
     // dummy main trace
     main_trace: ColMatrix<BaseElement>,
     info: TraceInfo,
 }
 
 impl LagrangeComplexTrace {
-    fn new(trace_len: usize, aux_segment_width: usize) -> Self {
+    pub(super) fn new(trace_len: usize, aux_segment_width: usize) -> Self {
         assert!(trace_len < u32::MAX.try_into().unwrap());
 
         let main_trace_col: Vec<BaseElement> =
@@ -58,7 +47,7 @@ impl LagrangeComplexTrace {
         }
     }
 
-    fn len(&self) -> usize {
+    pub(super) fn len(&self) -> usize {
         self.main_trace.num_rows()
     }
 }
@@ -70,8 +59,8 @@ impl Trace for LagrangeComplexTrace {
         &self.info
     }
 
-    fn main_segment(&self) -> &ColMatrix<Self::BaseField> {
-        &self.main_trace
+    fn main_segment(&self) -> Cow<'_, ColMatrix<Self::BaseField>> {
+        Cow::Borrowed(&self.main_trace)
     }
 
     fn read_main_frame(&self, row_idx: usize, frame: &mut EvaluationFrame<Self::BaseField>) {
@@ -87,7 +76,7 @@ impl Trace for LagrangeComplexTrace {
 // =================================================================================================
 
 #[derive(Debug, Clone, Default)]
-struct DummyGkrVerifier;
+pub(super) struct DummyGkrVerifier;
 
 impl GkrVerifier for DummyGkrVerifier {
     // `GkrProof` is log(trace_len) for this dummy example, so that the verifier knows how many aux
@@ -118,7 +107,7 @@ impl GkrVerifier for DummyGkrVerifier {
     }
 }
 
-struct LagrangeKernelComplexAir {
+pub(super) struct LagrangeKernelComplexAir {
     context: AirContext<BaseElement>,
 }
 
@@ -197,13 +186,13 @@ impl Air for LagrangeKernelComplexAir {
 // LagrangeComplexProver
 // ================================================================================================
 
-struct LagrangeComplexProver {
+pub(super) struct LagrangeComplexProver {
     aux_trace_width: usize,
     options: ProofOptions,
 }
 
 impl LagrangeComplexProver {
-    fn new(aux_trace_width: usize) -> Self {
+    pub(super) fn new(aux_trace_width: usize) -> Self {
         Self {
             aux_trace_width,
             options: ProofOptions::new(1, 2, 0, FieldExtension::None, 2, 1),
@@ -217,6 +206,8 @@ impl Prover for LagrangeComplexProver {
     type Trace = LagrangeComplexTrace;
     type HashFn = Blake3_256<BaseElement>;
     type VC = MerkleTree<Blake3_256<BaseElement>>;
+    type FriHashFn = Self::HashFn;
+    type FriVC = Self::VC;
     type RandomCoin = DefaultRandomCoin<Self::HashFn>;
     type TraceLde<E: FieldElement<BaseField = BaseElement>> =
         DefaultTraceLde<E, Self::HashFn, Self::VC>;
@@ -232,45 +223,9 @@ impl Prover for LagrangeComplexProver {
         &self.options
     }
 
-    fn new_trace_lde<E>(
-        &self,
-        trace_info: &TraceInfo,
-        main_trace: &ColMatrix<Self::BaseField>,
-        domain: &StarkDomain<Self::BaseField>,
-        partition_option: PartitionOptions,
-    ) -> (Self::TraceLde<E>, TracePolyTable<E>)
-    where
-        E: math::FieldElement<BaseField = Self::BaseField>,
-    {
-        DefaultTraceLde::new(trace_info, main_trace, domain, partition_option)
-    }
-
-    fn build_constraint_commitment<E: FieldElement<BaseField = Self::BaseField>>(
-        &self,
-        composition_poly_trace: CompositionPolyTrace<E>,
-        num_constraint_composition_columns: usize,
-        domain: &StarkDomain<Self::BaseField>,
-        partition_options: PartitionOptions,
-    ) -> (Self::ConstraintCommitment<E>, CompositionPoly<E>) {
-        DefaultConstraintCommitment::new(
-            composition_poly_trace,
-            num_constraint_composition_columns,
-            domain,
-            partition_options,
-        )
-    }
-
-    fn new_evaluator<'a, E>(
-        &self,
-        air: &'a Self::Air,
-        aux_rand_elements: Option<AuxRandElements<E>>,
-        composition_coefficients: ConstraintCompositionCoefficients<E>,
-    ) -> Self::ConstraintEvaluator<'a, E>
-    where
-        E: math::FieldElement<BaseField = Self::BaseField>,
-    {
-        DefaultConstraintEvaluator::new(air, aux_rand_elements, composition_coefficients)
-    }
+    default_trace_lde!();
+    default_constraint_commitment!();
+    default_evaluator!();
 
     fn generate_gkr_proof<E>(
         &self,
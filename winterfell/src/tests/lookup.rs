@@ -0,0 +1,346 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::*;
+
+#[test]
+fn test_lookup_tables() {
+    type Hasher = Blake3_256<BaseElement>;
+    type VC = MerkleTree<Hasher>;
+
+    let options = ProofOptions::new(1, 4, 0, FieldExtension::Quadratic, 2, 1);
+    let prover = LookupProver::<VC>::new(options);
+    let (proof, _) = prove(&prover, LookupTrace::new(64, 64));
+
+    // the proof carries a commitment to the lookup table of the AIR
+    let table_commitment = LookupTable::commitment::<Hasher>(&[lookup_table()]).to_bytes();
+    assert_eq!(Some(table_commitment.as_slice()), proof.context.lookup_table_commitment());
+    verify_proof::<LookupProver<VC>>(proof.clone(), ()).unwrap();
+
+    // the verifier rejects proofs generated against a different table
+    let mut other_proof = proof.clone();
+    other_proof.context = proof.context.clone().with_lookup_table_commitment(Some(vec![0; 32]));
+    let result = verify_proof::<LookupProver<VC>>(other_proof, ());
+    assert_eq!(Err(VerifierError::InconsistentLookupTables), result);
+
+    // as well as proofs which do not commit to any table
+    let mut other_proof = proof;
+    other_proof.context = other_proof.context.with_lookup_table_commitment(None);
+    let result = verify_proof::<LookupProver<VC>>(other_proof, ());
+    assert_eq!(Err(VerifierError::InconsistentLookupTables), result);
+}
+
+#[test]
+fn test_low_memory_trace_lde() {
+    type VC = MerkleTree<Blake3_256<BaseElement>>;
+
+    let options = ProofOptions::new(1, 4, 0, FieldExtension::Quadratic, 2, 1);
+    let expected = LookupProver::<VC>::new(options.clone())
+        .prove(LookupTrace::new(64, 64))
+        .unwrap();
+
+    // rows of the main and auxiliary segments recomputed from trace polynomials at query positions
+    // are the same as rows read from the extended trace
+    let mut prover = LookupProver::<VC>::new(options);
+    prover.low_memory = true;
+    let (proof, _) = prove_and_verify(&prover, LookupTrace::new(64, 64));
+    assert_eq!(expected, proof);
+}
+
+#[test]
+fn test_disk_trace_lde() {
+    type VC = MerkleTree<Blake3_256<BaseElement>>;
+
+    let options = ProofOptions::new(1, 4, 0, FieldExtension::Quadratic, 2, 1);
+    let expected = LookupProver::<VC>::new(options.clone())
+        .prove(LookupTrace::new(64, 64))
+        .unwrap();
+
+    // rows of the main and auxiliary segments read from files are the same as rows read from
+    // the extended trace kept in memory
+    let mut prover = LookupProver::<VC>::new(options);
+    prover.on_disk = true;
+    let (proof, _) = prove_and_verify(&prover, LookupTrace::new(64, 64));
+    assert_eq!(expected, proof);
+}
+
+#[test]
+fn test_zk_proofs() {
+    type Hasher = Blake3_256<BaseElement>;
+    type VC = SaltedMerkleTree<Hasher>;
+
+    let options = ProofOptions::new(1, 4, 0, FieldExtension::Quadratic, 2, 1).with_zk();
+    let trace_info = TraceInfo::new_multi_segment(3, 1, 1, 64, vec![]);
+    let air = LookupAir::new(trace_info, (), options.clone());
+    assert_eq!(4, air.context().num_random_rows());
+    assert_eq!(59, air.last_step());
+
+    // the computation occupies the rows up to the last step, and the remaining rows are
+    // replaced with random values by the prover
+    let prover = LookupProver::<VC>::new(options.clone());
+    let (proof, _) = prove_and_verify(&prover, LookupTrace::new(64, air.last_step() + 1));
+    assert!(proof.options().is_zk());
+
+    // proofs cannot be generated in zero-knowledge mode with a non-hiding vector commitment
+    let prover = LookupProver::<MerkleTree<Hasher>>::new(options.clone());
+    let result = prover.prove(LookupTrace::new(64, air.last_step() + 1));
+    assert_eq!(Err(ProverError::NonHidingVectorCommitment), result.map(|_| ()));
+
+    // zero-knowledge proofs and hiding commitments are randomized, and thus, cannot be canonical
+    let prover = LookupProver::<VC>::new(options.with_canonical_proving(true));
+    let result = prover.prove(LookupTrace::new(64, air.last_step() + 1));
+    assert_eq!(Err(ProverError::RandomizedCanonicalProof), result.map(|_| ()));
+
+    let options = ProofOptions::new(1, 4, 0, FieldExtension::Quadratic, 2, 1);
+    let prover = LookupProver::<VC>::new(options.with_canonical_proving(true));
+    let result = prover.prove(LookupTrace::new(64, 64));
+    assert_eq!(Err(ProverError::RandomizedCanonicalProof), result.map(|_| ()));
+}
+
+// LookupTrace
+// ================================================================================================
+
+/// Returns a table of values 0..8.
+fn lookup_table() -> LookupTable<BaseElement> {
+    LookupTable::new(vec![(0..8_u32).map(BaseElement::from).collect()])
+}
+
+/// Returns a LogUp argument which looks up values of column 0 in the table copied into column 1,
+/// with multiplicities of table entries in column 2.
+fn lookup_argument() -> LogUp<BaseElement> {
+    LogUp::new(0)
+        .with_lookup(vec![AccumulatorTerm::column(0)])
+        .with_table(vec![AccumulatorTerm::column(1)], AccumulatorTerm::column(2))
+}
+
+#[derive(Clone, Debug)]
+struct LookupTrace {
+    main_trace: ColMatrix<BaseElement>,
+    info: TraceInfo,
+}
+
+impl LookupTrace {
+    /// Builds a trace of `trace_len` rows for a computation of `num_steps` steps; the rows
+    /// beyond the last step of the computation are padded with zeros.
+    fn new(trace_len: usize, num_steps: usize) -> Self {
+        let table = lookup_table();
+
+        // the last step is not looked up by the LogUp argument
+        let lookups: Vec<[BaseElement; 1]> = (0..num_steps as u32 - 1)
+            .map(|i| [BaseElement::from((i * i + 1) % 8)])
+            .collect();
+        let multiplicities = table.get_multiplicities(trace_len, lookups.iter().map(|v| &v[..]));
+
+        let mut lookup_col: Vec<BaseElement> = lookups.iter().map(|v| v[0]).collect();
+        lookup_col.resize(trace_len, BaseElement::ZERO);
+        let table_col = (0..trace_len).map(|i| table.columns()[0][i % table.length()]).collect();
+
+        Self {
+            main_trace: ColMatrix::new(vec![lookup_col, table_col, multiplicities]),
+            info: TraceInfo::new_multi_segment(3, 1, 1, trace_len, vec![]),
+        }
+    }
+}
+
+impl Trace for LookupTrace {
+    type BaseField = BaseElement;
+
+    fn info(&self) -> &TraceInfo {
+        &self.info
+    }
+
+    fn main_segment(&self) -> Cow<'_, ColMatrix<Self::BaseField>> {
+        Cow::Borrowed(&self.main_trace)
+    }
+
+    fn read_main_frame(&self, row_idx: usize, frame: &mut EvaluationFrame<Self::BaseField>) {
+        let next_row_idx = (row_idx + 1) % self.main_trace.num_rows();
+        self.main_trace.read_row_into(row_idx, frame.current_mut());
+        self.main_trace.read_row_into(next_row_idx, frame.next_mut());
+    }
+}
+
+// LookupAir
+// ================================================================================================
+
+struct LookupAir {
+    context: AirContext<BaseElement>,
+    tables: Vec<LookupTable<BaseElement>>,
+    logup: LogUp<BaseElement>,
+}
+
+impl Air for LookupAir {
+    type BaseField = BaseElement;
+    type PublicInputs = ();
+    type GkrProof = ();
+    type GkrVerifier = ();
+
+    fn new(trace_info: TraceInfo, _pub_inputs: Self::PublicInputs, options: ProofOptions) -> Self {
+        // the LogUp constraint multiplies the lookup column, the table column, and the running
+        // sum column; the table column repeats every 8 steps, and thus, has a lower degree unless
+        // random rows are appended to it in zero-knowledge mode
+        let table = lookup_table();
+        let logup = lookup_argument();
+        let aux_degree = if options.is_zk() {
+            logup.transition_degree()
+        } else {
+            TransitionConstraintDegree::with_cycles(2, vec![table.length()])
+        };
+        Self {
+            context: AirContext::new_multi_segment(
+                trace_info,
+                vec![TransitionConstraintDegree::new(1)],
+                vec![aux_degree],
+                1,
+                2,
+                None,
+                options,
+            ),
+            tables: vec![table],
+            logup,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        // column 1 contains the lookup table
+        result[0] = frame.current()[1] - periodic_values[0];
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        vec![Assertion::single(1, 0, BaseElement::ZERO)]
+    }
+
+    fn evaluate_aux_transition<F, E>(
+        &self,
+        main_frame: &EvaluationFrame<F>,
+        aux_frame: &EvaluationFrame<E>,
+        _periodic_values: &[F],
+        aux_rand_elements: &AuxRandElements<E>,
+        result: &mut [E],
+    ) where
+        F: FieldElement<BaseField = Self::BaseField>,
+        E: FieldElement<BaseField = Self::BaseField> + ExtensionOf<F>,
+    {
+        result[0] = self.logup.evaluate_transition(
+            main_frame,
+            aux_frame.current()[0],
+            aux_frame.next()[0],
+            aux_rand_elements.rand_elements(),
+        );
+    }
+
+    fn get_aux_assertions<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        _aux_rand_elements: &AuxRandElements<E>,
+    ) -> Vec<Assertion<E>> {
+        self.logup.assertions(self.last_step() + 1)
+    }
+
+    fn get_periodic_column_values(&self) -> Vec<Vec<Self::BaseField>> {
+        self.tables[0].get_periodic_columns()
+    }
+
+    fn get_lookup_tables(&self) -> &[LookupTable<Self::BaseField>] {
+        &self.tables
+    }
+}
+
+// LookupProver
+// ================================================================================================
+
+struct LookupProver<V> {
+    options: ProofOptions,
+    low_memory: bool,
+    on_disk: bool,
+    _vc: PhantomData<V>,
+}
+
+impl<V> LookupProver<V> {
+    fn new(options: ProofOptions) -> Self {
+        Self {
+            options,
+            low_memory: false,
+            on_disk: false,
+            _vc: PhantomData,
+        }
+    }
+}
+
+impl<V> Prover for LookupProver<V>
+where
+    V: VectorCommitment<Blake3_256<BaseElement>> + Sync,
+{
+    type BaseField = BaseElement;
+    type Air = LookupAir;
+    type Trace = LookupTrace;
+    type HashFn = Blake3_256<BaseElement>;
+    type VC = V;
+    type FriHashFn = Self::HashFn;
+    type FriVC = Self::VC;
+    type RandomCoin = DefaultRandomCoin<Self::HashFn>;
+    type TraceLde<E: FieldElement<BaseField = BaseElement>> =
+        DefaultTraceLde<E, Self::HashFn, Self::VC>;
+    type ConstraintCommitment<E: FieldElement<BaseField = Self::BaseField>> =
+        DefaultConstraintCommitment<E, Self::HashFn, Self::VC>;
+    type ConstraintEvaluator<'a, E: FieldElement<BaseField = BaseElement>> =
+        DefaultConstraintEvaluator<'a, LookupAir, E>;
+
+    fn get_pub_inputs(&self, _trace: &Self::Trace) {}
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    fn new_trace_lde<E>(
+        &self,
+        trace_info: &TraceInfo,
+        main_trace: &ColMatrix<Self::BaseField>,
+        domain: &StarkDomain<Self::BaseField>,
+        partition_option: PartitionOptions,
+        storage: TraceLdeStorage,
+    ) -> (Self::TraceLde<E>, TracePolyTable<E>)
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        if self.low_memory {
+            DefaultTraceLde::new_low_memory(trace_info, main_trace, domain, partition_option)
+        } else if self.on_disk {
+            let options = DiskTraceLdeOptions::default().with_rows_per_page(64);
+            DefaultTraceLde::new_on_disk(trace_info, main_trace, domain, partition_option, options)
+        } else {
+            DefaultTraceLde::with_storage(trace_info, main_trace, domain, partition_option, storage)
+        }
+    }
+
+    default_constraint_commitment!();
+    default_evaluator!();
+
+    fn build_aux_trace<E>(
+        &self,
+        main_trace: &Self::Trace,
+        aux_rand_elements: &AuxRandElements<E>,
+    ) -> ColMatrix<E>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        let main_trace = main_trace.main_segment();
+        let column = lookup_argument().build_column(
+            main_trace.num_rows(),
+            main_trace.num_cols(),
+            aux_rand_elements.rand_elements(),
+            |step, row: &mut [BaseElement]| main_trace.read_row_into(step, row),
+        );
+        ColMatrix::new(vec![column])
+    }
+}
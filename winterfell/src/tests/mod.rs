@@ -0,0 +1,157 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use std::{
+    borrow::Cow,
+    format,
+    marker::PhantomData,
+    string::String,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    vec,
+    vec::Vec,
+};
+
+use air::{
+    proof::{Queries, Table},
+    GkrRandElements, LagrangeKernelRandElements,
+};
+use crypto::{
+    CpuLayerHasher, DiskMerkleTree, KaryMerkleTree, LayerHashedMerkleTree, MerkleLayerHasher,
+    MerkleTree, SaltedMerkleTree, VectorCommitment,
+};
+use prover::{
+    crypto::{
+        hashers::{Blake3_256, Rp64_256},
+        ByteDigest, DefaultRandomCoin, ElementHasher, RandomCoin, SpongeCoin, TranscriptCoin,
+    },
+    fri::{self, FriOptions, FriProof, FriProver, LowDegreeProver},
+    math::{fields::f64::BaseElement, ExtensionOf, FieldElement, ToElements},
+    matrix::ColMatrix,
+    CompositionPoly, DefaultConstraintCommitment, Deserializable, Serializable, SliceReader,
+};
+
+use super::*;
+
+// PROVER COMPONENTS
+// ================================================================================================
+// Most provers in these tests build default components; the macros below implement the
+// respective methods of the Prover trait so that the provers need to override only what differs.
+
+/// Implements [Prover::new_trace_lde()] via [DefaultTraceLde::with_storage()].
+macro_rules! default_trace_lde {
+    () => {
+        fn new_trace_lde<E>(
+            &self,
+            trace_info: &TraceInfo,
+            main_trace: &ColMatrix<Self::BaseField>,
+            domain: &StarkDomain<Self::BaseField>,
+            partition_option: PartitionOptions,
+            storage: TraceLdeStorage,
+        ) -> (Self::TraceLde<E>, TracePolyTable<E>)
+        where
+            E: FieldElement<BaseField = Self::BaseField>,
+        {
+            DefaultTraceLde::with_storage(trace_info, main_trace, domain, partition_option, storage)
+        }
+    };
+}
+
+/// Implements [Prover::build_constraint_commitment()] via [DefaultConstraintCommitment::new()].
+macro_rules! default_constraint_commitment {
+    () => {
+        fn build_constraint_commitment<E: FieldElement<BaseField = Self::BaseField>>(
+            &self,
+            composition_poly_trace: CompositionPolyTrace<E>,
+            num_constraint_composition_columns: usize,
+            domain: &StarkDomain<Self::BaseField>,
+            partition_options: PartitionOptions,
+        ) -> (Self::ConstraintCommitment<E>, CompositionPoly<E>) {
+            DefaultConstraintCommitment::new(
+                composition_poly_trace,
+                num_constraint_composition_columns,
+                domain,
+                partition_options,
+            )
+        }
+    };
+}
+
+/// Implements [Prover::new_evaluator()] via [DefaultConstraintEvaluator::new()].
+macro_rules! default_evaluator {
+    () => {
+        fn new_evaluator<'a, E>(
+            &self,
+            air: &'a Self::Air,
+            aux_rand_elements: Option<AuxRandElements<E>>,
+            composition_coefficients: ConstraintCompositionCoefficients<E>,
+        ) -> Self::ConstraintEvaluator<'a, E>
+        where
+            E: FieldElement<BaseField = Self::BaseField>,
+        {
+            DefaultConstraintEvaluator::new(air, aux_rand_elements, composition_coefficients)
+        }
+    };
+}
+
+mod assertions;
+mod coins;
+mod commitments;
+mod composed;
+mod divisors;
+mod frames;
+mod lagrange;
+mod lookup;
+mod periodic;
+mod program;
+mod trace;
+
+use coins::beacon_coin;
+use divisors::{SparseSquareAir, SparseSquareProver};
+use lagrange::{
+    LagrangeComplexProver, LagrangeComplexTrace, LagrangeKernelComplexAir, AUX_TRACE_WIDTH,
+};
+use periodic::{RoundConstantAir, RoundConstantProver};
+use program::{IndividualOpening, ProgramAir, ProgramProver};
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Options accepted by the verifier in these tests; test proofs are generated with minimal
+/// proof parameters.
+const ACCEPTABLE_OPTIONS: AcceptableOptions = AcceptableOptions::MinConjecturedSecurity(0);
+
+/// Public inputs of the computation proven by the prover `P`.
+type PubInputs<P> = <<P as Prover>::Air as Air>::PublicInputs;
+
+/// Generates a proof for the specified trace, and returns the proof together with the public
+/// inputs of the computation.
+fn prove<P: Prover>(prover: &P, trace: P::Trace) -> (Proof, PubInputs<P>) {
+    let pub_inputs = prover.get_pub_inputs(&trace);
+    let proof = prover.prove(trace).unwrap();
+    (proof, pub_inputs)
+}
+
+/// Verifies the proof using the AIR, the hash function, the public coin, and the vector
+/// commitment scheme of the prover `P`.
+///
+/// The prover is expected to use the same hash function and vector commitment scheme for FRI
+/// layers as for the trace and constraint commitments.
+fn verify_proof<P: Prover>(proof: Proof, pub_inputs: PubInputs<P>) -> Result<(), VerifierError> {
+    verify::<P::Air, P::HashFn, P::RandomCoin, P::VC>(proof, pub_inputs, &ACCEPTABLE_OPTIONS)
+}
+
+/// Generates a proof for the specified trace, checks that the proof verifies, and returns the
+/// proof together with the public inputs of the computation.
+fn prove_and_verify<P: Prover>(prover: &P, trace: P::Trace) -> (Proof, PubInputs<P>)
+where
+    PubInputs<P>: Clone,
+{
+    let (proof, pub_inputs) = prove(prover, trace);
+    verify_proof::<P>(proof.clone(), pub_inputs.clone()).unwrap();
+    (proof, pub_inputs)
+}
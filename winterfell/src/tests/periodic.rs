@@ -0,0 +1,573 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::*;
+
+#[test]
+fn test_non_power_of_two_periodic_columns() {
+    let options = ProofOptions::new(2, 8, 0, FieldExtension::None, 2, 1);
+    let prover = RoundConstantProver::new(options);
+    let (_, result) = prove_and_verify(&prover, prover.build_trace(64));
+
+    // the 10 round constants are padded to a cycle of 16 steps
+    let expected: u64 = (0..63).filter(|i| i % 16 < 10).map(|i| (i % 16 + 1) * i).sum();
+    assert_eq!(BaseElement::new(expected), result);
+}
+
+#[test]
+fn test_batch_air() {
+    const NUM_INSTANCES: usize = 4;
+    let options = ProofOptions::new(2, 8, 0, FieldExtension::None, 2, 1);
+
+    let air = BatchAir::<RoundConstantAir>::new(
+        TraceInfo::new(2 * NUM_INSTANCES, 64),
+        BatchPublicInputs::new(vec![BaseElement::ONE; NUM_INSTANCES]),
+        options.clone(),
+    );
+    assert_eq!(2, air.instance_width());
+    assert_eq!(2 * NUM_INSTANCES, air.context().num_main_transition_constraints());
+    assert_eq!(3 * NUM_INSTANCES, air.get_assertions().len());
+    assert_eq!(NUM_INSTANCES, air.get_periodic_column_values().len());
+
+    let prover = BatchRoundConstantProver::new(options.clone());
+    let (proof, pub_inputs) = prove_and_verify(&prover, prover.build_trace(NUM_INSTANCES, 64));
+    let batch_proof_size = proof.to_bytes().len();
+
+    // the proof does not verify if the result of any instance is different
+    let mut results = pub_inputs.inputs().to_vec();
+    results[NUM_INSTANCES - 1] += BaseElement::ONE;
+    let result = verify_proof::<BatchRoundConstantProver>(proof, BatchPublicInputs::new(results));
+    assert!(result.is_err());
+
+    // the batch proof is smaller than the individual proofs of all instances
+    let prover = RoundConstantProver::new(options);
+    let proof = prover.prove(prover.build_trace(64)).unwrap();
+    assert!(batch_proof_size < NUM_INSTANCES * proof.to_bytes().len());
+}
+
+#[test]
+fn test_wide_trace() {
+    // a batch of 160 instances results in a trace with 320 columns
+    let options = ProofOptions::new(2, 8, 0, FieldExtension::None, 2, 1);
+    let prover = BatchRoundConstantProver::new(options);
+    let trace = prover.build_trace(160, 64);
+    assert_eq!(320, trace.width());
+    let (proof, pub_inputs) = prove(&prover, trace);
+    assert_eq!(320, proof.context.trace_info().main_trace_width());
+
+    let proof = Proof::from_bytes(&proof.to_bytes()).unwrap();
+    verify_proof::<BatchRoundConstantProver>(proof, pub_inputs).unwrap();
+}
+
+#[test]
+fn test_symbolic_constraints() {
+    // degrees inferred from symbolic constraints are the same as the declared degrees
+    let options = ProofOptions::new(2, 8, 0, FieldExtension::None, 2, 1);
+    let trace_info = TraceInfo::new(2, 256);
+    let air = SymbolicRoundConstantAir::new(trace_info.clone(), BaseElement::ONE, options.clone());
+    assert!(
+        *air.context()
+            == *RoundConstantAir::new(trace_info, BaseElement::ONE, options.clone()).context()
+    );
+
+    // constraints compiled from their symbolic representation evaluate to the same values as
+    // constraints evaluated via evaluate_transition(), and thus, the proofs are the same
+    let prover = RoundConstantProver::new(options.clone());
+    let trace = prover.build_trace(256);
+    let expected = prover.prove(trace.clone()).unwrap();
+
+    let prover = RoundConstantProver::<SymbolicRoundConstantAir>::new_symbolic(options);
+    let (proof, _) = prove_and_verify(&prover, trace);
+    assert_eq!(expected, proof);
+}
+
+#[test]
+fn test_constraint_evaluation_backend() {
+    /// Evaluates constraints on the CPU over blocks of the specified size, and counts the
+    /// number of evaluated blocks.
+    #[derive(Clone)]
+    struct BlockBackend {
+        block_size: usize,
+        num_blocks: &'static AtomicUsize,
+    }
+
+    impl ConstraintEvaluationBackend<BaseElement> for BlockBackend {
+        type Program = <CpuEvaluationBackend as ConstraintEvaluationBackend<BaseElement>>::Program;
+
+        fn block_size(&self) -> usize {
+            self.block_size
+        }
+
+        fn compile(
+            &self,
+            constraints: &SymbolicConstraints<BaseElement>,
+            num_frame_rows: usize,
+            num_columns: usize,
+            num_periodic_columns: usize,
+        ) -> Self::Program {
+            assert_eq!((2, 2, 1), (num_frame_rows, num_columns, num_periodic_columns));
+            CpuEvaluationBackend.compile(constraints, 2, 2, 1)
+        }
+
+        fn num_constraints(&self, program: &Self::Program) -> usize {
+            CpuEvaluationBackend.num_constraints(program)
+        }
+
+        fn evaluate_block(
+            &self,
+            program: &Self::Program,
+            block: &FrameBlock<BaseElement>,
+            evaluations: &mut [BaseElement],
+        ) {
+            assert!(block.num_rows() <= self.block_size);
+            self.num_blocks.fetch_add(1, Ordering::Relaxed);
+            CpuEvaluationBackend.evaluate_block(program, block, evaluations);
+        }
+    }
+
+    static NUM_BLOCKS: AtomicUsize = AtomicUsize::new(0);
+    static NUM_SYMBOLIC_BLOCKS: AtomicUsize = AtomicUsize::new(0);
+
+    let options = ProofOptions::new(2, 8, 0, FieldExtension::None, 2, 1);
+    let prover = RoundConstantProver::new(options.clone());
+    let trace = prover.build_trace(256);
+    let expected = prover.prove(trace.clone()).unwrap();
+
+    // the backend is not used when the AIR does not describe its constraints symbolically
+    let backend = BlockBackend { block_size: 100, num_blocks: &NUM_BLOCKS };
+    let prover = RoundConstantProver::new(options.clone()).with_backend(backend);
+    assert_eq!(expected, prover.prove(trace.clone()).unwrap());
+    assert_eq!(0, NUM_BLOCKS.load(Ordering::Relaxed));
+
+    // symbolic constraints are evaluated by the backend over blocks which are not aligned with
+    // batches of the CPU backend, and the proof is the same
+    let backend = BlockBackend {
+        block_size: 100,
+        num_blocks: &NUM_SYMBOLIC_BLOCKS,
+    };
+    let prover = RoundConstantProver::<SymbolicRoundConstantAir>::new_symbolic(options)
+        .with_backend(backend);
+    assert_eq!(expected, prover.prove(trace).unwrap());
+    assert!(NUM_SYMBOLIC_BLOCKS.load(Ordering::Relaxed) > 0);
+}
+
+#[test]
+fn test_lde_domain_too_large() {
+    let options = ProofOptions::new(2, 8, 0, FieldExtension::None, 2, 1);
+    let prover = RoundConstantProver::new(options);
+    let (proof, result) = prove(&prover, prover.build_trace(64));
+
+    // the trace length is stored as a power of two in byte 5 of the proof; a trace of 2^33 steps
+    // with blowup factor 8 requires an LDE domain of 2^36, which does not fit into the f64 field
+    let mut bytes = proof.to_bytes();
+    assert_eq!(6, bytes[5]);
+    bytes[5] = 33;
+    let proof = Proof::from_bytes(&bytes).unwrap();
+    assert_eq!(1 << 33, proof.trace_info().length());
+
+    let result = verify_proof::<RoundConstantProver>(proof, result);
+    assert_eq!(Err(VerifierError::LdeDomainTooLarge(36, 32)), result);
+}
+
+#[test]
+fn test_memory_budget() {
+    type Hasher = Blake3_256<BaseElement>;
+
+    let options = ProofOptions::new(2, 8, 0, FieldExtension::None, 2, 1);
+    let air = RoundConstantAir::new(TraceInfo::new(2, 64), BaseElement::ONE, options.clone());
+    let estimate = MemoryEstimate::new::<_, BaseElement, Hasher>(&air);
+    // the trace LDE consists of 512 rows of 2 elements of 8 bytes each
+    assert_eq!(512 * 2 * 8, estimate.trace_lde());
+    assert_eq!(2 * 512 * 32, estimate.trace_commitment());
+
+    assert_eq!(estimate.total(), estimate.peak(&TraceLdeStorage::Memory));
+    let recompute_peak = estimate.peak(&TraceLdeStorage::Recompute);
+    let disk_peak = estimate.peak(&TraceLdeStorage::Disk(Default::default()));
+    assert!(recompute_peak < estimate.total());
+    assert!(disk_peak <= recompute_peak);
+
+    let prover = RoundConstantProver::new(options.clone());
+    let (expected_proof, _) = prove_and_verify(&prover, prover.build_trace(64));
+
+    // the trace LDE is kept in memory when everything fits into the budget, and is recomputed
+    // when it does not; the proof does not depend on where the trace LDE is kept
+    for (budget, expected_storage) in [
+        (estimate.total(), TraceLdeStorage::Memory),
+        (estimate.total() - 1, TraceLdeStorage::Recompute),
+        (recompute_peak, TraceLdeStorage::Recompute),
+    ] {
+        let prover = RoundConstantProver::new(options.clone()).with_memory_budget(budget);
+        let proof = prover.prove(prover.build_trace(64)).unwrap();
+        assert_eq!(Some(expected_storage), prover.trace_lde_storage());
+        assert_eq!(expected_proof, proof);
+    }
+
+    // proof generation fails before any work is done when the budget is too small for every
+    // storage option
+    let budget = disk_peak - 1;
+    let prover = RoundConstantProver::new(options).with_memory_budget(budget);
+    let error = prover.prove(prover.build_trace(64)).unwrap_err();
+    assert_eq!(ProverError::MemoryBudgetExceeded(disk_peak, budget), error);
+    assert_eq!(None, prover.trace_lde_storage());
+}
+
+// RoundConstantAir
+// ================================================================================================
+
+/// Returns round constants of a 10-round schedule.
+pub(super) fn round_constants() -> Vec<BaseElement> {
+    (1..11).map(BaseElement::new).collect()
+}
+
+/// An AIR for a computation which accumulates round constants multiplied by step numbers; the
+/// step number is kept in the second column of the trace.
+pub(super) struct RoundConstantAir {
+    context: AirContext<BaseElement>,
+    result: BaseElement,
+}
+
+impl Air for RoundConstantAir {
+    type BaseField = BaseElement;
+    type PublicInputs = BaseElement;
+    type GkrProof = ();
+    type GkrVerifier = ();
+
+    fn new(trace_info: TraceInfo, pub_inputs: Self::PublicInputs, options: ProofOptions) -> Self {
+        let degrees = vec![
+            TransitionConstraintDegree::with_padded_cycles(1, vec![10]),
+            TransitionConstraintDegree::new(1),
+        ];
+        Self {
+            context: AirContext::new(trace_info, degrees, 3, options),
+            result: pub_inputs,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        result[0] = next[0] - current[0] - periodic_values[0] * current[1];
+        result[1] = next[1] - current[1] - E::ONE;
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        vec![
+            Assertion::single(0, 0, BaseElement::ZERO),
+            Assertion::single(1, 0, BaseElement::ZERO),
+            Assertion::single(0, self.last_step(), self.result),
+        ]
+    }
+
+    fn get_periodic_column_values(&self) -> Vec<Vec<Self::BaseField>> {
+        vec![pad_periodic_column(round_constants())]
+    }
+}
+
+// SymbolicRoundConstantAir
+// ================================================================================================
+
+/// The same AIR as [RoundConstantAir], but with transition constraints described symbolically;
+/// constraint degrees are inferred from the constraints, and transition constraints are evaluated
+/// via [SymbolicConstraints::evaluate()].
+pub(super) struct SymbolicRoundConstantAir {
+    context: AirContext<BaseElement>,
+    constraints: SymbolicConstraints<BaseElement>,
+    result: BaseElement,
+}
+
+impl Air for SymbolicRoundConstantAir {
+    type BaseField = BaseElement;
+    type PublicInputs = BaseElement;
+    type GkrProof = ();
+    type GkrVerifier = ();
+
+    fn new(trace_info: TraceInfo, pub_inputs: Self::PublicInputs, options: ProofOptions) -> Self {
+        let (acc, step) = (ConstraintExpr::current(0), ConstraintExpr::current(1));
+        let round_constant = ConstraintExpr::periodic(0);
+
+        let mut constraints = SymbolicConstraints::new();
+        for expr in [
+            // acc' = acc + round_constant * step
+            ConstraintExpr::next(0) - (acc + round_constant * &step),
+            // step' = step + 1
+            ConstraintExpr::next(1) - (step + BaseElement::ONE),
+        ] {
+            let node = constraints.insert(&expr);
+            constraints.enforce(node);
+        }
+
+        let periodic_column_lengths = [pad_periodic_column(round_constants()).len()];
+        let context = AirContext::from_symbolic_constraints(
+            trace_info,
+            &constraints,
+            &periodic_column_lengths,
+            3,
+            options,
+        );
+        Self { context, constraints, result: pub_inputs }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        self.constraints.evaluate(frame, periodic_values, result);
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        vec![
+            Assertion::single(0, 0, BaseElement::ZERO),
+            Assertion::single(1, 0, BaseElement::ZERO),
+            Assertion::single(0, self.last_step(), self.result),
+        ]
+    }
+
+    fn get_periodic_column_values(&self) -> Vec<Vec<Self::BaseField>> {
+        vec![pad_periodic_column(round_constants())]
+    }
+
+    fn get_symbolic_constraints(&self) -> Option<&SymbolicConstraints<Self::BaseField>> {
+        Some(&self.constraints)
+    }
+}
+
+// RoundConstantProver
+// ================================================================================================
+
+pub(super) struct RoundConstantProver<A = RoundConstantAir, X = CpuEvaluationBackend> {
+    options: ProofOptions,
+    memory_budget: Option<usize>,
+    beacon: Option<BaseElement>,
+    /// Storage of the trace LDE selected by the prover for the last proof.
+    trace_lde_storage: Mutex<Option<TraceLdeStorage>>,
+    backend: X,
+    _air: PhantomData<A>,
+}
+
+impl RoundConstantProver {
+    pub(super) fn new(options: ProofOptions) -> Self {
+        Self {
+            options,
+            memory_budget: None,
+            beacon: None,
+            trace_lde_storage: Mutex::new(None),
+            backend: CpuEvaluationBackend,
+            _air: PhantomData,
+        }
+    }
+
+    pub(super) fn with_memory_budget(mut self, memory_budget: usize) -> Self {
+        self.memory_budget = Some(memory_budget);
+        self
+    }
+
+    /// Mixes the specified value of an external randomness beacon into the seed of the public
+    /// coin.
+    pub(super) fn with_beacon(mut self, beacon: BaseElement) -> Self {
+        self.beacon = Some(beacon);
+        self
+    }
+
+    pub(super) fn trace_lde_storage(&self) -> Option<TraceLdeStorage> {
+        self.trace_lde_storage.lock().unwrap().clone()
+    }
+}
+
+impl RoundConstantProver<SymbolicRoundConstantAir> {
+    pub(super) fn new_symbolic(options: ProofOptions) -> Self {
+        Self {
+            options,
+            memory_budget: None,
+            beacon: None,
+            trace_lde_storage: Mutex::new(None),
+            backend: CpuEvaluationBackend,
+            _air: PhantomData,
+        }
+    }
+}
+
+impl<A> RoundConstantProver<A> {
+    /// Evaluates symbolic constraints of the AIR with the specified backend.
+    pub(super) fn with_backend<X>(self, backend: X) -> RoundConstantProver<A, X> {
+        RoundConstantProver {
+            options: self.options,
+            memory_budget: self.memory_budget,
+            beacon: self.beacon,
+            trace_lde_storage: self.trace_lde_storage,
+            backend,
+            _air: PhantomData,
+        }
+    }
+}
+
+impl<A, X> TraceBuilder for RoundConstantProver<A, X>
+where
+    A: Air<BaseField = BaseElement, PublicInputs = BaseElement, GkrProof = (), GkrVerifier = ()>
+        + 'static,
+    X: ConstraintEvaluationBackend<BaseElement> + Clone,
+{
+    /// Length of the execution trace.
+    type Inputs = usize;
+
+    fn build_trace(&self, trace_len: usize) -> TraceTable<BaseElement> {
+        // round constants are zero at the steps beyond the 10 rounds of each 16-step cycle
+        let constants = pad_periodic_column(round_constants());
+
+        let mut trace = TraceTable::new(2, trace_len);
+        trace.fill(
+            |state| {
+                state[0] = BaseElement::ZERO;
+                state[1] = BaseElement::ZERO;
+            },
+            |step, state| {
+                state[0] += constants[step % constants.len()] * state[1];
+                state[1] += BaseElement::ONE;
+            },
+        );
+        trace
+    }
+}
+
+impl<A, X> Prover for RoundConstantProver<A, X>
+where
+    A: Air<BaseField = BaseElement, PublicInputs = BaseElement, GkrProof = (), GkrVerifier = ()>
+        + 'static,
+    X: ConstraintEvaluationBackend<BaseElement> + Clone,
+{
+    type BaseField = BaseElement;
+    type Air = A;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = Blake3_256<BaseElement>;
+    type VC = MerkleTree<Self::HashFn>;
+    type FriHashFn = Self::HashFn;
+    type FriVC = Self::VC;
+    type RandomCoin = DefaultRandomCoin<Self::HashFn>;
+    type TraceLde<E: FieldElement<BaseField = BaseElement>> =
+        DefaultTraceLde<E, Self::HashFn, Self::VC>;
+    type ConstraintCommitment<E: FieldElement<BaseField = Self::BaseField>> =
+        DefaultConstraintCommitment<E, Self::HashFn, Self::VC>;
+    type ConstraintEvaluator<'a, E: FieldElement<BaseField = BaseElement>> =
+        DefaultConstraintEvaluator<'a, A, E, X>;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> BaseElement {
+        trace.get(0, trace.length() - 1)
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    fn memory_budget(&self) -> Option<usize> {
+        self.memory_budget
+    }
+
+    fn new_public_coin(&self, seed: &[BaseElement]) -> Self::RandomCoin {
+        beacon_coin(seed, self.beacon)
+    }
+
+    fn new_trace_lde<E>(
+        &self,
+        trace_info: &TraceInfo,
+        main_trace: &ColMatrix<Self::BaseField>,
+        domain: &StarkDomain<Self::BaseField>,
+        partition_option: PartitionOptions,
+        storage: TraceLdeStorage,
+    ) -> (Self::TraceLde<E>, TracePolyTable<E>)
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        *self.trace_lde_storage.lock().unwrap() = Some(storage.clone());
+        DefaultTraceLde::with_storage(trace_info, main_trace, domain, partition_option, storage)
+    }
+
+    default_constraint_commitment!();
+
+    fn new_evaluator<'a, E>(
+        &self,
+        air: &'a Self::Air,
+        aux_rand_elements: Option<AuxRandElements<E>>,
+        composition_coefficients: ConstraintCompositionCoefficients<E>,
+    ) -> Self::ConstraintEvaluator<'a, E>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        DefaultConstraintEvaluator::new(air, aux_rand_elements, composition_coefficients)
+            .with_backend(self.backend.clone())
+    }
+}
+
+// BatchRoundConstantProver
+// ================================================================================================
+
+/// A prover for a batch of [RoundConstantAir] instances executed side by side.
+struct BatchRoundConstantProver {
+    options: ProofOptions,
+}
+
+impl BatchRoundConstantProver {
+    pub(super) fn new(options: ProofOptions) -> Self {
+        Self { options }
+    }
+
+    pub(super) fn build_trace(
+        &self,
+        num_instances: usize,
+        trace_len: usize,
+    ) -> TraceTable<BaseElement> {
+        let instance_trace = RoundConstantProver::new(self.options.clone()).build_trace(trace_len);
+        let mut columns = Vec::with_capacity(2 * num_instances);
+        for _ in 0..num_instances {
+            columns.push(instance_trace.get_column(0).to_vec());
+            columns.push(instance_trace.get_column(1).to_vec());
+        }
+        TraceTable::init(columns)
+    }
+}
+
+impl Prover for BatchRoundConstantProver {
+    type BaseField = BaseElement;
+    type Air = BatchAir<RoundConstantAir>;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = Blake3_256<BaseElement>;
+    type VC = MerkleTree<Self::HashFn>;
+    type FriHashFn = Self::HashFn;
+    type FriVC = Self::VC;
+    type RandomCoin = DefaultRandomCoin<Self::HashFn>;
+    type TraceLde<E: FieldElement<BaseField = BaseElement>> =
+        DefaultTraceLde<E, Self::HashFn, Self::VC>;
+    type ConstraintCommitment<E: FieldElement<BaseField = Self::BaseField>> =
+        DefaultConstraintCommitment<E, Self::HashFn, Self::VC>;
+    type ConstraintEvaluator<'a, E: FieldElement<BaseField = BaseElement>> =
+        DefaultConstraintEvaluator<'a, BatchAir<RoundConstantAir>, E>;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> BatchPublicInputs<BaseElement> {
+        let last_step = trace.length() - 1;
+        let results = (0..trace.width() / 2).map(|i| trace.get(2 * i, last_step)).collect();
+        BatchPublicInputs::new(results)
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    default_trace_lde!();
+    default_constraint_commitment!();
+    default_evaluator!();
+}
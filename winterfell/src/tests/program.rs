@@ -0,0 +1,481 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::*;
+
+#[test]
+fn test_program_air() {
+    type Hasher = Blake3_256<BaseElement>;
+    type RandCoin = DefaultRandomCoin<Hasher>;
+    type VC = MerkleTree<Hasher>;
+
+    let program = Program::new(vec![[3_u32, 1, 4, 1, 5, 9, 2, 6].map(BaseElement::from).to_vec()]);
+    let other_program =
+        Program::new(vec![[2_u32, 7, 1, 8, 2, 8, 1, 8].map(BaseElement::from).to_vec()]);
+    let program_hash = program.hash::<Hasher>();
+
+    let prover = ProgramProver::<VC>::new(program.clone());
+    let (proof, pub_inputs) = prove(&prover, prover.build_trace());
+
+    // the proof verifies against the hash of the executed program
+    verify_program::<ProgramAir, Hasher, RandCoin, VC>(
+        proof.clone(),
+        pub_inputs.clone(),
+        &program_hash,
+        &ACCEPTABLE_OPTIONS,
+    )
+    .unwrap();
+
+    // but the verifier rejects it if it expects a different program
+    let result = verify_program::<ProgramAir, Hasher, RandCoin, VC>(
+        proof.clone(),
+        pub_inputs.clone(),
+        &other_program.hash::<Hasher>(),
+        &ACCEPTABLE_OPTIONS,
+    );
+    assert_eq!(Err(VerifierError::InconsistentProgramHash), result);
+
+    // and the proof does not attest to the execution of a different program
+    let other_inputs = ProgramInputs {
+        program: other_program.clone(),
+        ..pub_inputs
+    };
+    let result = verify_program::<ProgramAir, Hasher, RandCoin, VC>(
+        proof,
+        other_inputs,
+        &other_program.hash::<Hasher>(),
+        &ACCEPTABLE_OPTIONS,
+    );
+    assert!(result.is_err());
+
+    // AIRs which do not define a program cannot be pinned to a program hash
+    let proof = LagrangeComplexProver::new(AUX_TRACE_WIDTH)
+        .prove(LagrangeComplexTrace::new(2_usize.pow(10), AUX_TRACE_WIDTH))
+        .unwrap();
+    let result = verify_program::<LagrangeKernelComplexAir, Hasher, RandCoin, VC>(
+        proof,
+        (),
+        &program_hash,
+        &ACCEPTABLE_OPTIONS,
+    );
+    assert_eq!(Err(VerifierError::MissingProgram), result);
+}
+
+// ProgramAir
+// ================================================================================================
+
+/// A minimal virtual machine which adds the values of the program to an accumulator.
+#[derive(Clone, Debug)]
+pub(super) struct ProgramInputs {
+    program: Program<BaseElement>,
+    result: BaseElement,
+}
+
+impl ToElements<BaseElement> for ProgramInputs {
+    fn to_elements(&self) -> Vec<BaseElement> {
+        let mut result = self.program.to_elements();
+        result.push(self.result);
+        result
+    }
+}
+
+pub(super) struct ProgramAir {
+    context: AirContext<BaseElement>,
+    program: Program<BaseElement>,
+    result: BaseElement,
+}
+
+impl Air for ProgramAir {
+    type BaseField = BaseElement;
+    type PublicInputs = ProgramInputs;
+    type GkrProof = ();
+    type GkrVerifier = ();
+
+    fn new(trace_info: TraceInfo, pub_inputs: Self::PublicInputs, options: ProofOptions) -> Self {
+        // the program value is added to (rather than multiplied by) the accumulator, and thus,
+        // the periodic column does not increase the degree of the constraint
+        let degrees = vec![TransitionConstraintDegree::new(1)];
+        Self {
+            context: AirContext::new(trace_info, degrees, 2, options),
+            program: pub_inputs.program,
+            result: pub_inputs.result,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        // adds the current program value to the accumulator
+        result[0] = frame.next()[0] - frame.current()[0] - periodic_values[0];
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        let last_step = self.trace_length() - 1;
+        vec![
+            Assertion::single(0, 0, BaseElement::ZERO),
+            Assertion::single(0, last_step, self.result),
+        ]
+    }
+
+    fn get_periodic_column_values(&self) -> Vec<Vec<Self::BaseField>> {
+        self.program.get_periodic_columns()
+    }
+
+    fn get_program(&self) -> Option<&Program<Self::BaseField>> {
+        Some(&self.program)
+    }
+}
+
+// IndividualOpening
+// ================================================================================================
+
+/// Opens each query position using an individual opening proof of the vector commitment.
+pub(super) struct IndividualOpening;
+
+impl<H: ElementHasher, V: VectorCommitment<H>> QueryOpening<H, V> for IndividualOpening {
+    fn open<E: FieldElement<BaseField = H::BaseField>>(
+        vector_commitment: &V,
+        positions: &[usize],
+        rows: Vec<Vec<E>>,
+    ) -> Queries {
+        let mut opening_proof = Vec::new();
+        for &position in positions {
+            let (_, proof) = vector_commitment.open(position).unwrap();
+            proof.write_into(&mut opening_proof);
+        }
+        Queries::with_opening_proof_bytes(opening_proof, rows)
+    }
+
+    fn verify<E: FieldElement<BaseField = H::BaseField>>(
+        commitment: H::Digest,
+        positions: &[usize],
+        _domain_size: usize,
+        values_per_query: usize,
+        queries: Queries,
+        hash_row: impl Fn(&[E]) -> H::Digest,
+    ) -> Result<Table<E>, QueryOpeningError> {
+        let (rows, opening_proof) = queries
+            .into_parts(positions.len(), values_per_query)
+            .map_err(QueryOpeningError::DeserializationError)?;
+
+        let mut reader = SliceReader::new(&opening_proof);
+        for (&position, row) in positions.iter().zip(rows.rows()) {
+            let proof = V::Proof::read_from(&mut reader)
+                .map_err(QueryOpeningError::DeserializationError)?;
+            V::verify(commitment, position, hash_row(row), &proof)
+                .map_err(|_| QueryOpeningError::CommitmentMismatch)?;
+        }
+
+        Ok(rows)
+    }
+}
+
+// ProgramProver
+// ================================================================================================
+
+pub(super) struct ProgramProver<
+    V,
+    R = DefaultRandomCoin<Blake3_256<BaseElement>>,
+    F = Blake3_256<BaseElement>,
+    FV = V,
+    O = BatchOpening,
+> {
+    program: Program<BaseElement>,
+    pub(super) options: ProofOptions,
+    num_low_degree_proofs: AtomicUsize,
+    phases: Mutex<Vec<(ProvingPhase, bool)>>,
+    blocking_phases: Mutex<Vec<ProvingPhase>>,
+    context: Option<ProverContext<BaseElement>>,
+    _vc: PhantomData<V>,
+    _random_coin: PhantomData<R>,
+    _fri_hasher: PhantomData<F>,
+    _fri_vc: PhantomData<FV>,
+    _query_opening: PhantomData<O>,
+}
+
+impl<V, R, F, FV, O> ProgramProver<V, R, F, FV, O> {
+    pub(super) fn new(program: Program<BaseElement>) -> Self {
+        Self {
+            program,
+            options: ProofOptions::new(1, 2, 0, FieldExtension::None, 2, 1),
+            num_low_degree_proofs: AtomicUsize::new(0),
+            phases: Mutex::new(Vec::new()),
+            blocking_phases: Mutex::new(Vec::new()),
+            context: None,
+            _vc: PhantomData,
+            _random_coin: PhantomData,
+            _fri_hasher: PhantomData,
+            _fri_vc: PhantomData,
+            _query_opening: PhantomData,
+        }
+    }
+
+    /// Builds an execution trace which runs the program 4 times.
+    pub(super) fn build_trace(&self) -> TraceTable<BaseElement> {
+        let values = &self.program.columns()[0];
+        let mut trace = TraceTable::new(1, values.len() * 4);
+        trace.fill(
+            |state| state[0] = BaseElement::ZERO,
+            |step, state| state[0] += values[step % values.len()],
+        );
+        trace
+    }
+}
+
+impl<V, R, F, FV, O> Prover for ProgramProver<V, R, F, FV, O>
+where
+    V: VectorCommitment<Blake3_256<BaseElement>> + Sync,
+    R: RandomCoin<
+        BaseField = BaseElement,
+        Hasher: ElementHasher<Digest: From<ByteDigest<32>> + From<F::Digest>>,
+    >,
+    F: ElementHasher<BaseField = BaseElement> + Sync,
+    FV: VectorCommitment<F> + Sync,
+    O: QueryOpening<Blake3_256<BaseElement>, V> + Sync,
+{
+    type BaseField = BaseElement;
+    type Air = ProgramAir;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = Blake3_256<BaseElement>;
+    type VC = V;
+    type FriHashFn = F;
+    type FriVC = FV;
+    type RandomCoin = R;
+    type TraceLde<E: FieldElement<BaseField = BaseElement>> =
+        DefaultTraceLde<E, Self::HashFn, Self::VC, O>;
+    type ConstraintCommitment<E: FieldElement<BaseField = Self::BaseField>> =
+        DefaultConstraintCommitment<E, Blake3_256<BaseElement>, Self::VC, O>;
+    type ConstraintEvaluator<'a, E: FieldElement<BaseField = BaseElement>> =
+        DefaultConstraintEvaluator<'a, ProgramAir, E>;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> ProgramInputs {
+        let last_step = trace.length() - 1;
+        ProgramInputs {
+            program: self.program.clone(),
+            result: trace.get(0, last_step),
+        }
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    fn new_trace_lde<E>(
+        &self,
+        trace_info: &TraceInfo,
+        main_trace: &ColMatrix<Self::BaseField>,
+        domain: &StarkDomain<Self::BaseField>,
+        partition_option: PartitionOptions,
+        storage: TraceLdeStorage,
+    ) -> (Self::TraceLde<E>, TracePolyTable<E>)
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        match self.context.as_ref().filter(|_| storage == TraceLdeStorage::Memory) {
+            Some(context) => DefaultTraceLde::new_with_buffer_pool(
+                trace_info,
+                main_trace,
+                domain,
+                partition_option,
+                context.buffer_pool(),
+            ),
+            None => DefaultTraceLde::with_storage(
+                trace_info,
+                main_trace,
+                domain,
+                partition_option,
+                storage,
+            ),
+        }
+    }
+
+    default_constraint_commitment!();
+
+    fn new_evaluator<'a, E>(
+        &self,
+        air: &'a Self::Air,
+        aux_rand_elements: Option<AuxRandElements<E>>,
+        composition_coefficients: ConstraintCompositionCoefficients<E>,
+    ) -> Self::ConstraintEvaluator<'a, E>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        match self.context.as_ref().filter(|context| context.is_compatible(air)) {
+            Some(context) => DefaultConstraintEvaluator::new_with_context(
+                air,
+                context,
+                aux_rand_elements,
+                composition_coefficients,
+            ),
+            None => {
+                DefaultConstraintEvaluator::new(air, aux_rand_elements, composition_coefficients)
+            },
+        }
+    }
+
+    fn prover_context(&self) -> Option<&ProverContext<Self::BaseField>> {
+        self.context.as_ref()
+    }
+
+    fn new_low_degree_prover<E, C>(&self, options: FriOptions) -> impl LowDegreeProver<E, C>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+        C: fri::ProverChannel<E, Hasher = Self::FriHashFn>,
+    {
+        let mut inner = FriProver::<E, C, Self::FriHashFn, Self::FriVC>::new(options);
+        if let Some(context) = self.context.as_ref() {
+            inner = inner.with_buffer_pool(context.buffer_pool().clone());
+        }
+        CountingLowDegreeProver {
+            inner,
+            num_proofs: &self.num_low_degree_proofs,
+        }
+    }
+
+    fn on_phase_start(&self, phase: ProvingPhase) {
+        self.phases.lock().unwrap().push((phase, true));
+    }
+
+    fn on_phase_end(&self, phase: ProvingPhase) {
+        self.phases.lock().unwrap().push((phase, false));
+    }
+
+    fn run_blocking<T, G>(&self, phase: ProvingPhase, f: G) -> T
+    where
+        G: FnOnce() -> T,
+    {
+        self.blocking_phases.lock().unwrap().push(phase);
+        f()
+    }
+}
+
+/// Low-degree prover which delegates to another prover and counts the number of built proofs.
+struct CountingLowDegreeProver<'a, P> {
+    inner: P,
+    num_proofs: &'a AtomicUsize,
+}
+
+impl<E, C, P> LowDegreeProver<E, C> for CountingLowDegreeProver<'_, P>
+where
+    E: FieldElement,
+    C: fri::ProverChannel<E>,
+    P: LowDegreeProver<E, C>,
+{
+    fn build_layers(&mut self, channel: &mut C, evaluations: Vec<E>) {
+        self.inner.build_layers(channel, evaluations)
+    }
+
+    fn build_proof(&mut self, positions: &[usize]) -> FriProof {
+        self.num_proofs.fetch_add(1, Ordering::Relaxed);
+        self.inner.build_proof(positions)
+    }
+}
+
+#[test]
+fn test_custom_low_degree_prover() {
+    type Hasher = Blake3_256<BaseElement>;
+
+    let program = Program::new(vec![[3_u32, 1, 4, 1, 5, 9, 2, 6].map(BaseElement::from).to_vec()]);
+    let prover = ProgramProver::<MerkleTree<Hasher>>::new(program);
+    prove_and_verify(&prover, prover.build_trace());
+
+    // the low-degree prover returned by the prover is used to generate the proof
+    assert_eq!(1, prover.num_low_degree_proofs.load(Ordering::Relaxed));
+}
+
+#[test]
+fn test_proving_phases() {
+    type Hasher = Blake3_256<BaseElement>;
+
+    let program = Program::new(vec![[3_u32, 1, 4, 1, 5, 9, 2, 6].map(BaseElement::from).to_vec()]);
+    let prover = ProgramProver::<MerkleTree<Hasher>>::new(program);
+    let trace = prover.build_trace();
+    prover.prove(trace).unwrap();
+
+    // every phase is reported as started and then ended; the auxiliary trace phase is skipped
+    // since the trace consists of a single segment
+    let expected = [
+        ProvingPhase::MainTraceCommitment,
+        ProvingPhase::ConstraintEvaluation,
+        ProvingPhase::ConstraintCommitment,
+        ProvingPhase::DeepComposition,
+        ProvingPhase::FriLayers,
+        ProvingPhase::QueryPositions,
+        ProvingPhase::ProofConstruction,
+    ]
+    .into_iter()
+    .flat_map(|phase| [(phase, true), (phase, false)])
+    .collect::<Vec<_>>();
+    assert_eq!(expected, *prover.phases.lock().unwrap());
+}
+
+#[test]
+fn test_blocking_phases() {
+    type Hasher = Blake3_256<BaseElement>;
+
+    let program = Program::new(vec![[3_u32, 1, 4, 1, 5, 9, 2, 6].map(BaseElement::from).to_vec()]);
+    let prover = ProgramProver::<MerkleTree<Hasher>>::new(program);
+    prove_and_verify(&prover, prover.build_trace());
+
+    // CPU-heavy work performed inline by the prover is run via the overridden method; the DEEP
+    // composition polynomial is built and evaluated in two separate steps
+    let expected = vec![
+        ProvingPhase::ConstraintEvaluation,
+        ProvingPhase::DeepComposition,
+        ProvingPhase::DeepComposition,
+        ProvingPhase::FriLayers,
+        ProvingPhase::QueryPositions,
+        ProvingPhase::ProofConstruction,
+    ];
+    assert_eq!(expected, *prover.blocking_phases.lock().unwrap());
+}
+
+#[test]
+fn test_prover_context() {
+    type Hasher = Blake3_256<BaseElement>;
+
+    let program = Program::new(vec![[3_u32, 1, 4, 1, 5, 9, 2, 6].map(BaseElement::from).to_vec()]);
+    let prover = ProgramProver::<MerkleTree<Hasher>>::new(program.clone());
+    let trace = prover.build_trace();
+    let pub_inputs = prover.get_pub_inputs(&trace);
+    let expected = prover.prove(trace.clone()).unwrap();
+
+    // proofs generated with a pre-computed context are the same as proofs generated without it
+    let air = ProgramAir::new(trace.info().clone(), pub_inputs.clone(), prover.options.clone());
+    let mut prover = ProgramProver::<MerkleTree<Hasher>>::new(program.clone());
+    prover.context = Some(ProverContext::new(&air));
+    assert_eq!(expected, prover.prove(trace.clone()).unwrap());
+
+    // buffers released by the first proof are reused by the second one, so the pool does not grow
+    let buffer_pool = prover.context.as_ref().unwrap().buffer_pool();
+    let num_buffers = buffer_pool.num_buffers();
+    assert!(num_buffers > 0);
+    let (proof, _) = prove_and_verify(&prover, trace);
+    assert_eq!(num_buffers, buffer_pool.num_buffers());
+    assert_eq!(expected, proof);
+
+    // an incompatible context is ignored
+    let mut longer_trace = TraceTable::new(1, 64);
+    longer_trace.fill(
+        |state| state[0] = BaseElement::ZERO,
+        |step, state| state[0] += program.columns()[0][step % 8],
+    );
+    assert!(!prover.context.as_ref().unwrap().is_compatible(&ProgramAir::new(
+        longer_trace.info().clone(),
+        prover.get_pub_inputs(&longer_trace),
+        prover.options.clone()
+    )));
+    let expected = ProgramProver::<MerkleTree<Hasher>>::new(program)
+        .prove(longer_trace.clone())
+        .unwrap();
+    assert_eq!(expected, prover.prove(longer_trace).unwrap());
+}
@@ -0,0 +1,126 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::*;
+
+#[test]
+fn test_trace_builder() {
+    let options = ProofOptions::new(2, 8, 0, FieldExtension::None, 2, 1);
+    let prover = RoundConstantProver::new(options);
+
+    let proof = prover.build_and_prove(64).unwrap();
+    let result = prover.get_pub_inputs(&prover.build_trace(64));
+    verify_proof::<RoundConstantProver>(proof.clone(), result).unwrap();
+
+    // the proof is the same as the one generated from a separately built trace
+    let expected = prover.prove(prover.build_trace(64)).unwrap();
+    assert_eq!(expected.to_bytes(), proof.to_bytes());
+}
+
+#[test]
+fn test_custom_trace_storage() {
+    let options = ProofOptions::new(2, 8, 0, FieldExtension::None, 2, 1);
+
+    // a trace kept in a row-major buffer is proven without being copied into a trace table
+    let expected = RoundConstantProver::new(options.clone()).build_trace(64);
+    let trace = RowMajorTrace::new(&expected);
+    let main_segment = trace.main_segment();
+    assert!(matches!(main_segment, Cow::Owned(_)));
+    assert!(main_segment.columns().eq(expected.main_segment().columns()));
+
+    let prover = RowMajorProver::new(options);
+    prove_and_verify(&prover, trace);
+}
+
+// RowMajorTrace
+// ================================================================================================
+
+/// An execution trace kept in a single row-major buffer (e.g., a memory dump of a virtual
+/// machine); the columns of the trace are built only when requested by the prover.
+struct RowMajorTrace {
+    rows: Vec<BaseElement>,
+    info: TraceInfo,
+}
+
+impl RowMajorTrace {
+    fn new(trace: &TraceTable<BaseElement>) -> Self {
+        let mut rows = vec![BaseElement::ZERO; trace.width() * trace.length()];
+        for (i, row) in rows.chunks_mut(trace.width()).enumerate() {
+            trace.read_row_into(i, row);
+        }
+        Self { rows, info: trace.info().clone() }
+    }
+
+    fn row(&self, row_idx: usize) -> &[BaseElement] {
+        let width = self.info.main_trace_width();
+        &self.rows[row_idx * width..(row_idx + 1) * width]
+    }
+}
+
+impl Trace for RowMajorTrace {
+    type BaseField = BaseElement;
+
+    fn info(&self) -> &TraceInfo {
+        &self.info
+    }
+
+    fn main_segment(&self) -> Cow<'_, ColMatrix<Self::BaseField>> {
+        let columns = (0..self.info.main_trace_width())
+            .map(|col_idx| self.rows.iter().skip(col_idx).step_by(self.info.main_trace_width()))
+            .map(|column| column.copied().collect())
+            .collect();
+        Cow::Owned(ColMatrix::new(columns))
+    }
+
+    fn read_main_frame(&self, row_idx: usize, frame: &mut EvaluationFrame<Self::BaseField>) {
+        for i in 0..frame.num_rows() {
+            let row_idx = (row_idx + frame.offsets()[i]) % self.info.length();
+            frame.row_mut(i).copy_from_slice(self.row(row_idx));
+        }
+    }
+}
+
+// RowMajorProver
+// ================================================================================================
+
+/// A prover for [RoundConstantAir] which consumes traces kept in row-major buffers.
+struct RowMajorProver {
+    options: ProofOptions,
+}
+
+impl RowMajorProver {
+    fn new(options: ProofOptions) -> Self {
+        Self { options }
+    }
+}
+
+impl Prover for RowMajorProver {
+    type BaseField = BaseElement;
+    type Air = RoundConstantAir;
+    type Trace = RowMajorTrace;
+    type HashFn = Blake3_256<BaseElement>;
+    type VC = MerkleTree<Self::HashFn>;
+    type FriHashFn = Self::HashFn;
+    type FriVC = Self::VC;
+    type RandomCoin = DefaultRandomCoin<Self::HashFn>;
+    type TraceLde<E: FieldElement<BaseField = BaseElement>> =
+        DefaultTraceLde<E, Self::HashFn, Self::VC>;
+    type ConstraintCommitment<E: FieldElement<BaseField = Self::BaseField>> =
+        DefaultConstraintCommitment<E, Self::HashFn, Self::VC>;
+    type ConstraintEvaluator<'a, E: FieldElement<BaseField = BaseElement>> =
+        DefaultConstraintEvaluator<'a, RoundConstantAir, E>;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> BaseElement {
+        trace.row(trace.length() - 1)[0]
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    default_trace_lde!();
+    default_constraint_commitment!();
+    default_evaluator!();
+}